@@ -297,6 +297,7 @@ fn config_file_type_from_str(ext: &str) -> Option<ConfigFileType> {
     match ext.to_lowercase().as_str() {
         "csv" => Some(ConfigFileType::CSV),
         "json" => Some(ConfigFileType::JSON),
+        "avro" => Some(ConfigFileType::AVRO),
         "parquet" => Some(ConfigFileType::PARQUET),
         _ => None,
     }
@@ -251,6 +251,31 @@ pub trait TableProvider: Debug + Sync + Send {
         None
     }
 
+    /// Stores the statistics computed for this table by `ANALYZE TABLE`, if
+    /// supported. They have a [`ColumnStatistics`] for each field of the
+    /// schema of the table.
+    ///
+    /// Tables which support this should return these statistics from
+    /// [`Self::statistics`], and use them for the plans returned by
+    /// [`Self::scan`] when they have no better statistics.
+    ///
+    /// [`ColumnStatistics`]: datafusion_common::ColumnStatistics
+    fn set_statistics(&self, _statistics: Statistics) -> Result<()> {
+        not_impl_err!("ANALYZE TABLE not implemented for this table")
+    }
+
+    /// Returns the version of the data in this table, if it is tracked.
+    ///
+    /// The version must change whenever the data returned by [`Self::scan`]
+    /// changes, for example after [`Self::insert_into`] or when the data is
+    /// modified outside of DataFusion, and should not be shared with other
+    /// tables. It is computed each time a query scanning the table is planned
+    /// with the result cache enabled. Query results are only cached for
+    /// tables which return a version.
+    async fn version(&self, _state: &dyn Session) -> Result<Option<u64>> {
+        Ok(None)
+    }
+
     /// Return an [`ExecutionPlan`] to insert data into this table, if
     /// supported.
     ///
@@ -20,10 +20,11 @@
 use std::any::Any;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt::{self, Display};
+use std::ops::Range;
 use std::str::FromStr;
 
 use crate::error::_config_err;
-use crate::parsers::CompressionTypeVariant;
+use crate::parsers::{CompressionTypeVariant, OnError, SchemaInferStrategy};
 use crate::{DataFusionError, Result};
 
 /// A macro that wraps a configuration struct and automatically derives
@@ -246,6 +247,19 @@ config_namespace! {
         /// Should DataFusion collect statistics after listing files
         pub collect_statistics: bool, default = false
 
+        /// Maximum number of buckets of the histograms computed for each column
+        /// by `ANALYZE TABLE`
+        pub analyze_histogram_buckets: usize, default = 64
+
+        /// Maximum number of most common values computed for each column by
+        /// `ANALYZE TABLE`. They are left out of the histograms of the columns.
+        pub analyze_most_common_values: usize, default = 16
+
+        /// Number of values of each column sampled by `ANALYZE TABLE`. The
+        /// statistics of the columns with more values are estimated from the
+        /// sample and from sketches of their values.
+        pub analyze_sample_size: usize, default = 30_000
+
         /// Number of partitions for query execution. Increasing partitions can increase
         /// concurrency.
         ///
@@ -314,6 +328,20 @@ config_namespace! {
         /// Should DataFusion support recursive CTEs
         pub enable_recursive_ctes: bool, default = true
 
+        /// Should DataFusion compute a common table expression (CTE) referenced
+        /// more than once in a query only once, and share its results between
+        /// the references. The results are spilled to disk if they do not fit
+        /// in memory. The `MATERIALIZED` and `NOT MATERIALIZED` hints of a CTE
+        /// take precedence over this setting
+        pub materialize_ctes: bool, default = false
+
+        /// Maximum total size, in bytes, of the query results kept in the
+        /// result cache of the session. Results of read-only queries over
+        /// tables which track their version, such as in-memory tables, are
+        /// cached and returned again while the tables are unchanged. The
+        /// cache is disabled when set to 0
+        pub result_cache_max_bytes: usize, default = 0
+
         /// Attempt to eliminate sorts by packing & sorting files with non-overlapping
         /// statistics into the same file groups.
         /// Currently experimental
@@ -386,6 +414,13 @@ config_namespace! {
         /// the filters are applied in the same order as written in the query
         pub reorder_filters: bool, default = false
 
+        /// (reading) If true, filter expressions evaluated during the parquet decoding operation
+        /// are reordered and disabled based on the selectivity and evaluation cost measured while
+        /// scanning, instead of the static heuristics of `reorder_filters`. Filters that remove
+        /// few rows stop being evaluated during decoding. As a filter may be disabled mid scan,
+        /// pushed down filters are also evaluated after the scan. Requires `pushdown_filters`
+        pub adaptive_filters: bool, default = false
+
         /// (reading) If true, parquet reader will read columns of `Utf8/Utf8Large` with `Utf8View`,
         /// and `Binary/BinaryLarge` with `BinaryView`.
         pub schema_force_view_types: bool, default = false
@@ -605,6 +640,48 @@ config_namespace! {
         /// HashJoin can work more efficiently than SortMergeJoin but consumes more memory
         pub prefer_hash_join: bool, default = true
 
+        /// When set to true, the physical plan optimizer will reorder chains of inner
+        /// hash joins to minimize the estimated size of their intermediate results,
+        /// using the row counts and distinct counts of the statistics of the joined
+        /// inputs. Joins are left in the written order when these statistics are absent
+        pub enable_join_reordering: bool, default = true
+
+        /// When set to true, the logical plan optimizer will push partial aggregations
+        /// below inner joins (eager aggregation), so that fewer rows are joined. This
+        /// is only done when the statistics of the aggregated input of the join
+        /// estimate that the partial aggregation reduces its number of rows enough,
+        /// see `eager_aggregation_max_group_ratio`
+        pub enable_eager_aggregation: bool, default = true
+
+        /// The maximum ratio of the estimated number of groups of a partial
+        /// aggregation pushed below a join by eager aggregation to the number of
+        /// rows of its input
+        pub eager_aggregation_max_group_ratio: f64, default = 0.5
+
+        /// When set to true, the logical plan optimizer will filter the scan of the
+        /// larger input of an equi join with the join keys of its smaller, filtered
+        /// input (semi-join reduction), so that parquet files, row groups and rows
+        /// without a matching key are skipped. This is only done when the statistics
+        /// of the inputs estimate that the filtered input is small enough, see
+        /// `semi_join_reduction_max_ratio`. The join keys are computed when the
+        /// query is executed
+        pub enable_semi_join_reduction: bool, default = false
+
+        /// The maximum ratio of the estimated number of rows of the filtered input
+        /// of a join to the number of rows of its other input for semi-join reduction
+        pub semi_join_reduction_max_ratio: f64, default = 0.1
+
+        /// The maximum number of distinct join keys of a semi-join reduction passed
+        /// to the scan as an `IN` list, whose values can prune row groups using their
+        /// statistics and bloom filters. More keys are passed as a bloom filter
+        pub semi_join_reduction_max_in_list_size: usize, default = 1024
+
+        /// The maximum number of join keys of a semi-join reduction. The keys are
+        /// computed when the query is executed and kept in memory: if the filtered
+        /// input returns more rows, or they do not fit in the memory pool, the
+        /// reduction is skipped
+        pub semi_join_reduction_max_keys: usize, default = 1_000_000
+
         /// The maximum estimated size in bytes for one input side of a HashJoin
         /// will be collected into a single partition
         pub hash_join_single_partition_threshold: usize, default = 1024 * 1024
@@ -613,6 +690,15 @@ config_namespace! {
         /// will be collected into a single partition
         pub hash_join_single_partition_threshold_rows: usize, default = 1024 * 128
 
+        /// When set to true, the physical plan optimizer chooses the partitioning
+        /// mode of hash joins at runtime instead of from the estimated statistics
+        /// of their inputs. The join reads its build side up to
+        /// `hash_join_single_partition_threshold` bytes and
+        /// `hash_join_single_partition_threshold_rows` rows: a side within both
+        /// thresholds is collected into a single partition, swapping the inputs
+        /// if needed, otherwise both sides are hash partitioned
+        pub enable_adaptive_join: bool, default = false
+
         /// The default filter selectivity used by Filter Statistics
         /// when an exact selectivity cannot be determined. Valid values are
         /// between 0 (no selectivity) and 100 (all rows are selected).
@@ -1028,6 +1114,28 @@ impl ConfigField for CompressionTypeVariant {
     }
 }
 
+impl ConfigField for OnError {
+    fn visit<V: Visit>(&self, v: &mut V, key: &str, description: &'static str) {
+        v.some(key, self, description)
+    }
+
+    fn set(&mut self, _: &str, value: &str) -> Result<()> {
+        *self = OnError::from_str(value)?;
+        Ok(())
+    }
+}
+
+impl ConfigField for SchemaInferStrategy {
+    fn visit<V: Visit>(&self, v: &mut V, key: &str, description: &'static str) {
+        v.some(key, self, description)
+    }
+
+    fn set(&mut self, _: &str, value: &str) -> Result<()> {
+        *self = SchemaInferStrategy::from_str(value)?;
+        Ok(())
+    }
+}
+
 /// An implementation trait used to recursively walk configuration
 pub trait Visit {
     fn some<V: Display>(&mut self, key: &str, value: V, description: &'static str);
@@ -1169,6 +1277,7 @@ pub enum ConfigFileType {
     #[cfg(feature = "parquet")]
     PARQUET,
     JSON,
+    AVRO,
 }
 
 /// Represents the configuration options available for handling different table formats within a data processing application.
@@ -1187,6 +1296,10 @@ pub struct TableOptions {
     /// Configuration options for JSON file handling.
     pub json: JsonOptions,
 
+    /// Configuration options for Avro file handling, such as the block compression codec
+    /// used when writing.
+    pub avro: AvroOptions,
+
     /// The current file format that the table operations should assume. This option allows
     /// for dynamic switching between the supported file types (e.g., CSV, Parquet, JSON).
     pub current_format: Option<ConfigFileType>,
@@ -1210,11 +1323,13 @@ impl ConfigField for TableOptions {
                 ConfigFileType::PARQUET => self.parquet.visit(v, "format", ""),
                 ConfigFileType::CSV => self.csv.visit(v, "format", ""),
                 ConfigFileType::JSON => self.json.visit(v, "format", ""),
+                ConfigFileType::AVRO => self.avro.visit(v, "format", ""),
             }
         } else {
             self.csv.visit(v, "csv", "");
             self.parquet.visit(v, "parquet", "");
             self.json.visit(v, "json", "");
+            self.avro.visit(v, "avro", "");
         }
     }
 
@@ -1246,6 +1361,7 @@ impl ConfigField for TableOptions {
                     ConfigFileType::PARQUET => self.parquet.set(rem, value),
                     ConfigFileType::CSV => self.csv.set(rem, value),
                     ConfigFileType::JSON => self.json.set(rem, value),
+                    ConfigFileType::AVRO => self.avro.set(rem, value),
                 }
             }
             _ => _config_err!("Config value \"{key}\" not found on TableOptions"),
@@ -1624,6 +1740,9 @@ config_namespace! {
         /// the configuration should be consulted.
         pub has_header: Option<bool>, default = None
         pub delimiter: u8, default = b','
+        /// A delimiter of multiple bytes, such as `||`, used instead of
+        /// `delimiter` when set. Cannot be combined with `newlines_in_values`
+        pub delimiter_sequence: Option<String>, default = None
         pub quote: u8, default = b'"'
         pub terminator: Option<u8>, default = None
         pub escape: Option<u8>, default = None
@@ -1638,13 +1757,40 @@ config_namespace! {
         pub newlines_in_values: Option<bool>, default = None
         pub compression: CompressionTypeVariant, default = CompressionTypeVariant::UNCOMPRESSED
         pub schema_infer_max_rec: usize, default = 100
+        /// How records are sampled to infer the schema: the first
+        /// `schema_infer_max_rec` records (`head`), `schema_infer_max_rec`
+        /// records spread across all files and byte ranges of each file
+        /// (`sample`), or every record (`full`)
+        pub schema_infer_strategy: SchemaInferStrategy, default = SchemaInferStrategy::Head
+        /// The number of byte ranges of each uncompressed file sampled by the
+        /// `sample` schema inference strategy
+        pub schema_infer_sample_ranges: usize, default = 4
+        /// If true, columns of fractional numbers with a fixed number of
+        /// decimal places are inferred as decimals rather than floats
+        pub schema_infer_decimal: bool, default = false
+        /// Format of dates, used to read dates and to infer date columns, as
+        /// well as to write dates
         pub date_format: Option<String>, default = None
         pub datetime_format: Option<String>, default = None
+        /// Format of timestamps without a time zone, used to read timestamps
+        /// and to infer timestamp columns, as well as to write timestamps
         pub timestamp_format: Option<String>, default = None
         pub timestamp_tz_format: Option<String>, default = None
         pub time_format: Option<String>, default = None
         pub null_value: Option<String>, default = None
         pub comment: Option<u8>, default = None
+        /// How records which fail to parse are handled: `fail` the query,
+        /// `skip` them, or keep them with the values which fail to parse set
+        /// to `null`
+        pub on_error: OnError, default = OnError::Fail
+        /// The maximum number of records which may fail to parse, when
+        /// `on_error` is `skip` or `null`, before the query fails.
+        /// Unlimited by default
+        pub max_errors: Option<usize>, default = None
+        /// Optional path of a local file which the records which fail to
+        /// parse are appended to, as newline delimited JSON objects with the
+        /// `file`, `line`, `reason` and `record` of each record
+        pub bad_records_path: Option<String>, default = None
     }
 }
 
@@ -1687,6 +1833,14 @@ impl CsvOptions {
         self
     }
 
+    /// A sequence of multiple bytes separating values within a row, used
+    /// instead of the delimiter character.
+    /// - default to None
+    pub fn with_delimiter_sequence(mut self, delimiter_sequence: Option<String>) -> Self {
+        self.delimiter_sequence = delimiter_sequence;
+        self
+    }
+
     /// The quote character in a row.
     /// - default to '"'
     pub fn with_quote(mut self, quote: u8) -> Self {
@@ -1763,6 +1917,98 @@ config_namespace! {
     pub struct JsonOptions {
         pub compression: CompressionTypeVariant, default = CompressionTypeVariant::UNCOMPRESSED
         pub schema_infer_max_rec: usize, default = 100
+        /// How records are sampled to infer the schema: the first
+        /// `schema_infer_max_rec` records (`head`), `schema_infer_max_rec`
+        /// records spread across all files and byte ranges of each file
+        /// (`sample`), or every record (`full`)
+        pub schema_infer_strategy: SchemaInferStrategy, default = SchemaInferStrategy::Head
+        /// The number of byte ranges of each uncompressed file sampled by the
+        /// `sample` schema inference strategy
+        pub schema_infer_sample_ranges: usize, default = 4
+        /// How records which fail to parse are handled: `fail` the query,
+        /// `skip` them, or keep them with the values which fail to parse set
+        /// to `null`
+        pub on_error: OnError, default = OnError::Fail
+        /// The maximum number of records which may fail to parse, when
+        /// `on_error` is `skip` or `null`, before the query fails.
+        /// Unlimited by default
+        pub max_errors: Option<usize>, default = None
+        /// Optional path of a local file which the records which fail to
+        /// parse are appended to, as newline delimited JSON objects with the
+        /// `file`, `line`, `reason` and `record` of each record
+        pub bad_records_path: Option<String>, default = None
+    }
+}
+
+config_namespace! {
+    /// Options controlling Avro format
+    pub struct AvroOptions {
+        /// Block compression codec used when writing Avro files. Valid values are:
+        /// uncompressed (or null), deflate, snappy, zstd, bzip2 and xz
+        pub compression: String, default = "uncompressed".to_string()
+    }
+}
+
+config_namespace! {
+    /// Options controlling the fixed width text format, whose values are at
+    /// fixed byte ranges of each line
+    pub struct FixedWidthOptions {
+        /// Comma separated widths in bytes of the columns, such as `5,10,3`
+        pub widths: String, default = String::new()
+        /// Comma separated byte offsets of the columns from the start of each
+        /// line. By default each column starts where the previous one ends
+        pub offsets: Option<String>, default = None
+        /// Whether the first line holds the column names. If not specified, the
+        /// value of `datafusion.catalog.has_header` is used
+        pub has_header: Option<bool>, default = None
+        /// Compression of the files, such as `gzip`
+        pub compression: CompressionTypeVariant, default = CompressionTypeVariant::UNCOMPRESSED
+        /// The number of lines read to infer the types of the columns
+        pub schema_infer_max_rec: usize, default = 100
+    }
+}
+
+impl FixedWidthOptions {
+    /// Returns the byte range of each column of a line
+    pub fn columns(&self) -> Result<Vec<Range<usize>>> {
+        let parse = |list: &str, name: &str| {
+            list.split(',')
+                .map(|value| {
+                    value.trim().parse::<usize>().map_err(|_| {
+                        DataFusionError::Configuration(format!(
+                            "Invalid {name} '{list}' of fixed width columns, expected comma separated integers"
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+        if self.widths.trim().is_empty() {
+            return _config_err!("The widths of the fixed width columns must be set");
+        }
+        let widths = parse(&self.widths, "widths")?;
+        let offsets = match &self.offsets {
+            Some(offsets) => parse(offsets, "offsets")?,
+            None => widths
+                .iter()
+                .scan(0, |offset, width| {
+                    let start = *offset;
+                    *offset += width;
+                    Some(start)
+                })
+                .collect(),
+        };
+        if offsets.len() != widths.len() {
+            return _config_err!(
+                "Expected {} fixed width column offsets, found {}",
+                widths.len(),
+                offsets.len()
+            );
+        }
+        Ok(offsets
+            .into_iter()
+            .zip(widths)
+            .map(|(offset, width)| offset..offset + width)
+            .collect())
     }
 }
 
@@ -18,13 +18,50 @@
 //! Types for plan display
 
 mod graphviz;
+mod trace;
 pub use graphviz::*;
+pub use trace::*;
 
 use std::{
     fmt::{self, Display, Formatter},
+    str::FromStr,
     sync::Arc,
 };
 
+use crate::error::{_plan_err, DataFusionError};
+
+/// The output format of an `EXPLAIN` statement
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Hash)]
+pub enum ExplainFormat {
+    /// Print the plans as indented text
+    #[default]
+    Indent,
+    /// Print an [`OptimizerTraceEvent`] for each optimizer rule instead of
+    /// the full plan produced by the rule
+    Trace,
+}
+
+impl FromStr for ExplainFormat {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "indent" => Ok(Self::Indent),
+            "trace" => Ok(Self::Trace),
+            _ => _plan_err!("Unsupported EXPLAIN format '{s}', expected INDENT or TRACE"),
+        }
+    }
+}
+
+impl Display for ExplainFormat {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ExplainFormat::Indent => write!(f, "INDENT"),
+            ExplainFormat::Trace => write!(f, "TRACE"),
+        }
+    }
+}
+
 /// Represents which type of plan, when storing multiple
 /// for use in EXPLAIN plans
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
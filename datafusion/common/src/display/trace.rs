@@ -0,0 +1,312 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Structured tracing of optimizer rule applications
+
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{PlanType, StringifiedPlan};
+
+/// Number of unchanged lines shown around each change in [`unified_diff`]
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Callback invoked with an [`OptimizerTraceEvent`] after each optimizer
+/// rule has been applied
+pub type OptimizerTraceObserver = Arc<dyn Fn(&OptimizerTraceEvent) + Send + Sync>;
+
+/// The optimizer which produced an [`OptimizerTraceEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OptimizerPhase {
+    /// A rule of the logical plan `Optimizer`
+    Logical,
+    /// A `PhysicalOptimizerRule`
+    Physical,
+}
+
+impl Display for OptimizerPhase {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            OptimizerPhase::Logical => write!(f, "logical"),
+            OptimizerPhase::Physical => write!(f, "physical"),
+        }
+    }
+}
+
+/// Describes the application of a single optimizer rule to a plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptimizerTraceEvent {
+    /// The optimizer the rule belongs to
+    pub phase: OptimizerPhase,
+    /// The name of the rule
+    pub rule_name: String,
+    /// The (zero based) optimizer pass in which the rule ran. Physical
+    /// optimizer rules run in a single pass.
+    pub pass: usize,
+    /// The time spent applying the rule
+    pub elapsed: Duration,
+    /// Whether the rule changed the displayed plan
+    pub changed: bool,
+    /// Unified diff between the plan text before and after the rule.
+    /// Empty if the plan was not changed.
+    pub diff: String,
+}
+
+impl OptimizerTraceEvent {
+    /// Create a new event from the text of the plan before and after
+    /// applying the rule
+    pub fn new(
+        phase: OptimizerPhase,
+        rule_name: impl Into<String>,
+        pass: usize,
+        elapsed: Duration,
+        before: &str,
+        after: &str,
+    ) -> Self {
+        Self {
+            phase,
+            rule_name: rule_name.into(),
+            pass,
+            elapsed,
+            changed: before != after,
+            diff: unified_diff(before, after),
+        }
+    }
+
+    /// Returns this event as a [`StringifiedPlan`] for use in EXPLAIN output
+    pub fn to_stringified(&self) -> StringifiedPlan {
+        let optimizer_name = self.rule_name.clone();
+        let plan_type = match self.phase {
+            OptimizerPhase::Logical => PlanType::OptimizedLogicalPlan { optimizer_name },
+            OptimizerPhase::Physical => {
+                PlanType::OptimizedPhysicalPlan { optimizer_name }
+            }
+        };
+        StringifiedPlan::new(plan_type, self.to_string())
+    }
+}
+
+impl Display for OptimizerTraceEvent {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let status = if self.changed { "changed" } else { "unchanged" };
+        write!(f, "pass {}: {status} in {:?}", self.pass, self.elapsed)?;
+        if !self.diff.is_empty() {
+            write!(f, "\n{}", self.diff.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns a unified diff of the lines of `before` and `after`, or an empty
+/// string if they contain the same lines
+pub fn unified_diff(before: &str, after: &str) -> String {
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+    let lines = diff_lines(&old, &new);
+
+    let changes: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (tag, _))| *tag != ' ')
+        .map(|(i, _)| i)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    let mut out = String::from("--- before\n+++ after\n");
+    let mut idx = 0;
+    while idx < changes.len() {
+        // merge changes separated by at most twice the context into one hunk
+        let start = changes[idx].saturating_sub(DIFF_CONTEXT_LINES);
+        let mut last = changes[idx];
+        idx += 1;
+        while idx < changes.len() && changes[idx] - last <= 2 * DIFF_CONTEXT_LINES + 1 {
+            last = changes[idx];
+            idx += 1;
+        }
+        let end = (last + DIFF_CONTEXT_LINES + 1).min(lines.len());
+
+        let count = |lines: &[(char, &str)], skip: char| {
+            lines.iter().filter(|(tag, _)| *tag != skip).count()
+        };
+        let (old_skipped, new_skipped) =
+            (count(&lines[..start], '+'), count(&lines[..start], '-'));
+        let hunk = &lines[start..end];
+        let (old_len, new_len) = (count(hunk, '+'), count(hunk, '-'));
+        // empty ranges refer to the line before them
+        let old_start = if old_len == 0 {
+            old_skipped
+        } else {
+            old_skipped + 1
+        };
+        let new_start = if new_len == 0 {
+            new_skipped
+        } else {
+            new_skipped + 1
+        };
+
+        out.push_str(&format!(
+            "@@ -{old_start},{old_len} +{new_start},{new_len} @@\n"
+        ));
+        for (tag, line) in hunk {
+            out.push(*tag);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Computes a minimal line based edit script transforming `old` into `new`,
+/// tagging each line with `' '`, `'-'` or `'+'`
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(char, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    // lcs[i * width + j] is the length of the longest common subsequence
+    // of old_mid[i..] and new_mid[j..]
+    let (n, m) = (old_mid.len(), new_mid.len());
+    let width = m + 1;
+    let mut lcs = vec![0usize; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i * width + j] = if old_mid[i] == new_mid[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut lines: Vec<_> = old[..prefix].iter().map(|l| (' ', *l)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_mid[i] == new_mid[j] {
+            lines.push((' ', old_mid[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+        {
+            lines.push(('-', old_mid[i]));
+            i += 1;
+        } else {
+            lines.push(('+', new_mid[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[old.len() - suffix..].iter().map(|l| (' ', *l)));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unified_diff_unchanged() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb"), "");
+        assert_eq!(unified_diff("", ""), "");
+    }
+
+    #[test]
+    fn unified_diff_single_hunk() {
+        let before = "Projection: a\n  Filter: a > 1\n    TableScan: t";
+        let after = "Projection: a\n  TableScan: t, full_filters=[a > 1]";
+        let expected = "--- before\n+++ after\n\
+                        @@ -1,3 +1,2 @@\n \
+                        Projection: a\n\
+                        -  Filter: a > 1\n\
+                        -    TableScan: t\n\
+                        +  TableScan: t, full_filters=[a > 1]\n";
+        assert_eq!(unified_diff(before, after), expected);
+    }
+
+    #[test]
+    fn unified_diff_multiple_hunks() {
+        let before: Vec<String> = (0..20).map(|i| format!("line {i}")).collect();
+        let mut after = before.clone();
+        after[1] = "changed 1".to_string();
+        after.remove(15);
+        let diff = unified_diff(&before.join("\n"), &after.join("\n"));
+        let expected = "--- before\n+++ after\n\
+                        @@ -1,5 +1,5 @@\n \
+                        line 0\n\
+                        -line 1\n\
+                        +changed 1\n \
+                        line 2\n \
+                        line 3\n \
+                        line 4\n\
+                        @@ -13,7 +13,6 @@\n \
+                        line 12\n \
+                        line 13\n \
+                        line 14\n\
+                        -line 15\n \
+                        line 16\n \
+                        line 17\n \
+                        line 18\n";
+        assert_eq!(diff, expected);
+    }
+
+    #[test]
+    fn unified_diff_insert_into_empty() {
+        assert_eq!(
+            unified_diff("", "a\nb"),
+            "--- before\n+++ after\n@@ -0,0 +1,2 @@\n+a\n+b\n"
+        );
+    }
+
+    #[test]
+    fn trace_event_display() {
+        let event = OptimizerTraceEvent::new(
+            OptimizerPhase::Logical,
+            "rule",
+            1,
+            Duration::from_micros(12),
+            "a",
+            "b",
+        );
+        assert!(event.changed);
+        assert_eq!(
+            event.to_string(),
+            "pass 1: changed in 12µs\n--- before\n+++ after\n@@ -1,1 +1,1 @@\n-a\n+b"
+        );
+        assert_eq!(
+            event.to_stringified().plan_type.to_string(),
+            "logical_plan after rule"
+        );
+
+        let event = OptimizerTraceEvent::new(
+            OptimizerPhase::Physical,
+            "rule",
+            0,
+            Duration::from_millis(2),
+            "a",
+            "a",
+        );
+        assert!(!event.changed);
+        assert_eq!(event.to_string(), "pass 0: unchanged in 2ms");
+    }
+}
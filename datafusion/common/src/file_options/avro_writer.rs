@@ -17,5 +17,78 @@
 
 //! Options related to how avro files should be written
 
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::{
+    config::AvroOptions,
+    error::{DataFusionError, Result},
+};
+
+/// Block compression codec of an Avro object container file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AvroCompression {
+    /// Blocks are written without compression (the `null` codec)
+    #[default]
+    Uncompressed,
+    Deflate,
+    Snappy,
+    Zstd,
+    Bzip2,
+    Xz,
+}
+
+impl FromStr for AvroCompression {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "" | "uncompressed" | "null" => Ok(Self::Uncompressed),
+            "deflate" => Ok(Self::Deflate),
+            "snappy" => Ok(Self::Snappy),
+            "zstd" | "zstandard" => Ok(Self::Zstd),
+            "bzip2" => Ok(Self::Bzip2),
+            "xz" => Ok(Self::Xz),
+            _ => Err(DataFusionError::Configuration(format!(
+                "Unknown or unsupported avro compression codec {s}. \
+                Valid values are: uncompressed, deflate, snappy, zstd, bzip2 and xz"
+            ))),
+        }
+    }
+}
+
+impl Display for AvroCompression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Uncompressed => "uncompressed",
+            Self::Deflate => "deflate",
+            Self::Snappy => "snappy",
+            Self::Zstd => "zstd",
+            Self::Bzip2 => "bzip2",
+            Self::Xz => "xz",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Options for writing Avro files
 #[derive(Clone, Debug)]
-pub struct AvroWriterOptions {}
+pub struct AvroWriterOptions {
+    pub compression: AvroCompression,
+}
+
+impl AvroWriterOptions {
+    pub fn new(compression: AvroCompression) -> Self {
+        Self { compression }
+    }
+}
+
+impl TryFrom<&AvroOptions> for AvroWriterOptions {
+    type Error = DataFusionError;
+
+    fn try_from(value: &AvroOptions) -> Result<Self> {
+        Ok(AvroWriterOptions {
+            compression: value.compression.parse()?,
+        })
+    }
+}
@@ -33,7 +33,11 @@ mod tests {
     use super::parquet_writer::ParquetWriterOptions;
     use crate::{
         config::{ConfigFileType, TableOptions},
-        file_options::{csv_writer::CsvWriterOptions, json_writer::JsonWriterOptions},
+        file_options::{
+            avro_writer::{AvroCompression, AvroWriterOptions},
+            csv_writer::CsvWriterOptions,
+            json_writer::JsonWriterOptions,
+        },
         parsers::CompressionTypeVariant,
         Result,
     };
@@ -314,4 +318,24 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    // for StatementOptions
+    fn test_writeroptions_avro_from_statement_options() -> Result<()> {
+        let mut option_map: HashMap<String, String> = HashMap::new();
+        option_map.insert("format.compression".to_owned(), "zstd".to_owned());
+
+        let mut table_config = TableOptions::new();
+        table_config.set_config_format(ConfigFileType::AVRO);
+        table_config.alter_with_string_hash_map(&option_map)?;
+
+        let avro_options = AvroWriterOptions::try_from(&table_config.avro)?;
+        assert_eq!(avro_options.compression, AvroCompression::Zstd);
+
+        option_map.insert("format.compression".to_owned(), "gzip".to_owned());
+        table_config.alter_with_string_hash_map(&option_map)?;
+        assert!(AvroWriterOptions::try_from(&table_config.avro).is_err());
+
+        Ok(())
+    }
 }
@@ -238,6 +238,22 @@ impl FunctionalDependencies {
         )
     }
 
+    /// Checks if the fields at `indices` contain a determinant key which may
+    /// occur only once, so that no two rows have the same values for these
+    /// fields (apart from the nulls of nullable keys).
+    pub fn contains_unique_key(&self, indices: &[usize]) -> bool {
+        self.deps.iter().any(
+            |FunctionalDependence {
+                 source_indices,
+                 mode,
+                 ..
+             }| {
+                *mode == Dependency::Single
+                    && source_indices.iter().all(|index| indices.contains(index))
+            },
+        )
+    }
+
     /// Adds the `offset` value to `source_indices` and `target_indices` for
     /// each functional dependency.
     pub fn add_offset(&mut self, offset: usize) {
@@ -600,6 +616,19 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn test_contains_unique_key() {
+        let dependencies = FunctionalDependencies::new(vec![
+            FunctionalDependence::new(vec![0, 1], vec![0, 1, 2, 3], false)
+                .with_mode(Dependency::Single),
+            FunctionalDependence::new(vec![2], vec![2, 3], true),
+        ]);
+        assert!(dependencies.contains_unique_key(&[0, 1]));
+        assert!(dependencies.contains_unique_key(&[3, 1, 0]));
+        assert!(!dependencies.contains_unique_key(&[0, 2]));
+        assert!(!dependencies.contains_unique_key(&[2, 3]));
+    }
+
     #[test]
     fn test_get_updated_id_keys() {
         let fund_dependencies =
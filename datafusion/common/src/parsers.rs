@@ -74,3 +74,80 @@ impl CompressionTypeVariant {
         !matches!(self, &Self::UNCOMPRESSED)
     }
 }
+
+/// How records of CSV and JSON files which fail to parse are handled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OnError {
+    /// Fail the query
+    #[default]
+    Fail,
+    /// Skip the record
+    Skip,
+    /// Keep the record, with the values which fail to parse set to null
+    Null,
+}
+
+impl FromStr for OnError {
+    type Err = ParserError;
+
+    fn from_str(s: &str) -> result::Result<Self, ParserError> {
+        match s.to_lowercase().as_str() {
+            "fail" => Ok(Self::Fail),
+            "skip" => Ok(Self::Skip),
+            "null" => Ok(Self::Null),
+            _ => Err(ParserError::ParserError(format!(
+                "Unsupported on_error mode {s}, expected one of fail, skip or null"
+            ))),
+        }
+    }
+}
+
+impl Display for OnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Self::Fail => "fail",
+            Self::Skip => "skip",
+            Self::Null => "null",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+/// How records of CSV and JSON files are sampled to infer their schema
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SchemaInferStrategy {
+    /// Read the first `schema_infer_max_rec` records, in file order
+    #[default]
+    Head,
+    /// Read `schema_infer_max_rec` records spread evenly across all files,
+    /// and across byte ranges within each uncompressed file
+    Sample,
+    /// Read every record of every file
+    Full,
+}
+
+impl FromStr for SchemaInferStrategy {
+    type Err = ParserError;
+
+    fn from_str(s: &str) -> result::Result<Self, ParserError> {
+        match s.to_lowercase().as_str() {
+            "head" => Ok(Self::Head),
+            "sample" => Ok(Self::Sample),
+            "full" => Ok(Self::Full),
+            _ => Err(ParserError::ParserError(format!(
+                "Unsupported schema inference strategy {s}, expected one of head, sample or full"
+            ))),
+        }
+    }
+}
+
+impl Display for SchemaInferStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Self::Head => "head",
+            Self::Sample => "sample",
+            Self::Full => "full",
+        };
+        write!(f, "{}", str)
+    }
+}
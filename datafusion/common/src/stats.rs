@@ -19,9 +19,10 @@
 
 use std::fmt::{self, Debug, Display};
 
+use crate::error::_internal_err;
 use crate::{Result, ScalarValue};
 
-use arrow_schema::{Schema, SchemaRef};
+use arrow_schema::{DataType, Schema, SchemaRef};
 
 /// Represents a value with a degree of certainty. `Precision` is used to
 /// propagate information the precision of statistical values.
@@ -258,6 +259,26 @@ impl Statistics {
         self
     }
 
+    /// Fills the absent statistics with those of `other`, such as the
+    /// statistics computed by `ANALYZE TABLE`, which describe the same columns.
+    /// The filled statistics are [`Precision::Inexact`], as `other` may be
+    /// outdated.
+    pub fn fill_absent(mut self, other: &Statistics) -> Self {
+        if self.num_rows == Precision::Absent {
+            self.num_rows = other.num_rows.to_inexact();
+        }
+        if self.total_byte_size == Precision::Absent {
+            self.total_byte_size = other.total_byte_size.to_inexact();
+        }
+        self.column_statistics = self
+            .column_statistics
+            .into_iter()
+            .zip(&other.column_statistics)
+            .map(|(stats, other)| stats.fill_absent(other))
+            .collect();
+        self
+    }
+
     /// Calculates the statistics after `fetch` and `skip` operations apply.
     /// Here, `self` denotes per-partition statistics. Use the `n_partitions`
     /// parameter to compute global statistics in a multi-partition setting.
@@ -369,6 +390,16 @@ impl Display for Statistics {
                 } else {
                     s
                 };
+                let s = match cs.histogram.get_value() {
+                    Some(histogram) => {
+                        format!("{} Histogram={}", s, histogram.buckets().len())
+                    }
+                    None => s,
+                };
+                let s = match cs.most_common_values.get_value() {
+                    Some(mcv) => format!("{} MostCommon={}", s, mcv.values().len()),
+                    None => s,
+                };
 
                 s + ")"
             })
@@ -396,6 +427,11 @@ pub struct ColumnStatistics {
     pub min_value: Precision<ScalarValue>,
     /// Number of distinct values
     pub distinct_count: Precision<usize>,
+    /// Equi-depth histogram of the non null values of column, except for its
+    /// most common values
+    pub histogram: Precision<Histogram>,
+    /// Most common non null values of column
+    pub most_common_values: Precision<MostCommonValues>,
 }
 
 impl ColumnStatistics {
@@ -417,6 +453,8 @@ impl ColumnStatistics {
             max_value: Precision::Absent,
             min_value: Precision::Absent,
             distinct_count: Precision::Absent,
+            histogram: Precision::Absent,
+            most_common_values: Precision::Absent,
         }
     }
 
@@ -428,8 +466,204 @@ impl ColumnStatistics {
         self.max_value = self.max_value.to_inexact();
         self.min_value = self.min_value.to_inexact();
         self.distinct_count = self.distinct_count.to_inexact();
+        self.histogram = self.histogram.to_inexact();
+        self.most_common_values = self.most_common_values.to_inexact();
         self
     }
+
+    /// Fills the absent statistics with the inexact statistics of `other`,
+    /// see [`Statistics::fill_absent`]
+    pub fn fill_absent(self, other: &ColumnStatistics) -> Self {
+        fn fill<T: Debug + Clone + PartialEq + Eq + PartialOrd>(
+            value: Precision<T>,
+            other: &Precision<T>,
+        ) -> Precision<T> {
+            match value {
+                Precision::Absent => other.clone().to_inexact(),
+                value => value,
+            }
+        }
+        Self {
+            null_count: fill(self.null_count, &other.null_count),
+            max_value: fill(self.max_value, &other.max_value),
+            min_value: fill(self.min_value, &other.min_value),
+            distinct_count: fill(self.distinct_count, &other.distinct_count),
+            histogram: fill(self.histogram, &other.histogram),
+            most_common_values: fill(self.most_common_values, &other.most_common_values),
+        }
+    }
+}
+
+/// An equi-depth histogram of the values of a column: each of its buckets
+/// holds about the same number of values, so that the estimates made for the
+/// ranges of skewed columns are more accurate than with their minimum and
+/// maximum values alone.
+///
+/// The most common values of a column are not in its histogram, see
+/// [`ColumnStatistics::most_common_values`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Default)]
+pub struct Histogram {
+    buckets: Vec<HistogramBucket>,
+}
+
+/// A bucket of a [`Histogram`]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd)]
+pub struct HistogramBucket {
+    /// Smallest value of the bucket
+    pub lower: ScalarValue,
+    /// Largest value of the bucket
+    pub upper: ScalarValue,
+    /// Number of values in the bucket
+    pub count: usize,
+    /// Number of distinct values in the bucket
+    pub distinct_count: usize,
+}
+
+impl Histogram {
+    /// Creates a histogram from its buckets, which must be sorted by their
+    /// values and must not overlap
+    pub fn try_new(buckets: Vec<HistogramBucket>) -> Result<Self> {
+        for bucket in &buckets {
+            if bucket.lower > bucket.upper
+                || bucket.distinct_count == 0
+                || bucket.distinct_count > bucket.count
+            {
+                return _internal_err!("Invalid histogram bucket {bucket:?}");
+            }
+        }
+        for pair in buckets.windows(2) {
+            if pair[0].upper >= pair[1].lower {
+                return _internal_err!(
+                    "Histogram buckets {:?} and {:?} are not sorted",
+                    pair[0],
+                    pair[1]
+                );
+            }
+        }
+        Ok(Self { buckets })
+    }
+
+    /// The buckets of the histogram, sorted by their values
+    pub fn buckets(&self) -> &[HistogramBucket] {
+        &self.buckets
+    }
+
+    /// Number of values in the histogram
+    pub fn count(&self) -> usize {
+        self.buckets.iter().map(|bucket| bucket.count).sum()
+    }
+
+    /// Estimates the number of values of the histogram between `lower` and
+    /// `upper`, inclusive. Null bounds are unbounded.
+    pub fn estimate_range_count(&self, lower: &ScalarValue, upper: &ScalarValue) -> f64 {
+        self.buckets
+            .iter()
+            .map(|bucket| bucket.count as f64 * bucket.estimate_fraction(lower, upper))
+            .sum()
+    }
+
+    /// Estimates the number of values of the histogram equal to `value`,
+    /// assuming that the distinct values of a bucket are equally frequent
+    pub fn estimate_eq_count(&self, value: &ScalarValue) -> f64 {
+        self.buckets
+            .iter()
+            .find(|bucket| &bucket.lower <= value && value <= &bucket.upper)
+            .map_or(0.0, |bucket| {
+                bucket.count as f64 / bucket.distinct_count as f64
+            })
+    }
+}
+
+impl HistogramBucket {
+    /// Estimates the fraction of the values of the bucket between `lower` and
+    /// `upper`, inclusive, assuming that the values are uniformly distributed
+    /// within the bucket. Null bounds are unbounded.
+    pub fn estimate_fraction(&self, lower: &ScalarValue, upper: &ScalarValue) -> f64 {
+        let lower = if lower.is_null() || lower < &self.lower {
+            &self.lower
+        } else {
+            lower
+        };
+        let upper = if upper.is_null() || upper > &self.upper {
+            &self.upper
+        } else {
+            upper
+        };
+        if lower > upper || lower.partial_cmp(upper).is_none() {
+            0.0
+        } else if lower == &self.lower && upper == &self.upper {
+            1.0
+        } else if lower == upper {
+            1.0 / self.distinct_count as f64
+        } else {
+            match (width(lower, upper), width(&self.lower, &self.upper)) {
+                (Some(part), Some(whole)) if whole > 0.0 => (part / whole).min(1.0),
+                // The values cannot be interpolated, such as for strings
+                _ => 0.5,
+            }
+        }
+    }
+}
+
+/// The width of the range of numeric values between `lower` and `upper`,
+/// counting both bounds for integers
+fn width(lower: &ScalarValue, upper: &ScalarValue) -> Option<f64> {
+    let width = match upper.sub_checked(lower).ok()?.cast_to(&DataType::Float64) {
+        Ok(ScalarValue::Float64(Some(width))) => width,
+        _ => return None,
+    };
+    if lower.data_type().is_integer() {
+        Some(width + 1.0)
+    } else {
+        Some(width)
+    }
+}
+
+/// The most common values of a column with their number of occurrences, in
+/// decreasing order of occurrences
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Default)]
+pub struct MostCommonValues {
+    values: Vec<(ScalarValue, usize)>,
+}
+
+impl MostCommonValues {
+    /// Creates the most common values from values and their number of
+    /// occurrences
+    pub fn new(mut values: Vec<(ScalarValue, usize)>) -> Self {
+        values.sort_by(|(_, a), (_, b)| b.cmp(a));
+        Self { values }
+    }
+
+    /// The values and their number of occurrences, in decreasing order of
+    /// occurrences
+    pub fn values(&self) -> &[(ScalarValue, usize)] {
+        &self.values
+    }
+
+    /// Number of occurrences of all the values
+    pub fn count(&self) -> usize {
+        self.values.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Number of occurrences of `value`, if it is one of the most common values
+    pub fn get(&self, value: &ScalarValue) -> Option<usize> {
+        self.values
+            .iter()
+            .find(|(v, _)| v == value)
+            .map(|(_, count)| *count)
+    }
+
+    /// Number of occurrences of the values between `lower` and `upper`,
+    /// inclusive. Null bounds are unbounded.
+    pub fn range_count(&self, lower: &ScalarValue, upper: &ScalarValue) -> usize {
+        self.values
+            .iter()
+            .filter(|(value, _)| {
+                (lower.is_null() || lower <= value) && (upper.is_null() || value <= upper)
+            })
+            .map(|(_, count)| count)
+            .sum()
+    }
 }
 
 #[cfg(test)]
@@ -546,6 +780,122 @@ mod tests {
         assert_eq!(precision1.multiply(&absent_precision), Precision::Absent);
     }
 
+    fn bucket(
+        lower: i64,
+        upper: i64,
+        count: usize,
+        distinct_count: usize,
+    ) -> HistogramBucket {
+        HistogramBucket {
+            lower: ScalarValue::from(lower),
+            upper: ScalarValue::from(upper),
+            count,
+            distinct_count,
+        }
+    }
+
+    #[test]
+    fn test_histogram() {
+        let histogram =
+            Histogram::try_new(vec![bucket(1, 10, 100, 10), bucket(11, 1000, 100, 50)])
+                .unwrap();
+        assert_eq!(histogram.count(), 200);
+
+        let range = |lower: Option<i64>, upper: Option<i64>| {
+            histogram.estimate_range_count(
+                &ScalarValue::from(lower),
+                &ScalarValue::from(upper),
+            )
+        };
+        assert_eq!(range(None, None), 200.0);
+        assert_eq!(range(Some(1), Some(5)), 50.0);
+        assert_eq!(range(Some(6), Some(505)), 100.0);
+        assert_eq!(range(Some(1001), None), 0.0);
+        assert_eq!(range(Some(5), Some(5)), 10.0);
+
+        assert_eq!(histogram.estimate_eq_count(&ScalarValue::from(3i64)), 10.0);
+        assert_eq!(
+            histogram.estimate_eq_count(&ScalarValue::from(2000i64)),
+            0.0
+        );
+
+        let strings = Histogram::try_new(vec![HistogramBucket {
+            lower: ScalarValue::from("a"),
+            upper: ScalarValue::from("m"),
+            count: 10,
+            distinct_count: 5,
+        }])
+        .unwrap();
+        let count = strings
+            .estimate_range_count(&ScalarValue::from("c"), &ScalarValue::Utf8(None));
+        assert_eq!(count, 5.0);
+    }
+
+    #[test]
+    fn test_invalid_histogram() {
+        let err = Histogram::try_new(vec![bucket(1, 10, 10, 5), bucket(10, 20, 10, 5)])
+            .unwrap_err();
+        assert!(err.to_string().contains("are not sorted"), "{err}");
+        let err = Histogram::try_new(vec![bucket(1, 10, 10, 11)]).unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid histogram bucket"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_most_common_values() {
+        let mcv = MostCommonValues::new(vec![
+            (ScalarValue::from(5i64), 10),
+            (ScalarValue::from(1i64), 30),
+            (ScalarValue::from(9i64), 20),
+        ]);
+        assert_eq!(mcv.values()[0], (ScalarValue::from(1i64), 30));
+        assert_eq!(mcv.count(), 60);
+        assert_eq!(mcv.get(&ScalarValue::from(9i64)), Some(20));
+        assert_eq!(mcv.get(&ScalarValue::from(2i64)), None);
+        let count = mcv.range_count(&ScalarValue::from(2i64), &ScalarValue::Int64(None));
+        assert_eq!(count, 30);
+    }
+
+    #[test]
+    fn test_fill_absent() {
+        let analyzed = Statistics {
+            num_rows: Precision::Exact(100),
+            total_byte_size: Precision::Absent,
+            column_statistics: vec![ColumnStatistics {
+                null_count: Precision::Exact(3),
+                max_value: Precision::Exact(ScalarValue::from(20i64)),
+                min_value: Precision::Exact(ScalarValue::from(1i64)),
+                distinct_count: Precision::Exact(20),
+                histogram: Precision::Exact(
+                    Histogram::try_new(vec![bucket(1, 20, 97, 20)]).unwrap(),
+                ),
+                most_common_values: Precision::Absent,
+            }],
+        };
+        let statistics = Statistics {
+            num_rows: Precision::Exact(90),
+            total_byte_size: Precision::Absent,
+            column_statistics: vec![ColumnStatistics {
+                null_count: Precision::Exact(0),
+                ..ColumnStatistics::new_unknown()
+            }],
+        }
+        .fill_absent(&analyzed);
+
+        assert_eq!(statistics.num_rows, Precision::Exact(90));
+        let column = &statistics.column_statistics[0];
+        assert_eq!(column.null_count, Precision::Exact(0));
+        assert_eq!(column.distinct_count, Precision::Inexact(20));
+        assert_eq!(
+            column.min_value,
+            Precision::Inexact(ScalarValue::from(1i64))
+        );
+        assert_eq!(column.histogram.get_value().unwrap().count(), 97);
+        assert_eq!(column.most_common_values, Precision::Absent);
+    }
+
     #[test]
     fn test_precision_cloning() {
         // Precision<usize> is copy
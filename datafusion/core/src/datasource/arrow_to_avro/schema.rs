@@ -0,0 +1,291 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::arrow::datatypes::{DataType, Field, Fields, Schema, TimeUnit};
+use crate::error::Result;
+use apache_avro::schema::{
+    DecimalSchema, FixedSchema, Name, RecordField, RecordFieldOrder, RecordSchema,
+    Schema as AvroSchema, UnionSchema,
+};
+use datafusion_common::not_impl_err;
+
+/// Name of the top level Avro record written for a [`Schema`]
+const TOP_LEVEL_RECORD_NAME: &str = "topLevelRecord";
+
+/// Converts an arrow [`Schema`] into the Avro record schema used to write it.
+///
+/// Nullable fields are written as a `["null", T]` union. Field names that are
+/// not valid Avro names are sanitized by replacing invalid characters with `_`.
+/// Timestamps with a time zone map to `timestamp-millis` / `timestamp-micros`,
+/// timestamps without a time zone to `local-timestamp-millis` /
+/// `local-timestamp-micros`. Second precision values are written as
+/// milliseconds and nanosecond precision values are truncated to microseconds.
+pub fn to_avro_schema(schema: &Schema) -> Result<AvroSchema> {
+    let mut names = HashSet::new();
+    record_schema(schema.fields(), TOP_LEVEL_RECORD_NAME, &mut names)
+}
+
+fn record_schema(
+    fields: &Fields,
+    name: &str,
+    names: &mut HashSet<String>,
+) -> Result<AvroSchema> {
+    let name = unique_name(name, names);
+    let mut field_names = HashSet::new();
+    let mut record_fields = Vec::with_capacity(fields.len());
+    let mut lookup = BTreeMap::new();
+    for (position, field) in fields.iter().enumerate() {
+        let field_name = unique_name(&sanitize_name(field.name()), &mut field_names);
+        let schema = field_schema(field, &format!("{name}_{field_name}"), names)?;
+        let default = matches!(schema, AvroSchema::Union(_) | AvroSchema::Null)
+            .then_some(serde_json::Value::Null);
+        lookup.insert(field_name.clone(), position);
+        record_fields.push(RecordField {
+            name: field_name,
+            doc: None,
+            aliases: None,
+            default,
+            schema,
+            order: RecordFieldOrder::Ascending,
+            position,
+            custom_attributes: BTreeMap::new(),
+        });
+    }
+    Ok(AvroSchema::Record(RecordSchema {
+        name: Name::new(&name)?,
+        aliases: None,
+        doc: None,
+        fields: record_fields,
+        lookup,
+        attributes: BTreeMap::new(),
+    }))
+}
+
+fn field_schema(
+    field: &Field,
+    name: &str,
+    names: &mut HashSet<String>,
+) -> Result<AvroSchema> {
+    let schema = data_type_schema(field.data_type(), name, names)?;
+    if field.is_nullable() && !matches!(schema, AvroSchema::Null) {
+        Ok(AvroSchema::Union(UnionSchema::new(vec![
+            AvroSchema::Null,
+            schema,
+        ])?))
+    } else {
+        Ok(schema)
+    }
+}
+
+fn data_type_schema(
+    data_type: &DataType,
+    name: &str,
+    names: &mut HashSet<String>,
+) -> Result<AvroSchema> {
+    Ok(match data_type {
+        DataType::Null => AvroSchema::Null,
+        DataType::Boolean => AvroSchema::Boolean,
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::UInt8
+        | DataType::UInt16 => AvroSchema::Int,
+        DataType::Int64 | DataType::UInt32 | DataType::UInt64 => AvroSchema::Long,
+        DataType::Float16 | DataType::Float32 => AvroSchema::Float,
+        DataType::Float64 => AvroSchema::Double,
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => AvroSchema::String,
+        DataType::Binary | DataType::LargeBinary | DataType::BinaryView => {
+            AvroSchema::Bytes
+        }
+        DataType::FixedSizeBinary(size) => AvroSchema::Fixed(FixedSchema {
+            name: Name::new(&unique_name(name, names))?,
+            aliases: None,
+            doc: None,
+            size: *size as usize,
+            attributes: BTreeMap::new(),
+        }),
+        DataType::Decimal128(precision, scale)
+        | DataType::Decimal256(precision, scale) => {
+            if *scale < 0 {
+                return not_impl_err!(
+                    "Writing decimals with a negative scale to Avro is not supported"
+                );
+            }
+            AvroSchema::Decimal(DecimalSchema {
+                precision: *precision as usize,
+                scale: *scale as usize,
+                inner: Box::new(AvroSchema::Bytes),
+            })
+        }
+        DataType::Date32 | DataType::Date64 => AvroSchema::Date,
+        DataType::Time32(_) => AvroSchema::TimeMillis,
+        DataType::Time64(_) => AvroSchema::TimeMicros,
+        DataType::Timestamp(unit, tz) => match (unit, tz.is_some()) {
+            (TimeUnit::Second | TimeUnit::Millisecond, true) => {
+                AvroSchema::TimestampMillis
+            }
+            (TimeUnit::Microsecond | TimeUnit::Nanosecond, true) => {
+                AvroSchema::TimestampMicros
+            }
+            (TimeUnit::Second | TimeUnit::Millisecond, false) => {
+                AvroSchema::LocalTimestampMillis
+            }
+            (TimeUnit::Microsecond | TimeUnit::Nanosecond, false) => {
+                AvroSchema::LocalTimestampMicros
+            }
+        },
+        DataType::List(field)
+        | DataType::LargeList(field)
+        | DataType::FixedSizeList(field, _) => {
+            AvroSchema::Array(Box::new(field_schema(field, name, names)?))
+        }
+        DataType::Struct(fields) => record_schema(fields, name, names)?,
+        DataType::Map(entries, _) => {
+            let DataType::Struct(fields) = entries.data_type() else {
+                return not_impl_err!("Unsupported map entries type {entries}");
+            };
+            let (key, value) = (&fields[0], &fields[1]);
+            if !matches!(
+                key.data_type(),
+                DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+            ) {
+                return not_impl_err!(
+                    "Avro maps only support string keys, found {}",
+                    key.data_type()
+                );
+            }
+            AvroSchema::Map(Box::new(field_schema(value, name, names)?))
+        }
+        DataType::Dictionary(_, value_type) => data_type_schema(value_type, name, names)?,
+        other => return not_impl_err!("Writing {other} to Avro is not supported"),
+    })
+}
+
+/// Returns `name` with every character that is not valid in an Avro name
+/// replaced by `_`
+fn sanitize_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !sanitized.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+/// Returns `name`, suffixed with a counter if it was already used
+fn unique_name(name: &str, names: &mut HashSet<String>) -> String {
+    let mut candidate = name.to_string();
+    let mut suffix = 1;
+    while !names.insert(candidate.clone()) {
+        candidate = format!("{name}_{suffix}");
+        suffix += 1;
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn nested_schema() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("my name", DataType::Utf8, true),
+            Field::new("amount", DataType::Decimal128(10, 2), true),
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+                false,
+            ),
+            Field::new(
+                "local_ts",
+                DataType::Timestamp(TimeUnit::Second, None),
+                false,
+            ),
+            Field::new(
+                "point",
+                DataType::Struct(Fields::from(vec![
+                    Field::new("x", DataType::Float64, false),
+                    Field::new("1y", DataType::Float32, true),
+                ])),
+                true,
+            ),
+            Field::new_list(
+                "tags",
+                Arc::new(Field::new_list_field(DataType::Utf8, true)),
+                false,
+            ),
+            Field::new("hash", DataType::FixedSizeBinary(4), false),
+        ]);
+        let avro_schema = to_avro_schema(&schema)?;
+        let expected: serde_json::Value = serde_json::from_str(
+            r#"{
+                "type": "record",
+                "name": "topLevelRecord",
+                "fields": [
+                    {"name": "id", "type": "long"},
+                    {"name": "my_name", "type": ["null", "string"], "default": null},
+                    {"name": "amount", "type": ["null", {"type": "bytes", "logicalType": "decimal", "precision": 10, "scale": 2}], "default": null},
+                    {"name": "ts", "type": {"type": "long", "logicalType": "timestamp-micros"}},
+                    {"name": "local_ts", "type": {"type": "long", "logicalType": "local-timestamp-millis"}},
+                    {"name": "point", "type": ["null", {
+                        "type": "record",
+                        "name": "topLevelRecord_point",
+                        "fields": [
+                            {"name": "x", "type": "double"},
+                            {"name": "_1y", "type": ["null", "float"], "default": null}
+                        ]
+                    }], "default": null},
+                    {"name": "tags", "type": {"type": "array", "items": ["null", "string"]}},
+                    {"name": "hash", "type": {"type": "fixed", "name": "topLevelRecord_hash", "size": 4}}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(serde_json::to_value(&avro_schema).unwrap(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_types() {
+        let schema = Schema::new(vec![Field::new(
+            "m",
+            DataType::Map(
+                Arc::new(Field::new(
+                    "entries",
+                    DataType::Struct(Fields::from(vec![
+                        Field::new("key", DataType::Int32, false),
+                        Field::new("value", DataType::Int32, true),
+                    ])),
+                    false,
+                )),
+                false,
+            ),
+            false,
+        )]);
+        let err = to_avro_schema(&schema).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Avro maps only support string keys"));
+    }
+}
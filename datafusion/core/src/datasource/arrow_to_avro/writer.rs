@@ -0,0 +1,370 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Arrow to Avro value conversion and object container file encoding
+
+use std::collections::HashMap;
+
+use super::to_avro_schema;
+use crate::arrow::array::{Array, ArrayRef, ArrowPrimitiveType, AsArray};
+use crate::arrow::compute::cast;
+use crate::arrow::datatypes::{
+    i256, DataType, Date32Type, Date64Type, Decimal128Type, Decimal256Type, Float16Type,
+    Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, Schema,
+    Time32MillisecondType, Time32SecondType, Time64MicrosecondType, Time64NanosecondType,
+    TimeUnit, TimestampMicrosecondType, TimestampMillisecondType,
+    TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type, UInt64Type,
+    UInt8Type,
+};
+use crate::arrow::record_batch::RecordBatch;
+use crate::error::{DataFusionError, Result};
+use apache_avro::schema::{RecordSchema, Schema as AvroSchema};
+use apache_avro::types::Value;
+use apache_avro::{to_avro_datum, Codec, Decimal};
+use datafusion_common::{exec_err, internal_err};
+
+/// Magic bytes at the start of an Avro object container file
+const AVRO_MAGIC: &[u8; 4] = b"Obj\x01";
+
+const MILLIS_PER_DAY: i64 = 86_400_000;
+
+/// Encodes [`RecordBatch`]es as an Avro object container file.
+///
+/// The file header and each batch are encoded independently, so that batches
+/// of the same file may be serialized concurrently. Every writer uses its own
+/// random sync marker, which is shared by the header and all data blocks.
+#[derive(Debug, Clone)]
+pub struct Writer {
+    codec: Codec,
+    marker: [u8; 16],
+}
+
+impl Writer {
+    /// Creates a writer compressing data blocks with `codec`
+    pub fn new(codec: Codec) -> Self {
+        Self {
+            codec,
+            marker: rand::random(),
+        }
+    }
+
+    /// Appends the file header for data of the given `schema` to `buf`
+    pub fn write_header(&self, schema: &Schema, buf: &mut Vec<u8>) -> Result<()> {
+        let avro_schema = to_avro_schema(schema)?;
+        let schema_json = serde_json::to_string(&avro_schema)
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        let metadata = HashMap::from([
+            (
+                "avro.schema".to_string(),
+                Value::Bytes(schema_json.into_bytes()),
+            ),
+            ("avro.codec".to_string(), Value::from(self.codec)),
+        ]);
+
+        buf.extend_from_slice(AVRO_MAGIC);
+        buf.extend(to_avro_datum(
+            &AvroSchema::Map(Box::new(AvroSchema::Bytes)),
+            Value::Map(metadata),
+        )?);
+        buf.extend_from_slice(&self.marker);
+        Ok(())
+    }
+
+    /// Appends the data blocks holding the rows of `batch` to `buf`
+    pub fn write_batch(&self, batch: &RecordBatch, buf: &mut Vec<u8>) -> Result<()> {
+        let avro_schema = to_avro_schema(&batch.schema())?;
+        let AvroSchema::Record(record) = &avro_schema else {
+            return internal_err!("Expected an Avro record schema, got {avro_schema:?}");
+        };
+        let records = record_values(batch.columns(), record, batch.num_rows())?;
+
+        let mut writer = apache_avro::Writer::append_to_with_codec(
+            &avro_schema,
+            buf,
+            self.codec,
+            self.marker,
+        );
+        for record in records {
+            writer.append(record)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Converts the rows of `columns` into Avro records of the given schema
+fn record_values(
+    columns: &[ArrayRef],
+    record: &RecordSchema,
+    num_rows: usize,
+) -> Result<Vec<Value>> {
+    let mut columns = columns
+        .iter()
+        .zip(&record.fields)
+        .map(|(column, field)| Ok(array_values(column, &field.schema)?.into_iter()))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut records = Vec::with_capacity(num_rows);
+    for _ in 0..num_rows {
+        let fields = columns
+            .iter_mut()
+            .zip(&record.fields)
+            .map(|(values, field)| {
+                (field.name.clone(), values.next().unwrap_or(Value::Null))
+            })
+            .collect();
+        records.push(Value::Record(fields));
+    }
+    Ok(records)
+}
+
+/// Converts every slot of `array` into an Avro value of the given schema.
+///
+/// Null slots of non nullable schemas are returned as [`Value::Null`]; they
+/// only occur below a null parent, whose value replaces them.
+fn array_values(array: &ArrayRef, schema: &AvroSchema) -> Result<Vec<Value>> {
+    if let AvroSchema::Union(union) = schema {
+        let values = array_values(array, &union.variants()[1])?;
+        return Ok(values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                if array.is_null(i) {
+                    Value::Union(0, Box::new(Value::Null))
+                } else {
+                    Value::Union(1, Box::new(value))
+                }
+            })
+            .collect());
+    }
+
+    let values = match (array.data_type(), schema) {
+        (DataType::Null, _) => vec![Value::Null; array.len()],
+        (DataType::Boolean, _) => array
+            .as_boolean()
+            .iter()
+            .map(|v| v.map(Value::Boolean).unwrap_or(Value::Null))
+            .collect(),
+        (DataType::Int8, _) => {
+            primitive::<Int8Type>(array, |v| Ok(Value::Int(v as i32)))?
+        }
+        (DataType::Int16, _) => {
+            primitive::<Int16Type>(array, |v| Ok(Value::Int(v as i32)))?
+        }
+        (DataType::Int32, _) => primitive::<Int32Type>(array, |v| Ok(Value::Int(v)))?,
+        (DataType::Int64, _) => primitive::<Int64Type>(array, |v| Ok(Value::Long(v)))?,
+        (DataType::UInt8, _) => {
+            primitive::<UInt8Type>(array, |v| Ok(Value::Int(v as i32)))?
+        }
+        (DataType::UInt16, _) => {
+            primitive::<UInt16Type>(array, |v| Ok(Value::Int(v as i32)))?
+        }
+        (DataType::UInt32, _) => {
+            primitive::<UInt32Type>(array, |v| Ok(Value::Long(v as i64)))?
+        }
+        (DataType::UInt64, _) => {
+            primitive::<UInt64Type>(array, |v| match i64::try_from(v) {
+                Ok(v) => Ok(Value::Long(v)),
+                Err(_) => exec_err!("UInt64 value {v} does not fit into an Avro long"),
+            })?
+        }
+        (DataType::Float16, _) => {
+            primitive::<Float16Type>(array, |v| Ok(Value::Float(v.to_f32())))?
+        }
+        (DataType::Float32, _) => {
+            primitive::<Float32Type>(array, |v| Ok(Value::Float(v)))?
+        }
+        (DataType::Float64, _) => {
+            primitive::<Float64Type>(array, |v| Ok(Value::Double(v)))?
+        }
+        (DataType::Utf8, _) => strings(array.as_string::<i32>().iter()),
+        (DataType::LargeUtf8, _) => strings(array.as_string::<i64>().iter()),
+        (DataType::Utf8View, _) => strings(array.as_string_view().iter()),
+        (DataType::Binary, _) => binaries(array.as_binary::<i32>().iter()),
+        (DataType::LargeBinary, _) => binaries(array.as_binary::<i64>().iter()),
+        (DataType::BinaryView, _) => binaries(array.as_binary_view().iter()),
+        (DataType::FixedSizeBinary(size), _) => array
+            .as_fixed_size_binary()
+            .iter()
+            .map(|v| {
+                v.map(|v| Value::Fixed(*size as usize, v.to_vec()))
+                    .unwrap_or(Value::Null)
+            })
+            .collect(),
+        (DataType::Decimal128(_, _), _) => {
+            primitive::<Decimal128Type>(array, |v| Ok(decimal_value(&v.to_be_bytes())))?
+        }
+        (DataType::Decimal256(_, _), _) => {
+            primitive::<Decimal256Type>(array, |v: i256| {
+                Ok(decimal_value(&v.to_be_bytes()))
+            })?
+        }
+        (DataType::Date32, _) => primitive::<Date32Type>(array, |v| Ok(Value::Date(v)))?,
+        (DataType::Date64, _) => primitive::<Date64Type>(array, |v| {
+            Ok(Value::Date(v.div_euclid(MILLIS_PER_DAY) as i32))
+        })?,
+        (DataType::Time32(TimeUnit::Second), _) => {
+            primitive::<Time32SecondType>(array, |v| {
+                Ok(Value::TimeMillis(checked_scale(v as i64, 1000)? as i32))
+            })?
+        }
+        (DataType::Time32(_), _) => {
+            primitive::<Time32MillisecondType>(array, |v| Ok(Value::TimeMillis(v)))?
+        }
+        (DataType::Time64(TimeUnit::Nanosecond), _) => {
+            primitive::<Time64NanosecondType>(array, |v| {
+                Ok(Value::TimeMicros(v.div_euclid(1000)))
+            })?
+        }
+        (DataType::Time64(_), _) => {
+            primitive::<Time64MicrosecondType>(array, |v| Ok(Value::TimeMicros(v)))?
+        }
+        (DataType::Timestamp(unit, tz), _) => {
+            let utc = tz.is_some();
+            let millis = move |v: i64| {
+                if utc {
+                    Value::TimestampMillis(v)
+                } else {
+                    Value::LocalTimestampMillis(v)
+                }
+            };
+            let micros = move |v: i64| {
+                if utc {
+                    Value::TimestampMicros(v)
+                } else {
+                    Value::LocalTimestampMicros(v)
+                }
+            };
+            match unit {
+                TimeUnit::Second => primitive::<TimestampSecondType>(array, |v| {
+                    Ok(millis(checked_scale(v, 1000)?))
+                })?,
+                TimeUnit::Millisecond => {
+                    primitive::<TimestampMillisecondType>(array, |v| Ok(millis(v)))?
+                }
+                TimeUnit::Microsecond => {
+                    primitive::<TimestampMicrosecondType>(array, |v| Ok(micros(v)))?
+                }
+                TimeUnit::Nanosecond => {
+                    primitive::<TimestampNanosecondType>(array, |v| {
+                        Ok(micros(v.div_euclid(1000)))
+                    })?
+                }
+            }
+        }
+        (DataType::List(_), AvroSchema::Array(items)) => array
+            .as_list::<i32>()
+            .iter()
+            .map(|v| list_value(v, items))
+            .collect::<Result<_>>()?,
+        (DataType::LargeList(_), AvroSchema::Array(items)) => array
+            .as_list::<i64>()
+            .iter()
+            .map(|v| list_value(v, items))
+            .collect::<Result<_>>()?,
+        (DataType::FixedSizeList(_, _), AvroSchema::Array(items)) => array
+            .as_fixed_size_list()
+            .iter()
+            .map(|v| list_value(v, items))
+            .collect::<Result<_>>()?,
+        (DataType::Struct(_), AvroSchema::Record(record)) => {
+            record_values(array.as_struct().columns(), record, array.len())?
+        }
+        (DataType::Map(_, _), AvroSchema::Map(value_schema)) => {
+            let map = array.as_map();
+            let keys = cast(map.keys(), &DataType::Utf8)?;
+            let offsets = map.value_offsets();
+            let first = offsets[0] as usize;
+            let mut keys = keys.as_string::<i32>().iter().skip(first);
+            let mut values = array_values(map.values(), value_schema)?
+                .into_iter()
+                .skip(first);
+
+            let mut result = Vec::with_capacity(map.len());
+            for window in offsets.windows(2) {
+                let len = (window[1] - window[0]) as usize;
+                let entries = keys
+                    .by_ref()
+                    .take(len)
+                    .zip(values.by_ref())
+                    .map(|(key, value)| (key.unwrap_or_default().to_string(), value))
+                    .collect::<HashMap<_, _>>();
+                result.push(Value::Map(entries));
+            }
+            result
+        }
+        (DataType::Dictionary(_, value_type), _) => {
+            array_values(&cast(array, value_type)?, schema)?
+        }
+        (data_type, _) => {
+            return internal_err!(
+                "Cannot convert {data_type} values to Avro schema {schema:?}"
+            )
+        }
+    };
+    Ok(values)
+}
+
+fn primitive<T: ArrowPrimitiveType>(
+    array: &ArrayRef,
+    f: impl Fn(T::Native) -> Result<Value>,
+) -> Result<Vec<Value>> {
+    array
+        .as_primitive::<T>()
+        .iter()
+        .map(|v| v.map(&f).unwrap_or(Ok(Value::Null)))
+        .collect()
+}
+
+fn strings<'a>(iter: impl Iterator<Item = Option<&'a str>>) -> Vec<Value> {
+    iter.map(|v| {
+        v.map(|v| Value::String(v.to_string()))
+            .unwrap_or(Value::Null)
+    })
+    .collect()
+}
+
+fn binaries<'a>(iter: impl Iterator<Item = Option<&'a [u8]>>) -> Vec<Value> {
+    iter.map(|v| v.map(|v| Value::Bytes(v.to_vec())).unwrap_or(Value::Null))
+        .collect()
+}
+
+fn list_value(list: Option<ArrayRef>, items: &AvroSchema) -> Result<Value> {
+    match list {
+        Some(list) => Ok(Value::Array(array_values(&list, items)?)),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Encodes a big-endian two's complement integer as a decimal value using the
+/// minimum number of bytes
+fn decimal_value(bytes: &[u8]) -> Value {
+    let redundant = bytes
+        .windows(2)
+        .take_while(|w| {
+            (w[0] == 0x00 && w[1] & 0x80 == 0) || (w[0] == 0xFF && w[1] & 0x80 != 0)
+        })
+        .count();
+    Value::Decimal(Decimal::from(bytes[redundant..].to_vec()))
+}
+
+fn checked_scale(value: i64, factor: i64) -> Result<i64> {
+    match value.checked_mul(factor) {
+        Some(v) => Ok(v),
+        None => exec_err!("Value {value} overflows when converted for Avro"),
+    }
+}
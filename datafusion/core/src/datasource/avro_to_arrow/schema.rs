@@ -150,8 +150,12 @@ fn schema_to_field_with_props(
         AvroSchema::TimeMicros => DataType::Time64(TimeUnit::Microsecond),
         AvroSchema::TimestampMillis => DataType::Timestamp(TimeUnit::Millisecond, None),
         AvroSchema::TimestampMicros => DataType::Timestamp(TimeUnit::Microsecond, None),
-        AvroSchema::LocalTimestampMillis => todo!(),
-        AvroSchema::LocalTimestampMicros => todo!(),
+        AvroSchema::LocalTimestampMillis => {
+            DataType::Timestamp(TimeUnit::Millisecond, None)
+        }
+        AvroSchema::LocalTimestampMicros => {
+            DataType::Timestamp(TimeUnit::Microsecond, None)
+        }
         AvroSchema::Duration => DataType::Duration(TimeUnit::Millisecond),
     };
 
@@ -25,18 +25,21 @@ use std::sync::Arc;
 use arrow::datatypes::Schema;
 use arrow::datatypes::SchemaRef;
 use async_trait::async_trait;
+use datafusion_common::config::{AvroOptions, ConfigField, ConfigFileType};
+use datafusion_common::not_impl_err;
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::DataFusionError;
 use datafusion_common::GetExt;
 use datafusion_common::DEFAULT_AVRO_EXTENSION;
 use datafusion_physical_expr::PhysicalExpr;
+use datafusion_physical_expr_common::sort_expr::LexRequirement;
 use object_store::{GetResultPayload, ObjectMeta, ObjectStore};
 
 use super::file_compression_type::FileCompressionType;
 use super::FileFormat;
 use super::FileFormatFactory;
 use crate::datasource::avro_to_arrow::read_avro_schema_from_reader;
-use crate::datasource::physical_plan::{AvroExec, FileScanConfig};
+use crate::datasource::physical_plan::{AvroExec, FileScanConfig, FileSinkConfig};
 use crate::error::Result;
 use crate::execution::context::SessionState;
 use crate::physical_plan::ExecutionPlan;
@@ -44,26 +47,52 @@ use crate::physical_plan::Statistics;
 
 #[derive(Default)]
 /// Factory struct used to create [AvroFormat]
-pub struct AvroFormatFactory;
+pub struct AvroFormatFactory {
+    /// the options carried by format factory
+    pub options: Option<AvroOptions>,
+}
 
 impl AvroFormatFactory {
     /// Creates an instance of [AvroFormatFactory]
     pub fn new() -> Self {
-        Self {}
+        Self { options: None }
+    }
+
+    /// Creates an instance of [AvroFormatFactory] with customized default options
+    pub fn new_with_options(options: AvroOptions) -> Self {
+        Self {
+            options: Some(options),
+        }
     }
 }
 
 impl FileFormatFactory for AvroFormatFactory {
     fn create(
         &self,
-        _state: &SessionState,
-        _format_options: &HashMap<String, String>,
+        state: &SessionState,
+        format_options: &HashMap<String, String>,
     ) -> Result<Arc<dyn FileFormat>> {
-        Ok(Arc::new(AvroFormat))
+        let avro_options = match &self.options {
+            None => {
+                let mut table_options = state.default_table_options();
+                table_options.set_config_format(ConfigFileType::AVRO);
+                table_options.alter_with_string_hash_map(format_options)?;
+                table_options.avro
+            }
+            Some(avro_options) => {
+                let mut avro_options = avro_options.clone();
+                for (k, v) in format_options {
+                    avro_options.set(k, v)?;
+                }
+                avro_options
+            }
+        };
+
+        Ok(Arc::new(AvroFormat::default().with_options(avro_options)))
     }
 
     fn default(&self) -> Arc<dyn FileFormat> {
-        Arc::new(AvroFormat)
+        Arc::new(AvroFormat::default())
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -86,7 +115,29 @@ impl GetExt for AvroFormatFactory {
 
 /// Avro `FileFormat` implementation.
 #[derive(Default, Debug)]
-pub struct AvroFormat;
+pub struct AvroFormat {
+    options: AvroOptions,
+}
+
+impl AvroFormat {
+    /// Set Avro options
+    pub fn with_options(mut self, options: AvroOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Retrieve Avro options
+    pub fn options(&self) -> &AvroOptions {
+        &self.options
+    }
+
+    /// Set the block compression codec used when writing, see
+    /// [`AvroOptions::compression`] for the supported values
+    pub fn with_compression(mut self, compression: impl Into<String>) -> Self {
+        self.options.compression = compression.into();
+        self
+    }
+}
 
 #[async_trait]
 impl FileFormat for AvroFormat {
@@ -155,6 +206,189 @@ impl FileFormat for AvroFormat {
         let exec = AvroExec::new(conf);
         Ok(Arc::new(exec))
     }
+
+    #[cfg(feature = "avro")]
+    async fn create_writer_physical_plan(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        _state: &SessionState,
+        conf: FileSinkConfig,
+        order_requirements: Option<LexRequirement>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        use crate::physical_plan::insert::DataSinkExec;
+        use datafusion_common::file_options::avro_writer::AvroWriterOptions;
+        use datafusion_expr::dml::InsertOp;
+
+        if conf.insert_op == InsertOp::Replace {
+            return not_impl_err!("{} is not implemented yet for Avro", conf.insert_op);
+        }
+
+        let writer_options = AvroWriterOptions::try_from(&self.options)?;
+
+        let sink_schema = conf.output_schema().clone();
+        let sink = Arc::new(AvroSink::new(conf, writer_options));
+
+        Ok(Arc::new(DataSinkExec::new(
+            input,
+            sink,
+            sink_schema,
+            order_requirements,
+        )) as _)
+    }
+
+    #[cfg(not(feature = "avro"))]
+    async fn create_writer_physical_plan(
+        &self,
+        _input: Arc<dyn ExecutionPlan>,
+        _state: &SessionState,
+        _conf: FileSinkConfig,
+        _order_requirements: Option<LexRequirement>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Writing Avro files requires the 'avro' feature to be enabled")
+    }
+}
+
+#[cfg(feature = "avro")]
+pub use sink::{AvroSerializer, AvroSink};
+
+#[cfg(feature = "avro")]
+mod sink {
+    use std::any::Any;
+    use std::fmt::{self, Debug};
+    use std::sync::Arc;
+
+    use arrow::array::RecordBatch;
+    use bytes::Bytes;
+    use datafusion_common::file_options::avro_writer::{
+        AvroCompression, AvroWriterOptions,
+    };
+    use datafusion_execution::TaskContext;
+    use datafusion_physical_plan::metrics::MetricsSet;
+
+    use crate::datasource::arrow_to_avro::Writer;
+    use crate::datasource::file_format::file_compression_type::FileCompressionType;
+    use crate::datasource::file_format::write::orchestration::stateless_multipart_put;
+    use crate::datasource::file_format::write::BatchSerializer;
+    use crate::datasource::physical_plan::{FileGroupDisplay, FileSinkConfig};
+    use crate::error::Result;
+    use crate::physical_plan::insert::DataSink;
+    use crate::physical_plan::{DisplayAs, DisplayFormatType, SendableRecordBatchStream};
+
+    /// Serializes record batches into the blocks of an Avro object container file
+    pub struct AvroSerializer {
+        writer: Writer,
+    }
+
+    impl AvroSerializer {
+        /// Constructor for the AvroSerializer object, compressing data blocks
+        /// with the given codec
+        pub fn new(compression: AvroCompression) -> Self {
+            let codec = match compression {
+                AvroCompression::Uncompressed => apache_avro::Codec::Null,
+                AvroCompression::Deflate => apache_avro::Codec::Deflate,
+                AvroCompression::Snappy => apache_avro::Codec::Snappy,
+                AvroCompression::Zstd => apache_avro::Codec::Zstandard,
+                AvroCompression::Bzip2 => apache_avro::Codec::Bzip2,
+                AvroCompression::Xz => apache_avro::Codec::Xz,
+            };
+            Self {
+                writer: Writer::new(codec),
+            }
+        }
+    }
+
+    impl BatchSerializer for AvroSerializer {
+        fn serialize(&self, batch: RecordBatch, initial: bool) -> Result<Bytes> {
+            let mut buffer = Vec::with_capacity(4096);
+            if initial {
+                self.writer.write_header(&batch.schema(), &mut buffer)?;
+            }
+            self.writer.write_batch(&batch, &mut buffer)?;
+            Ok(Bytes::from(buffer))
+        }
+    }
+
+    /// Implements [`DataSink`] for writing to an Avro file.
+    pub struct AvroSink {
+        /// Config options for writing data
+        config: FileSinkConfig,
+        /// Writer options for underlying Avro writer
+        writer_options: AvroWriterOptions,
+    }
+
+    impl Debug for AvroSink {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("AvroSink").finish()
+        }
+    }
+
+    impl DisplayAs for AvroSink {
+        fn fmt_as(
+            &self,
+            t: DisplayFormatType,
+            f: &mut fmt::Formatter<'_>,
+        ) -> fmt::Result {
+            match t {
+                DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                    write!(f, "AvroSink(file_groups=",)?;
+                    FileGroupDisplay(&self.config.file_groups).fmt_as(t, f)?;
+                    write!(f, ")")
+                }
+            }
+        }
+    }
+
+    impl AvroSink {
+        /// Create from config.
+        pub fn new(config: FileSinkConfig, writer_options: AvroWriterOptions) -> Self {
+            Self {
+                config,
+                writer_options,
+            }
+        }
+
+        /// Retrieve the inner [`FileSinkConfig`].
+        pub fn config(&self) -> &FileSinkConfig {
+            &self.config
+        }
+
+        /// Retrieve the writer options
+        pub fn writer_options(&self) -> &AvroWriterOptions {
+            &self.writer_options
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl DataSink for AvroSink {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn metrics(&self) -> Option<MetricsSet> {
+            None
+        }
+
+        async fn write_all(
+            &self,
+            data: SendableRecordBatchStream,
+            context: &Arc<TaskContext>,
+        ) -> Result<u64> {
+            let compression = self.writer_options.compression;
+            let get_serializer = move || Arc::new(AvroSerializer::new(compression)) as _;
+
+            // Avro compresses the data blocks itself, the file is never
+            // compressed as a whole
+            stateless_multipart_put(
+                data,
+                context,
+                "avro".into(),
+                Box::new(get_serializer),
+                &self.config,
+                FileCompressionType::UNCOMPRESSED,
+            )
+            .await
+        }
+    }
 }
 
 #[cfg(test)]
@@ -162,9 +396,11 @@ impl FileFormat for AvroFormat {
 mod tests {
     use super::*;
     use crate::datasource::file_format::test_util::scan_format;
+    use crate::datasource::file_format::write::BatchSerializer;
     use crate::physical_plan::collect;
     use crate::prelude::{SessionConfig, SessionContext};
     use arrow::array::{as_string_array, Array};
+    use datafusion_common::assert_batches_eq;
     use datafusion_common::cast::{
         as_binary_array, as_boolean_array, as_float32_array, as_float64_array,
         as_int32_array, as_timestamp_microsecond_array,
@@ -501,6 +737,111 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn write_read_roundtrip() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        for compression in ["uncompressed", "deflate", "snappy", "zstd"] {
+            let ctx = SessionContext::new();
+            let path = tmp_dir.path().join(format!("{compression}.avro"));
+            let path = path.to_str().unwrap();
+            ctx.sql(&format!(
+                "COPY (SELECT * FROM (VALUES \
+                    (1, 'a', [1, 2], named_struct('x', 1.5, 'y', 'foo'), \
+                     arrow_cast('2024-01-02T03:04:05.123456789', 'Timestamp(Nanosecond, None)'), \
+                     DATE '2024-01-02'), \
+                    (2, NULL, [], NULL, NULL, NULL)) \
+                 AS t(id, name, tags, point, ts, day)) \
+                 TO '{path}' STORED AS AVRO OPTIONS ('format.compression' '{compression}')"
+            ))
+            .await?
+            .collect()
+            .await?;
+
+            let batches = ctx
+                .read_avro(path, Default::default())
+                .await?
+                .collect()
+                .await?;
+            assert_batches_eq!(
+                &[
+                    "+----+------+--------+------------------+----------------------------+------------+",
+                    "| id | name | tags   | point            | ts                         | day        |",
+                    "+----+------+--------+------------------+----------------------------+------------+",
+                    "| 1  | a    | [1, 2] | {x: 1.5, y: foo} | 2024-01-02T03:04:05.123456 | 2024-01-02 |",
+                    "| 2  |      | []     |                  |                            |            |",
+                    "+----+------+--------+------------------+----------------------------+------------+",
+                ],
+                &batches
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_decimal_and_timestamps() -> Result<()> {
+        use arrow::array::{Decimal128Array, TimestampSecondArray};
+        use arrow::datatypes::{DataType, Field, TimeUnit};
+        use arrow::record_batch::RecordBatch;
+        use datafusion_common::file_options::avro_writer::AvroCompression;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("amount", DataType::Decimal128(10, 2), true),
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Second, Some("+01:00".into())),
+                false,
+            ),
+        ]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(
+                    Decimal128Array::from(vec![Some(12345), None, Some(-1)])
+                        .with_precision_and_scale(10, 2)?,
+                ),
+                Arc::new(
+                    TimestampSecondArray::from(vec![1, 2, 3]).with_timezone("+01:00"),
+                ),
+            ],
+        )?;
+
+        let serializer = AvroSerializer::new(AvroCompression::Deflate);
+        let mut bytes = serializer.serialize(batch.clone(), true)?.to_vec();
+        bytes.extend(serializer.serialize(batch.slice(1, 2), false)?);
+
+        let reader = apache_avro::Reader::new(bytes.as_slice())?;
+        let values = reader
+            .map(|record| match record? {
+                apache_avro::types::Value::Record(fields) => Ok(fields),
+                other => panic!("expected record, got {other:?}"),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(values.len(), 5);
+
+        use apache_avro::types::Value;
+        use apache_avro::Decimal;
+        let expected = |amount: Option<Vec<u8>>, ts: i64| {
+            vec![
+                (
+                    "amount".to_string(),
+                    match amount {
+                        Some(bytes) => Value::Union(
+                            1,
+                            Box::new(Value::Decimal(Decimal::from(bytes))),
+                        ),
+                        None => Value::Union(0, Box::new(Value::Null)),
+                    },
+                ),
+                ("ts".to_string(), Value::TimestampMillis(ts)),
+            ]
+        };
+        assert_eq!(values[0], expected(Some(vec![0x30, 0x39]), 1000));
+        assert_eq!(values[1], expected(None, 2000));
+        assert_eq!(values[2], expected(Some(vec![0xFF]), 3000));
+        assert_eq!(values[4], expected(Some(vec![0xFF]), 3000));
+        Ok(())
+    }
+
     async fn get_exec(
         state: &SessionState,
         file_name: &str,
@@ -509,7 +850,7 @@ mod tests {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let testdata = crate::test_util::arrow_test_data();
         let store_root = format!("{testdata}/avro");
-        let format = AvroFormat {};
+        let format = AvroFormat::default();
         scan_format(state, &format, &store_root, file_name, projection, limit).await
     }
 }
@@ -527,7 +868,7 @@ mod tests {
     async fn test() -> Result<()> {
         let session_ctx = SessionContext::new();
         let state = session_ctx.state();
-        let format = AvroFormat {};
+        let format = AvroFormat::default();
         let testdata = crate::test_util::arrow_test_data();
         let filename = "avro/alltypes_plain.avro";
         let result = scan_format(&state, &format, &testdata, filename, None, None).await;
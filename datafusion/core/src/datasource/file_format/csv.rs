@@ -18,16 +18,20 @@
 //! [`CsvFormat`], Comma Separated Value (CSV) [`FileFormat`] abstractions
 
 use std::any::Any;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::fmt::{self, Debug};
 use std::sync::Arc;
 
+use super::schema_inference::{
+    merge_inferred_schemas, read_sample_range, sample_ranges, sampled_bytes, widen,
+    SampleCoverage,
+};
 use super::write::orchestration::stateless_multipart_put;
 use super::{FileFormat, FileFormatFactory};
 use crate::datasource::file_format::file_compression_type::FileCompressionType;
 use crate::datasource::file_format::write::BatchSerializer;
 use crate::datasource::physical_plan::{
-    CsvExec, FileGroupDisplay, FileScanConfig, FileSinkConfig,
+    CsvExec, CsvTranscoder, FileGroupDisplay, FileScanConfig, FileSinkConfig, LineFormat,
 };
 use crate::error::Result;
 use crate::execution::context::SessionState;
@@ -36,12 +40,15 @@ use crate::physical_plan::{
     DisplayAs, DisplayFormatType, ExecutionPlan, SendableRecordBatchStream, Statistics,
 };
 
-use arrow::array::RecordBatch;
+use arrow::array::{AsArray, RecordBatch};
 use arrow::csv::WriterBuilder;
 use arrow::datatypes::SchemaRef;
-use arrow::datatypes::{DataType, Field, Fields, Schema};
+use arrow::datatypes::{
+    DataType, Field, Fields, Schema, TimeUnit, DECIMAL128_MAX_PRECISION,
+};
 use datafusion_common::config::{ConfigField, ConfigFileType, CsvOptions};
 use datafusion_common::file_options::csv_writer::CsvWriterOptions;
+use datafusion_common::parsers::{OnError, SchemaInferStrategy};
 use datafusion_common::{
     exec_err, not_impl_err, DataFusionError, GetExt, DEFAULT_CSV_EXTENSION,
 };
@@ -52,6 +59,7 @@ use datafusion_physical_plan::metrics::MetricsSet;
 
 use async_trait::async_trait;
 use bytes::{Buf, Bytes};
+use chrono::{NaiveDate, NaiveDateTime};
 use datafusion_physical_expr_common::sort_expr::LexRequirement;
 use futures::stream::BoxStream;
 use futures::{pin_mut, Stream, StreamExt, TryStreamExt};
@@ -208,6 +216,22 @@ impl CsvFormat {
         self
     }
 
+    /// Set how the records to infer the schema from are chosen
+    /// - defaults to [`SchemaInferStrategy::Head`], the first
+    ///   `schema_infer_max_rec` records of the files
+    pub fn with_schema_infer_strategy(mut self, strategy: SchemaInferStrategy) -> Self {
+        self.options.schema_infer_strategy = strategy;
+        self
+    }
+
+    /// Set the number of byte ranges of each file sampled with
+    /// [`SchemaInferStrategy::Sample`]
+    /// - defaults to 4
+    pub fn with_schema_infer_sample_ranges(mut self, ranges: usize) -> Self {
+        self.options.schema_infer_sample_ranges = ranges;
+        self
+    }
+
     /// Set true to indicate that the first line is a header.
     /// - default to true
     pub fn with_has_header(mut self, has_header: bool) -> Self {
@@ -234,6 +258,14 @@ impl CsvFormat {
         self
     }
 
+    /// A sequence of multiple bytes separating values within a row, such
+    /// as `||`, used instead of the delimiter character.
+    /// - default to None
+    pub fn with_delimiter_sequence(mut self, delimiter_sequence: Option<String>) -> Self {
+        self.options.delimiter_sequence = delimiter_sequence;
+        self
+    }
+
     /// The quote character in a row.
     /// - default to '"'
     pub fn with_quote(mut self, quote: u8) -> Self {
@@ -317,24 +349,107 @@ impl FileFormat for CsvFormat {
         store: &Arc<dyn ObjectStore>,
         objects: &[ObjectMeta],
     ) -> Result<SchemaRef> {
-        let mut schemas = vec![];
-
-        let mut records_to_read = self.options.schema_infer_max_rec;
+        let has_header = self.has_header_or_default(state);
+        let newlines_in_values = self
+            .options
+            .newlines_in_values
+            .unwrap_or(state.config_options().catalog.newlines_in_values);
+        let compression: FileCompressionType = self.options.compression.into();
+        let splittable = !compression.is_compressed() && !newlines_in_values;
 
-        for object in objects {
-            let stream = self.read_to_delimited_chunks(store, object).await;
-            let (schema, records_read) = self
-                .infer_schema_from_stream(state, records_to_read, stream)
-                .await?;
-            records_to_read -= records_read;
-            schemas.push(schema);
-            if records_to_read == 0 {
-                break;
+        let mut schemas = vec![];
+        let mut coverage = SampleCoverage::new(objects);
+        let ranges = sample_ranges(
+            self.options.schema_infer_strategy,
+            objects,
+            self.options.schema_infer_max_rec,
+            self.options.schema_infer_sample_ranges,
+            splittable,
+        );
+        match ranges {
+            None => {
+                let mut records_to_read = self.options.schema_infer_max_rec;
+                for (i, object) in objects.iter().enumerate() {
+                    let stream = self.read_to_delimited_chunks(store, object).await;
+                    let sample = self
+                        .infer_schema_from_chunks(has_header, records_to_read, stream)
+                        .await?;
+                    records_to_read -= sample.records;
+                    coverage.add(sample.bytes, sample.complete);
+                    schemas.push(sample.schema);
+                    if records_to_read == 0 {
+                        if i + 1 < objects.len() {
+                            coverage.skip_files();
+                        }
+                        break;
+                    }
+                }
+            }
+            Some(ranges) => {
+                for (object, ranges) in objects.iter().zip(ranges) {
+                    let mut column_names: Option<Vec<String>> = None;
+                    for range in ranges {
+                        let stream = if range.start == 0 && range.len >= object.size {
+                            self.read_to_delimited_chunks(store, object).await
+                        } else {
+                            read_sample_range(store, object, range).await?
+                        };
+                        let sample = self
+                            .infer_schema_from_chunks(
+                                has_header && range.start == 0,
+                                range.records,
+                                stream,
+                            )
+                            .await?;
+                        coverage.add(sample.bytes, sample.complete);
+                        // Ranges after the first have no header, their columns
+                        // are named after those of the first range
+                        let schema = match &column_names {
+                            None => {
+                                column_names = Some(
+                                    sample
+                                        .schema
+                                        .fields()
+                                        .iter()
+                                        .map(|field| field.name().clone())
+                                        .collect(),
+                                );
+                                sample.schema
+                            }
+                            Some(_) if sample.records == 0 => continue,
+                            Some(names) => {
+                                if names.len() != sample.schema.fields().len() {
+                                    return exec_err!(
+                                        "Encountered unequal lengths between records on CSV file {} whilst inferring schema. \
+                                         Expected {} records, found {} records",
+                                        object.location,
+                                        names.len(),
+                                        sample.schema.fields().len()
+                                    );
+                                }
+                                Schema::new(
+                                    names
+                                        .iter()
+                                        .zip(sample.schema.fields())
+                                        .map(|(name, field)| {
+                                            Field::new(
+                                                name,
+                                                field.data_type().clone(),
+                                                true,
+                                            )
+                                        })
+                                        .collect::<Fields>(),
+                                )
+                            }
+                        };
+                        schemas.push(schema);
+                    }
+                }
             }
         }
 
-        let merged_schema = Schema::try_merge(schemas)?;
-        Ok(Arc::new(merged_schema))
+        let merged_schema = merge_inferred_schemas(schemas);
+        Ok(Arc::new(coverage.annotate(merged_schema)))
     }
 
     async fn infer_stats(
@@ -363,14 +478,32 @@ impl FileFormat for CsvFormat {
             .newlines_in_values
             .unwrap_or(state.config_options().catalog.newlines_in_values);
 
+        if newlines_in_values && self.options.on_error != OnError::Fail {
+            return not_impl_err!(
+                "on_error '{}' is not supported for CSV files with newlines in values",
+                self.options.on_error
+            );
+        }
+        if newlines_in_values && self.options.delimiter_sequence.is_some() {
+            return not_impl_err!(
+                "delimiter_sequence is not supported for CSV files with newlines in values"
+            );
+        }
+
         let exec = CsvExec::builder(conf)
             .with_has_header(has_header)
             .with_delimeter(self.options.delimiter)
+            .with_delimiter_sequence(self.options.delimiter_sequence.clone())
             .with_quote(self.options.quote)
             .with_terminator(self.options.terminator)
             .with_escape(self.options.escape)
             .with_comment(self.options.comment)
             .with_newlines_in_values(newlines_in_values)
+            .with_date_format(self.options.date_format.clone())
+            .with_timestamp_format(self.options.timestamp_format.clone())
+            .with_on_error(self.options.on_error)
+            .with_max_errors(self.options.max_errors)
+            .with_bad_records_path(self.options.bad_records_path.clone())
             .with_file_compression_type(self.options.compression.into())
             .build();
         Ok(Arc::new(exec))
@@ -383,8 +516,11 @@ impl FileFormat for CsvFormat {
         conf: FileSinkConfig,
         order_requirements: Option<LexRequirement>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        if conf.insert_op != InsertOp::Append {
-            return not_impl_err!("Overwrites are not implemented yet for CSV");
+        if conf.insert_op == InsertOp::Replace {
+            return not_impl_err!("{} is not implemented yet for CSV", conf.insert_op);
+        }
+        if self.options.delimiter_sequence.is_some() {
+            return not_impl_err!("Writing CSV files with a delimiter_sequence");
         }
 
         // `has_header` and `newlines_in_values` fields of CsvOptions may inherit
@@ -420,115 +556,266 @@ impl FileFormat for CsvFormat {
     }
 }
 
+/// A schema inferred from a part of a CSV file
+struct CsvSample {
+    schema: Schema,
+    /// The number of records read
+    records: usize,
+    /// The estimated number of bytes read
+    bytes: usize,
+    /// Whether all the records of the part were read
+    complete: bool,
+}
+
 impl CsvFormat {
+    /// Returns the [`CsvTranscoder`] converting the values separated by the
+    /// delimiter sequence to comma separated values, if set
+    fn transcoder(&self) -> Option<CsvTranscoder> {
+        let delimiter = self.options.delimiter_sequence.as_ref()?;
+        let format = LineFormat::Delimited {
+            delimiter: delimiter.as_bytes().to_vec(),
+            escape: self.options.escape,
+        };
+        Some(CsvTranscoder::new(format, self.options.quote))
+    }
+
+    fn has_header_or_default(&self, state: &SessionState) -> bool {
+        self.options
+            .has_header
+            .unwrap_or(state.config_options().catalog.has_header)
+    }
+
     /// Return the inferred schema reading up to records_to_read from a
-    /// stream of delimited chunks returning the inferred schema and the
-    /// number of lines that were read
-    async fn infer_schema_from_stream(
+    /// stream of delimited chunks returning the inferred schema, the
+    /// number of lines that were read and the estimated number of bytes
+    async fn infer_schema_from_chunks(
         &self,
-        state: &SessionState,
+        has_header: bool,
         mut records_to_read: usize,
         stream: impl Stream<Item = Result<Bytes>>,
-    ) -> Result<(Schema, usize)> {
+    ) -> Result<CsvSample> {
         let mut total_records_read = 0;
+        let mut total_bytes_read = 0;
         let mut column_names = vec![];
-        let mut column_type_possibilities = vec![];
+        let mut column_types: Vec<Option<DataType>> = vec![];
         let mut first_chunk = true;
+        let mut complete = true;
 
         pin_mut!(stream);
 
-        while let Some(chunk) = stream.next().await.transpose()? {
+        while let Some(read) = stream.next().await.transpose()? {
+            let (chunk, delimiter) = match self.transcoder() {
+                Some(mut transcoder) => {
+                    let mut out = vec![];
+                    transcoder.transcode(&read, &mut out);
+                    transcoder.finish(&mut out);
+                    (Bytes::from(out), CsvTranscoder::DELIMITER)
+                }
+                None => (read.clone(), self.options.delimiter),
+            };
             let mut format = arrow::csv::reader::Format::default()
-                .with_header(
-                    first_chunk
-                        && self
-                            .options
-                            .has_header
-                            .unwrap_or(state.config_options().catalog.has_header),
-                )
-                .with_delimiter(self.options.delimiter);
+                .with_header(first_chunk && has_header)
+                .with_delimiter(delimiter);
 
             if let Some(comment) = self.options.comment {
                 format = format.with_comment(comment);
             }
 
             let (Schema { fields, .. }, records_read) =
-                format.infer_schema(chunk.reader(), Some(records_to_read))?;
+                format.infer_schema(chunk.clone().reader(), Some(records_to_read))?;
+            let fields =
+                self.refine_inferred_types(&format, &chunk, fields, records_read)?;
 
             records_to_read -= records_read;
             total_records_read += records_read;
+            total_bytes_read += sampled_bytes(&read, records_read);
 
             if first_chunk {
                 // set up initial structures for recording inferred schema across chunks
-                (column_names, column_type_possibilities) = fields
-                    .into_iter()
+                (column_names, column_types) = fields
+                    .iter()
                     .map(|field| {
-                        let mut possibilities = HashSet::new();
-                        if records_read > 0 {
-                            // at least 1 data row read, record the inferred datatype
-                            possibilities.insert(field.data_type().clone());
-                        }
-                        (field.name().clone(), possibilities)
+                        // only record the inferred datatype if at least 1 data row was read
+                        let data_type =
+                            (records_read > 0).then(|| field.data_type().clone());
+                        (field.name().clone(), data_type)
                     })
                     .unzip();
                 first_chunk = false;
             } else {
-                if fields.len() != column_type_possibilities.len() {
+                if fields.len() != column_types.len() {
                     return exec_err!(
                             "Encountered unequal lengths between records on CSV file whilst inferring schema. \
                              Expected {} records, found {} records",
-                            column_type_possibilities.len(),
+                            column_types.len(),
                             fields.len()
                         );
                 }
 
-                column_type_possibilities.iter_mut().zip(&fields).for_each(
-                    |(possibilities, field)| {
-                        possibilities.insert(field.data_type().clone());
-                    },
-                );
+                column_types
+                    .iter_mut()
+                    .zip(&fields)
+                    .for_each(|(data_type, field)| {
+                        *data_type = Some(match data_type {
+                            Some(data_type) => widen(data_type, field.data_type()),
+                            None => field.data_type().clone(),
+                        });
+                    });
             }
 
             if records_to_read == 0 {
+                complete = false;
                 break;
             }
         }
 
-        let schema = build_schema_helper(column_names, &column_type_possibilities);
-        Ok((schema, total_records_read))
+        let fields = column_names
+            .into_iter()
+            .zip(column_types)
+            .map(|(name, data_type)| {
+                // columns of which no value was read default to Utf8
+                Field::new(name, data_type.unwrap_or(DataType::Utf8), true)
+            })
+            .collect::<Fields>();
+        Ok(CsvSample {
+            schema: Schema::new(fields),
+            records: total_records_read,
+            bytes: total_bytes_read,
+            complete,
+        })
+    }
+
+    /// Refines the types of `fields` inferred from the first `records`
+    /// records of `chunk`, detecting the dates and timestamps in the
+    /// `date_format` and `timestamp_format` of the options, and decimals if
+    /// `schema_infer_decimal` is set
+    fn refine_inferred_types(
+        &self,
+        format: &arrow::csv::reader::Format,
+        chunk: &Bytes,
+        fields: Fields,
+        records: usize,
+    ) -> Result<Fields> {
+        let date_format = self.options.date_format.as_deref();
+        let timestamp_format = self.options.timestamp_format.as_deref();
+        let decimal = self.options.schema_infer_decimal;
+        if records == 0
+            || (date_format.is_none() && timestamp_format.is_none() && !decimal)
+        {
+            return Ok(fields);
+        }
+
+        let strings = fields
+            .iter()
+            .map(|field| Field::new(field.name(), DataType::Utf8, true))
+            .collect::<Fields>();
+        let mut reader = arrow::csv::ReaderBuilder::new(Arc::new(Schema::new(strings)))
+            .with_format(format.clone())
+            .with_batch_size(records)
+            .build(chunk.clone().reader())?;
+        let Some(batch) = reader.next().transpose()? else {
+            return Ok(fields);
+        };
+
+        let fields = fields
+            .iter()
+            .zip(batch.columns())
+            .map(|(field, column)| {
+                let values = column
+                    .as_string::<i32>()
+                    .iter()
+                    .flatten()
+                    .filter(|value| !value.is_empty())
+                    .collect::<Vec<_>>();
+                let data_type = refine_type(
+                    field.data_type(),
+                    &values,
+                    date_format,
+                    timestamp_format,
+                    decimal,
+                );
+                Field::new(field.name(), data_type, true)
+            })
+            .collect();
+        Ok(fields)
     }
 }
 
-fn build_schema_helper(names: Vec<String>, types: &[HashSet<DataType>]) -> Schema {
-    let fields = names
-        .into_iter()
-        .zip(types)
-        .map(|(field_name, data_type_possibilities)| {
-            // ripped from arrow::csv::reader::infer_reader_schema_with_csv_options
-            // determine data type based on possible types
-            // if there are incompatible types, use DataType::Utf8
-            match data_type_possibilities.len() {
-                1 => Field::new(
-                    field_name,
-                    data_type_possibilities.iter().next().unwrap().clone(),
-                    true,
-                ),
-                2 => {
-                    if data_type_possibilities.contains(&DataType::Int64)
-                        && data_type_possibilities.contains(&DataType::Float64)
-                    {
-                        // we have an integer and double, fall down to double
-                        Field::new(field_name, DataType::Float64, true)
-                    } else {
-                        // default to Utf8 for conflicting datatypes (e.g bool and int)
-                        Field::new(field_name, DataType::Utf8, true)
-                    }
+/// Returns the type of the non-empty `values` of a column inferred as
+/// `data_type` by the CSV reader.
+///
+/// Columns which all parse with `date_format` or `timestamp_format` are
+/// dates or timestamps, while columns inferred as ISO 8601 dates or
+/// timestamps which do not parse with the format are strings. Floats
+/// without exponents are decimals if `decimal` is set and their digits fit.
+fn refine_type(
+    data_type: &DataType,
+    values: &[&str],
+    date_format: Option<&str>,
+    timestamp_format: Option<&str>,
+    decimal: bool,
+) -> DataType {
+    match data_type {
+        DataType::Utf8
+        | DataType::Int64
+        | DataType::Date32
+        | DataType::Timestamp(_, None)
+            if date_format.is_some() || timestamp_format.is_some() =>
+        {
+            if let Some(format) = date_format {
+                if !matches!(data_type, DataType::Timestamp(_, _))
+                    && values
+                        .iter()
+                        .all(|value| NaiveDate::parse_from_str(value, format).is_ok())
+                {
+                    return DataType::Date32;
                 }
-                _ => Field::new(field_name, DataType::Utf8, true),
             }
-        })
-        .collect::<Fields>();
-    Schema::new(fields)
+            if let Some(format) = timestamp_format {
+                if !matches!(data_type, DataType::Date32)
+                    && values
+                        .iter()
+                        .all(|value| NaiveDateTime::parse_from_str(value, format).is_ok())
+                {
+                    return DataType::Timestamp(TimeUnit::Nanosecond, None);
+                }
+            }
+            match data_type {
+                DataType::Date32 if date_format.is_some() => DataType::Utf8,
+                DataType::Timestamp(_, None) if timestamp_format.is_some() => {
+                    DataType::Utf8
+                }
+                other => other.clone(),
+            }
+        }
+        DataType::Float64 if decimal => {
+            infer_decimal(values).unwrap_or(DataType::Float64)
+        }
+        other => other.clone(),
+    }
+}
+
+/// Returns the narrowest decimal type of `values`, if they are all decimal
+/// numbers without exponents
+fn infer_decimal(values: &[&str]) -> Option<DataType> {
+    let mut integer_digits = 0;
+    let mut scale = 0;
+    for value in values {
+        let digits = value.strip_prefix(['-', '+']).unwrap_or(value);
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+        {
+            return None;
+        }
+        integer_digits = integer_digits.max(integer.trim_start_matches('0').len());
+        scale = scale.max(fraction.len());
+    }
+    let precision = (integer_digits + scale).max(1);
+    (precision <= DECIMAL128_MAX_PRECISION as usize)
+        .then_some(DataType::Decimal128(precision as u8, scale as i8))
 }
 
 impl Default for CsvSerializer {
@@ -683,6 +970,7 @@ mod tests {
     use crate::datasource::listing::ListingOptions;
     use crate::physical_plan::collect;
     use crate::prelude::{CsvReadOptions, SessionConfig, SessionContext};
+    use crate::test::object_store::local_unpartitioned_file;
     use crate::test_util::arrow_test_data;
 
     use arrow::compute::concat_batches;
@@ -690,6 +978,7 @@ mod tests {
     use datafusion_common::internal_err;
     use datafusion_common::stats::Precision;
     use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+    use datafusion_expr::logical_plan::INFERRED_CONFIDENCE_METADATA_KEY;
     use datafusion_expr::{col, lit};
 
     use crate::execution::session_state::SessionStateBuilder;
@@ -782,6 +1071,100 @@ mod tests {
         Ok(())
     }
 
+    /// Infers the schema of a CSV file with `contents` with `format`
+    async fn infer_csv_schema(format: CsvFormat, contents: &str) -> Result<SchemaRef> {
+        let dir = tempfile::TempDir::new()?;
+        let path = dir.path().join("file.csv");
+        std::fs::write(&path, contents)?;
+        let store = Arc::new(LocalFileSystem::new()) as Arc<dyn ObjectStore>;
+        let state = SessionContext::new().state();
+        format
+            .infer_schema(&state, &store, &[local_unpartitioned_file(path)])
+            .await
+    }
+
+    fn confidence(schema: &Schema) -> Option<f64> {
+        schema
+            .field(0)
+            .metadata()
+            .get(INFERRED_CONFIDENCE_METADATA_KEY)
+            .map(|c| c.parse().unwrap())
+    }
+
+    #[tokio::test]
+    async fn infer_schema_strategies() -> Result<()> {
+        // Integers in the first half of the file, strings in the second
+        let mut contents = "a,b\n".to_string();
+        for i in 0..1000 {
+            contents.push_str(&format!("{i},{i}\n"));
+        }
+        for i in 0..1000 {
+            contents.push_str(&format!("x{i},{i}\n"));
+        }
+        let head = || {
+            CsvFormat::default()
+                .with_has_header(true)
+                .with_schema_infer_max_rec(100)
+        };
+
+        let schema = infer_csv_schema(head(), &contents).await?;
+        assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+        let head_confidence = confidence(&schema).unwrap();
+        assert!(
+            head_confidence > 0.0 && head_confidence < 0.1,
+            "{head_confidence}"
+        );
+
+        let format = head().with_schema_infer_strategy(SchemaInferStrategy::Sample);
+        let schema = infer_csv_schema(format, &contents).await?;
+        assert_eq!(schema.field(0).data_type(), &DataType::Utf8);
+        assert_eq!(schema.field(1).data_type(), &DataType::Int64);
+        let sample_confidence = confidence(&schema).unwrap();
+        assert!(sample_confidence < 0.1, "{sample_confidence}");
+
+        let format = head().with_schema_infer_strategy(SchemaInferStrategy::Full);
+        let schema = infer_csv_schema(format, &contents).await?;
+        assert_eq!(schema.field(0).data_type(), &DataType::Utf8);
+        assert_eq!(confidence(&schema), None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn infer_schema_with_formats() -> Result<()> {
+        let contents = "d,t,n,i,s\n\
+            02/01/2024,02/01/2024 10:30,1.25,1,2024-01-02\n\
+            ,03/01/2024 11:00,-12.5,2,2024-01-03\n";
+        let options = CsvOptions {
+            has_header: Some(true),
+            date_format: Some("%d/%m/%Y".to_string()),
+            timestamp_format: Some("%d/%m/%Y %H:%M".to_string()),
+            schema_infer_decimal: true,
+            ..Default::default()
+        };
+
+        let schema =
+            infer_csv_schema(CsvFormat::default().with_options(options), contents)
+                .await?;
+        let types: Vec<_> = schema
+            .fields()
+            .iter()
+            .map(|f| f.data_type().clone())
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                DataType::Date32,
+                DataType::Timestamp(TimeUnit::Nanosecond, None),
+                DataType::Decimal128(4, 2),
+                DataType::Int64,
+                // ISO dates cannot be read with the custom date format
+                DataType::Utf8,
+            ]
+        );
+        assert_eq!(confidence(&schema), None);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn read_char_column() -> Result<()> {
         let session_ctx = SessionContext::new();
@@ -919,12 +1302,13 @@ mod tests {
         let decoded_stream = compressed_csv
             .read_to_delimited_chunks_from_stream(compressed_stream.unwrap())
             .await;
-        let (schema, records_read) = compressed_csv
-            .infer_schema_from_stream(&session_state, records_to_read, decoded_stream)
+        let has_header = compressed_csv.has_header_or_default(&session_state);
+        let sample = compressed_csv
+            .infer_schema_from_chunks(has_header, records_to_read, decoded_stream)
             .await?;
 
-        assert_eq!(expected, schema);
-        assert_eq!(100, records_read);
+        assert_eq!(expected, sample.schema);
+        assert_eq!(100, sample.records);
         Ok(())
     }
 
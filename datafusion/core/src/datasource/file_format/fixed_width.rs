@@ -0,0 +1,387 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`FixedWidthFormat`] fixed width text [`FileFormat`] abstractions
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+use arrow::csv::reader::Format;
+use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+use datafusion_common::config::{ConfigField, FixedWidthOptions};
+use datafusion_common::{plan_err, DataFusionError, GetExt};
+use datafusion_physical_expr::PhysicalExpr;
+use futures::{StreamExt, TryStreamExt};
+use object_store::{ObjectMeta, ObjectStore};
+
+use super::file_compression_type::FileCompressionType;
+use super::schema_inference::merge_inferred_schemas;
+use super::{FileFormat, FileFormatFactory};
+use crate::datasource::physical_plan::{
+    transcode_stream, CsvTranscoder, FileScanConfig, FixedWidthExec, LineFormat,
+};
+use crate::error::Result;
+use crate::execution::context::SessionState;
+use crate::physical_plan::ExecutionPlan;
+use crate::physical_plan::Statistics;
+
+/// The name of the fixed width format, as in `STORED AS FIXEDWIDTH`
+const FIXED_WIDTH_FORMAT_NAME: &str = "fixedwidth";
+
+/// The extension of the fixed width files listed by a [`FixedWidthFormat`]
+const DEFAULT_FIXED_WIDTH_EXTENSION: &str = ".txt";
+
+#[derive(Default)]
+/// Factory struct used to create [FixedWidthFormat]
+pub struct FixedWidthFormatFactory {
+    /// the options carried by format factory
+    pub options: Option<FixedWidthOptions>,
+}
+
+impl FixedWidthFormatFactory {
+    /// Creates an instance of [FixedWidthFormatFactory]
+    pub fn new() -> Self {
+        Self { options: None }
+    }
+
+    /// Creates an instance of [FixedWidthFormatFactory] with customized default options
+    pub fn new_with_options(options: FixedWidthOptions) -> Self {
+        Self {
+            options: Some(options),
+        }
+    }
+}
+
+impl FileFormatFactory for FixedWidthFormatFactory {
+    fn create(
+        &self,
+        _state: &SessionState,
+        format_options: &HashMap<String, String>,
+    ) -> Result<Arc<dyn FileFormat>> {
+        let mut options = self.options.clone().unwrap_or_default();
+        for (k, v) in format_options {
+            options.set(k.strip_prefix("format.").unwrap_or(k), v)?;
+        }
+        // Fail on invalid widths when the table is created rather than scanned
+        options.columns()?;
+
+        Ok(Arc::new(FixedWidthFormat::default().with_options(options)))
+    }
+
+    fn default(&self) -> Arc<dyn FileFormat> {
+        Arc::new(FixedWidthFormat::default())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl fmt::Debug for FixedWidthFormatFactory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FixedWidthFormatFactory")
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl GetExt for FixedWidthFormatFactory {
+    fn get_ext(&self) -> String {
+        FIXED_WIDTH_FORMAT_NAME.to_string()
+    }
+}
+
+/// Fixed width text `FileFormat` implementation, reading the values at the
+/// byte ranges given by [`FixedWidthOptions::widths`] and
+/// [`FixedWidthOptions::offsets`] of each line
+#[derive(Default, Debug)]
+pub struct FixedWidthFormat {
+    options: FixedWidthOptions,
+}
+
+impl FixedWidthFormat {
+    /// Set fixed width options
+    pub fn with_options(mut self, options: FixedWidthOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Retrieve fixed width options
+    pub fn options(&self) -> &FixedWidthOptions {
+        &self.options
+    }
+
+    /// Set the comma separated widths in bytes of the columns
+    pub fn with_widths(mut self, widths: impl Into<String>) -> Self {
+        self.options.widths = widths.into();
+        self
+    }
+
+    /// Set the comma separated byte offsets of the columns
+    /// - defaults to the offsets of adjacent columns
+    pub fn with_offsets(mut self, offsets: Option<String>) -> Self {
+        self.options.offsets = offsets;
+        self
+    }
+
+    /// Set true to indicate that the first line is a header.
+    /// - defaults to the `datafusion.catalog.has_header` setting
+    pub fn with_has_header(mut self, has_header: bool) -> Self {
+        self.options.has_header = Some(has_header);
+        self
+    }
+
+    /// Set a `FileCompressionType` of the files
+    /// - defaults to `FileCompressionType::UNCOMPRESSED`
+    pub fn with_file_compression_type(
+        mut self,
+        file_compression_type: FileCompressionType,
+    ) -> Self {
+        self.options.compression = file_compression_type.into();
+        self
+    }
+
+    /// Set a limit in terms of records to scan to infer the schema
+    pub fn with_schema_infer_max_rec(mut self, max_rec: usize) -> Self {
+        self.options.schema_infer_max_rec = max_rec;
+        self
+    }
+
+    fn has_header_or_default(&self, state: &SessionState) -> bool {
+        self.options
+            .has_header
+            .unwrap_or(state.config_options().catalog.has_header)
+    }
+
+    fn transcoder(columns: Vec<Range<usize>>) -> CsvTranscoder {
+        CsvTranscoder::new(LineFormat::FixedWidth { columns }, b'"')
+    }
+}
+
+#[async_trait]
+impl FileFormat for FixedWidthFormat {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_ext(&self) -> String {
+        // Removes the dot, i.e. ".txt" -> "txt"
+        DEFAULT_FIXED_WIDTH_EXTENSION[1..].to_string()
+    }
+
+    fn get_ext_with_compression(
+        &self,
+        file_compression_type: &FileCompressionType,
+    ) -> Result<String> {
+        let ext = self.get_ext();
+        Ok(format!("{}{}", ext, file_compression_type.get_ext()))
+    }
+
+    async fn infer_schema(
+        &self,
+        state: &SessionState,
+        store: &Arc<dyn ObjectStore>,
+        objects: &[ObjectMeta],
+    ) -> Result<SchemaRef> {
+        let has_header = self.has_header_or_default(state);
+        let columns = self.options.columns()?;
+        let compression: FileCompressionType = self.options.compression.into();
+
+        let mut schemas = vec![];
+        let mut records_to_read = self.options.schema_infer_max_rec;
+        for object in objects {
+            let stream = store
+                .get(&object.location)
+                .await?
+                .into_stream()
+                .map_err(DataFusionError::ObjectStore)
+                .boxed();
+            let mut stream = transcode_stream(
+                compression.convert_stream(stream)?,
+                Self::transcoder(columns.clone()),
+            );
+
+            // Only read as many lines as needed into memory
+            let lines_to_read = records_to_read + usize::from(has_header);
+            let mut data = vec![];
+            let mut lines = 0;
+            while lines < lines_to_read {
+                let Some(chunk) = stream.next().await.transpose()? else {
+                    break;
+                };
+                lines += chunk.iter().filter(|b| **b == b'\n').count();
+                data.extend_from_slice(&chunk);
+            }
+
+            let (schema, records_read) = Format::default()
+                .with_header(has_header)
+                .infer_schema(data.as_slice(), Some(records_to_read))?;
+            schemas.push(schema);
+            records_to_read -= records_read;
+            if records_to_read == 0 {
+                break;
+            }
+        }
+
+        Ok(Arc::new(merge_inferred_schemas(schemas)))
+    }
+
+    async fn infer_stats(
+        &self,
+        _state: &SessionState,
+        _store: &Arc<dyn ObjectStore>,
+        table_schema: SchemaRef,
+        _object: &ObjectMeta,
+    ) -> Result<Statistics> {
+        Ok(Statistics::new_unknown(&table_schema))
+    }
+
+    async fn create_physical_plan(
+        &self,
+        state: &SessionState,
+        conf: FileScanConfig,
+        _filters: Option<&Arc<dyn PhysicalExpr>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let columns = self.options.columns()?;
+        if conf.file_schema.fields().len() != columns.len() {
+            return plan_err!(
+                "Fixed width files with {} columns cannot be read with a schema of {} fields",
+                columns.len(),
+                conf.file_schema.fields().len()
+            );
+        }
+
+        let exec = FixedWidthExec::new(
+            conf,
+            columns,
+            self.has_header_or_default(state),
+            self.options.compression.into(),
+        );
+        Ok(Arc::new(exec))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::SessionContext;
+    use crate::test::object_store::local_unpartitioned_file;
+
+    use arrow::datatypes::DataType;
+    use object_store::local::LocalFileSystem;
+
+    #[tokio::test]
+    async fn infer_schema() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+        let store = Arc::new(LocalFileSystem::new()) as _;
+
+        let tmp_dir = tempfile::TempDir::new()?;
+        let path = tmp_dir.path().join("accounts.txt");
+        std::fs::write(
+            &path,
+            "    id  name  balance\n\
+             \x20    1 Alice   12.50\n\
+             \x20    2 Bob        -3\n",
+        )?;
+        let objects = [local_unpartitioned_file(&path)];
+
+        let format = FixedWidthFormat::default()
+            .with_widths("6,6,9")
+            .with_has_header(true);
+        let schema = format.infer_schema(&state, &store, &objects).await?;
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|f| (f.name().as_str(), f.data_type().clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec![
+                ("id", DataType::Int64),
+                ("name", DataType::Utf8),
+                ("balance", DataType::Float64)
+            ]
+        );
+
+        // Only read the name, at an offset
+        let format = FixedWidthFormat::default()
+            .with_widths("5")
+            .with_offsets(Some("7".to_string()))
+            .with_has_header(false)
+            .with_schema_infer_max_rec(1);
+        let schema = format.infer_schema(&state, &store, &objects).await?;
+        assert_eq!(schema.fields().len(), 1);
+        assert_eq!(schema.field(0).name(), "column_1");
+        assert_eq!(schema.field(0).data_type(), &DataType::Utf8);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_options() {
+        let state = SessionContext::new().state();
+        let factory = FixedWidthFormatFactory::new();
+        let options = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+
+        let err = factory.create(&state, &options(&[])).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Invalid or Unsupported Configuration: The widths of the fixed width columns must be set"
+        );
+        let err = factory
+            .create(&state, &options(&[("format.widths", "5,x")]))
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid widths '5,x'"), "{err}");
+        let err = factory
+            .create(
+                &state,
+                &options(&[("format.widths", "5,5"), ("format.offsets", "0")]),
+            )
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Expected 2 fixed width column offsets, found 1"),
+            "{err}"
+        );
+        let err = factory
+            .create(
+                &state,
+                &options(&[("format.widths", "5"), ("format.delimiter", ",")]),
+            )
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Config value \"delimiter\" not found on FixedWidthOptions"),
+            "{err}"
+        );
+
+        let format = factory
+            .create(&state, &options(&[("format.widths", "5, 10")]))
+            .unwrap();
+        let format = format.as_any().downcast_ref::<FixedWidthFormat>().unwrap();
+        assert_eq!(format.options().columns().unwrap(), vec![0..5, 5..15]);
+    }
+}
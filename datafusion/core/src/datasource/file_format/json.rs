@@ -21,9 +21,13 @@ use std::any::Any;
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
-use std::io::BufReader;
+use std::io::{BufReader, Read};
 use std::sync::Arc;
 
+use super::schema_inference::{
+    merge_inferred_schemas, read_sample_range, sample_ranges, sampled_bytes,
+    SampleCoverage,
+};
 use super::write::orchestration::stateless_multipart_put;
 use super::{FileFormat, FileFormatFactory, FileScanConfig};
 use crate::datasource::file_format::file_compression_type::FileCompressionType;
@@ -44,6 +48,7 @@ use arrow::json::reader::{infer_json_schema_from_iterator, ValueIter};
 use arrow_array::RecordBatch;
 use datafusion_common::config::{ConfigField, ConfigFileType, JsonOptions};
 use datafusion_common::file_options::json_writer::JsonWriterOptions;
+use datafusion_common::parsers::SchemaInferStrategy;
 use datafusion_common::{not_impl_err, GetExt, DEFAULT_JSON_EXTENSION};
 use datafusion_execution::TaskContext;
 use datafusion_expr::dml::InsertOp;
@@ -54,6 +59,7 @@ use datafusion_physical_plan::ExecutionPlan;
 use async_trait::async_trait;
 use bytes::{Buf, Bytes};
 use datafusion_physical_expr_common::sort_expr::LexRequirement;
+use futures::TryStreamExt;
 use object_store::{GetResultPayload, ObjectMeta, ObjectStore};
 
 #[derive(Default)]
@@ -151,6 +157,22 @@ impl JsonFormat {
         self
     }
 
+    /// Set how the records to infer the schema from are chosen
+    /// - defaults to [`SchemaInferStrategy::Head`], the first
+    ///   `schema_infer_max_rec` records of the files
+    pub fn with_schema_infer_strategy(mut self, strategy: SchemaInferStrategy) -> Self {
+        self.options.schema_infer_strategy = strategy;
+        self
+    }
+
+    /// Set the number of byte ranges of each file sampled with
+    /// [`SchemaInferStrategy::Sample`]
+    /// - defaults to 4
+    pub fn with_schema_infer_sample_ranges(mut self, ranges: usize) -> Self {
+        self.options.schema_infer_sample_ranges = ranges;
+        self
+    }
+
     /// Set a `FileCompressionType` of JSON
     /// - defaults to `FileCompressionType::UNCOMPRESSED`
     pub fn with_file_compression_type(
@@ -162,6 +184,91 @@ impl JsonFormat {
     }
 }
 
+impl JsonFormat {
+    /// Infers the schema of up to `records_to_read` values from the start
+    /// of `object`
+    async fn infer_schema_from_file(
+        &self,
+        store: &Arc<dyn ObjectStore>,
+        object: &ObjectMeta,
+        records_to_read: usize,
+    ) -> Result<JsonSample> {
+        let file_compression_type = FileCompressionType::from(self.options.compression);
+        let r = store.as_ref().get(&object.location).await?;
+        match r.payload {
+            GetResultPayload::File(file, _) => {
+                let decoder = file_compression_type.convert_read(file)?;
+                infer_schema_from_reader(decoder, records_to_read)
+            }
+            GetResultPayload::Stream(_) => {
+                let data = r.bytes().await?;
+                let decoder = file_compression_type.convert_read(data.reader())?;
+                infer_schema_from_reader(decoder, records_to_read)
+            }
+        }
+    }
+}
+
+/// A schema inferred from a part of a JSON file
+struct JsonSample {
+    schema: Schema,
+    /// The number of values read
+    records: usize,
+    /// The number of bytes read
+    bytes: usize,
+    /// Whether all the values of the part were read
+    complete: bool,
+}
+
+/// Infers the schema of up to `records_to_read` values of `reader`
+fn infer_schema_from_reader(
+    reader: impl Read,
+    records_to_read: usize,
+) -> Result<JsonSample> {
+    let mut reader = BufReader::new(CountingReader {
+        inner: reader,
+        bytes: 0,
+    });
+    let mut records = 0;
+    let mut complete = true;
+    let iter = ValueIter::new(&mut reader, None).take_while(|_| {
+        let should_take = records < records_to_read;
+        if should_take {
+            records += 1;
+        } else {
+            complete = false;
+        }
+        should_take
+    });
+    let schema = infer_json_schema_from_iterator(iter)?;
+    // The buffered but unread bytes are not counted if some values remain
+    let bytes = if complete {
+        reader.get_ref().bytes
+    } else {
+        reader.get_ref().bytes - reader.buffer().len()
+    };
+    Ok(JsonSample {
+        schema,
+        records,
+        bytes,
+        complete,
+    })
+}
+
+/// Counts the bytes read from a reader
+struct CountingReader<R> {
+    inner: R,
+    bytes: usize,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.bytes += read;
+        Ok(read)
+    }
+}
+
 #[async_trait]
 impl FileFormat for JsonFormat {
     fn as_any(&self) -> &dyn Any {
@@ -187,42 +294,60 @@ impl FileFormat for JsonFormat {
         objects: &[ObjectMeta],
     ) -> Result<SchemaRef> {
         let mut schemas = Vec::new();
-        let mut records_to_read = self.options.schema_infer_max_rec;
+        let mut coverage = SampleCoverage::new(objects);
         let file_compression_type = FileCompressionType::from(self.options.compression);
-        for object in objects {
-            let mut take_while = || {
-                let should_take = records_to_read > 0;
-                if should_take {
-                    records_to_read -= 1;
-                }
-                should_take
-            };
-
-            let r = store.as_ref().get(&object.location).await?;
-            let schema = match r.payload {
-                GetResultPayload::File(file, _) => {
-                    let decoder = file_compression_type.convert_read(file)?;
-                    let mut reader = BufReader::new(decoder);
-                    let iter = ValueIter::new(&mut reader, None);
-                    infer_json_schema_from_iterator(iter.take_while(|_| take_while()))?
+        let ranges = sample_ranges(
+            self.options.schema_infer_strategy,
+            objects,
+            self.options.schema_infer_max_rec,
+            self.options.schema_infer_sample_ranges,
+            !file_compression_type.is_compressed(),
+        );
+        match ranges {
+            None => {
+                let mut records_to_read = self.options.schema_infer_max_rec;
+                for (i, object) in objects.iter().enumerate() {
+                    let sample = self
+                        .infer_schema_from_file(store, object, records_to_read)
+                        .await?;
+                    records_to_read -= sample.records;
+                    coverage.add(sample.bytes, sample.complete);
+                    schemas.push(sample.schema);
+                    if records_to_read == 0 {
+                        if i + 1 < objects.len() {
+                            coverage.skip_files();
+                        }
+                        break;
+                    }
                 }
-                GetResultPayload::Stream(_) => {
-                    let data = r.bytes().await?;
-                    let decoder = file_compression_type.convert_read(data.reader())?;
-                    let mut reader = BufReader::new(decoder);
-                    let iter = ValueIter::new(&mut reader, None);
-                    infer_json_schema_from_iterator(iter.take_while(|_| take_while()))?
+            }
+            Some(ranges) => {
+                for (object, ranges) in objects.iter().zip(ranges) {
+                    for range in ranges {
+                        let sample = if range.start == 0 && range.len >= object.size {
+                            self.infer_schema_from_file(store, object, range.records)
+                                .await?
+                        } else {
+                            let chunks: Vec<Bytes> =
+                                read_sample_range(store, object, range)
+                                    .await?
+                                    .try_collect()
+                                    .await?;
+                            let data = chunks.concat();
+                            let mut sample =
+                                infer_schema_from_reader(data.as_slice(), range.records)?;
+                            sample.bytes = sampled_bytes(&data, sample.records);
+                            sample
+                        };
+                        coverage.add(sample.bytes, sample.complete);
+                        schemas.push(sample.schema);
+                    }
                 }
-            };
-
-            schemas.push(schema);
-            if records_to_read == 0 {
-                break;
             }
         }
 
-        let schema = Schema::try_merge(schemas)?;
-        Ok(Arc::new(schema))
+        let schema = merge_inferred_schemas(schemas);
+        Ok(Arc::new(coverage.annotate(schema)))
     }
 
     async fn infer_stats(
@@ -242,7 +367,12 @@ impl FileFormat for JsonFormat {
         _filters: Option<&Arc<dyn PhysicalExpr>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let exec =
-            NdJsonExec::new(conf, FileCompressionType::from(self.options.compression));
+            NdJsonExec::new(conf, FileCompressionType::from(self.options.compression))
+                .with_bad_record_handling(
+                    self.options.on_error,
+                    self.options.max_errors,
+                    self.options.bad_records_path.clone(),
+                );
         Ok(Arc::new(exec))
     }
 
@@ -253,8 +383,8 @@ impl FileFormat for JsonFormat {
         conf: FileSinkConfig,
         order_requirements: Option<LexRequirement>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        if conf.insert_op != InsertOp::Append {
-            return not_impl_err!("Overwrites are not implemented yet for Json");
+        if conf.insert_op == InsertOp::Replace {
+            return not_impl_err!("{} is not implemented yet for Json", conf.insert_op);
         }
 
         let writer_options = JsonWriterOptions::try_from(&self.options)?;
@@ -389,9 +519,11 @@ mod tests {
     use crate::test::object_store::local_unpartitioned_file;
 
     use arrow::util::pretty;
+    use arrow_schema::{DataType, Field};
     use datafusion_common::cast::as_int64_array;
     use datafusion_common::stats::Precision;
     use datafusion_common::{assert_batches_eq, internal_err};
+    use datafusion_expr::logical_plan::INFERRED_CONFIDENCE_METADATA_KEY;
 
     use futures::StreamExt;
     use object_store::local::LocalFileSystem;
@@ -518,6 +650,48 @@ mod tests {
         assert_eq!(vec!["a: Int64", "b: Float64", "c: Boolean"], fields);
     }
 
+    #[tokio::test]
+    async fn infer_schema_strategies() -> Result<()> {
+        let dir = tempfile::TempDir::new()?;
+        let path = dir.path().join("file.json");
+        let mut contents = String::new();
+        for i in 0..100 {
+            contents.push_str(&format!("{{\"a\": {i}, \"b\": {{\"c\": {i}}}}}\n"));
+        }
+        contents.push_str("{\"a\": 0.5, \"b\": {\"d\": true}}\n");
+        std::fs::write(&path, contents)?;
+        let store = Arc::new(LocalFileSystem::new()) as _;
+        let state = SessionContext::new().state();
+        let files = [local_unpartitioned_file(path)];
+
+        let format = JsonFormat::default().with_schema_infer_max_rec(10);
+        let schema = format.infer_schema(&state, &store, &files).await?;
+        assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+        assert!(schema
+            .field(0)
+            .metadata()
+            .contains_key(INFERRED_CONFIDENCE_METADATA_KEY));
+
+        let format = format.with_schema_infer_strategy(SchemaInferStrategy::Full);
+        let schema = format.infer_schema(&state, &store, &files).await?;
+        let expected = Schema::new(vec![
+            Field::new("a", DataType::Float64, true),
+            Field::new(
+                "b",
+                DataType::Struct(
+                    vec![
+                        Field::new("c", DataType::Int64, true),
+                        Field::new("d", DataType::Boolean, true),
+                    ]
+                    .into(),
+                ),
+                true,
+            ),
+        ]);
+        assert_eq!(schema.as_ref(), &expected);
+        Ok(())
+    }
+
     async fn count_num_partitions(ctx: &SessionContext, query: &str) -> Result<usize> {
         let result = ctx
             .sql(&format!("EXPLAIN {query}"))
@@ -25,10 +25,12 @@ pub mod arrow;
 pub mod avro;
 pub mod csv;
 pub mod file_compression_type;
+pub mod fixed_width;
 pub mod json;
 pub mod options;
 #[cfg(feature = "parquet")]
 pub mod parquet;
+mod schema_inference;
 pub mod write;
 
 use std::any::Any;
@@ -45,7 +47,7 @@ use crate::physical_plan::{ExecutionPlan, Statistics};
 use arrow_schema::{DataType, Field, FieldRef, Schema};
 use datafusion_common::file_options::file_type::FileType;
 use datafusion_common::{internal_err, not_impl_err, GetExt};
-use datafusion_expr::Expr;
+use datafusion_expr::{Expr, SortExpr};
 use datafusion_physical_expr::PhysicalExpr;
 
 use async_trait::async_trait;
@@ -119,6 +121,22 @@ pub trait FileFormat: Send + Sync + fmt::Debug {
         object: &ObjectMeta,
     ) -> Result<Statistics>;
 
+    /// Infer the order of the rows of the provided objects from the ordering
+    /// declared in their metadata, if any.
+    ///
+    /// Returns `None` unless all the objects declare the same ordering, in
+    /// terms of columns of `table_schema`. Defaults to `None` for formats
+    /// that can not declare an ordering.
+    async fn infer_ordering(
+        &self,
+        _state: &SessionState,
+        _store: &Arc<dyn ObjectStore>,
+        _table_schema: SchemaRef,
+        _objects: &[ObjectMeta],
+    ) -> Result<Option<Vec<SortExpr>>> {
+        Ok(None)
+    }
+
     /// Take a list of files and convert it to the appropriate executor
     /// according to this file format.
     async fn create_physical_plan(
@@ -24,6 +24,7 @@ use std::ops::Range;
 use std::sync::Arc;
 
 use super::write::demux::start_demuxer_task;
+use super::write::orchestration::FileCommitter;
 use super::write::{create_writer, SharedBuffer};
 use super::{
     coerce_file_schema_to_string_type, coerce_file_schema_to_view_type,
@@ -39,8 +40,8 @@ use crate::error::Result;
 use crate::execution::context::SessionState;
 use crate::physical_plan::insert::{DataSink, DataSinkExec};
 use crate::physical_plan::{
-    Accumulator, DisplayAs, DisplayFormatType, ExecutionPlan, SendableRecordBatchStream,
-    Statistics,
+    Accumulator, DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties,
+    SendableRecordBatchStream, Statistics,
 };
 
 use arrow::compute::sum;
@@ -56,9 +57,10 @@ use datafusion_common_runtime::SpawnedTask;
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation};
 use datafusion_execution::TaskContext;
 use datafusion_expr::dml::InsertOp;
-use datafusion_expr::Expr;
+use datafusion_expr::{Expr, SortExpr};
 use datafusion_functions_aggregate::min_max::{MaxAccumulator, MinAccumulator};
-use datafusion_physical_expr::PhysicalExpr;
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_expr::{PhysicalExpr, PhysicalSortRequirement};
 use datafusion_physical_plan::metrics::MetricsSet;
 
 use async_trait::async_trait;
@@ -74,9 +76,9 @@ use parquet::arrow::{
     arrow_to_parquet_schema, parquet_to_arrow_schema, AsyncArrowWriter,
 };
 use parquet::file::metadata::{ParquetMetaData, ParquetMetaDataReader, RowGroupMetaData};
-use parquet::file::properties::WriterProperties;
+use parquet::file::properties::{WriterProperties, WriterPropertiesBuilder};
 use parquet::file::writer::SerializedFileWriter;
-use parquet::format::FileMetaData;
+use parquet::format::{FileMetaData, SortingColumn};
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 use tokio::sync::mpsc::{self, Receiver, Sender};
 use tokio::task::JoinSet;
@@ -399,6 +401,30 @@ impl FileFormat for ParquetFormat {
         Ok(stats)
     }
 
+    async fn infer_ordering(
+        &self,
+        _state: &SessionState,
+        store: &Arc<dyn ObjectStore>,
+        table_schema: SchemaRef,
+        objects: &[ObjectMeta],
+    ) -> Result<Option<Vec<SortExpr>>> {
+        let mut ordering = None;
+        for object in objects {
+            let metadata =
+                fetch_parquet_metadata(store.as_ref(), object, self.metadata_size_hint())
+                    .await?;
+            match (&ordering, declared_ordering(&metadata, &table_schema)) {
+                (_, None) => return Ok(None),
+                (None, file_ordering) => ordering = file_ordering,
+                (Some(ordering), Some(file_ordering)) if *ordering != file_ordering => {
+                    return Ok(None)
+                }
+                _ => {}
+            }
+        }
+        Ok(ordering)
+    }
+
     async fn create_physical_plan(
         &self,
         _state: &SessionState,
@@ -430,12 +456,26 @@ impl FileFormat for ParquetFormat {
         conf: FileSinkConfig,
         order_requirements: Option<LexRequirement>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        if conf.insert_op != InsertOp::Append {
-            return not_impl_err!("Overwrites are not implemented yet for Parquet");
+        if conf.insert_op == InsertOp::Replace {
+            return not_impl_err!(
+                "{} is not implemented yet for Parquet",
+                conf.insert_op
+            );
         }
 
+        // Files are written in the order of their input, so an already ordered
+        // input is required to stay ordered and is recorded in the file metadata
+        let order_requirements = order_requirements.or_else(|| {
+            input
+                .output_ordering()
+                .map(PhysicalSortRequirement::from_sort_exprs)
+        });
+
         let sink_schema = conf.output_schema().clone();
-        let sink = Arc::new(ParquetSink::new(conf, self.options.clone()));
+        let sink = Arc::new(
+            ParquetSink::new(conf, self.options.clone())
+                .with_sort_order(order_requirements.clone()),
+        );
 
         Ok(Arc::new(DataSinkExec::new(
             input,
@@ -455,9 +495,12 @@ impl FileFormat for ParquetFormat {
             return Ok(FilePushdownSupport::NoSupport);
         }
 
-        let all_supported = filters.iter().all(|filter| {
-            can_expr_be_pushed_down_with_schemas(filter, file_schema, table_schema)
-        });
+        // Adaptive filtering may stop evaluating a filter during the scan, so
+        // the filters must also be applied to the output of the scan
+        let all_supported = !self.options().global.adaptive_filters
+            && filters.iter().all(|filter| {
+                can_expr_be_pushed_down_with_schemas(filter, file_schema, table_schema)
+            });
 
         Ok(if all_supported {
             FilePushdownSupport::Supported
@@ -494,6 +537,46 @@ impl<'a> MetadataFetch for ObjectStoreFetch<'a> {
     }
 }
 
+/// The ordering declared by the `sorting_columns` of a parquet file, in
+/// terms of columns of `table_schema`.
+///
+/// Returns `None` if the row groups do not all declare the same ordering.
+/// Only the prefix of the `sorting_columns` made of top level columns of
+/// `table_schema` is returned.
+fn declared_ordering(
+    metadata: &ParquetMetaData,
+    table_schema: &Schema,
+) -> Option<Vec<SortExpr>> {
+    let row_groups = metadata.row_groups();
+    let sorting_columns = row_groups.first()?.sorting_columns()?;
+    if row_groups
+        .iter()
+        .any(|row_group| row_group.sorting_columns() != Some(sorting_columns))
+    {
+        return None;
+    }
+
+    let schema_descr = metadata.file_metadata().schema_descr();
+    let ordering: Vec<_> = sorting_columns
+        .iter()
+        .map_while(|sorting_column| {
+            let column = schema_descr
+                .columns()
+                .get(usize::try_from(sorting_column.column_idx).ok()?)?;
+            let [name] = column.path().parts() else {
+                return None;
+            };
+            let field = table_schema.field_with_name(name).ok()?;
+            Some(SortExpr::new(
+                Expr::Column(datafusion_common::Column::new_unqualified(field.name())),
+                !sorting_column.descending,
+                sorting_column.nulls_first,
+            ))
+        })
+        .collect();
+    (!ordering.is_empty()).then_some(ordering)
+}
+
 /// Fetches parquet metadata from ObjectStore for given object
 ///
 /// This component is a subject to **change** in near future and is exposed for low level integrations
@@ -508,11 +591,43 @@ pub async fn fetch_parquet_metadata(
     let file_size = meta.size;
     let fetch = ObjectStoreFetch::new(store, meta);
 
-    ParquetMetaDataReader::new()
+    match ParquetMetaDataReader::new()
         .with_prefetch_hint(size_hint)
         .load_and_finish(fetch, file_size)
         .await
-        .map_err(DataFusionError::from)
+    {
+        Ok(metadata) => Ok(metadata),
+        Err(e)
+            if has_encrypted_footer(ObjectStoreFetch::new(store, meta), file_size)
+                .await =>
+        {
+            not_impl_err!(
+                "Reading parquet file {} with an encrypted footer is not supported: {e}",
+                meta.location
+            )
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Magic number ending parquet files whose footer is encrypted with
+/// parquet modular encryption, instead of `PAR1`
+const PARQUET_ENCRYPTED_FOOTER_MAGIC: &[u8; 4] = b"PARE";
+
+/// Returns true if the parquet file of `file_size` bytes read by `fetch`
+/// has a footer encrypted with parquet modular encryption, which can not
+/// be decoded by the parquet crate version in use.
+///
+/// Only meant to explain why the footer of a file failed to decode.
+pub(crate) async fn has_encrypted_footer<F: MetadataFetch>(
+    mut fetch: F,
+    file_size: usize,
+) -> bool {
+    file_size >= PARQUET_ENCRYPTED_FOOTER_MAGIC.len()
+        && fetch
+            .fetch(file_size - PARQUET_ENCRYPTED_FOOTER_MAGIC.len()..file_size)
+            .await
+            .is_ok_and(|magic| magic.as_ref() == PARQUET_ENCRYPTED_FOOTER_MAGIC)
 }
 
 /// Read and parse the schema of the Parquet file at location `path`
@@ -680,6 +795,9 @@ pub struct ParquetSink {
     config: FileSinkConfig,
     /// Underlying parquet options
     parquet_options: TableParquetOptions,
+    /// Order of the rows written to each file, recorded as the parquet
+    /// `sorting_columns` of every row group
+    sort_order: Option<LexRequirement>,
     /// File metadata from successfully produced parquet files. The Mutex is only used
     /// to allow inserting to HashMap from behind borrowed reference in DataSink::write_all.
     written: Arc<parking_lot::Mutex<HashMap<Path, FileMetaData>>>,
@@ -709,10 +827,18 @@ impl ParquetSink {
         Self {
             config,
             parquet_options,
+            sort_order: None,
             written: Default::default(),
         }
     }
 
+    /// Set the order of the rows written by this sink, which is recorded as
+    /// the `sorting_columns` of the written row groups.
+    pub fn with_sort_order(mut self, sort_order: Option<LexRequirement>) -> Self {
+        self.sort_order = sort_order;
+        self
+    }
+
     /// Retrieve the inner [`FileSinkConfig`].
     pub fn config(&self) -> &FileSinkConfig {
         &self.config
@@ -752,6 +878,35 @@ impl ParquetSink {
         }
     }
 
+    /// Converts the sort order of this sink to parquet [`SortingColumn`]s of
+    /// the writer schema.
+    ///
+    /// Only the longest prefix of the sort order made of plain columns
+    /// stored in the files is converted, as `sorting_columns` can not
+    /// describe an ordering on expressions or partition columns.
+    fn sorting_columns(&self) -> Result<Option<Vec<SortingColumn>>> {
+        let Some(sort_order) = &self.sort_order else {
+            return Ok(None);
+        };
+        let parquet_schema = arrow_to_parquet_schema(&self.get_writer_schema())?;
+        let sorting_columns: Vec<_> = sort_order
+            .iter()
+            .map_while(|requirement| {
+                let column = requirement.expr.as_any().downcast_ref::<Column>()?;
+                let column_idx = parquet_schema.columns().iter().position(
+                    |c| matches!(c.path().parts(), [name] if name == column.name()),
+                )?;
+                let options = requirement.options.unwrap_or_default();
+                Some(SortingColumn {
+                    column_idx: column_idx as i32,
+                    descending: options.descending,
+                    nulls_first: options.nulls_first,
+                })
+            })
+            .collect();
+        Ok((!sorting_columns.is_empty()).then_some(sorting_columns))
+    }
+
     /// Creates an AsyncArrowWriter which serializes a parquet file to an ObjectStore
     /// AsyncArrowWriters are used when individual parquet file serialization is not parallelized
     async fn create_async_arrow_writer(
@@ -773,28 +928,20 @@ impl ParquetSink {
     pub fn parquet_options(&self) -> &TableParquetOptions {
         &self.parquet_options
     }
-}
-
-#[async_trait]
-impl DataSink for ParquetSink {
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
 
-    fn metrics(&self) -> Option<MetricsSet> {
-        None
-    }
-
-    async fn write_all(
+    /// Writes the files of `data`, registering them with `committer`
+    async fn write_files(
         &self,
         data: SendableRecordBatchStream,
         context: &Arc<TaskContext>,
+        object_store: Arc<dyn ObjectStore>,
+        committer: &mut FileCommitter,
     ) -> Result<u64> {
-        let parquet_props = ParquetWriterOptions::try_from(&self.parquet_options)?;
-
-        let object_store = context
-            .runtime_env()
-            .object_store(&self.config.object_store_url)?;
+        let parquet_props = ParquetWriterOptions::new(
+            WriterPropertiesBuilder::try_from(&self.parquet_options)?
+                .set_sorting_columns(self.sorting_columns()?)
+                .build(),
+        );
 
         let parquet_opts = &self.parquet_options;
         let allow_single_file_parallelism =
@@ -829,6 +976,8 @@ impl DataSink for ParquetSink {
         > = JoinSet::new();
 
         while let Some((path, mut rx)) = file_stream_rx.recv().await {
+            let final_path = path.clone();
+            let path = committer.stage(path);
             if !allow_single_file_parallelism {
                 let mut writer = self
                     .create_async_arrow_writer(
@@ -849,7 +998,7 @@ impl DataSink for ParquetSink {
                         .close()
                         .await
                         .map_err(DataFusionError::ParquetError)?;
-                    Ok((path, file_metadata))
+                    Ok((final_path, file_metadata))
                 });
             } else {
                 let writer = create_writer(
@@ -874,7 +1023,7 @@ impl DataSink for ParquetSink {
                         pool,
                     )
                     .await?;
-                    Ok((path, file_metadata))
+                    Ok((final_path, file_metadata))
                 });
             }
         }
@@ -910,6 +1059,33 @@ impl DataSink for ParquetSink {
     }
 }
 
+#[async_trait]
+impl DataSink for ParquetSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
+
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let object_store = context
+            .runtime_env()
+            .object_store(&self.config.object_store_url)?;
+
+        let mut committer = FileCommitter::new(&self.config, Arc::clone(&object_store));
+        let result = self
+            .write_files(data, context, object_store, &mut committer)
+            .await;
+        committer.finish(result).await
+    }
+}
+
 /// Consumes a stream of [ArrowLeafColumn] via a channel and serializes them using an [ArrowColumnWriter]
 /// Once the channel is exhausted, returns the ArrowColumnWriter.
 async fn column_serializer_task(
@@ -1308,6 +1484,7 @@ mod tests {
     use crate::physical_plan::metrics::MetricValue;
     use crate::prelude::{SessionConfig, SessionContext};
     use arrow::array::{Array, ArrayRef, StringArray};
+    use arrow::compute::SortOptions;
     use arrow_array::types::Int32Type;
     use arrow_array::{DictionaryArray, Int32Array, Int64Array};
     use arrow_schema::{DataType, Field};
@@ -2285,7 +2462,7 @@ mod tests {
             table_paths: vec![ListingTableUrl::parse("file:///")?],
             output_schema: schema.clone(),
             table_partition_cols: vec![],
-            insert_op: InsertOp::Overwrite,
+            insert_op: InsertOp::Append,
             keep_partition_by_columns: false,
         };
         let parquet_sink = Arc::new(ParquetSink::new(
@@ -2366,6 +2543,104 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn fetch_metadata_with_encrypted_footer() -> Result<()> {
+        let store = object_store::memory::InMemory::new();
+        let location = Path::from("encrypted.parquet");
+        // encrypted footer, of 4 bytes, followed by its length and the magic
+        let data = Bytes::from_static(b"PAR1\x00\x00\x00\x00\x04\x00\x00\x00PARE");
+        store.put(&location, data.clone().into()).await?;
+        let meta = store.head(&location).await?;
+
+        let err = fetch_parquet_metadata(&store, &meta, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DataFusionError::NotImplemented(_)), "{err}");
+        assert!(
+            err.to_string().contains("with an encrypted footer"),
+            "{err}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn parquet_sink_write_sorting_columns() -> Result<()> {
+        let field_a = Field::new("a", DataType::Utf8, false);
+        let field_b = Field::new("b", DataType::Utf8, false);
+        let schema = Arc::new(Schema::new(vec![field_a, field_b]));
+        let object_store_url = ObjectStoreUrl::local_filesystem();
+
+        let file_sink_config = FileSinkConfig {
+            object_store_url: object_store_url.clone(),
+            file_groups: vec![PartitionedFile::new("/tmp".to_string(), 1)],
+            table_paths: vec![ListingTableUrl::parse("file:///")?],
+            output_schema: schema.clone(),
+            table_partition_cols: vec![],
+            insert_op: InsertOp::Append,
+            keep_partition_by_columns: false,
+        };
+        // b ASC NULLS LAST, a DESC NULLS FIRST
+        let sort_order = LexRequirement::new(vec![
+            PhysicalSortRequirement::new(
+                Arc::new(Column::new("b", 1)),
+                Some(SortOptions {
+                    descending: false,
+                    nulls_first: false,
+                }),
+            ),
+            PhysicalSortRequirement::new(
+                Arc::new(Column::new("a", 0)),
+                Some(SortOptions {
+                    descending: true,
+                    nulls_first: true,
+                }),
+            ),
+        ]);
+        let parquet_sink = Arc::new(
+            ParquetSink::new(file_sink_config, TableParquetOptions::default())
+                .with_sort_order(Some(sort_order)),
+        );
+
+        // create data
+        let col_a: ArrayRef = Arc::new(StringArray::from(vec!["foo", "bar"]));
+        let col_b: ArrayRef = Arc::new(StringArray::from(vec!["baz", "baz"]));
+        let batch = RecordBatch::try_from_iter(vec![("a", col_a), ("b", col_b)]).unwrap();
+
+        // write stream
+        parquet_sink
+            .write_all(
+                Box::pin(RecordBatchStreamAdapter::new(
+                    schema,
+                    futures::stream::iter(vec![Ok(batch)]),
+                )),
+                &build_ctx(object_store_url.as_ref()),
+            )
+            .await
+            .unwrap();
+
+        // assert the sort order is recorded on every row group
+        let written = parquet_sink.written();
+        let (_, file_metadata) = written.iter().next().unwrap();
+        assert!(!file_metadata.row_groups.is_empty());
+        let expected = vec![
+            SortingColumn {
+                column_idx: 1,
+                descending: false,
+                nulls_first: false,
+            },
+            SortingColumn {
+                column_idx: 0,
+                descending: true,
+                nulls_first: true,
+            },
+        ];
+        for row_group in &file_metadata.row_groups {
+            assert_eq!(row_group.sorting_columns.as_ref(), Some(&expected));
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn parquet_sink_write_partitions() -> Result<()> {
         let field_a = Field::new("a", DataType::Utf8, false);
@@ -2380,7 +2655,7 @@ mod tests {
             table_paths: vec![ListingTableUrl::parse("file:///")?],
             output_schema: schema.clone(),
             table_partition_cols: vec![("a".to_string(), DataType::Utf8)], // add partitioning
-            insert_op: InsertOp::Overwrite,
+            insert_op: InsertOp::Append,
             keep_partition_by_columns: false,
         };
         let parquet_sink = Arc::new(ParquetSink::new(
@@ -2463,7 +2738,7 @@ mod tests {
                 table_paths: vec![ListingTableUrl::parse("file:///")?],
                 output_schema: schema.clone(),
                 table_partition_cols: vec![],
-                insert_op: InsertOp::Overwrite,
+                insert_op: InsertOp::Append,
                 keep_partition_by_columns: false,
             };
             let parquet_sink = Arc::new(ParquetSink::new(
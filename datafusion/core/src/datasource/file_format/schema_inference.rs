@@ -0,0 +1,508 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Record sampling and type widening shared by the schema inference of the
+//! line oriented formats (CSV and NDJSON)
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::error::{DataFusionError, Result};
+
+use arrow::datatypes::{
+    DataType, Field, Fields, Schema, TimeUnit, DECIMAL128_MAX_PRECISION,
+};
+use bytes::Bytes;
+use datafusion_common::parsers::SchemaInferStrategy;
+use datafusion_expr::logical_plan::INFERRED_CONFIDENCE_METADATA_KEY;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use object_store::delimited::newline_delimited_stream;
+use object_store::{GetOptions, GetRange, ObjectMeta, ObjectStore};
+
+/// A byte range of a file to infer the schema from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SampleRange {
+    /// The first byte of the range. Unless zero, the line containing this
+    /// byte is skipped as it may be incomplete.
+    pub start: usize,
+    /// The maximum number of bytes read after `start`, the line containing
+    /// the last byte is read in full
+    pub len: usize,
+    /// The maximum number of records read
+    pub records: usize,
+}
+
+/// Returns the ranges of each of `objects` to read to infer their schema
+/// with `strategy`, or `None` if the files should be read in order until
+/// `max_records` records are read.
+///
+/// `splittable` is false if the files cannot be read from an offset, for
+/// example because they are compressed or records may span multiple lines.
+pub(crate) fn sample_ranges(
+    strategy: SchemaInferStrategy,
+    objects: &[ObjectMeta],
+    max_records: usize,
+    ranges_per_file: usize,
+    splittable: bool,
+) -> Option<Vec<Vec<SampleRange>>> {
+    let whole_file = |object: &ObjectMeta, records| SampleRange {
+        start: 0,
+        len: object.size,
+        records,
+    };
+    match strategy {
+        SchemaInferStrategy::Head => None,
+        SchemaInferStrategy::Full => Some(
+            objects
+                .iter()
+                .map(|object| vec![whole_file(object, usize::MAX)])
+                .collect(),
+        ),
+        SchemaInferStrategy::Sample => {
+            let records_per_file = max_records.div_ceil(objects.len().max(1)).max(1);
+            let ranges = objects.iter().map(|object| {
+                let num_ranges = if splittable {
+                    ranges_per_file
+                        .clamp(1, records_per_file)
+                        .min(object.size.max(1))
+                } else {
+                    1
+                };
+                if num_ranges == 1 {
+                    return vec![whole_file(object, records_per_file)];
+                }
+                let records = records_per_file.div_ceil(num_ranges);
+                (0..num_ranges)
+                    .map(|i| {
+                        let start = object.size * i / num_ranges;
+                        let end = object.size * (i + 1) / num_ranges;
+                        SampleRange {
+                            start,
+                            len: end - start,
+                            records,
+                        }
+                    })
+                    .collect()
+            });
+            Some(ranges.collect())
+        }
+    }
+}
+
+/// Restricts a stream of newline delimited chunks read from `range.start - 1`
+/// to the lines starting within `range`.
+///
+/// The byte preceding the range is read so that a range starting at the
+/// beginning of a line does not skip that line.
+pub(crate) fn restrict_to_range(
+    stream: BoxStream<'static, Result<Bytes>>,
+    range: SampleRange,
+) -> BoxStream<'static, Result<Bytes>> {
+    let skip_first_line = range.start > 0;
+    let remaining = if skip_first_line {
+        range.len + 1
+    } else {
+        range.len
+    };
+    stream
+        .scan(
+            (skip_first_line, remaining),
+            |(skip_first_line, remaining), chunk| {
+                let mut chunk = match chunk {
+                    Ok(chunk) if *remaining > 0 => chunk,
+                    Ok(_) => return futures::future::ready(None),
+                    Err(e) => return futures::future::ready(Some(Err(e))),
+                };
+                if *skip_first_line {
+                    match chunk.iter().position(|b| *b == b'\n') {
+                        Some(end) => {
+                            *remaining = remaining.saturating_sub(end + 1);
+                            chunk = chunk.slice(end + 1..);
+                            *skip_first_line = false;
+                        }
+                        None => {
+                            *remaining = remaining.saturating_sub(chunk.len());
+                            return futures::future::ready(Some(Ok(Bytes::new())));
+                        }
+                    }
+                }
+                if chunk.len() > *remaining {
+                    // Keep the line containing the last byte of the range
+                    let end = chunk[*remaining..]
+                        .iter()
+                        .position(|b| *b == b'\n')
+                        .map_or(chunk.len(), |end| *remaining + end + 1);
+                    chunk.truncate(end);
+                    *remaining = 0;
+                } else {
+                    *remaining -= chunk.len();
+                }
+                futures::future::ready(Some(Ok(chunk)))
+            },
+        )
+        .try_filter(|chunk| futures::future::ready(!chunk.is_empty()))
+        .boxed()
+}
+
+/// Returns a stream of newline delimited chunks of the lines of `object`
+/// starting within `range`, which must not be compressed
+pub(crate) async fn read_sample_range(
+    store: &Arc<dyn ObjectStore>,
+    object: &ObjectMeta,
+    range: SampleRange,
+) -> Result<BoxStream<'static, Result<Bytes>>> {
+    let options = GetOptions {
+        range: Some(GetRange::Offset(range.start.saturating_sub(1))),
+        ..Default::default()
+    };
+    let stream = store
+        .get_opts(&object.location, options)
+        .await?
+        .into_stream();
+    let stream = newline_delimited_stream(stream)
+        .map_err(DataFusionError::from)
+        .boxed();
+    Ok(restrict_to_range(stream, range))
+}
+
+/// Estimates the number of bytes of `chunk` taken by its first `records`
+/// records, assuming records are single lines of similar length
+pub(crate) fn sampled_bytes(chunk: &[u8], records: usize) -> usize {
+    let lines = chunk.iter().filter(|b| **b == b'\n').count().max(1);
+    if records >= lines {
+        chunk.len()
+    } else {
+        chunk.len() * records / lines
+    }
+}
+
+/// Tracks how much of the input a schema was inferred from
+#[derive(Debug)]
+pub(crate) struct SampleCoverage {
+    total_bytes: usize,
+    sampled_bytes: usize,
+    exhaustive: bool,
+}
+
+impl SampleCoverage {
+    pub fn new(objects: &[ObjectMeta]) -> Self {
+        Self {
+            total_bytes: objects.iter().map(|object| object.size).sum(),
+            sampled_bytes: 0,
+            exhaustive: true,
+        }
+    }
+
+    /// Record that `bytes` were sampled from a range, `complete` if the
+    /// range was read to its end
+    pub fn add(&mut self, bytes: usize, complete: bool) {
+        self.sampled_bytes += bytes;
+        self.exhaustive &= complete;
+    }
+
+    /// Record that some files were not sampled
+    pub fn skip_files(&mut self) {
+        self.exhaustive = false;
+    }
+
+    /// Returns the estimated fraction of the input that was sampled, or
+    /// `None` if all of it was read
+    pub fn confidence(&self) -> Option<f64> {
+        if self.exhaustive || self.total_bytes == 0 {
+            return None;
+        }
+        Some((self.sampled_bytes as f64 / self.total_bytes as f64).min(1.0))
+    }
+
+    /// Record the confidence in the inferred `schema` in the metadata of
+    /// its fields, if it was inferred from a part of the input
+    pub fn annotate(&self, schema: Schema) -> Schema {
+        let Some(confidence) = self.confidence() else {
+            return schema;
+        };
+        let confidence = format!("{confidence:.4}");
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let mut metadata = field.metadata().clone();
+                metadata.insert(
+                    INFERRED_CONFIDENCE_METADATA_KEY.to_string(),
+                    confidence.clone(),
+                );
+                Arc::new(field.as_ref().clone().with_metadata(metadata))
+            })
+            .collect::<Fields>();
+        Schema::new_with_metadata(fields, schema.metadata().clone())
+    }
+}
+
+/// Returns the narrowest type which can represent the values of both `left`
+/// and `right`, as inferred from text.
+///
+/// * A null type widens to the other type
+/// * Integers, decimals and floats widen to decimals if the integer and
+///   fractional digits fit, and otherwise to floats
+/// * Dates widen to timestamps, and timestamps to the finer time unit
+/// * Lists widen their element types, and widen from a single value
+/// * Structs widen to the union of their fields
+/// * Any other combination widens to strings
+pub(crate) fn widen(left: &DataType, right: &DataType) -> DataType {
+    use DataType::*;
+    match (left, right) {
+        (l, r) if l == r => l.clone(),
+        (Null, other) | (other, Null) => other.clone(),
+        (Int64, Float64) | (Float64, Int64) => Float64,
+        (Int64, Decimal128(p, s)) | (Decimal128(p, s), Int64) => {
+            decimal_or_float(i64::MAX.to_string().len(), (*p - *s as u8) as usize, *s)
+        }
+        (Decimal128(p1, s1), Decimal128(p2, s2)) => {
+            let integer_digits = (*p1 as i16 - *s1 as i16).max(*p2 as i16 - *s2 as i16);
+            decimal_or_float(integer_digits.max(0) as usize, 0, (*s1).max(*s2))
+        }
+        (Float64, Decimal128(_, _)) | (Decimal128(_, _), Float64) => Float64,
+        (Date32, Date64) | (Date64, Date32) => Date64,
+        (Date32 | Date64, Timestamp(unit, tz))
+        | (Timestamp(unit, tz), Date32 | Date64) => Timestamp(*unit, tz.clone()),
+        (Timestamp(u1, tz1), Timestamp(u2, tz2)) if tz1 == tz2 => {
+            Timestamp(finer_time_unit(*u1, *u2), tz1.clone())
+        }
+        (List(l), List(r)) => List(Arc::new(Field::new(
+            l.name(),
+            widen(l.data_type(), r.data_type()),
+            true,
+        ))),
+        (List(l), other) | (other, List(l)) if !matches!(other, Struct(_)) => List(
+            Arc::new(Field::new(l.name(), widen(l.data_type(), other), true)),
+        ),
+        (Struct(l), Struct(r)) => Struct(merge_fields(l.iter().chain(r.iter()))),
+        _ => Utf8,
+    }
+}
+
+fn decimal_or_float(left_digits: usize, right_digits: usize, scale: i8) -> DataType {
+    let precision = left_digits.max(right_digits) + scale.max(0) as usize;
+    if precision <= DECIMAL128_MAX_PRECISION as usize {
+        DataType::Decimal128(precision as u8, scale)
+    } else {
+        DataType::Float64
+    }
+}
+
+fn finer_time_unit(left: TimeUnit, right: TimeUnit) -> TimeUnit {
+    let rank = |unit: &TimeUnit| match unit {
+        TimeUnit::Second => 0,
+        TimeUnit::Millisecond => 1,
+        TimeUnit::Microsecond => 2,
+        TimeUnit::Nanosecond => 3,
+    };
+    if rank(&left) >= rank(&right) {
+        left
+    } else {
+        right
+    }
+}
+
+/// Merges fields by name, in the order they first appear, widening the types
+/// of fields with the same name
+fn merge_fields<'a>(fields: impl IntoIterator<Item = &'a Arc<Field>>) -> Fields {
+    let mut merged: Vec<Field> = vec![];
+    let mut positions = HashMap::new();
+    for field in fields {
+        match positions.get(field.name()) {
+            Some(&i) => {
+                let existing: &mut Field = &mut merged[i];
+                let data_type = widen(existing.data_type(), field.data_type());
+                let mut metadata = existing.metadata().clone();
+                metadata.extend(field.metadata().clone());
+                *existing = existing
+                    .clone()
+                    .with_data_type(data_type)
+                    .with_nullable(true)
+                    .with_metadata(metadata);
+            }
+            None => {
+                positions.insert(field.name().clone(), merged.len());
+                merged.push(field.as_ref().clone().with_nullable(true));
+            }
+        }
+    }
+    merged.into()
+}
+
+/// Merges the schemas inferred from different files or parts of a file,
+/// widening the types of the fields with the same name with [`widen`]
+pub(crate) fn merge_inferred_schemas(
+    schemas: impl IntoIterator<Item = Schema>,
+) -> Schema {
+    let schemas: Vec<_> = schemas.into_iter().collect();
+    let fields = merge_fields(schemas.iter().flat_map(|schema| schema.fields().iter()));
+    let metadata = schemas
+        .iter()
+        .flat_map(|schema| schema.metadata().clone())
+        .collect();
+    Schema::new_with_metadata(fields, metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::datatypes::DataType::*;
+    use chrono::Utc;
+    use object_store::path::Path;
+
+    #[test]
+    fn widen_types() {
+        let cases = [
+            (Null, Int64, Int64),
+            (Int64, Float64, Float64),
+            (Boolean, Int64, Utf8),
+            (Int64, Decimal128(5, 2), Decimal128(21, 2)),
+            (Decimal128(5, 2), Decimal128(4, 3), Decimal128(6, 3)),
+            (Decimal128(38, 2), Int64, Decimal128(38, 2)),
+            (Decimal128(38, 0), Decimal128(38, 1), Float64),
+            (Decimal128(5, 2), Float64, Float64),
+            (
+                Date32,
+                Timestamp(TimeUnit::Second, None),
+                Timestamp(TimeUnit::Second, None),
+            ),
+            (
+                Timestamp(TimeUnit::Millisecond, None),
+                Timestamp(TimeUnit::Nanosecond, None),
+                Timestamp(TimeUnit::Nanosecond, None),
+            ),
+            (Date32, Utf8, Utf8),
+            (
+                List(Arc::new(Field::new("item", Int64, true))),
+                Float64,
+                List(Arc::new(Field::new("item", Float64, true))),
+            ),
+        ];
+        for (left, right, expected) in cases {
+            assert_eq!(widen(&left, &right), expected, "{left} and {right}");
+            assert_eq!(widen(&right, &left), expected, "{right} and {left}");
+        }
+    }
+
+    #[test]
+    fn merge_schemas() {
+        let a = Schema::new(vec![
+            Field::new("a", Int64, true),
+            Field::new("b", Date32, true),
+        ]);
+        let b = Schema::new(vec![
+            Field::new("c", Boolean, true),
+            Field::new("a", Float64, true),
+            Field::new("b", Utf8, true),
+        ]);
+        let merged = merge_inferred_schemas([a, b]);
+        let expected = Schema::new(vec![
+            Field::new("a", Float64, true),
+            Field::new("b", Utf8, true),
+            Field::new("c", Boolean, true),
+        ]);
+        assert_eq!(merged, expected);
+    }
+
+    fn object(size: usize) -> ObjectMeta {
+        ObjectMeta {
+            location: Path::from("file"),
+            last_modified: Utc::now(),
+            size,
+            e_tag: None,
+            version: None,
+        }
+    }
+
+    #[test]
+    fn sample_ranges_across_files() {
+        let objects = [object(1000), object(10)];
+        assert_eq!(
+            sample_ranges(SchemaInferStrategy::Head, &objects, 100, 4, true),
+            None
+        );
+
+        let ranges =
+            sample_ranges(SchemaInferStrategy::Sample, &objects, 100, 4, true).unwrap();
+        let starts: Vec<Vec<_>> = ranges
+            .iter()
+            .map(|ranges| ranges.iter().map(|r| (r.start, r.len, r.records)).collect())
+            .collect();
+        assert_eq!(
+            starts,
+            vec![
+                vec![(0, 250, 13), (250, 250, 13), (500, 250, 13), (750, 250, 13)],
+                vec![(0, 2, 13), (2, 3, 13), (5, 2, 13), (7, 3, 13)],
+            ]
+        );
+
+        let ranges =
+            sample_ranges(SchemaInferStrategy::Sample, &objects, 100, 4, false).unwrap();
+        assert_eq!(
+            ranges[0],
+            vec![SampleRange {
+                start: 0,
+                len: 1000,
+                records: 50
+            }]
+        );
+
+        let ranges =
+            sample_ranges(SchemaInferStrategy::Full, &objects, 100, 4, true).unwrap();
+        assert_eq!(
+            ranges[1],
+            vec![SampleRange {
+                start: 0,
+                len: 10,
+                records: usize::MAX
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn restrict_stream_to_range() {
+        let chunks = |range: SampleRange| async move {
+            // The stream starts at the byte preceding the range
+            let data = &b"aa\nbbbb\ncc\ndd\n"[range.start.saturating_sub(1)..];
+            let stream = futures::stream::iter(
+                data.split_inclusive(|b| *b == b'\n')
+                    .map(|line| Ok(Bytes::copy_from_slice(line))),
+            )
+            .boxed();
+            let chunks: Vec<Bytes> = restrict_to_range(stream, range)
+                .try_collect()
+                .await
+                .unwrap();
+            chunks.concat()
+        };
+        let range = |start, len| SampleRange {
+            start,
+            len,
+            records: 10,
+        };
+
+        assert_eq!(chunks(range(0, 4)).await, b"aa\nbbbb\n");
+        // A range starting at the beginning of a line includes that line
+        assert_eq!(chunks(range(3, 4)).await, b"bbbb\n");
+        assert_eq!(chunks(range(4, 4)).await, b"");
+        assert_eq!(chunks(range(4, 5)).await, b"cc\n");
+        assert_eq!(chunks(range(7, 7)).await, b"cc\ndd\n");
+    }
+}
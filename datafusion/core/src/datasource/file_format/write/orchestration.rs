@@ -19,6 +19,7 @@
 //! orchestrating file serialization, streaming to object store,
 //! parallelization, and abort handling
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use super::demux::start_demuxer_task;
@@ -32,9 +33,12 @@ use arrow_array::RecordBatch;
 use datafusion_common::{internal_datafusion_err, internal_err, DataFusionError};
 use datafusion_common_runtime::SpawnedTask;
 use datafusion_execution::TaskContext;
+use datafusion_expr::dml::InsertOp;
 
 use bytes::Bytes;
-use futures::join;
+use futures::{join, StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::ObjectStore;
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 use tokio::sync::mpsc::{self, Receiver};
 use tokio::task::JoinSet;
@@ -250,6 +254,32 @@ pub(crate) async fn stateless_multipart_put(
         .runtime_env()
         .object_store(&config.object_store_url)?;
 
+    let mut committer = FileCommitter::new(config, Arc::clone(&object_store));
+    let result = stateless_multipart_put_files(
+        data,
+        context,
+        file_extension,
+        get_serializer,
+        config,
+        compression,
+        object_store,
+        &mut committer,
+    )
+    .await;
+    committer.finish(result).await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn stateless_multipart_put_files(
+    data: SendableRecordBatchStream,
+    context: &Arc<TaskContext>,
+    file_extension: String,
+    get_serializer: Box<dyn Fn() -> Arc<dyn BatchSerializer> + Send>,
+    config: &FileSinkConfig,
+    compression: FileCompressionType,
+    object_store: Arc<dyn ObjectStore>,
+    committer: &mut FileCommitter,
+) -> Result<u64> {
     let base_output_path = &config.table_paths[0];
     let part_cols = if !config.table_partition_cols.is_empty() {
         Some(config.table_partition_cols.clone())
@@ -279,6 +309,7 @@ pub(crate) async fn stateless_multipart_put(
     });
     while let Some((location, rb_stream)) = file_stream_rx.recv().await {
         let serializer = get_serializer();
+        let location = committer.stage(location);
         let writer = create_writer(compression, &location, object_store.clone()).await?;
 
         tx_file_bundle
@@ -307,3 +338,153 @@ pub(crate) async fn stateless_multipart_put(
 
     Ok(total_count)
 }
+
+/// Makes the files written by a file sink part of the table once all of them
+/// were written successfully.
+///
+/// Appends write every file directly to its final location. For
+/// [`InsertOp::Overwrite`] the files are written to a hidden temporary
+/// location next to their final one, which isn't picked up by listing tables,
+/// and only renamed into place on [`Self::commit`]. After that the existing
+/// files that are replaced are deleted: all files of the table if it isn't
+/// partitioned, otherwise only the files of the hive style partitions that were
+/// written to (dynamic partition overwrite). If the write fails the temporary
+/// files are deleted by [`Self::abort`], leaving the table unchanged.
+pub(crate) struct FileCommitter {
+    object_store: Arc<dyn ObjectStore>,
+    insert_op: InsertOp,
+    partitioned: bool,
+    /// The files of the table when the write was planned
+    existing_files: Vec<Path>,
+    /// The temporary and final locations of the files written so far
+    staged: Vec<(Path, Path)>,
+}
+
+impl FileCommitter {
+    pub(crate) fn new(
+        config: &FileSinkConfig,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> Self {
+        Self {
+            object_store,
+            insert_op: config.insert_op,
+            partitioned: !config.table_partition_cols.is_empty(),
+            existing_files: config
+                .file_groups
+                .iter()
+                .map(|file| file.object_meta.location.clone())
+                .collect(),
+            staged: vec![],
+        }
+    }
+
+    /// Returns the location to write the file that ends up at `location` to
+    pub(crate) fn stage(&mut self, location: Path) -> Path {
+        if self.insert_op != InsertOp::Overwrite {
+            return location;
+        }
+        let staged = staging_location(&location);
+        self.staged.push((staged.clone(), location));
+        staged
+    }
+
+    /// Commits the written files if `result` is successful, otherwise aborts
+    /// the write, returning `result`
+    pub(crate) async fn finish<T>(self, result: Result<T>) -> Result<T> {
+        match result {
+            Ok(value) => {
+                self.commit().await?;
+                Ok(value)
+            }
+            Err(e) => {
+                self.abort().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Moves the written files into place and deletes the files they replace
+    pub(crate) async fn commit(self) -> Result<()> {
+        if self.insert_op != InsertOp::Overwrite {
+            return Ok(());
+        }
+
+        let written = self
+            .staged
+            .iter()
+            .map(|(_, location)| location.clone())
+            .collect::<HashSet<_>>();
+        let written_partitions = written.iter().map(parent).collect::<Vec<_>>();
+        let replaced = self
+            .existing_files
+            .into_iter()
+            .filter(|file| {
+                !written.contains(file)
+                    && (!self.partitioned
+                        || written_partitions
+                            .iter()
+                            .any(|partition| file.prefix_matches(partition)))
+            })
+            .collect::<Vec<_>>();
+
+        // Rename before deleting, so that a failure while committing never
+        // loses the existing data
+        let store = self.object_store;
+        futures::stream::iter(self.staged)
+            .map(|(staged, location)| {
+                let store = Arc::clone(&store);
+                async move { store.rename(&staged, &location).await }
+            })
+            .buffer_unordered(COMMIT_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        futures::stream::iter(replaced)
+            .map(|file| {
+                let store = Arc::clone(&store);
+                async move {
+                    match store.delete(&file).await {
+                        Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+                        Err(e) => Err(e),
+                    }
+                }
+            })
+            .buffer_unordered(COMMIT_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(())
+    }
+
+    /// Deletes the temporary files of a failed write
+    pub(crate) async fn abort(self) {
+        let store = self.object_store;
+        futures::stream::iter(self.staged)
+            .map(|(staged, _)| {
+                let store = Arc::clone(&store);
+                // Files that failed to be created don't exist, and otherwise
+                // the original error is more useful than a cleanup error
+                async move { store.delete(&staged).await.ok() }
+            })
+            .buffer_unordered(COMMIT_CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await;
+    }
+}
+
+/// Maximum number of concurrent object store requests while committing
+const COMMIT_CONCURRENCY: usize = 10;
+
+/// Returns the hidden location a file that ends up at `location` is written
+/// to until the write is committed. It doesn't end with the file extension so
+/// that listing tables ignore it.
+fn staging_location(location: &Path) -> Path {
+    let file_name = location.filename().unwrap_or_default();
+    parent(location).child(format!(".{file_name}.inprogress"))
+}
+
+/// Returns the directory containing `location`
+fn parent(location: &Path) -> Path {
+    let mut parts = location.parts().collect::<Vec<_>>();
+    parts.pop();
+    Path::from_iter(parts)
+}
@@ -17,7 +17,9 @@
 
 //! The table implementation.
 
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::{any::Any, str::FromStr, sync::Arc};
 
 use super::helpers::{expr_applicable_for_cols, pruned_partition_list, split_files};
@@ -29,6 +31,7 @@ use crate::datasource::{
         file_compression_type::FileCompressionType, FileFormat, FilePushdownSupport,
     },
     get_statistics_with_limit,
+    memory::next_table_version,
     physical_plan::{FileScanConfig, FileSinkConfig},
 };
 use crate::execution::context::SessionState;
@@ -58,6 +61,7 @@ use datafusion_physical_expr_common::sort_expr::LexRequirement;
 use futures::{future, stream, StreamExt, TryStreamExt};
 use itertools::Itertools;
 use object_store::ObjectStore;
+use parking_lot::RwLock;
 
 /// Configuration for creating a [`ListingTable`]
 #[derive(Debug, Clone)]
@@ -171,9 +175,17 @@ impl ListingTableConfig {
     /// Infer the [`SchemaRef`] based on `table_path` suffix.  Requires `self.options` to be set prior to using.
     pub async fn infer_schema(self, state: &SessionState) -> Result<Self> {
         match self.options {
-            Some(options) => {
+            Some(mut options) => {
                 let schema = if let Some(url) = self.table_paths.first() {
-                    options.infer_schema(state, url).await?
+                    let schema = options.infer_schema(state, url).await?;
+                    if options.file_sort_order.is_empty() {
+                        options.file_sort_order.extend(
+                            options
+                                .infer_file_sort_order(state, url, Arc::clone(&schema))
+                                .await?,
+                        );
+                    }
+                    schema
                 } else {
                     Arc::new(Schema::empty())
                 };
@@ -478,6 +490,31 @@ impl ListingOptions {
         Ok(schema)
     }
 
+    /// Infer the order of the rows of the files at the given path from the
+    /// ordering declared in their metadata, if all files declare the same
+    /// ordering on columns of `file_schema`.
+    ///
+    /// Like [`Self::infer_schema`], this method is called before creating
+    /// the table, whose [`Self::file_sort_order`] can be set to the result.
+    pub async fn infer_file_sort_order<'a>(
+        &'a self,
+        state: &SessionState,
+        table_path: &'a ListingTableUrl,
+        file_schema: SchemaRef,
+    ) -> Result<Option<Vec<SortExpr>>> {
+        let store = state.runtime_env().object_store(table_path)?;
+
+        let files: Vec<_> = table_path
+            .list_all_files(state, store.as_ref(), &self.file_extension)
+            .await?
+            .try_collect()
+            .await?;
+
+        self.format
+            .infer_ordering(state, &store, file_schema, &files)
+            .await
+    }
+
     /// Infers the partition columns stored in `LOCATION` and compares
     /// them with the columns provided in `PARTITIONED BY` to help prevent
     /// accidental corrupts of partitioned tables.
@@ -691,6 +728,11 @@ pub struct ListingTable {
     collected_statistics: FileStatisticsCache,
     constraints: Constraints,
     column_defaults: HashMap<String, Expr>,
+    /// Statistics computed by `ANALYZE TABLE`, for the table schema
+    analyzed_statistics: RwLock<Option<Statistics>>,
+    /// Unique across all tables, so that the versions of a recreated table
+    /// differ from the versions of the dropped one
+    id: u64,
 }
 
 impl ListingTable {
@@ -734,6 +776,8 @@ impl ListingTable {
             collected_statistics: Arc::new(DefaultFileStatisticsCache::default()),
             constraints: Constraints::empty(),
             column_defaults: HashMap::new(),
+            analyzed_statistics: RwLock::new(None),
+            id: next_table_version(),
         };
 
         Ok(table)
@@ -816,6 +860,15 @@ impl TableProvider for ListingTable {
         TableType::Base
     }
 
+    fn statistics(&self) -> Option<Statistics> {
+        self.analyzed_statistics.read().clone()
+    }
+
+    fn set_statistics(&self, statistics: Statistics) -> Result<()> {
+        *self.analyzed_statistics.write() = Some(statistics);
+        Ok(())
+    }
+
     async fn scan(
         &self,
         state: &dyn Session,
@@ -843,9 +896,12 @@ impl TableProvider for ListingTable {
             });
         // TODO (https://github.com/apache/datafusion/issues/11600) remove downcast_ref from here?
         let session_state = state.as_any().downcast_ref::<SessionState>().unwrap();
-        let (mut partitioned_file_lists, statistics) = self
+        let (mut partitioned_file_lists, mut statistics) = self
             .list_files_for_scan(session_state, &partition_filters, limit)
             .await?;
+        if let Some(analyzed) = self.analyzed_statistics.read().as_ref() {
+            statistics = statistics.fill_absent(analyzed);
+        }
 
         // if no files need to be read, return an `EmptyExec`
         if partitioned_file_lists.is_empty() {
@@ -1052,6 +1108,45 @@ impl TableProvider for ListingTable {
     fn get_column_default(&self, column: &str) -> Option<&Expr> {
         self.column_defaults.get(column)
     }
+
+    /// The version is a hash of the location, size, modification time and
+    /// entity tag of the listed files, so it changes when files are added,
+    /// removed or rewritten, by DataFusion or not. When a list files cache is
+    /// configured in the [`CacheManager`], changes are only seen once the
+    /// cached listing is refreshed.
+    ///
+    /// [`CacheManager`]: datafusion_execution::cache::cache_manager::CacheManager
+    async fn version(&self, state: &dyn Session) -> Result<Option<u64>> {
+        let Some(url) = self.table_paths.first() else {
+            return Ok(Some(self.id));
+        };
+        let session_state = state.as_any().downcast_ref::<SessionState>().unwrap();
+        let store = session_state.runtime_env().object_store(url)?;
+        let mut files = vec![];
+        for table_path in &self.table_paths {
+            let mut listed = table_path
+                .list_all_files(
+                    session_state,
+                    store.as_ref(),
+                    &self.options.file_extension,
+                )
+                .await?;
+            while let Some(meta) = listed.try_next().await? {
+                files.push(meta);
+            }
+        }
+        files.sort_unstable_by(|a, b| a.location.cmp(&b.location));
+
+        let mut hasher = DefaultHasher::new();
+        self.id.hash(&mut hasher);
+        for meta in files {
+            meta.location.hash(&mut hasher);
+            meta.size.hash(&mut hasher);
+            meta.last_modified.hash(&mut hasher);
+            meta.e_tag.hash(&mut hasher);
+        }
+        Ok(Some(hasher.finish()))
+    }
 }
 
 impl ListingTable {
@@ -1345,8 +1440,8 @@ mod tests {
         let path = String::from("table/p1=v1/file.avro");
         register_test_store(&ctx, &[(&path, 100)]);
 
-        let opt = ListingOptions::new(Arc::new(AvroFormat {}))
-            .with_file_extension(AvroFormat.get_ext())
+        let opt = ListingOptions::new(Arc::new(AvroFormat::default()))
+            .with_file_extension(AvroFormat::default().get_ext())
             .with_table_partition_cols(vec![(String::from("p1"), DataType::Utf8)])
             .with_target_partitions(4);
 
@@ -1589,7 +1684,7 @@ mod tests {
         let ctx = SessionContext::new();
         register_test_store(&ctx, &files.iter().map(|f| (*f, 10)).collect::<Vec<_>>());
 
-        let format = AvroFormat {};
+        let format = AvroFormat::default();
 
         let opt = ListingOptions::new(Arc::new(format))
             .with_file_extension_opt(file_ext)
@@ -1623,7 +1718,7 @@ mod tests {
         let ctx = SessionContext::new();
         register_test_store(&ctx, &files.iter().map(|f| (*f, 10)).collect::<Vec<_>>());
 
-        let format = AvroFormat {};
+        let format = AvroFormat::default();
 
         let opt = ListingOptions::new(Arc::new(format))
             .with_file_extension_opt(file_ext)
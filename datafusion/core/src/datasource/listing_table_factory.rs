@@ -120,6 +120,7 @@ impl TableProviderFactory for ListingTableFactory {
             .validate_partitions(session_state, &table_path)
             .await?;
 
+        let mut file_sort_order = cmd.order_exprs.clone();
         let resolved_schema = match provided_schema {
             // We will need to check the table columns against the schema
             // this is done so that we can do an ORDER BY for external table creation
@@ -141,12 +142,26 @@ impl TableProviderFactory for ListingTableFactory {
                     }
                 }
 
+                // Without a declared WITH ORDER, use the ordering the files
+                // declare in their metadata, if any
+                if file_sort_order.is_empty() {
+                    file_sort_order.extend(
+                        options
+                            .infer_file_sort_order(
+                                session_state,
+                                &table_path,
+                                Arc::clone(&schema),
+                            )
+                            .await?,
+                    );
+                }
+
                 schema
             }
             Some(s) => s,
         };
         let config = ListingTableConfig::new(table_path)
-            .with_listing_options(options.with_file_sort_order(cmd.order_exprs.clone()))
+            .with_listing_options(options.with_file_sort_order(file_sort_order))
             .with_schema(resolved_schema);
         let provider = ListingTable::try_new(config)?
             .with_cache(state.runtime_env().cache_manager.get_file_statistic_cache());
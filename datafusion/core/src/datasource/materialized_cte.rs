@@ -0,0 +1,114 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! MaterializedCte implementation used for common table expressions computed once
+
+use std::sync::Arc;
+use std::{any::Any, borrow::Cow};
+
+use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+use datafusion_catalog::Session;
+use datafusion_physical_plan::shared_scan::{SharedScanExec, Spool};
+
+use crate::{
+    error::Result,
+    logical_expr::{Expr, LogicalPlan, TableProviderFilterPushDown},
+    physical_plan::ExecutionPlan,
+};
+
+use crate::datasource::{TableProvider, TableType};
+
+/// A common table expression whose results are computed once and shared by
+/// all the references to it, instead of being recomputed for each of them.
+///
+/// Every scan plans the CTE again and returns a [`SharedScanExec`] reading
+/// from the same [`Spool`], so the scans executed together compute the CTE
+/// once while a plan created later still sees the current data.
+/// See here for more details: www.postgresql.org/docs/current/queries-with.html#QUERIES-WITH-CTE-MATERIALIZATION
+#[derive(Debug)]
+pub struct MaterializedCte {
+    /// The logical plan computing the CTE
+    plan: LogicalPlan,
+    /// The schema of the CTE
+    table_schema: SchemaRef,
+    /// The spool shared by all the scans of the CTE
+    spool: Arc<Spool>,
+}
+
+impl MaterializedCte {
+    /// construct a new MaterializedCte computing the results of `plan`
+    pub fn new(name: &str, plan: LogicalPlan) -> Self {
+        let table_schema = Arc::new(plan.schema().as_arrow().clone());
+        Self {
+            plan,
+            table_schema,
+            spool: Arc::new(Spool::new(name)),
+        }
+    }
+
+    /// The logical plan computing the CTE
+    pub fn plan(&self) -> &LogicalPlan {
+        &self.plan
+    }
+}
+
+#[async_trait]
+impl TableProvider for MaterializedCte {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_logical_plan(&self) -> Option<Cow<LogicalPlan>> {
+        // the plan must not be inlined in the referencing queries
+        None
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.table_schema)
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Temporary
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let input = state.create_physical_plan(&self.plan).await?;
+        Ok(Arc::new(SharedScanExec::try_new(
+            Arc::clone(&self.spool),
+            input,
+            projection.cloned(),
+        )?))
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        // the filters of one reference do not apply to the others
+        Ok(vec![
+            TableProviderFilterPushDown::Unsupported;
+            filters.len()
+        ])
+    }
+}
@@ -20,6 +20,7 @@
 use std::any::Any;
 use std::collections::HashMap;
 use std::fmt::{self, Debug};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use crate::datasource::{TableProvider, TableType};
@@ -38,7 +39,9 @@ use crate::physical_planner::create_physical_sort_exprs;
 use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
 use datafusion_catalog::Session;
-use datafusion_common::{not_impl_err, plan_err, Constraints, DFSchema, SchemaExt};
+use datafusion_common::{
+    not_impl_err, plan_err, Constraints, DFSchema, SchemaExt, Statistics,
+};
 use datafusion_execution::TaskContext;
 use datafusion_expr::dml::InsertOp;
 use datafusion_expr::SortExpr;
@@ -54,6 +57,13 @@ use tokio::task::JoinSet;
 /// Type alias for partition data
 pub type PartitionData = Arc<RwLock<Vec<RecordBatch>>>;
 
+/// Returns a new version for the data of a table, unique across all tables
+/// so that a recreated table never reuses the version of a dropped one
+pub(crate) fn next_table_version() -> u64 {
+    static NEXT_VERSION: AtomicU64 = AtomicU64::new(0);
+    NEXT_VERSION.fetch_add(1, Ordering::Relaxed)
+}
+
 /// In-memory data source for presenting a `Vec<RecordBatch>` as a
 /// data source that can be queried by DataFusion. This allows data to
 /// be pre-loaded into memory and then repeatedly queried without
@@ -67,6 +77,10 @@ pub struct MemTable {
     /// Optional pre-known sort order(s). Must be `SortExpr`s.
     /// inserting data into this table removes the order
     pub sort_order: Arc<Mutex<Vec<Vec<SortExpr>>>>,
+    /// Statistics computed by `ANALYZE TABLE`
+    analyzed_statistics: Mutex<Option<Statistics>>,
+    /// Version of the data, changed by every insert
+    version: Arc<AtomicU64>,
 }
 
 impl MemTable {
@@ -92,6 +106,8 @@ impl MemTable {
             constraints: Constraints::empty(),
             column_defaults: HashMap::new(),
             sort_order: Arc::new(Mutex::new(vec![])),
+            analyzed_statistics: Mutex::new(None),
+            version: Arc::new(AtomicU64::new(next_table_version())),
         })
     }
 
@@ -207,6 +223,15 @@ impl TableProvider for MemTable {
         TableType::Base
     }
 
+    fn statistics(&self) -> Option<Statistics> {
+        self.analyzed_statistics.lock().clone()
+    }
+
+    fn set_statistics(&self, statistics: Statistics) -> Result<()> {
+        *self.analyzed_statistics.lock() = Some(statistics);
+        Ok(())
+    }
+
     async fn scan(
         &self,
         state: &dyn Session,
@@ -226,6 +251,10 @@ impl TableProvider for MemTable {
         let show_sizes = state.config_options().explain.show_sizes;
         exec = exec.with_show_sizes(show_sizes);
 
+        if let Some(statistics) = self.analyzed_statistics.lock().clone() {
+            exec = exec.try_with_statistics(statistics)?;
+        }
+
         // add sort information if present
         let sort_order = self.sort_order.lock();
         if !sort_order.is_empty() {
@@ -293,7 +322,10 @@ impl TableProvider for MemTable {
         if insert_op != InsertOp::Append {
             return not_impl_err!("{insert_op} not implemented for MemoryTable yet");
         }
-        let sink = Arc::new(MemSink::new(self.batches.clone()));
+        let sink = Arc::new(MemSink::new(
+            self.batches.clone(),
+            Arc::clone(&self.version),
+        ));
         Ok(Arc::new(DataSinkExec::new(
             input,
             sink,
@@ -305,12 +337,18 @@ impl TableProvider for MemTable {
     fn get_column_default(&self, column: &str) -> Option<&Expr> {
         self.column_defaults.get(column)
     }
+
+    async fn version(&self, _state: &dyn Session) -> Result<Option<u64>> {
+        Ok(Some(self.version.load(Ordering::Acquire)))
+    }
 }
 
 /// Implements for writing to a [`MemTable`]
 struct MemSink {
     /// Target locations for writing data
     batches: Vec<PartitionData>,
+    /// Version of the table, changed after writing
+    version: Arc<AtomicU64>,
 }
 
 impl Debug for MemSink {
@@ -333,8 +371,8 @@ impl DisplayAs for MemSink {
 }
 
 impl MemSink {
-    fn new(batches: Vec<PartitionData>) -> Self {
-        Self { batches }
+    fn new(batches: Vec<PartitionData>, version: Arc<AtomicU64>) -> Self {
+        Self { batches, version }
     }
 }
 
@@ -371,6 +409,7 @@ impl DataSink for MemSink {
             // Append all the new batches in one go to minimize locking overhead
             target.write().await.append(&mut batches);
         }
+        self.version.store(next_table_version(), Ordering::Release);
 
         Ok(row_count as u64)
     }
@@ -19,6 +19,7 @@
 //!
 //! [`ListingTable`]: crate::datasource::listing::ListingTable
 
+pub mod arrow_to_avro;
 pub mod avro_to_arrow;
 pub mod cte_worktable;
 pub mod default_table_source;
@@ -28,6 +29,7 @@ pub mod file_format;
 pub mod function;
 pub mod listing;
 pub mod listing_table_factory;
+pub mod materialized_cte;
 pub mod memory;
 pub mod physical_plan;
 pub mod provider;
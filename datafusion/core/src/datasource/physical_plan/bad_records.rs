@@ -0,0 +1,396 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Per-record error handling for line oriented formats (CSV and NDJSON)
+
+use std::fs::{File, OpenOptions};
+use std::io::BufRead;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+
+use crate::error::Result;
+use crate::physical_plan::metrics::Count;
+
+use arrow::array::{new_null_array, ArrayRef, RecordBatch, StringArray, UInt64Array};
+use arrow::compute::concat_batches;
+use arrow::compute::kernels::cast::{cast_with_options, CastOptions};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::error::ArrowError;
+use arrow::json::LineDelimitedWriter;
+use bytes::{Buf, Bytes};
+use datafusion_common::exec_datafusion_err;
+use datafusion_common::parsers::OnError;
+use futures::stream::BoxStream;
+use futures::{ready, StreamExt};
+
+/// Shared state for the records rejected while scanning a single plan.
+///
+/// The error count is shared by all partitions so that `max_errors` applies
+/// to the scan as a whole. Rejected records are optionally appended to a
+/// local file as newline delimited JSON objects with the fields `file`,
+/// `line`, `reason` and `record`.
+#[derive(Debug)]
+pub(crate) struct BadRecords {
+    on_error: OnError,
+    max_errors: Option<usize>,
+    path: Option<String>,
+    count: AtomicUsize,
+    sink: Mutex<Option<File>>,
+}
+
+impl BadRecords {
+    /// Returns `None` if malformed records should fail the query
+    pub fn try_new(
+        on_error: OnError,
+        max_errors: Option<usize>,
+        path: Option<String>,
+    ) -> Option<Arc<Self>> {
+        (on_error != OnError::Fail).then(|| {
+            Arc::new(Self {
+                on_error,
+                max_errors,
+                path,
+                count: AtomicUsize::new(0),
+                sink: Mutex::new(None),
+            })
+        })
+    }
+
+    /// How malformed records are handled
+    pub fn on_error(&self) -> OnError {
+        self.on_error
+    }
+
+    /// Record a rejected `record` found at `line` of `file`, returning an
+    /// error once more than `max_errors` records have been rejected.
+    fn record(&self, file: &str, line: usize, reason: &str, record: &[u8]) -> Result<()> {
+        let count = self.count.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(max_errors) = self.max_errors {
+            if count > max_errors {
+                return Err(exec_datafusion_err!(
+                    "Exceeded the maximum of {max_errors} malformed records, \
+                     last at line {line} of {file}: {reason}"
+                ));
+            }
+        }
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let batch = RecordBatch::try_from_iter([
+            ("file", Arc::new(StringArray::from(vec![file])) as ArrayRef),
+            ("line", Arc::new(UInt64Array::from(vec![line as u64]))),
+            ("reason", Arc::new(StringArray::from(vec![reason]))),
+            (
+                "record",
+                Arc::new(StringArray::from_iter_values([String::from_utf8_lossy(
+                    record,
+                )])),
+            ),
+        ])?;
+
+        let mut sink = self.sink.lock().unwrap();
+        if sink.is_none() {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| {
+                    exec_datafusion_err!("Failed to open bad records file {path}: {e}")
+                })?;
+            *sink = Some(file);
+        }
+        let mut writer = LineDelimitedWriter::new(sink.as_mut().unwrap());
+        writer.write(&batch)?;
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+/// Parses complete lines of a line oriented format into a [`RecordBatch`]
+pub(crate) trait RecordParser: Send + 'static {
+    /// The schema of the records in the file
+    fn file_schema(&self) -> &SchemaRef;
+
+    /// The schema of the batches returned by [`Self::parse`]
+    fn output_schema(&self) -> SchemaRef;
+
+    /// Parse `data`, consisting of one or more terminated lines.
+    ///
+    /// If `lenient_schema` is provided the records are read with it in place
+    /// of [`Self::file_schema`], converting primitive values to strings.
+    fn parse(
+        &self,
+        data: &[u8],
+        lenient_schema: Option<SchemaRef>,
+    ) -> Result<RecordBatch, ArrowError>;
+}
+
+/// A decoder that splits its input into lines and isolates the lines that
+/// fail to parse, so that they can be skipped or read as nulls according to
+/// the configured [`OnError`].
+///
+/// Lines are first parsed a batch at a time and only parsed individually if
+/// the batch contains a malformed record. This relies on every record being
+/// contained in a single line.
+pub(crate) struct BadRecordDecoder<P> {
+    parser: P,
+    bad_records: Arc<BadRecords>,
+    metric: Count,
+    file: String,
+    batch_size: usize,
+    /// The line terminator, `None` for `\n` with an optional preceding `\r`
+    terminator: Option<u8>,
+    has_header: bool,
+    comment: Option<u8>,
+    /// The number of lines seen so far, including headers and comments
+    line_number: usize,
+    /// The trailing bytes of the input that are not yet terminated
+    partial: Vec<u8>,
+    /// The buffered lines, each followed by its terminator
+    data: Vec<u8>,
+    /// The line number and position in `data` of each buffered line
+    lines: Vec<(usize, Range<usize>)>,
+}
+
+impl<P: RecordParser> BadRecordDecoder<P> {
+    pub fn new(
+        parser: P,
+        bad_records: Arc<BadRecords>,
+        metric: Count,
+        file: String,
+        batch_size: usize,
+    ) -> Self {
+        Self {
+            parser,
+            bad_records,
+            metric,
+            file,
+            batch_size,
+            terminator: None,
+            has_header: false,
+            comment: None,
+            line_number: 0,
+            partial: vec![],
+            data: vec![],
+            lines: vec![],
+        }
+    }
+
+    pub fn with_terminator(mut self, terminator: Option<u8>) -> Self {
+        self.terminator = terminator;
+        self
+    }
+
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    pub fn with_comment(mut self, comment: Option<u8>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    /// Buffer the complete lines in `buf`, returning the number of bytes
+    /// consumed. An empty `buf` marks the end of the input.
+    ///
+    /// Returns `0` once `batch_size` lines are buffered, at which point
+    /// [`Self::flush`] should be called.
+    pub fn decode(&mut self, buf: &[u8]) -> Result<usize, ArrowError> {
+        if buf.is_empty() {
+            if !self.partial.is_empty() {
+                let line = std::mem::take(&mut self.partial);
+                self.push_line(&line);
+            }
+            return Ok(0);
+        }
+
+        let terminator = self.terminator.unwrap_or(b'\n');
+        let mut consumed = 0;
+        while self.lines.len() < self.batch_size && consumed < buf.len() {
+            let rest = &buf[consumed..];
+            match rest.iter().position(|b| *b == terminator) {
+                Some(end) if self.partial.is_empty() => {
+                    self.push_line(&rest[..end]);
+                    consumed += end + 1;
+                }
+                Some(end) => {
+                    let mut line = std::mem::take(&mut self.partial);
+                    line.extend_from_slice(&rest[..end]);
+                    self.push_line(&line);
+                    consumed += end + 1;
+                }
+                None => {
+                    self.partial.extend_from_slice(rest);
+                    consumed = buf.len();
+                }
+            }
+        }
+        Ok(consumed)
+    }
+
+    fn push_line(&mut self, mut line: &[u8]) {
+        self.line_number += 1;
+        if self.terminator.is_none() {
+            line = line.strip_suffix(b"\r").unwrap_or(line);
+        }
+        let is_comment =
+            matches!((self.comment, line.first()), (Some(c), Some(f)) if c == *f);
+        if line.is_empty() || is_comment || (self.has_header && self.line_number == 1) {
+            return;
+        }
+
+        let start = self.data.len();
+        self.data.extend_from_slice(line);
+        self.lines.push((self.line_number, start..self.data.len()));
+        self.data.push(self.terminator.unwrap_or(b'\n'));
+    }
+
+    /// Parse the buffered lines, returning `None` if there are none
+    pub fn flush(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
+        if self.lines.is_empty() {
+            return Ok(None);
+        }
+        let lines = std::mem::take(&mut self.lines);
+        let data = std::mem::take(&mut self.data);
+
+        if let Ok(batch) = self.parser.parse(&data, None) {
+            return Ok(Some(batch));
+        }
+
+        let mut batches = Vec::with_capacity(lines.len());
+        for (line_number, range) in lines {
+            // Include the terminator so the record is delimited
+            let record = &data[range.start..range.end + 1];
+            match self.parser.parse(record, None) {
+                Ok(batch) => batches.push(batch),
+                Err(e) => {
+                    self.metric.add(1);
+                    self.bad_records
+                        .record(&self.file, line_number, &e.to_string(), &data[range])
+                        .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+                    if self.bad_records.on_error == OnError::Null {
+                        batches.push(self.parse_lenient(record)?);
+                    }
+                }
+            }
+        }
+        concat_batches(&self.parser.output_schema(), &batches).map(Some)
+    }
+
+    /// Parse a malformed record, replacing the values that cannot be read
+    /// with nulls. If the record cannot be split into its fields, all of its
+    /// values are null.
+    fn parse_lenient(&self, record: &[u8]) -> Result<RecordBatch, ArrowError> {
+        let output_schema = self.parser.output_schema();
+        let lenient_schema = lenient_schema(self.parser.file_schema());
+        let columns = match self.parser.parse(record, Some(lenient_schema)) {
+            Ok(batch) if batch.num_rows() == 1 => {
+                let options = CastOptions {
+                    safe: true,
+                    ..Default::default()
+                };
+                batch
+                    .columns()
+                    .iter()
+                    .zip(output_schema.fields())
+                    .map(|(column, field)| {
+                        cast_with_options(column, field.data_type(), &options)
+                            .unwrap_or_else(|_| new_null_array(field.data_type(), 1))
+                    })
+                    .collect()
+            }
+            _ => output_schema
+                .fields()
+                .iter()
+                .map(|field| new_null_array(field.data_type(), 1))
+                .collect(),
+        };
+        RecordBatch::try_new(output_schema, columns)
+    }
+
+    /// Decode all of the records in `reader`
+    pub fn decode_reader<R: BufRead + Send + 'static>(
+        mut self,
+        mut reader: R,
+    ) -> impl Iterator<Item = Result<RecordBatch, ArrowError>> + Send {
+        std::iter::from_fn(move || {
+            loop {
+                let buf = match reader.fill_buf() {
+                    Ok(buf) => buf,
+                    Err(e) => return Some(Err(e.into())),
+                };
+                let decoded = match self.decode(buf) {
+                    Ok(decoded) => decoded,
+                    Err(e) => return Some(Err(e)),
+                };
+                reader.consume(decoded);
+                if decoded == 0 {
+                    break;
+                }
+            }
+            self.flush().transpose()
+        })
+    }
+
+    /// Decode all of the records in `input`
+    pub fn decode_stream(
+        mut self,
+        input: BoxStream<'static, Result<Bytes>>,
+    ) -> BoxStream<'static, Result<RecordBatch, ArrowError>> {
+        let mut input = input.fuse();
+        let mut buffered = Bytes::new();
+        futures::stream::poll_fn(move |cx| {
+            loop {
+                if buffered.is_empty() {
+                    match ready!(input.poll_next_unpin(cx)) {
+                        Some(Ok(b)) => buffered = b,
+                        Some(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+                        None => {}
+                    };
+                }
+                let decoded = match self.decode(buffered.as_ref()) {
+                    Ok(0) => break,
+                    Ok(decoded) => decoded,
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                };
+                buffered.advance(decoded);
+            }
+            Poll::Ready(self.flush().transpose())
+        })
+        .boxed()
+    }
+}
+
+/// Returns `schema` with its primitive fields read as nullable strings, so
+/// that they can be cast to their declared types one value at a time
+fn lenient_schema(schema: &SchemaRef) -> SchemaRef {
+    let fields = schema.fields().iter().map(|field| {
+        if field.data_type().is_nested() {
+            Arc::new(field.as_ref().clone().with_nullable(true))
+        } else {
+            Arc::new(Field::new(field.name(), DataType::Utf8, true))
+        }
+    });
+    Arc::new(Schema::new_with_metadata(
+        fields.collect::<Vec<_>>(),
+        schema.metadata().clone(),
+    ))
+}
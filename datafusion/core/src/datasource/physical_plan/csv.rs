@@ -18,10 +18,12 @@
 //! Execution plan for reading CSV files
 
 use std::any::Any;
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::sync::Arc;
 use std::task::Poll;
 
+use super::bad_records::{BadRecordDecoder, BadRecords, RecordParser};
+use super::transcode::{transcode_stream, CsvTranscoder, LineFormat, TranscodingReader};
 use super::{calculate_range, FileGroupPartitioner, FileScanConfig, RangeCalculation};
 use crate::datasource::file_format::file_compression_type::FileCompressionType;
 use crate::datasource::listing::{FileRange, ListingTableUrl, PartitionedFile};
@@ -30,19 +32,31 @@ use crate::datasource::physical_plan::file_stream::{
 };
 use crate::datasource::physical_plan::FileMeta;
 use crate::error::{DataFusionError, Result};
-use crate::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use crate::physical_plan::metrics::{
+    Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+};
 use crate::physical_plan::{
     DisplayAs, DisplayFormatType, ExecutionMode, ExecutionPlan, ExecutionPlanProperties,
     Partitioning, PlanProperties, SendableRecordBatchStream, Statistics,
 };
 
+use arrow::array::{
+    ArrayRef, ArrowPrimitiveType, AsArray, PrimitiveArray, RecordBatch, StringArray,
+};
 use arrow::csv;
-use arrow::datatypes::SchemaRef;
+use arrow::datatypes::{
+    DataType, Date32Type, Date64Type, Fields, Schema, SchemaRef, TimeUnit,
+    TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+    TimestampSecondType,
+};
+use arrow::error::ArrowError;
 use datafusion_common::config::ConfigOptions;
+use datafusion_common::parsers::OnError;
 use datafusion_execution::TaskContext;
 use datafusion_physical_expr::{EquivalenceProperties, LexOrdering};
 
 use bytes::{Buf, Bytes};
+use chrono::{NaiveDate, NaiveDateTime};
 use futures::{ready, StreamExt, TryStreamExt};
 use object_store::buffered::BufWriter;
 use object_store::{GetOptions, GetResultPayload, ObjectStore};
@@ -76,11 +90,16 @@ pub struct CsvExec {
     projected_statistics: Statistics,
     has_header: bool,
     delimiter: u8,
+    delimiter_sequence: Option<String>,
     quote: u8,
     terminator: Option<u8>,
     escape: Option<u8>,
     comment: Option<u8>,
     newlines_in_values: bool,
+    date_format: Option<String>,
+    timestamp_format: Option<String>,
+    /// Malformed records shared by all partitions, `None` if they fail the scan
+    bad_records: Option<Arc<BadRecords>>,
     /// Execution metrics
     metrics: ExecutionPlanMetricsSet,
     /// Compression type of the file associated with CsvExec
@@ -98,11 +117,17 @@ pub struct CsvExecBuilder {
     // TODO: it seems like these format options could be reused across all the various CSV config
     has_header: bool,
     delimiter: u8,
+    delimiter_sequence: Option<String>,
     quote: u8,
     terminator: Option<u8>,
     escape: Option<u8>,
     comment: Option<u8>,
     newlines_in_values: bool,
+    date_format: Option<String>,
+    timestamp_format: Option<String>,
+    on_error: OnError,
+    max_errors: Option<usize>,
+    bad_records_path: Option<String>,
 }
 
 impl CsvExecBuilder {
@@ -113,11 +138,17 @@ impl CsvExecBuilder {
             // TODO: these defaults are duplicated from `CsvOptions` - should they be computed?
             has_header: false,
             delimiter: b',',
+            delimiter_sequence: None,
             quote: b'"',
             terminator: None,
             escape: None,
             comment: None,
             newlines_in_values: false,
+            date_format: None,
+            timestamp_format: None,
+            on_error: OnError::Fail,
+            max_errors: None,
+            bad_records_path: None,
             file_compression_type: FileCompressionType::UNCOMPRESSED,
         }
     }
@@ -182,6 +213,63 @@ impl CsvExecBuilder {
         self
     }
 
+    /// Set a delimiter of multiple bytes, such as `||`, used instead of the
+    /// single byte delimiter.
+    ///
+    /// Values must not contain newlines, so this cannot be combined with
+    /// `newlines_in_values`. The default is `None`.
+    pub fn with_delimiter_sequence(mut self, delimiter_sequence: Option<String>) -> Self {
+        self.delimiter_sequence = delimiter_sequence;
+        self
+    }
+
+    /// Set the [chrono format](https://docs.rs/chrono/latest/chrono/format/strftime/index.html)
+    /// of the date columns.
+    ///
+    /// The default is `None`, dates are read in the ISO 8601 format.
+    pub fn with_date_format(mut self, date_format: Option<String>) -> Self {
+        self.date_format = date_format;
+        self
+    }
+
+    /// Set the [chrono format](https://docs.rs/chrono/latest/chrono/format/strftime/index.html)
+    /// of the timestamp columns without a time zone.
+    ///
+    /// The default is `None`, timestamps are read in the RFC 3339 format.
+    pub fn with_timestamp_format(mut self, timestamp_format: Option<String>) -> Self {
+        self.timestamp_format = timestamp_format;
+        self
+    }
+
+    /// Set how rows that cannot be parsed are handled.
+    ///
+    /// With [`OnError::Skip`] or [`OnError::Null`] each row must be contained
+    /// in a single line, so this cannot be combined with `newlines_in_values`.
+    ///
+    /// The default is [`OnError::Fail`].
+    pub fn with_on_error(mut self, on_error: OnError) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    /// Set the maximum number of malformed rows tolerated before the scan
+    /// fails. Only used when malformed rows are skipped or read as nulls.
+    ///
+    /// The default is `None`, meaning no limit.
+    pub fn with_max_errors(mut self, max_errors: Option<usize>) -> Self {
+        self.max_errors = max_errors;
+        self
+    }
+
+    /// Set a local file that malformed rows are appended to, as newline
+    /// delimited JSON objects with the file, line number, reason and record.
+    ///
+    /// The default is `None`.
+    pub fn with_bad_records_path(mut self, bad_records_path: Option<String>) -> Self {
+        self.bad_records_path = bad_records_path;
+        self
+    }
+
     /// Set the file compression type.
     ///
     /// The default is [`FileCompressionType::UNCOMPRESSED`].
@@ -201,11 +289,17 @@ impl CsvExecBuilder {
             file_compression_type,
             has_header,
             delimiter,
+            delimiter_sequence,
             quote,
             terminator,
             escape,
             comment,
             newlines_in_values,
+            date_format,
+            timestamp_format,
+            on_error,
+            max_errors,
+            bad_records_path,
         } = self;
 
         let (projected_schema, projected_statistics, projected_output_ordering) =
@@ -221,10 +315,14 @@ impl CsvExecBuilder {
             projected_statistics,
             has_header,
             delimiter,
+            delimiter_sequence,
             quote,
             terminator,
             escape,
             newlines_in_values,
+            date_format,
+            timestamp_format,
+            bad_records: BadRecords::try_new(on_error, max_errors, bad_records_path),
             metrics: ExecutionPlanMetricsSet::new(),
             file_compression_type,
             cache,
@@ -311,6 +409,14 @@ impl CsvExec {
         self.newlines_in_values
     }
 
+    /// How rows that cannot be parsed are handled
+    pub fn on_error(&self) -> OnError {
+        self.bad_records
+            .as_ref()
+            .map(|bad_records| bad_records.on_error())
+            .unwrap_or_default()
+    }
+
     fn output_partitioning_helper(file_scan_config: &FileScanConfig) -> Partitioning {
         Partitioning::UnknownPartitioning(file_scan_config.file_groups.len())
     }
@@ -348,7 +454,11 @@ impl DisplayAs for CsvExec {
     ) -> std::fmt::Result {
         write!(f, "CsvExec: ")?;
         self.base_config.fmt_as(t, f)?;
-        write!(f, ", has_header={}", self.has_header)
+        write!(f, ", has_header={}", self.has_header)?;
+        if let Some(bad_records) = &self.bad_records {
+            write!(f, ", on_error={}", bad_records.on_error())?;
+        }
+        Ok(())
     }
 }
 
@@ -381,7 +491,8 @@ impl ExecutionPlan for CsvExec {
     /// Redistribute files across partitions according to their size
     /// See comments on [`FileGroupPartitioner`] for more detail.
     ///
-    /// Return `None` if can't get repartitioned (empty, compressed file, or `newlines_in_values` set).
+    /// Return `None` if can't get repartitioned (empty, compressed file, `newlines_in_values`
+    /// set, or malformed rows are tolerated, which requires line numbers from the file start).
     fn repartitioned(
         &self,
         target_partitions: usize,
@@ -389,7 +500,10 @@ impl ExecutionPlan for CsvExec {
     ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
         let repartition_file_min_size = config.optimizer.repartition_file_min_size;
         // Parallel execution on compressed CSV files or files that must support newlines in values is not supported yet.
-        if self.file_compression_type.is_compressed() || self.newlines_in_values {
+        if self.file_compression_type.is_compressed()
+            || self.newlines_in_values
+            || self.bad_records.is_some()
+        {
             return Ok(None);
         }
 
@@ -424,15 +538,24 @@ impl ExecutionPlan for CsvExec {
             file_projection: self.base_config.file_column_projection_indices(),
             has_header: self.has_header,
             delimiter: self.delimiter,
+            delimiter_sequence: self.delimiter_sequence.clone(),
             quote: self.quote,
             escape: self.escape,
             terminator: self.terminator,
             object_store,
             comment: self.comment,
+            date_format: self.date_format.clone(),
+            timestamp_format: self.timestamp_format.clone(),
+        });
+        let bad_records = self.bad_records.as_ref().map(|bad_records| {
+            let metric =
+                MetricBuilder::new(&self.metrics).counter("bad_records", partition);
+            (Arc::clone(bad_records), metric)
         });
         let opener = CsvOpener {
             config,
             file_compression_type: self.file_compression_type.to_owned(),
+            bad_records,
         };
         let stream =
             FileStream::new(&self.base_config, partition, opener, &self.metrics)?;
@@ -459,11 +582,15 @@ impl ExecutionPlan for CsvExec {
             projected_statistics: self.projected_statistics.clone(),
             has_header: self.has_header,
             delimiter: self.delimiter,
+            delimiter_sequence: self.delimiter_sequence.clone(),
             quote: self.quote,
             escape: self.escape,
             terminator: self.terminator,
             comment: self.comment,
             newlines_in_values: self.newlines_in_values,
+            date_format: self.date_format.clone(),
+            timestamp_format: self.timestamp_format.clone(),
+            bad_records: self.bad_records.clone(),
             metrics: self.metrics.clone(),
             file_compression_type: self.file_compression_type,
             cache: self.cache.clone(),
@@ -479,11 +606,14 @@ pub struct CsvConfig {
     file_projection: Option<Vec<usize>>,
     has_header: bool,
     delimiter: u8,
+    delimiter_sequence: Option<String>,
     quote: u8,
     terminator: Option<u8>,
     escape: Option<u8>,
     object_store: Arc<dyn ObjectStore>,
     comment: Option<u8>,
+    date_format: Option<String>,
+    timestamp_format: Option<String>,
 }
 
 impl CsvConfig {
@@ -506,13 +636,34 @@ impl CsvConfig {
             file_projection,
             has_header,
             delimiter,
+            delimiter_sequence: None,
             quote,
             terminator,
             escape: None,
             object_store,
             comment,
+            date_format: None,
+            timestamp_format: None,
         }
     }
+
+    /// Separate values with a sequence of multiple bytes instead of `delimiter`
+    pub fn with_delimiter_sequence(mut self, delimiter_sequence: Option<String>) -> Self {
+        self.delimiter_sequence = delimiter_sequence;
+        self
+    }
+
+    /// Parse dates with this [chrono format](https://docs.rs/chrono/latest/chrono/format/strftime/index.html)
+    pub fn with_date_format(mut self, date_format: Option<String>) -> Self {
+        self.date_format = date_format;
+        self
+    }
+
+    /// Parse timestamps without a time zone with this [chrono format](https://docs.rs/chrono/latest/chrono/format/strftime/index.html)
+    pub fn with_timestamp_format(mut self, timestamp_format: Option<String>) -> Self {
+        self.timestamp_format = timestamp_format;
+        self
+    }
 }
 
 impl CsvConfig {
@@ -521,8 +672,12 @@ impl CsvConfig {
     }
 
     fn builder(&self) -> csv::ReaderBuilder {
-        let mut builder = csv::ReaderBuilder::new(self.file_schema.clone())
-            .with_delimiter(self.delimiter)
+        let delimiter = match self.transcoder() {
+            Some(_) => CsvTranscoder::DELIMITER,
+            None => self.delimiter,
+        };
+        let mut builder = csv::ReaderBuilder::new(self.read_schema())
+            .with_delimiter(delimiter)
             .with_batch_size(self.batch_size)
             .with_header(self.has_header)
             .with_quote(self.quote);
@@ -541,12 +696,150 @@ impl CsvConfig {
 
         builder
     }
+
+    /// Returns the [`CsvTranscoder`] converting the values separated by the
+    /// delimiter sequence to comma separated values, if set
+    fn transcoder(&self) -> Option<CsvTranscoder> {
+        let delimiter = self.delimiter_sequence.as_ref()?;
+        let format = LineFormat::Delimited {
+            delimiter: delimiter.as_bytes().to_vec(),
+            escape: self.escape,
+        };
+        Some(CsvTranscoder::new(format, self.quote))
+    }
+
+    /// Returns the custom format of the values of `data_type`, if any
+    fn custom_format(&self, data_type: &DataType) -> Option<&str> {
+        match data_type {
+            DataType::Date32 | DataType::Date64 => self.date_format.as_deref(),
+            DataType::Timestamp(_, None) => self.timestamp_format.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The schema read by the CSV decoder, in which the columns with a
+    /// custom format are read as strings, to be parsed by [`Self::parse_formatted`]
+    fn read_schema(&self) -> SchemaRef {
+        if self.date_format.is_none() && self.timestamp_format.is_none() {
+            return Arc::clone(&self.file_schema);
+        }
+        let fields = self
+            .file_schema
+            .fields()
+            .iter()
+            .map(|field| match self.custom_format(field.data_type()) {
+                Some(_) => {
+                    Arc::new(field.as_ref().clone().with_data_type(DataType::Utf8))
+                }
+                None => Arc::clone(field),
+            })
+            .collect::<Fields>();
+        Arc::new(Schema::new_with_metadata(
+            fields,
+            self.file_schema.metadata().clone(),
+        ))
+    }
+
+    /// Parses the columns of a batch decoded with [`Self::read_schema`] that
+    /// have a custom format
+    fn parse_formatted(&self, batch: RecordBatch) -> Result<RecordBatch, ArrowError> {
+        if self.date_format.is_none() && self.timestamp_format.is_none() {
+            return Ok(batch);
+        }
+        let schema = match &self.file_projection {
+            Some(projection) => Arc::new(self.file_schema.project(projection)?),
+            None => Arc::clone(&self.file_schema),
+        };
+        let columns = batch
+            .columns()
+            .iter()
+            .zip(schema.fields())
+            .map(
+                |(column, field)| match self.custom_format(field.data_type()) {
+                    Some(format) if column.data_type() == &DataType::Utf8 => {
+                        parse_with_format(column.as_string(), field.data_type(), format)
+                    }
+                    _ => Ok(Arc::clone(column)),
+                },
+            )
+            .collect::<Result<Vec<_>, _>>()?;
+        RecordBatch::try_new(schema, columns)
+    }
+}
+
+/// Parses dates or timestamps without a time zone with a chrono `format`,
+/// empty values are read as nulls
+fn parse_with_format(
+    values: &StringArray,
+    data_type: &DataType,
+    format: &str,
+) -> Result<ArrayRef, ArrowError> {
+    let parse_error = |value: &str, kind: &str| {
+        ArrowError::ParseError(format!(
+            "Error parsing '{value}' as {kind} with format '{format}'"
+        ))
+    };
+    let parse_date = |value: &str| {
+        NaiveDate::parse_from_str(value, format).map_err(|_| parse_error(value, "date"))
+    };
+    let parse_timestamp = |value: &str| {
+        let timestamp = NaiveDateTime::parse_from_str(value, format)
+            .map_err(|_| parse_error(value, "timestamp"))?
+            .and_utc();
+        match data_type {
+            DataType::Timestamp(TimeUnit::Second, _) => Ok(timestamp.timestamp()),
+            DataType::Timestamp(TimeUnit::Millisecond, _) => {
+                Ok(timestamp.timestamp_millis())
+            }
+            DataType::Timestamp(TimeUnit::Microsecond, _) => {
+                Ok(timestamp.timestamp_micros())
+            }
+            _ => timestamp
+                .timestamp_nanos_opt()
+                .ok_or_else(|| parse_error(value, "timestamp")),
+        }
+    };
+    fn parse<T: ArrowPrimitiveType>(
+        values: &StringArray,
+        parse: impl Fn(&str) -> Result<T::Native, ArrowError>,
+    ) -> Result<PrimitiveArray<T>, ArrowError> {
+        values
+            .iter()
+            .map(|value| value.filter(|v| !v.is_empty()).map(&parse).transpose())
+            .collect()
+    }
+    Ok(match data_type {
+        DataType::Date32 => Arc::new(parse::<Date32Type>(values, |value| {
+            parse_date(value).map(Date32Type::from_naive_date)
+        })?),
+        DataType::Date64 => Arc::new(parse::<Date64Type>(values, |value| {
+            parse_date(value).map(Date64Type::from_naive_date)
+        })?),
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            Arc::new(parse::<TimestampSecondType>(values, parse_timestamp)?)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            Arc::new(parse::<TimestampMillisecondType>(values, parse_timestamp)?)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            Arc::new(parse::<TimestampMicrosecondType>(values, parse_timestamp)?)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            Arc::new(parse::<TimestampNanosecondType>(values, parse_timestamp)?)
+        }
+        other => {
+            return Err(ArrowError::CastError(format!(
+                "Cannot parse {other} with a custom format"
+            )))
+        }
+    })
 }
 
 /// A [`FileOpener`] that opens a CSV file and yields a [`FileOpenFuture`]
 pub struct CsvOpener {
     config: Arc<CsvConfig>,
     file_compression_type: FileCompressionType,
+    bad_records: Option<(Arc<BadRecords>, Count)>,
 }
 
 impl CsvOpener {
@@ -558,6 +851,7 @@ impl CsvOpener {
         Self {
             config,
             file_compression_type,
+            bad_records: None,
         }
     }
 }
@@ -612,6 +906,7 @@ impl FileOpener for CsvOpener {
         }
 
         let store = self.config.object_store.clone();
+        let bad_records = self.bad_records.clone();
 
         Ok(Box::pin(async move {
             // Current partition contains bytes [start_byte, end_byte) (might contain incomplete lines at boundaries)
@@ -635,10 +930,23 @@ impl FileOpener for CsvOpener {
 
             let result = store.get_opts(file_meta.location(), options).await?;
 
+            let bad_record_decoder = bad_records.map(|(bad_records, metric)| {
+                BadRecordDecoder::new(
+                    CsvRecordParser::new(&config),
+                    bad_records,
+                    metric,
+                    file_meta.location().to_string(),
+                    config.batch_size,
+                )
+                .with_header(config.has_header)
+                .with_terminator(config.terminator)
+                .with_comment(config.comment)
+            });
+
             match result.payload {
                 GetResultPayload::File(mut file, _) => {
                     let is_whole_file_scanned = file_meta.range.is_none();
-                    let decoder = if is_whole_file_scanned {
+                    let mut decoder = if is_whole_file_scanned {
                         // Don't seek if no range as breaks FIFO files
                         file_compression_type.convert_read(file)?
                     } else {
@@ -647,14 +955,31 @@ impl FileOpener for CsvOpener {
                             file.take((result.range.end - result.range.start) as u64),
                         )?
                     };
-
-                    Ok(futures::stream::iter(config.open(decoder)?).boxed())
+                    if let Some(transcoder) = config.transcoder() {
+                        decoder = Box::new(TranscodingReader::new(decoder, transcoder));
+                    }
+
+                    if let Some(bad_record_decoder) = bad_record_decoder {
+                        let batches =
+                            bad_record_decoder.decode_reader(BufReader::new(decoder));
+                        return Ok(futures::stream::iter(batches).boxed());
+                    }
+                    let batches = config.open(decoder)?.map(move |batch| {
+                        batch.and_then(|batch| config.parse_formatted(batch))
+                    });
+                    Ok(futures::stream::iter(batches).boxed())
                 }
                 GetResultPayload::Stream(s) => {
-                    let mut decoder = config.builder().build_decoder();
                     let s = s.map_err(DataFusionError::from);
-                    let mut input =
-                        file_compression_type.convert_stream(s.boxed())?.fuse();
+                    let mut input = file_compression_type.convert_stream(s.boxed())?;
+                    if let Some(transcoder) = config.transcoder() {
+                        input = transcode_stream(input, transcoder);
+                    }
+                    if let Some(bad_record_decoder) = bad_record_decoder {
+                        return Ok(bad_record_decoder.decode_stream(input));
+                    }
+                    let mut decoder = config.builder().build_decoder();
+                    let mut input = input.fuse();
                     let mut buffered = Bytes::new();
 
                     let s = futures::stream::poll_fn(move |cx| {
@@ -678,7 +1003,10 @@ impl FileOpener for CsvOpener {
                             buffered.advance(decoded);
                         }
 
-                        Poll::Ready(decoder.flush().transpose())
+                        let batch = decoder.flush().and_then(|batch| {
+                            batch.map(|batch| config.parse_formatted(batch)).transpose()
+                        });
+                        Poll::Ready(batch.transpose())
                     });
                     Ok(s.boxed())
                 }
@@ -687,6 +1015,68 @@ impl FileOpener for CsvOpener {
     }
 }
 
+/// Parses the lines of a CSV file for a [`BadRecordDecoder`]
+struct CsvRecordParser {
+    config: CsvConfig,
+    output_schema: SchemaRef,
+}
+
+impl CsvRecordParser {
+    fn new(config: &CsvConfig) -> Self {
+        let output_schema = match &config.file_projection {
+            Some(projection) => Arc::new(config.file_schema.project(projection).unwrap()),
+            None => Arc::clone(&config.file_schema),
+        };
+        Self {
+            // Headers and comments are removed by the `BadRecordDecoder`
+            config: CsvConfig {
+                has_header: false,
+                comment: None,
+                ..config.clone()
+            },
+            output_schema,
+        }
+    }
+}
+
+impl RecordParser for CsvRecordParser {
+    fn file_schema(&self) -> &SchemaRef {
+        &self.config.file_schema
+    }
+
+    fn output_schema(&self) -> SchemaRef {
+        Arc::clone(&self.output_schema)
+    }
+
+    fn parse(
+        &self,
+        mut data: &[u8],
+        lenient_schema: Option<SchemaRef>,
+    ) -> Result<RecordBatch, ArrowError> {
+        let mut builder = match lenient_schema {
+            Some(file_schema) => CsvConfig {
+                file_schema,
+                ..self.config.clone()
+            }
+            .builder(),
+            None => self.config.builder(),
+        };
+        builder = builder.with_batch_size(data.len().max(1));
+        let mut decoder = builder.build_decoder();
+        loop {
+            let decoded = decoder.decode(data)?;
+            if decoded == 0 {
+                break;
+            }
+            data = &data[decoded..];
+        }
+        match decoder.flush()? {
+            Some(batch) => self.config.parse_formatted(batch),
+            None => Ok(RecordBatch::new_empty(self.output_schema())),
+        }
+    }
+}
+
 pub async fn plan_to_csv(
     task_ctx: Arc<TaskContext>,
     plan: Arc<dyn ExecutionPlan>,
@@ -1497,16 +1887,23 @@ mod tests {
             file_compression_type: _,
             has_header,
             delimiter,
+            delimiter_sequence,
             quote,
             terminator,
             escape,
             comment,
             newlines_in_values,
+            date_format,
+            timestamp_format,
+            on_error,
+            max_errors,
+            bad_records_path,
         } = CsvExecBuilder::new(file_scan_config);
 
         let default_options = CsvOptions::default();
         assert_eq!(has_header, default_options.has_header.unwrap_or(false));
         assert_eq!(delimiter, default_options.delimiter);
+        assert_eq!(delimiter_sequence, default_options.delimiter_sequence);
         assert_eq!(quote, default_options.quote);
         assert_eq!(terminator, default_options.terminator);
         assert_eq!(escape, default_options.escape);
@@ -1515,5 +1912,96 @@ mod tests {
             newlines_in_values,
             default_options.newlines_in_values.unwrap_or(false)
         );
+        assert_eq!(date_format, default_options.date_format);
+        assert_eq!(timestamp_format, default_options.timestamp_format);
+        assert_eq!(on_error, default_options.on_error);
+        assert_eq!(max_errors, default_options.max_errors);
+        assert_eq!(bad_records_path, default_options.bad_records_path);
+    }
+
+    #[tokio::test]
+    async fn test_on_error() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let path = tmp_dir.path().join("vendor.csv");
+        std::fs::write(&path, "a,b\n1,x\n# note\nbad,y\n4\n5,z\n")?;
+        let bad_records_path = tmp_dir.path().join("bad_records.json");
+
+        // Keep the case of the bad records path
+        let ctx = SessionContext::new_with_config(SessionConfig::new().set_bool(
+            "datafusion.sql_parser.enable_options_value_normalization",
+            false,
+        ));
+        let create_table = |name: &str, options: &str| {
+            format!(
+                "CREATE EXTERNAL TABLE {name} (a INT, b VARCHAR) STORED AS CSV \
+                 LOCATION '{}' OPTIONS ('format.has_header' 'true', \
+                 'format.comment' '#', {options})",
+                path.display()
+            )
+        };
+
+        ctx.sql(&create_table(
+            "skipped",
+            &format!(
+                "'format.on_error' 'skip', 'format.bad_records_path' '{}'",
+                bad_records_path.display()
+            ),
+        ))
+        .await?;
+        let result = ctx.sql("SELECT * FROM skipped").await?.collect().await?;
+        let expected = [
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | x |",
+            "| 5 | z |",
+            "+---+---+",
+        ];
+        crate::assert_batches_eq!(expected, &result);
+
+        let bad_records = std::fs::read_to_string(&bad_records_path)?;
+        let lines: Vec<_> = bad_records.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains(r#""line":4"#), "{}", lines[0]);
+        assert!(lines[0].contains(r#""record":"bad,y""#), "{}", lines[0]);
+        assert!(lines[1].contains(r#""line":5"#), "{}", lines[1]);
+        assert!(
+            lines[1].contains("incorrect number of fields"),
+            "{}",
+            lines[1]
+        );
+
+        ctx.sql(&create_table("nulls", "'format.on_error' 'null'"))
+            .await?;
+        let result = ctx.sql("SELECT * FROM nulls").await?.collect().await?;
+        let expected = [
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | x |",
+            "|   | y |",
+            "|   |   |",
+            "| 5 | z |",
+            "+---+---+",
+        ];
+        crate::assert_batches_eq!(expected, &result);
+
+        ctx.sql(&create_table(
+            "limited",
+            "'format.on_error' 'skip', 'format.max_errors' '1'",
+        ))
+        .await?;
+        let e = ctx
+            .sql("SELECT * FROM limited")
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert!(
+            e.to_string()
+                .contains("Exceeded the maximum of 1 malformed records, last at line 5"),
+            "{e}"
+        );
+        Ok(())
     }
 }
@@ -0,0 +1,312 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Execution plan for reading fixed width text files
+
+use std::any::Any;
+use std::io::Read;
+use std::ops::Range;
+use std::sync::Arc;
+
+use super::file_stream::{FileOpenFuture, FileOpener, FileStream};
+use super::transcode::{CsvTranscoder, LineFormat, TranscodingReader};
+use super::{FileMeta, FileScanConfig};
+use crate::datasource::file_format::file_compression_type::FileCompressionType;
+use crate::error::Result;
+use crate::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use crate::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionMode, ExecutionPlan, Partitioning,
+    PlanProperties, SendableRecordBatchStream, Statistics,
+};
+
+use arrow::csv;
+use arrow::datatypes::SchemaRef;
+use bytes::Buf;
+use datafusion_execution::TaskContext;
+use datafusion_physical_expr::{EquivalenceProperties, LexOrdering};
+use futures::{StreamExt, TryStreamExt};
+use object_store::{GetResultPayload, ObjectStore};
+
+/// Execution plan for scanning fixed width text files, whose values are at
+/// the same byte ranges of each line
+#[derive(Debug, Clone)]
+pub struct FixedWidthExec {
+    base_config: FileScanConfig,
+    projected_statistics: Statistics,
+    /// The byte range of each column of a line
+    columns: Vec<Range<usize>>,
+    has_header: bool,
+    file_compression_type: FileCompressionType,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    cache: PlanProperties,
+}
+
+impl FixedWidthExec {
+    /// Create a new fixed width reader execution plan reading the values at
+    /// the byte ranges `columns` of each line
+    pub fn new(
+        base_config: FileScanConfig,
+        columns: Vec<Range<usize>>,
+        has_header: bool,
+        file_compression_type: FileCompressionType,
+    ) -> Self {
+        let (projected_schema, projected_statistics, projected_output_ordering) =
+            base_config.project();
+        let cache = Self::compute_properties(
+            projected_schema,
+            &projected_output_ordering,
+            &base_config,
+        );
+        Self {
+            base_config,
+            projected_statistics,
+            columns,
+            has_header,
+            file_compression_type,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        }
+    }
+
+    /// Ref to the base configs
+    pub fn base_config(&self) -> &FileScanConfig {
+        &self.base_config
+    }
+
+    /// The byte range of each column of a line
+    pub fn columns(&self) -> &[Range<usize>] {
+        &self.columns
+    }
+
+    /// true if the first line of each file is a header
+    pub fn has_header(&self) -> bool {
+        self.has_header
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        schema: SchemaRef,
+        orderings: &[LexOrdering],
+        file_scan_config: &FileScanConfig,
+    ) -> PlanProperties {
+        // Equivalence Properties
+        let eq_properties = EquivalenceProperties::new_with_orderings(schema, orderings);
+        let n_partitions = file_scan_config.file_groups.len();
+
+        PlanProperties::new(
+            eq_properties,
+            Partitioning::UnknownPartitioning(n_partitions), // Output Partitioning
+            ExecutionMode::Bounded,                          // Execution Mode
+        )
+    }
+}
+
+impl DisplayAs for FixedWidthExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        write!(f, "FixedWidthExec: ")?;
+        self.base_config.fmt_as(t, f)?;
+        write!(f, ", has_header={}", self.has_header)
+    }
+}
+
+impl ExecutionPlan for FixedWidthExec {
+    fn name(&self) -> &'static str {
+        "FixedWidthExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let object_store = context
+            .runtime_env()
+            .object_store(&self.base_config.object_store_url)?;
+
+        let opener = FixedWidthOpener {
+            file_schema: Arc::clone(&self.base_config.file_schema),
+            file_projection: self.base_config.file_column_projection_indices(),
+            batch_size: context.session_config().batch_size(),
+            columns: self.columns.clone(),
+            has_header: self.has_header,
+            file_compression_type: self.file_compression_type,
+            object_store,
+        };
+        let stream =
+            FileStream::new(&self.base_config, partition, opener, &self.metrics)?;
+        Ok(Box::pin(stream))
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        Ok(self.projected_statistics.clone())
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn fetch(&self) -> Option<usize> {
+        self.base_config.limit
+    }
+
+    fn with_fetch(&self, limit: Option<usize>) -> Option<Arc<dyn ExecutionPlan>> {
+        let new_config = self.base_config.clone().with_limit(limit);
+
+        Some(Arc::new(Self {
+            base_config: new_config,
+            projected_statistics: self.projected_statistics.clone(),
+            columns: self.columns.clone(),
+            has_header: self.has_header,
+            file_compression_type: self.file_compression_type,
+            metrics: self.metrics.clone(),
+            cache: self.cache.clone(),
+        }))
+    }
+}
+
+/// A [`FileOpener`] that opens a fixed width file and yields a
+/// [`FileOpenFuture`]
+#[derive(Clone)]
+struct FixedWidthOpener {
+    file_schema: SchemaRef,
+    file_projection: Option<Vec<usize>>,
+    batch_size: usize,
+    columns: Vec<Range<usize>>,
+    has_header: bool,
+    file_compression_type: FileCompressionType,
+    object_store: Arc<dyn ObjectStore>,
+}
+
+impl FixedWidthOpener {
+    /// Converts the lines read from `reader` to comma separated values and
+    /// parses them with the arrow CSV reader
+    fn build_reader<R: Read + Send + 'static>(
+        &self,
+        reader: R,
+    ) -> Result<csv::Reader<TranscodingReader<Box<dyn Read + Send>>>> {
+        let decoder = self.file_compression_type.convert_read(reader)?;
+        let format = LineFormat::FixedWidth {
+            columns: self.columns.clone(),
+        };
+        let reader = TranscodingReader::new(decoder, CsvTranscoder::new(format, b'"'));
+        let mut builder = csv::ReaderBuilder::new(Arc::clone(&self.file_schema))
+            .with_header(self.has_header)
+            .with_batch_size(self.batch_size);
+        if let Some(projection) = &self.file_projection {
+            builder = builder.with_projection(projection.clone());
+        }
+        Ok(builder.build(reader)?)
+    }
+}
+
+impl FileOpener for FixedWidthOpener {
+    fn open(&self, file_meta: FileMeta) -> Result<FileOpenFuture> {
+        let opener = self.clone();
+        Ok(Box::pin(async move {
+            let result = opener.object_store.get(file_meta.location()).await?;
+            let reader = match result.payload {
+                GetResultPayload::File(file, _) => opener.build_reader(file)?,
+                GetResultPayload::Stream(_) => {
+                    let bytes = result.bytes().await?;
+                    opener.build_reader(bytes.reader())?
+                }
+            };
+            Ok(futures::stream::iter(reader).map_err(Into::into).boxed())
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::datatypes::{DataType, Field, Schema};
+    use crate::datasource::object_store::ObjectStoreUrl;
+    use crate::prelude::SessionContext;
+    use crate::test::object_store::local_unpartitioned_file;
+
+    use arrow::util::pretty::pretty_format_batches;
+    use datafusion_physical_plan::common::collect;
+
+    #[tokio::test]
+    async fn fixed_width_exec() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let tmp_dir = tempfile::TempDir::new()?;
+        let path = tmp_dir.path().join("accounts.txt");
+        std::fs::write(
+            &path,
+            "ID   NAME      BALANCE\n\
+             00001Alice     12.50\n\
+             00002Bob, Jr.  -3\n\
+             00003          \n",
+        )?;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("balance", DataType::Float64, true),
+        ]));
+        let exec = FixedWidthExec::new(
+            FileScanConfig::new(ObjectStoreUrl::local_filesystem(), schema)
+                .with_file(local_unpartitioned_file(&path).into())
+                .with_projection(Some(vec![2, 1])),
+            vec![0..5, 5..15, 15..22],
+            true,
+            FileCompressionType::UNCOMPRESSED,
+        );
+        assert_eq!(exec.schema().fields().len(), 2);
+
+        let batches = collect(exec.execute(0, session_ctx.task_ctx())?).await?;
+        let expected = [
+            "+---------+----------+",
+            "| balance | name     |",
+            "+---------+----------+",
+            "| 12.5    | Alice    |",
+            "| -3.0    | Bob, Jr. |",
+            "|         |          |",
+            "+---------+----------+",
+        ];
+        assert_eq!(
+            pretty_format_batches(&batches)?.to_string(),
+            expected.join("\n")
+        );
+        Ok(())
+    }
+}
@@ -22,6 +22,7 @@ use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::sync::Arc;
 use std::task::Poll;
 
+use super::bad_records::{BadRecordDecoder, BadRecords, RecordParser};
 use super::{calculate_range, FileGroupPartitioner, FileScanConfig, RangeCalculation};
 use crate::datasource::file_format::file_compression_type::FileCompressionType;
 use crate::datasource::listing::{ListingTableUrl, PartitionedFile};
@@ -30,14 +31,19 @@ use crate::datasource::physical_plan::file_stream::{
 };
 use crate::datasource::physical_plan::FileMeta;
 use crate::error::{DataFusionError, Result};
-use crate::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use crate::physical_plan::metrics::{
+    Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+};
 use crate::physical_plan::{
     DisplayAs, DisplayFormatType, ExecutionMode, ExecutionPlan, ExecutionPlanProperties,
     Partitioning, PlanProperties, SendableRecordBatchStream, Statistics,
 };
 
+use arrow::array::RecordBatch;
+use arrow::error::ArrowError;
 use arrow::json::ReaderBuilder;
 use arrow::{datatypes::SchemaRef, json};
+use datafusion_common::parsers::OnError;
 use datafusion_execution::TaskContext;
 use datafusion_physical_expr::{EquivalenceProperties, LexOrdering};
 
@@ -53,6 +59,8 @@ use tokio::task::JoinSet;
 pub struct NdJsonExec {
     base_config: FileScanConfig,
     projected_statistics: Statistics,
+    /// Malformed records shared by all partitions, `None` if they fail the scan
+    bad_records: Option<Arc<BadRecords>>,
     /// Execution metrics
     metrics: ExecutionPlanMetricsSet,
     file_compression_type: FileCompressionType,
@@ -75,17 +83,41 @@ impl NdJsonExec {
         Self {
             base_config,
             projected_statistics,
+            bad_records: None,
             metrics: ExecutionPlanMetricsSet::new(),
             file_compression_type,
             cache,
         }
     }
 
+    /// Set how lines that cannot be parsed are handled, the maximum number of
+    /// malformed lines tolerated before the scan fails, and a local file that
+    /// malformed lines are appended to as newline delimited JSON objects.
+    ///
+    /// By default malformed lines fail the scan.
+    pub fn with_bad_record_handling(
+        mut self,
+        on_error: OnError,
+        max_errors: Option<usize>,
+        bad_records_path: Option<String>,
+    ) -> Self {
+        self.bad_records = BadRecords::try_new(on_error, max_errors, bad_records_path);
+        self
+    }
+
     /// Ref to the base configs
     pub fn base_config(&self) -> &FileScanConfig {
         &self.base_config
     }
 
+    /// How lines that cannot be parsed are handled
+    pub fn on_error(&self) -> OnError {
+        self.bad_records
+            .as_ref()
+            .map(|bad_records| bad_records.on_error())
+            .unwrap_or_default()
+    }
+
     fn output_partitioning_helper(file_scan_config: &FileScanConfig) -> Partitioning {
         Partitioning::UnknownPartitioning(file_scan_config.file_groups.len())
     }
@@ -122,7 +154,11 @@ impl DisplayAs for NdJsonExec {
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
         write!(f, "JsonExec: ")?;
-        self.base_config.fmt_as(t, f)
+        self.base_config.fmt_as(t, f)?;
+        if let Some(bad_records) = &self.bad_records {
+            write!(f, ", on_error={}", bad_records.on_error())?;
+        }
+        Ok(())
     }
 }
 
@@ -154,7 +190,8 @@ impl ExecutionPlan for NdJsonExec {
         target_partitions: usize,
         config: &datafusion_common::config::ConfigOptions,
     ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
-        if self.file_compression_type.is_compressed() {
+        // Line numbers of malformed records are only known when reading from the file start
+        if self.file_compression_type.is_compressed() || self.bad_records.is_some() {
             return Ok(None);
         }
         let repartition_file_min_size = config.optimizer.repartition_file_min_size;
@@ -186,11 +223,17 @@ impl ExecutionPlan for NdJsonExec {
         let object_store = context
             .runtime_env()
             .object_store(&self.base_config.object_store_url)?;
+        let bad_records = self.bad_records.as_ref().map(|bad_records| {
+            let metric =
+                MetricBuilder::new(&self.metrics).counter("bad_records", partition);
+            (Arc::clone(bad_records), metric)
+        });
         let opener = JsonOpener {
             batch_size,
             projected_schema: self.base_config.projected_file_schema(),
             file_compression_type: self.file_compression_type.to_owned(),
             object_store,
+            bad_records,
         };
 
         let stream =
@@ -217,6 +260,7 @@ impl ExecutionPlan for NdJsonExec {
         Some(Arc::new(Self {
             base_config: new_config,
             projected_statistics: self.projected_statistics.clone(),
+            bad_records: self.bad_records.clone(),
             metrics: self.metrics.clone(),
             file_compression_type: self.file_compression_type,
             cache: self.cache.clone(),
@@ -230,6 +274,7 @@ pub struct JsonOpener {
     projected_schema: SchemaRef,
     file_compression_type: FileCompressionType,
     object_store: Arc<dyn ObjectStore>,
+    bad_records: Option<(Arc<BadRecords>, Count)>,
 }
 
 impl JsonOpener {
@@ -245,6 +290,7 @@ impl JsonOpener {
             projected_schema,
             file_compression_type,
             object_store,
+            bad_records: None,
         }
     }
 }
@@ -266,6 +312,7 @@ impl FileOpener for JsonOpener {
         let schema = self.projected_schema.clone();
         let batch_size = self.batch_size;
         let file_compression_type = self.file_compression_type.to_owned();
+        let bad_records = self.bad_records.clone();
 
         Ok(Box::pin(async move {
             let calculated_range = calculate_range(&file_meta, &store).await?;
@@ -287,6 +334,18 @@ impl FileOpener for JsonOpener {
 
             let result = store.get_opts(file_meta.location(), options).await?;
 
+            let bad_record_decoder = bad_records.map(|(bad_records, metric)| {
+                BadRecordDecoder::new(
+                    JsonRecordParser {
+                        schema: Arc::clone(&schema),
+                    },
+                    bad_records,
+                    metric,
+                    file_meta.location().to_string(),
+                    batch_size,
+                )
+            });
+
             match result.payload {
                 GetResultPayload::File(mut file, _) => {
                     let bytes = match file_meta.range {
@@ -298,6 +357,12 @@ impl FileOpener for JsonOpener {
                         }
                     };
 
+                    if let Some(bad_record_decoder) = bad_record_decoder {
+                        let batches =
+                            bad_record_decoder.decode_reader(BufReader::new(bytes));
+                        return Ok(futures::stream::iter(batches).boxed());
+                    }
+
                     let reader = ReaderBuilder::new(schema)
                         .with_batch_size(batch_size)
                         .build(BufReader::new(bytes))?;
@@ -306,12 +371,15 @@ impl FileOpener for JsonOpener {
                 }
                 GetResultPayload::Stream(s) => {
                     let s = s.map_err(DataFusionError::from);
+                    let input = file_compression_type.convert_stream(s.boxed())?;
+                    if let Some(bad_record_decoder) = bad_record_decoder {
+                        return Ok(bad_record_decoder.decode_stream(input));
+                    }
 
                     let mut decoder = ReaderBuilder::new(schema)
                         .with_batch_size(batch_size)
                         .build_decoder()?;
-                    let mut input =
-                        file_compression_type.convert_stream(s.boxed())?.fuse();
+                    let mut input = input.fuse();
                     let mut buffer = Bytes::new();
 
                     let s = futures::stream::poll_fn(move |cx| {
@@ -344,6 +412,43 @@ impl FileOpener for JsonOpener {
     }
 }
 
+/// Parses the lines of a NDJSON file for a [`BadRecordDecoder`]
+struct JsonRecordParser {
+    schema: SchemaRef,
+}
+
+impl RecordParser for JsonRecordParser {
+    fn file_schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn output_schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn parse(
+        &self,
+        mut data: &[u8],
+        lenient_schema: Option<SchemaRef>,
+    ) -> Result<RecordBatch, ArrowError> {
+        let coerce_primitive = lenient_schema.is_some();
+        let schema = lenient_schema.unwrap_or_else(|| Arc::clone(&self.schema));
+        let mut decoder = ReaderBuilder::new(Arc::clone(&schema))
+            .with_batch_size(data.len().max(1))
+            .with_coerce_primitive(coerce_primitive)
+            .build_decoder()?;
+        loop {
+            let decoded = decoder.decode(data)?;
+            if decoded == 0 {
+                break;
+            }
+            data = &data[decoded..];
+        }
+        let batch = decoder.flush()?;
+        Ok(batch.unwrap_or_else(|| RecordBatch::new_empty(schema)))
+    }
+}
+
 pub async fn plan_to_json(
     task_ctx: Arc<TaskContext>,
     plan: Arc<dyn ExecutionPlan>,
@@ -19,19 +19,23 @@
 
 mod arrow_file;
 mod avro;
+mod bad_records;
 mod csv;
 mod file_groups;
 mod file_scan_config;
 mod file_stream;
+mod fixed_width;
 mod json;
 #[cfg(feature = "parquet")]
 pub mod parquet;
 mod statistics;
+mod transcode;
 
 pub(crate) use self::csv::plan_to_csv;
 pub(crate) use self::json::plan_to_json;
 #[cfg(feature = "parquet")]
 pub use self::parquet::{ParquetExec, ParquetFileMetrics, ParquetFileReaderFactory};
+pub(crate) use self::transcode::{transcode_stream, CsvTranscoder, LineFormat};
 
 pub use arrow_file::ArrowExec;
 pub use avro::AvroExec;
@@ -42,6 +46,7 @@ pub use file_scan_config::{
     wrap_partition_type_in_dict, wrap_partition_value_in_dict, FileScanConfig,
 };
 pub use file_stream::{FileOpenFuture, FileOpener, FileStream, OnError};
+pub use fixed_width::FixedWidthExec;
 pub use json::{JsonOpener, NdJsonExec};
 
 use std::{
@@ -45,6 +45,9 @@ pub struct ParquetFileMetrics {
     pub pushdown_rows_matched: Count,
     /// Total time spent evaluating row-level pushdown filters
     pub row_pushdown_eval_time: Time,
+    /// Number of row-level pushdown filters disabled during the scan because
+    /// they were not selective enough (see `adaptive_filters`)
+    pub pushdown_filters_disabled: Count,
     /// Total time spent evaluating row group-level statistics filters
     pub statistics_eval_time: Time,
     /// Total time spent evaluating row group Bloom Filters
@@ -57,6 +60,10 @@ pub struct ParquetFileMetrics {
     pub page_index_eval_time: Time,
     /// Total time spent reading and parsing metadata from the footer
     pub metadata_load_time: Time,
+    /// Number of times the metadata was found in the file metadata cache
+    pub metadata_cache_hits: Count,
+    /// Number of times the metadata was not found in the file metadata cache
+    pub metadata_cache_misses: Count,
 }
 
 impl ParquetFileMetrics {
@@ -96,6 +103,9 @@ impl ParquetFileMetrics {
         let pushdown_rows_matched = MetricBuilder::new(metrics)
             .with_new_label("filename", filename.to_string())
             .counter("pushdown_rows_matched", partition);
+        let pushdown_filters_disabled = MetricBuilder::new(metrics)
+            .with_new_label("filename", filename.to_string())
+            .counter("pushdown_filters_disabled", partition);
 
         let row_pushdown_eval_time = MetricBuilder::new(metrics)
             .with_new_label("filename", filename.to_string())
@@ -122,6 +132,13 @@ impl ParquetFileMetrics {
             .with_new_label("filename", filename.to_string())
             .subset_time("metadata_load_time", partition);
 
+        let metadata_cache_hits = MetricBuilder::new(metrics)
+            .with_new_label("filename", filename.to_string())
+            .counter("metadata_cache_hits", partition);
+        let metadata_cache_misses = MetricBuilder::new(metrics)
+            .with_new_label("filename", filename.to_string())
+            .counter("metadata_cache_misses", partition);
+
         Self {
             predicate_evaluation_errors,
             row_groups_matched_bloom_filter,
@@ -131,6 +148,7 @@ impl ParquetFileMetrics {
             bytes_scanned,
             pushdown_rows_pruned,
             pushdown_rows_matched,
+            pushdown_filters_disabled,
             row_pushdown_eval_time,
             page_index_rows_pruned,
             page_index_rows_matched,
@@ -138,6 +156,8 @@ impl ParquetFileMetrics {
             bloom_filter_eval_time,
             page_index_eval_time,
             metadata_load_time,
+            metadata_cache_hits,
+            metadata_cache_misses,
         }
     }
 }
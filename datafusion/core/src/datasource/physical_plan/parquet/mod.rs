@@ -33,7 +33,8 @@ use crate::{
     execution::context::TaskContext,
     physical_optimizer::pruning::PruningPredicate,
     physical_plan::{
-        metrics::{ExecutionPlanMetricsSet, MetricBuilder, MetricsSet},
+        metrics::{Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet},
+        semi_join_reduction::resolve_semi_join_reductions,
         DisplayFormatType, ExecutionMode, ExecutionPlan, Partitioning, PlanProperties,
         SendableRecordBatchStream, Statistics,
     },
@@ -60,8 +61,11 @@ use crate::datasource::schema_adapter::{
 pub use access_plan::{ParquetAccessPlan, RowGroupAccess};
 pub use metrics::ParquetFileMetrics;
 use opener::ParquetOpener;
-pub use reader::{DefaultParquetFileReaderFactory, ParquetFileReaderFactory};
+pub use reader::{
+    CachedParquetMetaData, DefaultParquetFileReaderFactory, ParquetFileReaderFactory,
+};
 pub use row_filter::can_expr_be_pushed_down_with_schemas;
+use row_filter::AdaptiveFilterStats;
 pub use writer::plan_to_parquet;
 
 /// Execution plan for reading one or more Parquet files.
@@ -282,6 +286,9 @@ pub struct ParquetExec {
     table_parquet_options: TableParquetOptions,
     /// Optional user defined schema adapter
     schema_adapter_factory: Option<Arc<dyn SchemaAdapterFactory>>,
+    /// Runtime statistics of the conjuncts of `predicate`, shared by all
+    /// partitions (used when `adaptive_filters` is enabled)
+    adaptive_filter_stats: Option<Arc<AdaptiveFilterStats>>,
 }
 
 impl From<ParquetExec> for ParquetExecBuilder {
@@ -421,27 +428,20 @@ impl ParquetExecBuilder {
         let predicate_creation_errors =
             MetricBuilder::new(&metrics).global_counter("num_predicate_creation_errors");
 
-        let file_schema = &base_config.file_schema;
-        let pruning_predicate = predicate
-            .clone()
-            .and_then(|predicate_expr| {
-                match PruningPredicate::try_new(predicate_expr, file_schema.clone()) {
-                    Ok(pruning_predicate) => Some(Arc::new(pruning_predicate)),
-                    Err(e) => {
-                        debug!("Could not create pruning predicate for: {e}");
-                        predicate_creation_errors.add(1);
-                        None
-                    }
-                }
-            })
-            .filter(|p| !p.always_true());
-
-        let page_pruning_predicate = predicate
+        let (pruning_predicate, page_pruning_predicate) = predicate
             .as_ref()
             .map(|predicate_expr| {
-                PagePruningAccessPlanFilter::new(predicate_expr, file_schema.clone())
+                create_pruning_predicates(
+                    predicate_expr,
+                    &base_config.file_schema,
+                    &predicate_creation_errors,
+                )
             })
-            .map(Arc::new);
+            .unwrap_or_default();
+
+        let adaptive_filter_stats = predicate
+            .as_ref()
+            .map(|predicate_expr| Arc::new(AdaptiveFilterStats::new(predicate_expr)));
 
         let (projected_schema, projected_statistics, projected_output_ordering) =
             base_config.project();
@@ -463,6 +463,7 @@ impl ParquetExecBuilder {
             cache,
             table_parquet_options,
             schema_adapter_factory,
+            adaptive_filter_stats,
         }
     }
 }
@@ -515,6 +516,7 @@ impl ParquetExec {
             cache: _,
             table_parquet_options,
             schema_adapter_factory,
+            adaptive_filter_stats: _,
         } = self;
         ParquetExecBuilder {
             file_scan_config: base_config,
@@ -609,6 +611,22 @@ impl ParquetExec {
         self.table_parquet_options.global.reorder_filters
     }
 
+    /// If true, the `RowFilter` made by `pushdown_filters` orders its
+    /// predicates based on the selectivity and evaluation cost measured
+    /// while scanning, and stops evaluating predicates that filter out few
+    /// rows. As predicates may be skipped, the filters must also be applied
+    /// after the scan. Takes precedence over `reorder_filters`. Defaults to
+    /// false.
+    pub fn with_adaptive_filters(mut self, adaptive_filters: bool) -> Self {
+        self.table_parquet_options.global.adaptive_filters = adaptive_filters;
+        self
+    }
+
+    /// Return the value described in [`Self::with_adaptive_filters`]
+    fn adaptive_filters(&self) -> bool {
+        self.table_parquet_options.global.adaptive_filters
+    }
+
     /// If enabled, the reader will read the page index
     /// This is used to optimise filter pushdown
     /// via `RowSelector` and `RowFilter` by
@@ -787,10 +805,19 @@ impl ExecutionPlan for ParquetExec {
             .as_ref()
             .map(|f| Ok(Arc::clone(f)))
             .unwrap_or_else(|| {
-                ctx.runtime_env()
+                let runtime_env = ctx.runtime_env();
+                let file_metadata_cache =
+                    runtime_env.cache_manager.get_file_metadata_cache();
+                let column_chunk_cache =
+                    runtime_env.cache_manager.get_column_chunk_cache();
+                runtime_env
                     .object_store(&self.base_config.object_store_url)
                     .map(|store| {
-                        Arc::new(DefaultParquetFileReaderFactory::new(store)) as _
+                        Arc::new(
+                            DefaultParquetFileReaderFactory::new(store)
+                                .with_file_metadata_cache(file_metadata_cache)
+                                .with_column_chunk_cache(column_chunk_cache),
+                        ) as _
                     })
             })?;
 
@@ -799,20 +826,43 @@ impl ExecutionPlan for ParquetExec {
             .clone()
             .unwrap_or_else(|| Arc::new(DefaultSchemaAdapterFactory));
 
+        // the keys of semi-join reductions are only known at execution
+        let mut predicate = self.predicate.clone();
+        let mut pruning_predicate = self.pruning_predicate.clone();
+        let mut page_pruning_predicate = self.page_pruning_predicate.clone();
+        if let Some(predicate_expr) = &self.predicate {
+            let resolved =
+                resolve_semi_join_reductions(Arc::clone(predicate_expr), &ctx)?;
+            if resolved.transformed {
+                let predicate_creation_errors = MetricBuilder::new(&self.metrics)
+                    .global_counter("num_predicate_creation_errors");
+                (pruning_predicate, page_pruning_predicate) = create_pruning_predicates(
+                    &resolved.data,
+                    &self.base_config.file_schema,
+                    &predicate_creation_errors,
+                );
+                predicate = Some(resolved.data);
+            }
+        }
+
         let opener = ParquetOpener {
             partition_index,
             projection: Arc::from(projection),
             batch_size: ctx.session_config().batch_size(),
             limit: self.base_config.limit,
-            predicate: self.predicate.clone(),
-            pruning_predicate: self.pruning_predicate.clone(),
-            page_pruning_predicate: self.page_pruning_predicate.clone(),
+            predicate,
+            pruning_predicate,
+            page_pruning_predicate,
             table_schema: self.base_config.file_schema.clone(),
             metadata_size_hint: self.metadata_size_hint,
             metrics: self.metrics.clone(),
             parquet_file_reader_factory,
             pushdown_filters: self.pushdown_filters(),
             reorder_filters: self.reorder_filters(),
+            adaptive_filter_stats: self
+                .adaptive_filters()
+                .then(|| self.adaptive_filter_stats.clone())
+                .flatten(),
             enable_page_index: self.enable_page_index(),
             enable_bloom_filter: self.bloom_filter_on_read(),
             schema_adapter_factory,
@@ -862,10 +912,37 @@ impl ExecutionPlan for ParquetExec {
             cache: self.cache.clone(),
             table_parquet_options: self.table_parquet_options.clone(),
             schema_adapter_factory: self.schema_adapter_factory.clone(),
+            adaptive_filter_stats: self.adaptive_filter_stats.clone(),
         }))
     }
 }
 
+/// Creates the predicates pruning the row groups and the pages of the files
+/// scanned with `predicate`
+fn create_pruning_predicates(
+    predicate: &Arc<dyn PhysicalExpr>,
+    file_schema: &SchemaRef,
+    predicate_creation_errors: &Count,
+) -> (
+    Option<Arc<PruningPredicate>>,
+    Option<Arc<PagePruningAccessPlanFilter>>,
+) {
+    let pruning_predicate =
+        match PruningPredicate::try_new(Arc::clone(predicate), file_schema.clone()) {
+            Ok(pruning_predicate) => Some(Arc::new(pruning_predicate)),
+            Err(e) => {
+                debug!("Could not create pruning predicate for: {e}");
+                predicate_creation_errors.add(1);
+                None
+            }
+        }
+        .filter(|p| !p.always_true());
+
+    let page_pruning_predicate =
+        PagePruningAccessPlanFilter::new(predicate, file_schema.clone());
+    (pruning_predicate, Some(Arc::new(page_pruning_predicate)))
+}
+
 fn should_enable_page_index(
     enable_page_index: bool,
     page_pruning_predicate: &Option<Arc<PagePruningAccessPlanFilter>>,
@@ -909,6 +986,12 @@ mod tests {
     use arrow::record_batch::RecordBatch;
     use arrow_schema::{DataType, Fields};
     use datafusion_common::{assert_contains, ScalarValue};
+    use datafusion_execution::cache::cache_manager::CacheManagerConfig;
+    use datafusion_execution::cache::cache_unit::{
+        DefaultColumnChunkCache, DefaultFileMetadataCache,
+    };
+    use datafusion_execution::cache::CacheAccessor;
+    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
     use datafusion_expr::{col, lit, when, Expr};
     use datafusion_physical_expr::planner::logical2physical;
     use datafusion_physical_plan::ExecutionPlanProperties;
@@ -1972,6 +2055,82 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn parquet_exec_column_chunk_cache() -> Result<()> {
+        let batch = int64_batch();
+        let (meta, _files) = store_parquet(vec![batch.clone()], false).await?;
+
+        let cache = Arc::new(DefaultColumnChunkCache::new(1024 * 1024));
+        let runtime = RuntimeEnvBuilder::new()
+            .with_cache_manager(
+                CacheManagerConfig::default()
+                    .with_column_chunk_cache(Some(Arc::clone(&cache) as _)),
+            )
+            .build_arc()?;
+        let ctx = SessionContext::new_with_config_rt(SessionConfig::new(), runtime);
+
+        let mut bytes_scanned = vec![];
+        for _ in 0..2 {
+            let parquet_exec = ParquetExec::builder(
+                FileScanConfig::new(ObjectStoreUrl::local_filesystem(), batch.schema())
+                    .with_file_group(meta.iter().cloned().map(Into::into).collect()),
+            )
+            .build_arc();
+            let batches = collect(Arc::clone(&parquet_exec) as _, ctx.task_ctx()).await?;
+            assert_eq!(batches, vec![batch.clone()]);
+
+            let metrics = parquet_exec.metrics().unwrap();
+            bytes_scanned.push(get_value(&metrics, "bytes_scanned"));
+        }
+
+        // one chunk per column, read from the cache by the second scan
+        assert_eq!(cache.len(), 3);
+        assert!(bytes_scanned[0] > 0);
+        assert_eq!(bytes_scanned[1], 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn parquet_exec_file_metadata_cache() -> Result<()> {
+        let batch = int64_batch();
+        let (meta, _files) = store_parquet(vec![batch.clone()], true).await?;
+
+        let cache = Arc::new(DefaultFileMetadataCache::new(1024 * 1024));
+        let runtime = RuntimeEnvBuilder::new()
+            .with_cache_manager(
+                CacheManagerConfig::default()
+                    .with_file_metadata_cache(Some(Arc::clone(&cache) as _)),
+            )
+            .build_arc()?;
+        let ctx = SessionContext::new_with_config_rt(SessionConfig::new(), runtime);
+
+        let mut hits_and_misses = vec![];
+        for _ in 0..2 {
+            let parquet_exec = ParquetExec::builder(
+                FileScanConfig::new(ObjectStoreUrl::local_filesystem(), batch.schema())
+                    .with_file_group(meta.iter().cloned().map(Into::into).collect()),
+            )
+            .build_arc();
+            let batches = collect(Arc::clone(&parquet_exec) as _, ctx.task_ctx()).await?;
+            assert_eq!(batches, vec![batch.clone()]);
+
+            let metrics = parquet_exec.metrics().unwrap();
+            hits_and_misses.push((
+                get_value(&metrics, "metadata_cache_hits"),
+                get_value(&metrics, "metadata_cache_misses"),
+            ));
+        }
+        assert_eq!(hits_and_misses, vec![(0, 1), (1, 0)]);
+
+        // the page indexes are cached along with the footer
+        let cached = cache.get_with_extra(&meta[0].location, &meta[0]).unwrap();
+        let cached = cached.as_any().downcast_ref::<CachedParquetMetaData>();
+        let parquet_metadata = cached.unwrap().parquet_metadata();
+        assert!(parquet_metadata.column_index().is_some());
+        assert!(parquet_metadata.offset_index().is_some());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn parquet_exec_display() {
         // batch1: c1(string)
@@ -23,7 +23,8 @@ use crate::datasource::file_format::{
 use crate::datasource::physical_plan::parquet::page_filter::PagePruningAccessPlanFilter;
 use crate::datasource::physical_plan::parquet::row_group_filter::RowGroupAccessPlanFilter;
 use crate::datasource::physical_plan::parquet::{
-    row_filter, should_enable_page_index, ParquetAccessPlan,
+    row_filter, row_filter::AdaptiveFilterStats, should_enable_page_index,
+    ParquetAccessPlan,
 };
 use crate::datasource::physical_plan::{
     FileMeta, FileOpenFuture, FileOpener, ParquetFileMetrics, ParquetFileReaderFactory,
@@ -71,6 +72,9 @@ pub(super) struct ParquetOpener {
     pub pushdown_filters: bool,
     /// Should the filters be reordered to optimize the scan?
     pub reorder_filters: bool,
+    /// Statistics used to order and disable filters at runtime, if adaptive
+    /// filtering is enabled
+    pub adaptive_filter_stats: Option<Arc<AdaptiveFilterStats>>,
     /// Should the page index be read from parquet files, if present, to skip
     /// data pages
     pub enable_page_index: bool,
@@ -109,6 +113,7 @@ impl FileOpener for ParquetOpener {
         let page_pruning_predicate = self.page_pruning_predicate.clone();
         let table_schema = self.table_schema.clone();
         let reorder_predicates = self.reorder_filters;
+        let adaptive_filter_stats = self.adaptive_filter_stats.clone();
         let pushdown_filters = self.pushdown_filters;
         let enable_page_index = should_enable_page_index(
             self.enable_page_index,
@@ -168,6 +173,7 @@ impl FileOpener for ParquetOpener {
                     reorder_predicates,
                     &file_metrics,
                     Arc::clone(&schema_mapping),
+                    adaptive_filter_stats.as_deref(),
                 );
 
                 match row_filter {
@@ -18,13 +18,21 @@
 //! [`ParquetFileReaderFactory`] and [`DefaultParquetFileReaderFactory`] for
 //! low level control of parquet file readers
 
+use crate::datasource::file_format::parquet::has_encrypted_footer;
 use crate::datasource::physical_plan::{FileMeta, ParquetFileMetrics};
 use bytes::Bytes;
+use datafusion_execution::cache::cache_manager::{
+    ColumnChunkCache, ColumnChunkKey, FileMetadata, FileMetadataCache,
+};
 use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
 use futures::future::BoxFuture;
-use object_store::ObjectStore;
+use futures::FutureExt;
+use object_store::{ObjectMeta, ObjectStore};
 use parquet::arrow::async_reader::{AsyncFileReader, ParquetObjectReader};
+use parquet::errors::ParquetError;
 use parquet::file::metadata::ParquetMetaData;
+use std::any::Any;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::ops::Range;
 use std::sync::Arc;
@@ -65,16 +73,46 @@ pub trait ParquetFileReaderFactory: Debug + Send + Sync + 'static {
 /// This implementation:
 /// 1. Reads parquet directly from an underlying [`ObjectStore`] instance.
 /// 2. Reads the footer and page metadata on demand.
-/// 3. Does not cache metadata or coalesce I/O operations.
+/// 3. Does not coalesce I/O operations.
+/// 4. Optionally caches the metadata, including the page indexes, in a
+///    [`FileMetadataCache`], see [`Self::with_file_metadata_cache`].
+/// 5. Optionally caches the column chunks it reads in a [`ColumnChunkCache`],
+///    see [`Self::with_column_chunk_cache`].
 #[derive(Debug)]
 pub struct DefaultParquetFileReaderFactory {
     store: Arc<dyn ObjectStore>,
+    file_metadata_cache: Option<FileMetadataCache>,
+    column_chunk_cache: Option<ColumnChunkCache>,
 }
 
 impl DefaultParquetFileReaderFactory {
     /// Create a new `DefaultParquetFileReaderFactory`.
     pub fn new(store: Arc<dyn ObjectStore>) -> Self {
-        Self { store }
+        Self {
+            store,
+            file_metadata_cache: None,
+            column_chunk_cache: None,
+        }
+    }
+
+    /// Read the metadata of the files from `cache` if present, and store the
+    /// metadata read from the object store in it.
+    ///
+    /// When a cache is used, the page indexes are always read along with the
+    /// footer, so that they are cached as well.
+    pub fn with_file_metadata_cache(mut self, cache: Option<FileMetadataCache>) -> Self {
+        self.file_metadata_cache = cache;
+        self
+    }
+
+    /// Read the column chunks from `cache` if present, and store the column
+    /// chunks fetched from the object store in it.
+    ///
+    /// Only whole column chunks are cached: pages fetched individually, for
+    /// example when the page index is used to skip pages, are not.
+    pub fn with_column_chunk_cache(mut self, cache: Option<ColumnChunkCache>) -> Self {
+        self.column_chunk_cache = cache;
+        self
     }
 }
 
@@ -83,12 +121,136 @@ impl DefaultParquetFileReaderFactory {
 /// This implementation uses the [`ParquetObjectReader`] to read data from the
 /// object store on demand, as required, tracking the number of bytes read.
 ///
-/// This implementation does not coalesce I/O operations or cache bytes. Such
-/// optimizations can be done either at the object store level or by providing a
-/// custom implementation of [`ParquetFileReaderFactory`].
+/// This implementation does not coalesce I/O operations. Column chunks are
+/// cached if a [`ColumnChunkCache`] is provided. Other optimizations can be done
+/// either at the object store level or by providing a custom implementation of
+/// [`ParquetFileReaderFactory`].
 pub(crate) struct ParquetFileReader {
     pub file_metrics: ParquetFileMetrics,
     pub inner: ParquetObjectReader,
+    pub metadata: Option<CachedMetadata>,
+    pub column_chunks: Option<CachedColumnChunks>,
+    pub file_size: usize,
+}
+
+/// Load the metadata of a parquet file of `file_size` bytes, reporting files
+/// with an encrypted footer explicitly rather than as corrupted
+async fn load_metadata(
+    inner: &mut ParquetObjectReader,
+    file_size: usize,
+) -> parquet::errors::Result<Arc<ParquetMetaData>> {
+    match inner.get_metadata().await {
+        Ok(metadata) => Ok(metadata),
+        Err(e) if has_encrypted_footer(inner, file_size).await => Err(ParquetError::NYI(
+            format!("reading parquet files with an encrypted footer: {e}"),
+        )),
+        Err(e) => Err(e),
+    }
+}
+
+/// [`ParquetMetaData`] stored in a [`FileMetadataCache`]
+#[derive(Debug)]
+pub struct CachedParquetMetaData(Arc<ParquetMetaData>);
+
+impl CachedParquetMetaData {
+    /// Wrap `metadata` to store it in a [`FileMetadataCache`]
+    pub fn new(metadata: Arc<ParquetMetaData>) -> Self {
+        Self(metadata)
+    }
+
+    /// The cached metadata
+    pub fn parquet_metadata(&self) -> &Arc<ParquetMetaData> {
+        &self.0
+    }
+}
+
+impl FileMetadata for CachedParquetMetaData {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn memory_size(&self) -> usize {
+        self.0.memory_size()
+    }
+}
+
+/// The metadata of a file in a [`FileMetadataCache`]
+pub(crate) struct CachedMetadata {
+    cache: FileMetadataCache,
+    object_meta: ObjectMeta,
+}
+
+impl CachedMetadata {
+    pub fn new(cache: FileMetadataCache, object_meta: ObjectMeta) -> Self {
+        Self { cache, object_meta }
+    }
+
+    fn get(&self) -> Option<Arc<ParquetMetaData>> {
+        let metadata = self
+            .cache
+            .get_with_extra(&self.object_meta.location, &self.object_meta)?;
+        metadata
+            .as_any()
+            .downcast_ref::<CachedParquetMetaData>()
+            .map(|metadata| Arc::clone(metadata.parquet_metadata()))
+    }
+
+    fn put(&self, metadata: Arc<ParquetMetaData>) {
+        self.cache.put_with_extra(
+            &self.object_meta.location,
+            Arc::new(CachedParquetMetaData::new(metadata)),
+            &self.object_meta,
+        );
+    }
+}
+
+/// The column chunks of a file in a [`ColumnChunkCache`]
+pub(crate) struct CachedColumnChunks {
+    cache: ColumnChunkCache,
+    object_meta: ObjectMeta,
+    /// The row group and column of each column chunk, by byte range in the
+    /// file. Empty until the metadata is read.
+    chunks: HashMap<Range<usize>, (usize, usize)>,
+}
+
+impl CachedColumnChunks {
+    pub fn new(cache: ColumnChunkCache, object_meta: ObjectMeta) -> Self {
+        Self {
+            cache,
+            object_meta,
+            chunks: HashMap::new(),
+        }
+    }
+
+    /// Record the byte ranges of the column chunks described by `metadata`
+    fn index(&mut self, metadata: &ParquetMetaData) {
+        self.chunks = metadata
+            .row_groups()
+            .iter()
+            .enumerate()
+            .flat_map(|(row_group, rg_metadata)| {
+                rg_metadata.columns().iter().enumerate().map(
+                    move |(column, col_metadata)| {
+                        let (start, len) = col_metadata.byte_range();
+                        let range = start as usize..(start + len) as usize;
+                        (range, (row_group, column))
+                    },
+                )
+            })
+            .collect();
+    }
+
+    /// The cache key of the column chunk stored at `range`, if any
+    fn key(&self, range: &Range<usize>) -> Option<ColumnChunkKey> {
+        let (row_group, column) = *self.chunks.get(range)?;
+        Some(ColumnChunkKey {
+            location: self.object_meta.location.clone(),
+            e_tag: self.object_meta.e_tag.clone(),
+            size: self.object_meta.size,
+            row_group,
+            column,
+        })
+    }
 }
 
 impl AsyncFileReader for ParquetFileReader {
@@ -107,15 +269,69 @@ impl AsyncFileReader for ParquetFileReader {
     where
         Self: Send,
     {
-        let total = ranges.iter().map(|r| r.end - r.start).sum();
-        self.file_metrics.bytes_scanned.add(total);
-        self.inner.get_byte_ranges(ranges)
+        let Some(column_chunks) = &self.column_chunks else {
+            let total = ranges.iter().map(|r| r.end - r.start).sum();
+            self.file_metrics.bytes_scanned.add(total);
+            return self.inner.get_byte_ranges(ranges);
+        };
+
+        let keys: Vec<_> = ranges.iter().map(|r| column_chunks.key(r)).collect();
+        let mut cached: Vec<_> = keys
+            .iter()
+            .map(|key| key.as_ref().and_then(|key| column_chunks.cache.get(key)))
+            .collect();
+        let (missing_ranges, missing): (Vec<_>, Vec<_>) = ranges
+            .into_iter()
+            .enumerate()
+            .filter(|(idx, _)| cached[*idx].is_none())
+            .map(|(idx, range)| (range, idx))
+            .unzip();
+
+        async move {
+            if !missing_ranges.is_empty() {
+                let total = missing_ranges.iter().map(|r| r.end - r.start).sum();
+                self.file_metrics.bytes_scanned.add(total);
+                let fetched = self.inner.get_byte_ranges(missing_ranges).await?;
+                for (idx, bytes) in missing.into_iter().zip(fetched) {
+                    if let (Some(key), Some(column_chunks)) =
+                        (&keys[idx], &self.column_chunks)
+                    {
+                        column_chunks.cache.put(key, bytes.clone());
+                    }
+                    cached[idx] = Some(bytes);
+                }
+            }
+            Ok(cached.into_iter().flatten().collect())
+        }
+        .boxed()
     }
 
     fn get_metadata(
         &mut self,
     ) -> BoxFuture<'_, parquet::errors::Result<Arc<ParquetMetaData>>> {
-        self.inner.get_metadata()
+        async move {
+            let metadata = match &self.metadata {
+                Some(cached) => match cached.get() {
+                    Some(metadata) => {
+                        self.file_metrics.metadata_cache_hits.add(1);
+                        metadata
+                    }
+                    None => {
+                        self.file_metrics.metadata_cache_misses.add(1);
+                        let metadata =
+                            load_metadata(&mut self.inner, self.file_size).await?;
+                        cached.put(Arc::clone(&metadata));
+                        metadata
+                    }
+                },
+                None => load_metadata(&mut self.inner, self.file_size).await?,
+            };
+            if let Some(column_chunks) = &mut self.column_chunks {
+                column_chunks.index(&metadata);
+            }
+            Ok(metadata)
+        }
+        .boxed()
     }
 }
 
@@ -133,15 +349,31 @@ impl ParquetFileReaderFactory for DefaultParquetFileReaderFactory {
             metrics,
         );
         let store = Arc::clone(&self.store);
+        let column_chunks = self.column_chunk_cache.as_ref().map(|cache| {
+            CachedColumnChunks::new(Arc::clone(cache), file_meta.object_meta.clone())
+        });
+        let metadata = self.file_metadata_cache.as_ref().map(|cache| {
+            CachedMetadata::new(Arc::clone(cache), file_meta.object_meta.clone())
+        });
+        let file_size = file_meta.object_meta.size;
         let mut inner = ParquetObjectReader::new(store, file_meta.object_meta);
 
         if let Some(hint) = metadata_size_hint {
             inner = inner.with_footer_size_hint(hint)
         };
+        if metadata.is_some() {
+            // cache the page indexes along with the footer
+            inner = inner
+                .with_preload_column_index(true)
+                .with_preload_offset_index(true);
+        }
 
         Ok(Box::new(ParquetFileReader {
             inner,
             file_metrics,
+            metadata,
+            column_chunks,
+            file_size,
         }))
     }
 }
@@ -58,9 +58,28 @@
 //! 8. Build the `RowFilter` with the sorted predicates followed by
 //!    the unsorted predicates. Within each partition, predicates are
 //!    still be sorted by size.
+//!
+//! # Adaptive filtering
+//!
+//! The static heuristics above do not take the selectivity of each predicate
+//! into account, which is usually not known before the data is read. When
+//! adaptive filtering is enabled, the selectivity and evaluation cost of each
+//! conjunct is measured while scanning (see [`AdaptiveFilterStats`]) and shared
+//! between all the files of a scan:
+//!
+//! 1. Predicates that have not yet been measured are evaluated first, in the
+//!    heuristic order described above.
+//! 2. Measured predicates are ordered by their evaluation time per pruned row,
+//!    so cheap and selective predicates run before expensive or unselective
+//!    ones.
+//! 3. Predicates that let almost all rows through are disabled: they stop
+//!    being evaluated during decoding, including mid file, as the decoder is
+//!    handed an all `true` mask. The rows are instead filtered after the scan,
+//!    so the scan must not report the filters as exactly applied.
 
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::Arc;
 
 use arrow::array::BooleanArray;
@@ -73,6 +92,7 @@ use parquet::file::metadata::ParquetMetaData;
 
 use crate::datasource::schema_adapter::SchemaMapper;
 use datafusion_common::cast::as_boolean_array;
+use datafusion_common::instant::Instant;
 use datafusion_common::tree_node::{
     Transformed, TransformedResult, TreeNode, TreeNodeRecursion, TreeNodeRewriter,
 };
@@ -112,6 +132,17 @@ pub(crate) struct DatafusionArrowPredicate {
     time: metrics::Time,
     /// used to perform type coercion while filtering rows
     schema_mapping: Arc<dyn SchemaMapper>,
+    /// runtime statistics of this predicate, if adaptive filtering is enabled
+    adaptive: Option<AdaptivePredicate>,
+}
+
+/// Runtime statistics of a [`DatafusionArrowPredicate`] in adaptive mode
+#[derive(Debug)]
+struct AdaptivePredicate {
+    /// statistics shared with the other files of the scan
+    stats: Arc<PredicateStats>,
+    /// incremented when this predicate gets disabled
+    filters_disabled: metrics::Count,
 }
 
 impl DatafusionArrowPredicate {
@@ -147,8 +178,25 @@ impl DatafusionArrowPredicate {
             rows_matched,
             time,
             schema_mapping,
+            adaptive: None,
         })
     }
+
+    /// Record the selectivity and cost of this predicate in `stats`, and stop
+    /// evaluating it once `stats` is disabled.
+    ///
+    /// `filters_disabled` is incremented if this predicate disables `stats`
+    fn with_adaptive_stats(
+        mut self,
+        stats: Arc<PredicateStats>,
+        filters_disabled: metrics::Count,
+    ) -> Self {
+        self.adaptive = Some(AdaptivePredicate {
+            stats,
+            filters_disabled,
+        });
+        self
+    }
 }
 
 impl ArrowPredicate for DatafusionArrowPredicate {
@@ -157,6 +205,13 @@ impl ArrowPredicate for DatafusionArrowPredicate {
     }
 
     fn evaluate(&mut self, mut batch: RecordBatch) -> ArrowResult<BooleanArray> {
+        if let Some(adaptive) = &self.adaptive {
+            if adaptive.stats.is_disabled() {
+                // the rows are filtered after the scan instead
+                return Ok(BooleanArray::from(vec![true; batch.num_rows()]));
+            }
+        }
+
         if !self.projection.is_empty() {
             batch = batch.project(&self.projection)?;
         };
@@ -165,6 +220,7 @@ impl ArrowPredicate for DatafusionArrowPredicate {
 
         // scoped timer updates on drop
         let mut timer = self.time.timer();
+        let start = Instant::now();
 
         self.physical_expr
             .evaluate(&batch)
@@ -176,6 +232,12 @@ impl ArrowPredicate for DatafusionArrowPredicate {
                 self.rows_pruned.add(num_pruned);
                 self.rows_matched.add(num_matched);
                 timer.stop();
+                if let Some(adaptive) = &self.adaptive {
+                    let elapsed = start.elapsed().as_nanos() as u64;
+                    if adaptive.stats.record(bool_arr.len(), num_matched, elapsed) {
+                        adaptive.filters_disabled.add(1);
+                    }
+                }
                 Ok(bool_arr)
             })
             .map_err(|e| {
@@ -186,6 +248,88 @@ impl ArrowPredicate for DatafusionArrowPredicate {
     }
 }
 
+/// Minimum number of rows a predicate must have evaluated before its measured
+/// selectivity and cost are used to reorder or disable it
+const ADAPTIVE_MIN_ROWS: usize = 8192;
+
+/// Predicates that let at least this fraction of the rows they evaluate pass
+/// are disabled in adaptive mode
+const ADAPTIVE_DISABLE_PASS_RATIO: f64 = 0.95;
+
+/// Selectivity and evaluation cost of each conjunct of a scan predicate,
+/// measured while scanning and shared by all the files of the scan.
+///
+/// See the module level documentation for more information.
+#[derive(Debug)]
+pub(crate) struct AdaptiveFilterStats {
+    /// one entry per conjunct of the predicate, in `split_conjunction` order
+    predicates: Vec<Arc<PredicateStats>>,
+}
+
+impl AdaptiveFilterStats {
+    /// Create empty statistics for the conjuncts of `predicate`
+    pub fn new(predicate: &Arc<dyn PhysicalExpr>) -> Self {
+        let predicates = split_conjunction(predicate)
+            .into_iter()
+            .map(|_| Arc::new(PredicateStats::default()))
+            .collect();
+        Self { predicates }
+    }
+}
+
+/// Runtime statistics of a single conjunct
+#[derive(Debug, Default)]
+struct PredicateStats {
+    /// number of rows the predicate was evaluated on
+    rows_evaluated: AtomicUsize,
+    /// number of rows that passed the predicate
+    rows_matched: AtomicUsize,
+    /// time spent evaluating the predicate, in nanoseconds
+    eval_nanos: AtomicU64,
+    /// set once the predicate is found not to be selective enough
+    disabled: AtomicBool,
+}
+
+impl PredicateStats {
+    fn is_disabled(&self) -> bool {
+        self.disabled.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Record an evaluation of the predicate, returning true if the predicate
+    /// was disabled as a result
+    fn record(&self, rows_evaluated: usize, rows_matched: usize, nanos: u64) -> bool {
+        let rows_evaluated = self
+            .rows_evaluated
+            .fetch_add(rows_evaluated, AtomicOrdering::Relaxed)
+            + rows_evaluated;
+        let rows_matched = self
+            .rows_matched
+            .fetch_add(rows_matched, AtomicOrdering::Relaxed)
+            + rows_matched;
+        self.eval_nanos.fetch_add(nanos, AtomicOrdering::Relaxed);
+
+        rows_evaluated >= ADAPTIVE_MIN_ROWS
+            && rows_matched as f64 >= rows_evaluated as f64 * ADAPTIVE_DISABLE_PASS_RATIO
+            && !self.disabled.swap(true, AtomicOrdering::Relaxed)
+    }
+
+    /// The evaluation time per pruned row, or `None` if the predicate has not
+    /// evaluated enough rows yet
+    fn cost_per_pruned_row(&self) -> Option<f64> {
+        let rows_evaluated = self.rows_evaluated.load(AtomicOrdering::Relaxed);
+        if rows_evaluated < ADAPTIVE_MIN_ROWS {
+            return None;
+        }
+        let rows_matched = self.rows_matched.load(AtomicOrdering::Relaxed);
+        let rows_pruned = rows_evaluated.saturating_sub(rows_matched);
+        let nanos = self.eval_nanos.load(AtomicOrdering::Relaxed);
+        Some(match rows_pruned {
+            0 => f64::INFINITY,
+            _ => nanos as f64 / rows_pruned as f64,
+        })
+    }
+}
+
 /// A candidate expression for creating a `RowFilter`.
 ///
 /// Each candidate contains the expression as well as data to estimate the cost
@@ -520,7 +664,12 @@ fn columns_sorted(
 /// For example, if the expression is `a = 1 AND b = 2 AND c = 3` and `b = 2`
 /// can not be evaluated for some reason, the returned `RowFilter` will contain
 /// `a = 1` and `c = 3`.
-pub fn build_row_filter(
+///
+/// If `adaptive_stats` is provided, the predicates are ordered (and possibly
+/// skipped) based on the statistics measured so far, instead of
+/// `reorder_predicates`. See the module level documentation for details.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_row_filter(
     expr: &Arc<dyn PhysicalExpr>,
     file_schema: &Schema,
     table_schema: &Schema,
@@ -528,6 +677,7 @@ pub fn build_row_filter(
     reorder_predicates: bool,
     file_metrics: &ParquetFileMetrics,
     schema_mapping: Arc<dyn SchemaMapper>,
+    adaptive_stats: Option<&AdaptiveFilterStats>,
 ) -> Result<Option<RowFilter>> {
     let rows_pruned = &file_metrics.pushdown_rows_pruned;
     let rows_matched = &file_metrics.pushdown_rows_matched;
@@ -537,35 +687,56 @@ pub fn build_row_filter(
     // `a = 1 AND b = 2 AND c = 3` -> [`a = 1`, `b = 2`, `c = 3`]
     let predicates = split_conjunction(expr);
 
-    // Determine which conjuncts can be evaluated as ArrowPredicates, if any
-    let mut candidates: Vec<FilterCandidate> = predicates
+    // Determine which conjuncts can be evaluated as ArrowPredicates, if any,
+    // keeping track of the index of each conjunct
+    let mut candidates: Vec<(usize, FilterCandidate)> = predicates
         .into_iter()
-        .map(|expr| {
+        .enumerate()
+        .map(|(idx, expr)| {
             FilterCandidateBuilder::new(expr.clone(), file_schema, table_schema)
                 .build(metadata)
+                .map(|candidate| candidate.map(|candidate| (idx, candidate)))
         })
         .collect::<Result<Vec<_>, _>>()?
         .into_iter()
         .flatten()
         .collect();
 
-    // no candidates
-    if candidates.is_empty() {
-        return Ok(None);
-    }
+    let heuristic_order = |c1: &FilterCandidate, c2: &FilterCandidate| match c1
+        .can_use_index
+        .cmp(&c2.can_use_index)
+    {
+        Ordering::Equal => c1.required_bytes.cmp(&c2.required_bytes),
+        ord => ord,
+    };
 
-    if reorder_predicates {
-        candidates.sort_unstable_by(|c1, c2| {
-            match c1.can_use_index.cmp(&c2.can_use_index) {
-                Ordering::Equal => c1.required_bytes.cmp(&c2.required_bytes),
-                ord => ord,
+    if let Some(stats) = adaptive_stats {
+        let stats = &stats.predicates;
+        candidates.retain(|(idx, _)| !stats[*idx].is_disabled());
+        candidates.sort_by(|(_, c1), (_, c2)| heuristic_order(c1, c2));
+        // stable sort: unmeasured predicates keep their heuristic order and
+        // are evaluated first, as `None` sorts before `Some`
+        candidates.sort_by(|(idx1, _), (idx2, _)| {
+            match (
+                stats[*idx1].cost_per_pruned_row(),
+                stats[*idx2].cost_per_pruned_row(),
+            ) {
+                (Some(cost1), Some(cost2)) => cost1.total_cmp(&cost2),
+                (cost1, cost2) => cost1.is_some().cmp(&cost2.is_some()),
             }
         });
+    } else if reorder_predicates {
+        candidates.sort_unstable_by(|(_, c1), (_, c2)| heuristic_order(c1, c2));
+    }
+
+    // no candidates
+    if candidates.is_empty() {
+        return Ok(None);
     }
 
     candidates
         .into_iter()
-        .map(|candidate| {
+        .map(|(idx, candidate)| {
             DatafusionArrowPredicate::try_new(
                 candidate,
                 file_schema,
@@ -575,6 +746,13 @@ pub fn build_row_filter(
                 time.clone(),
                 Arc::clone(&schema_mapping),
             )
+            .map(|pred| match adaptive_stats {
+                Some(stats) => pred.with_adaptive_stats(
+                    Arc::clone(&stats.predicates[idx]),
+                    file_metrics.pushdown_filters_disabled.clone(),
+                ),
+                None => pred,
+            })
             .map(|pred| Box::new(pred) as _)
         })
         .collect::<Result<Vec<_>, _>>()
@@ -765,6 +943,47 @@ mod test {
         }
     }
 
+    #[test]
+    fn adaptive_predicate_stats() {
+        let selective = PredicateStats::default();
+        let unselective = PredicateStats::default();
+
+        // not measured until enough rows were evaluated
+        assert!(!selective.record(ADAPTIVE_MIN_ROWS - 1, 0, 100));
+        assert!(!unselective.record(ADAPTIVE_MIN_ROWS - 1, ADAPTIVE_MIN_ROWS - 1, 100));
+        assert_eq!(selective.cost_per_pruned_row(), None);
+        assert_eq!(unselective.cost_per_pruned_row(), None);
+
+        assert!(!selective.record(1, 0, 100));
+        assert_eq!(
+            selective.cost_per_pruned_row(),
+            Some(200.0 / ADAPTIVE_MIN_ROWS as f64)
+        );
+        assert!(!selective.is_disabled());
+
+        // disabled exactly once
+        assert!(unselective.record(1, 1, 100));
+        assert!(unselective.is_disabled());
+        assert!(!unselective.record(1, 1, 100));
+        assert_eq!(unselective.cost_per_pruned_row(), Some(f64::INFINITY));
+    }
+
+    #[test]
+    fn adaptive_filter_stats_per_conjunct() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("int_col", DataType::Int32, true),
+            Field::new("bigint_col", DataType::Int64, true),
+        ]);
+        let expr = col("int_col")
+            .gt(lit(1))
+            .and(col("bigint_col").lt(lit(10i64)).and(col("id").eq(lit(3))));
+        let expr = logical2physical(&expr, &schema);
+
+        let stats = AdaptiveFilterStats::new(&expr);
+        assert_eq!(stats.predicates.len(), 3);
+    }
+
     #[test]
     fn nested_data_structures_prevent_pushdown() {
         let table_schema = get_basic_table_schema();
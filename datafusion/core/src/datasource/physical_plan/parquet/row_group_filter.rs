@@ -1522,9 +1522,13 @@ mod tests {
         let metrics = ExecutionPlanMetricsSet::new();
         let file_metrics =
             ParquetFileMetrics::new(0, object_meta.location.as_ref(), &metrics);
+        let file_size = object_meta.size;
         let reader = ParquetFileReader {
             inner: ParquetObjectReader::new(Arc::new(in_memory), object_meta),
             file_metrics: file_metrics.clone(),
+            metadata: None,
+            column_chunks: None,
+            file_size,
         };
         let mut builder = ParquetRecordBatchStreamBuilder::new(reader).await.unwrap();
 
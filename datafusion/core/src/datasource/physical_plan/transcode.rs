@@ -0,0 +1,322 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Conversion of line oriented text files that the arrow CSV reader cannot
+//! parse, such as files with multi-byte delimiters or fixed width columns,
+//! to comma separated values
+
+use std::io::Read;
+use std::ops::Range;
+
+use crate::error::Result;
+
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+
+/// The layout of the values of each line of a text file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum LineFormat {
+    /// Values separated by a sequence of bytes, optionally enclosed in the
+    /// quote character of the [`CsvTranscoder`]
+    Delimited {
+        delimiter: Vec<u8>,
+        escape: Option<u8>,
+    },
+    /// Values at fixed byte ranges of each line, padded with spaces
+    FixedWidth { columns: Vec<Range<usize>> },
+}
+
+/// Converts lines of a [`LineFormat`] to lines of values separated by commas,
+/// quoting the values which contain commas or quotes.
+///
+/// Lines do not need to be complete in the chunks passed to
+/// [`Self::transcode`], the end of a line is buffered until its newline.
+#[derive(Debug, Clone)]
+pub(crate) struct CsvTranscoder {
+    format: LineFormat,
+    quote: u8,
+    partial: Vec<u8>,
+}
+
+impl CsvTranscoder {
+    /// The delimiter of the converted values
+    pub const DELIMITER: u8 = b',';
+
+    pub fn new(format: LineFormat, quote: u8) -> Self {
+        Self {
+            format,
+            quote,
+            partial: vec![],
+        }
+    }
+
+    /// Converts the lines of `data` which end in it, appending them to `out`
+    pub fn transcode(&mut self, data: &[u8], out: &mut Vec<u8>) {
+        let Some(last) = data.iter().rposition(|b| *b == b'\n') else {
+            self.partial.extend_from_slice(data);
+            return;
+        };
+        let mut partial = std::mem::take(&mut self.partial);
+        partial.extend_from_slice(&data[..=last]);
+        for line in partial.split_inclusive(|b| *b == b'\n') {
+            self.transcode_line(line, out);
+        }
+        partial.clear();
+        partial.extend_from_slice(&data[last + 1..]);
+        self.partial = partial;
+    }
+
+    /// Converts the last line, if it does not end with a newline
+    pub fn finish(&mut self, out: &mut Vec<u8>) {
+        let partial = std::mem::take(&mut self.partial);
+        if !partial.is_empty() {
+            self.transcode_line(&partial, out);
+        }
+    }
+
+    fn transcode_line(&self, line: &[u8], out: &mut Vec<u8>) {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        match &self.format {
+            LineFormat::Delimited { delimiter, escape } => {
+                let mut start = 0;
+                let mut first = true;
+                while start <= line.len() {
+                    let end =
+                        self.delimited_value_end(&line[start..], delimiter, *escape);
+                    if !first {
+                        out.push(Self::DELIMITER);
+                    }
+                    first = false;
+                    let value = &line[start..start + end];
+                    if value.first() == Some(&self.quote) {
+                        // Quoted values are valid CSV values
+                        out.extend_from_slice(value);
+                    } else {
+                        self.push_value(value, *escape, out);
+                    }
+                    start += end + delimiter.len();
+                }
+            }
+            LineFormat::FixedWidth { columns } => {
+                for (i, column) in columns.iter().enumerate() {
+                    if i > 0 {
+                        out.push(Self::DELIMITER);
+                    }
+                    let start = column.start.min(line.len());
+                    let end = column.end.min(line.len());
+                    self.push_value(trim_spaces(&line[start..end]), None, out);
+                }
+            }
+        }
+        out.push(b'\n');
+    }
+
+    /// Returns the length of the delimited value at the start of `line`
+    fn delimited_value_end(
+        &self,
+        line: &[u8],
+        delimiter: &[u8],
+        escape: Option<u8>,
+    ) -> usize {
+        let mut quoted = false;
+        let mut i = 0;
+        while i < line.len() {
+            let b = line[i];
+            if quoted {
+                if Some(b) == escape || (b == self.quote && line.get(i + 1) == Some(&b)) {
+                    // An escaped character or a doubled quote
+                    i += 2;
+                    continue;
+                }
+                quoted = b != self.quote;
+            } else if b == self.quote && i == 0 {
+                quoted = true;
+            } else if line[i..].starts_with(delimiter) {
+                return i;
+            }
+            i += 1;
+        }
+        line.len()
+    }
+
+    /// Appends `value`, quoting it if it contains a comma or a quote
+    fn push_value(&self, value: &[u8], escape: Option<u8>, out: &mut Vec<u8>) {
+        let special = |b: &u8| {
+            *b == Self::DELIMITER || *b == self.quote || *b == b'\r' || Some(*b) == escape
+        };
+        if !value.iter().any(special) {
+            out.extend_from_slice(value);
+            return;
+        }
+        out.push(self.quote);
+        for b in value {
+            if *b == self.quote {
+                out.push(self.quote);
+            } else if Some(*b) == escape {
+                out.push(*b);
+            }
+            out.push(*b);
+        }
+        out.push(self.quote);
+    }
+}
+
+/// Removes the leading and trailing whitespace of a fixed width value
+fn trim_spaces(value: &[u8]) -> &[u8] {
+    let start = value
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(value.len());
+    let end = value
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map_or(start, |end| end + 1);
+    &value[start..end]
+}
+
+/// A [`Read`] of the comma separated values converted from another reader
+pub(crate) struct TranscodingReader<R> {
+    inner: R,
+    transcoder: CsvTranscoder,
+    buffer: Vec<u8>,
+    out: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+impl<R: Read> TranscodingReader<R> {
+    pub fn new(inner: R, transcoder: CsvTranscoder) -> Self {
+        Self {
+            inner,
+            transcoder,
+            buffer: vec![0; 64 * 1024],
+            out: vec![],
+            position: 0,
+            finished: false,
+        }
+    }
+}
+
+impl<R: Read> Read for TranscodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.position == self.out.len() {
+            if self.finished {
+                return Ok(0);
+            }
+            self.out.clear();
+            self.position = 0;
+            let read = self.inner.read(&mut self.buffer)?;
+            if read == 0 {
+                self.transcoder.finish(&mut self.out);
+                self.finished = true;
+            } else {
+                self.transcoder
+                    .transcode(&self.buffer[..read], &mut self.out);
+            }
+        }
+        let len = buf.len().min(self.out.len() - self.position);
+        buf[..len].copy_from_slice(&self.out[self.position..self.position + len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+/// Returns the comma separated values converted from a stream of bytes
+pub(crate) fn transcode_stream(
+    stream: BoxStream<'static, Result<Bytes>>,
+    mut transcoder: CsvTranscoder,
+) -> BoxStream<'static, Result<Bytes>> {
+    stream
+        .map(Some)
+        .chain(futures::stream::once(futures::future::ready(None)))
+        .map(move |chunk| {
+            let mut out = vec![];
+            match chunk {
+                Some(chunk) => transcoder.transcode(&chunk?, &mut out),
+                None => transcoder.finish(&mut out),
+            }
+            Ok(Bytes::from(out))
+        })
+        .try_filter(|chunk| futures::future::ready(!chunk.is_empty()))
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcode(format: LineFormat, chunks: &[&str]) -> String {
+        let mut transcoder = CsvTranscoder::new(format, b'"');
+        let mut out = vec![];
+        for chunk in chunks {
+            transcoder.transcode(chunk.as_bytes(), &mut out);
+        }
+        transcoder.finish(&mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn transcode_delimited() {
+        let format = LineFormat::Delimited {
+            delimiter: b"||".to_vec(),
+            escape: None,
+        };
+        assert_eq!(
+            transcode(format.clone(), &["a||b||c\n1||2", ",5||\"x||y\"\r\n||"]),
+            "a,b,c\n1,\"2,5\",\"x||y\"\n,\n"
+        );
+        assert_eq!(
+            transcode(format, &["a|b||\"c\"\"||d\"||"]),
+            "a|b,\"c\"\"||d\",\n"
+        );
+
+        let format = LineFormat::Delimited {
+            delimiter: b"::".to_vec(),
+            escape: Some(b'\\'),
+        };
+        assert_eq!(
+            transcode(format, &["\"a\\\"::b\"::c\\d\n"]),
+            "\"a\\\"::b\",\"c\\\\d\"\n"
+        );
+    }
+
+    #[test]
+    fn transcode_fixed_width() {
+        let format = LineFormat::FixedWidth {
+            columns: vec![0..2, 2..7, 7..9],
+        };
+        assert_eq!(
+            transcode(format, &["idname xx\n 1a,b  ", "7\n 2\n"]),
+            "id,name,xx\n1,\"a,b\",7\n2,,\n"
+        );
+    }
+
+    #[test]
+    fn transcoding_reader() {
+        let format = LineFormat::Delimited {
+            delimiter: b"<>".to_vec(),
+            escape: None,
+        };
+        let transcoder = CsvTranscoder::new(format, b'"');
+        let mut reader = TranscodingReader::new("a<>b\nc<>d".as_bytes(), transcoder);
+        let mut out = String::new();
+        reader.read_to_string(&mut out).unwrap();
+        assert_eq!(out, "a,b\nc,d\n");
+    }
+}
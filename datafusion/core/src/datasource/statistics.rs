@@ -114,6 +114,8 @@ pub async fn get_statistics_with_limit(
                         max_value: file_max,
                         min_value: file_min,
                         distinct_count: _,
+                        histogram: _,
+                        most_common_values: _,
                     } = file_col_stats;
 
                     col_stats.null_count = add_row_stats(*file_nc, col_stats.null_count);
@@ -205,6 +207,8 @@ pub(crate) fn get_col_stats(
                 max_value: max_value.map(Precision::Exact).unwrap_or(Precision::Absent),
                 min_value: min_value.map(Precision::Exact).unwrap_or(Precision::Absent),
                 distinct_count: Precision::Absent,
+                histogram: Precision::Absent,
+                most_common_values: Precision::Absent,
             }
         })
         .collect()
@@ -0,0 +1,313 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ColumnAnalyzer`] computing the statistics of a column for `ANALYZE TABLE`
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::physical_plan::common::{compute_column_statistics, is_sortable};
+
+use arrow::array::{Array, ArrayRef};
+use arrow::datatypes::DataType;
+use datafusion_common::config::ExecutionOptions;
+use datafusion_common::stats::{Histogram, HistogramBucket, MostCommonValues, Precision};
+use datafusion_common::{ColumnStatistics, Result, ScalarValue};
+use datafusion_expr::Accumulator;
+use datafusion_functions_aggregate::min_max::{MaxAccumulator, MinAccumulator};
+use datafusion_functions_aggregate::space_saving::SpaceSaving;
+use datafusion_functions_aggregate_common::hyperloglog::HyperLogLog;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Number of counters of the [`SpaceSaving`] sketch per most common value
+const COUNTERS_PER_VALUE: usize = 10;
+
+/// Computes the statistics of a column from its batches, in bounded memory.
+///
+/// The null count and the minimum and maximum values are exact. The values
+/// are sampled with reservoir sampling: while the column has no more values
+/// than the sample size, its statistics are computed exactly from all of them
+/// with [`compute_column_statistics`]. Otherwise the distinct count is
+/// estimated with a [`HyperLogLog`] sketch, the most common values with a
+/// [`SpaceSaving`] sketch, and the histogram from the sample.
+pub(super) struct ColumnAnalyzer {
+    histogram_buckets: usize,
+    most_common_values: usize,
+    sample_size: usize,
+    null_count: usize,
+    /// The number of non null values
+    count: usize,
+    /// The accumulators of the column statistics, `None` if the values of the
+    /// column cannot be sorted
+    accumulators: Option<Accumulators>,
+}
+
+struct Accumulators {
+    min: MinAccumulator,
+    max: MaxAccumulator,
+    distinct: HyperLogLog<ScalarValue>,
+    common: SpaceSaving<ScalarValue>,
+    /// Uniform sample of the non null values
+    sample: Vec<ScalarValue>,
+    /// Seeded, so that analyzing the same table gives the same statistics
+    rng: StdRng,
+}
+
+impl ColumnAnalyzer {
+    pub(super) fn try_new(
+        data_type: &DataType,
+        options: &ExecutionOptions,
+    ) -> Result<Self> {
+        let accumulators = if is_sortable(data_type) {
+            Some(Accumulators {
+                min: MinAccumulator::try_new(data_type)?,
+                max: MaxAccumulator::try_new(data_type)?,
+                distinct: HyperLogLog::new(),
+                common: SpaceSaving::new(
+                    options
+                        .analyze_most_common_values
+                        .saturating_mul(COUNTERS_PER_VALUE),
+                ),
+                sample: vec![],
+                rng: StdRng::seed_from_u64(0),
+            })
+        } else {
+            None
+        };
+        Ok(Self {
+            histogram_buckets: options.analyze_histogram_buckets,
+            most_common_values: options.analyze_most_common_values,
+            sample_size: options.analyze_sample_size,
+            null_count: 0,
+            count: 0,
+            accumulators,
+        })
+    }
+
+    /// Updates the statistics with the values of `array`
+    pub(super) fn update(&mut self, array: &ArrayRef) -> Result<()> {
+        let null_count = array
+            .logical_nulls()
+            .map(|nulls| nulls.null_count())
+            .unwrap_or_default();
+        self.null_count += null_count;
+        let Some(accumulators) = &mut self.accumulators else {
+            self.count += array.len() - null_count;
+            return Ok(());
+        };
+        accumulators.min.update_batch(&[Arc::clone(array)])?;
+        accumulators.max.update_batch(&[Arc::clone(array)])?;
+
+        // Pre-aggregate the batch so that each distinct value updates the
+        // sketches only once
+        let mut batch_counts: HashMap<ScalarValue, u64> = HashMap::new();
+        for index in 0..array.len() {
+            if array.is_null(index) {
+                continue;
+            }
+            let value = ScalarValue::try_from_array(array, index)?;
+            self.count += 1;
+            if accumulators.sample.len() < self.sample_size {
+                accumulators.sample.push(value.clone());
+            } else {
+                let position = accumulators.rng.gen_range(0..self.count);
+                if position < self.sample_size {
+                    accumulators.sample[position] = value.clone();
+                }
+            }
+            *batch_counts.entry(value).or_default() += 1;
+        }
+        for (value, count) in batch_counts {
+            accumulators.distinct.add(&value);
+            accumulators.common.add_weighted(value, count);
+        }
+        Ok(())
+    }
+
+    /// Returns the statistics of the column
+    pub(super) fn finish(self) -> Result<ColumnStatistics> {
+        let mut statistics = ColumnStatistics::new_unknown();
+        statistics.null_count = Precision::Exact(self.null_count);
+        let Some(mut accumulators) = self.accumulators else {
+            return Ok(statistics);
+        };
+
+        // All the values are sampled
+        if self.count <= self.sample_size {
+            let arrays = match accumulators.sample.is_empty() {
+                true => vec![],
+                false => vec![ScalarValue::iter_to_array(accumulators.sample)?],
+            };
+            let mut statistics = compute_column_statistics(
+                &arrays,
+                self.histogram_buckets,
+                self.most_common_values,
+            )?;
+            statistics.null_count = Precision::Exact(self.null_count);
+            return Ok(statistics);
+        }
+
+        statistics.min_value = Precision::Exact(accumulators.min.evaluate()?);
+        statistics.max_value = Precision::Exact(accumulators.max.evaluate()?);
+        let distinct_count = accumulators.distinct.count().clamp(1, self.count);
+        statistics.distinct_count = Precision::Inexact(distinct_count);
+
+        // The most common values are the values repeated more than average,
+        // which are left out of the histogram. The counts of the sketch are
+        // overestimated: the values must be repeated more than average even
+        // without their estimation error.
+        let average = self.count as f64 / distinct_count as f64;
+        let common = accumulators
+            .common
+            .top_k(self.most_common_values)
+            .into_iter()
+            .filter(|(_, counter)| {
+                let count = counter.count - counter.error;
+                count > 1 && count as f64 > average
+            })
+            .map(|(value, counter)| (value.clone(), counter.count as usize))
+            .collect::<Vec<_>>();
+        let common_count = common.iter().map(|(_, count)| count).sum::<usize>();
+        accumulators
+            .sample
+            .retain(|value| !common.iter().any(|(common, _)| common == value));
+        if !common.is_empty() {
+            statistics.most_common_values =
+                Precision::Inexact(MostCommonValues::new(common.clone()));
+        }
+
+        if self.histogram_buckets > 0 && !accumulators.sample.is_empty() {
+            let sample_size = accumulators.sample.len();
+            let sample = ScalarValue::iter_to_array(accumulators.sample)?;
+            let sampled =
+                compute_column_statistics(&[sample], self.histogram_buckets, 0)?;
+            if let (Some(histogram), Some(sample_distinct_count)) = (
+                sampled.histogram.get_value(),
+                sampled.distinct_count.get_value(),
+            ) {
+                // Scale the buckets of the sample to the values of the column
+                let count = self.count.saturating_sub(common_count);
+                let count_scale = count as f64 / sample_size as f64;
+                let distinct_scale = (distinct_count.saturating_sub(common.len()) as f64
+                    / *sample_distinct_count as f64)
+                    .max(1.0);
+                let buckets = histogram
+                    .buckets()
+                    .iter()
+                    .map(|bucket| {
+                        let count =
+                            ((bucket.count as f64 * count_scale).round() as usize).max(1);
+                        let distinct_count =
+                            ((bucket.distinct_count as f64 * distinct_scale).round()
+                                as usize)
+                                .clamp(1, count);
+                        HistogramBucket {
+                            lower: bucket.lower.clone(),
+                            upper: bucket.upper.clone(),
+                            count,
+                            distinct_count,
+                        }
+                    })
+                    .collect();
+                statistics.histogram = Precision::Inexact(Histogram::try_new(buckets)?);
+            }
+        }
+
+        Ok(statistics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::Int32Array;
+
+    fn options(sample_size: usize) -> ExecutionOptions {
+        ExecutionOptions {
+            analyze_histogram_buckets: 4,
+            analyze_most_common_values: 2,
+            analyze_sample_size: sample_size,
+            ..Default::default()
+        }
+    }
+
+    /// 1000 values: 500 zeros, and the values 1 to 500, in batches of 100
+    fn analyze(sample_size: usize) -> Result<ColumnStatistics> {
+        let mut analyzer =
+            ColumnAnalyzer::try_new(&DataType::Int32, &options(sample_size))?;
+        for batch in 0..10 {
+            let values = (batch * 100..(batch + 1) * 100)
+                .map(|v| if v % 2 == 0 { Some(0) } else { Some(v / 2 + 1) })
+                .chain([None]);
+            analyzer.update(&(Arc::new(Int32Array::from_iter(values)) as ArrayRef))?;
+        }
+        analyzer.finish()
+    }
+
+    #[test]
+    fn exact_statistics_of_sampled_column() -> Result<()> {
+        let statistics = analyze(1000)?;
+        assert_eq!(statistics.null_count, Precision::Exact(10));
+        assert_eq!(statistics.distinct_count, Precision::Exact(501));
+        assert_eq!(statistics.min_value, Precision::Exact(ScalarValue::from(0)));
+        assert_eq!(
+            statistics.max_value,
+            Precision::Exact(ScalarValue::from(500))
+        );
+        assert_eq!(
+            statistics.most_common_values,
+            Precision::Exact(MostCommonValues::new(vec![(ScalarValue::from(0), 500)]))
+        );
+        let histogram = statistics.histogram.get_value().unwrap();
+        assert_eq!(histogram.count(), 500);
+        assert!(statistics.histogram.is_exact().unwrap());
+        Ok(())
+    }
+
+    #[test]
+    fn estimated_statistics_beyond_sample_size() -> Result<()> {
+        let statistics = analyze(100)?;
+        assert_eq!(statistics.null_count, Precision::Exact(10));
+        assert_eq!(statistics.min_value, Precision::Exact(ScalarValue::from(0)));
+        assert_eq!(
+            statistics.max_value,
+            Precision::Exact(ScalarValue::from(500))
+        );
+        let distinct_count = *statistics.distinct_count.get_value().unwrap();
+        assert!(!statistics.distinct_count.is_exact().unwrap());
+        assert!((450..550).contains(&distinct_count), "{distinct_count}");
+        let most_common_values = statistics.most_common_values.get_value().unwrap();
+        assert!(!statistics.most_common_values.is_exact().unwrap());
+        assert_eq!(most_common_values.values().len(), 1);
+        assert_eq!(most_common_values.values()[0].0, ScalarValue::from(0));
+        assert!(most_common_values.values()[0].1 >= 500);
+
+        // The histogram of the sampled values is scaled to the other values
+        let histogram = statistics.histogram.get_value().unwrap();
+        assert!(!statistics.histogram.is_exact().unwrap());
+        assert!(histogram.buckets().len() <= 4);
+        assert!(
+            (490..510).contains(&histogram.count()),
+            "{}",
+            histogram.count()
+        );
+        Ok(())
+    }
+}
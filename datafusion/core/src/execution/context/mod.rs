@@ -40,9 +40,10 @@ use crate::{
     logical_expr::AggregateUDF,
     logical_expr::ScalarUDF,
     logical_expr::{
-        CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
-        CreateMemoryTable, CreateView, DropCatalogSchema, DropFunction, DropTable,
-        DropView, LogicalPlan, LogicalPlanBuilder, SetVariable, TableType, UNNAMED_TABLE,
+        AnalyzeTable, CreateCatalog, CreateCatalogSchema, CreateExternalTable,
+        CreateFunction, CreateMemoryTable, CreateView, DropCatalogSchema, DropFunction,
+        DropTable, DropView, LogicalPlan, LogicalPlanBuilder, SetVariable, TableType,
+        UNNAMED_TABLE,
     },
     physical_expr::PhysicalExpr,
     physical_plan::ExecutionPlan,
@@ -55,8 +56,9 @@ use arrow_schema::Schema;
 use datafusion_common::{
     config::{ConfigExtension, TableOptions},
     exec_err, not_impl_err, plan_datafusion_err, plan_err,
+    stats::Precision,
     tree_node::{TreeNodeRecursion, TreeNodeVisitor},
-    DFSchema, SchemaReference, TableReference,
+    DFSchema, SchemaReference, Statistics, TableReference,
 };
 use datafusion_execution::registry::SerializerRegistry;
 use datafusion_expr::{
@@ -71,6 +73,7 @@ pub use crate::execution::session_state::SessionState;
 
 use crate::datasource::dynamic_file::DynamicListTableFactory;
 use crate::execution::session_state::SessionStateBuilder;
+use analyze::ColumnAnalyzer;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use datafusion_catalog::{DynamicFileCatalog, SessionStore, UrlTableFactory};
@@ -78,10 +81,12 @@ pub use datafusion_execution::config::SessionConfig;
 pub use datafusion_execution::TaskContext;
 pub use datafusion_expr::execution_props::ExecutionProps;
 use datafusion_optimizer::{AnalyzerRule, OptimizerRule};
+use futures::StreamExt;
 use object_store::ObjectStore;
 use parking_lot::RwLock;
 use url::Url;
 
+mod analyze;
 mod avro;
 mod csv;
 mod json;
@@ -680,6 +685,9 @@ impl SessionContext {
                     DdlStatement::DropFunction(cmd) => {
                         Box::pin(self.drop_function(cmd)).await
                     }
+                    DdlStatement::AnalyzeTable(cmd) => {
+                        Box::pin(self.analyze_table(cmd)).await
+                    }
                     ddl => Ok(DataFrame::new(self.state(), LogicalPlan::Ddl(ddl))),
                 }
             }
@@ -1088,6 +1096,59 @@ impl SessionContext {
         }
     }
 
+    /// Scans the analyzed columns of the table to compute their statistics with
+    /// a [`ColumnAnalyzer`] each, and stores them with
+    /// [`TableProvider::set_statistics`]
+    async fn analyze_table(&self, cmd: AnalyzeTable) -> Result<DataFrame> {
+        let AnalyzeTable { name, columns, .. } = cmd;
+        let provider = self.table_provider(name.clone()).await?;
+        let schema = provider.schema();
+        let indices = match &columns {
+            Some(columns) => columns
+                .iter()
+                .map(|column| schema.index_of(column))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => (0..schema.fields().len()).collect(),
+        };
+
+        let plan = LogicalPlanBuilder::scan(
+            name,
+            provider_as_source(Arc::clone(&provider)),
+            Some(indices.clone()),
+        )?
+        .build()?;
+        let options = self.copied_config();
+        let execution = &options.options().execution;
+        let mut analyzers = indices
+            .iter()
+            .map(|index| {
+                ColumnAnalyzer::try_new(schema.field(*index).data_type(), execution)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut num_rows = 0;
+        let mut stream = DataFrame::new(self.state(), plan).execute_stream().await?;
+        while let Some(batch) = stream.next().await.transpose()? {
+            num_rows += batch.num_rows();
+            for (analyzer, array) in analyzers.iter_mut().zip(batch.columns()) {
+                analyzer.update(array)?;
+            }
+        }
+
+        // Keep the statistics of the columns which are not analyzed
+        let mut statistics = provider
+            .statistics()
+            .filter(|statistics| {
+                statistics.column_statistics.len() == schema.fields().len()
+            })
+            .unwrap_or_else(|| Statistics::new_unknown(&schema));
+        statistics.num_rows = Precision::Exact(num_rows);
+        for (analyzer, index) in analyzers.into_iter().zip(indices) {
+            statistics.column_statistics[index] = analyzer.finish()?;
+        }
+        provider.set_statistics(statistics)?;
+        self.return_empty_dataframe()
+    }
+
     /// Registers a variable provider within this context.
     pub fn register_variable(
         &self,
@@ -1287,9 +1348,25 @@ impl SessionContext {
         sql_definition: Option<String>,
     ) -> Result<()> {
         let table_path = ListingTableUrl::parse(table_path)?;
+        let mut options = options;
         let resolved_schema = match provided_schema {
             Some(s) => s,
-            None => options.infer_schema(&self.state(), &table_path).await?,
+            None => {
+                let state = self.state();
+                let schema = options.infer_schema(&state, &table_path).await?;
+                if options.file_sort_order.is_empty() {
+                    options.file_sort_order.extend(
+                        options
+                            .infer_file_sort_order(
+                                &state,
+                                &table_path,
+                                Arc::clone(&schema),
+                            )
+                            .await?,
+                    );
+                }
+                schema
+            }
         };
         let config = ListingTableConfig::new(table_path)
             .with_listing_options(options)
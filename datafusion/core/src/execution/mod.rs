@@ -18,6 +18,8 @@
 //! Shared state for query planning and execution.
 
 pub mod context;
+pub mod result_cache;
+pub mod semi_join_reduction;
 pub mod session_state;
 pub use session_state::{SessionState, SessionStateBuilder};
 
@@ -0,0 +1,778 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ResultCache`]: caches the results of queries between executions
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::datasource::source_as_provider;
+use crate::execution::context::SessionState;
+use crate::physical_plan::common::compute_record_batch_statistics;
+use crate::physical_plan::memory::MemoryStream;
+use crate::physical_plan::metrics::{
+    BaselineMetrics, Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+};
+use crate::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties, PlanProperties,
+    RecordBatchStream, SendableRecordBatchStream, Statistics,
+};
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::tree_node::TreeNodeRecursion;
+use datafusion_common::{internal_err, ResolvedTableReference, Result};
+use datafusion_execution::TaskContext;
+use datafusion_expr::LogicalPlan;
+use datafusion_optimizer::plan_signature::LogicalPlanSignature;
+
+use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
+
+/// Identifies the results of a query in a [`ResultCache`]
+#[derive(Debug, Clone)]
+pub(crate) struct ResultCacheKey {
+    signature: LogicalPlanSignature,
+    /// The optimized plan, compared on lookup to rule out signature collisions
+    plan: LogicalPlan,
+    /// The versions of the tables scanned by the plan
+    table_versions: Vec<(ResolvedTableReference, u64)>,
+}
+
+impl ResultCacheKey {
+    /// Returns the key for the results of the optimized `plan`, or `None` if
+    /// its results can not be cached.
+    ///
+    /// Only read-only plans without volatile expressions, which scan at least
+    /// one table and only tables reporting a [`TableProvider::version`], are
+    /// cached.
+    ///
+    /// [`TableProvider::version`]: crate::datasource::TableProvider::version
+    pub(crate) async fn try_new(
+        plan: &LogicalPlan,
+        state: &SessionState,
+    ) -> Result<Option<Self>> {
+        let mut scans = vec![];
+        let mut cacheable = true;
+        plan.apply_with_subqueries(|node| {
+            cacheable = match node {
+                LogicalPlan::TableScan(scan) => match source_as_provider(&scan.source) {
+                    Ok(provider) => {
+                        let table = state.resolve_table_ref(scan.table_name.clone());
+                        scans.push((table, provider));
+                        true
+                    }
+                    Err(_) => false,
+                },
+                LogicalPlan::Projection(_)
+                | LogicalPlan::Filter(_)
+                | LogicalPlan::Window(_)
+                | LogicalPlan::Aggregate(_)
+                | LogicalPlan::Sort(_)
+                | LogicalPlan::Join(_)
+                | LogicalPlan::Repartition(_)
+                | LogicalPlan::Union(_)
+                | LogicalPlan::EmptyRelation(_)
+                | LogicalPlan::Subquery(_)
+                | LogicalPlan::SubqueryAlias(_)
+                | LogicalPlan::Limit(_)
+                | LogicalPlan::Values(_)
+                | LogicalPlan::Distinct(_)
+                | LogicalPlan::Unnest(_)
+                | LogicalPlan::RecursiveQuery(_) => true,
+                // statements, DML and extension nodes may have side effects
+                _ => false,
+            };
+            if cacheable {
+                node.apply_expressions(|expr| {
+                    cacheable = !expr.is_volatile()?;
+                    Ok(if cacheable {
+                        TreeNodeRecursion::Continue
+                    } else {
+                        TreeNodeRecursion::Stop
+                    })
+                })?;
+            }
+            Ok(if cacheable {
+                TreeNodeRecursion::Continue
+            } else {
+                TreeNodeRecursion::Stop
+            })
+        })?;
+
+        if !cacheable || scans.is_empty() {
+            return Ok(None);
+        }
+
+        let mut table_versions = Vec::with_capacity(scans.len());
+        for (table, provider) in scans {
+            match provider.version(state).await? {
+                Some(version) => table_versions.push((table, version)),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(Self {
+            signature: LogicalPlanSignature::new(plan),
+            plan: plan.clone(),
+            table_versions,
+        }))
+    }
+}
+
+/// A cache of query results, shared by all queries of a [`SessionState`].
+///
+/// Results are keyed by the optimized [`LogicalPlan`] of the query and the
+/// versions of the tables it scans, so a result is only returned again while
+/// none of these tables changed. Results of queries scanning a table are
+/// also removed when data is inserted into the table.
+///
+/// The cache is enabled by setting
+/// `datafusion.execution.result_cache_max_bytes`, which bounds the total
+/// size of the cached results. The least recently used results are evicted
+/// to make room for new ones.
+#[derive(Debug, Default)]
+pub struct ResultCache {
+    inner: Mutex<ResultCacheInner>,
+}
+
+#[derive(Debug, Default)]
+struct ResultCacheInner {
+    entries: HashMap<LogicalPlanSignature, CachedResult>,
+    memory_size: usize,
+    /// Incremented on every access, to find the least recently used entry
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct CachedResult {
+    key: ResultCacheKey,
+    /// Properties of the plan which produced the results
+    properties: PlanProperties,
+    partitions: Arc<Vec<Vec<RecordBatch>>>,
+    memory_size: usize,
+    last_used: u64,
+}
+
+impl ResultCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of cached query results
+    pub fn len(&self) -> usize {
+        self.inner.lock().entries.len()
+    }
+
+    /// Returns true if no query results are cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total size, in bytes, of the cached query results
+    pub fn memory_size(&self) -> usize {
+        self.inner.lock().memory_size
+    }
+
+    /// Removes all cached query results
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock();
+        inner.entries.clear();
+        inner.memory_size = 0;
+    }
+
+    /// Removes the cached results of all queries scanning `table`
+    pub fn invalidate_table(&self, table: &ResolvedTableReference) {
+        let mut inner = self.inner.lock();
+        let mut removed_size = 0;
+        inner.entries.retain(|_, entry| {
+            let scans_table = entry.key.table_versions.iter().any(|(t, _)| t == table);
+            if scans_table {
+                removed_size += entry.memory_size;
+            }
+            !scans_table
+        });
+        inner.memory_size -= removed_size;
+    }
+
+    /// Returns a plan producing the cached results for `key`, if any. Stale
+    /// results of the same plan are removed.
+    pub(crate) fn get(&self, key: &ResultCacheKey) -> Option<Arc<dyn ExecutionPlan>> {
+        let mut inner = self.inner.lock();
+        inner.clock += 1;
+        let clock = inner.clock;
+        let entry = inner.entries.get_mut(&key.signature)?;
+        if entry.key.plan != key.plan {
+            return None;
+        }
+        if entry.key.table_versions != key.table_versions {
+            let memory_size = entry.memory_size;
+            inner.entries.remove(&key.signature);
+            inner.memory_size -= memory_size;
+            return None;
+        }
+        entry.last_used = clock;
+        Some(Arc::new(ResultCacheExec {
+            mode: CacheMode::Hit(Arc::clone(&entry.partitions)),
+            properties: entry.properties.clone(),
+            metrics: ExecutionPlanMetricsSet::new(),
+        }))
+    }
+
+    /// Stores the results of the plan with `properties` for `key`, evicting
+    /// the least recently used results until the cache fits in `max_bytes`
+    fn insert(
+        &self,
+        key: ResultCacheKey,
+        properties: PlanProperties,
+        partitions: Vec<Vec<RecordBatch>>,
+        memory_size: usize,
+        max_bytes: usize,
+    ) {
+        if memory_size > max_bytes {
+            return;
+        }
+        let mut inner = self.inner.lock();
+        if let Some(previous) = inner.entries.remove(&key.signature) {
+            inner.memory_size -= previous.memory_size;
+        }
+        while inner.memory_size + memory_size > max_bytes {
+            let Some(lru) = inner
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(signature, _)| *signature)
+            else {
+                break;
+            };
+            if let Some(evicted) = inner.entries.remove(&lru) {
+                inner.memory_size -= evicted.memory_size;
+            }
+        }
+
+        inner.clock += 1;
+        inner.memory_size += memory_size;
+        let entry = CachedResult {
+            last_used: inner.clock,
+            key,
+            properties,
+            partitions: Arc::new(partitions),
+            memory_size,
+        };
+        inner.entries.insert(entry.key.signature, entry);
+    }
+}
+
+/// Collects the results of all partitions of a [`ResultCacheExec`] and
+/// stores them in the cache once every partition completed
+#[derive(Debug)]
+struct ResultRecorder {
+    cache: Arc<ResultCache>,
+    properties: PlanProperties,
+    max_bytes: usize,
+    state: Mutex<RecorderState>,
+}
+
+#[derive(Debug)]
+struct RecorderState {
+    /// `None` once the results were stored, or can not be cached
+    key: Option<ResultCacheKey>,
+    partitions: Vec<Option<Vec<RecordBatch>>>,
+    memory_size: usize,
+}
+
+impl ResultRecorder {
+    /// Accounts for a batch of `size` bytes, returning false if the results
+    /// are not recorded or no longer fit in the cache
+    fn try_reserve(&self, size: usize) -> bool {
+        let mut state = self.state.lock();
+        state.memory_size += size;
+        if state.memory_size > self.max_bytes {
+            state.key = None;
+        }
+        state.key.is_some()
+    }
+
+    /// Stops recording, for example because a partition failed
+    fn abandon(&self) {
+        self.state.lock().key = None;
+    }
+
+    /// Records the complete results of `partition`
+    fn finish_partition(&self, partition: usize, batches: Vec<RecordBatch>) {
+        let mut state = self.state.lock();
+        if state.key.is_none() {
+            return;
+        }
+        // a partition executed more than once can not be recorded reliably
+        if state.partitions[partition].replace(batches).is_some() {
+            state.key = None;
+            return;
+        }
+        if state.partitions.iter().all(Option::is_some) {
+            let key = state.key.take().unwrap();
+            let partitions = std::mem::take(&mut state.partitions)
+                .into_iter()
+                .flatten()
+                .collect();
+            self.cache.insert(
+                key,
+                self.properties.clone(),
+                partitions,
+                state.memory_size,
+                self.max_bytes,
+            );
+        }
+    }
+}
+
+#[derive(Debug)]
+enum CacheMode {
+    /// The results were found in the cache
+    Hit(Arc<Vec<Vec<RecordBatch>>>),
+    /// The results of the input are recorded into the cache
+    Miss {
+        input: Arc<dyn ExecutionPlan>,
+        recorder: Arc<ResultRecorder>,
+    },
+}
+
+/// Returns the results of a query from a [`ResultCache`], or records the
+/// results of its input into the cache
+#[derive(Debug)]
+pub struct ResultCacheExec {
+    mode: CacheMode,
+    properties: PlanProperties,
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl ResultCacheExec {
+    /// Create a plan recording the results of `input` in `cache` for `key`,
+    /// if they fit in `max_bytes`
+    pub(crate) fn new_miss(
+        input: Arc<dyn ExecutionPlan>,
+        cache: Arc<ResultCache>,
+        key: ResultCacheKey,
+        max_bytes: usize,
+    ) -> Self {
+        let properties = input.properties().clone();
+        let recorder = ResultRecorder {
+            cache,
+            properties: properties.clone(),
+            max_bytes,
+            state: Mutex::new(RecorderState {
+                key: Some(key),
+                partitions: vec![None; input.output_partitioning().partition_count()],
+                memory_size: 0,
+            }),
+        };
+        Self {
+            mode: CacheMode::Miss {
+                input,
+                recorder: Arc::new(recorder),
+            },
+            properties,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+
+    /// Returns true if the results are read from the cache
+    pub fn is_hit(&self) -> bool {
+        matches!(self.mode, CacheMode::Hit(_))
+    }
+}
+
+impl DisplayAs for ResultCacheExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let status = if self.is_hit() { "hit" } else { "miss" };
+                write!(f, "ResultCacheExec: status={status}")
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for ResultCacheExec {
+    fn name(&self) -> &'static str {
+        "ResultCacheExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.properties
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        match &self.mode {
+            CacheMode::Hit(_) => vec![],
+            CacheMode::Miss { input, .. } => vec![input],
+        }
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match &self.mode {
+            CacheMode::Hit(_) => Ok(self),
+            // the recorded results would not match the new input
+            CacheMode::Miss { .. } => Ok(children.swap_remove(0)),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let stream = match &self.mode {
+            CacheMode::Hit(partitions) => {
+                let Some(batches) = partitions.get(partition) else {
+                    return internal_err!(
+                        "Invalid partition {partition} for ResultCacheExec"
+                    );
+                };
+                ResultCacheStream {
+                    input: Box::pin(MemoryStream::try_new(
+                        batches.clone(),
+                        self.schema(),
+                        None,
+                    )?),
+                    recording: None,
+                    metrics: StreamMetrics::Hit(
+                        MetricBuilder::new(&self.metrics).output_rows(partition),
+                    ),
+                }
+            }
+            CacheMode::Miss { input, recorder } => ResultCacheStream {
+                input: input.execute(partition, context)?,
+                recording: Some(Recording {
+                    recorder: Arc::clone(recorder),
+                    partition,
+                    batches: vec![],
+                }),
+                metrics: StreamMetrics::Miss(BaselineMetrics::new(
+                    &self.metrics,
+                    partition,
+                )),
+            },
+        };
+        Ok(Box::pin(stream))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        match &self.mode {
+            CacheMode::Hit(partitions) => Ok(compute_record_batch_statistics(
+                partitions,
+                &self.schema(),
+                None,
+            )),
+            CacheMode::Miss { input, .. } => input.statistics(),
+        }
+    }
+}
+
+/// The state of a partition of a [`ResultCacheExec`] recording its results
+struct Recording {
+    recorder: Arc<ResultRecorder>,
+    partition: usize,
+    batches: Vec<RecordBatch>,
+}
+
+/// The metrics of a [`ResultCacheStream`]
+enum StreamMetrics {
+    /// Returning cached batches does no work, only the output rows are counted
+    Hit(Count),
+    Miss(BaselineMetrics),
+}
+
+/// Stream of a [`ResultCacheExec`] partition, recording the batches passing
+/// through it in case of a cache miss
+struct ResultCacheStream {
+    input: SendableRecordBatchStream,
+    /// `None` if the batches are not, or no longer, recorded
+    recording: Option<Recording>,
+    metrics: StreamMetrics,
+}
+
+impl Stream for ResultCacheStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let poll = this.input.poll_next_unpin(cx);
+        match &poll {
+            Poll::Ready(Some(Ok(batch))) => {
+                if let Some(recording) = &mut this.recording {
+                    if recording
+                        .recorder
+                        .try_reserve(batch.get_array_memory_size())
+                    {
+                        recording.batches.push(batch.clone());
+                    } else {
+                        this.recording = None;
+                    }
+                }
+            }
+            Poll::Ready(Some(Err(_))) => {
+                if let Some(recording) = this.recording.take() {
+                    recording.recorder.abandon();
+                }
+            }
+            Poll::Ready(None) => {
+                if let Some(recording) = this.recording.take() {
+                    recording
+                        .recorder
+                        .finish_partition(recording.partition, recording.batches);
+                }
+            }
+            Poll::Pending => {}
+        }
+        match &this.metrics {
+            StreamMetrics::Hit(output_rows) => {
+                if let Poll::Ready(Some(Ok(batch))) = &poll {
+                    output_rows.add(batch.num_rows());
+                }
+                poll
+            }
+            StreamMetrics::Miss(baseline_metrics) => baseline_metrics.record_poll(poll),
+        }
+    }
+}
+
+impl RecordBatchStream for ResultCacheStream {
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use crate::physical_plan::{collect, displayable};
+    use crate::prelude::{SessionConfig, SessionContext};
+
+    async fn context(max_bytes: usize) -> Result<SessionContext> {
+        let config = SessionConfig::new()
+            .with_information_schema(true)
+            .set_usize("datafusion.execution.result_cache_max_bytes", max_bytes);
+        let ctx = SessionContext::new_with_config(config);
+        ctx.sql("CREATE TABLE t AS VALUES (1, 'a'), (2, 'b'), (3, 'c')")
+            .await?
+            .collect()
+            .await?;
+        Ok(ctx)
+    }
+
+    /// Plans and runs `sql`, returning the display of the plan and the results
+    async fn run(ctx: &SessionContext, sql: &str) -> Result<(String, Vec<RecordBatch>)> {
+        let plan = ctx.sql(sql).await?.create_physical_plan().await?;
+        let display = displayable(plan.as_ref()).indent(false).to_string();
+        let batches = collect(plan, ctx.task_ctx()).await?;
+        Ok((display, batches))
+    }
+
+    fn status(display: &str) -> Option<&str> {
+        let line = display.lines().next()?;
+        line.strip_prefix("ResultCacheExec: status=")
+    }
+
+    #[tokio::test]
+    async fn cached_results() -> Result<()> {
+        let ctx = context(1024 * 1024).await?;
+        let sql = "SELECT column2 FROM t WHERE column1 > 1 ORDER BY column1";
+        let expected = [
+            "+---------+",
+            "| column2 |",
+            "+---------+",
+            "| b       |",
+            "| c       |",
+            "+---------+",
+        ];
+
+        let (display, batches) = run(&ctx, sql).await?;
+        assert_eq!(status(&display), Some("miss"));
+        assert_batches_eq!(expected, &batches);
+        let cache = Arc::clone(ctx.state().result_cache());
+        assert_eq!(cache.len(), 1);
+        assert!(cache.memory_size() > 0);
+
+        let (display, batches) = run(&ctx, sql).await?;
+        assert_eq!(display, "ResultCacheExec: status=hit\n");
+        assert_batches_eq!(expected, &batches);
+
+        // queries with the same optimized plan share the results
+        let sql = "SELECT column2 FROM t WHERE 1 < column1 ORDER BY column1";
+        let (display, _) = run(&ctx, sql).await?;
+        assert_eq!(status(&display), Some("hit"));
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.memory_size(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn explain_analyze_shows_status() -> Result<()> {
+        let ctx = context(1024 * 1024).await?;
+        let sql = "EXPLAIN ANALYZE SELECT count(*) FROM t";
+        for expected in [
+            "ResultCacheExec: status=miss",
+            "ResultCacheExec: status=hit",
+        ] {
+            let batches = ctx.sql(sql).await?.collect().await?;
+            let formatted = arrow::util::pretty::pretty_format_batches(&batches)?;
+            assert!(formatted.to_string().contains(expected), "{formatted}");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_invalidates_results() -> Result<()> {
+        let ctx = context(1024 * 1024).await?;
+        let sql = "SELECT count(*) FROM t";
+        run(&ctx, sql).await?;
+        assert_eq!(status(&run(&ctx, sql).await?.0), Some("hit"));
+
+        ctx.sql("INSERT INTO t VALUES (4, 'd')")
+            .await?
+            .collect()
+            .await?;
+        assert!(ctx.state().result_cache().is_empty());
+
+        let (display, batches) = run(&ctx, sql).await?;
+        assert_eq!(status(&display), Some("miss"));
+        let expected = [
+            "+----------+",
+            "| count(*) |",
+            "+----------+",
+            "| 4        |",
+            "+----------+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stale_results_are_not_returned() -> Result<()> {
+        let ctx = context(1024 * 1024).await?;
+        let sql = "SELECT count(*) FROM t";
+        run(&ctx, sql).await?;
+
+        // the table changes without the cache being told about it
+        let state = ctx.state();
+        let table = ctx.table_provider("t").await?;
+        let input = ctx
+            .sql("VALUES (4, 'd')")
+            .await?
+            .create_physical_plan()
+            .await?;
+        let insert = table
+            .insert_into(&state, input, datafusion_expr::dml::InsertOp::Append)
+            .await?;
+        collect(insert, ctx.task_ctx()).await?;
+
+        let (display, batches) = run(&ctx, sql).await?;
+        assert_eq!(status(&display), Some("miss"));
+        assert_eq!(batches[0].num_rows(), 1);
+        let expected = [
+            "+----------+",
+            "| count(*) |",
+            "+----------+",
+            "| 4        |",
+            "+----------+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn uncacheable_queries() -> Result<()> {
+        let ctx = context(1024 * 1024).await?;
+        for sql in [
+            // volatile functions
+            "SELECT column1, random() FROM t",
+            // no tables
+            "SELECT 1",
+            // tables without versions
+            "SELECT * FROM information_schema.tables",
+        ] {
+            let (display, _) = run(&ctx, sql).await?;
+            assert_eq!(status(&display), None, "{sql}");
+        }
+        assert!(ctx.state().result_cache().is_empty());
+
+        // the cache is disabled by default
+        let ctx = context(0).await?;
+        let (display, _) = run(&ctx, "SELECT * FROM t").await?;
+        assert_eq!(status(&display), None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn memory_budget() -> Result<()> {
+        let queries = [
+            "SELECT column1 FROM t",
+            "SELECT column1 + 1 FROM t",
+            "SELECT column2 FROM t",
+        ];
+        let ctx = context(1024 * 1024).await?;
+        let mut sizes = vec![];
+        for sql in queries {
+            let before = ctx.state().result_cache().memory_size();
+            run(&ctx, sql).await?;
+            sizes.push(ctx.state().result_cache().memory_size() - before);
+        }
+
+        // results larger than the budget are not cached
+        let ctx = context(sizes[0] - 1).await?;
+        run(&ctx, queries[0]).await?;
+        assert!(ctx.state().result_cache().is_empty());
+
+        // the least recently used results are evicted
+        let max_bytes = sizes[0] + sizes[1].max(sizes[2]);
+        let ctx = context(max_bytes).await?;
+        let cache = Arc::clone(ctx.state().result_cache());
+        run(&ctx, queries[0]).await?;
+        run(&ctx, queries[1]).await?;
+        assert_eq!(cache.len(), 2);
+        // use the first results, so the second ones are evicted next
+        let (display, _) = run(&ctx, queries[0]).await?;
+        assert_eq!(status(&display), Some("hit"));
+        run(&ctx, queries[2]).await?;
+        assert_eq!(cache.len(), 2);
+        assert!(cache.memory_size() <= max_bytes);
+        let (display, _) = run(&ctx, queries[0]).await?;
+        assert_eq!(status(&display), Some("hit"));
+        let (display, _) = run(&ctx, queries[1]).await?;
+        assert_eq!(status(&display), Some("miss"));
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Plans the semi-join reductions of table scans, see [`SemiJoinReduction`]
+//!
+//! [`SemiJoinReduction`]: datafusion_optimizer::semi_join_reduction::SemiJoinReduction
+
+use std::sync::Arc;
+
+use crate::execution::context::SessionState;
+use crate::physical_planner::DefaultPhysicalPlanner;
+
+use datafusion_common::Result;
+use datafusion_expr::expr::InSubquery;
+use datafusion_expr::Expr;
+use datafusion_physical_plan::semi_join_reduction::{
+    semi_join_reduction_filter, SemiJoinKeys, SemiJoinReduction,
+};
+
+/// Replaces the semi-join reductions among the `filters` of a table scan,
+/// uncorrelated `IN` subqueries, with a filter by the keys of the subquery,
+/// see [`semi_join_reduction_filter`].
+///
+/// Only the subqueries are planned here, the returned reductions are executed
+/// by a [`SemiJoinReductionExec`] above the scan, which computes their keys
+/// before the scan is executed.
+///
+/// [`SemiJoinReductionExec`]: datafusion_physical_plan::semi_join_reduction::SemiJoinReductionExec
+pub(crate) async fn plan_semi_join_reductions(
+    filters: Vec<Expr>,
+    planner: &DefaultPhysicalPlanner,
+    session_state: &SessionState,
+) -> Result<(Vec<Expr>, Vec<SemiJoinReduction>)> {
+    let mut planned = Vec::with_capacity(filters.len());
+    let mut reductions = vec![];
+    for filter in filters {
+        match filter {
+            Expr::InSubquery(InSubquery {
+                expr,
+                subquery,
+                negated: false,
+            }) if subquery.outer_ref_columns.is_empty() => {
+                // the subquery is optimized with the plan of the scan
+                let keys_plan = Box::pin(
+                    planner.create_initial_plan(&subquery.subquery, session_state),
+                )
+                .await?;
+                let keys = Arc::new(SemiJoinKeys::new(expr.to_string()));
+                planned.push(semi_join_reduction_filter(Arc::clone(&keys), *expr));
+                reductions.push(SemiJoinReduction { keys_plan, keys });
+            }
+            filter => planned.push(filter),
+        }
+    }
+    Ok((planned, reductions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::physical_plan::ParquetExec;
+    use crate::execution::context::SessionContext;
+    use crate::physical_plan::{collect, displayable, ExecutionPlan};
+    use crate::prelude::SessionConfig;
+    use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
+
+    /// Creates the parquet tables `fact`, of 10000 rows in row groups of 1000
+    /// rows, and `dim`, of 100 rows with two keys of category 'a', with their
+    /// statistics
+    async fn context(config: SessionConfig) -> Result<SessionContext> {
+        let config =
+            config.set_bool("datafusion.optimizer.enable_semi_join_reduction", true);
+        let ctx = SessionContext::new_with_config(config);
+        let dir = tempfile::tempdir()?;
+        let dir = dir.into_path();
+        for sql in [
+            format!(
+                "COPY (SELECT v / 100 AS k, v AS amount FROM (SELECT unnest(range(0, 10000)) AS v)) \
+                TO '{}/fact.parquet' STORED AS PARQUET OPTIONS ('format.max_row_group_size' 1000)",
+                dir.display()
+            ),
+            format!(
+                "COPY (SELECT v AS k, CASE WHEN v IN (3, 42) THEN 'a' ELSE 'b' END AS cat \
+                FROM (SELECT unnest(range(0, 100)) AS v)) \
+                TO '{}/dim.parquet' STORED AS PARQUET",
+                dir.display()
+            ),
+            format!(
+                "CREATE EXTERNAL TABLE fact STORED AS PARQUET LOCATION '{}/fact.parquet'",
+                dir.display()
+            ),
+            format!(
+                "CREATE EXTERNAL TABLE dim STORED AS PARQUET LOCATION '{}/dim.parquet'",
+                dir.display()
+            ),
+            "ANALYZE TABLE fact".to_string(),
+            "ANALYZE TABLE dim".to_string(),
+        ] {
+            ctx.sql(&sql).await?.collect().await?;
+        }
+        Ok(ctx)
+    }
+
+    /// The physical plan of a join of `fact` with the keys of category 'a'
+    async fn plan(ctx: &SessionContext) -> Result<Arc<dyn ExecutionPlan>> {
+        ctx.sql("SELECT f.amount FROM fact f JOIN dim d ON f.k = d.k WHERE d.cat = 'a'")
+            .await?
+            .create_physical_plan()
+            .await
+    }
+
+    /// Executes `plan`, returning its number of rows and the sum of the
+    /// `metric` of the scans of `fact`
+    async fn execute(
+        ctx: &SessionContext,
+        plan: &Arc<dyn ExecutionPlan>,
+        metric: &str,
+    ) -> Result<(usize, usize)> {
+        let batches = collect(Arc::clone(plan), ctx.task_ctx()).await?;
+        let num_rows = batches.iter().map(|batch| batch.num_rows()).sum();
+        let mut value = 0;
+        plan.apply(|plan| {
+            if let Some(scan) = plan.as_any().downcast_ref::<ParquetExec>() {
+                if scan.predicate().is_some() {
+                    let metrics = scan.metrics().unwrap();
+                    value += metrics.sum_by_name(metric).map_or(0, |v| v.as_usize());
+                }
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+        Ok((num_rows, value))
+    }
+
+    #[tokio::test]
+    async fn resolve_keys() -> Result<()> {
+        let ctx = context(SessionConfig::new()).await?;
+        let plan = plan(&ctx).await?;
+        // planning the query does not compute the keys
+        let display = displayable(plan.as_ref()).indent(false).to_string();
+        assert!(
+            display.contains("SemiJoinReductionExec: keys=[k]"),
+            "{display}"
+        );
+        assert!(
+            display.contains("predicate=semi_join_reduction(k@0)"),
+            "{display}"
+        );
+
+        // only the row groups 0 and 4 contain the keys 3 and 42
+        let metric = "row_groups_pruned_statistics";
+        assert_eq!(execute(&ctx, &plan, metric).await?, (200, 8));
+        // the keys are computed again by each execution
+        assert_eq!(execute(&ctx, &plan, metric).await?, (200, 16));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn bloom_filter_keys() -> Result<()> {
+        let config = SessionConfig::new().set_usize(
+            "datafusion.optimizer.semi_join_reduction_max_in_list_size",
+            1,
+        );
+        let ctx = context(config).await?;
+        let plan = plan(&ctx).await?;
+        // the bloom filter does not prune row groups using their statistics
+        let metric = "row_groups_pruned_statistics";
+        assert_eq!(execute(&ctx, &plan, metric).await?, (200, 0));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn too_many_keys() -> Result<()> {
+        let config = SessionConfig::new()
+            .set_usize("datafusion.optimizer.semi_join_reduction_max_keys", 1);
+        let ctx = context(config).await?;
+        let plan = plan(&ctx).await?;
+        let metric = "row_groups_pruned_statistics";
+        assert_eq!(execute(&ctx, &plan, metric).await?, (200, 0));
+        Ok(())
+    }
+}
@@ -25,8 +25,10 @@ use crate::catalog_common::MemoryCatalogProviderList;
 use crate::datasource::cte_worktable::CteWorkTable;
 use crate::datasource::file_format::{format_as_file_type, FileFormatFactory};
 use crate::datasource::function::{TableFunction, TableFunctionImpl};
+use crate::datasource::materialized_cte::MaterializedCte;
 use crate::datasource::provider_as_source;
 use crate::execution::context::{EmptySerializerRegistry, FunctionFactory, QueryPlanner};
+use crate::execution::result_cache::ResultCache;
 use crate::execution::SessionStateDefaults;
 use crate::physical_optimizer::optimizer::PhysicalOptimizer;
 use crate::physical_planner::{DefaultPhysicalPlanner, PhysicalPlanner};
@@ -36,7 +38,10 @@ use chrono::{DateTime, Utc};
 use datafusion_catalog::Session;
 use datafusion_common::alias::AliasGenerator;
 use datafusion_common::config::{ConfigExtension, ConfigOptions, TableOptions};
-use datafusion_common::display::{PlanType, StringifiedPlan, ToStringifiedPlan};
+use datafusion_common::display::{
+    ExplainFormat, OptimizerTraceEvent, OptimizerTraceObserver, PlanType,
+    StringifiedPlan, ToStringifiedPlan,
+};
 use datafusion_common::file_options::file_type::FileType;
 use datafusion_common::tree_node::TreeNode;
 use datafusion_common::{
@@ -171,6 +176,12 @@ pub struct SessionState {
     /// It will be invoked on `CREATE FUNCTION` statements.
     /// thus, changing dialect o PostgreSql is required
     function_factory: Option<Arc<dyn FunctionFactory>>,
+    /// Callback receiving an [`OptimizerTraceEvent`] for each applied
+    /// logical and physical optimizer rule
+    optimizer_trace_observer: Option<OptimizerTraceObserver>,
+    /// Cache of query results, enabled by
+    /// `datafusion.execution.result_cache_max_bytes`
+    result_cache: Arc<ResultCache>,
 }
 
 impl Debug for SessionState {
@@ -188,6 +199,11 @@ impl Debug for SessionState {
             .field("table_options", &self.table_options)
             .field("table_factories", &self.table_factories)
             .field("function_factory", &self.function_factory)
+            .field(
+                "optimizer_trace_observer",
+                &self.optimizer_trace_observer.is_some(),
+            )
+            .field("result_cache", &self.result_cache)
             .field("expr_planners", &self.expr_planners)
             .field("query_planners", &self.query_planner)
             .field("analyzer", &self.analyzer)
@@ -444,6 +460,22 @@ impl SessionState {
         self.function_factory.as_ref()
     }
 
+    /// Registers an [`OptimizerTraceObserver`] which is invoked after each
+    /// logical and physical optimizer rule is applied
+    pub fn set_optimizer_trace_observer(&mut self, observer: OptimizerTraceObserver) {
+        self.optimizer_trace_observer = Some(observer);
+    }
+
+    /// Get the optimizer trace observer
+    pub fn optimizer_trace_observer(&self) -> Option<&OptimizerTraceObserver> {
+        self.optimizer_trace_observer.as_ref()
+    }
+
+    /// Get the query result cache
+    pub fn result_cache(&self) -> &Arc<ResultCache> {
+        &self.result_cache
+    }
+
     /// Get the table factories
     pub fn table_factories(&self) -> &HashMap<String, Arc<dyn TableProviderFactory>> {
         &self.table_factories
@@ -624,15 +656,20 @@ impl SessionState {
     pub fn optimize(&self, plan: &LogicalPlan) -> datafusion_common::Result<LogicalPlan> {
         if let LogicalPlan::Explain(e) = plan {
             let mut stringified_plans = e.stringified_plans.clone();
+            // when tracing, rule traces replace the intermediate plans and
+            // the analyzer plans are only shown in verbose mode
+            let trace = e.explain_format == ExplainFormat::Trace;
 
             // analyze & capture output of each rule
             let analyzer_result = self.analyzer.execute_and_check(
                 e.plan.as_ref().clone(),
                 self.options(),
                 |analyzed_plan, analyzer| {
-                    let analyzer_name = analyzer.name().to_string();
-                    let plan_type = PlanType::AnalyzedLogicalPlan { analyzer_name };
-                    stringified_plans.push(analyzed_plan.to_stringified(plan_type));
+                    if !trace || e.verbose {
+                        let analyzer_name = analyzer.name().to_string();
+                        let plan_type = PlanType::AnalyzedLogicalPlan { analyzer_name };
+                        stringified_plans.push(analyzed_plan.to_stringified(plan_type));
+                    }
                 },
             );
             let analyzed_plan = match analyzer_result {
@@ -644,6 +681,7 @@ impl SessionState {
 
                     return Ok(LogicalPlan::Explain(Explain {
                         verbose: e.verbose,
+                        explain_format: e.explain_format,
                         plan: e.plan.clone(),
                         stringified_plans,
                         schema: e.schema.clone(),
@@ -658,15 +696,22 @@ impl SessionState {
                 .push(analyzed_plan.to_stringified(PlanType::FinalAnalyzedLogicalPlan));
 
             // optimize the child plan, capturing the output of each optimizer
-            let optimized_plan = self.optimizer.optimize(
+            let mut traced_plans = vec![];
+            let mut tracer = |event: &OptimizerTraceEvent| {
+                traced_plans.push(event.to_stringified());
+            };
+            let optimized_plan = self.run_optimizer(
                 analyzed_plan,
-                self,
                 |optimized_plan, optimizer| {
-                    let optimizer_name = optimizer.name().to_string();
-                    let plan_type = PlanType::OptimizedLogicalPlan { optimizer_name };
-                    stringified_plans.push(optimized_plan.to_stringified(plan_type));
+                    if !trace {
+                        let optimizer_name = optimizer.name().to_string();
+                        let plan_type = PlanType::OptimizedLogicalPlan { optimizer_name };
+                        stringified_plans.push(optimized_plan.to_stringified(plan_type));
+                    }
                 },
+                trace.then_some(&mut tracer as _),
             );
+            stringified_plans.extend(traced_plans);
             let (plan, logical_optimization_succeeded) = match optimized_plan {
                 Ok(plan) => (Arc::new(plan), true),
                 Err(DataFusionError::Context(optimizer_name, err)) => {
@@ -680,6 +725,7 @@ impl SessionState {
 
             Ok(LogicalPlan::Explain(Explain {
                 verbose: e.verbose,
+                explain_format: e.explain_format,
                 plan,
                 stringified_plans,
                 schema: e.schema.clone(),
@@ -691,10 +737,36 @@ impl SessionState {
                 self.options(),
                 |_, _| {},
             )?;
-            self.optimizer.optimize(analyzed_plan, self, |_, _| {})
+            self.run_optimizer(analyzed_plan, |_, _| {}, None)
         }
     }
 
+    /// Runs the logical [`Optimizer`], reporting each applied rule to
+    /// `tracer` and the registered [`OptimizerTraceObserver`], if any
+    fn run_optimizer<F>(
+        &self,
+        plan: LogicalPlan,
+        observer: F,
+        mut tracer: Option<&mut dyn FnMut(&OptimizerTraceEvent)>,
+    ) -> datafusion_common::Result<LogicalPlan>
+    where
+        F: FnMut(&LogicalPlan, &dyn OptimizerRule),
+    {
+        let trace_observer = self.optimizer_trace_observer.as_ref();
+        if tracer.is_none() && trace_observer.is_none() {
+            return self.optimizer.optimize(plan, self, observer);
+        }
+        self.optimizer
+            .optimize_with_trace(plan, self, observer, |event| {
+                if let Some(tracer) = tracer.as_mut() {
+                    tracer(event);
+                }
+                if let Some(trace_observer) = trace_observer {
+                    trace_observer(event);
+                }
+            })
+    }
+
     /// Creates a physical [`ExecutionPlan`] plan from a [`LogicalPlan`].
     ///
     /// Note: this first calls [`Self::optimize`] on the provided
@@ -932,6 +1004,8 @@ pub struct SessionStateBuilder {
     table_factories: Option<HashMap<String, Arc<dyn TableProviderFactory>>>,
     runtime_env: Option<Arc<RuntimeEnv>>,
     function_factory: Option<Arc<dyn FunctionFactory>>,
+    optimizer_trace_observer: Option<OptimizerTraceObserver>,
+    result_cache: Option<Arc<ResultCache>>,
     // fields to support convenience functions
     analyzer_rules: Option<Vec<Arc<dyn AnalyzerRule + Send + Sync>>>,
     optimizer_rules: Option<Vec<Arc<dyn OptimizerRule + Send + Sync>>>,
@@ -961,6 +1035,8 @@ impl SessionStateBuilder {
             table_factories: None,
             runtime_env: None,
             function_factory: None,
+            optimizer_trace_observer: None,
+            result_cache: None,
             // fields to support convenience functions
             analyzer_rules: None,
             optimizer_rules: None,
@@ -1010,6 +1086,8 @@ impl SessionStateBuilder {
             table_factories: Some(existing.table_factories),
             runtime_env: Some(existing.runtime_env),
             function_factory: existing.function_factory,
+            optimizer_trace_observer: existing.optimizer_trace_observer,
+            result_cache: Some(existing.result_cache),
 
             // fields to support convenience functions
             analyzer_rules: None,
@@ -1234,6 +1312,23 @@ impl SessionStateBuilder {
         self
     }
 
+    /// Set an [`OptimizerTraceObserver`] which is invoked after each logical
+    /// and physical optimizer rule is applied
+    pub fn with_optimizer_trace_observer(
+        mut self,
+        observer: Option<OptimizerTraceObserver>,
+    ) -> Self {
+        self.optimizer_trace_observer = observer;
+        self
+    }
+
+    /// Set the [`ResultCache`] storing query results, for example to share
+    /// cached results between sessions
+    pub fn with_result_cache(mut self, result_cache: Arc<ResultCache>) -> Self {
+        self.result_cache = Some(result_cache);
+        self
+    }
+
     /// Register an `ObjectStore` to the [`RuntimeEnv`]. See [`RuntimeEnv::register_object_store`]
     /// for more details.
     ///
@@ -1295,6 +1390,8 @@ impl SessionStateBuilder {
             table_factories,
             runtime_env,
             function_factory,
+            optimizer_trace_observer,
+            result_cache,
             analyzer_rules,
             optimizer_rules,
             physical_optimizer_rules,
@@ -1327,6 +1424,8 @@ impl SessionStateBuilder {
             table_factories: table_factories.unwrap_or_default(),
             runtime_env,
             function_factory,
+            optimizer_trace_observer,
+            result_cache: result_cache.unwrap_or_default(),
         };
 
         if let Some(file_formats) = file_formats {
@@ -1500,6 +1599,16 @@ impl SessionStateBuilder {
         &mut self.function_factory
     }
 
+    /// Returns the current optimizer_trace_observer value
+    pub fn optimizer_trace_observer(&mut self) -> &mut Option<OptimizerTraceObserver> {
+        &mut self.optimizer_trace_observer
+    }
+
+    /// Returns the current result_cache value
+    pub fn result_cache(&mut self) -> &mut Option<Arc<ResultCache>> {
+        &mut self.result_cache
+    }
+
     /// Returns the current analyzer_rules value
     pub fn analyzer_rules(
         &mut self,
@@ -1537,6 +1646,11 @@ impl Debug for SessionStateBuilder {
             .field("table_options", &self.table_options)
             .field("table_factories", &self.table_factories)
             .field("function_factory", &self.function_factory)
+            .field(
+                "optimizer_trace_observer",
+                &self.optimizer_trace_observer.is_some(),
+            )
+            .field("result_cache", &self.result_cache)
             .field("expr_planners", &self.expr_planners)
             .field("query_planners", &self.query_planner)
             .field("analyzer_rules", &self.analyzer_rules)
@@ -1618,6 +1732,17 @@ impl<'a> ContextProvider for SessionContextProvider<'a> {
         Ok(provider_as_source(table))
     }
 
+    /// Create a table computing the results of a CTE once, to be shared by
+    /// the references to the CTE
+    fn create_materialized_cte(
+        &self,
+        name: &str,
+        plan: LogicalPlan,
+    ) -> datafusion_common::Result<Arc<dyn TableSource>> {
+        let table = Arc::new(MaterializedCte::new(name, plan));
+        Ok(provider_as_source(table))
+    }
+
     fn get_function_meta(&self, name: &str) -> Option<Arc<ScalarUDF>> {
         self.state.scalar_functions().get(name).cloned()
     }
@@ -1882,8 +2007,10 @@ mod tests {
     use crate::catalog_common::MemoryCatalogProviderList;
     use crate::datasource::MemTable;
     use crate::execution::context::SessionState;
+    use crate::prelude::SessionContext;
     use arrow_array::{ArrayRef, Int32Array, RecordBatch, StringArray};
     use arrow_schema::{DataType, Field, Schema};
+    use datafusion_common::display::{OptimizerPhase, OptimizerTraceEvent};
     use datafusion_common::DFSchema;
     use datafusion_common::Result;
     use datafusion_execution::config::SessionConfig;
@@ -1892,7 +2019,7 @@ mod tests {
     use datafusion_optimizer::Optimizer;
     use datafusion_sql::planner::{PlannerContext, SqlToRel};
     use std::collections::HashMap;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn test_session_state_with_default_features() {
@@ -2015,6 +2142,67 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_optimizer_trace_observer() -> Result<()> {
+        let events = Arc::new(Mutex::new(vec![]));
+        let captured = Arc::clone(&events);
+        let state = SessionStateBuilder::new()
+            .with_default_features()
+            .with_optimizer_trace_observer(Some(Arc::new(
+                move |event: &OptimizerTraceEvent| {
+                    captured.lock().unwrap().push(event.clone())
+                },
+            )))
+            .build();
+        let ctx = SessionContext::new_with_state(state);
+        ctx.sql("CREATE TABLE t AS VALUES (1, 2), (3, 4)")
+            .await?
+            .collect()
+            .await?;
+        events.lock().unwrap().clear();
+
+        ctx.sql("SELECT column1 FROM t WHERE column2 > 2")
+            .await?
+            .collect()
+            .await?;
+
+        let events = events.lock().unwrap();
+        let logical: Vec<_> = events
+            .iter()
+            .filter(|e| e.phase == OptimizerPhase::Logical)
+            .collect();
+        let rule_count = ctx.state().optimizers().len();
+        // the second pass does not change the plan
+        assert_eq!(logical.len(), 2 * rule_count);
+        assert!(logical[rule_count..]
+            .iter()
+            .all(|e| e.pass == 1 && !e.changed));
+
+        let changed: Vec<_> = logical.iter().filter(|e| e.changed).collect();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].rule_name, "optimize_projections");
+        assert_eq!(
+            changed[0].diff,
+            "--- before\n+++ after\n\
+             @@ -1,3 +1,3 @@\n \
+             Projection: t.column1\n\
+             \x20  Filter: t.column2 > Int64(2)\n\
+             -    TableScan: t\n\
+             +    TableScan: t projection=[column1, column2]\n"
+        );
+
+        let physical: Vec<_> = events
+            .iter()
+            .filter(|e| e.phase == OptimizerPhase::Physical)
+            .collect();
+        assert_eq!(physical.len(), ctx.state().physical_optimizers().len());
+        assert!(physical.iter().all(|e| e.pass == 0));
+        assert!(physical
+            .iter()
+            .any(|e| e.rule_name == "OutputRequirements" && e.changed));
+        Ok(())
+    }
+
     #[test]
     fn test_with_table_factories() -> Result<()> {
         use crate::test_util::TestTableFactory;
@@ -21,6 +21,7 @@ use crate::catalog_common::{MemoryCatalogProvider, MemorySchemaProvider};
 use crate::datasource::file_format::arrow::ArrowFormatFactory;
 use crate::datasource::file_format::avro::AvroFormatFactory;
 use crate::datasource::file_format::csv::CsvFormatFactory;
+use crate::datasource::file_format::fixed_width::FixedWidthFormatFactory;
 use crate::datasource::file_format::json::JsonFormatFactory;
 #[cfg(feature = "parquet")]
 use crate::datasource::file_format::parquet::ParquetFormatFactory;
@@ -55,6 +56,7 @@ impl SessionStateDefaults {
         table_factories.insert("NDJSON".into(), Arc::new(DefaultTableFactory::new()));
         table_factories.insert("AVRO".into(), Arc::new(DefaultTableFactory::new()));
         table_factories.insert("ARROW".into(), Arc::new(DefaultTableFactory::new()));
+        table_factories.insert("FIXEDWIDTH".into(), Arc::new(DefaultTableFactory::new()));
 
         table_factories
     }
@@ -128,6 +130,7 @@ impl SessionStateDefaults {
             Arc::new(CsvFormatFactory::new()),
             Arc::new(ArrowFormatFactory::new()),
             Arc::new(AvroFormatFactory::new()),
+            Arc::new(FixedWidthFormatFactory::new()),
         ];
 
         file_formats
@@ -24,8 +24,11 @@ use crate::{
     config::ConfigOptions,
     error::Result,
     physical_plan::{
-        coalesce_batches::CoalesceBatchesExec, filter::FilterExec, joins::HashJoinExec,
-        repartition::RepartitionExec, Partitioning,
+        coalesce_batches::CoalesceBatchesExec,
+        filter::FilterExec,
+        joins::{AdaptiveHashJoinExec, HashJoinExec},
+        repartition::RepartitionExec,
+        Partitioning,
     },
 };
 
@@ -62,6 +65,7 @@ impl PhysicalOptimizerRule for CoalesceBatches {
             // See https://github.com/apache/datafusion/issues/139
             let wrap_in_coalesce = plan_any.downcast_ref::<FilterExec>().is_some()
                 || plan_any.downcast_ref::<HashJoinExec>().is_some()
+                || plan_any.downcast_ref::<AdaptiveHashJoinExec>().is_some()
                 // Don't need to add CoalesceBatchesExec after a round robin RepartitionExec
                 || plan_any
                     .downcast_ref::<RepartitionExec>()
@@ -0,0 +1,796 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The [`JoinReorder`] rule reorders the inputs of chains of inner hash joins
+//! so that the estimated sizes of the intermediate join results are as small
+//! as possible.
+//!
+//! A chain of joins, called a join graph below, is a tree of [`HashJoinExec`]s
+//! of [`JoinType::Inner`] with equality join keys and no join filter, possibly
+//! separated by [`ProjectionExec`]s selecting columns. The inputs of the joins
+//! of the tree which are not part of it are the relations of the graph. Other
+//! joins, such as outer, semi and anti joins or joins with a filter, cannot be
+//! freely reordered with the inner joins and are relations of the graph, whose
+//! own inputs are reordered independently.
+//!
+//! The relations are joined greedily: the pair of connected relations, or of
+//! already joined relations, with the smallest estimated join cardinality is
+//! joined first, until all the relations are joined. The cardinality of a join
+//! is estimated from the row counts of its inputs and the distinct counts of
+//! its join keys, as `|L| * |R| / max(distinct(L.key), distinct(R.key))`
+//! using the most selective key. The join graph is left unchanged if any of its
+//! relations has no estimated row count, or if the greedy order is not
+//! estimated to produce fewer intermediate rows than the original order.
+
+use std::sync::Arc;
+
+use crate::config::ConfigOptions;
+use crate::error::Result;
+use crate::physical_plan::joins::{HashJoinExec, PartitionMode};
+use crate::physical_plan::projection::ProjectionExec;
+use crate::physical_plan::{ExecutionPlan, ExecutionPlanProperties};
+
+use arrow_schema::FieldRef;
+use datafusion_common::tree_node::{
+    Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
+};
+use datafusion_common::JoinType;
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_expr::utils::collect_columns;
+use datafusion_physical_expr::PhysicalExprRef;
+use datafusion_physical_optimizer::PhysicalOptimizerRule;
+
+/// The largest number of relations of a join graph that is reordered
+const MAX_RELATIONS: usize = 64;
+
+/// The [`JoinReorder`] rule reorders chains of inner hash joins using the
+/// statistics of their inputs, see the [module documentation](self).
+#[derive(Default, Debug)]
+pub struct JoinReorder {}
+
+impl JoinReorder {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for JoinReorder {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.enable_join_reordering {
+            return Ok(plan);
+        }
+        reorder_joins(plan)
+    }
+
+    fn name(&self) -> &str {
+        "join_reorder"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+fn reorder_joins(plan: Arc<dyn ExecutionPlan>) -> Result<Arc<dyn ExecutionPlan>> {
+    plan.transform_down(|plan| {
+        let Some(graph) = JoinGraph::try_new(&plan)? else {
+            return Ok(Transformed::no(plan));
+        };
+        match graph.reorder(plan.schema().fields())? {
+            // The relations were reordered themselves, skip them
+            Some(reordered) => {
+                Ok(Transformed::new(reordered, true, TreeNodeRecursion::Jump))
+            }
+            None => Ok(Transformed::no(plan)),
+        }
+    })
+    .data()
+}
+
+/// Whether `plan` is a join which can be reordered with the other joins of a
+/// join graph
+fn is_reorderable_join(plan: &dyn ExecutionPlan) -> bool {
+    plan.as_any()
+        .downcast_ref::<HashJoinExec>()
+        .is_some_and(|join| {
+            join.join_type() == &JoinType::Inner
+                && join.filter().is_none()
+                && !join.contain_projection()
+                && !join.null_equals_null()
+        })
+}
+
+/// Returns the input of `plan` if it is a projection of columns of a join
+/// which can be reordered
+fn column_projection_input(plan: &dyn ExecutionPlan) -> Option<&Arc<dyn ExecutionPlan>> {
+    let projection = plan.as_any().downcast_ref::<ProjectionExec>()?;
+    let only_columns = projection
+        .expr()
+        .iter()
+        .all(|(expr, _)| expr.as_any().is::<Column>());
+    (only_columns && is_reorderable_join(projection.input().as_ref()))
+        .then_some(projection.input())
+}
+
+/// An input of a join graph
+#[derive(Debug)]
+struct Relation {
+    plan: Arc<dyn ExecutionPlan>,
+    /// The index of the first column of the relation in the columns of all
+    /// the relations of the graph
+    offset: usize,
+    rows: usize,
+    /// The distinct count of each column, if known
+    distinct_counts: Vec<Option<usize>>,
+}
+
+/// An equality of two join keys of different relations. The keys are
+/// expressions of the columns of all the relations of the graph
+#[derive(Debug)]
+struct EquiCondition {
+    left: PhysicalExprRef,
+    left_relation: usize,
+    right: PhysicalExprRef,
+    right_relation: usize,
+}
+
+/// The order of the joins of a join graph
+#[derive(Debug)]
+enum JoinTree {
+    Relation(usize),
+    Join(Box<JoinTree>, Box<JoinTree>),
+}
+
+/// The estimated cardinality of a join of a set of relations
+#[derive(Debug, Clone, Copy)]
+struct Estimate {
+    /// A bit set of the relations
+    relations: u64,
+    rows: f64,
+}
+
+/// A join of a set of relations, being built
+struct Component {
+    plan: Arc<dyn ExecutionPlan>,
+    /// The column of all the relations of the graph at each output position
+    columns: Vec<usize>,
+    estimate: Estimate,
+}
+
+/// Inner joins which can be reordered, see the [module documentation](self)
+#[derive(Debug)]
+struct JoinGraph {
+    relations: Vec<Relation>,
+    /// The columns of all the relations
+    fields: Vec<FieldRef>,
+    conditions: Vec<EquiCondition>,
+    tree: JoinTree,
+    /// The column of all the relations at each output position of the graph
+    output: Vec<usize>,
+    mode: PartitionMode,
+}
+
+impl JoinGraph {
+    /// Returns the join graph whose root is `plan`, if it has at least three
+    /// relations with known row counts
+    fn try_new(plan: &Arc<dyn ExecutionPlan>) -> Result<Option<Self>> {
+        let Some(join) = plan.as_any().downcast_ref::<HashJoinExec>() else {
+            return Ok(None);
+        };
+        if !is_reorderable_join(join) {
+            return Ok(None);
+        }
+        let mut graph = Self {
+            relations: vec![],
+            fields: vec![],
+            conditions: vec![],
+            tree: JoinTree::Relation(0),
+            output: vec![],
+            mode: *join.partition_mode(),
+        };
+        let Some((output, tree)) = graph.flatten(plan)? else {
+            return Ok(None);
+        };
+        if graph.relations.len() < 3 || graph.relations.len() > MAX_RELATIONS {
+            return Ok(None);
+        }
+        graph.output = output;
+        graph.tree = tree;
+        Ok(Some(graph))
+    }
+
+    /// Adds the relations and join conditions of `plan` to the graph,
+    /// returning the columns of the output of `plan` and its join order, or
+    /// `None` if it cannot be reordered
+    fn flatten(
+        &mut self,
+        plan: &Arc<dyn ExecutionPlan>,
+    ) -> Result<Option<(Vec<usize>, JoinTree)>> {
+        if let Some(input) = column_projection_input(plan.as_ref()) {
+            let Some((columns, tree)) = self.flatten(input)? else {
+                return Ok(None);
+            };
+            let projection = plan.as_any().downcast_ref::<ProjectionExec>().unwrap();
+            let columns = projection
+                .expr()
+                .iter()
+                .map(|(expr, _)| {
+                    let column = expr.as_any().downcast_ref::<Column>().unwrap();
+                    columns[column.index()]
+                })
+                .collect();
+            return Ok(Some((columns, tree)));
+        }
+
+        if is_reorderable_join(plan.as_ref()) {
+            let join = plan.as_any().downcast_ref::<HashJoinExec>().unwrap();
+            let Some((left_columns, left_tree)) = self.flatten(join.left())? else {
+                return Ok(None);
+            };
+            let Some((right_columns, right_tree)) = self.flatten(join.right())? else {
+                return Ok(None);
+            };
+            for (left, right) in join.on() {
+                let (Some((left, left_relation)), Some((right, right_relation))) = (
+                    self.global_key(left, &left_columns)?,
+                    self.global_key(right, &right_columns)?,
+                ) else {
+                    return Ok(None);
+                };
+                self.conditions.push(EquiCondition {
+                    left,
+                    left_relation,
+                    right,
+                    right_relation,
+                });
+            }
+            let columns = left_columns.into_iter().chain(right_columns).collect();
+            let tree = JoinTree::Join(Box::new(left_tree), Box::new(right_tree));
+            return Ok(Some((columns, tree)));
+        }
+
+        // Inputs whose size is unknown, or which are unbounded, are not reordered
+        if plan.execution_mode().is_unbounded() {
+            return Ok(None);
+        }
+        let statistics = plan.statistics()?;
+        let Some(rows) = statistics.num_rows.get_value() else {
+            return Ok(None);
+        };
+        let offset = self.fields.len();
+        self.fields.extend(plan.schema().fields().iter().cloned());
+        self.relations.push(Relation {
+            plan: Arc::clone(plan),
+            offset,
+            rows: *rows,
+            distinct_counts: statistics
+                .column_statistics
+                .iter()
+                .map(|column| column.distinct_count.get_value().copied())
+                .collect(),
+        });
+        let columns = (offset..self.fields.len()).collect();
+        Ok(Some((
+            columns,
+            JoinTree::Relation(self.relations.len() - 1),
+        )))
+    }
+
+    /// Rewrites the join key `key` of an input whose columns are `columns` to
+    /// an expression of the columns of all the relations, returning it with
+    /// the relation it references, or `None` if it does not reference exactly
+    /// one relation
+    fn global_key(
+        &self,
+        key: &PhysicalExprRef,
+        columns: &[usize],
+    ) -> Result<Option<(PhysicalExprRef, usize)>> {
+        let key = rewrite_columns(key, |index| {
+            let column = columns[index];
+            (self.fields[column].name().as_str(), column)
+        })?;
+        let mut relations = collect_columns(&key)
+            .into_iter()
+            .map(|column| self.relation_of(column.index()))
+            .collect::<Vec<_>>();
+        relations.dedup();
+        Ok(match relations.as_slice() {
+            [relation] => Some((key, *relation)),
+            _ => None,
+        })
+    }
+
+    /// Returns the index of the relation of a column of all the relations
+    fn relation_of(&self, column: usize) -> usize {
+        self.relations
+            .partition_point(|relation| relation.offset <= column)
+            - 1
+    }
+
+    /// Reorders the joins of the graph, returning the reordered plan with the
+    /// columns of `fields`, or `None` if the current order is estimated to be
+    /// as good as the greedy order
+    fn reorder(&self, fields: &[FieldRef]) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+        let (_, current_cost) = self.tree_cost(&self.tree);
+        let (order, greedy_cost) = self.greedy_order();
+        if greedy_cost >= current_cost {
+            return Ok(None);
+        }
+
+        let mut components = self
+            .relations
+            .iter()
+            .enumerate()
+            .map(|(i, relation)| {
+                Ok(Component {
+                    plan: reorder_joins(Arc::clone(&relation.plan))?,
+                    columns: (relation.offset
+                        ..relation.offset + relation.distinct_counts.len())
+                        .collect(),
+                    estimate: Estimate {
+                        relations: 1 << i,
+                        rows: relation.rows as f64,
+                    },
+                })
+            })
+            .map(|component| component.map(Some))
+            .collect::<Result<Vec<_>>>()?;
+        let mut root = 0;
+        for (left, right) in order {
+            let left_component = components[left].take().unwrap();
+            let right_component = components[right].take().unwrap();
+            components[left] = Some(self.join(left_component, right_component)?);
+            root = left;
+        }
+        let root = components[root].take().unwrap();
+
+        // Restore the columns of the original plan
+        let projection = self
+            .output
+            .iter()
+            .zip(fields)
+            .map(|(column, field)| {
+                let index = root.columns.iter().position(|c| c == column).unwrap();
+                let expr: PhysicalExprRef = Arc::new(Column::new(field.name(), index));
+                (expr, field.name().clone())
+            })
+            .collect::<Vec<_>>();
+        let identity = root.columns.len() == projection.len()
+            && projection.iter().enumerate().all(|(i, (expr, name))| {
+                let column = expr.as_any().downcast_ref::<Column>().unwrap();
+                column.index() == i && root.plan.schema().field(i).name() == name
+            });
+        if identity {
+            return Ok(Some(root.plan));
+        }
+        Ok(Some(Arc::new(ProjectionExec::try_new(
+            projection, root.plan,
+        )?)))
+    }
+
+    /// Joins two components, the smaller being the build side
+    fn join(&self, left: Component, right: Component) -> Result<Component> {
+        let (left, right) = if left.estimate.rows <= right.estimate.rows {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        let on = self
+            .connecting_conditions(left.estimate.relations, right.estimate.relations)
+            .map(|(left_key, right_key)| {
+                Ok((
+                    local_key(left_key, &left.columns, &self.fields)?,
+                    local_key(right_key, &right.columns, &self.fields)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let estimate = self.join_estimate(left.estimate, right.estimate);
+        let plan = Arc::new(HashJoinExec::try_new(
+            left.plan,
+            right.plan,
+            on,
+            None,
+            &JoinType::Inner,
+            None,
+            self.mode,
+            false,
+        )?);
+        let columns = left.columns.into_iter().chain(right.columns).collect();
+        Ok(Component {
+            plan,
+            columns,
+            estimate,
+        })
+    }
+
+    /// Returns the keys of the conditions joining the relations of `left` to
+    /// those of `right`, oriented from left to right
+    fn connecting_conditions(
+        &self,
+        left: u64,
+        right: u64,
+    ) -> impl Iterator<Item = (&PhysicalExprRef, &PhysicalExprRef)> {
+        self.conditions.iter().filter_map(move |condition| {
+            let l = 1 << condition.left_relation;
+            let r = 1 << condition.right_relation;
+            if left & l != 0 && right & r != 0 {
+                Some((&condition.left, &condition.right))
+            } else if left & r != 0 && right & l != 0 {
+                Some((&condition.right, &condition.left))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Estimates the cardinality of the join of `left` and `right`, using the
+    /// most selective of their join conditions
+    fn join_estimate(&self, left: Estimate, right: Estimate) -> Estimate {
+        let divisor = self
+            .connecting_conditions(left.relations, right.relations)
+            .map(|(left_key, right_key)| {
+                self.distinct_count(left_key, left.rows)
+                    .max(self.distinct_count(right_key, right.rows))
+            })
+            .fold(1.0, f64::max);
+        Estimate {
+            relations: left.relations | right.relations,
+            rows: left.rows * right.rows / divisor,
+        }
+    }
+
+    /// Estimates the number of distinct values of a join key in a join result
+    /// of `rows` rows. Keys which are not columns, or whose distinct count is
+    /// unknown, are assumed to be unique in their relation
+    fn distinct_count(&self, key: &PhysicalExprRef, rows: f64) -> f64 {
+        let relation_count = match key.as_any().downcast_ref::<Column>() {
+            Some(column) => {
+                let relation = &self.relations[self.relation_of(column.index())];
+                relation.distinct_counts[column.index() - relation.offset]
+                    .unwrap_or(relation.rows)
+            }
+            None => {
+                let column = &collect_columns(key).into_iter().next().unwrap();
+                self.relations[self.relation_of(column.index())].rows
+            }
+        };
+        (relation_count as f64).min(rows).max(1.0)
+    }
+
+    /// Returns the estimate of the result of `tree` and the sum of the
+    /// estimated rows of its joins
+    fn tree_cost(&self, tree: &JoinTree) -> (Estimate, f64) {
+        match tree {
+            JoinTree::Relation(i) => (
+                Estimate {
+                    relations: 1 << i,
+                    rows: self.relations[*i].rows as f64,
+                },
+                0.0,
+            ),
+            JoinTree::Join(left, right) => {
+                let (left, left_cost) = self.tree_cost(left);
+                let (right, right_cost) = self.tree_cost(right);
+                let estimate = self.join_estimate(left, right);
+                (estimate, left_cost + right_cost + estimate.rows)
+            }
+        }
+    }
+
+    /// Returns the pairs of components joined in the greedy order, and the
+    /// sum of the estimated rows of the joins. A pair `(i, j)` joins the
+    /// component `j` into the component `i`
+    fn greedy_order(&self) -> (Vec<(usize, usize)>, f64) {
+        let mut components = self
+            .relations
+            .iter()
+            .enumerate()
+            .map(|(i, relation)| {
+                Some(Estimate {
+                    relations: 1 << i,
+                    rows: relation.rows as f64,
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut order = vec![];
+        let mut cost = 0.0;
+        for _ in 1..components.len() {
+            let mut best: Option<(usize, usize, Estimate)> = None;
+            for (i, left) in components.iter().enumerate() {
+                let Some(left) = left else { continue };
+                for (j, right) in components.iter().enumerate().skip(i + 1) {
+                    let Some(right) = right else { continue };
+                    let connected = self
+                        .connecting_conditions(left.relations, right.relations)
+                        .next()
+                        .is_some();
+                    if !connected {
+                        continue;
+                    }
+                    let estimate = self.join_estimate(*left, *right);
+                    if best.map_or(true, |(_, _, best)| estimate.rows < best.rows) {
+                        best = Some((i, j, estimate));
+                    }
+                }
+            }
+            // The relations of a join graph are always connected
+            let Some((i, j, estimate)) = best else {
+                return (order, f64::INFINITY);
+            };
+            components[i] = Some(estimate);
+            components[j] = None;
+            order.push((i, j));
+            cost += estimate.rows;
+        }
+        (order, cost)
+    }
+}
+
+/// Rewrites a join key of the columns of all the relations to an expression
+/// of the columns of a component
+fn local_key(
+    key: &PhysicalExprRef,
+    columns: &[usize],
+    fields: &[FieldRef],
+) -> Result<PhysicalExprRef> {
+    rewrite_columns(key, |index| {
+        let position = columns.iter().position(|c| *c == index).unwrap();
+        (fields[index].name().as_str(), position)
+    })
+}
+
+/// Replaces each column of `expr` with the column of the name and index
+/// returned by `f` for its index
+fn rewrite_columns<'a>(
+    expr: &PhysicalExprRef,
+    f: impl Fn(usize) -> (&'a str, usize),
+) -> Result<PhysicalExprRef> {
+    Arc::clone(expr)
+        .transform(|expr| match expr.as_any().downcast_ref::<Column>() {
+            Some(column) => {
+                let (name, index) = f(column.index());
+                Ok(Transformed::yes(Arc::new(Column::new(name, index)) as _))
+            }
+            None => Ok(Transformed::no(expr)),
+        })
+        .data()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_plan::joins::NestedLoopJoinExec;
+    use crate::physical_plan::{displayable, ColumnStatistics, Statistics};
+    use crate::test::StatisticsExec;
+
+    use arrow_schema::{DataType, Field, Schema};
+    use datafusion_common::stats::Precision;
+
+    /// A relation of `rows` rows whose columns have the given names and
+    /// distinct counts
+    fn relation(
+        rows: usize,
+        columns: &[(&str, Option<usize>)],
+    ) -> Arc<dyn ExecutionPlan> {
+        let schema = Schema::new(
+            columns
+                .iter()
+                .map(|(name, _)| Field::new(*name, DataType::Int32, true))
+                .collect::<Vec<_>>(),
+        );
+        let statistics = Statistics {
+            num_rows: Precision::Inexact(rows),
+            total_byte_size: Precision::Absent,
+            column_statistics: columns
+                .iter()
+                .map(|(_, distinct_count)| ColumnStatistics {
+                    distinct_count: distinct_count
+                        .map_or(Precision::Absent, Precision::Inexact),
+                    ..ColumnStatistics::new_unknown()
+                })
+                .collect(),
+        };
+        Arc::new(StatisticsExec::new(statistics, schema))
+    }
+
+    fn hash_join(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: &[(&str, &str)],
+    ) -> Arc<dyn ExecutionPlan> {
+        join_of_type(left, right, on, JoinType::Inner)
+    }
+
+    fn join_of_type(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: &[(&str, &str)],
+        join_type: JoinType,
+    ) -> Arc<dyn ExecutionPlan> {
+        let column = |plan: &Arc<dyn ExecutionPlan>, name: &str| -> PhysicalExprRef {
+            Arc::new(Column::new_with_schema(name, &plan.schema()).unwrap())
+        };
+        let on = on
+            .iter()
+            .map(|(l, r)| (column(&left, l), column(&right, r)))
+            .collect();
+        Arc::new(
+            HashJoinExec::try_new(
+                left,
+                right,
+                on,
+                None,
+                &join_type,
+                None,
+                PartitionMode::Auto,
+                false,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn optimize(plan: Arc<dyn ExecutionPlan>) -> Vec<String> {
+        let optimized = JoinReorder::new()
+            .optimize(Arc::clone(&plan), &ConfigOptions::new())
+            .unwrap();
+        assert_eq!(optimized.schema(), plan.schema());
+        let formatted = displayable(optimized.as_ref()).indent(true).to_string();
+        formatted.trim().lines().map(String::from).collect()
+    }
+
+    /// A fact table joined to a large dimension first, and then to a small
+    /// and selective one
+    fn star_join() -> Arc<dyn ExecutionPlan> {
+        let fact = relation(1_000_000, &[("f_a", Some(100)), ("f_b", Some(1_000))]);
+        let a = relation(100_000, &[("a", Some(100)), ("a_v", None)]);
+        let b = relation(10, &[("b", None)]);
+        hash_join(hash_join(fact, a, &[("f_a", "a")]), b, &[("f_b", "b")])
+    }
+
+    #[test]
+    fn reorder_star_join() {
+        let expected = [
+            "ProjectionExec: expr=[f_a@1 as f_a, f_b@2 as f_b, a@3 as a, a_v@4 as a_v, b@0 as b]",
+            "  HashJoinExec: mode=Auto, join_type=Inner, on=[(f_a@1, a@0)]",
+            "    HashJoinExec: mode=Auto, join_type=Inner, on=[(b@0, f_b@1)]",
+            "      StatisticsExec: col_count=1, row_count=Inexact(10)",
+            "      StatisticsExec: col_count=2, row_count=Inexact(1000000)",
+            "    StatisticsExec: col_count=2, row_count=Inexact(100000)",
+        ];
+        assert_eq!(optimize(star_join()), expected);
+    }
+
+    #[test]
+    fn keep_better_order() {
+        let fact = relation(1_000_000, &[("f_a", Some(100)), ("f_b", Some(1_000))]);
+        let a = relation(100_000, &[("a", Some(100))]);
+        let b = relation(10, &[("b", None)]);
+        let plan = hash_join(hash_join(fact, b, &[("f_b", "b")]), a, &[("f_a", "a")]);
+        let expected = [
+            "HashJoinExec: mode=Auto, join_type=Inner, on=[(f_a@0, a@0)]",
+            "  HashJoinExec: mode=Auto, join_type=Inner, on=[(f_b@1, b@0)]",
+            "    StatisticsExec: col_count=2, row_count=Inexact(1000000)",
+            "    StatisticsExec: col_count=1, row_count=Inexact(10)",
+            "  StatisticsExec: col_count=1, row_count=Inexact(100000)",
+        ];
+        assert_eq!(optimize(plan), expected);
+    }
+
+    #[test]
+    fn keep_order_without_statistics() {
+        let fact = relation(1_000_000, &[("f_a", Some(100)), ("f_b", Some(1_000))]);
+        let a = relation(100_000, &[("a", Some(100))]);
+        let b = Arc::new(StatisticsExec::new(
+            Statistics::new_unknown(&Schema::new(vec![Field::new(
+                "b",
+                DataType::Int32,
+                true,
+            )])),
+            Schema::new(vec![Field::new("b", DataType::Int32, true)]),
+        ));
+        let plan = hash_join(hash_join(fact, a, &[("f_a", "a")]), b, &[("f_b", "b")]);
+        let expected = [
+            "HashJoinExec: mode=Auto, join_type=Inner, on=[(f_b@1, b@0)]",
+            "  HashJoinExec: mode=Auto, join_type=Inner, on=[(f_a@0, a@0)]",
+            "    StatisticsExec: col_count=2, row_count=Inexact(1000000)",
+            "    StatisticsExec: col_count=1, row_count=Inexact(100000)",
+            "  StatisticsExec: col_count=1, row_count=Absent",
+        ];
+        assert_eq!(optimize(plan), expected);
+    }
+
+    #[test]
+    fn outer_join_is_a_relation() {
+        // The left join is not reordered with the inner joins, but its
+        // input is reordered independently
+        let c = relation(1_000, &[("c", Some(1_000))]);
+        let plan = join_of_type(c, star_join(), &[("c", "f_a")], JoinType::Left);
+        let expected = [
+            "HashJoinExec: mode=Auto, join_type=Left, on=[(c@0, f_a@0)]",
+            "  StatisticsExec: col_count=1, row_count=Inexact(1000)",
+            "  ProjectionExec: expr=[f_a@1 as f_a, f_b@2 as f_b, a@3 as a, a_v@4 as a_v, b@0 as b]",
+            "    HashJoinExec: mode=Auto, join_type=Inner, on=[(f_a@1, a@0)]",
+            "      HashJoinExec: mode=Auto, join_type=Inner, on=[(b@0, f_b@1)]",
+            "        StatisticsExec: col_count=1, row_count=Inexact(10)",
+            "        StatisticsExec: col_count=2, row_count=Inexact(1000000)",
+            "      StatisticsExec: col_count=2, row_count=Inexact(100000)",
+        ];
+        assert_eq!(optimize(plan), expected);
+
+        // The cardinality of the cross join is unknown, so only the joins
+        // below it are reordered
+        let d = relation(5, &[("d", Some(5))]);
+        let e = relation(5, &[("e", Some(5))]);
+        let cross =
+            Arc::new(NestedLoopJoinExec::try_new(d, e, None, &JoinType::Inner).unwrap());
+        let plan = hash_join(star_join(), cross, &[("a_v", "d")]);
+        let expected = [
+            "HashJoinExec: mode=Auto, join_type=Inner, on=[(a_v@3, d@0)]",
+            "  ProjectionExec: expr=[f_a@1 as f_a, f_b@2 as f_b, a@3 as a, a_v@4 as a_v, b@0 as b]",
+            "    HashJoinExec: mode=Auto, join_type=Inner, on=[(f_a@1, a@0)]",
+            "      HashJoinExec: mode=Auto, join_type=Inner, on=[(b@0, f_b@1)]",
+            "        StatisticsExec: col_count=1, row_count=Inexact(10)",
+            "        StatisticsExec: col_count=2, row_count=Inexact(1000000)",
+            "      StatisticsExec: col_count=2, row_count=Inexact(100000)",
+            "  NestedLoopJoinExec: join_type=Inner",
+            "    StatisticsExec: col_count=1, row_count=Inexact(5)",
+            "    StatisticsExec: col_count=1, row_count=Inexact(5)",
+        ];
+        assert_eq!(optimize(plan), expected);
+    }
+
+    #[test]
+    fn projections_between_joins() {
+        let fact = relation(1_000_000, &[("f_a", Some(100)), ("f_b", Some(1_000))]);
+        let a = relation(100_000, &[("a", Some(100)), ("a_v", None)]);
+        let b = relation(10, &[("b", None)]);
+        let join = hash_join(fact, a, &[("f_a", "a")]);
+        let exprs = [("f_b", 1), ("a_v", 3)]
+            .into_iter()
+            .map(|(name, index)| {
+                (Arc::new(Column::new(name, index)) as _, name.to_string())
+            })
+            .collect();
+        let projection = Arc::new(ProjectionExec::try_new(exprs, join).unwrap());
+        let plan = hash_join(projection, b, &[("f_b", "b")]);
+        let expected = [
+            "ProjectionExec: expr=[f_b@2 as f_b, a_v@4 as a_v, b@0 as b]",
+            "  HashJoinExec: mode=Auto, join_type=Inner, on=[(f_a@1, a@0)]",
+            "    HashJoinExec: mode=Auto, join_type=Inner, on=[(b@0, f_b@1)]",
+            "      StatisticsExec: col_count=1, row_count=Inexact(10)",
+            "      StatisticsExec: col_count=2, row_count=Inexact(1000000)",
+            "    StatisticsExec: col_count=2, row_count=Inexact(100000)",
+        ];
+        assert_eq!(optimize(plan), expected);
+    }
+
+    #[test]
+    fn disabled() {
+        let plan = star_join();
+        let mut config = ConfigOptions::new();
+        config.optimizer.enable_join_reordering = false;
+        let optimized = JoinReorder::new()
+            .optimize(Arc::clone(&plan), &config)
+            .unwrap();
+        assert!(Arc::ptr_eq(&plan, &optimized));
+    }
+}
@@ -27,20 +27,21 @@ use std::sync::Arc;
 
 use crate::config::ConfigOptions;
 use crate::error::Result;
-use crate::physical_plan::joins::utils::{ColumnIndex, JoinFilter};
+use crate::physical_plan::joins::utils::{
+    swap_reverting_projection, ColumnIndex, JoinFilter,
+};
 use crate::physical_plan::joins::{
-    CrossJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode,
+    AdaptiveHashJoinExec, CrossJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode,
     StreamJoinPartitionMode, SymmetricHashJoinExec,
 };
 use crate::physical_plan::projection::ProjectionExec;
 use crate::physical_plan::{ExecutionPlan, ExecutionPlanProperties};
 
-use arrow_schema::Schema;
 use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
 use datafusion_common::{internal_err, JoinSide, JoinType};
 use datafusion_expr::sort_properties::SortProperties;
 use datafusion_physical_expr::expressions::Column;
-use datafusion_physical_expr::{PhysicalExpr, PhysicalSortExpr};
+use datafusion_physical_expr::PhysicalSortExpr;
 use datafusion_physical_optimizer::PhysicalOptimizerRule;
 
 /// The [`JoinSelection`] rule tries to modify a given plan so that it can
@@ -120,54 +121,6 @@ fn supports_swap(join_type: JoinType) -> bool {
     )
 }
 
-/// This function returns the new join type we get after swapping the given
-/// join's inputs.
-fn swap_join_type(join_type: JoinType) -> JoinType {
-    match join_type {
-        JoinType::Inner => JoinType::Inner,
-        JoinType::Full => JoinType::Full,
-        JoinType::Left => JoinType::Right,
-        JoinType::Right => JoinType::Left,
-        JoinType::LeftSemi => JoinType::RightSemi,
-        JoinType::RightSemi => JoinType::LeftSemi,
-        JoinType::LeftAnti => JoinType::RightAnti,
-        JoinType::RightAnti => JoinType::LeftAnti,
-    }
-}
-
-/// This function swaps the given join's projection.
-fn swap_join_projection(
-    left_schema_len: usize,
-    right_schema_len: usize,
-    projection: Option<&Vec<usize>>,
-    join_type: &JoinType,
-) -> Option<Vec<usize>> {
-    match join_type {
-        // For Anti/Semi join types, projection should remain unmodified,
-        // since these joins output schema remains the same after swap
-        JoinType::LeftAnti
-        | JoinType::LeftSemi
-        | JoinType::RightAnti
-        | JoinType::RightSemi => projection.cloned(),
-
-        _ => projection.map(|p| {
-            p.iter()
-                .map(|i| {
-                    // If the index is less than the left schema length, it is from
-                    // the left schema, so we add the right schema length to it.
-                    // Otherwise, it is from the right schema, so we subtract the left
-                    // schema length from it.
-                    if *i < left_schema_len {
-                        *i + right_schema_len
-                    } else {
-                        *i - left_schema_len
-                    }
-                })
-                .collect()
-        }),
-    }
-}
-
 /// This function swaps the inputs of the given join operator.
 /// This function is public so other downstream projects can use it
 /// to construct `HashJoinExec` with right side as the build side.
@@ -175,51 +128,13 @@ pub fn swap_hash_join(
     hash_join: &HashJoinExec,
     partition_mode: PartitionMode,
 ) -> Result<Arc<dyn ExecutionPlan>> {
-    let left = hash_join.left();
-    let right = hash_join.right();
-    let new_join = HashJoinExec::try_new(
-        Arc::clone(right),
-        Arc::clone(left),
-        hash_join
-            .on()
-            .iter()
-            .map(|(l, r)| (r.clone(), l.clone()))
-            .collect(),
-        swap_join_filter(hash_join.filter()),
-        &swap_join_type(*hash_join.join_type()),
-        swap_join_projection(
-            left.schema().fields().len(),
-            right.schema().fields().len(),
-            hash_join.projection.as_ref(),
-            hash_join.join_type(),
-        ),
-        partition_mode,
-        hash_join.null_equals_null(),
-    )?;
-    // In case of anti / semi joins or if there is embedded projection in HashJoinExec, output column order is preserved, no need to add projection again
-    if matches!(
-        hash_join.join_type(),
-        JoinType::LeftSemi
-            | JoinType::RightSemi
-            | JoinType::LeftAnti
-            | JoinType::RightAnti
-    ) || hash_join.projection.is_some()
-    {
-        Ok(Arc::new(new_join))
-    } else {
-        // TODO avoid adding ProjectionExec again and again, only adding Final Projection
-        let proj = ProjectionExec::try_new(
-            swap_reverting_projection(&left.schema(), &right.schema()),
-            Arc::new(new_join),
-        )?;
-        Ok(Arc::new(proj))
-    }
+    hash_join.swap_inputs(partition_mode)
 }
 
 /// Swaps inputs of `NestedLoopJoinExec` and wraps it into `ProjectionExec` is required
 fn swap_nl_join(join: &NestedLoopJoinExec) -> Result<Arc<dyn ExecutionPlan>> {
-    let new_filter = swap_join_filter(join.filter());
-    let new_join_type = &swap_join_type(*join.join_type());
+    let new_filter = join.filter().map(JoinFilter::swap);
+    let new_join_type = &join.join_type().swap();
 
     let new_join = NestedLoopJoinExec::try_new(
         Arc::clone(join.right()),
@@ -248,54 +163,6 @@ fn swap_nl_join(join: &NestedLoopJoinExec) -> Result<Arc<dyn ExecutionPlan>> {
     Ok(plan)
 }
 
-/// When the order of the join is changed by the optimizer, the columns in
-/// the output should not be impacted. This function creates the expressions
-/// that will allow to swap back the values from the original left as the first
-/// columns and those on the right next.
-fn swap_reverting_projection(
-    left_schema: &Schema,
-    right_schema: &Schema,
-) -> Vec<(Arc<dyn PhysicalExpr>, String)> {
-    let right_cols = right_schema.fields().iter().enumerate().map(|(i, f)| {
-        (
-            Arc::new(Column::new(f.name(), i)) as Arc<dyn PhysicalExpr>,
-            f.name().to_owned(),
-        )
-    });
-    let right_len = right_cols.len();
-    let left_cols = left_schema.fields().iter().enumerate().map(|(i, f)| {
-        (
-            Arc::new(Column::new(f.name(), right_len + i)) as Arc<dyn PhysicalExpr>,
-            f.name().to_owned(),
-        )
-    });
-
-    left_cols.chain(right_cols).collect()
-}
-
-/// Swaps join sides for filter column indices and produces new JoinFilter
-fn swap_filter(filter: &JoinFilter) -> JoinFilter {
-    let column_indices = filter
-        .column_indices()
-        .iter()
-        .map(|idx| ColumnIndex {
-            index: idx.index,
-            side: idx.side.negate(),
-        })
-        .collect();
-
-    JoinFilter::new(
-        filter.expression().clone(),
-        column_indices,
-        filter.schema().clone(),
-    )
-}
-
-/// Swaps join sides for filter column indices and produces new `JoinFilter` (if exists).
-fn swap_join_filter(filter: Option<&JoinFilter>) -> Option<JoinFilter> {
-    filter.map(swap_filter)
-}
-
 impl PhysicalOptimizerRule for JoinSelection {
     fn optimize(
         &self,
@@ -325,6 +192,13 @@ impl PhysicalOptimizerRule for JoinSelection {
         //   do not modify join sides.
         // - We will also swap left and right sides for cross joins so that the left
         //   side is the small side.
+        // - If adaptive joins are enabled, a hash join with partition mode
+        //   [`PartitionMode::Auto`] is instead replaced by an
+        //   [`AdaptiveHashJoinExec`] choosing its mode at runtime.
+        let adaptive_join_partitions = config
+            .optimizer
+            .enable_adaptive_join
+            .then_some(config.execution.target_partitions);
         let config = &config.optimizer;
         let collect_threshold_byte_size = config.hash_join_single_partition_threshold;
         let collect_threshold_num_rows = config.hash_join_single_partition_threshold_rows;
@@ -334,6 +208,7 @@ impl PhysicalOptimizerRule for JoinSelection {
                     plan,
                     collect_threshold_byte_size,
                     collect_threshold_num_rows,
+                    adaptive_join_partitions,
                 )
             })
             .data()
@@ -438,11 +313,33 @@ fn partitioned_hash_join(hash_join: &HashJoinExec) -> Result<Arc<dyn ExecutionPl
 
 /// This subrule tries to modify a given plan so that it can
 /// optimize hash and cross joins in the plan according to available statistical information.
+///
+/// When `adaptive_join_partitions` is set, hash joins in [`PartitionMode::Auto`]
+/// over bounded inputs are replaced by an [`AdaptiveHashJoinExec`] with that
+/// many output partitions.
 fn statistical_join_selection_subrule(
     plan: Arc<dyn ExecutionPlan>,
     collect_threshold_byte_size: usize,
     collect_threshold_num_rows: usize,
+    adaptive_join_partitions: Option<usize>,
 ) -> Result<Transformed<Arc<dyn ExecutionPlan>>> {
+    if let (Some(hash_join), Some(partitions)) = (
+        plan.as_any().downcast_ref::<HashJoinExec>(),
+        adaptive_join_partitions,
+    ) {
+        if hash_join.partition_mode() == &PartitionMode::Auto
+            && !hash_join.left().execution_mode().is_unbounded()
+            && !hash_join.right().execution_mode().is_unbounded()
+        {
+            return Ok(Transformed::yes(Arc::new(AdaptiveHashJoinExec::try_new(
+                hash_join,
+                collect_threshold_byte_size,
+                collect_threshold_num_rows,
+                partitions,
+            )?)));
+        }
+    }
+
     let transformed =
         if let Some(hash_join) = plan.as_any().downcast_ref::<HashJoinExec>() {
             match hash_join.partition_mode() {
@@ -726,11 +623,11 @@ mod tests_statistical {
         test::StatisticsExec,
     };
 
-    use arrow::datatypes::{DataType, Field};
+    use arrow::datatypes::{DataType, Field, Schema};
     use datafusion_common::{stats::Precision, JoinType, ScalarValue};
     use datafusion_expr::Operator;
     use datafusion_physical_expr::expressions::BinaryExpr;
-    use datafusion_physical_expr::PhysicalExprRef;
+    use datafusion_physical_expr::{PhysicalExpr, PhysicalExprRef};
 
     use rstest::rstest;
 
@@ -899,6 +796,49 @@ mod tests_statistical {
         (big, medium, small)
     }
 
+    #[tokio::test]
+    async fn test_adaptive_join() {
+        let (big, small) = create_big_and_small();
+
+        let join = Arc::new(
+            HashJoinExec::try_new(
+                Arc::clone(&big),
+                Arc::clone(&small),
+                vec![(
+                    Arc::new(Column::new_with_schema("big_col", &big.schema()).unwrap()),
+                    Arc::new(
+                        Column::new_with_schema("small_col", &small.schema()).unwrap(),
+                    ),
+                )],
+                None,
+                &JoinType::Inner,
+                None,
+                PartitionMode::Auto,
+                false,
+            )
+            .unwrap(),
+        );
+
+        let mut config = ConfigOptions::new();
+        config.optimizer.enable_adaptive_join = true;
+        config.execution.target_partitions = 4;
+        let optimized_join = JoinSelection::new().optimize(join, &config).unwrap();
+
+        // the inputs are not swapped from the estimated statistics
+        let adaptive_join = optimized_join
+            .as_any()
+            .downcast_ref::<AdaptiveHashJoinExec>()
+            .expect("The join should be adaptive");
+        assert_eq!(
+            adaptive_join.join().left().schema().field(0).name(),
+            "big_col"
+        );
+        assert_eq!(
+            optimized_join.output_partitioning().partition_count(),
+            config.execution.target_partitions
+        );
+    }
+
     #[tokio::test]
     async fn test_join_with_swap() {
         let (big, small) = create_big_and_small();
@@ -1618,7 +1558,7 @@ mod hash_join_tests {
     use crate::physical_optimizer::test_utils::SourceType;
     use crate::test_util::UnboundedExec;
 
-    use arrow::datatypes::{DataType, Field};
+    use arrow::datatypes::{DataType, Field, Schema};
     use arrow::record_batch::RecordBatch;
 
     struct TestCase {
@@ -1699,7 +1639,7 @@ mod hash_join_tests {
                 initial_join_type: join_type,
                 initial_mode: PartitionMode::CollectLeft,
                 expected_sources_unbounded: (SourceType::Bounded, SourceType::Unbounded),
-                expected_join_type: swap_join_type(join_type),
+                expected_join_type: join_type.swap(),
                 expected_mode: PartitionMode::CollectLeft,
                 expecting_swap: true,
             });
@@ -1742,7 +1682,7 @@ mod hash_join_tests {
                 initial_join_type: join_type,
                 initial_mode: PartitionMode::Partitioned,
                 expected_sources_unbounded: (SourceType::Bounded, SourceType::Unbounded),
-                expected_join_type: swap_join_type(join_type),
+                expected_join_type: join_type.swap(),
                 expected_mode: PartitionMode::Partitioned,
                 expecting_swap: true,
             });
@@ -1800,7 +1740,7 @@ mod hash_join_tests {
                 initial_join_type: join_type,
                 initial_mode: PartitionMode::Partitioned,
                 expected_sources_unbounded: (SourceType::Bounded, SourceType::Unbounded),
-                expected_join_type: swap_join_type(join_type),
+                expected_join_type: join_type.swap(),
                 expected_mode: PartitionMode::Partitioned,
                 expecting_swap: true,
             });
@@ -27,6 +27,7 @@ use crate::physical_optimizer::coalesce_batches::CoalesceBatches;
 use crate::physical_optimizer::combine_partial_final_agg::CombinePartialFinalAggregate;
 use crate::physical_optimizer::enforce_distribution::EnforceDistribution;
 use crate::physical_optimizer::enforce_sorting::EnforceSorting;
+use crate::physical_optimizer::join_reorder::JoinReorder;
 use crate::physical_optimizer::join_selection::JoinSelection;
 use crate::physical_optimizer::limit_pushdown::LimitPushdown;
 use crate::physical_optimizer::limited_distinct_aggregation::LimitedDistinctAggregation;
@@ -55,6 +56,9 @@ impl PhysicalOptimizer {
             // this information is not lost across different rules during optimization.
             Arc::new(OutputRequirements::new_add_mode()),
             Arc::new(AggregateStatistics::new()),
+            // The JoinReorder rule changes the order of chains of inner joins using statistics,
+            // so it should run before JoinSelection, which picks the build side of each join.
+            Arc::new(JoinReorder::new()),
             // Statistics-based join selection will change the Auto mode to a real join implementation,
             // like collect left, or hash join, or future sort merge join, which will influence the
             // EnforceDistribution and EnforceSorting rules as they decide whether to add additional
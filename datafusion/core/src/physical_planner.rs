@@ -27,6 +27,8 @@ use crate::datasource::physical_plan::FileSinkConfig;
 use crate::datasource::source_as_provider;
 use crate::error::{DataFusionError, Result};
 use crate::execution::context::{ExecutionProps, SessionState};
+use crate::execution::result_cache::{ResultCacheExec, ResultCacheKey};
+use crate::execution::semi_join_reduction::plan_semi_join_reductions;
 use crate::logical_expr::utils::generate_sort_key;
 use crate::logical_expr::{
     Aggregate, EmptyRelation, Join, Projection, Sort, TableScan, Unnest, Values, Window,
@@ -51,6 +53,7 @@ use crate::physical_plan::memory::MemoryExec;
 use crate::physical_plan::projection::ProjectionExec;
 use crate::physical_plan::recursive_query::RecursiveQueryExec;
 use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::semi_join_reduction::SemiJoinReductionExec;
 use crate::physical_plan::sorts::sort::SortExec;
 use crate::physical_plan::union::UnionExec;
 use crate::physical_plan::unnest::UnnestExec;
@@ -63,9 +66,12 @@ use crate::physical_plan::{
 
 use arrow::compute::SortOptions;
 use arrow::datatypes::{Schema, SchemaRef};
-use arrow_array::builder::StringBuilder;
-use arrow_array::RecordBatch;
-use datafusion_common::display::ToStringifiedPlan;
+use arrow_array::builder::{Float64Builder, StringBuilder};
+use arrow_array::{ArrayRef, RecordBatch};
+use datafusion_common::display::{
+    ExplainFormat, OptimizerPhase, OptimizerTraceEvent, ToStringifiedPlan,
+};
+use datafusion_common::instant::Instant;
 use datafusion_common::{
     exec_err, internal_datafusion_err, internal_err, not_impl_err, plan_err, DFSchema,
     ScalarValue,
@@ -76,6 +82,7 @@ use datafusion_expr::expr::{
 };
 use datafusion_expr::expr_rewriter::unnormalize_cols;
 use datafusion_expr::logical_plan::builder::wrap_projection_for_join_if_necessary;
+use datafusion_expr::logical_plan::INFERRED_CONFIDENCE_METADATA_KEY;
 use datafusion_expr::{
     DescribeTable, DmlStatement, Extension, FetchType, Filter, JoinType, RecursiveQuery,
     SkipType, SortExpr, StringifiedPlan, WindowFrame, WindowFrameBound, WriteOp,
@@ -177,11 +184,40 @@ impl PhysicalPlanner for DefaultPhysicalPlanner {
         match self.handle_explain(logical_plan, session_state).await? {
             Some(plan) => Ok(plan),
             None => {
+                if let LogicalPlan::Dml(DmlStatement { table_name, .. }) = logical_plan {
+                    let table = session_state.resolve_table_ref(table_name.clone());
+                    session_state.result_cache().invalidate_table(&table);
+                }
+
+                let max_bytes = session_state
+                    .config_options()
+                    .execution
+                    .result_cache_max_bytes;
+                let cache_key = if max_bytes > 0 {
+                    ResultCacheKey::try_new(logical_plan, session_state).await?
+                } else {
+                    None
+                };
+                if let Some(key) = &cache_key {
+                    if let Some(plan) = session_state.result_cache().get(key) {
+                        return Ok(plan);
+                    }
+                }
+
                 let plan = self
                     .create_initial_plan(logical_plan, session_state)
                     .await?;
-
-                self.optimize_physical_plan(plan, session_state, |_, _| {})
+                let plan = self.optimize_physical_plan(plan, session_state, |_, _| {})?;
+
+                Ok(match cache_key {
+                    Some(key) => Arc::new(ResultCacheExec::new_miss(
+                        plan,
+                        Arc::clone(session_state.result_cache()),
+                        key,
+                        max_bytes,
+                    )),
+                    None => plan,
+                })
             }
         }
     }
@@ -271,7 +307,7 @@ impl DefaultPhysicalPlanner {
     }
 
     /// Create a physical plan from a logical plan
-    async fn create_initial_plan(
+    pub(crate) async fn create_initial_plan(
         &self,
         logical_plan: &LogicalPlan,
         session_state: &SessionState,
@@ -450,9 +486,16 @@ impl DefaultPhysicalPlanner {
                 // doesn't know (nor should care) how the relation was
                 // referred to in the query
                 let filters = unnormalize_cols(filters.iter().cloned());
-                source
+                let (filters, reductions) =
+                    plan_semi_join_reductions(filters, self, session_state).await?;
+                let scan = source
                     .scan(session_state, projection.as_ref(), &filters, *fetch)
-                    .await?
+                    .await?;
+                if reductions.is_empty() {
+                    scan
+                } else {
+                    Arc::new(SemiJoinReductionExec::try_new(scan, reductions)?)
+                }
             }
             LogicalPlan::Values(Values { values, schema }) => {
                 let exec_schema = schema.as_ref().to_owned().into();
@@ -1097,7 +1140,12 @@ impl DefaultPhysicalPlanner {
                     && prefer_hash_join
                 {
                     let partition_mode = {
-                        if session_state.config().collect_statistics() {
+                        if session_state.config().collect_statistics()
+                            || session_state
+                                .config_options()
+                                .optimizer
+                                .enable_adaptive_join
+                        {
                             PartitionMode::Auto
                         } else {
                             PartitionMode::Partitioned
@@ -1688,6 +1736,8 @@ impl DefaultPhysicalPlanner {
             let mut stringified_plans = vec![];
 
             let config = &session_state.config_options().explain;
+            // when tracing, rule traces replace the intermediate plans
+            let trace = e.explain_format == ExplainFormat::Trace;
 
             if !config.physical_plan_only {
                 stringified_plans.clone_from(&e.stringified_plans);
@@ -1735,10 +1785,17 @@ impl DefaultPhysicalPlanner {
                             }
                         }
 
-                        let optimized_plan = self.optimize_physical_plan(
+                        let mut traced_plans = vec![];
+                        let mut tracer = |event: &OptimizerTraceEvent| {
+                            traced_plans.push(event.to_stringified());
+                        };
+                        let optimized_plan = self.optimize_physical_plan_internal(
                             input,
                             session_state,
                             |plan, optimizer| {
+                                if trace {
+                                    return;
+                                }
                                 let optimizer_name = optimizer.name().to_string();
                                 let plan_type = OptimizedPhysicalPlan { optimizer_name };
                                 stringified_plans.push(
@@ -1748,7 +1805,9 @@ impl DefaultPhysicalPlanner {
                                         .to_stringified(e.verbose, plan_type),
                                 );
                             },
+                            trace.then_some(&mut tracer as _),
                         );
+                        stringified_plans.extend(traced_plans);
                         match optimized_plan {
                             Ok(input) => {
                                 // This plan will includes statistics if show_statistics is on
@@ -1800,7 +1859,7 @@ impl DefaultPhysicalPlanner {
             Ok(Some(Arc::new(ExplainExec::new(
                 SchemaRef::new(e.schema.as_ref().to_owned().into()),
                 stringified_plans,
-                e.verbose,
+                e.verbose || trace,
             ))))
         } else if let LogicalPlan::Analyze(a) = logical_plan {
             let input = self.create_physical_plan(&a.input, session_state).await?;
@@ -1820,10 +1879,45 @@ impl DefaultPhysicalPlanner {
     /// Optimize a physical plan by applying each physical optimizer,
     /// calling observer(plan, optimizer after each one)
     pub fn optimize_physical_plan<F>(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        session_state: &SessionState,
+        observer: F,
+    ) -> Result<Arc<dyn ExecutionPlan>>
+    where
+        F: FnMut(&dyn ExecutionPlan, &dyn PhysicalOptimizerRule),
+    {
+        self.optimize_physical_plan_internal(plan, session_state, observer, None)
+    }
+
+    /// Optimize a physical plan like [`Self::optimize_physical_plan`],
+    /// additionally calling `tracer` with an [`OptimizerTraceEvent`]
+    /// describing the elapsed time and plan changes of each optimizer
+    pub fn optimize_physical_plan_with_trace<F, T>(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        session_state: &SessionState,
+        observer: F,
+        mut tracer: T,
+    ) -> Result<Arc<dyn ExecutionPlan>>
+    where
+        F: FnMut(&dyn ExecutionPlan, &dyn PhysicalOptimizerRule),
+        T: FnMut(&OptimizerTraceEvent),
+    {
+        self.optimize_physical_plan_internal(
+            plan,
+            session_state,
+            observer,
+            Some(&mut tracer),
+        )
+    }
+
+    fn optimize_physical_plan_internal<F>(
         &self,
         plan: Arc<dyn ExecutionPlan>,
         session_state: &SessionState,
         mut observer: F,
+        mut tracer: Option<&mut dyn FnMut(&OptimizerTraceEvent)>,
     ) -> Result<Arc<dyn ExecutionPlan>>
     where
         F: FnMut(&dyn ExecutionPlan, &dyn PhysicalOptimizerRule),
@@ -1838,14 +1932,22 @@ impl DefaultPhysicalPlanner {
             displayable(plan.as_ref()).indent(true)
         );
 
+        let trace_observer = session_state.optimizer_trace_observer();
+        let tracing = tracer.is_some() || trace_observer.is_some();
+        let render =
+            |plan: &dyn ExecutionPlan| displayable(plan).indent(false).to_string();
+
         let mut new_plan = plan;
+        let mut plan_text = tracing.then(|| render(new_plan.as_ref()));
         for optimizer in optimizers {
             let before_schema = new_plan.schema();
+            let start = Instant::now();
             new_plan = optimizer
                 .optimize(new_plan, session_state.config_options())
                 .map_err(|e| {
                     DataFusionError::Context(optimizer.name().to_string(), Box::new(e))
                 })?;
+            let elapsed = start.elapsed();
             if optimizer.schema_check() && new_plan.schema() != before_schema {
                 let e = DataFusionError::Internal(format!(
                     "PhysicalOptimizer rule '{}' failed, due to generate a different schema, original schema: {:?}, new schema: {:?}",
@@ -1863,7 +1965,25 @@ impl DefaultPhysicalPlanner {
                 optimizer.name(),
                 displayable(new_plan.as_ref()).indent(false)
             );
-            observer(new_plan.as_ref(), optimizer.as_ref())
+            observer(new_plan.as_ref(), optimizer.as_ref());
+            if let Some(before) = plan_text.take() {
+                let after = render(new_plan.as_ref());
+                let event = OptimizerTraceEvent::new(
+                    OptimizerPhase::Physical,
+                    optimizer.name(),
+                    0,
+                    elapsed,
+                    &before,
+                    &after,
+                );
+                if let Some(tracer) = tracer.as_mut() {
+                    tracer(&event);
+                }
+                if let Some(trace_observer) = trace_observer {
+                    trace_observer(&event);
+                }
+                plan_text = Some(after);
+            }
         }
         debug!(
             "Optimized physical plan:\n{}\n",
@@ -1882,6 +2002,7 @@ impl DefaultPhysicalPlanner {
         let mut column_names = StringBuilder::new();
         let mut data_types = StringBuilder::new();
         let mut is_nullables = StringBuilder::new();
+        let mut confidences = Float64Builder::new();
         for field in table_schema.fields() {
             column_names.append_value(field.name());
 
@@ -1892,16 +2013,24 @@ impl DefaultPhysicalPlanner {
             // "YES if the column is possibly nullable, NO if it is known not nullable. "
             let nullable_str = if field.is_nullable() { "YES" } else { "NO" };
             is_nullables.append_value(nullable_str);
+
+            // The fraction of the input the type was inferred from, if sampled
+            let confidence = field
+                .metadata()
+                .get(INFERRED_CONFIDENCE_METADATA_KEY)
+                .and_then(|confidence| confidence.parse().ok());
+            confidences.append_option(confidence);
         }
 
-        let record_batch = RecordBatch::try_new(
-            output_schema,
-            vec![
-                Arc::new(column_names.finish()),
-                Arc::new(data_types.finish()),
-                Arc::new(is_nullables.finish()),
-            ],
-        )?;
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(column_names.finish()),
+            Arc::new(data_types.finish()),
+            Arc::new(is_nullables.finish()),
+        ];
+        if output_schema.fields().len() > columns.len() {
+            columns.push(Arc::new(confidences.finish()));
+        }
+        let record_batch = RecordBatch::try_new(output_schema, columns)?;
 
         let schema = record_batch.schema();
         let partitions = vec![vec![record_batch]];
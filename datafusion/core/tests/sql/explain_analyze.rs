@@ -833,3 +833,136 @@ async fn csv_explain_analyze_with_statistics() {
         ", statistics=[Rows=Absent, Bytes=Absent, [(Col[0]:)]]"
     );
 }
+
+#[tokio::test]
+async fn explain_analyze_adaptive_join() {
+    let config = SessionConfig::new()
+        .with_target_partitions(2)
+        .set_bool("datafusion.optimizer.enable_adaptive_join", true)
+        .set_usize(
+            "datafusion.optimizer.hash_join_single_partition_threshold_rows",
+            100,
+        );
+    let ctx = SessionContext::new_with_config(config);
+    for sql in [
+        "CREATE TABLE small AS SELECT unnest(range(1, 11)) AS s",
+        "CREATE TABLE big AS SELECT unnest(range(1, 1001)) AS b",
+        "CREATE TABLE other AS SELECT unnest(range(1, 501)) AS o",
+    ] {
+        ctx.sql(sql).await.unwrap().collect().await.unwrap();
+    }
+
+    // the small side is collected, swapping the inputs if needed
+    let sql = "EXPLAIN ANALYZE SELECT count(*) FROM big JOIN small ON b = s";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let formatted = arrow::util::pretty::pretty_format_batches(&actual)
+        .unwrap()
+        .to_string();
+    let join =
+        "AdaptiveHashJoinExec: join_type=Inner, on=[(b@0, s@0)], strategy=CollectRight";
+    assert_metrics!(&formatted, join, "metrics=[output_rows=10,");
+    assert_metrics!(&formatted, join, "left_buffered_rows=1000,");
+    assert_metrics!(&formatted, join, "right_buffered_rows=10,");
+
+    // both sides are partitioned when none of them is small
+    let sql = "EXPLAIN ANALYZE SELECT count(*) FROM big JOIN other ON b = o";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let formatted = arrow::util::pretty::pretty_format_batches(&actual)
+        .unwrap()
+        .to_string();
+    assert_metrics!(
+        &formatted,
+        "AdaptiveHashJoinExec: join_type=Inner, on=[(b@0, o@0)], strategy=Partitioned",
+        "metrics=[output_rows=500,"
+    );
+}
+
+#[tokio::test]
+async fn explain_format_trace() {
+    let ctx = SessionContext::new();
+    ctx.sql("CREATE TABLE t AS VALUES (1, 2), (3, 4)")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    let sql = "EXPLAIN (FORMAT TRACE) SELECT column1 FROM t WHERE column2 > 2";
+    let actual = execute(&ctx, sql).await;
+    let plans = |plan_type: &str| {
+        actual
+            .iter()
+            .filter(|row| row[0] == plan_type)
+            .map(|row| row[1].as_str())
+            .collect::<Vec<_>>()
+    };
+    // splits the trace header, which contains the elapsed time, from the diff
+    let split = |trace: &str| {
+        let (header, diff) = trace.split_once('\n').unwrap_or((trace, ""));
+        let (status, _elapsed) = header.split_once(" in ").unwrap();
+        (status.to_string(), diff.to_string())
+    };
+
+    // one trace per optimizer pass
+    let traces = plans("logical_plan after optimize_projections");
+    assert_eq!(traces.len(), 2);
+    assert_eq!(
+        split(traces[0]),
+        (
+            "pass 0: changed".to_string(),
+            "--- before\n+++ after\n\
+             @@ -1,3 +1,3 @@\n \
+             Projection: t.column1\n\
+             \x20  Filter: t.column2 > Int64(2)\n\
+             -    TableScan: t\n\
+             +    TableScan: t projection=[column1, column2]"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        split(traces[1]),
+        ("pass 1: unchanged".to_string(), "".to_string())
+    );
+    let traces = plans("logical_plan after push_down_filter");
+    assert_eq!(split(traces[0]).0, "pass 0: unchanged");
+
+    // the rule first adds and finally removes the OutputRequirementExec
+    let traces = plans("physical_plan after OutputRequirements");
+    assert_eq!(traces.len(), 2);
+    assert_eq!(split(traces[1]).0, "pass 0: changed");
+    assert_eq!(
+        split(traces[0]),
+        (
+            "pass 0: changed".to_string(),
+            "--- before\n+++ after\n\
+             @@ -1,3 +1,4 @@\n\
+             -ProjectionExec: expr=[column1@0 as column1]\n\
+             -  FilterExec: column2@1 > 2\n\
+             -    MemoryExec: partitions=1, partition_sizes=[1]\n\
+             +OutputRequirementExec\n\
+             +  ProjectionExec: expr=[column1@0 as column1]\n\
+             +    FilterExec: column2@1 > 2\n\
+             +      MemoryExec: partitions=1, partition_sizes=[1]"
+                .to_string()
+        )
+    );
+
+    // the start and end of each phase are still shown, but no verbose plans
+    for plan_type in [
+        "initial_logical_plan",
+        "logical_plan",
+        "initial_physical_plan",
+        "physical_plan",
+    ] {
+        assert_eq!(plans(plan_type).len(), 1, "{plan_type}");
+    }
+    assert!(plans("logical_plan after type_coercion").is_empty());
+    assert!(plans("physical_plan_with_stats").is_empty());
+
+    let sql = "EXPLAIN ANALYZE (FORMAT TRACE) SELECT column1 FROM t";
+    let err = ctx.sql(sql).await.unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "This feature is not implemented: EXPLAIN ANALYZE does not support FORMAT TRACE"
+    );
+}
@@ -16,9 +16,11 @@
 // under the License.
 
 use crate::cache::CacheAccessor;
+use bytes::Bytes;
 use datafusion_common::{Result, Statistics};
 use object_store::path::Path;
 use object_store::ObjectMeta;
+use std::any::Any;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 
@@ -32,6 +34,53 @@ pub type FileStatisticsCache =
 pub type ListFilesCache =
     Arc<dyn CacheAccessor<Path, Arc<Vec<ObjectMeta>>, Extra = ObjectMeta>>;
 
+/// The cache of file metadata, such as the footers of parquet files.
+/// if set [`CacheManagerConfig::with_file_metadata_cache`]
+/// Will avoid reading and decoding the metadata of the same files repeatedly
+/// during the session lifetime.
+pub type FileMetadataCache =
+    Arc<dyn CacheAccessor<Path, Arc<dyn FileMetadata>, Extra = ObjectMeta>>;
+
+/// Metadata of a file that can be stored in a [`FileMetadataCache`].
+///
+/// The concrete type depends on the file format, for example the parquet
+/// reader stores the decoded footer including its page indexes.
+pub trait FileMetadata: Any + Send + Sync {
+    /// Returns the metadata as [`Any`] so that it can be downcast to its
+    /// concrete type.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns the memory used by the metadata, in bytes.
+    fn memory_size(&self) -> usize;
+}
+
+/// The cache of the column chunks of parquet files.
+/// if set [`CacheManagerConfig::with_column_chunk_cache`]
+/// Will avoid fetching the same column chunks from the object store repeatedly
+/// when the same files are scanned by several queries.
+///
+/// The cached bytes are the (compressed) column chunks as stored in the file,
+/// keyed by [`ColumnChunkKey`].
+pub type ColumnChunkCache = Arc<dyn CacheAccessor<ColumnChunkKey, Bytes, Extra = ()>>;
+
+/// Identifies a column chunk in a specific version of a file.
+///
+/// The entity tag and size of the file are part of the key, so that entries of
+/// a file which has been overwritten are not returned.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ColumnChunkKey {
+    /// The path of the file
+    pub location: Path,
+    /// The unique identifier of the file version, if known
+    pub e_tag: Option<String>,
+    /// The size of the file in bytes
+    pub size: usize,
+    /// The index of the row group in the file
+    pub row_group: usize,
+    /// The index of the column in the row group
+    pub column: usize,
+}
+
 impl Debug for dyn CacheAccessor<Path, Arc<Statistics>, Extra = ObjectMeta> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "Cache name: {} with length: {}", self.name(), self.len())
@@ -44,10 +93,24 @@ impl Debug for dyn CacheAccessor<Path, Arc<Vec<ObjectMeta>>, Extra = ObjectMeta>
     }
 }
 
+impl Debug for dyn CacheAccessor<Path, Arc<dyn FileMetadata>, Extra = ObjectMeta> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cache name: {} with length: {}", self.name(), self.len())
+    }
+}
+
+impl Debug for dyn CacheAccessor<ColumnChunkKey, Bytes, Extra = ()> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cache name: {} with length: {}", self.name(), self.len())
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct CacheManager {
     file_statistic_cache: Option<FileStatisticsCache>,
     list_files_cache: Option<ListFilesCache>,
+    file_metadata_cache: Option<FileMetadataCache>,
+    column_chunk_cache: Option<ColumnChunkCache>,
 }
 
 impl CacheManager {
@@ -59,6 +122,12 @@ impl CacheManager {
         if let Some(lc) = &config.list_files_cache {
             manager.list_files_cache = Some(Arc::clone(lc))
         }
+        if let Some(mc) = &config.file_metadata_cache {
+            manager.file_metadata_cache = Some(Arc::clone(mc))
+        }
+        if let Some(cc) = &config.column_chunk_cache {
+            manager.column_chunk_cache = Some(Arc::clone(cc))
+        }
         Ok(Arc::new(manager))
     }
 
@@ -71,6 +140,16 @@ impl CacheManager {
     pub fn get_list_files_cache(&self) -> Option<ListFilesCache> {
         self.list_files_cache.clone()
     }
+
+    /// Get the cache of file metadata.
+    pub fn get_file_metadata_cache(&self) -> Option<FileMetadataCache> {
+        self.file_metadata_cache.clone()
+    }
+
+    /// Get the cache of parquet column chunks.
+    pub fn get_column_chunk_cache(&self) -> Option<ColumnChunkCache> {
+        self.column_chunk_cache.clone()
+    }
 }
 
 #[derive(Clone, Default)]
@@ -86,6 +165,22 @@ pub struct CacheManagerConfig {
     /// location.  
     /// Default is disable.
     pub list_files_cache: Option<ListFilesCache>,
+    /// Enable cache of the metadata of files, such as the footers and page
+    /// indexes of parquet files.
+    /// This setting avoids reading and decoding the metadata of the same files
+    /// repeatedly in same session, which may require several requests to the
+    /// object store.
+    /// See [`crate::cache::cache_unit::DefaultFileMetadataCache`] for a size
+    /// bounded implementation.
+    /// Default is disable.
+    pub file_metadata_cache: Option<FileMetadataCache>,
+    /// Enable cache of the column chunks read from parquet files.
+    /// This setting avoids fetching the same column chunks repeatedly from
+    /// the object store when the same files are scanned by several queries.
+    /// See [`crate::cache::cache_unit::DefaultColumnChunkCache`] for a memory
+    /// bounded implementation.
+    /// Default is disable.
+    pub column_chunk_cache: Option<ColumnChunkCache>,
 }
 
 impl CacheManagerConfig {
@@ -101,4 +196,14 @@ impl CacheManagerConfig {
         self.list_files_cache = cache;
         self
     }
+
+    pub fn with_file_metadata_cache(mut self, cache: Option<FileMetadataCache>) -> Self {
+        self.file_metadata_cache = cache;
+        self
+    }
+
+    pub fn with_column_chunk_cache(mut self, cache: Option<ColumnChunkCache>) -> Self {
+        self.column_chunk_cache = cache;
+        self
+    }
 }
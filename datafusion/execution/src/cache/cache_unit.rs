@@ -15,15 +15,21 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
 use std::sync::Arc;
 
+use crate::cache::cache_manager::{ColumnChunkKey, FileMetadata};
 use crate::cache::CacheAccessor;
+use crate::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation};
 
 use datafusion_common::Statistics;
 
+use bytes::Bytes;
 use dashmap::DashMap;
 use object_store::path::Path;
 use object_store::ObjectMeta;
+use parking_lot::Mutex;
 
 /// Collected statistics for files
 /// Cache is invalided when file size or last modification has changed
@@ -157,11 +163,317 @@ impl CacheAccessor<Path, Arc<Vec<ObjectMeta>>> for DefaultListFilesCache {
     }
 }
 
+/// Column chunks of parquet files, with least recently used eviction.
+///
+/// The total size of the cached chunks is bounded by the capacity of the cache
+/// and, if [`Self::with_memory_pool`] is used, by the memory available in the
+/// [`MemoryPool`]: least recently used chunks are evicted until the new chunk
+/// can be reserved. Chunks larger than the capacity are not cached.
+pub struct DefaultColumnChunkCache {
+    /// Maximum total size of the cached chunks, in bytes
+    capacity: usize,
+    state: Mutex<LruState<ColumnChunkKey, Bytes>>,
+}
+
+impl DefaultColumnChunkCache {
+    /// Create a cache holding at most `capacity` bytes of column chunks
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    /// Account the memory used by the cached chunks in `pool`
+    pub fn with_memory_pool(self, pool: &Arc<dyn MemoryPool>) -> Self {
+        {
+            let mut state = self.state.lock();
+            let mut reservation =
+                MemoryConsumer::new("DefaultColumnChunkCache").register(pool);
+            // chunks cached before the pool was set are not evicted
+            reservation.grow(state.size);
+            state.reservation = Some(reservation);
+        }
+        self
+    }
+
+    /// The maximum total size of the cached chunks, in bytes
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The total size of the cached chunks, in bytes
+    pub fn size(&self) -> usize {
+        self.state.lock().size
+    }
+}
+
+impl CacheAccessor<ColumnChunkKey, Bytes> for DefaultColumnChunkCache {
+    type Extra = ();
+
+    fn get(&self, k: &ColumnChunkKey) -> Option<Bytes> {
+        self.state.lock().get(k)
+    }
+
+    fn get_with_extra(&self, k: &ColumnChunkKey, _e: &Self::Extra) -> Option<Bytes> {
+        self.get(k)
+    }
+
+    /// Cache `value`, evicting least recently used chunks to make room for it.
+    /// `value` is not cached if there is not enough room.
+    fn put(&self, key: &ColumnChunkKey, value: Bytes) -> Option<Bytes> {
+        let size = value.len();
+        self.state.lock().insert(key, value, size, self.capacity)
+    }
+
+    fn put_with_extra(
+        &self,
+        key: &ColumnChunkKey,
+        value: Bytes,
+        _e: &Self::Extra,
+    ) -> Option<Bytes> {
+        self.put(key, value)
+    }
+
+    fn remove(&mut self, k: &ColumnChunkKey) -> Option<Bytes> {
+        self.state.lock().remove(k)
+    }
+
+    fn contains_key(&self, k: &ColumnChunkKey) -> bool {
+        self.state.lock().entries.contains_key(k)
+    }
+
+    fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    fn clear(&self) {
+        self.state.lock().clear()
+    }
+
+    fn name(&self) -> String {
+        "DefaultColumnChunkCache".to_string()
+    }
+}
+
+/// Metadata of files, such as parquet footers, with least recently used
+/// eviction.
+///
+/// The total [`FileMetadata::memory_size`] of the cached metadata is bounded by
+/// the capacity of the cache. Cached metadata is invalidated when the size or
+/// last modification time of the file changes.
+pub struct DefaultFileMetadataCache {
+    /// Maximum total size of the cached metadata, in bytes
+    capacity: usize,
+    state: Mutex<LruState<Path, CachedFileMetadata>>,
+}
+
+/// The metadata of a file, and the file it was read from
+type CachedFileMetadata = (ObjectMeta, Arc<dyn FileMetadata>);
+
+impl DefaultFileMetadataCache {
+    /// Create a cache holding at most `capacity` bytes of metadata
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    /// The maximum total size of the cached metadata, in bytes
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The total size of the cached metadata, in bytes
+    pub fn size(&self) -> usize {
+        self.state.lock().size
+    }
+}
+
+impl CacheAccessor<Path, Arc<dyn FileMetadata>> for DefaultFileMetadataCache {
+    type Extra = ObjectMeta;
+
+    fn get(&self, k: &Path) -> Option<Arc<dyn FileMetadata>> {
+        self.state.lock().get(k).map(|(_, metadata)| metadata)
+    }
+
+    /// Get the metadata of a file. Returns None if file has changed or not found.
+    fn get_with_extra(&self, k: &Path, e: &Self::Extra) -> Option<Arc<dyn FileMetadata>> {
+        let mut state = self.state.lock();
+        let (saved_meta, metadata) = state.get(k)?;
+        if saved_meta.size != e.size || saved_meta.last_modified != e.last_modified {
+            // file has changed
+            state.remove(k);
+            None
+        } else {
+            Some(metadata)
+        }
+    }
+
+    fn put(
+        &self,
+        _key: &Path,
+        _value: Arc<dyn FileMetadata>,
+    ) -> Option<Arc<dyn FileMetadata>> {
+        panic!("Put cache in DefaultFileMetadataCache without Extra not supported.")
+    }
+
+    /// Cache the metadata of a file, evicting least recently used metadata to
+    /// make room for it. `value` is not cached if there is not enough room.
+    fn put_with_extra(
+        &self,
+        key: &Path,
+        value: Arc<dyn FileMetadata>,
+        e: &Self::Extra,
+    ) -> Option<Arc<dyn FileMetadata>> {
+        let size = value.memory_size();
+        self.state
+            .lock()
+            .insert(key, (e.clone(), value), size, self.capacity)
+            .map(|(_, metadata)| metadata)
+    }
+
+    fn remove(&mut self, k: &Path) -> Option<Arc<dyn FileMetadata>> {
+        self.state.lock().remove(k).map(|(_, metadata)| metadata)
+    }
+
+    fn contains_key(&self, k: &Path) -> bool {
+        self.state.lock().entries.contains_key(k)
+    }
+
+    fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    fn clear(&self) {
+        self.state.lock().clear()
+    }
+
+    fn name(&self) -> String {
+        "DefaultFileMetadataCache".to_string()
+    }
+}
+
+/// Entries of a size bounded cache, with least recently used eviction
+struct LruState<K, V> {
+    /// The cached values, their size and the tick they were last accessed at
+    entries: HashMap<K, (V, usize, u64)>,
+    /// The keys of `entries` by last access, least recently used first
+    lru: BTreeMap<u64, K>,
+    /// Incremented on each access
+    tick: u64,
+    /// Total size of `entries`, in bytes
+    size: usize,
+    /// Memory reserved for `entries`, if the cache accounts its memory usage
+    reservation: Option<MemoryReservation>,
+}
+
+impl<K, V> Default for LruState<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            lru: BTreeMap::new(),
+            tick: 0,
+            size: 0,
+            reservation: None,
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> LruState<K, V> {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(&mut self, k: &K) -> Option<V> {
+        let tick = self.next_tick();
+        let (value, _, last_access) = self.entries.get_mut(k)?;
+        let key = self.lru.remove(last_access)?;
+        self.lru.insert(tick, key);
+        *last_access = tick;
+        Some(value.clone())
+    }
+
+    /// Insert `value` of `size` bytes, evicting least recently used entries to
+    /// make room for it. `value` is not inserted if there is not enough room.
+    fn insert(&mut self, key: &K, value: V, size: usize, capacity: usize) -> Option<V> {
+        let old = self.remove(key);
+        if size <= capacity && self.reserve(size, capacity) {
+            let tick = self.next_tick();
+            self.size += size;
+            self.lru.insert(tick, key.clone());
+            self.entries.insert(key.clone(), (value, size, tick));
+        }
+        old
+    }
+
+    fn remove(&mut self, k: &K) -> Option<V> {
+        let (value, size, last_access) = self.entries.remove(k)?;
+        self.lru.remove(&last_access);
+        self.size -= size;
+        if let Some(reservation) = &mut self.reservation {
+            reservation.shrink(size);
+        }
+        Some(value)
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.lru.clear();
+        self.size = 0;
+        if let Some(reservation) = &mut self.reservation {
+            reservation.free();
+        }
+    }
+
+    /// Evict the least recently used entry, returning false if it is empty
+    fn evict(&mut self) -> bool {
+        match self.lru.first_key_value() {
+            Some((_, key)) => {
+                let key = key.clone();
+                self.remove(&key);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Make room for `size` bytes, returning false if it is not possible
+    fn reserve(&mut self, size: usize, capacity: usize) -> bool {
+        while self.size + size > capacity {
+            if !self.evict() {
+                return false;
+            }
+        }
+        while let Some(reservation) = &mut self.reservation {
+            if reservation.try_grow(size).is_ok() {
+                break;
+            }
+            if !self.evict() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::cache::cache_unit::{DefaultFileStatisticsCache, DefaultListFilesCache};
+    use std::sync::Arc;
+
+    use std::any::Any;
+
+    use crate::cache::cache_manager::{ColumnChunkKey, FileMetadata};
+    use crate::cache::cache_unit::{
+        DefaultColumnChunkCache, DefaultFileMetadataCache, DefaultFileStatisticsCache,
+        DefaultListFilesCache,
+    };
     use crate::cache::CacheAccessor;
+    use crate::memory_pool::{GreedyMemoryPool, MemoryPool};
     use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use bytes::Bytes;
     use chrono::DateTime;
     use datafusion_common::Statistics;
     use object_store::path::Path;
@@ -232,4 +544,122 @@ mod tests {
             meta.clone()
         );
     }
+
+    fn chunk_key(row_group: usize) -> ColumnChunkKey {
+        ColumnChunkKey {
+            location: Path::from("test"),
+            e_tag: Some("v1".to_string()),
+            size: 1024,
+            row_group,
+            column: 0,
+        }
+    }
+
+    #[test]
+    fn test_column_chunk_cache() {
+        let cache = DefaultColumnChunkCache::new(100);
+        assert!(cache.get(&chunk_key(0)).is_none());
+
+        cache.put(&chunk_key(0), Bytes::from(vec![0; 40]));
+        cache.put(&chunk_key(1), Bytes::from(vec![1; 40]));
+        assert_eq!(cache.size(), 80);
+
+        // different file version
+        let mut key = chunk_key(0);
+        key.e_tag = Some("v2".to_string());
+        assert!(cache.get(&key).is_none());
+
+        // chunk 0 is now the most recently used, chunk 1 is evicted
+        assert_eq!(cache.get(&chunk_key(0)).unwrap(), vec![0; 40]);
+        cache.put(&chunk_key(2), Bytes::from(vec![2; 40]));
+        assert!(cache.contains_key(&chunk_key(0)));
+        assert!(!cache.contains_key(&chunk_key(1)));
+        assert!(cache.contains_key(&chunk_key(2)));
+        assert_eq!(cache.size(), 80);
+
+        // too large to be cached
+        cache.put(&chunk_key(3), Bytes::from(vec![3; 101]));
+        assert!(!cache.contains_key(&chunk_key(3)));
+        assert_eq!(cache.len(), 2);
+
+        cache.clear();
+        assert_eq!(cache.size(), 0);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_column_chunk_cache_memory_pool() {
+        let pool: Arc<dyn MemoryPool> = Arc::new(GreedyMemoryPool::new(50));
+        let cache = DefaultColumnChunkCache::new(100).with_memory_pool(&pool);
+
+        cache.put(&chunk_key(0), Bytes::from(vec![0; 30]));
+        assert_eq!(pool.reserved(), 30);
+
+        // the pool only has room for one chunk
+        cache.put(&chunk_key(1), Bytes::from(vec![1; 30]));
+        assert!(!cache.contains_key(&chunk_key(0)));
+        assert!(cache.contains_key(&chunk_key(1)));
+        assert_eq!(pool.reserved(), 30);
+
+        // the pool is too small
+        cache.put(&chunk_key(2), Bytes::from(vec![2; 60]));
+        assert!(cache.is_empty());
+        assert_eq!(pool.reserved(), 0);
+    }
+
+    struct TestMetadata(usize);
+
+    impl FileMetadata for TestMetadata {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn memory_size(&self) -> usize {
+            self.0
+        }
+    }
+
+    fn object_meta(location: &str) -> ObjectMeta {
+        ObjectMeta {
+            location: Path::from(location),
+            last_modified: DateTime::parse_from_rfc3339("2022-09-27T22:36:00+02:00")
+                .unwrap()
+                .into(),
+            size: 1024,
+            e_tag: None,
+            version: None,
+        }
+    }
+
+    #[test]
+    fn test_file_metadata_cache() {
+        let meta = object_meta("test");
+        let cache = DefaultFileMetadataCache::new(100);
+        assert!(cache.get_with_extra(&meta.location, &meta).is_none());
+
+        cache.put_with_extra(&meta.location, Arc::new(TestMetadata(40)), &meta);
+        let cached = cache.get_with_extra(&meta.location, &meta).unwrap();
+        assert_eq!(
+            cached.as_any().downcast_ref::<TestMetadata>().unwrap().0,
+            40
+        );
+
+        // file size changed
+        let mut meta2 = meta.clone();
+        meta2.size = 2048;
+        assert!(cache.get_with_extra(&meta2.location, &meta2).is_none());
+        assert!(cache.is_empty());
+
+        // least recently used metadata is evicted
+        let meta2 = object_meta("test2");
+        let meta3 = object_meta("test3");
+        cache.put_with_extra(&meta.location, Arc::new(TestMetadata(40)), &meta);
+        cache.put_with_extra(&meta2.location, Arc::new(TestMetadata(40)), &meta2);
+        assert!(cache.get_with_extra(&meta.location, &meta).is_some());
+        cache.put_with_extra(&meta3.location, Arc::new(TestMetadata(40)), &meta3);
+        assert!(cache.contains_key(&meta.location));
+        assert!(!cache.contains_key(&meta2.location));
+        assert!(cache.contains_key(&meta3.location));
+        assert_eq!(cache.size(), 80);
+    }
 }
@@ -48,7 +48,7 @@ use super::dml::InsertOp;
 use super::plan::ColumnUnnestList;
 use arrow::compute::can_cast_types;
 use arrow::datatypes::{DataType, Field, Fields, Schema, SchemaRef};
-use datafusion_common::display::ToStringifiedPlan;
+use datafusion_common::display::{ExplainFormat, ToStringifiedPlan};
 use datafusion_common::file_options::file_type::FileType;
 use datafusion_common::{
     exec_err, get_target_functional_dependencies, internal_err, not_impl_err,
@@ -1116,6 +1116,7 @@ impl LogicalPlanBuilder {
 
             Ok(Self::new(LogicalPlan::Explain(Explain {
                 verbose,
+                explain_format: ExplainFormat::Indent,
                 plan: self.plan,
                 stringified_plans,
                 schema,
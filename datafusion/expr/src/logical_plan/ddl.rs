@@ -54,6 +54,8 @@ pub enum DdlStatement {
     CreateFunction(CreateFunction),
     /// Drop function statement
     DropFunction(DropFunction),
+    /// Computes the statistics of a table.
+    AnalyzeTable(AnalyzeTable),
 }
 
 impl DdlStatement {
@@ -75,6 +77,7 @@ impl DdlStatement {
             DdlStatement::DropCatalogSchema(DropCatalogSchema { schema, .. }) => schema,
             DdlStatement::CreateFunction(CreateFunction { schema, .. }) => schema,
             DdlStatement::DropFunction(DropFunction { schema, .. }) => schema,
+            DdlStatement::AnalyzeTable(AnalyzeTable { schema, .. }) => schema,
         }
     }
 
@@ -93,6 +96,7 @@ impl DdlStatement {
             DdlStatement::DropCatalogSchema(_) => "DropCatalogSchema",
             DdlStatement::CreateFunction(_) => "CreateFunction",
             DdlStatement::DropFunction(_) => "DropFunction",
+            DdlStatement::AnalyzeTable(_) => "AnalyzeTable",
         }
     }
 
@@ -112,6 +116,7 @@ impl DdlStatement {
             DdlStatement::DropCatalogSchema(_) => vec![],
             DdlStatement::CreateFunction(_) => vec![],
             DdlStatement::DropFunction(_) => vec![],
+            DdlStatement::AnalyzeTable(_) => vec![],
         }
     }
 
@@ -180,6 +185,16 @@ impl DdlStatement {
                     DdlStatement::DropFunction(DropFunction { name, .. }) => {
                         write!(f, "CreateFunction: name {name:?}")
                     }
+                    DdlStatement::AnalyzeTable(AnalyzeTable {
+                        name, columns, ..
+                    }) => match columns {
+                        Some(columns) => write!(
+                            f,
+                            "AnalyzeTable: {name:?} columns:=[{}]",
+                            columns.join(", ")
+                        ),
+                        None => write!(f, "AnalyzeTable: {name:?}"),
+                    },
                 }
             }
         }
@@ -403,6 +418,29 @@ impl PartialOrd for DropView {
     }
 }
 
+/// Computes the statistics of a table, such as the histograms and most common
+/// values of its columns, and stores them with the table, for
+/// `ANALYZE TABLE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnalyzeTable {
+    /// The table name
+    pub name: TableReference,
+    /// The columns to analyze, or `None` for all the columns of the table
+    pub columns: Option<Vec<String>>,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+// Manual implementation needed because of `schema` field. Comparison excludes this field.
+impl PartialOrd for AnalyzeTable {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.name.partial_cmp(&other.name) {
+            Some(Ordering::Equal) => self.columns.partial_cmp(&other.columns),
+            cmp => cmp,
+        }
+    }
+}
+
 /// Drops a schema
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DropCatalogSchema {
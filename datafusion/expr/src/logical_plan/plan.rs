@@ -57,9 +57,17 @@ use indexmap::IndexSet;
 // backwards compatibility
 use crate::display::PgJsonVisitor;
 use crate::tree_node::replace_sort_expressions;
-pub use datafusion_common::display::{PlanType, StringifiedPlan, ToStringifiedPlan};
+pub use datafusion_common::display::{
+    ExplainFormat, PlanType, StringifiedPlan, ToStringifiedPlan,
+};
 pub use datafusion_common::{JoinConstraint, JoinType};
 
+/// Field metadata key of the estimated fraction of the input a table schema
+/// was inferred from, if it was inferred from a sample of its files.
+///
+/// Reported in the `inferred_confidence` column of `DESCRIBE`.
+pub const INFERRED_CONFIDENCE_METADATA_KEY: &str = "datafusion.inferred_confidence";
+
 /// A `LogicalPlan` is a node in a tree of relational operators (such as
 /// Projection or Filter).
 ///
@@ -366,6 +374,18 @@ impl LogicalPlan {
         ])
     }
 
+    /// Returns the output schema for `DESCRIBE` plans of tables whose schema
+    /// was inferred from a sample, see [`INFERRED_CONFIDENCE_METADATA_KEY`]
+    pub fn describe_schema_with_confidence() -> Schema {
+        let mut fields = Self::describe_schema().fields().to_vec();
+        fields.push(Arc::new(Field::new(
+            "inferred_confidence",
+            DataType::Float64,
+            true,
+        )));
+        Schema::new(fields)
+    }
+
     /// Returns all expressions (non-recursively) evaluated by the current
     /// logical plan node. This does not include expressions in any children.
     ///
@@ -1053,6 +1073,7 @@ impl LogicalPlan {
                 let input = self.only_input(inputs)?;
                 Ok(LogicalPlan::Explain(Explain {
                     verbose: e.verbose,
+                    explain_format: e.explain_format,
                     plan: Arc::new(input),
                     stringified_plans: e.stringified_plans.clone(),
                     schema: Arc::clone(&e.schema),
@@ -2640,6 +2661,8 @@ impl PartialOrd for DescribeTable {
 pub struct Explain {
     /// Should extra (detailed, intermediate plans) be included?
     pub verbose: bool,
+    /// How the plans produced by each optimizer rule are displayed
+    pub explain_format: ExplainFormat,
     /// The logical plan that is being EXPLAIN'd
     pub plan: Arc<LogicalPlan>,
     /// Represent the various stages plans have gone through
@@ -2657,6 +2680,8 @@ impl PartialOrd for Explain {
         struct ComparableExplain<'a> {
             /// Should extra (detailed, intermediate plans) be included?
             pub verbose: &'a bool,
+            /// How the plans produced by each optimizer rule are displayed
+            pub explain_format: &'a ExplainFormat,
             /// The logical plan that is being EXPLAIN'd
             pub plan: &'a Arc<LogicalPlan>,
             /// Represent the various stages plans have gone through
@@ -2666,12 +2691,14 @@ impl PartialOrd for Explain {
         }
         let comparable_self = ComparableExplain {
             verbose: &self.verbose,
+            explain_format: &self.explain_format,
             plan: &self.plan,
             stringified_plans: &self.stringified_plans,
             logical_optimization_succeeded: &self.logical_optimization_succeeded,
         };
         let comparable_other = ComparableExplain {
             verbose: &other.verbose,
+            explain_format: &other.explain_format,
             plan: &other.plan,
             stringified_plans: &other.stringified_plans,
             logical_optimization_succeeded: &other.logical_optimization_succeeded,
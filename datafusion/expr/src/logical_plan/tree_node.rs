@@ -207,6 +207,7 @@ impl TreeNode for LogicalPlan {
             .update_data(LogicalPlan::Distinct),
             LogicalPlan::Explain(Explain {
                 verbose,
+                explain_format,
                 plan,
                 stringified_plans,
                 schema,
@@ -214,6 +215,7 @@ impl TreeNode for LogicalPlan {
             }) => rewrite_arc(plan, f)?.update_data(|plan| {
                 LogicalPlan::Explain(Explain {
                     verbose,
+                    explain_format,
                     plan,
                     stringified_plans,
                     schema,
@@ -306,7 +308,8 @@ impl TreeNode for LogicalPlan {
                     | DdlStatement::DropView(_)
                     | DdlStatement::DropCatalogSchema(_)
                     | DdlStatement::CreateFunction(_)
-                    | DdlStatement::DropFunction(_) => Transformed::no(ddl),
+                    | DdlStatement::DropFunction(_)
+                    | DdlStatement::AnalyzeTable(_) => Transformed::no(ddl),
                 }
                 .update_data(LogicalPlan::Ddl)
             }
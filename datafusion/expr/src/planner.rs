@@ -26,7 +26,9 @@ use datafusion_common::{
     Result, TableReference,
 };
 
-use crate::{AggregateUDF, Expr, GetFieldAccess, ScalarUDF, TableSource, WindowUDF};
+use crate::{
+    AggregateUDF, Expr, GetFieldAccess, LogicalPlan, ScalarUDF, TableSource, WindowUDF,
+};
 
 /// Provides the `SQL` query planner  meta-data about tables and
 /// functions referenced in SQL statements, without a direct dependency on other
@@ -61,6 +63,18 @@ pub trait ContextProvider {
         not_impl_err!("Recursive CTE is not implemented")
     }
 
+    /// This provides a table computing the results of a CTE once, shared by all
+    /// the references to the CTE in the query.
+    /// Like [`Self::create_cte_work_table`], the table is created by the
+    /// [`ContextProvider`] as it needs execution-related types.
+    fn create_materialized_cte(
+        &self,
+        _name: &str,
+        _plan: LogicalPlan,
+    ) -> Result<Arc<dyn TableSource>> {
+        not_impl_err!("Materialized CTE is not implemented")
+    }
+
     /// Getter for expr planners
     fn get_expr_planners(&self) -> &[Arc<dyn ExprPlanner>] {
         &[]
@@ -798,6 +798,8 @@ pub mod scalar_doc_sections {
             DOC_SECTION_STRUCT,
             DOC_SECTION_MAP,
             DOC_SECTION_HASHING,
+            DOC_SECTION_BITMAP,
+            DOC_SECTION_SKETCH,
             DOC_SECTION_OTHER,
         ]
     }
@@ -867,6 +869,18 @@ The following regular expression functions are supported:"#,
         description: None,
     };
 
+    pub const DOC_SECTION_BITMAP: DocSection = DocSection {
+        include: true,
+        label: "Bitmap Functions",
+        description: None,
+    };
+
+    pub const DOC_SECTION_SKETCH: DocSection = DocSection {
+        include: true,
+        label: "Sketch Functions",
+        description: None,
+    };
+
     pub const DOC_SECTION_OTHER: DocSection = DocSection {
         include: true,
         label: "Other Functions",
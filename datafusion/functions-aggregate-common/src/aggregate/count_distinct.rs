@@ -15,9 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
+mod bitmap;
 mod bytes;
 mod native;
 
+pub use bitmap::BitmapDistinctCountAccumulator;
 pub use bytes::BytesDistinctCountAccumulator;
 pub use bytes::BytesViewDistinctCountAccumulator;
 pub use native::FloatDistinctCountAccumulator;
@@ -0,0 +1,198 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Specialized implementation of `COUNT DISTINCT` for integer arrays of at
+//! most 32 bits, such as [`Int32Array`], backed by a roaring bitmap
+//!
+//! [`Int32Array`]: arrow::array::Int32Array
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use arrow::array::types::ArrowPrimitiveType;
+use arrow::array::ArrayRef;
+use arrow::array::PrimitiveArray;
+use arrow::datatypes::{ArrowNativeType, DataType};
+use roaring::RoaringBitmap;
+
+use datafusion_common::cast::{as_list_array, as_primitive_array};
+use datafusion_common::utils::array_into_list_array_nullable;
+use datafusion_common::ScalarValue;
+use datafusion_expr_common::accumulator::Accumulator;
+
+/// Distinct count accumulator storing the values in a [`RoaringBitmap`],
+/// which uses much less memory than a hash set for large numbers of distinct
+/// (and especially dense, such as identifiers) values.
+///
+/// The state is the same list of distinct values as the one of
+/// [`PrimitiveDistinctCountAccumulator`], so both accumulators can be used
+/// interchangeably.
+///
+/// Values are mapped to the `u32` with the same bit pattern (sign extended
+/// for narrower types), so this must only be used for integer types of at
+/// most 32 bits.
+///
+/// [`PrimitiveDistinctCountAccumulator`]: super::PrimitiveDistinctCountAccumulator
+#[derive(Debug)]
+pub struct BitmapDistinctCountAccumulator<T>
+where
+    T: ArrowPrimitiveType + Send,
+{
+    values: RoaringBitmap,
+    data_type: DataType,
+    phantom: PhantomData<T::Native>,
+}
+
+impl<T> BitmapDistinctCountAccumulator<T>
+where
+    T: ArrowPrimitiveType + Send,
+{
+    pub fn new(data_type: &DataType) -> Self {
+        debug_assert!(
+            data_type.is_integer() && data_type.primitive_width() <= Some(4),
+            "unsupported bitmap count distinct type {data_type}"
+        );
+        Self {
+            values: RoaringBitmap::new(),
+            data_type: data_type.clone(),
+            phantom: PhantomData,
+        }
+    }
+
+    fn insert_all(&mut self, values: impl IntoIterator<Item = T::Native>) {
+        self.values
+            .extend(values.into_iter().map(|value| value.as_usize() as u32));
+    }
+}
+
+impl<T> Accumulator for BitmapDistinctCountAccumulator<T>
+where
+    T: ArrowPrimitiveType + Send + Debug,
+{
+    fn state(&mut self) -> datafusion_common::Result<Vec<ScalarValue>> {
+        // undo the mapping of `insert_all`, sign extending to restore
+        // negative values
+        let arr = Arc::new(
+            PrimitiveArray::<T>::from_iter_values(
+                self.values
+                    .iter()
+                    .map(|value| T::Native::usize_as(value as i32 as isize as usize)),
+            )
+            .with_data_type(self.data_type.clone()),
+        );
+        let list = Arc::new(array_into_list_array_nullable(arr));
+        Ok(vec![ScalarValue::List(list)])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> datafusion_common::Result<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let arr = as_primitive_array::<T>(&values[0])?;
+        self.insert_all(arr.iter().flatten());
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> datafusion_common::Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        assert_eq!(
+            states.len(),
+            1,
+            "count_distinct states must be single array"
+        );
+
+        let arr = as_list_array(&states[0])?;
+        arr.iter().try_for_each(|maybe_list| {
+            if let Some(list) = maybe_list {
+                let list = as_primitive_array::<T>(&list)?;
+                self.insert_all(list.values().iter().copied())
+            };
+            Ok(())
+        })
+    }
+
+    fn evaluate(&mut self) -> datafusion_common::Result<ScalarValue> {
+        Ok(ScalarValue::Int64(Some(self.values.len() as i64)))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.values.serialized_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int32Array, Int8Array, UInt32Array};
+    use arrow::datatypes::{Int32Type, Int8Type, UInt32Type};
+
+    fn roundtrip<T: ArrowPrimitiveType + Send + Debug>(
+        input: ArrayRef,
+    ) -> datafusion_common::Result<(ScalarValue, ScalarValue)> {
+        let mut acc = BitmapDistinctCountAccumulator::<T>::new(input.data_type());
+        acc.update_batch(&[input])?;
+        let state = acc.state()?.remove(0);
+
+        let mut merged = BitmapDistinctCountAccumulator::<T>::new(&T::DATA_TYPE);
+        merged.merge_batch(&[state.to_array()?])?;
+        Ok((merged.evaluate()?, merged.state()?.remove(0)))
+    }
+
+    #[test]
+    fn test_signed_values() -> datafusion_common::Result<()> {
+        let input: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(-1),
+            Some(i32::MIN),
+            None,
+            Some(i32::MAX),
+            Some(-1),
+            Some(0),
+        ]));
+        let (count, state) = roundtrip::<Int32Type>(input)?;
+        assert_eq!(count, ScalarValue::Int64(Some(4)));
+        // bitmaps iterate in unsigned order
+        let expected: ArrayRef =
+            Arc::new(Int32Array::from(vec![0, i32::MAX, i32::MIN, -1]));
+        assert_eq!(
+            state,
+            ScalarValue::List(Arc::new(array_into_list_array_nullable(expected)))
+        );
+
+        let input: ArrayRef = Arc::new(Int8Array::from(vec![-128, 127, -1, -128]));
+        let (count, state) = roundtrip::<Int8Type>(input)?;
+        assert_eq!(count, ScalarValue::Int64(Some(3)));
+        let expected: ArrayRef = Arc::new(Int8Array::from(vec![127, -128, -1]));
+        assert_eq!(
+            state,
+            ScalarValue::List(Arc::new(array_into_list_array_nullable(expected)))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsigned_values() -> datafusion_common::Result<()> {
+        let input: ArrayRef =
+            Arc::new(UInt32Array::from(vec![u32::MAX, 0, 7, u32::MAX, 7]));
+        let (count, _) = roundtrip::<UInt32Type>(input)?;
+        assert_eq!(count, ScalarValue::Int64(Some(3)));
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Serialization of the [`RoaringBitmap`]s exchanged by the bitmap functions
+//!
+//! Bitmaps are stored as binary values using the portable Roaring
+//! serialization format (<https://github.com/RoaringBitmap/RoaringFormatSpec>),
+//! so they can be persisted and read back by other Roaring implementations.
+
+use datafusion_common::{exec_datafusion_err, Result, ScalarValue};
+
+pub use roaring::RoaringBitmap;
+
+/// Serializes `bitmap` using the portable Roaring format
+pub fn bitmap_to_bytes(bitmap: &RoaringBitmap) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(bitmap.serialized_size());
+    bitmap
+        .serialize_into(&mut bytes)
+        .expect("writing to a Vec cannot fail");
+    bytes
+}
+
+/// Deserializes a bitmap previously serialized with [`bitmap_to_bytes`]
+pub fn bitmap_from_bytes(bytes: &[u8]) -> Result<RoaringBitmap> {
+    RoaringBitmap::deserialize_from(bytes)
+        .map_err(|e| exec_datafusion_err!("Invalid roaring bitmap: {e}"))
+}
+
+/// Returns `bitmap` serialized as a [`ScalarValue::Binary`]
+pub fn bitmap_to_scalar(bitmap: &RoaringBitmap) -> ScalarValue {
+    ScalarValue::Binary(Some(bitmap_to_bytes(bitmap)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() -> Result<()> {
+        let bitmap = RoaringBitmap::from_iter([0, 1, 7, 100_000, u32::MAX]);
+        let decoded = bitmap_from_bytes(&bitmap_to_bytes(&bitmap))?;
+        assert_eq!(decoded, bitmap);
+
+        let empty = RoaringBitmap::new();
+        assert_eq!(bitmap_from_bytes(&bitmap_to_bytes(&empty))?, empty);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_bytes() {
+        let err = bitmap_from_bytes(b"not a bitmap").unwrap_err();
+        assert!(err.to_string().contains("Invalid roaring bitmap"), "{err}");
+    }
+}
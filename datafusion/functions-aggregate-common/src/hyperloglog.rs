@@ -33,8 +33,26 @@
 //! conversion, largely to simplify the code.
 //!
 //! This module also borrows some code structure from [pdatastructs.rs](https://github.com/crepererum/pdatastructs.rs/blob/3997ed50f6b6871c9e53c4c5e0f48f431405fc63/src/hyperloglog.rs).
+//!
+//! # Serialized form
+//!
+//! [`HyperLogLog::to_bytes`] produces a stable binary representation that
+//! can be persisted (for example in a Parquet `BINARY` column) and merged
+//! later on with [`HyperLogLog::try_from_bytes`]:
+//!
+//! ```text
+//! ┌─────────┬───────────┬──────────────────────────────┐
+//! │ version │ precision │ registers (2^precision bytes) │
+//! │  (u8)   │   (u8)    │                              │
+//! └─────────┴───────────┴──────────────────────────────┘
+//! ```
+//!
+//! Registers only depend on the hash of the values added, which uses a fixed
+//! seed (see `SEED`), so sketches produced by different processes over
+//! values of the same type can be merged.
 
 use ahash::RandomState;
+use datafusion_common::{exec_err, internal_err, DataFusionError, Result, ScalarValue};
 use std::hash::Hash;
 use std::marker::PhantomData;
 
@@ -45,9 +63,13 @@ const HLL_Q: usize = 64_usize - HLL_P;
 const NUM_REGISTERS: usize = 1_usize << HLL_P;
 /// Mask to obtain index into the registers
 const HLL_P_MASK: u64 = (NUM_REGISTERS as u64) - 1;
+/// Version of the format produced by [`HyperLogLog::to_bytes`]
+const SERIALIZATION_VERSION: u8 = 1;
+/// Size of the header of the format produced by [`HyperLogLog::to_bytes`]
+const SERIALIZATION_HEADER_SIZE: usize = 2;
 
 #[derive(Clone, Debug)]
-pub(crate) struct HyperLogLog<T>
+pub struct HyperLogLog<T>
 where
     T: Hash + ?Sized,
 {
@@ -90,7 +112,7 @@ where
     /// Creates a HyperLogLog from already populated registers
     /// note that this method should not be invoked in untrusted environment
     /// because the internal structure of registers are not examined.
-    pub(crate) fn new_with_registers(registers: [u8; NUM_REGISTERS]) -> Self {
+    pub fn new_with_registers(registers: [u8; NUM_REGISTERS]) -> Self {
         Self {
             registers,
             phantom: PhantomData,
@@ -150,6 +172,81 @@ where
         z += m * hll_sigma(histogram[0] as f64 / m);
         (0.5 / 2_f64.ln() * m * m / z).round() as usize
     }
+
+    /// Returns the raw registers as a [`ScalarValue::Binary`], used as the
+    /// intermediate state of aggregations. The [`TryFrom<&ScalarValue>`]
+    /// implementation reverses this process.
+    pub fn to_scalar_state(&self) -> ScalarValue {
+        ScalarValue::Binary(Some(self.registers.to_vec()))
+    }
+
+    /// Serializes the registers into the stable binary format described in
+    /// the [module documentation](self).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(SERIALIZATION_HEADER_SIZE + NUM_REGISTERS);
+        bytes.push(SERIALIZATION_VERSION);
+        bytes.push(HLL_P as u8);
+        bytes.extend_from_slice(&self.registers);
+        bytes
+    }
+
+    /// Deserializes a [`HyperLogLog`] produced by [`Self::to_bytes`].
+    ///
+    /// Unlike [`Self::new_with_registers`] the input is validated, so this
+    /// method can be used on untrusted data.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < SERIALIZATION_HEADER_SIZE {
+            return exec_err!("Invalid HyperLogLog sketch: too short");
+        }
+        let (header, registers) = bytes.split_at(SERIALIZATION_HEADER_SIZE);
+        if header[0] != SERIALIZATION_VERSION {
+            return exec_err!(
+                "Unsupported HyperLogLog sketch version {}, expected {SERIALIZATION_VERSION}",
+                header[0]
+            );
+        }
+        if header[1] as usize != HLL_P {
+            return exec_err!(
+                "Unsupported HyperLogLog sketch precision {}, expected {HLL_P}",
+                header[1]
+            );
+        }
+        let registers: [u8; NUM_REGISTERS] = registers.try_into().map_err(|_| {
+            DataFusionError::Execution(format!(
+                "Invalid HyperLogLog sketch: expected {NUM_REGISTERS} registers, got {}",
+                registers.len()
+            ))
+        })?;
+        if registers.iter().any(|r| *r as usize > HLL_Q + 1) {
+            return exec_err!("Invalid HyperLogLog sketch: register out of range");
+        }
+        Ok(Self::new_with_registers(registers))
+    }
+}
+
+impl<T: Hash + ?Sized> TryFrom<&[u8]> for HyperLogLog<T> {
+    type Error = DataFusionError;
+    fn try_from(v: &[u8]) -> Result<HyperLogLog<T>> {
+        let arr: [u8; 16384] = v.try_into().map_err(|_| {
+            DataFusionError::Internal(
+                "Impossibly got invalid binary array from states".into(),
+            )
+        })?;
+        Ok(HyperLogLog::<T>::new_with_registers(arr))
+    }
+}
+
+impl<T: Hash + ?Sized> TryFrom<&ScalarValue> for HyperLogLog<T> {
+    type Error = DataFusionError;
+    fn try_from(v: &ScalarValue) -> Result<HyperLogLog<T>> {
+        if let ScalarValue::Binary(Some(slice)) = v {
+            slice.as_slice().try_into()
+        } else {
+            internal_err!(
+                "Impossibly got invalid scalar value while converting to HyperLogLog"
+            )
+        }
+    }
 }
 
 /// Helper function sigma as defined in
@@ -358,6 +455,42 @@ mod tests {
         compare_with_delta(hll.count(), 1000);
     }
 
+    #[test]
+    fn test_serialization_roundtrip() {
+        let mut hll = HyperLogLog::<String>::new();
+        hll.extend((0..1000).map(|i| i.to_string()));
+
+        let bytes = hll.to_bytes();
+        assert_eq!(bytes.len(), NUM_REGISTERS + 2);
+        let other = HyperLogLog::<String>::try_from_bytes(&bytes).unwrap();
+        assert_eq!(hll.count(), other.count());
+        assert_eq!(hll.as_ref(), other.as_ref());
+    }
+
+    #[test]
+    fn test_deserialization_errors() {
+        let bytes = HyperLogLog::<u64>::new().to_bytes();
+
+        let err = HyperLogLog::<u64>::try_from_bytes(&bytes[..1]).unwrap_err();
+        assert!(err.to_string().contains("too short"), "{err}");
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = 42;
+        let err = HyperLogLog::<u64>::try_from_bytes(&wrong_version).unwrap_err();
+        assert!(err.to_string().contains("version 42"), "{err}");
+
+        let err = HyperLogLog::<u64>::try_from_bytes(&bytes[..100]).unwrap_err();
+        assert!(
+            err.to_string().contains("expected 16384 registers"),
+            "{err}"
+        );
+
+        let mut invalid_register = bytes;
+        invalid_register[10] = 255;
+        let err = HyperLogLog::<u64>::try_from_bytes(&invalid_register).unwrap_err();
+        assert!(err.to_string().contains("out of range"), "{err}");
+    }
+
     #[test]
     fn test_repetition() {
         let mut hll = HyperLogLog::<u32>::new();
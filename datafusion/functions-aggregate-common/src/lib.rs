@@ -27,6 +27,8 @@
 
 pub mod accumulator;
 pub mod aggregate;
+pub mod bitmap;
+pub mod hyperloglog;
 pub mod merge_arrays;
 pub mod order;
 pub mod stats;
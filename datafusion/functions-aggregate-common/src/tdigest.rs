@@ -32,10 +32,18 @@ use arrow::datatypes::Float64Type;
 use datafusion_common::cast::as_primitive_array;
 use datafusion_common::Result;
 use datafusion_common::ScalarValue;
+use datafusion_common::{exec_datafusion_err, exec_err};
 use std::cmp::Ordering;
 
 pub const DEFAULT_MAX_SIZE: usize = 100;
 
+/// Version of the format produced by [`TDigest::to_bytes`]
+const SERIALIZATION_VERSION: u8 = 1;
+/// Size of the fixed part of the format produced by [`TDigest::to_bytes`]:
+/// the version followed by `max_size`, `sum`, `count`, `max`, `min` and the
+/// number of centroids
+const SERIALIZATION_HEADER_SIZE: usize = 1 + 6 * 8;
+
 // Cast a non-null [`ScalarValue::Float64`] to an [`f64`], or
 // panic.
 macro_rules! cast_scalar_f64 {
@@ -657,6 +665,111 @@ impl TDigest {
             centroids,
         }
     }
+
+    /// Serializes this [`TDigest`] into a stable, self describing binary
+    /// format that can be persisted and merged later on.
+    ///
+    /// All values are little endian:
+    ///
+    /// ```text
+    /// ┌─────────┬──────────┬─────┬───────┬─────┬─────┬─────────────┬───────────────────────┐
+    /// │ version │ max_size │ sum │ count │ max │ min │ n_centroids │ (mean, weight) * n    │
+    /// │  (u8)   │  (u64)   │(f64)│ (u64) │(f64)│(f64)│    (u64)    │ (f64, f64)            │
+    /// └─────────┴──────────┴─────┴───────┴─────┴─────┴─────────────┴───────────────────────┘
+    /// ```
+    ///
+    /// The [`TDigest::try_from_bytes()`] method reverses this process.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(SERIALIZATION_HEADER_SIZE + self.centroids.len() * 16);
+        bytes.push(SERIALIZATION_VERSION);
+        bytes.extend_from_slice(&(self.max_size as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.sum.to_le_bytes());
+        bytes.extend_from_slice(&self.count.to_le_bytes());
+        bytes.extend_from_slice(&self.max.to_le_bytes());
+        bytes.extend_from_slice(&self.min.to_le_bytes());
+        bytes.extend_from_slice(&(self.centroids.len() as u64).to_le_bytes());
+        for centroid in &self.centroids {
+            bytes.extend_from_slice(&centroid.mean.to_le_bytes());
+            bytes.extend_from_slice(&centroid.weight.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Deserializes a [`TDigest`] produced by [`Self::to_bytes()`].
+    ///
+    /// Unlike [`Self::from_scalar_state()`] the input is validated and an
+    /// error is returned for malformed data.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < SERIALIZATION_HEADER_SIZE {
+            return exec_err!("Invalid TDigest sketch: too short");
+        }
+        if bytes[0] != SERIALIZATION_VERSION {
+            return exec_err!(
+                "Unsupported TDigest sketch version {}, expected {SERIALIZATION_VERSION}",
+                bytes[0]
+            );
+        }
+
+        let mut words = bytes[1..]
+            .chunks_exact(8)
+            .map(|chunk| <[u8; 8]>::try_from(chunk).unwrap());
+        let mut next_word = || {
+            words
+                .next()
+                .ok_or_else(|| exec_datafusion_err!("Invalid TDigest sketch: too short"))
+        };
+
+        let max_size = u64::from_le_bytes(next_word()?) as usize;
+        let sum = f64::from_le_bytes(next_word()?);
+        let count = u64::from_le_bytes(next_word()?);
+        let max = f64::from_le_bytes(next_word()?);
+        let min = f64::from_le_bytes(next_word()?);
+        let n_centroids = u64::from_le_bytes(next_word()?) as usize;
+
+        let expected_len = n_centroids
+            .checked_mul(16)
+            .and_then(|len| len.checked_add(SERIALIZATION_HEADER_SIZE));
+        if expected_len != Some(bytes.len()) {
+            return exec_err!(
+                "Invalid TDigest sketch: expected {n_centroids} centroids in {} bytes",
+                bytes.len()
+            );
+        }
+        if max_size == 0 {
+            return exec_err!("Invalid TDigest sketch: max_size must be greater than 0");
+        }
+
+        let mut centroids = Vec::with_capacity(n_centroids);
+        for _ in 0..n_centroids {
+            let mean = f64::from_le_bytes(next_word()?);
+            let weight = f64::from_le_bytes(next_word()?);
+            if !(weight.is_finite() && weight > 0.0) {
+                return exec_err!(
+                    "Invalid TDigest sketch: invalid centroid weight {weight}"
+                );
+            }
+            centroids.push(Centroid::new(mean, weight));
+        }
+        if !centroids
+            .windows(2)
+            .all(|w| w[0].mean().total_cmp(&w[1].mean()).is_le())
+        {
+            return exec_err!("Invalid TDigest sketch: centroids are not sorted");
+        }
+        if count > 0 && max.total_cmp(&min).is_lt() {
+            return exec_err!("Invalid TDigest sketch: max {max} is less than min {min}");
+        }
+
+        Ok(Self {
+            max_size,
+            sum,
+            count,
+            max,
+            min,
+            centroids,
+        })
+    }
 }
 
 #[cfg(debug_assertions)]
@@ -699,6 +812,10 @@ mod tests {
             let state = $t.to_scalar_state();
             let other = TDigest::from_scalar_state(&state);
             assert_eq!($t, other);
+
+            let bytes = $t.to_bytes();
+            let other = TDigest::try_from_bytes(&bytes).unwrap();
+            assert_eq!($t, other);
         };
     }
 
@@ -788,4 +905,25 @@ mod tests {
 
         assert_eq!(t.size(), 96);
     }
+
+    #[test]
+    fn test_try_from_bytes_errors() {
+        let t = TDigest::new(100).merge_unsorted_f64(vec![1.0, 2.0, 3.0]);
+        let bytes = t.to_bytes();
+
+        let err = TDigest::try_from_bytes(&bytes[..10]).unwrap_err();
+        assert!(err.to_string().contains("too short"), "{err}");
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = 7;
+        let err = TDigest::try_from_bytes(&wrong_version).unwrap_err();
+        assert!(err.to_string().contains("version 7"), "{err}");
+
+        let err = TDigest::try_from_bytes(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert!(err.to_string().contains("expected 3 centroids"), "{err}");
+
+        let empty = TDigest::try_from_bytes(&TDigest::new(10).to_bytes()).unwrap();
+        assert_eq!(empty.count(), 0);
+        assert_eq!(empty.max_size(), 10);
+    }
 }
@@ -17,7 +17,6 @@
 
 //! Defines physical expressions that can evaluated at runtime during query execution
 
-use crate::hyperloglog::HyperLogLog;
 use arrow::array::BinaryArray;
 use arrow::array::{
     GenericBinaryArray, GenericStringArray, OffsetSizeTrait, PrimitiveArray,
@@ -28,15 +27,14 @@ use arrow::datatypes::{
 };
 use arrow::{array::ArrayRef, datatypes::DataType, datatypes::Field};
 use datafusion_common::ScalarValue;
-use datafusion_common::{
-    downcast_value, internal_err, not_impl_err, DataFusionError, Result,
-};
+use datafusion_common::{downcast_value, not_impl_err, DataFusionError, Result};
 use datafusion_expr::aggregate_doc_sections::DOC_SECTION_APPROXIMATE;
 use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
 use datafusion_expr::utils::format_state_name;
 use datafusion_expr::{
     Accumulator, AggregateUDFImpl, Documentation, Signature, Volatility,
 };
+use datafusion_functions_aggregate_common::hyperloglog::HyperLogLog;
 use std::any::Any;
 use std::fmt::{Debug, Formatter};
 use std::hash::Hash;
@@ -50,38 +48,6 @@ make_udaf_expr_and_func!(
     approx_distinct_udaf
 );
 
-impl<T: Hash> From<&HyperLogLog<T>> for ScalarValue {
-    fn from(v: &HyperLogLog<T>) -> ScalarValue {
-        let values = v.as_ref().to_vec();
-        ScalarValue::Binary(Some(values))
-    }
-}
-
-impl<T: Hash> TryFrom<&[u8]> for HyperLogLog<T> {
-    type Error = DataFusionError;
-    fn try_from(v: &[u8]) -> Result<HyperLogLog<T>> {
-        let arr: [u8; 16384] = v.try_into().map_err(|_| {
-            DataFusionError::Internal(
-                "Impossibly got invalid binary array from states".into(),
-            )
-        })?;
-        Ok(HyperLogLog::<T>::new_with_registers(arr))
-    }
-}
-
-impl<T: Hash> TryFrom<&ScalarValue> for HyperLogLog<T> {
-    type Error = DataFusionError;
-    fn try_from(v: &ScalarValue) -> Result<HyperLogLog<T>> {
-        if let ScalarValue::Binary(Some(slice)) = v {
-            slice.as_slice().try_into()
-        } else {
-            internal_err!(
-                "Impossibly got invalid scalar value while converting to HyperLogLog"
-            )
-        }
-    }
-}
-
 #[derive(Debug)]
 struct NumericHLLAccumulator<T>
 where
@@ -166,7 +132,7 @@ macro_rules! default_accumulator_impl {
         }
 
         fn state(&mut self) -> Result<Vec<ScalarValue>> {
-            let value = ScalarValue::from(&self.hll);
+            let value = self.hll.to_scalar_state();
             Ok(vec![value])
         }
 
@@ -282,30 +248,7 @@ impl AggregateUDFImpl for ApproxDistinct {
 
     fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
         let data_type = acc_args.exprs[0].data_type(acc_args.schema)?;
-
-        let accumulator: Box<dyn Accumulator> = match data_type {
-            // TODO u8, i8, u16, i16 shall really be done using bitmap, not HLL
-            // TODO support for boolean (trivial case)
-            // https://github.com/apache/datafusion/issues/1109
-            DataType::UInt8 => Box::new(NumericHLLAccumulator::<UInt8Type>::new()),
-            DataType::UInt16 => Box::new(NumericHLLAccumulator::<UInt16Type>::new()),
-            DataType::UInt32 => Box::new(NumericHLLAccumulator::<UInt32Type>::new()),
-            DataType::UInt64 => Box::new(NumericHLLAccumulator::<UInt64Type>::new()),
-            DataType::Int8 => Box::new(NumericHLLAccumulator::<Int8Type>::new()),
-            DataType::Int16 => Box::new(NumericHLLAccumulator::<Int16Type>::new()),
-            DataType::Int32 => Box::new(NumericHLLAccumulator::<Int32Type>::new()),
-            DataType::Int64 => Box::new(NumericHLLAccumulator::<Int64Type>::new()),
-            DataType::Utf8 => Box::new(StringHLLAccumulator::<i32>::new()),
-            DataType::LargeUtf8 => Box::new(StringHLLAccumulator::<i64>::new()),
-            DataType::Binary => Box::new(BinaryHLLAccumulator::<i32>::new()),
-            DataType::LargeBinary => Box::new(BinaryHLLAccumulator::<i64>::new()),
-            other => {
-                return not_impl_err!(
-                "Support for 'approx_distinct' for data type {other} is not implemented"
-            )
-            }
-        };
-        Ok(accumulator)
+        hll_accumulator(self.name(), &data_type)
     }
 
     fn documentation(&self) -> Option<&Documentation> {
@@ -313,6 +256,39 @@ impl AggregateUDFImpl for ApproxDistinct {
     }
 }
 
+/// Creates an accumulator maintaining a [`HyperLogLog`] of the values of type
+/// `data_type`, whose state is the raw HLL registers.
+///
+/// The accumulator evaluates to the estimated number of distinct values.
+pub(crate) fn hll_accumulator(
+    name: &str,
+    data_type: &DataType,
+) -> Result<Box<dyn Accumulator>> {
+    let accumulator: Box<dyn Accumulator> = match data_type {
+        // TODO u8, i8, u16, i16 shall really be done using bitmap, not HLL
+        // TODO support for boolean (trivial case)
+        // https://github.com/apache/datafusion/issues/1109
+        DataType::UInt8 => Box::new(NumericHLLAccumulator::<UInt8Type>::new()),
+        DataType::UInt16 => Box::new(NumericHLLAccumulator::<UInt16Type>::new()),
+        DataType::UInt32 => Box::new(NumericHLLAccumulator::<UInt32Type>::new()),
+        DataType::UInt64 => Box::new(NumericHLLAccumulator::<UInt64Type>::new()),
+        DataType::Int8 => Box::new(NumericHLLAccumulator::<Int8Type>::new()),
+        DataType::Int16 => Box::new(NumericHLLAccumulator::<Int16Type>::new()),
+        DataType::Int32 => Box::new(NumericHLLAccumulator::<Int32Type>::new()),
+        DataType::Int64 => Box::new(NumericHLLAccumulator::<Int64Type>::new()),
+        DataType::Utf8 => Box::new(StringHLLAccumulator::<i32>::new()),
+        DataType::LargeUtf8 => Box::new(StringHLLAccumulator::<i64>::new()),
+        DataType::Binary => Box::new(BinaryHLLAccumulator::<i32>::new()),
+        DataType::LargeBinary => Box::new(BinaryHLLAccumulator::<i64>::new()),
+        other => {
+            return not_impl_err!(
+                "Support for '{name}' for data type {other} is not implemented"
+            )
+        }
+    };
+    Ok(accumulator)
+}
+
 static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
 
 fn get_approx_distinct_doc() -> &'static Documentation {
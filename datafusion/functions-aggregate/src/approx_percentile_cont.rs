@@ -109,7 +109,10 @@ impl ApproxPercentileCont {
     ) -> Result<ApproxPercentileAccumulator> {
         let percentile = validate_input_percentile_expr(&args.exprs[1])?;
         let tdigest_max_size = if args.exprs.len() == 3 {
-            Some(validate_input_max_size_expr(&args.exprs[2])?)
+            Some(validate_input_max_size_expr(
+                &args.exprs[2],
+                "APPROX_PERCENTILE_CONT",
+            )?)
         } else {
             None
         };
@@ -144,7 +147,7 @@ impl ApproxPercentileCont {
     }
 }
 
-fn get_scalar_value(expr: &Arc<dyn PhysicalExpr>) -> Result<ScalarValue> {
+pub(crate) fn get_scalar_value(expr: &Arc<dyn PhysicalExpr>) -> Result<ScalarValue> {
     let empty_schema = Arc::new(Schema::empty());
     let batch = RecordBatch::new_empty(Arc::clone(&empty_schema));
     if let ColumnarValue::Scalar(s) = expr.evaluate(&batch)? {
@@ -180,9 +183,12 @@ fn validate_input_percentile_expr(expr: &Arc<dyn PhysicalExpr>) -> Result<f64> {
     Ok(percentile)
 }
 
-fn validate_input_max_size_expr(expr: &Arc<dyn PhysicalExpr>) -> Result<usize> {
+pub(crate) fn validate_input_max_size_expr(
+    expr: &Arc<dyn PhysicalExpr>,
+    function_name: &str,
+) -> Result<usize> {
     let max_size = match get_scalar_value(expr)
-        .map_err(|_| not_impl_datafusion_err!("Tdigest max_size value for 'APPROX_PERCENTILE_CONT' must be a literal, got: {expr}"))? {
+        .map_err(|_| not_impl_datafusion_err!("Tdigest max_size value for '{function_name}' must be a literal, got: {expr}"))? {
         ScalarValue::UInt8(Some(q)) => q as usize,
         ScalarValue::UInt16(Some(q)) => q as usize,
         ScalarValue::UInt32(Some(q)) => q as usize,
@@ -193,7 +199,7 @@ fn validate_input_max_size_expr(expr: &Arc<dyn PhysicalExpr>) -> Result<usize> {
         ScalarValue::Int8(Some(q)) if q > 0 => q as usize,
         sv => {
             return not_impl_err!(
-                "Tdigest max_size value for 'APPROX_PERCENTILE_CONT' must be UInt > 0 literal (got data type {}).",
+                "Tdigest max_size value for '{function_name}' must be UInt > 0 literal (got data type {}).",
                 sv.data_type()
             )
         },
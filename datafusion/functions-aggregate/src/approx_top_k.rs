@@ -0,0 +1,429 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the `approx_top_k` aggregate, returning the most frequent values
+//! using the [`SpaceSaving`] sketch
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{new_empty_array, Array, ArrayRef, StructArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, Fields};
+use datafusion_common::cast::{as_list_array, as_uint64_array};
+use datafusion_common::utils::array_into_list_array_nullable;
+use datafusion_common::{
+    internal_err, not_impl_datafusion_err, not_impl_err, plan_err, Result, ScalarValue,
+};
+use datafusion_expr::aggregate_doc_sections::DOC_SECTION_APPROXIMATE;
+use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion_expr::utils::format_state_name;
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, Documentation, Signature, Volatility,
+};
+use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
+
+use crate::approx_percentile_cont::get_scalar_value;
+use crate::space_saving::{Counter, SpaceSaving};
+
+make_udaf_expr_and_func!(
+    ApproxTopK,
+    approx_top_k,
+    expression k,
+    "Returns the approximate most frequent values and their counts",
+    approx_top_k_udaf
+);
+
+/// Number of counters kept by the sketch for every requested item.
+///
+/// Space-Saving only guarantees to find items more frequent than
+/// `N / capacity`, so tracking more counters than `k` makes the reported
+/// items and counts much more accurate on skewed data.
+const COUNTERS_PER_ITEM: usize = 10;
+
+/// Name of the struct field holding the value in the `approx_top_k` output
+const VALUE_FIELD_NAME: &str = "value";
+/// Name of the struct field holding the count in the `approx_top_k` output
+const COUNT_FIELD_NAME: &str = "count";
+
+pub struct ApproxTopK {
+    signature: Signature,
+}
+
+impl Debug for ApproxTopK {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApproxTopK")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for ApproxTopK {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ApproxTopK {
+    /// Create a new [`ApproxTopK`] aggregate function.
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::any(2, Volatility::Immutable),
+        }
+    }
+}
+
+/// The fields of the struct returned for every frequent value
+fn top_k_struct_fields(value_type: &DataType) -> Fields {
+    Fields::from(vec![
+        Field::new(VALUE_FIELD_NAME, value_type.clone(), true),
+        Field::new(COUNT_FIELD_NAME, DataType::UInt64, false),
+    ])
+}
+
+fn validate_input_k_expr(expr: &Arc<dyn PhysicalExpr>) -> Result<usize> {
+    let k = match get_scalar_value(expr).map_err(|_| {
+        not_impl_datafusion_err!(
+            "k value for 'APPROX_TOP_K' must be a literal, got: {expr}"
+        )
+    })? {
+        ScalarValue::UInt8(Some(k)) => k as usize,
+        ScalarValue::UInt16(Some(k)) => k as usize,
+        ScalarValue::UInt32(Some(k)) => k as usize,
+        ScalarValue::UInt64(Some(k)) => k as usize,
+        ScalarValue::Int8(Some(k)) if k > 0 => k as usize,
+        ScalarValue::Int16(Some(k)) if k > 0 => k as usize,
+        ScalarValue::Int32(Some(k)) if k > 0 => k as usize,
+        ScalarValue::Int64(Some(k)) if k > 0 => k as usize,
+        sv => {
+            return not_impl_err!(
+                "k value for 'APPROX_TOP_K' must be an integer > 0 literal (got {sv})"
+            )
+        }
+    };
+
+    if k == 0 {
+        return plan_err!("k value for 'APPROX_TOP_K' must be greater than 0");
+    }
+    Ok(k)
+}
+
+impl AggregateUDFImpl for ApproxTopK {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "approx_top_k"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        if !arg_types[1].is_integer() {
+            return plan_err!("approx_top_k requires an integer k");
+        }
+        Ok(DataType::List(Arc::new(Field::new_list_field(
+            DataType::Struct(top_k_struct_fields(&arg_types[0])),
+            true,
+        ))))
+    }
+
+    /// The sketch is serialized as the tracked values and their counters,
+    /// see [`ApproxTopKAccumulator::state`].
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        Ok(vec![
+            Field::new_list(
+                format_state_name(args.name, "values"),
+                Field::new_list_field(args.input_types[0].clone(), true),
+                false,
+            ),
+            Field::new_list(
+                format_state_name(args.name, "counts"),
+                Field::new_list_field(DataType::UInt64, true),
+                false,
+            ),
+            Field::new_list(
+                format_state_name(args.name, "errors"),
+                Field::new_list_field(DataType::UInt64, true),
+                false,
+            ),
+        ])
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        if acc_args.is_distinct {
+            return not_impl_err!(
+                "approx_top_k(DISTINCT) aggregations are not available"
+            );
+        }
+        let k = validate_input_k_expr(&acc_args.exprs[1])?;
+        let data_type = acc_args.exprs[0].data_type(acc_args.schema)?;
+        Ok(Box::new(ApproxTopKAccumulator::new(k, data_type)))
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_approx_top_k_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_approx_top_k_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_APPROXIMATE)
+            .with_description(
+                "Returns the approximate `k` most frequent input values and their counts, calculated using the Space-Saving algorithm. The result is a list of `{value, count}` structs ordered by decreasing count. Counts are upper bounds of the true frequencies.",
+            )
+            .with_syntax_example("approx_top_k(expression, k)")
+            .with_sql_example(r#"```sql
+> SELECT approx_top_k(column_name, 2) FROM table_name;
++--------------------------------------------------------------+
+| approx_top_k(column_name, Int64(2))                          |
++--------------------------------------------------------------+
+| [{value: foo, count: 42}, {value: bar, count: 17}]           |
++--------------------------------------------------------------+
+```"#)
+            .with_standard_argument("expression", None)
+            .with_argument("k", "Number of most frequent values to return. Must be an integer literal greater than 0.")
+            .build()
+            .unwrap()
+    })
+}
+
+/// Accumulator for `approx_top_k`, keyed by [`ScalarValue`] so that any
+/// hashable input type is supported.
+#[derive(Debug)]
+pub struct ApproxTopKAccumulator {
+    sketch: SpaceSaving<ScalarValue>,
+    k: usize,
+    data_type: DataType,
+}
+
+impl ApproxTopKAccumulator {
+    pub fn new(k: usize, data_type: DataType) -> Self {
+        Self {
+            sketch: SpaceSaving::new(k.saturating_mul(COUNTERS_PER_ITEM)),
+            k,
+            data_type,
+        }
+    }
+
+    fn values_to_array(&self, values: Vec<ScalarValue>) -> Result<ArrayRef> {
+        if values.is_empty() {
+            Ok(new_empty_array(&self.data_type))
+        } else {
+            ScalarValue::iter_to_array(values)
+        }
+    }
+}
+
+/// Wraps `array` into a single element [`ScalarValue::List`]
+fn to_list_scalar(array: ArrayRef) -> ScalarValue {
+    ScalarValue::List(Arc::new(array_into_list_array_nullable(array)))
+}
+
+impl Accumulator for ApproxTopKAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let array = &values[0];
+
+        // Pre-aggregate the batch so that each distinct value updates the
+        // sketch (and possibly evicts a counter) only once
+        let mut batch_counts: HashMap<ScalarValue, u64> = HashMap::new();
+        for index in 0..array.len() {
+            if array.is_null(index) {
+                continue;
+            }
+            let value = ScalarValue::try_from_array(array, index)?;
+            *batch_counts.entry(value).or_default() += 1;
+        }
+        for (value, count) in batch_counts {
+            self.sketch.add_weighted(value, count);
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        if states.len() != 3 {
+            return internal_err!(
+                "approx_top_k expects 3 state arrays, got {}",
+                states.len()
+            );
+        }
+        let values = as_list_array(&states[0])?;
+        let counts = as_list_array(&states[1])?;
+        let errors = as_list_array(&states[2])?;
+
+        for index in 0..values.len() {
+            if values.is_null(index) {
+                continue;
+            }
+            let row_values = values.value(index);
+            let row_counts = counts.value(index);
+            let row_counts = as_uint64_array(&row_counts)?;
+            let row_errors = errors.value(index);
+            let row_errors = as_uint64_array(&row_errors)?;
+
+            let counters = (0..row_values.len())
+                .map(|i| {
+                    let value = ScalarValue::try_from_array(&row_values, i)?;
+                    let counter = Counter {
+                        count: row_counts.value(i),
+                        error: row_errors.value(i),
+                    };
+                    Ok((value, counter))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let other = SpaceSaving::new_with_counters(self.sketch.capacity(), counters);
+            self.sketch.merge(&other);
+        }
+        Ok(())
+    }
+
+    /// The state is three lists of the same length: the tracked values, their
+    /// estimated counts and their error bounds.
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let mut values = Vec::with_capacity(self.sketch.len());
+        let mut counts = Vec::with_capacity(self.sketch.len());
+        let mut errors = Vec::with_capacity(self.sketch.len());
+        for (value, counter) in self.sketch.counters() {
+            values.push(value.clone());
+            counts.push(counter.count);
+            errors.push(counter.error);
+        }
+        Ok(vec![
+            to_list_scalar(self.values_to_array(values)?),
+            to_list_scalar(Arc::new(UInt64Array::from(counts))),
+            to_list_scalar(Arc::new(UInt64Array::from(errors))),
+        ])
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let top_k = self.sketch.top_k(self.k);
+        let values = top_k
+            .iter()
+            .map(|(value, _)| (*value).clone())
+            .collect::<Vec<_>>();
+        let values = self.values_to_array(values)?;
+        let counts: ArrayRef = Arc::new(
+            top_k
+                .iter()
+                .map(|(_, counter)| counter.count)
+                .collect::<UInt64Array>(),
+        );
+        let items = StructArray::try_new(
+            top_k_struct_fields(&self.data_type),
+            vec![values, counts],
+            None,
+        )?;
+        Ok(to_list_scalar(Arc::new(items)))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) - std::mem::size_of_val(&self.sketch)
+            + self.sketch.size(ScalarValue::size)
+            + self.data_type.size()
+            - std::mem::size_of_val(&self.data_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int32Array, StringArray};
+    use datafusion_common::cast::as_struct_array;
+
+    fn evaluate_top_k(acc: &mut ApproxTopKAccumulator) -> Vec<(ScalarValue, u64)> {
+        let ScalarValue::List(list) = acc.evaluate().unwrap() else {
+            panic!("expected list");
+        };
+        let items = list.value(0);
+        let items = as_struct_array(&items).unwrap();
+        let counts = as_uint64_array(items.column(1)).unwrap();
+        (0..items.len())
+            .map(|i| {
+                (
+                    ScalarValue::try_from_array(items.column(0), i).unwrap(),
+                    counts.value(i),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_update_skips_nulls() -> Result<()> {
+        let mut acc = ApproxTopKAccumulator::new(2, DataType::Utf8);
+        let values: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("a"),
+            None,
+            Some("b"),
+            Some("a"),
+            None,
+            None,
+            Some("c"),
+            Some("a"),
+            Some("b"),
+        ]));
+        acc.update_batch(&[values])?;
+        assert_eq!(
+            evaluate_top_k(&mut acc),
+            vec![(ScalarValue::from("a"), 3), (ScalarValue::from("b"), 2)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_partial_states() -> Result<()> {
+        let mut partial_1 = ApproxTopKAccumulator::new(2, DataType::Int32);
+        let mut partial_2 = ApproxTopKAccumulator::new(2, DataType::Int32);
+        partial_1.update_batch(&[Arc::new(Int32Array::from(vec![1, 1, 2, 3]))])?;
+        partial_2.update_batch(&[Arc::new(Int32Array::from(vec![3, 3, 3, 1]))])?;
+
+        let mut final_acc = ApproxTopKAccumulator::new(2, DataType::Int32);
+        for partial in [&mut partial_1, &mut partial_2] {
+            let state = partial
+                .state()?
+                .iter()
+                .map(|s| s.to_array())
+                .collect::<Result<Vec<_>>>()?;
+            final_acc.merge_batch(&state)?;
+        }
+        assert_eq!(
+            evaluate_top_k(&mut final_acc),
+            vec![
+                (ScalarValue::Int32(Some(3)), 4),
+                (ScalarValue::Int32(Some(1)), 3)
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty() -> Result<()> {
+        let mut acc = ApproxTopKAccumulator::new(3, DataType::Int32);
+        assert!(evaluate_top_k(&mut acc).is_empty());
+        Ok(())
+    }
+}
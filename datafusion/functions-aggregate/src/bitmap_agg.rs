@@ -0,0 +1,370 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the `bitmap_agg` and `bitmap_or_agg` aggregates, which build
+//! serialized [`RoaringBitmap`]s
+
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::sync::OnceLock;
+
+use arrow::array::ArrayRef;
+use arrow::compute::{cast_with_options, CastOptions};
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::cast::{as_binary_array, as_uint32_array};
+use datafusion_common::{exec_datafusion_err, not_impl_err, Result, ScalarValue};
+use datafusion_expr::aggregate_doc_sections::DOC_SECTION_GENERAL;
+use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion_expr::type_coercion::aggregates::INTEGERS;
+use datafusion_expr::utils::format_state_name;
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, Documentation, Signature, Volatility,
+};
+use datafusion_functions_aggregate_common::bitmap::{
+    bitmap_from_bytes, bitmap_to_scalar, RoaringBitmap,
+};
+
+make_udaf_expr_and_func!(
+    BitmapAgg,
+    bitmap_agg,
+    expression,
+    "Builds a serialized roaring bitmap of the input integers",
+    bitmap_agg_udaf
+);
+
+make_udaf_expr_and_func!(
+    BitmapOrAgg,
+    bitmap_or_agg,
+    bitmap,
+    "Computes the union of serialized roaring bitmaps",
+    bitmap_or_agg_udaf
+);
+
+fn bitmap_state_fields(args: StateFieldsArgs) -> Result<Vec<Field>> {
+    Ok(vec![Field::new(
+        format_state_name(args.name, "bitmap"),
+        DataType::Binary,
+        true,
+    )])
+}
+
+/// Unions every serialized bitmap of `array` into `bitmap`
+fn union_serialized(bitmap: &mut RoaringBitmap, array: &ArrayRef) -> Result<()> {
+    let array = as_binary_array(array)?;
+    // flatten because we would skip nulls
+    for bytes in array.iter().flatten() {
+        *bitmap |= bitmap_from_bytes(bytes)?;
+    }
+    Ok(())
+}
+
+pub struct BitmapAgg {
+    signature: Signature,
+}
+
+impl Debug for BitmapAgg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BitmapAgg")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for BitmapAgg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BitmapAgg {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::uniform(1, INTEGERS.to_vec(), Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for BitmapAgg {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "bitmap_agg"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Binary)
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        bitmap_state_fields(args)
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        if acc_args.is_distinct {
+            return not_impl_err!("bitmap_agg(DISTINCT) aggregations are not available");
+        }
+        Ok(Box::<BitmapAggAccumulator>::default())
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_bitmap_agg_doc())
+    }
+}
+
+#[derive(Debug, Default)]
+struct BitmapAggAccumulator {
+    bitmap: RoaringBitmap,
+}
+
+impl Accumulator for BitmapAggAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        // bitmaps hold 32 bit unsigned integers, reject anything that does
+        // not fit rather than silently dropping it
+        let options = CastOptions {
+            safe: false,
+            ..Default::default()
+        };
+        let values =
+            cast_with_options(&values[0], &DataType::UInt32, &options).map_err(|e| {
+                exec_datafusion_err!(
+                    "bitmap_agg only supports values between 0 and {}: {e}",
+                    u32::MAX
+                )
+            })?;
+        // flatten because we would skip nulls
+        self.bitmap
+            .extend(as_uint32_array(&values)?.iter().flatten());
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        union_serialized(&mut self.bitmap, &states[0])
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        Ok(bitmap_to_scalar(&self.bitmap))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.bitmap.serialized_size()
+    }
+}
+
+pub struct BitmapOrAgg {
+    signature: Signature,
+}
+
+impl Debug for BitmapOrAgg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BitmapOrAgg")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for BitmapOrAgg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BitmapOrAgg {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::coercible(
+                vec![DataType::Binary],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl AggregateUDFImpl for BitmapOrAgg {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "bitmap_or_agg"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Binary)
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        bitmap_state_fields(args)
+    }
+
+    fn accumulator(&self, _acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::<BitmapOrAggAccumulator>::default())
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_bitmap_or_agg_doc())
+    }
+}
+
+#[derive(Debug, Default)]
+struct BitmapOrAggAccumulator {
+    bitmap: RoaringBitmap,
+}
+
+impl Accumulator for BitmapOrAggAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        union_serialized(&mut self.bitmap, &values[0])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        union_serialized(&mut self.bitmap, &states[0])
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        Ok(bitmap_to_scalar(&self.bitmap))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.bitmap.serialized_size()
+    }
+}
+
+static BITMAP_AGG_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_bitmap_agg_doc() -> &'static Documentation {
+    BITMAP_AGG_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_GENERAL)
+            .with_description(
+                "Returns a roaring bitmap containing the distinct input values, serialized in the portable Roaring format. Input values must be integers between 0 and 4294967295. Bitmaps can be combined with `bitmap_or_agg` and `bitmap_and` and queried with `bitmap_cardinality` and `bitmap_contains`.",
+            )
+            .with_syntax_example("bitmap_agg(expression)")
+            .with_sql_example(r#"```sql
+> SELECT bitmap_cardinality(bitmap_agg(user_id)) FROM table_name;
++----------------------------------------------------+
+| bitmap_cardinality(bitmap_agg(table_name.user_id)) |
++----------------------------------------------------+
+| 42                                                 |
++----------------------------------------------------+
+```"#,
+            )
+            .with_argument("expression", "Integer expression to operate on. Can be a constant, column, or function, and any combination of operators.")
+            .build()
+            .unwrap()
+    })
+}
+
+static BITMAP_OR_AGG_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_bitmap_or_agg_doc() -> &'static Documentation {
+    BITMAP_OR_AGG_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_GENERAL)
+            .with_description(
+                "Returns the union of the roaring bitmaps produced by `bitmap_agg` or `bitmap_or_agg`.",
+            )
+            .with_syntax_example("bitmap_or_agg(bitmap)")
+            .with_sql_example(r#"```sql
+> SELECT bitmap_cardinality(bitmap_or_agg(daily_users)) FROM bitmaps;
++--------------------------------------------------------+
+| bitmap_cardinality(bitmap_or_agg(bitmaps.daily_users)) |
++--------------------------------------------------------+
+| 1234                                                   |
++--------------------------------------------------------+
+```"#,
+            )
+            .with_argument("bitmap", "Binary expression containing a bitmap produced by `bitmap_agg` or `bitmap_or_agg`.")
+            .build()
+            .unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{BinaryArray, Int64Array};
+    use std::sync::Arc;
+
+    fn to_bitmap(value: &ScalarValue) -> RoaringBitmap {
+        match value {
+            ScalarValue::Binary(Some(bytes)) => bitmap_from_bytes(bytes).unwrap(),
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_bitmap_agg_and_or() -> Result<()> {
+        let mut bitmaps = vec![];
+        for values in [
+            vec![Some(1), Some(2), None, Some(2)],
+            vec![Some(2), Some(3)],
+        ] {
+            let mut acc = BitmapAggAccumulator::default();
+            let values: ArrayRef = Arc::new(Int64Array::from(values));
+            acc.update_batch(&[values])?;
+            bitmaps.push(acc.evaluate()?);
+        }
+        assert_eq!(to_bitmap(&bitmaps[0]), RoaringBitmap::from_iter([1, 2]));
+
+        let mut union = BitmapOrAggAccumulator::default();
+        union.update_batch(&[ScalarValue::iter_to_array(bitmaps)?])?;
+        assert_eq!(
+            to_bitmap(&union.evaluate()?),
+            RoaringBitmap::from_iter([1, 2, 3])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_bitmap_agg_out_of_range() {
+        let mut acc = BitmapAggAccumulator::default();
+        let values: ArrayRef = Arc::new(Int64Array::from(vec![1, -1]));
+        let err = acc.update_batch(&[values]).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("bitmap_agg only supports values between 0 and 4294967295"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_bitmap_or_agg_invalid() {
+        let mut acc = BitmapOrAggAccumulator::default();
+        let values: ArrayRef = Arc::new(BinaryArray::from_iter_values([b"invalid"]));
+        let err = acc.update_batch(&[values]).unwrap_err();
+        assert!(err.to_string().contains("Invalid roaring bitmap"), "{err}");
+    }
+}
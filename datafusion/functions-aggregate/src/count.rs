@@ -52,8 +52,8 @@ use datafusion_expr::{
 };
 use datafusion_expr::{Expr, ReversedUDAF, StatisticsArgs, TypeSignature};
 use datafusion_functions_aggregate_common::aggregate::count_distinct::{
-    BytesDistinctCountAccumulator, FloatDistinctCountAccumulator,
-    PrimitiveDistinctCountAccumulator,
+    BitmapDistinctCountAccumulator, BytesDistinctCountAccumulator,
+    FloatDistinctCountAccumulator, PrimitiveDistinctCountAccumulator,
 };
 use datafusion_functions_aggregate_common::aggregate::groups_accumulator::accumulate::accumulate_indices;
 use datafusion_physical_expr_common::binary_map::OutputType;
@@ -159,27 +159,27 @@ impl AggregateUDFImpl for Count {
         let data_type = &acc_args.exprs[0].data_type(acc_args.schema)?;
         Ok(match data_type {
             // try and use a specialized accumulator if possible, otherwise fall back to generic accumulator
-            DataType::Int8 => Box::new(
-                PrimitiveDistinctCountAccumulator::<Int8Type>::new(data_type),
-            ),
-            DataType::Int16 => Box::new(
-                PrimitiveDistinctCountAccumulator::<Int16Type>::new(data_type),
-            ),
-            DataType::Int32 => Box::new(
-                PrimitiveDistinctCountAccumulator::<Int32Type>::new(data_type),
-            ),
+            DataType::Int8 => {
+                Box::new(BitmapDistinctCountAccumulator::<Int8Type>::new(data_type))
+            }
+            DataType::Int16 => {
+                Box::new(BitmapDistinctCountAccumulator::<Int16Type>::new(data_type))
+            }
+            DataType::Int32 => {
+                Box::new(BitmapDistinctCountAccumulator::<Int32Type>::new(data_type))
+            }
             DataType::Int64 => Box::new(
                 PrimitiveDistinctCountAccumulator::<Int64Type>::new(data_type),
             ),
-            DataType::UInt8 => Box::new(
-                PrimitiveDistinctCountAccumulator::<UInt8Type>::new(data_type),
-            ),
-            DataType::UInt16 => Box::new(
-                PrimitiveDistinctCountAccumulator::<UInt16Type>::new(data_type),
-            ),
-            DataType::UInt32 => Box::new(
-                PrimitiveDistinctCountAccumulator::<UInt32Type>::new(data_type),
-            ),
+            DataType::UInt8 => {
+                Box::new(BitmapDistinctCountAccumulator::<UInt8Type>::new(data_type))
+            }
+            DataType::UInt16 => {
+                Box::new(BitmapDistinctCountAccumulator::<UInt16Type>::new(data_type))
+            }
+            DataType::UInt32 => {
+                Box::new(BitmapDistinctCountAccumulator::<UInt32Type>::new(data_type))
+            }
             DataType::UInt64 => Box::new(
                 PrimitiveDistinctCountAccumulator::<UInt64Type>::new(data_type),
             ),
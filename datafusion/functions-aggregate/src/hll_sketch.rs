@@ -0,0 +1,364 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the `hll_sketch` and `hll_merge` aggregates, which produce
+//! serialized [`HyperLogLog`] sketches that can be stored and merged later
+
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::sync::OnceLock;
+
+use arrow::array::ArrayRef;
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::cast::as_binary_array;
+use datafusion_common::{not_impl_err, Result, ScalarValue};
+use datafusion_expr::aggregate_doc_sections::DOC_SECTION_APPROXIMATE;
+use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion_expr::utils::format_state_name;
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, Documentation, Signature, Volatility,
+};
+use datafusion_functions_aggregate_common::hyperloglog::HyperLogLog;
+
+use crate::approx_distinct::hll_accumulator;
+
+make_udaf_expr_and_func!(
+    HllSketch,
+    hll_sketch,
+    expression,
+    "Builds a serialized HyperLogLog sketch of the input values",
+    hll_sketch_udaf
+);
+
+make_udaf_expr_and_func!(
+    HllMerge,
+    hll_merge,
+    sketch,
+    "Merges serialized HyperLogLog sketches into a single sketch",
+    hll_merge_udaf
+);
+
+/// Registers of a sketch, independently of the type of the values hashed
+/// into it
+type Registers = HyperLogLog<[u8]>;
+
+fn hll_state_fields(args: StateFieldsArgs) -> Result<Vec<Field>> {
+    Ok(vec![Field::new(
+        format_state_name(args.name, "hll_registers"),
+        DataType::Binary,
+        false,
+    )])
+}
+
+pub struct HllSketch {
+    signature: Signature,
+}
+
+impl Debug for HllSketch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HllSketch")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for HllSketch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HllSketch {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for HllSketch {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "hll_sketch"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Binary)
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        hll_state_fields(args)
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        let data_type = acc_args.exprs[0].data_type(acc_args.schema)?;
+        Ok(Box::new(HllSketchAccumulator {
+            inner: hll_accumulator(self.name(), &data_type)?,
+        }))
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_hll_sketch_doc())
+    }
+}
+
+/// Wraps the `approx_distinct` accumulator of the input type, returning the
+/// serialized sketch instead of the estimated count
+#[derive(Debug)]
+struct HllSketchAccumulator {
+    inner: Box<dyn Accumulator>,
+}
+
+impl Accumulator for HllSketchAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.inner.update_batch(values)
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        self.inner.merge_batch(states)
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        self.inner.state()
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let state = self.inner.state()?;
+        let hll = Registers::try_from(&state[0])?;
+        Ok(ScalarValue::Binary(Some(hll.to_bytes())))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.inner.size()
+    }
+}
+
+pub struct HllMerge {
+    signature: Signature,
+}
+
+impl Debug for HllMerge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HllMerge")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for HllMerge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HllMerge {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::coercible(
+                vec![DataType::Binary],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl AggregateUDFImpl for HllMerge {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "hll_merge"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Binary)
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        hll_state_fields(args)
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        if acc_args.is_distinct {
+            return not_impl_err!("hll_merge(DISTINCT) aggregations are not available");
+        }
+        Ok(Box::new(HllMergeAccumulator {
+            hll: Registers::new(),
+        }))
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_hll_merge_doc())
+    }
+}
+
+#[derive(Debug)]
+struct HllMergeAccumulator {
+    hll: Registers,
+}
+
+impl Accumulator for HllMergeAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let array = as_binary_array(&values[0])?;
+        // flatten because we would skip nulls
+        for bytes in array.iter().flatten() {
+            self.hll.merge(&Registers::try_from_bytes(bytes)?);
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let array = as_binary_array(&states[0])?;
+        for registers in array.iter().flatten() {
+            self.hll.merge(&Registers::try_from(registers)?);
+        }
+        Ok(())
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![self.hll.to_scalar_state()])
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        Ok(ScalarValue::Binary(Some(self.hll.to_bytes())))
+    }
+
+    fn size(&self) -> usize {
+        // HLL has static size
+        std::mem::size_of_val(self)
+    }
+}
+
+static HLL_SKETCH_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_hll_sketch_doc() -> &'static Documentation {
+    HLL_SKETCH_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_APPROXIMATE)
+            .with_description(
+                "Returns a HyperLogLog sketch of the input values, serialized in a stable binary format. Sketches can be stored, combined with `hll_merge` and turned into a distinct count estimate with `hll_estimate`.",
+            )
+            .with_syntax_example("hll_sketch(expression)")
+            .with_sql_example(r#"```sql
+> SELECT hll_estimate(hll_sketch(column_name)) FROM table_name;
++--------------------------------------------------+
+| hll_estimate(hll_sketch(table_name.column_name)) |
++--------------------------------------------------+
+| 42                                               |
++--------------------------------------------------+
+```"#,
+            )
+            .with_standard_argument("expression", None)
+            .build()
+            .unwrap()
+    })
+}
+
+static HLL_MERGE_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_hll_merge_doc() -> &'static Documentation {
+    HLL_MERGE_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_APPROXIMATE)
+            .with_description(
+                "Merges HyperLogLog sketches produced by `hll_sketch` or `hll_merge` into a single sketch, estimating the distinct values of the union of their inputs.",
+            )
+            .with_syntax_example("hll_merge(sketch)")
+            .with_sql_example(r#"```sql
+> SELECT hll_estimate(hll_merge(daily_sketch)) FROM sketches;
++------------------------------------------------+
+| hll_estimate(hll_merge(sketches.daily_sketch)) |
++------------------------------------------------+
+| 1234                                           |
++------------------------------------------------+
+```"#,
+            )
+            .with_argument("sketch", "Binary expression containing a sketch produced by `hll_sketch` or `hll_merge`.")
+            .build()
+            .unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{BinaryArray, Int64Array};
+    use std::sync::Arc;
+
+    fn evaluate_bytes(acc: &mut dyn Accumulator) -> Vec<u8> {
+        match acc.evaluate().unwrap() {
+            ScalarValue::Binary(Some(bytes)) => bytes,
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_sketch_and_merge() -> Result<()> {
+        let mut sketches = vec![];
+        for day in 0..3_i64 {
+            let mut acc = HllSketchAccumulator {
+                inner: hll_accumulator("hll_sketch", &DataType::Int64)?,
+            };
+            // each day sees 1000 values, overlapping by half with the previous day
+            let values: ArrayRef = Arc::new(Int64Array::from_iter_values(
+                (day * 500)..(day * 500 + 1000),
+            ));
+            acc.update_batch(&[values])?;
+            sketches.push(Some(evaluate_bytes(&mut acc)));
+        }
+
+        let mut merge = HllMergeAccumulator {
+            hll: Registers::new(),
+        };
+        let sketches: ArrayRef = Arc::new(BinaryArray::from_iter(
+            sketches.iter().map(|s| s.as_deref()).chain([None]),
+        ));
+        merge.update_batch(&[sketches])?;
+
+        let merged = Registers::try_from_bytes(&evaluate_bytes(&mut merge))?;
+        let count = merged.count() as f64;
+        assert!((count - 2000.0).abs() / 2000.0 < 0.05, "{count}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_invalid_sketch() {
+        let mut merge = HllMergeAccumulator {
+            hll: Registers::new(),
+        };
+        let sketches: ArrayRef = Arc::new(BinaryArray::from_iter_values([b"invalid"]));
+        let err = merge.update_batch(&[sketches]).unwrap_err();
+        assert!(
+            err.to_string().contains("Unsupported HyperLogLog sketch"),
+            "{err}"
+        );
+    }
+}
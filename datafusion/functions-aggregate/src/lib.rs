@@ -63,10 +63,10 @@ pub mod correlation;
 pub mod count;
 pub mod covariance;
 pub mod first_last;
-pub mod hyperloglog;
 pub mod median;
 pub mod min_max;
 pub mod regr;
+pub mod space_saving;
 pub mod stddev;
 pub mod sum;
 pub mod variance;
@@ -74,12 +74,21 @@ pub mod variance;
 pub mod approx_median;
 pub mod approx_percentile_cont;
 pub mod approx_percentile_cont_with_weight;
+pub mod approx_top_k;
 pub mod average;
 pub mod bit_and_or_xor;
+pub mod bitmap_agg;
 pub mod bool_and_or;
 pub mod grouping;
+pub mod hll_sketch;
 pub mod nth_value;
 pub mod string_agg;
+pub mod tdigest_sketch;
+
+/// The [`HyperLogLog`] sketch, moved to `datafusion-functions-aggregate-common`
+///
+/// [`HyperLogLog`]: hyperloglog::HyperLogLog
+pub use datafusion_functions_aggregate_common::hyperloglog;
 
 use crate::approx_percentile_cont::approx_percentile_cont_udaf;
 use crate::approx_percentile_cont_with_weight::approx_percentile_cont_with_weight_udaf;
@@ -95,11 +104,14 @@ pub mod expr_fn {
     pub use super::approx_median::approx_median;
     pub use super::approx_percentile_cont::approx_percentile_cont;
     pub use super::approx_percentile_cont_with_weight::approx_percentile_cont_with_weight;
+    pub use super::approx_top_k::approx_top_k;
     pub use super::array_agg::array_agg;
     pub use super::average::avg;
     pub use super::bit_and_or_xor::bit_and;
     pub use super::bit_and_or_xor::bit_or;
     pub use super::bit_and_or_xor::bit_xor;
+    pub use super::bitmap_agg::bitmap_agg;
+    pub use super::bitmap_agg::bitmap_or_agg;
     pub use super::bool_and_or::bool_and;
     pub use super::bool_and_or::bool_or;
     pub use super::correlation::corr;
@@ -110,6 +122,8 @@ pub mod expr_fn {
     pub use super::first_last::first_value;
     pub use super::first_last::last_value;
     pub use super::grouping::grouping;
+    pub use super::hll_sketch::hll_merge;
+    pub use super::hll_sketch::hll_sketch;
     pub use super::median::median;
     pub use super::min_max::max;
     pub use super::min_max::min;
@@ -126,6 +140,8 @@ pub mod expr_fn {
     pub use super::stddev::stddev;
     pub use super::stddev::stddev_pop;
     pub use super::sum::sum;
+    pub use super::tdigest_sketch::tdigest_merge;
+    pub use super::tdigest_sketch::tdigest_sketch;
     pub use super::variance::var_pop;
     pub use super::variance::var_sample;
 }
@@ -161,10 +177,17 @@ pub fn all_default_aggregate_functions() -> Vec<Arc<AggregateUDF>> {
         approx_distinct::approx_distinct_udaf(),
         approx_percentile_cont_udaf(),
         approx_percentile_cont_with_weight_udaf(),
+        approx_top_k::approx_top_k_udaf(),
+        hll_sketch::hll_sketch_udaf(),
+        hll_sketch::hll_merge_udaf(),
+        tdigest_sketch::tdigest_sketch_udaf(),
+        tdigest_sketch::tdigest_merge_udaf(),
         string_agg::string_agg_udaf(),
         bit_and_or_xor::bit_and_udaf(),
         bit_and_or_xor::bit_or_udaf(),
         bit_and_or_xor::bit_xor_udaf(),
+        bitmap_agg::bitmap_agg_udaf(),
+        bitmap_agg::bitmap_or_agg_udaf(),
         bool_and_or::bool_and_udaf(),
         bool_and_or::bool_or_udaf(),
         average::avg_udaf(),
@@ -0,0 +1,497 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! # Space-Saving
+//!
+//! `space_saving` is a module that contains an implementation of the
+//! Space-Saving heavy hitters sketch described in "Efficient Computation of
+//! Frequent and Top-k Elements in Data Streams" (Metwally, Agrawal and
+//! El Abbadi, 2005).
+//!
+//! The sketch keeps at most `capacity` counters. Each counter tracks an
+//! over-estimate of the number of times an item was seen (`count`) together
+//! with the maximum amount by which that estimate may be too large
+//! (`error`). Any item whose true frequency is greater than `N / capacity`,
+//! where `N` is the total number of items added, is guaranteed to be tracked.
+//!
+//! Sketches are merged following "A parallel space saving algorithm for
+//! frequent items and the Hurwitz zeta distribution" (Cafaro, Pulimeno and
+//! Tempesta, 2016), which keeps the same error guarantees as a single sketch
+//! built over the combined input. This makes the sketch suitable for
+//! partial / final aggregation.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem::{size_of, size_of_val};
+
+/// Estimated frequency of an item tracked by a [`SpaceSaving`] sketch
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Counter {
+    /// Upper bound of the number of times the item was seen
+    pub count: u64,
+    /// Maximum over-estimation of `count`
+    pub error: u64,
+}
+
+/// Space-Saving sketch estimating the most frequent items of a stream, and
+/// their frequencies, in memory bounded by its `capacity`.
+///
+/// See the [module documentation](crate::space_saving) for the guarantees of
+/// the estimates. The counters are kept in a binary min-heap ordered by count,
+/// so that the least frequent item is found and evicted in `O(log capacity)`
+/// when a new item is added to a full sketch.
+///
+/// ```
+/// # use datafusion_functions_aggregate::space_saving::SpaceSaving;
+/// let mut sketch = SpaceSaving::new(2);
+/// for item in ["a", "b", "a", "c", "a"] {
+///     sketch.add(item);
+/// }
+/// let top = sketch.top_k(1);
+/// assert_eq!(*top[0].0, "a");
+/// assert_eq!(top[0].1.count, 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpaceSaving<T>
+where
+    T: Hash + Eq,
+{
+    capacity: usize,
+    /// The tracked items and their counters, a binary min-heap by count
+    heap: Vec<(T, Counter)>,
+    /// The position of each tracked item in `heap`
+    positions: HashMap<T, usize>,
+}
+
+impl<T> SpaceSaving<T>
+where
+    T: Hash + Eq + Clone,
+{
+    /// Creates a new, empty sketch tracking at most `capacity` items
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            heap: Vec::with_capacity(capacity),
+            positions: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Creates a sketch from previously exported counters, for example the
+    /// output of [`Self::counters`] on another instance.
+    ///
+    /// If more than `capacity` counters are passed only the largest ones are
+    /// kept.
+    pub fn new_with_counters(
+        capacity: usize,
+        counters: impl IntoIterator<Item = (T, Counter)>,
+    ) -> Self {
+        let mut sketch = Self::new(capacity);
+        for (item, counter) in counters {
+            match sketch.positions.get(&item) {
+                Some(&position) => sketch.heap[position].1 = counter,
+                None => {
+                    sketch.positions.insert(item.clone(), sketch.heap.len());
+                    sketch.heap.push((item, counter));
+                }
+            }
+        }
+        sketch.truncate();
+        sketch.heapify();
+        sketch
+    }
+
+    /// Maximum number of items tracked by the sketch
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of items currently tracked by the sketch
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true if no item has been added to the sketch
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Iterates over all tracked items and their counters, in no particular
+    /// order
+    pub fn counters(&self) -> impl Iterator<Item = (&T, &Counter)> {
+        self.heap.iter().map(|(item, counter)| (item, counter))
+    }
+
+    /// Adds a single occurrence of `item`
+    pub fn add(&mut self, item: T) {
+        self.add_weighted(item, 1)
+    }
+
+    /// Adds `weight` occurrences of `item`
+    pub fn add_weighted(&mut self, item: T, weight: u64) {
+        if let Some(&position) = self.positions.get(&item) {
+            self.heap[position].1.count += weight;
+            self.sift_down(position);
+            return;
+        }
+
+        if self.heap.len() < self.capacity {
+            self.positions.insert(item.clone(), self.heap.len());
+            self.heap.push((
+                item,
+                Counter {
+                    count: weight,
+                    error: 0,
+                },
+            ));
+            self.sift_up(self.heap.len() - 1);
+            return;
+        }
+
+        // The sketch is full: the new item replaces the least frequent one, at
+        // the root of the heap, and inherits its count as the error bound.
+        let min_count = self.heap[0].1.count;
+        self.positions.remove(&self.heap[0].0);
+        self.positions.insert(item.clone(), 0);
+        self.heap[0] = (
+            item,
+            Counter {
+                count: min_count + weight,
+                error: min_count,
+            },
+        );
+        self.sift_down(0);
+    }
+
+    /// Merge the other [`SpaceSaving`] into this one
+    pub fn merge(&mut self, other: &SpaceSaving<T>) {
+        if other.is_empty() {
+            return;
+        }
+
+        // Items missing from a full sketch may have been seen up to its
+        // minimum count times, so that count is used as their estimate.
+        let self_min = self.min_count();
+        let other_min = other.min_count();
+
+        for (_, counter) in self.heap.iter_mut() {
+            counter.count += other_min;
+            counter.error += other_min;
+        }
+        for (item, other_counter) in other.counters() {
+            match self.positions.get(item) {
+                Some(&position) => {
+                    // undo the estimate added above, the exact value is known
+                    let counter = &mut self.heap[position].1;
+                    counter.count = counter.count - other_min + other_counter.count;
+                    counter.error = counter.error - other_min + other_counter.error;
+                }
+                None => {
+                    self.positions.insert(item.clone(), self.heap.len());
+                    self.heap.push((
+                        item.clone(),
+                        Counter {
+                            count: other_counter.count + self_min,
+                            error: other_counter.error + self_min,
+                        },
+                    ));
+                }
+            }
+        }
+        self.truncate();
+        self.heapify();
+    }
+
+    /// Returns the memory size of the sketch, in bytes, given the size of an
+    /// item, including both its inline `size_of::<T>()` and the memory it
+    /// allocates
+    pub fn size(&self, item_size: impl Fn(&T) -> usize) -> usize {
+        // every item is stored twice, in the heap and as a key of `positions`
+        let allocated = self
+            .heap
+            .iter()
+            .map(|(item, _)| item_size(item).saturating_sub(size_of::<T>()))
+            .sum::<usize>();
+        // hashbrown keeps 1/8 of the buckets empty, with one control byte each
+        let buckets = self.positions.capacity() * 8 / 7;
+        size_of_val(self)
+            + self.heap.capacity() * size_of::<(T, Counter)>()
+            + buckets * (size_of::<(T, usize)>() + 1)
+            + 2 * allocated
+    }
+
+    /// Returns the (at most) `k` most frequent items, ordered by decreasing
+    /// estimated count. Ties are broken by the item ordering so that the
+    /// output is deterministic.
+    pub fn top_k(&self, k: usize) -> Vec<(&T, Counter)>
+    where
+        T: PartialOrd,
+    {
+        let mut items = self
+            .heap
+            .iter()
+            .map(|(item, counter)| (item, *counter))
+            .collect::<Vec<_>>();
+        items.sort_by(|(a_item, a), (b_item, b)| {
+            b.count.cmp(&a.count).then_with(|| {
+                a_item
+                    .partial_cmp(b_item)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+        });
+        items.truncate(k);
+        items
+    }
+
+    /// The smallest count of a full sketch, or 0 if the sketch still has room
+    /// for new items (in which case untracked items have never been seen).
+    fn min_count(&self) -> u64 {
+        if self.heap.len() < self.capacity {
+            return 0;
+        }
+        self.heap[0].1.count
+    }
+
+    /// Only keep the `capacity` items with the largest counts, leaving the
+    /// heap to be rebuilt with [`Self::heapify`]
+    fn truncate(&mut self) {
+        if self.heap.len() <= self.capacity {
+            return;
+        }
+        self.heap
+            .select_nth_unstable_by(self.capacity, |(_, a), (_, b)| {
+                b.count.cmp(&a.count)
+            });
+        for (item, _) in self.heap.drain(self.capacity..) {
+            self.positions.remove(&item);
+        }
+    }
+
+    /// Restores the heap order of all the items
+    fn heapify(&mut self) {
+        for (position, (item, _)) in self.heap.iter().enumerate() {
+            *self.positions.get_mut(item).expect("tracked item") = position;
+        }
+        for position in (0..self.heap.len() / 2).rev() {
+            self.sift_down(position);
+        }
+    }
+
+    /// Moves the item at `position` towards the root while its count is
+    /// smaller than the count of its parent
+    fn sift_up(&mut self, mut position: usize) {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if self.heap[parent].1.count <= self.heap[position].1.count {
+                break;
+            }
+            self.swap(parent, position);
+            position = parent;
+        }
+    }
+
+    /// Moves the item at `position` towards the leaves while its count is
+    /// larger than the count of one of its children
+    fn sift_down(&mut self, mut position: usize) {
+        loop {
+            let left = 2 * position + 1;
+            let right = left + 1;
+            let mut smallest = position;
+            if left < self.heap.len()
+                && self.heap[left].1.count < self.heap[smallest].1.count
+            {
+                smallest = left;
+            }
+            if right < self.heap.len()
+                && self.heap[right].1.count < self.heap[smallest].1.count
+            {
+                smallest = right;
+            }
+            if smallest == position {
+                break;
+            }
+            self.swap(smallest, position);
+            position = smallest;
+        }
+    }
+
+    /// Swaps the items at positions `a` and `b` of the heap
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        *self
+            .positions
+            .get_mut(&self.heap[a].0)
+            .expect("tracked item") = a;
+        *self
+            .positions
+            .get_mut(&self.heap[b].0)
+            .expect("tracked item") = b;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Counter, SpaceSaving};
+    use std::mem::size_of_val;
+
+    #[test]
+    fn test_empty() {
+        let sketch = SpaceSaving::<u64>::new(10);
+        assert!(sketch.is_empty());
+        assert!(sketch.top_k(3).is_empty());
+    }
+
+    #[test]
+    fn test_exact_below_capacity() {
+        let mut sketch = SpaceSaving::<u64>::new(10);
+        for i in 0..5_u64 {
+            for _ in 0..=i {
+                sketch.add(i);
+            }
+        }
+        let top = sketch.top_k(3);
+        assert_eq!(
+            top,
+            vec![
+                (&4, Counter { count: 5, error: 0 }),
+                (&3, Counter { count: 4, error: 0 }),
+                (&2, Counter { count: 3, error: 0 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_evicts_least_frequent() {
+        let mut sketch = SpaceSaving::<&str>::new(3);
+        sketch.add_weighted("a", 5);
+        sketch.add_weighted("b", 3);
+        sketch.add("c");
+        sketch.add("d");
+        sketch.add("c");
+        let mut counters = sketch
+            .counters()
+            .map(|(item, counter)| (*item, *counter))
+            .collect::<Vec<_>>();
+        counters.sort_by_key(|(item, _)| *item);
+        assert_eq!(
+            counters,
+            vec![
+                ("a", Counter { count: 5, error: 0 }),
+                ("b", Counter { count: 3, error: 0 }),
+                ("c", Counter { count: 3, error: 2 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_size_counts_both_copies_of_items() {
+        let mut sketch = SpaceSaving::<String>::new(4);
+        let empty_size = sketch.size(|item| size_of_val(item) + item.capacity());
+        for item in ["a", "bb", "ccc"] {
+            sketch.add(item.repeat(100));
+        }
+        let size = sketch.size(|item| size_of_val(item) + item.capacity());
+        assert_eq!(size - empty_size, 2 * (100 + 200 + 300));
+    }
+
+    #[test]
+    fn test_heavy_hitters_survive_eviction() {
+        let mut sketch = SpaceSaving::<u64>::new(20);
+        for i in 0..10_000_u64 {
+            // every third item is one of two heavy hitters
+            match i % 3 {
+                0 => sketch.add(1_000_000),
+                1 => sketch.add(i),
+                _ => sketch.add(2_000_000 + (i % 2)),
+            }
+        }
+        assert_eq!(sketch.len(), 20);
+        let top = sketch.top_k(3);
+        let mut items = top.iter().map(|(item, _)| **item).collect::<Vec<_>>();
+        assert_eq!(items[0], 1_000_000);
+        items[1..].sort();
+        assert_eq!(&items[1..], &[2_000_000, 2_000_001]);
+        for (_, counter) in top {
+            assert!(counter.count >= counter.error);
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut left = SpaceSaving::<String>::new(5);
+        let mut right = SpaceSaving::<String>::new(5);
+        left.add_weighted("a".to_string(), 10);
+        left.add_weighted("b".to_string(), 3);
+        right.add_weighted("a".to_string(), 2);
+        right.add_weighted("c".to_string(), 7);
+
+        left.merge(&right);
+        let top = left.top_k(3);
+        assert_eq!(
+            top,
+            vec![
+                (
+                    &"a".to_string(),
+                    Counter {
+                        count: 12,
+                        error: 0
+                    }
+                ),
+                (&"c".to_string(), Counter { count: 7, error: 0 }),
+                (&"b".to_string(), Counter { count: 3, error: 0 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_full_sketches() {
+        let mut left = SpaceSaving::<u64>::new(2);
+        let mut right = SpaceSaving::<u64>::new(2);
+        left.extend_test(&[1, 1, 1, 2, 3]);
+        right.extend_test(&[1, 4, 4, 4, 5]);
+
+        left.merge(&right);
+        assert_eq!(left.len(), 2);
+        let top = left.top_k(2);
+        assert_eq!(*top[0].0, 1);
+        assert_eq!(*top[1].0, 4);
+        // the true count is always within [count - error, count]
+        assert!(top[0].1.count >= 4 && top[0].1.count - top[0].1.error <= 4);
+        assert!(top[1].1.count >= 3 && top[1].1.count - top[1].1.error <= 3);
+    }
+
+    #[test]
+    fn test_new_with_counters_truncates() {
+        let counters = (0..10_u64).map(|i| (i, Counter { count: i, error: 0 }));
+        let sketch = SpaceSaving::new_with_counters(3, counters);
+        assert_eq!(sketch.len(), 3);
+        let items = sketch
+            .top_k(3)
+            .into_iter()
+            .map(|(item, _)| *item)
+            .collect::<Vec<_>>();
+        assert_eq!(items, vec![9, 8, 7]);
+    }
+
+    impl SpaceSaving<u64> {
+        fn extend_test(&mut self, items: &[u64]) {
+            for item in items {
+                self.add(*item);
+            }
+        }
+    }
+}
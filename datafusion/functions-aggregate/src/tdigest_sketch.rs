@@ -0,0 +1,423 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the `tdigest_sketch` and `tdigest_merge` aggregates, which produce
+//! serialized [`TDigest`] sketches that can be stored and merged later
+
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::sync::OnceLock;
+
+use arrow::array::ArrayRef;
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::cast::as_binary_array;
+use datafusion_common::{not_impl_err, plan_err, Result, ScalarValue};
+use datafusion_expr::aggregate_doc_sections::DOC_SECTION_APPROXIMATE;
+use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion_expr::type_coercion::aggregates::{INTEGERS, NUMERICS};
+use datafusion_expr::utils::format_state_name;
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, Documentation, Expr, Signature, TypeSignature,
+    Volatility,
+};
+use datafusion_functions_aggregate_common::tdigest::{TDigest, DEFAULT_MAX_SIZE};
+
+use crate::approx_percentile_cont::{
+    validate_input_max_size_expr, ApproxPercentileAccumulator,
+};
+
+create_func!(TDigestSketch, tdigest_sketch_udaf);
+
+/// Builds a serialized t-digest sketch of a set of numbers
+pub fn tdigest_sketch(expression: Expr, centroids: Option<Expr>) -> Expr {
+    let args = if let Some(centroids) = centroids {
+        vec![expression, centroids]
+    } else {
+        vec![expression]
+    };
+    tdigest_sketch_udaf().call(args)
+}
+
+make_udaf_expr_and_func!(
+    TDigestMerge,
+    tdigest_merge,
+    sketch,
+    "Merges serialized t-digest sketches into a single sketch",
+    tdigest_merge_udaf
+);
+
+/// The percentile passed to the wrapped [`ApproxPercentileAccumulator`]s.
+/// It is never evaluated as only the digest is returned.
+const UNUSED_PERCENTILE: f64 = 0.5;
+
+/// See [`TDigest::to_scalar_state()`] for a description of the serialised
+/// state.
+fn tdigest_state_fields(args: StateFieldsArgs) -> Result<Vec<Field>> {
+    Ok(vec![
+        Field::new(
+            format_state_name(args.name, "max_size"),
+            DataType::UInt64,
+            false,
+        ),
+        Field::new(
+            format_state_name(args.name, "sum"),
+            DataType::Float64,
+            false,
+        ),
+        Field::new(
+            format_state_name(args.name, "count"),
+            DataType::UInt64,
+            false,
+        ),
+        Field::new(
+            format_state_name(args.name, "max"),
+            DataType::Float64,
+            false,
+        ),
+        Field::new(
+            format_state_name(args.name, "min"),
+            DataType::Float64,
+            false,
+        ),
+        Field::new_list(
+            format_state_name(args.name, "centroids"),
+            Field::new("item", DataType::Float64, true),
+            false,
+        ),
+    ])
+}
+
+pub struct TDigestSketch {
+    signature: Signature,
+}
+
+impl Debug for TDigestSketch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TDigestSketch")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for TDigestSketch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TDigestSketch {
+    /// Create a new [`TDigestSketch`] aggregate function.
+    pub fn new() -> Self {
+        let mut variants = Vec::with_capacity(NUMERICS.len() * (INTEGERS.len() + 1));
+        // Accept any numeric value, with an optional integer number of centroids
+        for num in NUMERICS {
+            variants.push(TypeSignature::Exact(vec![num.clone()]));
+            for int in INTEGERS {
+                variants.push(TypeSignature::Exact(vec![num.clone(), int.clone()]))
+            }
+        }
+        Self {
+            signature: Signature::one_of(variants, Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for TDigestSketch {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "tdigest_sketch"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        if !arg_types[0].is_numeric() {
+            return plan_err!("tdigest_sketch requires numeric input types");
+        }
+        Ok(DataType::Binary)
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        tdigest_state_fields(args)
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        if acc_args.is_distinct {
+            return not_impl_err!(
+                "tdigest_sketch(DISTINCT) aggregations are not available"
+            );
+        }
+        let max_size = if acc_args.exprs.len() == 2 {
+            validate_input_max_size_expr(&acc_args.exprs[1], "TDIGEST_SKETCH")?
+        } else {
+            DEFAULT_MAX_SIZE
+        };
+        let data_type = acc_args.exprs[0].data_type(acc_args.schema)?;
+        Ok(Box::new(TDigestSketchAccumulator {
+            inner: ApproxPercentileAccumulator::new_with_max_size(
+                UNUSED_PERCENTILE,
+                data_type,
+                max_size,
+            ),
+        }))
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_tdigest_sketch_doc())
+    }
+}
+
+pub struct TDigestMerge {
+    signature: Signature,
+}
+
+impl Debug for TDigestMerge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TDigestMerge")
+            .field("name", &self.name())
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl Default for TDigestMerge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TDigestMerge {
+    /// Create a new [`TDigestMerge`] aggregate function.
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::coercible(
+                vec![DataType::Binary],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl AggregateUDFImpl for TDigestMerge {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "tdigest_merge"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Binary)
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        tdigest_state_fields(args)
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        if acc_args.is_distinct {
+            return not_impl_err!(
+                "tdigest_merge(DISTINCT) aggregations are not available"
+            );
+        }
+        Ok(Box::new(TDigestMergeAccumulator {
+            inner: ApproxPercentileAccumulator::new(UNUSED_PERCENTILE, DataType::Float64),
+        }))
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_tdigest_merge_doc())
+    }
+}
+
+/// Serializes the digest held by `accumulator`
+fn evaluate_digest(accumulator: &mut ApproxPercentileAccumulator) -> Result<ScalarValue> {
+    let digest = TDigest::from_scalar_state(&accumulator.state()?);
+    Ok(ScalarValue::Binary(Some(digest.to_bytes())))
+}
+
+/// Wraps the `approx_percentile_cont` accumulator, returning the serialized
+/// digest instead of a percentile
+#[derive(Debug)]
+struct TDigestSketchAccumulator {
+    inner: ApproxPercentileAccumulator,
+}
+
+impl Accumulator for TDigestSketchAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.inner.update_batch(values)
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        self.inner.merge_batch(states)
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        self.inner.state()
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        evaluate_digest(&mut self.inner)
+    }
+
+    fn size(&self) -> usize {
+        self.inner.size()
+    }
+}
+
+/// Merges serialized digests using the `approx_percentile_cont` accumulator
+#[derive(Debug)]
+struct TDigestMergeAccumulator {
+    inner: ApproxPercentileAccumulator,
+}
+
+impl Accumulator for TDigestMergeAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let array = as_binary_array(&values[0])?;
+        // flatten because we would skip nulls
+        let digests = array
+            .iter()
+            .flatten()
+            .map(TDigest::try_from_bytes)
+            .collect::<Result<Vec<_>>>()?;
+        self.inner.merge_digests(&digests);
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        self.inner.merge_batch(states)
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        self.inner.state()
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        evaluate_digest(&mut self.inner)
+    }
+
+    fn size(&self) -> usize {
+        self.inner.size()
+    }
+}
+
+static TDIGEST_SKETCH_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_tdigest_sketch_doc() -> &'static Documentation {
+    TDIGEST_SKETCH_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_APPROXIMATE)
+            .with_description(
+                "Returns a t-digest sketch of the input values, serialized in a stable binary format. Sketches can be stored, combined with `tdigest_merge` and queried with `tdigest_quantile`.",
+            )
+            .with_syntax_example("tdigest_sketch(expression, centroids)")
+            .with_sql_example(r#"```sql
+> SELECT tdigest_quantile(tdigest_sketch(column_name), 0.75) FROM table_name;
++------------------------------------------------------------------------+
+| tdigest_quantile(tdigest_sketch(table_name.column_name),Float64(0.75)) |
++------------------------------------------------------------------------+
+| 65.0                                                                   |
++------------------------------------------------------------------------+
+```"#)
+            .with_standard_argument("expression", None)
+            .with_argument("centroids", "Number of centroids to use in the t-digest algorithm. _Default is 100_. A higher number results in more accurate approximation but requires more memory.")
+            .build()
+            .unwrap()
+    })
+}
+
+static TDIGEST_MERGE_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_tdigest_merge_doc() -> &'static Documentation {
+    TDIGEST_MERGE_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_APPROXIMATE)
+            .with_description(
+                "Merges t-digest sketches produced by `tdigest_sketch` or `tdigest_merge` into a single sketch of the union of their inputs.",
+            )
+            .with_syntax_example("tdigest_merge(sketch)")
+            .with_sql_example(r#"```sql
+> SELECT tdigest_quantile(tdigest_merge(daily_sketch), 0.5) FROM sketches;
++---------------------------------------------------------------------+
+| tdigest_quantile(tdigest_merge(sketches.daily_sketch),Float64(0.5)) |
++---------------------------------------------------------------------+
+| 23.5                                                                |
++---------------------------------------------------------------------+
+```"#)
+            .with_argument("sketch", "Binary expression containing a sketch produced by `tdigest_sketch` or `tdigest_merge`.")
+            .build()
+            .unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{BinaryArray, Float64Array};
+    use std::sync::Arc;
+
+    fn evaluate_digest_bytes(acc: &mut dyn Accumulator) -> Vec<u8> {
+        match acc.evaluate().unwrap() {
+            ScalarValue::Binary(Some(bytes)) => bytes,
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_sketch_and_merge() -> Result<()> {
+        let mut sketches = vec![];
+        for part in 0..4 {
+            let mut acc = TDigestSketchAccumulator {
+                inner: ApproxPercentileAccumulator::new_with_max_size(
+                    UNUSED_PERCENTILE,
+                    DataType::Float64,
+                    100,
+                ),
+            };
+            let values: ArrayRef = Arc::new(Float64Array::from_iter_values(
+                (part * 250..(part + 1) * 250).map(|v| v as f64),
+            ));
+            acc.update_batch(&[values])?;
+            sketches.push(evaluate_digest_bytes(&mut acc));
+        }
+
+        let mut merge = TDigestMergeAccumulator {
+            inner: ApproxPercentileAccumulator::new(UNUSED_PERCENTILE, DataType::Float64),
+        };
+        let sketches: ArrayRef = Arc::new(BinaryArray::from_iter_values(&sketches));
+        merge.update_batch(&[sketches])?;
+
+        let digest = TDigest::try_from_bytes(&evaluate_digest_bytes(&mut merge))?;
+        assert_eq!(digest.count(), 1000);
+        assert_eq!(digest.min(), 0.0);
+        assert_eq!(digest.max(), 999.0);
+        let median = digest.estimate_quantile(0.5);
+        assert!((median - 500.0).abs() < 10.0, "{median}");
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{ArrayRef, BinaryArray};
+use arrow::datatypes::DataType;
+use datafusion_common::cast::as_binary_array;
+use datafusion_common::Result;
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_BITMAP;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+use datafusion_functions_aggregate_common::bitmap::{bitmap_from_bytes, bitmap_to_bytes};
+
+use crate::utils::make_scalar_function;
+
+#[derive(Debug)]
+pub struct BitmapAndFunc {
+    signature: Signature,
+}
+
+impl Default for BitmapAndFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BitmapAndFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::coercible(
+                vec![DataType::Binary, DataType::Binary],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for BitmapAndFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "bitmap_and"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Binary)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(bitmap_and, vec![])(args)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_bitmap_and_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_bitmap_and_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_BITMAP)
+            .with_description(
+                "Returns the intersection of two roaring bitmaps produced by `bitmap_agg` or `bitmap_or_agg`.",
+            )
+            .with_syntax_example("bitmap_and(bitmap1, bitmap2)")
+            .with_sql_example(
+                r#"```sql
+> select bitmap_cardinality(bitmap_and(monday_users, tuesday_users)) from table_name;
++----------------------------------------------------------------------------------+
+| bitmap_cardinality(bitmap_and(table_name.monday_users,table_name.tuesday_users)) |
++----------------------------------------------------------------------------------+
+| 12                                                                               |
++----------------------------------------------------------------------------------+
+```"#,
+            )
+            .with_argument("bitmap1", "Binary expression containing a serialized roaring bitmap.")
+            .with_argument("bitmap2", "Binary expression containing a serialized roaring bitmap.")
+            .build()
+            .unwrap()
+    })
+}
+
+/// bitmap_and SQL function
+pub fn bitmap_and(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let left = as_binary_array(&args[0])?;
+    let right = as_binary_array(&args[1])?;
+    let result = left
+        .iter()
+        .zip(right.iter())
+        .map(|(left, right)| match (left, right) {
+            (Some(left), Some(right)) => {
+                let intersection = bitmap_from_bytes(left)? & bitmap_from_bytes(right)?;
+                Ok(Some(bitmap_to_bytes(&intersection)))
+            }
+            _ => Ok(None),
+        })
+        .collect::<Result<BinaryArray>>()?;
+    Ok(Arc::new(result))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use arrow::array::{Array, ArrayRef, BinaryArray};
+    use datafusion_common::cast::as_binary_array;
+    use datafusion_functions_aggregate_common::bitmap::{
+        bitmap_from_bytes, bitmap_to_bytes, RoaringBitmap,
+    };
+
+    use crate::bitmap::bitmap_and::bitmap_and;
+
+    #[test]
+    fn test_bitmap_and() {
+        let left = bitmap_to_bytes(&RoaringBitmap::from_iter([1, 2, 3]));
+        let right = bitmap_to_bytes(&RoaringBitmap::from_iter([2, 3, 4]));
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(BinaryArray::from_opt_vec(vec![Some(&left), Some(&left)])),
+            Arc::new(BinaryArray::from_opt_vec(vec![Some(&right), None])),
+        ];
+        let result = bitmap_and(&args).unwrap();
+        let result = as_binary_array(&result).unwrap();
+        assert_eq!(
+            bitmap_from_bytes(result.value(0)).unwrap(),
+            RoaringBitmap::from_iter([2, 3])
+        );
+        assert!(result.is_null(1));
+    }
+}
@@ -0,0 +1,157 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{ArrayRef, UInt64Array};
+use arrow::datatypes::DataType;
+use datafusion_common::cast::as_binary_array;
+use datafusion_common::Result;
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_BITMAP;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+use datafusion_functions_aggregate_common::bitmap::bitmap_from_bytes;
+
+use crate::utils::make_scalar_function;
+
+#[derive(Debug)]
+pub struct BitmapCardinalityFunc {
+    signature: Signature,
+}
+
+impl Default for BitmapCardinalityFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BitmapCardinalityFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::coercible(
+                vec![DataType::Binary],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for BitmapCardinalityFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "bitmap_cardinality"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::UInt64)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(bitmap_cardinality, vec![])(args)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_bitmap_cardinality_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_bitmap_cardinality_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_BITMAP)
+            .with_description(
+                "Returns the number of distinct values contained in a roaring bitmap.",
+            )
+            .with_syntax_example("bitmap_cardinality(bitmap)")
+            .with_sql_example(
+                r#"```sql
+> select bitmap_cardinality(bitmap_agg(user_id)) from table_name;
++----------------------------------------------------+
+| bitmap_cardinality(bitmap_agg(table_name.user_id)) |
++----------------------------------------------------+
+| 42                                                 |
++----------------------------------------------------+
+```"#,
+            )
+            .with_argument(
+                "bitmap",
+                "Binary expression containing a serialized roaring bitmap.",
+            )
+            .build()
+            .unwrap()
+    })
+}
+
+/// bitmap_cardinality SQL function
+pub fn bitmap_cardinality(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let bitmaps = as_binary_array(&args[0])?;
+    let result = bitmaps
+        .iter()
+        .map(|bitmap| {
+            bitmap
+                .map(|bytes| bitmap_from_bytes(bytes).map(|bitmap| bitmap.len()))
+                .transpose()
+        })
+        .collect::<Result<UInt64Array>>()?;
+    Ok(Arc::new(result))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use arrow::array::{Array, ArrayRef, BinaryArray};
+    use datafusion_common::cast::as_uint64_array;
+    use datafusion_functions_aggregate_common::bitmap::{bitmap_to_bytes, RoaringBitmap};
+
+    use crate::bitmap::bitmap_cardinality::bitmap_cardinality;
+
+    #[test]
+    fn test_bitmap_cardinality() {
+        let bitmap = bitmap_to_bytes(&RoaringBitmap::from_iter([1, 5, 100_000]));
+        let empty = bitmap_to_bytes(&RoaringBitmap::new());
+        let args: Vec<ArrayRef> = vec![Arc::new(BinaryArray::from_opt_vec(vec![
+            Some(&bitmap),
+            Some(&empty),
+            None,
+        ]))];
+        let result = bitmap_cardinality(&args).unwrap();
+        let result = as_uint64_array(&result).unwrap();
+        assert_eq!(result.value(0), 3);
+        assert_eq!(result.value(1), 0);
+        assert!(result.is_null(2));
+    }
+
+    #[test]
+    fn test_bitmap_cardinality_invalid() {
+        let args: Vec<ArrayRef> =
+            vec![Arc::new(BinaryArray::from_vec(vec![b"not a bitmap"]))];
+        let err = bitmap_cardinality(&args).unwrap_err();
+        assert!(err.to_string().contains("Invalid roaring bitmap"), "{err}");
+    }
+}
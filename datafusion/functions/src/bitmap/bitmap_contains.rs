@@ -0,0 +1,159 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{ArrayRef, BooleanArray};
+use arrow::datatypes::DataType;
+use datafusion_common::cast::{as_binary_array, as_int64_array};
+use datafusion_common::Result;
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_BITMAP;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+use datafusion_functions_aggregate_common::bitmap::bitmap_from_bytes;
+
+use crate::utils::make_scalar_function;
+
+#[derive(Debug)]
+pub struct BitmapContainsFunc {
+    signature: Signature,
+}
+
+impl Default for BitmapContainsFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BitmapContainsFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::coercible(
+                vec![DataType::Binary, DataType::Int64],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for BitmapContainsFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "bitmap_contains"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(bitmap_contains, vec![])(args)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_bitmap_contains_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_bitmap_contains_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_BITMAP)
+            .with_description(
+                "Returns true if a roaring bitmap contains the given value. Values that cannot be stored in a bitmap (negative or greater than 4294967295) are never contained.",
+            )
+            .with_syntax_example("bitmap_contains(bitmap, value)")
+            .with_sql_example(
+                r#"```sql
+> select bitmap_contains(bitmap_agg(user_id), 42) from table_name;
++-----------------------------------------------------------+
+| bitmap_contains(bitmap_agg(table_name.user_id),Int64(42)) |
++-----------------------------------------------------------+
+| true                                                      |
++-----------------------------------------------------------+
+```"#,
+            )
+            .with_argument("bitmap", "Binary expression containing a serialized roaring bitmap.")
+            .with_argument("value", "Integer expression to look up in the bitmap.")
+            .build()
+            .unwrap()
+    })
+}
+
+/// bitmap_contains SQL function
+pub fn bitmap_contains(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let bitmaps = as_binary_array(&args[0])?;
+    let values = as_int64_array(&args[1])?;
+    let result = bitmaps
+        .iter()
+        .zip(values.iter())
+        .map(|(bitmap, value)| match (bitmap, value) {
+            (Some(bitmap), Some(value)) => {
+                let bitmap = bitmap_from_bytes(bitmap)?;
+                Ok(Some(
+                    u32::try_from(value).is_ok_and(|value| bitmap.contains(value)),
+                ))
+            }
+            _ => Ok(None),
+        })
+        .collect::<Result<BooleanArray>>()?;
+    Ok(Arc::new(result))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use arrow::array::{Array, ArrayRef, BinaryArray, Int64Array};
+    use datafusion_common::cast::as_boolean_array;
+    use datafusion_functions_aggregate_common::bitmap::{bitmap_to_bytes, RoaringBitmap};
+
+    use crate::bitmap::bitmap_contains::bitmap_contains;
+
+    #[test]
+    fn test_bitmap_contains() {
+        let bitmap = bitmap_to_bytes(&RoaringBitmap::from_iter([1, 5, u32::MAX]));
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(BinaryArray::from_vec(vec![&bitmap; 5])),
+            Arc::new(Int64Array::from(vec![
+                Some(5),
+                Some(2),
+                Some(-1),
+                Some(u32::MAX as i64),
+                None,
+            ])),
+        ];
+        let result = bitmap_contains(&args).unwrap();
+        let result = as_boolean_array(&result).unwrap();
+        assert!(result.value(0));
+        assert!(!result.value(1));
+        assert!(!result.value(2));
+        assert!(result.value(3));
+        assert!(result.is_null(4));
+    }
+}
@@ -0,0 +1,59 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! "bitmap" DataFusion functions, operating on the serialized roaring
+//! bitmaps produced by aggregates such as `bitmap_agg`
+
+use datafusion_expr::ScalarUDF;
+use std::sync::Arc;
+
+pub mod bitmap_and;
+pub mod bitmap_cardinality;
+pub mod bitmap_contains;
+
+make_udf_function!(bitmap_and::BitmapAndFunc, BITMAP_AND, bitmap_and);
+make_udf_function!(
+    bitmap_cardinality::BitmapCardinalityFunc,
+    BITMAP_CARDINALITY,
+    bitmap_cardinality
+);
+make_udf_function!(
+    bitmap_contains::BitmapContainsFunc,
+    BITMAP_CONTAINS,
+    bitmap_contains
+);
+
+pub mod expr_fn {
+    export_functions!((
+        bitmap_and,
+        "Returns the intersection of two serialized roaring bitmaps.",
+        left right
+    ),(
+        bitmap_cardinality,
+        "Returns the number of values in a serialized roaring bitmap.",
+        bitmap
+    ),(
+        bitmap_contains,
+        "Returns true if a serialized roaring bitmap contains the given value.",
+        bitmap value
+    ));
+}
+
+/// Returns all DataFusion functions defined in this package
+pub fn functions() -> Vec<Arc<ScalarUDF>> {
+    vec![bitmap_and(), bitmap_cardinality(), bitmap_contains()]
+}
@@ -0,0 +1,319 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{Array, ArrayRef, IntervalMonthDayNanoArray};
+use arrow::datatypes::DataType::{Interval, Timestamp};
+use arrow::datatypes::IntervalUnit::MonthDayNano;
+use arrow::datatypes::TimeUnit::Nanosecond;
+use arrow::datatypes::{DataType, IntervalMonthDayNano};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+
+use datafusion_common::cast::as_timestamp_nanosecond_array;
+use datafusion_common::{exec_err, internal_err, plan_err, Result, ScalarValue};
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_DATETIME;
+use datafusion_expr::simplify::{ExprSimplifyResult, SimplifyInfo};
+use datafusion_expr::{
+    ColumnarValue, Documentation, Expr, ScalarUDFImpl, Signature, Volatility,
+};
+
+use crate::datetime::common::{
+    coerce_to_timestamp_nanos, nanos_to_local, parse_timezone,
+};
+use crate::utils::make_scalar_function;
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+#[derive(Debug)]
+pub struct AgeFunc {
+    signature: Signature,
+}
+
+impl Default for AgeFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AgeFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for AgeFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "age"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(Interval(MonthDayNano))
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        if args.len() != 2 {
+            return internal_err!(
+                "invoke should not be called on a single argument age() before simplification"
+            );
+        }
+        make_scalar_function(age, vec![])(args)
+    }
+
+    /// `age(timestamp)` is `age(current_date, timestamp)`, where
+    /// `current_date` is midnight of the statement start date
+    fn simplify(
+        &self,
+        mut args: Vec<Expr>,
+        info: &dyn SimplifyInfo,
+    ) -> Result<ExprSimplifyResult> {
+        if args.len() != 1 {
+            return Ok(ExprSimplifyResult::Original(args));
+        }
+        let midnight = info
+            .execution_props()
+            .query_execution_start_time
+            .date_naive()
+            .and_time(Default::default())
+            .and_utc()
+            .timestamp_nanos_opt();
+        args.insert(
+            0,
+            Expr::Literal(ScalarValue::TimestampNanosecond(midnight, None)),
+        );
+        Ok(ExprSimplifyResult::Simplified(super::age().call(args)))
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        if !(1..=2).contains(&arg_types.len()) {
+            return plan_err!(
+                "age function requires 1 or 2 arguments, got {}",
+                arg_types.len()
+            );
+        }
+        arg_types
+            .iter()
+            .map(|data_type| coerce_to_timestamp_nanos(self.name(), data_type))
+            .collect()
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_age_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_age_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_DATETIME)
+            .with_description("Subtracts the second timestamp from the first one, producing a symbolic interval that uses years, months and days rather than just days. With a single argument, subtracts it from midnight of the current date. Timestamps with a timezone are compared using their wall clock time in that timezone.")
+            .with_syntax_example("age(expression1[, expression2])")
+            .with_argument(
+                "expression1",
+                "Timestamp expression to operate on. Can be a constant, column, or function.",
+            )
+            .with_argument(
+                "expression2",
+                "Timestamp expression to subtract. Can be a constant, column, or function.",
+            )
+            .with_sql_example(r#"```sql
+> select age('2001-04-10'::timestamp, '1957-06-13'::timestamp);
++--------------------------------------------+
+| age(Utf8("2001-04-10"),Utf8("1957-06-13")) |
++--------------------------------------------+
+| 525 mons 27 days                           |
++--------------------------------------------+
+```"#)
+            .build()
+            .unwrap()
+    })
+}
+
+/// age SQL function
+fn age(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let (Timestamp(Nanosecond, tz1), Timestamp(Nanosecond, tz2)) =
+        (args[0].data_type(), args[1].data_type())
+    else {
+        return exec_err!(
+            "age function requires timestamp arguments, got {} and {}",
+            args[0].data_type(),
+            args[1].data_type()
+        );
+    };
+    let (tz1, tz2) = (
+        parse_timezone(tz1.as_deref())?,
+        parse_timezone(tz2.as_deref())?,
+    );
+    let left = as_timestamp_nanosecond_array(&args[0])?;
+    let right = as_timestamp_nanosecond_array(&args[1])?;
+
+    let result = left
+        .iter()
+        .zip(right.iter())
+        .map(|(left, right)| match (left, right) {
+            (Some(left), Some(right)) => Some(age_between(
+                &nanos_to_local(left, tz1.as_ref()),
+                &nanos_to_local(right, tz2.as_ref()),
+            )),
+            _ => None,
+        })
+        .collect::<IntervalMonthDayNanoArray>();
+    Ok(Arc::new(result))
+}
+
+/// Computes `left - right` field by field, following PostgreSQL:
+/// negative fields borrow from the next larger one, and days borrow the
+/// length of the month of the earlier date time.
+fn age_between(left: &NaiveDateTime, right: &NaiveDateTime) -> IntervalMonthDayNano {
+    // normalize on a positive difference, the sign is restored at the end
+    let (sign, (later, earlier)) = if left >= right {
+        (1, (left, right))
+    } else {
+        (-1, (right, left))
+    };
+
+    let mut nanos = later.nanosecond() as i64 % NANOS_PER_SECOND
+        - earlier.nanosecond() as i64 % NANOS_PER_SECOND;
+    let mut seconds = later.second() as i64 - earlier.second() as i64;
+    let mut minutes = later.minute() as i64 - earlier.minute() as i64;
+    let mut hours = later.hour() as i64 - earlier.hour() as i64;
+    let mut days = later.day() as i32 - earlier.day() as i32;
+    let mut months = (later.year() - earlier.year()) * 12 + later.month() as i32
+        - earlier.month() as i32;
+
+    if nanos < 0 {
+        nanos += NANOS_PER_SECOND;
+        seconds -= 1;
+    }
+    if seconds < 0 {
+        seconds += 60;
+        minutes -= 1;
+    }
+    if minutes < 0 {
+        minutes += 60;
+        hours -= 1;
+    }
+    if hours < 0 {
+        hours += 24;
+        days -= 1;
+    }
+    if days < 0 {
+        days += days_in_month(earlier.year(), earlier.month());
+        months -= 1;
+    }
+
+    let time = ((hours * 60 + minutes) * 60 + seconds) * NANOS_PER_SECOND + nanos;
+    IntervalMonthDayNano::new(sign * months, sign * days, sign as i64 * time)
+}
+
+/// Number of days in the given month
+fn days_in_month(year: i32, month: u32) -> i32 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|date| date.pred_opt())
+        .map(|date| date.day() as i32)
+        .unwrap_or(31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(s: &str) -> NaiveDateTime {
+        s.parse().unwrap()
+    }
+
+    fn interval(months: i32, days: i32, seconds: i64) -> IntervalMonthDayNano {
+        IntervalMonthDayNano::new(months, days, seconds * NANOS_PER_SECOND)
+    }
+
+    #[test]
+    fn test_age_between() {
+        let cases = [
+            (
+                "2001-04-10T00:00:00",
+                "1957-06-13T00:00:00",
+                interval(43 * 12 + 9, 27, 0),
+            ),
+            // days borrow the length of the month of the earlier date
+            (
+                "2024-03-01T00:00:00",
+                "2024-02-15T00:00:00",
+                interval(0, 15, 0),
+            ),
+            (
+                "2024-03-01T10:00:00",
+                "2024-01-31T12:30:00",
+                interval(1, 0, 21 * 3600 + 30 * 60),
+            ),
+            // the sign applies to all the fields
+            (
+                "1957-06-13T00:00:00",
+                "2001-04-10T00:00:00",
+                interval(-(43 * 12 + 9), -27, 0),
+            ),
+        ];
+        for (left, right, expected) in cases {
+            assert_eq!(
+                age_between(&datetime(left), &datetime(right)),
+                expected,
+                "age({left}, {right})"
+            );
+        }
+    }
+
+    #[test]
+    fn test_age_with_timezone() -> Result<()> {
+        use arrow::array::TimestampNanosecondArray;
+
+        // midnight in New York, 4 hours after midnight UTC
+        let left: ArrayRef = Arc::new(
+            TimestampNanosecondArray::from(vec![Some(1_719_806_400_000_000_000), None])
+                .with_timezone("America/New_York"),
+        );
+        let right: ArrayRef = Arc::new(TimestampNanosecondArray::from(vec![
+            Some(1_719_792_000_000_000_000),
+            Some(0),
+        ]));
+        let result = age(&[left, right])?;
+        let result = result
+            .as_any()
+            .downcast_ref::<IntervalMonthDayNanoArray>()
+            .unwrap();
+        // both are midnight of 2024-07-01 in their own timezone
+        assert_eq!(result.value(0), IntervalMonthDayNano::ZERO);
+        assert!(result.is_null(1));
+        Ok(())
+    }
+}
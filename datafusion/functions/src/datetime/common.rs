@@ -15,22 +15,27 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::borrow::Cow;
 use std::sync::Arc;
 
+use arrow::array::timezone::Tz;
 use arrow::array::{
     Array, ArrowPrimitiveType, AsArray, GenericStringArray, PrimitiveArray,
     StringViewArray,
 };
 use arrow::compute::kernels::cast_utils::string_to_timestamp_nanos;
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, TimeUnit};
 use chrono::format::{parse, Parsed, StrftimeItems};
 use chrono::LocalResult::Single;
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{
+    DateTime, MappedLocalTime, NaiveDateTime, Offset, TimeDelta, TimeZone, Utc,
+};
 
 use crate::strings::StringArrayType;
 use datafusion_common::cast::as_generic_string_array;
 use datafusion_common::{
-    exec_err, unwrap_or_internal_err, DataFusionError, Result, ScalarType, ScalarValue,
+    exec_err, plan_err, unwrap_or_internal_err, DataFusionError, Result, ScalarType,
+    ScalarValue,
 };
 use datafusion_expr::ColumnarValue;
 
@@ -66,6 +71,146 @@ pub(crate) fn validate_data_types(args: &[ColumnarValue], name: &str) -> Result<
     Ok(())
 }
 
+/// Returns the instant, as a UTC date time, of the wall clock time `local` in
+/// the timezone `tz`.
+///
+/// Like PostgreSQL, a local time that is ambiguous because of a daylight saving
+/// time transition uses the offset in effect after the transition, and a local
+/// time skipped by a transition uses the offset in effect before it.
+pub(crate) fn local_to_utc(tz: &Tz, local: &NaiveDateTime) -> NaiveDateTime {
+    match tz.offset_from_local_datetime(local) {
+        MappedLocalTime::Single(offset) => *local - offset.fix(),
+        MappedLocalTime::Ambiguous(_, after) => *local - after.fix(),
+        MappedLocalTime::None => {
+            let before = tz.offset_from_utc_datetime(&(*local - TimeDelta::days(1)));
+            *local - before.fix()
+        }
+    }
+}
+
+/// Returns the wall clock time in the timezone `tz` of the instant `utc`
+pub(crate) fn utc_to_local(tz: &Tz, utc: &NaiveDateTime) -> NaiveDateTime {
+    *utc + tz.offset_from_utc_datetime(utc).fix()
+}
+
+/// Converts a nanosecond timestamp to its wall clock time in `tz`, or to the
+/// equivalent naive date time for timestamps without timezone
+pub(crate) fn nanos_to_local(nanos: i64, tz: Option<&Tz>) -> NaiveDateTime {
+    let utc = DateTime::from_timestamp_nanos(nanos).naive_utc();
+    match tz {
+        Some(tz) => utc_to_local(tz, &utc),
+        None => utc,
+    }
+}
+
+/// Inverse of [`nanos_to_local`]
+pub(crate) fn local_to_nanos(local: &NaiveDateTime, tz: Option<&Tz>) -> Result<i64> {
+    let utc = match tz {
+        Some(tz) => local_to_utc(tz, local),
+        None => *local,
+    };
+    utc.and_utc().timestamp_nanos_opt().ok_or_else(|| {
+        DataFusionError::Execution(ERR_NANOSECONDS_NOT_SUPPORTED.to_string())
+    })
+}
+
+/// Parses the timezone of a timestamp type, if any
+pub(crate) fn parse_timezone(tz: Option<&str>) -> Result<Option<Tz>> {
+    Ok(tz.map(|tz| tz.parse()).transpose()?)
+}
+
+/// Returns the type an argument of `function_name` accepting timestamps is
+/// coerced to: timestamps keep their timezone but use nanosecond precision,
+/// dates and strings become timestamps without timezone
+pub(crate) fn coerce_to_timestamp_nanos(
+    function_name: &str,
+    data_type: &DataType,
+) -> Result<DataType> {
+    match data_type {
+        DataType::Timestamp(_, tz) => {
+            Ok(DataType::Timestamp(TimeUnit::Nanosecond, tz.clone()))
+        }
+        DataType::Null
+        | DataType::Date32
+        | DataType::Date64
+        | DataType::Utf8
+        | DataType::LargeUtf8
+        | DataType::Utf8View => Ok(DataType::Timestamp(TimeUnit::Nanosecond, None)),
+        other => plan_err!(
+            "The {function_name} function can only accept timestamps, dates or strings, got {other}"
+        ),
+    }
+}
+
+/// PostgreSQL template patterns and their [`chrono::format::strftime`]
+/// equivalent, longest patterns first
+const POSTGRES_FORMAT_PATTERNS: &[(&str, &str)] = &[
+    ("TZH:TZM", "%:z"),
+    ("MONTH", "%B"),
+    ("HH24", "%H"),
+    ("HH12", "%I"),
+    ("YYYY", "%Y"),
+    ("DAY", "%A"),
+    ("DDD", "%j"),
+    ("MON", "%b"),
+    ("TZH", "%#z"),
+    ("AM", "%p"),
+    ("DD", "%d"),
+    ("DY", "%a"),
+    ("FM", ""),
+    ("HH", "%I"),
+    ("MI", "%M"),
+    ("MM", "%m"),
+    ("MS", "%3f"),
+    ("OF", "%:z"),
+    ("PM", "%p"),
+    ("SS", "%S"),
+    ("US", "%6f"),
+    ("YY", "%y"),
+];
+
+/// Translates a PostgreSQL style format, such as `YYYY-MM-DD HH24:MI:SS`, to
+/// the equivalent [`chrono::format::strftime`] format.
+///
+/// Patterns are matched case insensitively and text in double quotes is
+/// copied as is. Formats containing a `%` are already `chrono` formats and
+/// are returned unchanged.
+///
+/// [`chrono::format::strftime`]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html
+pub(crate) fn postgres_to_chrono_format(format: &str) -> Cow<'_, str> {
+    if format.contains('%') {
+        return Cow::Borrowed(format);
+    }
+
+    let mut result = String::with_capacity(format.len() * 2);
+    let mut rest = format;
+    while let Some(c) = rest.chars().next() {
+        if c == '"' {
+            let literal = &rest[1..];
+            let end = literal.find('"').unwrap_or(literal.len());
+            result.push_str(&literal[..end]);
+            rest = &literal[(end + 1).min(literal.len())..];
+            continue;
+        }
+
+        let pattern = POSTGRES_FORMAT_PATTERNS.iter().find(|(pattern, _)| {
+            rest.get(..pattern.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(pattern))
+        });
+        match pattern {
+            Some((pattern, chrono_format)) => {
+                result.push_str(chrono_format);
+                rest = &rest[pattern.len()..];
+            }
+            None => {
+                result.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    Cow::Owned(result)
+}
+
 /// Accepts a string and parses it using the [`chrono::format::strftime`] specifiers
 /// relative to the provided `timezone`
 ///
@@ -76,6 +221,9 @@ pub(crate) fn validate_data_types(args: &[ColumnarValue], name: &str) -> Result<
 /// If a timestamp is ambiguous, for example as a result of daylight-savings time, an error
 /// will be returned
 ///
+/// PostgreSQL style formats, such as `YYYY-MM-DD HH24:MI:SS`, are also accepted,
+/// see [`postgres_to_chrono_format`]
+///
 /// [`chrono::format::strftime`]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html
 /// [IANA timezones]: https://www.iana.org/time-zones
 pub(crate) fn string_to_datetime_formatted<T: TimeZone>(
@@ -90,7 +238,9 @@ pub(crate) fn string_to_datetime_formatted<T: TimeZone>(
     };
 
     let mut parsed = Parsed::new();
-    parse(&mut parsed, s, StrftimeItems::new(format)).map_err(|e| err(&e.to_string()))?;
+    let chrono_format = postgres_to_chrono_format(format);
+    parse(&mut parsed, s, StrftimeItems::new(&chrono_format))
+        .map_err(|e| err(&e.to_string()))?;
 
     // attempt to parse the string assuming it has a timezone
     let dt = parsed.to_datetime();
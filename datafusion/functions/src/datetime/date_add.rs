@@ -0,0 +1,350 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::timezone::Tz;
+use arrow::array::{Array, ArrayRef, TimestampNanosecondArray};
+use arrow::datatypes::DataType::{Interval, Timestamp, Utf8};
+use arrow::datatypes::IntervalUnit::MonthDayNano;
+use arrow::datatypes::TimeUnit::Nanosecond;
+use arrow::datatypes::{DataType, IntervalMonthDayNano};
+use chrono::{Days, Months, NaiveDateTime};
+
+use datafusion_common::cast::{
+    as_interval_mdn_array, as_string_array, as_timestamp_nanosecond_array,
+};
+use datafusion_common::{exec_err, plan_err, DataFusionError, Result};
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_DATETIME;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+
+use crate::datetime::common::{
+    coerce_to_timestamp_nanos, local_to_nanos, nanos_to_local, parse_timezone,
+};
+use crate::utils::make_scalar_function;
+
+#[derive(Debug)]
+pub struct DateAddFunc {
+    signature: Signature,
+}
+
+impl Default for DateAddFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DateAddFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for DateAddFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "date_add"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(arg_types[0].clone())
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(|args| date_add(args, false), vec![])(args)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_date_add_types(self.name(), arg_types)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_date_add_doc())
+    }
+}
+
+#[derive(Debug)]
+pub struct DateSubFunc {
+    signature: Signature,
+}
+
+impl Default for DateSubFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DateSubFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for DateSubFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "date_sub"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(arg_types[0].clone())
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(|args| date_add(args, true), vec![])(args)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_date_add_types(self.name(), arg_types)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_date_sub_doc())
+    }
+}
+
+fn coerce_date_add_types(name: &str, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+    match arg_types {
+        [timestamp, _] => Ok(vec![
+            coerce_to_timestamp_nanos(name, timestamp)?,
+            Interval(MonthDayNano),
+        ]),
+        [timestamp, _, _] => Ok(vec![
+            coerce_to_timestamp_nanos(name, timestamp)?,
+            Interval(MonthDayNano),
+            Utf8,
+        ]),
+        _ => plan_err!(
+            "{name} function requires 2 or 3 arguments, got {}",
+            arg_types.len()
+        ),
+    }
+}
+
+static DATE_ADD_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_date_add_doc() -> &'static Documentation {
+    DATE_ADD_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_DATETIME)
+            .with_description("Adds an interval to a timestamp. The months and days of the interval are added to the wall clock time in the timezone, so that adding `1 day` across a daylight saving time transition keeps the same local time, while the time part of the interval is added as elapsed time.")
+            .with_syntax_example("date_add(expression, interval[, timezone])")
+            .with_argument(
+                "expression",
+                "Timestamp expression to operate on. Can be a constant, column, or function.",
+            )
+            .with_argument("interval", "Interval to add.")
+            .with_argument(
+                "timezone",
+                "Optional timezone used for the calendar arithmetic, for example `Europe/Brussels`. Defaults to the timezone of `expression`, if any.",
+            )
+            .with_sql_example(r#"```sql
+> select date_add('2021-10-30T12:00:00+02:00'::timestamp, interval '1 day', 'Europe/Brussels');
++---------------------------------------------------------------------------------------------------------------------------------------------------------+
+| date_add(Utf8("2021-10-30T12:00:00+02:00"),IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 1, nanoseconds: 0 }"),Utf8("Europe/Brussels")) |
++---------------------------------------------------------------------------------------------------------------------------------------------------------+
+| 2021-10-31T11:00:00                                                                                                                                     |
++---------------------------------------------------------------------------------------------------------------------------------------------------------+
+```"#)
+            .build()
+            .unwrap()
+    })
+}
+
+static DATE_SUB_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_date_sub_doc() -> &'static Documentation {
+    DATE_SUB_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_DATETIME)
+            .with_description("Subtracts an interval from a timestamp, see `date_add` for the handling of timezones.")
+            .with_syntax_example("date_sub(expression, interval[, timezone])")
+            .with_argument(
+                "expression",
+                "Timestamp expression to operate on. Can be a constant, column, or function.",
+            )
+            .with_argument("interval", "Interval to subtract.")
+            .with_argument(
+                "timezone",
+                "Optional timezone used for the calendar arithmetic, for example `Europe/Brussels`. Defaults to the timezone of `expression`, if any.",
+            )
+            .with_sql_example(r#"```sql
+> select date_sub('2024-03-31T10:00:00'::timestamp, interval '1 month');
++---------------------------------------------------------------------------------------------------------------------------+
+| date_sub(Utf8("2024-03-31T10:00:00"),IntervalMonthDayNano("IntervalMonthDayNano { months: 1, days: 0, nanoseconds: 0 }")) |
++---------------------------------------------------------------------------------------------------------------------------+
+| 2024-02-29T10:00:00                                                                                                       |
++---------------------------------------------------------------------------------------------------------------------------+
+```"#)
+            .build()
+            .unwrap()
+    })
+}
+
+/// date_add and date_sub SQL functions
+fn date_add(args: &[ArrayRef], negate: bool) -> Result<ArrayRef> {
+    let Timestamp(Nanosecond, tz_name) = args[0].data_type() else {
+        return exec_err!(
+            "date_add function requires a timestamp, got {}",
+            args[0].data_type()
+        );
+    };
+    let timestamps = as_timestamp_nanosecond_array(&args[0])?;
+    let intervals = as_interval_mdn_array(&args[1])?;
+    let timezones = args.get(2).map(|tz| as_string_array(tz)).transpose()?;
+    let timestamp_tz = parse_timezone(tz_name.as_deref())?;
+
+    let mut last_tz: Option<(&str, Tz)> = None;
+    let mut result = Vec::with_capacity(timestamps.len());
+    for i in 0..timestamps.len() {
+        if timestamps.is_null(i)
+            || intervals.is_null(i)
+            || timezones.is_some_and(|tz| tz.is_null(i))
+        {
+            result.push(None);
+            continue;
+        }
+
+        let tz = match timezones {
+            Some(timezones) => {
+                let name = timezones.value(i);
+                match &last_tz {
+                    Some((last_name, _)) if *last_name == name => {}
+                    _ => last_tz = Some((name, name.parse()?)),
+                }
+                last_tz.as_ref().map(|(_, tz)| tz)
+            }
+            None => timestamp_tz.as_ref(),
+        };
+
+        let interval = intervals.value(i);
+        let interval = if negate {
+            IntervalMonthDayNano::new(
+                interval.months.wrapping_neg(),
+                interval.days.wrapping_neg(),
+                interval.nanoseconds.wrapping_neg(),
+            )
+        } else {
+            interval
+        };
+        result.push(Some(add_interval(timestamps.value(i), interval, tz)?));
+    }
+
+    let result = TimestampNanosecondArray::from(result);
+    Ok(Arc::new(result.with_timezone_opt(tz_name.clone())))
+}
+
+/// Adds `interval` to the timestamp `nanos`. The months and days are added
+/// to the wall clock time in `tz`, the nanoseconds to the instant.
+pub(crate) fn add_interval(
+    nanos: i64,
+    interval: IntervalMonthDayNano,
+    tz: Option<&Tz>,
+) -> Result<i64> {
+    let out_of_range = || DataFusionError::Execution("timestamp out of range".into());
+
+    let local = nanos_to_local(nanos, tz);
+    let local = add_months(local, interval.months).ok_or_else(out_of_range)?;
+    let local = add_days(local, interval.days).ok_or_else(out_of_range)?;
+    local_to_nanos(&local, tz)?
+        .checked_add(interval.nanoseconds)
+        .ok_or_else(out_of_range)
+}
+
+fn add_months(local: NaiveDateTime, months: i32) -> Option<NaiveDateTime> {
+    let delta = Months::new(months.unsigned_abs());
+    if months >= 0 {
+        local.checked_add_months(delta)
+    } else {
+        local.checked_sub_months(delta)
+    }
+}
+
+fn add_days(local: NaiveDateTime, days: i32) -> Option<NaiveDateTime> {
+    let delta = Days::new(days.unsigned_abs() as u64);
+    if days >= 0 {
+        local.checked_add_days(delta)
+    } else {
+        local.checked_sub_days(delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nanos(s: &str) -> i64 {
+        s.parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap()
+            .timestamp_nanos_opt()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_add_interval_across_dst() {
+        let tz: Tz = "Europe/Brussels".parse().unwrap();
+        let start = nanos("2021-10-30T10:00:00Z");
+
+        // one day keeps the local time, even though 25 hours elapsed
+        let day = IntervalMonthDayNano::new(0, 1, 0);
+        assert_eq!(
+            add_interval(start, day, Some(&tz)).unwrap(),
+            nanos("2021-10-31T11:00:00Z")
+        );
+        // 24 hours are elapsed time
+        let hours = IntervalMonthDayNano::new(0, 0, 24 * 3600 * 1_000_000_000);
+        assert_eq!(
+            add_interval(start, hours, Some(&tz)).unwrap(),
+            nanos("2021-10-31T10:00:00Z")
+        );
+        // without timezone a day is always 24 hours
+        assert_eq!(
+            add_interval(start, day, None).unwrap(),
+            nanos("2021-10-31T10:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_add_months_clamps_day() {
+        let interval = IntervalMonthDayNano::new(-1, 0, 0);
+        assert_eq!(
+            add_interval(nanos("2024-03-31T10:00:00Z"), interval, None).unwrap(),
+            nanos("2024-02-29T10:00:00Z")
+        );
+    }
+}
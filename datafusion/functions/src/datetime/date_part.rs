@@ -31,7 +31,8 @@ use arrow::datatypes::TimeUnit::{Microsecond, Millisecond, Nanosecond, Second};
 use arrow::datatypes::{DataType, TimeUnit};
 
 use datafusion_common::cast::{
-    as_date32_array, as_date64_array, as_int32_array, as_time32_millisecond_array,
+    as_date32_array, as_date64_array, as_int32_array, as_interval_dt_array,
+    as_interval_mdn_array, as_interval_ym_array, as_time32_millisecond_array,
     as_time32_second_array, as_time64_microsecond_array, as_time64_nanosecond_array,
     as_timestamp_microsecond_array, as_timestamp_millisecond_array,
     as_timestamp_nanosecond_array, as_timestamp_second_array,
@@ -194,8 +195,10 @@ impl ScalarUDFImpl for DatePartFunc {
                 IntervalUnit::Millisecond => seconds(array.as_ref(), Millisecond)?,
                 IntervalUnit::Microsecond => seconds(array.as_ref(), Microsecond)?,
                 IntervalUnit::Nanosecond => seconds(array.as_ref(), Nanosecond)?,
-                // century and decade are not supported by `DatePart`, although they are supported in postgres
-                _ => return exec_err!("Date part '{part}' not supported"),
+                // century and decade are not supported by `DatePart`, they are
+                // computed from the year like in postgres
+                IntervalUnit::Century => years_grouped(array.as_ref(), 100)?,
+                IntervalUnit::Decade => years_grouped(array.as_ref(), 10)?,
             }
         } else {
             // special cases that can be extracted (in postgres) but are not interval units
@@ -203,6 +206,8 @@ impl ScalarUDFImpl for DatePartFunc {
                 "qtr" | "quarter" => date_part_f64(array.as_ref(), DatePart::Quarter)?,
                 "doy" => date_part_f64(array.as_ref(), DatePart::DayOfYear)?,
                 "dow" => date_part_f64(array.as_ref(), DatePart::DayOfWeekSunday0)?,
+                "isodow" => isodow(array.as_ref())?,
+                "millennium" | "millennia" => years_grouped(array.as_ref(), 1000)?,
                 "epoch" => epoch(array.as_ref())?,
                 _ => return exec_err!("Date part '{part}' not supported"),
             }
@@ -246,9 +251,13 @@ fn get_date_part_doc() -> &'static Documentation {
     - millisecond
     - microsecond
     - nanosecond
-    - dow (day of the week)
+    - dow (day of the week, Sunday is 0)
+    - isodow (ISO 8601 day of the week, Monday is 1 and Sunday is 7)
     - doy (day of the year)
-    - epoch (seconds since Unix epoch)
+    - decade (year divided by 10)
+    - century
+    - millennium
+    - epoch (seconds since Unix epoch, or total number of seconds of an interval)
 "#,
             )
             .with_argument(
@@ -305,6 +314,64 @@ fn seconds(array: &dyn Array, unit: TimeUnit) -> Result<ArrayRef> {
     }
 }
 
+/// ISO 8601 day of the week, from 1 for Monday to 7 for Sunday
+fn isodow(array: &dyn Array) -> Result<ArrayRef> {
+    let dow = date_part(array, DatePart::DayOfWeekMonday0)?;
+    let dow = as_int32_array(dow.as_ref())?;
+    let r: Float64Array = dow.unary(|dow| (dow + 1) as f64);
+    Ok(Arc::new(r))
+}
+
+/// Invoke [`date_part`] for the year and group it by `size` years (decades,
+/// centuries or millennia), following postgres: there is no year 0, so the
+/// first century spans years 1 to 100, and decades simply divide the year
+fn years_grouped(array: &dyn Array, size: i32) -> Result<ArrayRef> {
+    let years = date_part(array, DatePart::Year)?;
+    let years = as_int32_array(years.as_ref())?;
+    let is_interval = matches!(array.data_type(), Interval(_));
+    // dates use the proleptic Gregorian calendar, where year 0 is 1 BC
+    let r: Float64Array = years.unary(|year| {
+        let group = if is_interval || (size == 10 && year >= 0) {
+            year / size
+        } else if size == 10 {
+            -((8 - (year - 1)) / size)
+        } else if year > 0 {
+            (year + size - 1) / size
+        } else {
+            -((size - 1 - (year - 1)) / size)
+        };
+        group as f64
+    });
+    Ok(Arc::new(r))
+}
+
+/// Total number of seconds of an interval, where like in postgres a year is
+/// 365.25 days and a month 30 days
+fn interval_seconds(array: &dyn Array) -> Result<ArrayRef> {
+    const SECONDS_IN_A_DAY: f64 = 86400_f64;
+    const SECONDS_IN_A_MONTH: f64 = 30_f64 * SECONDS_IN_A_DAY;
+    const SECONDS_IN_A_YEAR: f64 = 365.25_f64 * SECONDS_IN_A_DAY;
+    let months_seconds = |months: i32| {
+        (months / 12) as f64 * SECONDS_IN_A_YEAR
+            + (months % 12) as f64 * SECONDS_IN_A_MONTH
+    };
+
+    let f: Float64Array = match array.data_type() {
+        Interval(YearMonth) => as_interval_ym_array(array)?.unary(months_seconds),
+        Interval(DayTime) => as_interval_dt_array(array)?.unary(|interval| {
+            interval.days as f64 * SECONDS_IN_A_DAY
+                + interval.milliseconds as f64 / 1_000_f64
+        }),
+        Interval(MonthDayNano) => as_interval_mdn_array(array)?.unary(|interval| {
+            months_seconds(interval.months)
+                + interval.days as f64 * SECONDS_IN_A_DAY
+                + interval.nanoseconds as f64 / 1_000_000_000_f64
+        }),
+        d => return exec_err!("Cannot convert {d:?} to epoch"),
+    };
+    Ok(Arc::new(f))
+}
+
 fn epoch(array: &dyn Array) -> Result<ArrayRef> {
     const SECONDS_IN_A_DAY: f64 = 86400_f64;
 
@@ -331,7 +398,8 @@ fn epoch(array: &dyn Array) -> Result<ArrayRef> {
         Time64(Nanosecond) => {
             as_time64_nanosecond_array(array)?.unary(|x| x as f64 / 1_000_000_000_f64)
         }
-        Interval(_) | Duration(_) => return seconds(array, Second),
+        Interval(_) => return interval_seconds(array),
+        Duration(_) => return seconds(array, Second),
         d => return exec_err!("Cannot convert {d:?} to epoch"),
     };
     Ok(Arc::new(f))
@@ -0,0 +1,304 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `justify_days`, `justify_hours` and `justify_interval`, which normalize
+//! intervals following PostgreSQL: 24 hours make a day and 30 days a month
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{ArrayRef, IntervalMonthDayNanoArray};
+use arrow::datatypes::DataType::Interval;
+use arrow::datatypes::IntervalUnit::MonthDayNano;
+use arrow::datatypes::{DataType, IntervalMonthDayNano};
+
+use datafusion_common::cast::as_interval_mdn_array;
+use datafusion_common::{DataFusionError, Result};
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_DATETIME;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+
+use crate::utils::make_scalar_function;
+
+const NANOS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000_000;
+const DAYS_PER_MONTH: i32 = 30;
+
+/// Defines a `ScalarUDFImpl` normalizing a single interval with `$JUSTIFY`
+macro_rules! make_justify_function {
+    ($UDF:ident, $NAME:literal, $JUSTIFY:ident, $DOC:ident) => {
+        #[derive(Debug)]
+        pub struct $UDF {
+            signature: Signature,
+        }
+
+        impl Default for $UDF {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl $UDF {
+            pub fn new() -> Self {
+                Self {
+                    signature: Signature::coercible(
+                        vec![Interval(MonthDayNano)],
+                        Volatility::Immutable,
+                    ),
+                }
+            }
+        }
+
+        impl ScalarUDFImpl for $UDF {
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn name(&self) -> &str {
+                $NAME
+            }
+
+            fn signature(&self) -> &Signature {
+                &self.signature
+            }
+
+            fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+                Ok(Interval(MonthDayNano))
+            }
+
+            fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+                make_scalar_function(|args| justify(args, $JUSTIFY), vec![])(args)
+            }
+
+            fn documentation(&self) -> Option<&Documentation> {
+                Some($DOC())
+            }
+        }
+    };
+}
+
+make_justify_function!(
+    JustifyDaysFunc,
+    "justify_days",
+    justify_days,
+    get_justify_days_doc
+);
+make_justify_function!(
+    JustifyHoursFunc,
+    "justify_hours",
+    justify_hours,
+    get_justify_hours_doc
+);
+make_justify_function!(
+    JustifyIntervalFunc,
+    "justify_interval",
+    justify_interval,
+    get_justify_interval_doc
+);
+
+fn justify(
+    args: &[ArrayRef],
+    justify: fn(IntervalMonthDayNano) -> Option<IntervalMonthDayNano>,
+) -> Result<ArrayRef> {
+    let result = as_interval_mdn_array(&args[0])?
+        .iter()
+        .map(|interval| match interval {
+            Some(interval) => justify(interval).map(Some),
+            None => Some(None),
+        })
+        .collect::<Option<IntervalMonthDayNanoArray>>()
+        .ok_or_else(|| DataFusionError::Execution("interval out of range".into()))?;
+    Ok(Arc::new(result))
+}
+
+/// Converts each 30 days to a month
+fn justify_days(interval: IntervalMonthDayNano) -> Option<IntervalMonthDayNano> {
+    let IntervalMonthDayNano {
+        mut months,
+        mut days,
+        nanoseconds,
+    } = interval;
+    months = months.checked_add(days / DAYS_PER_MONTH)?;
+    days %= DAYS_PER_MONTH;
+
+    if months > 0 && days < 0 {
+        days += DAYS_PER_MONTH;
+        months -= 1;
+    } else if months < 0 && days > 0 {
+        days -= DAYS_PER_MONTH;
+        months += 1;
+    }
+    Some(IntervalMonthDayNano::new(months, days, nanoseconds))
+}
+
+/// Converts each 24 hours to a day
+fn justify_hours(interval: IntervalMonthDayNano) -> Option<IntervalMonthDayNano> {
+    let IntervalMonthDayNano {
+        months,
+        mut days,
+        mut nanoseconds,
+    } = interval;
+    days = days.checked_add((nanoseconds / NANOS_PER_DAY).try_into().ok()?)?;
+    nanoseconds %= NANOS_PER_DAY;
+
+    if days > 0 && nanoseconds < 0 {
+        nanoseconds += NANOS_PER_DAY;
+        days -= 1;
+    } else if days < 0 && nanoseconds > 0 {
+        nanoseconds -= NANOS_PER_DAY;
+        days += 1;
+    }
+    Some(IntervalMonthDayNano::new(months, days, nanoseconds))
+}
+
+/// Combination of [`justify_hours`] and [`justify_days`], making the signs
+/// of all the fields consistent
+fn justify_interval(interval: IntervalMonthDayNano) -> Option<IntervalMonthDayNano> {
+    let IntervalMonthDayNano {
+        mut months,
+        mut days,
+        mut nanoseconds,
+    } = interval;
+
+    // move the whole days out of the nanoseconds first when it can't change
+    // their sign, which keeps large values from overflowing the days
+    if (days > 0 && nanoseconds > 0) || (days < 0 && nanoseconds < 0) {
+        days = days.checked_add((nanoseconds / NANOS_PER_DAY).try_into().ok()?)?;
+        nanoseconds %= NANOS_PER_DAY;
+    }
+
+    months = months.checked_add(days / DAYS_PER_MONTH)?;
+    days %= DAYS_PER_MONTH;
+
+    if months > 0 && (days < 0 || (days == 0 && nanoseconds < 0)) {
+        days += DAYS_PER_MONTH;
+        months -= 1;
+    } else if months < 0 && (days > 0 || (days == 0 && nanoseconds > 0)) {
+        days -= DAYS_PER_MONTH;
+        months += 1;
+    }
+
+    justify_hours(IntervalMonthDayNano::new(months, days, nanoseconds))
+}
+
+static JUSTIFY_DAYS_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_justify_days_doc() -> &'static Documentation {
+    JUSTIFY_DAYS_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_DATETIME)
+            .with_description("Adjusts an interval so that 30-day time periods are represented as months.")
+            .with_syntax_example("justify_days(interval)")
+            .with_argument("interval", "Interval expression to adjust.")
+            .with_sql_example(r#"```sql
+> select justify_days(interval '35 days');
++----------------------------------------------------------------------------------------------------+
+| justify_days(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 35, nanoseconds: 0 }")) |
++----------------------------------------------------------------------------------------------------+
+| 1 mons 5 days                                                                                      |
++----------------------------------------------------------------------------------------------------+
+```"#)
+            .build()
+            .unwrap()
+    })
+}
+
+static JUSTIFY_HOURS_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_justify_hours_doc() -> &'static Documentation {
+    JUSTIFY_HOURS_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_DATETIME)
+            .with_description("Adjusts an interval so that 24-hour time periods are represented as days.")
+            .with_syntax_example("justify_hours(interval)")
+            .with_argument("interval", "Interval expression to adjust.")
+            .with_sql_example(r#"```sql
+> select justify_hours(interval '27 hours');
++-----------------------------------------------------------------------------------------------------------------+
+| justify_hours(IntervalMonthDayNano("IntervalMonthDayNano { months: 0, days: 0, nanoseconds: 97200000000000 }")) |
++-----------------------------------------------------------------------------------------------------------------+
+| 1 days 3 hours                                                                                                  |
++-----------------------------------------------------------------------------------------------------------------+
+```"#)
+            .build()
+            .unwrap()
+    })
+}
+
+static JUSTIFY_INTERVAL_DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_justify_interval_doc() -> &'static Documentation {
+    JUSTIFY_INTERVAL_DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_DATETIME)
+            .with_description("Adjusts an interval using `justify_days` and `justify_hours`, with additional sign adjustments so that all the fields have the same sign.")
+            .with_syntax_example("justify_interval(interval)")
+            .with_argument("interval", "Interval expression to adjust.")
+            .with_sql_example(r#"```sql
+> select justify_interval(interval '1 mon -1 hour');
++--------------------------------------------------------------------------------------------------------------------+
+| justify_interval(IntervalMonthDayNano("IntervalMonthDayNano { months: 1, days: 0, nanoseconds: -3600000000000 }")) |
++--------------------------------------------------------------------------------------------------------------------+
+| 29 days 23 hours                                                                                                   |
++--------------------------------------------------------------------------------------------------------------------+
+```"#)
+            .build()
+            .unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NANOS_PER_HOUR: i64 = NANOS_PER_DAY / 24;
+
+    fn interval(months: i32, days: i32, hours: i64) -> IntervalMonthDayNano {
+        IntervalMonthDayNano::new(months, days, hours * NANOS_PER_HOUR)
+    }
+
+    #[test]
+    fn test_justify_days() {
+        assert_eq!(justify_days(interval(0, 35, 0)), Some(interval(1, 5, 0)));
+        assert_eq!(justify_days(interval(1, -5, 0)), Some(interval(0, 25, 0)));
+        assert_eq!(justify_days(interval(-1, 35, 0)), Some(interval(0, 5, 0)));
+        assert_eq!(justify_days(interval(i32::MAX, 30, 0)), None);
+    }
+
+    #[test]
+    fn test_justify_hours() {
+        assert_eq!(justify_hours(interval(0, 0, 27)), Some(interval(0, 1, 3)));
+        assert_eq!(justify_hours(interval(0, 1, -3)), Some(interval(0, 0, 21)));
+        assert_eq!(justify_hours(interval(0, -1, 27)), Some(interval(0, 0, 3)));
+    }
+
+    #[test]
+    fn test_justify_interval() {
+        assert_eq!(
+            justify_interval(interval(1, 0, -1)),
+            Some(interval(0, 29, 23))
+        );
+        assert_eq!(
+            justify_interval(interval(0, 40, 30)),
+            Some(interval(1, 11, 6))
+        );
+        assert_eq!(
+            justify_interval(interval(-1, 2, 0)),
+            Some(interval(0, -28, 0))
+        );
+    }
+}
@@ -0,0 +1,176 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{Array, ArrayRef, Date32Array};
+use arrow::datatypes::DataType::{Date32, Timestamp};
+use arrow::datatypes::TimeUnit::Nanosecond;
+use arrow::datatypes::{DataType, Date32Type};
+use chrono::{Datelike, Months, NaiveDate};
+
+use datafusion_common::cast::{as_date32_array, as_timestamp_nanosecond_array};
+use datafusion_common::{exec_err, plan_err, Result};
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_DATETIME;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+
+use crate::datetime::common::{
+    coerce_to_timestamp_nanos, nanos_to_local, parse_timezone,
+};
+use crate::utils::make_scalar_function;
+
+#[derive(Debug)]
+pub struct LastDayFunc {
+    signature: Signature,
+}
+
+impl Default for LastDayFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LastDayFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for LastDayFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "last_day"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(Date32)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(last_day, vec![])(args)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        match arg_types {
+            [Date32] => Ok(vec![Date32]),
+            [other] => Ok(vec![coerce_to_timestamp_nanos(self.name(), other)?]),
+            _ => plan_err!(
+                "last_day function requires 1 argument, got {}",
+                arg_types.len()
+            ),
+        }
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_last_day_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_last_day_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_DATETIME)
+            .with_description("Returns the last day of the month of a date or timestamp. Timestamps with a timezone use their date in that timezone.")
+            .with_syntax_example("last_day(expression)")
+            .with_argument(
+                "expression",
+                "Date or timestamp expression to operate on. Can be a constant, column, or function.",
+            )
+            .with_sql_example(r#"```sql
+> select last_day('2024-02-10'::date);
++------------------------------+
+| last_day(Utf8("2024-02-10")) |
++------------------------------+
+| 2024-02-29                   |
++------------------------------+
+```"#)
+            .build()
+            .unwrap()
+    })
+}
+
+/// last_day SQL function
+fn last_day(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let result: Date32Array = match args[0].data_type() {
+        Date32 => as_date32_array(&args[0])?
+            .iter()
+            .map(|days| {
+                days.map(|days| last_day_of_month(Date32Type::to_naive_date(days)))
+            })
+            .collect(),
+        Timestamp(Nanosecond, tz) => {
+            let tz = parse_timezone(tz.as_deref())?;
+            as_timestamp_nanosecond_array(&args[0])?
+                .iter()
+                .map(|nanos| {
+                    nanos.map(|nanos| {
+                        last_day_of_month(nanos_to_local(nanos, tz.as_ref()).date())
+                    })
+                })
+                .collect()
+        }
+        other => return exec_err!("last_day function does not support {other}"),
+    };
+    Ok(Arc::new(result))
+}
+
+/// Returns the last day of the month of `date`, as days since the epoch
+fn last_day_of_month(date: NaiveDate) -> i32 {
+    let first = date.with_day(1).expect("first day of month is valid");
+    let last = first
+        .checked_add_months(Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(NaiveDate::MAX);
+    Date32Type::from_naive_date(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_day_of_month() {
+        let cases = [
+            ("2024-02-10", "2024-02-29"),
+            ("2023-02-28", "2023-02-28"),
+            ("2023-12-01", "2023-12-31"),
+            ("2023-04-30", "2023-04-30"),
+        ];
+        for (date, expected) in cases {
+            let date = date.parse::<NaiveDate>().unwrap();
+            let expected = expected.parse::<NaiveDate>().unwrap();
+            assert_eq!(
+                last_day_of_month(date),
+                Date32Type::from_naive_date(expected)
+            );
+        }
+    }
+}
@@ -0,0 +1,221 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{Array, ArrayRef, IntervalMonthDayNanoBuilder};
+use arrow::datatypes::DataType::{Float64, Int32, Interval};
+use arrow::datatypes::IntervalUnit::MonthDayNano;
+use arrow::datatypes::{DataType, IntervalMonthDayNano};
+
+use datafusion_common::cast::{as_float64_array, as_int32_array};
+use datafusion_common::{exec_err, plan_err, Result, ScalarValue};
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_DATETIME;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, TypeSignature, Volatility,
+};
+
+use crate::utils::make_scalar_function;
+
+const NANOS_PER_SECOND: f64 = 1_000_000_000.0;
+const NANOS_PER_MINUTE: i64 = 60 * 1_000_000_000;
+const NANOS_PER_HOUR: i64 = 60 * NANOS_PER_MINUTE;
+
+/// Names of the arguments of `make_interval`, in order
+const FIELDS: [&str; 7] = ["years", "months", "weeks", "days", "hours", "mins", "secs"];
+
+#[derive(Debug)]
+pub struct MakeIntervalFunc {
+    signature: Signature,
+}
+
+impl Default for MakeIntervalFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MakeIntervalFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::one_of(
+                vec![TypeSignature::Any(0), TypeSignature::UserDefined],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for MakeIntervalFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "make_interval"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(Interval(MonthDayNano))
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        if args.is_empty() {
+            return Ok(ColumnarValue::Scalar(ScalarValue::IntervalMonthDayNano(
+                Some(IntervalMonthDayNano::ZERO),
+            )));
+        }
+        make_scalar_function(make_interval, vec![])(args)
+    }
+
+    fn invoke_no_args(&self, _number_rows: usize) -> Result<ColumnarValue> {
+        self.invoke(&[])
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        if arg_types.len() > FIELDS.len() {
+            return plan_err!(
+                "make_interval function accepts at most {} arguments, got {}",
+                FIELDS.len(),
+                arg_types.len()
+            );
+        }
+        // only the seconds may have a fractional part
+        Ok((0..arg_types.len())
+            .map(|i| if i == 6 { Float64 } else { Int32 })
+            .collect())
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_make_interval_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_make_interval_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_DATETIME)
+            .with_description("Make an interval from years, months, weeks, days, hours, minutes and seconds. Omitted trailing arguments default to zero.")
+            .with_syntax_example("make_interval([years[, months[, weeks[, days[, hours[, mins[, secs]]]]]]])")
+            .with_argument("years", "Number of years of the interval.")
+            .with_argument("months", "Number of months of the interval.")
+            .with_argument("weeks", "Number of weeks of the interval, converted to 7 days each.")
+            .with_argument("days", "Number of days of the interval.")
+            .with_argument("hours", "Number of hours of the interval.")
+            .with_argument("mins", "Number of minutes of the interval.")
+            .with_argument("secs", "Number of seconds of the interval. May have a fractional part.")
+            .with_sql_example(r#"```sql
+> select make_interval(1, 2, 0, 3, 4, 5, 6.5);
++-----------------------------------------------------------------------------------+
+| make_interval(Int64(1),Int64(2),Int64(0),Int64(3),Int64(4),Int64(5),Float64(6.5)) |
++-----------------------------------------------------------------------------------+
+| 14 mons 3 days 4 hours 5 mins 6.500000000 secs                                    |
++-----------------------------------------------------------------------------------+
+```"#)
+            .build()
+            .unwrap()
+    })
+}
+
+/// make_interval SQL function
+fn make_interval(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let int_fields = args
+        .iter()
+        .take(6)
+        .map(|arg| as_int32_array(arg))
+        .collect::<Result<Vec<_>>>()?;
+    let seconds = args.get(6).map(|arg| as_float64_array(arg)).transpose()?;
+
+    let len = args[0].len();
+    let mut builder = IntervalMonthDayNanoBuilder::with_capacity(len);
+    for i in 0..len {
+        if int_fields.iter().any(|field| field.is_null(i))
+            || seconds.is_some_and(|seconds| seconds.is_null(i))
+        {
+            builder.append_null();
+            continue;
+        }
+
+        let mut fields = [0_i32; 6];
+        for (field, array) in fields.iter_mut().zip(&int_fields) {
+            *field = array.value(i);
+        }
+        let [years, months, weeks, days, hours, mins] = fields;
+        let secs = seconds.map(|seconds| seconds.value(i)).unwrap_or_default();
+
+        let interval = build_interval(years, months, weeks, days, hours, mins, secs);
+        match interval {
+            Some(interval) => builder.append_value(interval),
+            None => return exec_err!("interval out of range"),
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+fn build_interval(
+    years: i32,
+    months: i32,
+    weeks: i32,
+    days: i32,
+    hours: i32,
+    mins: i32,
+    secs: f64,
+) -> Option<IntervalMonthDayNano> {
+    let months = years.checked_mul(12)?.checked_add(months)?;
+    let days = weeks.checked_mul(7)?.checked_add(days)?;
+
+    let secs_nanos = (secs * NANOS_PER_SECOND).round();
+    if !secs_nanos.is_finite() || secs_nanos.abs() >= i64::MAX as f64 {
+        return None;
+    }
+    let nanos = (hours as i64)
+        .checked_mul(NANOS_PER_HOUR)?
+        .checked_add((mins as i64).checked_mul(NANOS_PER_MINUTE)?)?
+        .checked_add(secs_nanos as i64)?;
+
+    Some(IntervalMonthDayNano::new(months, days, nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_interval() {
+        assert_eq!(
+            build_interval(1, 2, 1, 3, 4, 5, 6.5),
+            Some(IntervalMonthDayNano::new(
+                14,
+                10,
+                4 * NANOS_PER_HOUR + 5 * NANOS_PER_MINUTE + 6_500_000_000
+            ))
+        );
+        assert_eq!(
+            build_interval(0, 0, 0, -1, 0, 0, -0.25),
+            Some(IntervalMonthDayNano::new(0, -1, -250_000_000))
+        );
+        assert_eq!(build_interval(i32::MAX, 0, 0, 0, 0, 0, 0.0), None);
+        assert_eq!(build_interval(0, 0, 0, 0, 0, 0, f64::INFINITY), None);
+    }
+}
@@ -0,0 +1,195 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::timezone::Tz;
+use arrow::array::{Array, ArrayRef, TimestampNanosecondBuilder};
+use arrow::datatypes::DataType;
+use arrow::datatypes::DataType::{Float64, Int32, Timestamp, Utf8};
+use arrow::datatypes::TimeUnit::Nanosecond;
+use chrono::{NaiveDate, NaiveTime};
+
+use datafusion_common::cast::{as_float64_array, as_int32_array, as_string_array};
+use datafusion_common::{exec_err, plan_err, Result};
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_DATETIME;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+
+use crate::datetime::common::local_to_nanos;
+use crate::utils::make_scalar_function;
+
+#[derive(Debug)]
+pub struct MakeTimestampFunc {
+    signature: Signature,
+}
+
+impl Default for MakeTimestampFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MakeTimestampFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for MakeTimestampFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "make_timestamp"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        // with a timezone the fields describe an instant, returned in UTC
+        Ok(if arg_types.len() == 7 {
+            Timestamp(Nanosecond, Some("+00:00".into()))
+        } else {
+            Timestamp(Nanosecond, None)
+        })
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(make_timestamp, vec![])(args)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        match arg_types.len() {
+            6 => Ok(vec![Int32, Int32, Int32, Int32, Int32, Float64]),
+            7 => Ok(vec![Int32, Int32, Int32, Int32, Int32, Float64, Utf8]),
+            n => plan_err!("make_timestamp function requires 6 or 7 arguments, got {n}"),
+        }
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_make_timestamp_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_make_timestamp_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_DATETIME)
+            .with_description("Make a timestamp from year/month/day/hour/minute/second component parts. When a timezone is given, the component parts are the wall clock time in that timezone and the result is a UTC timestamp.")
+            .with_syntax_example("make_timestamp(year, month, day, hour, minute, second[, timezone])")
+            .with_argument("year", "Year to use when making the timestamp.")
+            .with_argument("month", "Month to use when making the timestamp, from 1 to 12.")
+            .with_argument("day", "Day to use when making the timestamp, from 1 to 31.")
+            .with_argument("hour", "Hour to use when making the timestamp, from 0 to 23.")
+            .with_argument("minute", "Minute to use when making the timestamp, from 0 to 59.")
+            .with_argument("second", "Seconds to use when making the timestamp, from 0 to 60 (exclusive). May have a fractional part.")
+            .with_argument("timezone", "Optional timezone of the component parts, for example `America/New_York` or `+02:00`.")
+            .with_sql_example(r#"```sql
+> select make_timestamp(2023, 1, 31, 9, 30, 15.5);
++---------------------------------------------------------------------------------+
+| make_timestamp(Int64(2023),Int64(1),Int64(31),Int64(9),Int64(30),Float64(15.5)) |
++---------------------------------------------------------------------------------+
+| 2023-01-31T09:30:15.500                                                         |
++---------------------------------------------------------------------------------+
+```"#)
+            .build()
+            .unwrap()
+    })
+}
+
+/// make_timestamp SQL function
+fn make_timestamp(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let years = as_int32_array(&args[0])?;
+    let months = as_int32_array(&args[1])?;
+    let days = as_int32_array(&args[2])?;
+    let hours = as_int32_array(&args[3])?;
+    let minutes = as_int32_array(&args[4])?;
+    let seconds = as_float64_array(&args[5])?;
+    let timezones = args.get(6).map(|tz| as_string_array(tz)).transpose()?;
+
+    let mut builder = TimestampNanosecondBuilder::with_capacity(years.len());
+    // the same timezone is usually used for all the rows
+    let mut last_tz: Option<(&str, Tz)> = None;
+    for i in 0..years.len() {
+        if years.is_null(i)
+            || months.is_null(i)
+            || days.is_null(i)
+            || hours.is_null(i)
+            || minutes.is_null(i)
+            || seconds.is_null(i)
+            || timezones.is_some_and(|tz| tz.is_null(i))
+        {
+            builder.append_null();
+            continue;
+        }
+
+        let tz = match timezones {
+            Some(timezones) => {
+                let name = timezones.value(i);
+                match &last_tz {
+                    Some((last_name, _)) if *last_name == name => {}
+                    _ => last_tz = Some((name, name.parse()?)),
+                }
+                last_tz.as_ref().map(|(_, tz)| tz)
+            }
+            None => None,
+        };
+
+        let (year, month, day) = (years.value(i), months.value(i), days.value(i));
+        let (hour, minute, second) = (hours.value(i), minutes.value(i), seconds.value(i));
+        let Some(date) = u32::try_from(month)
+            .ok()
+            .zip(u32::try_from(day).ok())
+            .and_then(|(month, day)| NaiveDate::from_ymd_opt(year, month, day))
+        else {
+            return exec_err!("Invalid date for make_timestamp: {year}-{month}-{day}");
+        };
+        if !(0.0..60.0).contains(&second) {
+            return exec_err!("Invalid second for make_timestamp: {second}");
+        }
+        let nanos = (second.fract() * 1_000_000_000.0).round() as u32;
+        let Some(time) = u32::try_from(hour)
+            .ok()
+            .zip(u32::try_from(minute).ok())
+            .and_then(|(hour, minute)| {
+                NaiveTime::from_hms_nano_opt(hour, minute, second as u32, nanos)
+            })
+        else {
+            return exec_err!(
+                "Invalid time for make_timestamp: {hour}:{minute}:{second}"
+            );
+        };
+
+        builder.append_value(local_to_nanos(&date.and_time(time), tz)?);
+    }
+
+    let result = builder.finish();
+    Ok(match timezones {
+        Some(_) => Arc::new(result.with_timezone("+00:00")),
+        None => Arc::new(result),
+    })
+}
@@ -21,15 +21,22 @@ use std::sync::Arc;
 
 use datafusion_expr::ScalarUDF;
 
+pub mod age;
 pub mod common;
 pub mod current_date;
 pub mod current_time;
+pub mod date_add;
 pub mod date_bin;
 pub mod date_part;
 pub mod date_trunc;
 pub mod from_unixtime;
+pub mod justify;
+pub mod last_day;
 pub mod make_date;
+pub mod make_interval;
+pub mod make_timestamp;
 pub mod now;
+pub mod timezone;
 pub mod to_char;
 pub mod to_date;
 pub mod to_local_time;
@@ -37,18 +44,40 @@ pub mod to_timestamp;
 pub mod to_unixtime;
 
 // create UDFs
+make_udf_function!(age::AgeFunc, AGE, age);
 make_udf_function!(current_date::CurrentDateFunc, CURRENT_DATE, current_date);
 make_udf_function!(current_time::CurrentTimeFunc, CURRENT_TIME, current_time);
+make_udf_function!(date_add::DateAddFunc, DATE_ADD, date_add);
+make_udf_function!(date_add::DateSubFunc, DATE_SUB, date_sub);
 make_udf_function!(date_bin::DateBinFunc, DATE_BIN, date_bin);
 make_udf_function!(date_part::DatePartFunc, DATE_PART, date_part);
 make_udf_function!(date_trunc::DateTruncFunc, DATE_TRUNC, date_trunc);
+make_udf_function!(justify::JustifyDaysFunc, JUSTIFY_DAYS, justify_days);
+make_udf_function!(justify::JustifyHoursFunc, JUSTIFY_HOURS, justify_hours);
+make_udf_function!(
+    justify::JustifyIntervalFunc,
+    JUSTIFY_INTERVAL,
+    justify_interval
+);
+make_udf_function!(last_day::LastDayFunc, LAST_DAY, last_day);
 make_udf_function!(make_date::MakeDateFunc, MAKE_DATE, make_date);
+make_udf_function!(
+    make_interval::MakeIntervalFunc,
+    MAKE_INTERVAL,
+    make_interval
+);
+make_udf_function!(
+    make_timestamp::MakeTimestampFunc,
+    MAKE_TIMESTAMP,
+    make_timestamp
+);
 make_udf_function!(
     from_unixtime::FromUnixtimeFunc,
     FROM_UNIXTIME,
     from_unixtime
 );
 make_udf_function!(now::NowFunc, NOW, now);
+make_udf_function!(timezone::TimezoneFunc, TIMEZONE, timezone);
 make_udf_function!(to_char::ToCharFunc, TO_CHAR, to_char);
 make_udf_function!(to_date::ToDateFunc, TO_DATE, to_date);
 make_udf_function!(to_local_time::ToLocalTimeFunc, TO_LOCAL_TIME, to_local_time);
@@ -82,6 +111,10 @@ pub mod expr_fn {
     use datafusion_expr::Expr;
 
     export_functions!((
+        age,
+        "subtracts timestamps, producing a symbolic interval using years, months and days",
+        args,
+    ),(
         current_date,
         "returns current UTC date as a Date32 value",
     ),(
@@ -91,6 +124,14 @@ pub mod expr_fn {
         from_unixtime,
         "converts an integer to RFC3339 timestamp format string",
         unixtime
+    ),(
+        date_add,
+        "adds an interval to a timestamp, using calendar arithmetic for months and days",
+        args,
+    ),(
+        date_sub,
+        "subtracts an interval from a timestamp, using calendar arithmetic for months and days",
+        args,
     ),(
         date_bin,
         "coerces an arbitrary timestamp to the start of the nearest specified interval",
@@ -103,13 +144,41 @@ pub mod expr_fn {
         date_trunc,
         "truncates the date to a specified level of precision",
         part date
+    ),(
+        justify_days,
+        "adjusts an interval so that 30-day time periods are represented as months",
+        interval
+    ),(
+        justify_hours,
+        "adjusts an interval so that 24-hour time periods are represented as days",
+        interval
+    ),(
+        justify_interval,
+        "adjusts an interval using justify_days and justify_hours, with additional sign adjustments",
+        interval
+    ),(
+        last_day,
+        "returns the last day of the month of a date or timestamp",
+        date
     ),(
         make_date,
         "make a date from year, month and day component parts",
         year month day
+    ),(
+        make_interval,
+        "make an interval from years, months, weeks, days, hours, minutes and seconds",
+        args,
+    ),(
+        make_timestamp,
+        "make a timestamp from year, month, day, hour, minute and second component parts, with an optional timezone",
+        args,
     ),(
         now,
         "returns the current timestamp in nanoseconds, using the same value for all instances of now() in same statement",
+    ),(
+        timezone,
+        "converts a timestamp with timezone to the wall clock time in a zone, or a wall clock time in a zone to a timestamp with timezone",
+        zone timestamp
     ),
     (
         to_local_time,
@@ -275,14 +344,24 @@ pub mod expr_fn {
 /// Returns all DataFusion functions defined in this package
 pub fn functions() -> Vec<Arc<ScalarUDF>> {
     vec![
+        age(),
         current_date(),
         current_time(),
+        date_add(),
+        date_sub(),
         date_bin(),
         date_part(),
         date_trunc(),
         from_unixtime(),
+        justify_days(),
+        justify_hours(),
+        justify_interval(),
+        last_day(),
         make_date(),
+        make_interval(),
+        make_timestamp(),
         now(),
+        timezone(),
         to_char(),
         to_date(),
         to_local_time(),
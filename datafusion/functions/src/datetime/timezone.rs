@@ -0,0 +1,198 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::timezone::Tz;
+use arrow::array::{Array, ArrayRef, TimestampNanosecondArray};
+use arrow::datatypes::DataType;
+use arrow::datatypes::DataType::{Timestamp, Utf8};
+use arrow::datatypes::TimeUnit::Nanosecond;
+
+use datafusion_common::cast::{as_string_array, as_timestamp_nanosecond_array};
+use datafusion_common::{exec_err, plan_err, ExprSchema, Result, ScalarValue};
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_DATETIME;
+use datafusion_expr::{
+    ColumnarValue, Documentation, Expr, ScalarUDFImpl, Signature, Volatility,
+};
+
+use crate::datetime::common::{
+    coerce_to_timestamp_nanos, local_to_nanos, nanos_to_local, parse_timezone,
+};
+use crate::utils::make_scalar_function;
+
+/// Implements PostgreSQL's `timezone(zone, timestamp)`, the function behind
+/// `timestamp AT TIME ZONE zone`
+#[derive(Debug)]
+pub struct TimezoneFunc {
+    signature: Signature,
+}
+
+impl Default for TimezoneFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimezoneFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for TimezoneFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "timezone"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        match &arg_types[1] {
+            Timestamp(_, Some(_)) => Ok(Timestamp(Nanosecond, None)),
+            _ => plan_err!(
+                "timezone function requires a constant timezone for timestamps without timezone"
+            ),
+        }
+    }
+
+    fn return_type_from_exprs(
+        &self,
+        args: &[Expr],
+        _schema: &dyn ExprSchema,
+        arg_types: &[DataType],
+    ) -> Result<DataType> {
+        if let Timestamp(_, Some(_)) = &arg_types[1] {
+            return self.return_type(arg_types);
+        }
+        // a timestamp without timezone is the wall clock time in the zone,
+        // the zone becomes part of the result type and must be known
+        let zone = match &args[0] {
+            Expr::Literal(
+                ScalarValue::Utf8(Some(zone))
+                | ScalarValue::LargeUtf8(Some(zone))
+                | ScalarValue::Utf8View(Some(zone)),
+            ) => zone,
+            _ => return self.return_type(arg_types),
+        };
+        if let Err(e) = zone.parse::<Tz>() {
+            return plan_err!("Invalid timezone for timezone function: {e}");
+        }
+        Ok(Timestamp(Nanosecond, Some(zone.as_str().into())))
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(timezone, vec![])(args)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        match arg_types {
+            [_, timestamp] => Ok(vec![
+                Utf8,
+                coerce_to_timestamp_nanos(self.name(), timestamp)?,
+            ]),
+            _ => plan_err!(
+                "timezone function requires 2 arguments, got {}",
+                arg_types.len()
+            ),
+        }
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_timezone_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_timezone_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_DATETIME)
+            .with_description("Converts between timestamps with and without timezone, following PostgreSQL. A timestamp with a timezone is converted to the wall clock time in `zone`, without timezone. A timestamp without timezone is taken as a wall clock time in `zone` and converted to a timestamp with timezone `zone`, which then must be a constant.")
+            .with_syntax_example("timezone(zone, expression)")
+            .with_argument("zone", "Timezone to convert to or from, for example `America/New_York` or `+02:00`.")
+            .with_argument(
+                "expression",
+                "Timestamp expression to operate on. Can be a constant, column, or function.",
+            )
+            .with_sql_example(r#"```sql
+> select timezone('America/New_York', '2024-07-01T12:00:00Z'::timestamp AT TIME ZONE 'UTC');
++-----------------------------------------------------------------+
+| timezone(Utf8("America/New_York"),Utf8("2024-07-01T12:00:00Z")) |
++-----------------------------------------------------------------+
+| 2024-07-01T08:00:00                                             |
++-----------------------------------------------------------------+
+```"#)
+            .build()
+            .unwrap()
+    })
+}
+
+/// timezone SQL function
+fn timezone(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let zones = as_string_array(&args[0])?;
+    let timestamps = as_timestamp_nanosecond_array(&args[1])?;
+    let Timestamp(Nanosecond, timestamp_tz) = args[1].data_type() else {
+        return exec_err!(
+            "timezone function requires a timestamp, got {}",
+            args[1].data_type()
+        );
+    };
+    let timestamp_tz = parse_timezone(timestamp_tz.as_deref())?;
+
+    let mut last_zone: Option<(&str, Tz)> = None;
+    let mut result = Vec::with_capacity(timestamps.len());
+    for i in 0..timestamps.len() {
+        if zones.is_null(i) || timestamps.is_null(i) {
+            result.push(None);
+            continue;
+        }
+        let name = zones.value(i);
+        match &last_zone {
+            Some((last_name, _)) if *last_name == name => {}
+            _ => last_zone = Some((name, name.parse()?)),
+        }
+        let zone = last_zone.as_ref().map(|(_, zone)| zone);
+
+        let value = timestamps.value(i);
+        result.push(Some(match &timestamp_tz {
+            // instant to wall clock time in zone
+            Some(_) => local_to_nanos(&nanos_to_local(value, zone), None)?,
+            // wall clock time in zone to instant
+            None => local_to_nanos(&nanos_to_local(value, None), zone)?,
+        }));
+    }
+
+    let result = TimestampNanosecondArray::from(result);
+    Ok(match &timestamp_tz {
+        Some(_) => Arc::new(result),
+        None => match zones.iter().flatten().next() {
+            Some(zone) => Arc::new(result.with_timezone(zone)),
+            None => Arc::new(result),
+        },
+    })
+}
@@ -207,7 +207,7 @@ Note: `to_timestamp` returns `Timestamp(Nanosecond)`. The supported range for in
             )
             .with_argument(
                 "format_n",
-                "Optional [Chrono format](https://docs.rs/chrono/latest/chrono/format/strftime/index.html) strings to use to parse the expression. Formats will be tried in the order they appear with the first successful one being returned. If none of the formats successfully parse the expression an error will be returned.",
+                "Optional [Chrono format](https://docs.rs/chrono/latest/chrono/format/strftime/index.html) strings to use to parse the expression. Formats will be tried in the order they appear with the first successful one being returned. If none of the formats successfully parse the expression an error will be returned. PostgreSQL style formats, such as `YYYY-MM-DD HH24:MI:SS`, are also accepted.",
             )
             .with_sql_example(r#"```sql
 > select to_timestamp('2023-01-31T09:26:56.123456789-05:00');
@@ -905,6 +905,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn string_to_timestamp_postgres_formatted() {
+        assert_eq!(
+            1599572549000000000,
+            parse_timestamp_formatted("2020-09-08 13:42:29", "YYYY-MM-DD HH24:MI:SS")
+                .unwrap()
+        );
+        assert_eq!(
+            1599572549190855000,
+            parse_timestamp_formatted(
+                "08/09/2020 01:42:29.190855 PM",
+                "dd/mm/yyyy hh12:mi:ss.us AM"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            1599590549000000000,
+            parse_timestamp_formatted(
+                "2020-09-08T13:42:29-05:00",
+                r#"YYYY-MM-DD"T"HH24:MI:SSTZH:TZM"#
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            1642896000000000000,
+            parse_timestamp_formatted("23 Jan 2022", "DD Mon YYYY").unwrap()
+        );
+    }
+
     fn parse_timestamp_formatted(s: &str, format: &str) -> Result<i64, DataFusionError> {
         let result = string_to_timestamp_nanos_formatted(s, format);
         if let Err(e) = &result {
@@ -124,6 +124,18 @@ make_stub_package!(math, "math_expressions");
 pub mod regex;
 make_stub_package!(regex, "regex_expressions");
 
+/// Functions operating on serialized roaring bitmaps.
+/// Enabled via feature flag `bitmap_expressions`
+#[cfg(feature = "bitmap_expressions")]
+pub mod bitmap;
+make_stub_package!(bitmap, "bitmap_expressions");
+
+/// Functions operating on serialized sketches.
+/// Enabled via feature flag `sketch_expressions`
+#[cfg(feature = "sketch_expressions")]
+pub mod sketch;
+make_stub_package!(sketch, "sketch_expressions");
+
 #[cfg(feature = "crypto_expressions")]
 pub mod crypto;
 make_stub_package!(crypto, "crypto_expressions");
@@ -141,6 +153,8 @@ mod utils;
 
 /// Fluent-style API for creating `Expr`s
 pub mod expr_fn {
+    #[cfg(feature = "bitmap_expressions")]
+    pub use super::bitmap::expr_fn::*;
     #[cfg(feature = "core_expressions")]
     pub use super::core::expr_fn::*;
     #[cfg(feature = "crypto_expressions")]
@@ -153,6 +167,8 @@ pub mod expr_fn {
     pub use super::math::expr_fn::*;
     #[cfg(feature = "regex_expressions")]
     pub use super::regex::expr_fn::*;
+    #[cfg(feature = "sketch_expressions")]
+    pub use super::sketch::expr_fn::*;
     #[cfg(feature = "string_expressions")]
     pub use super::string::expr_fn::*;
     #[cfg(feature = "unicode_expressions")]
@@ -168,6 +184,8 @@ pub fn all_default_functions() -> Vec<Arc<ScalarUDF>> {
         .chain(math::functions())
         .chain(regex::functions())
         .chain(crypto::functions())
+        .chain(bitmap::functions())
+        .chain(sketch::functions())
         .chain(unicode::functions())
         .chain(string::functions())
         .collect::<Vec<_>>()
@@ -0,0 +1,210 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Helpers shared by the regular expression functions
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, AsArray, LargeStringArray, StringArray, StringViewArray,
+};
+use arrow::datatypes::{DataType, Int64Type};
+use datafusion_common::{exec_err, plan_err, DataFusionError, Result};
+use regex::{CaptureLocations, Regex};
+
+/// Returns the type a string argument of `function_name` is coerced to:
+/// strings keep their type so that `Utf8View` is handled natively
+pub(crate) fn coerce_string_arg(
+    function_name: &str,
+    data_type: &DataType,
+) -> Result<DataType> {
+    match data_type {
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+            Ok(data_type.clone())
+        }
+        DataType::Null => Ok(DataType::Utf8),
+        DataType::Dictionary(_, value_type) => {
+            coerce_string_arg(function_name, value_type)
+        }
+        other => plan_err!(
+            "The {function_name} function can only accept strings, but got {other}"
+        ),
+    }
+}
+
+/// Returns the compiled regular expression for `pattern` and `flags`,
+/// compiling it only the first time it is seen.
+///
+/// Creating a [`Regex`] is expensive, so like `regexp_replace` the compiled
+/// expressions are memoized in `patterns`, keyed by the pattern with the
+/// flags applied.
+pub(crate) fn compile_and_cache_regex<'a>(
+    function_name: &str,
+    pattern: &str,
+    flags: Option<&str>,
+    patterns: &'a mut HashMap<String, Regex>,
+) -> Result<&'a Regex> {
+    let pattern = match flags {
+        None | Some("") => pattern.to_string(),
+        Some(flags) if flags.contains('g') => {
+            return exec_err!("{function_name}() does not support the \"global\" flag");
+        }
+        Some(flags) => format!("(?{flags}){pattern}"),
+    };
+
+    if !patterns.contains_key(&pattern) {
+        let re = Regex::new(&pattern)
+            .map_err(|err| DataFusionError::External(Box::new(err)))?;
+        patterns.insert(pattern.clone(), re);
+    }
+    Ok(&patterns[&pattern])
+}
+
+/// Returns the byte offset of the 1-based character position `start` in
+/// `value`, or `None` if `value` is shorter than that
+fn char_position_to_byte(
+    function_name: &str,
+    value: &str,
+    start: i64,
+) -> Result<Option<usize>> {
+    if start < 1 {
+        return exec_err!("{function_name}() requires start to be 1 based, got {start}");
+    }
+    let skip = (start - 1) as usize;
+    Ok(value
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(value.len()))
+        .nth(skip))
+}
+
+/// Converts a byte offset of `value` to a 1-based character position
+pub(crate) fn byte_to_char_position(value: &str, byte: usize) -> i64 {
+    value[..byte].chars().count() as i64 + 1
+}
+
+/// Finds the `occurrence`-th (1-based) match of `re` in `value` starting at
+/// byte offset `start`, storing its capture groups in `locations`.
+///
+/// Like PostgreSQL, an empty match doesn't prevent the next match from
+/// starting at the same position.
+fn find_occurrence(
+    re: &Regex,
+    locations: &mut CaptureLocations,
+    value: &str,
+    start: usize,
+    occurrence: i64,
+) -> bool {
+    let mut position = start;
+    let mut found = 0;
+    while position <= value.len() {
+        let Some(m) = re.captures_read_at(locations, value, position) else {
+            return false;
+        };
+        found += 1;
+        if found == occurrence {
+            return true;
+        }
+        position = if m.end() > m.start() {
+            m.end()
+        } else {
+            // skip a character after an empty match
+            value[m.end()..]
+                .chars()
+                .next()
+                .map_or(value.len() + 1, |c| m.end() + c.len_utf8())
+        };
+    }
+    false
+}
+
+/// Returns the byte range of the capture group `group` (0 for the whole
+/// match) of the `occurrence`-th match of `re` in `value`, searching from the
+/// 1-based character position `start`, following PostgreSQL's `regexp_instr`
+/// and `regexp_substr`
+pub(crate) fn find_match_group(
+    function_name: &str,
+    re: &Regex,
+    value: &str,
+    start: i64,
+    occurrence: i64,
+    group: i64,
+) -> Result<Option<(usize, usize)>> {
+    if occurrence < 1 {
+        return exec_err!(
+            "{function_name}() requires the occurrence to be 1 based, got {occurrence}"
+        );
+    }
+    if group < 0 {
+        return exec_err!(
+            "{function_name}() requires a non negative capture group, got {group}"
+        );
+    }
+    let Some(start) = char_position_to_byte(function_name, value, start)? else {
+        return Ok(None);
+    };
+    let mut locations = re.capture_locations();
+    if !find_occurrence(re, &mut locations, value, start, occurrence) {
+        return Ok(None);
+    }
+    Ok(locations.get(group as usize))
+}
+
+/// Builds a string array of the same type as `data_type` from `values`
+pub(crate) fn to_string_array<'a>(
+    data_type: &DataType,
+    values: impl IntoIterator<Item = Option<&'a str>>,
+) -> Result<ArrayRef> {
+    Ok(match data_type {
+        DataType::Utf8 => Arc::new(values.into_iter().collect::<StringArray>()),
+        DataType::LargeUtf8 => Arc::new(values.into_iter().collect::<LargeStringArray>()),
+        DataType::Utf8View => Arc::new(values.into_iter().collect::<StringViewArray>()),
+        other => return exec_err!("Unsupported data type {other:?} for string result"),
+    })
+}
+
+/// Returns the values of an optional `Int64` argument, or `default` for
+/// every row when the argument is absent
+pub(crate) fn int_arg(
+    args: &[ArrayRef],
+    index: usize,
+    default: i64,
+) -> impl Fn(usize) -> Option<i64> + '_ {
+    let array = args
+        .get(index)
+        .map(|array| array.as_primitive::<Int64Type>());
+    move |row| match array {
+        Some(array) if array.is_null(row) => None,
+        Some(array) => Some(array.value(row)),
+        None => Some(default),
+    }
+}
+
+/// Returns the values of an optional `Utf8` argument, or `""` for every row
+/// when the argument is absent
+pub(crate) fn str_arg<'a>(
+    args: &'a [ArrayRef],
+    index: usize,
+) -> impl Fn(usize) -> Option<&'a str> + 'a {
+    let array = args.get(index).map(|array| array.as_string::<i32>());
+    move |row| match array {
+        Some(array) if array.is_null(row) => None,
+        Some(array) => Some(array.value(row)),
+        None => Some(""),
+    }
+}
@@ -19,10 +19,15 @@
 
 use std::sync::Arc;
 
+mod common;
 pub mod regexpcount;
+pub mod regexpextract;
+pub mod regexpinstr;
 pub mod regexplike;
 pub mod regexpmatch;
 pub mod regexpreplace;
+pub mod regexpsplit;
+pub mod regexpsubstr;
 
 // create UDFs
 make_udf_function!(regexpcount::RegexpCountFunc, REGEXP_COUNT, regexp_count);
@@ -33,9 +38,38 @@ make_udf_function!(
     REGEXP_REPLACE,
     regexp_replace
 );
+make_udf_function!(regexpsubstr::RegexpSubstrFunc, REGEXP_SUBSTR, regexp_substr);
+make_udf_function!(regexpinstr::RegexpInstrFunc, REGEXP_INSTR, regexp_instr);
+make_udf_function!(
+    regexpsplit::RegexpSplitToArrayFunc,
+    REGEXP_SPLIT_TO_ARRAY,
+    regexp_split_to_array
+);
+make_udf_function!(
+    regexpextract::RegexpExtractGroupsFunc,
+    REGEXP_EXTRACT_GROUPS,
+    regexp_extract_groups
+);
 
 pub mod expr_fn {
-    use datafusion_expr::Expr;
+    use datafusion_expr::{lit, Expr};
+
+    /// Appends the optional trailing arguments of a function up to the last
+    /// one that is set, using the defaults for the ones before it that aren't
+    fn push_optional_args<const N: usize>(
+        args: &mut Vec<Expr>,
+        optional: [(Option<Expr>, Expr); N],
+    ) {
+        let Some(last) = optional.iter().rposition(|(arg, _)| arg.is_some()) else {
+            return;
+        };
+        args.extend(
+            optional
+                .into_iter()
+                .take(last + 1)
+                .map(|(arg, default)| arg.unwrap_or(default)),
+        );
+    }
 
     /// Returns the number of consecutive occurrences of a regular expression in a string.
     pub fn regexp_count(
@@ -86,6 +120,70 @@ pub mod expr_fn {
         };
         super::regexp_replace().call(args)
     }
+
+    /// Returns the substring of a string that matches a regular expression.
+    pub fn regexp_substr(
+        values: Expr,
+        regex: Expr,
+        start: Option<Expr>,
+        occurrence: Option<Expr>,
+        flags: Option<Expr>,
+        group: Option<Expr>,
+    ) -> Expr {
+        let mut args = vec![values, regex];
+        push_optional_args(
+            &mut args,
+            [
+                (start, lit(1i64)),
+                (occurrence, lit(1i64)),
+                (flags, lit("")),
+                (group, lit(0i64)),
+            ],
+        );
+        super::regexp_substr().call(args)
+    }
+
+    /// Returns the position in a string where a regular expression matches.
+    pub fn regexp_instr(
+        values: Expr,
+        regex: Expr,
+        start: Option<Expr>,
+        occurrence: Option<Expr>,
+        end_option: Option<Expr>,
+        flags: Option<Expr>,
+        group: Option<Expr>,
+    ) -> Expr {
+        let mut args = vec![values, regex];
+        push_optional_args(
+            &mut args,
+            [
+                (start, lit(1i64)),
+                (occurrence, lit(1i64)),
+                (end_option, lit(0i64)),
+                (flags, lit("")),
+                (group, lit(0i64)),
+            ],
+        );
+        super::regexp_instr().call(args)
+    }
+
+    /// Splits a string using a regular expression as the delimiter.
+    pub fn regexp_split_to_array(values: Expr, regex: Expr, flags: Option<Expr>) -> Expr {
+        let mut args = vec![values, regex];
+        if let Some(flags) = flags {
+            args.push(flags);
+        };
+        super::regexp_split_to_array().call(args)
+    }
+
+    /// Returns a struct of the named capture groups of a regular expression match.
+    pub fn regexp_extract_groups(values: Expr, regex: Expr, flags: Option<Expr>) -> Expr {
+        let mut args = vec![values, regex];
+        if let Some(flags) = flags {
+            args.push(flags);
+        };
+        super::regexp_extract_groups().call(args)
+    }
 }
 
 /// Returns all DataFusion functions defined in this package
@@ -95,5 +193,9 @@ pub fn functions() -> Vec<Arc<datafusion_expr::ScalarUDF>> {
         regexp_match(),
         regexp_like(),
         regexp_replace(),
+        regexp_substr(),
+        regexp_instr(),
+        regexp_split_to_array(),
+        regexp_extract_groups(),
     ]
 }
@@ -0,0 +1,320 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Regex expressions
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{Array, ArrayRef, AsArray, StructArray};
+use arrow::datatypes::DataType::{LargeUtf8, Struct, Utf8, Utf8View};
+use arrow::datatypes::{DataType, Field, Fields};
+use datafusion_common::{
+    exec_err, internal_err, plan_datafusion_err, plan_err, DataFusionError, ExprSchema,
+    Result, ScalarValue,
+};
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_REGEX;
+use datafusion_expr::{
+    ColumnarValue, Documentation, Expr, ScalarUDFImpl, Signature, Volatility,
+};
+use regex::Regex;
+
+use crate::regex::common::{coerce_string_arg, compile_and_cache_regex, to_string_array};
+use crate::strings::StringArrayType;
+
+#[derive(Debug)]
+pub struct RegexpExtractGroupsFunc {
+    signature: Signature,
+}
+
+impl Default for RegexpExtractGroupsFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegexpExtractGroupsFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for RegexpExtractGroupsFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "regexp_extract_groups"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        internal_err!(
+            "regexp_extract_groups: return_type called instead of return_type_from_exprs"
+        )
+    }
+
+    fn return_type_from_exprs(
+        &self,
+        args: &[Expr],
+        _schema: &dyn ExprSchema,
+        arg_types: &[DataType],
+    ) -> Result<DataType> {
+        let pattern = match args.get(1) {
+            Some(Expr::Literal(
+                ScalarValue::Utf8(Some(pattern))
+                | ScalarValue::LargeUtf8(Some(pattern))
+                | ScalarValue::Utf8View(Some(pattern)),
+            )) => pattern,
+            _ => {
+                return plan_err!(
+                    "regexp_extract_groups requires its second argument to be a constant string"
+                )
+            }
+        };
+        let re = Regex::new(pattern).map_err(|e| {
+            plan_datafusion_err!(
+                "regexp_extract_groups has an invalid regular expression: {e}"
+            )
+        })?;
+        let fields = group_fields(&re, &arg_types[0]);
+        if fields.is_empty() {
+            return plan_err!(
+                "regexp_extract_groups requires a regular expression with named capture groups, got '{pattern}'"
+            );
+        }
+        Ok(Struct(fields))
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        let pattern = match &args[1] {
+            ColumnarValue::Scalar(
+                ScalarValue::Utf8(pattern)
+                | ScalarValue::LargeUtf8(pattern)
+                | ScalarValue::Utf8View(pattern),
+            ) => pattern.as_deref(),
+            _ => {
+                return exec_err!(
+                    "regexp_extract_groups requires its second argument to be a constant string"
+                )
+            }
+        };
+        let flags = match args.get(2) {
+            None => Some(""),
+            Some(ColumnarValue::Scalar(
+                ScalarValue::Utf8(flags)
+                | ScalarValue::LargeUtf8(flags)
+                | ScalarValue::Utf8View(flags),
+            )) => flags.as_deref(),
+            Some(_) => {
+                return exec_err!(
+                    "regexp_extract_groups requires its flags to be a constant string"
+                )
+            }
+        };
+        let Some(pattern) = pattern else {
+            return exec_err!("regexp_extract_groups requires a non null pattern");
+        };
+
+        let is_scalar = matches!(args[0], ColumnarValue::Scalar(_));
+        let values = args[0].clone().into_array(1)?;
+        let result = regexp_extract_groups(&values, pattern, flags)?;
+        if is_scalar {
+            ScalarValue::try_from_array(&result, 0).map(ColumnarValue::Scalar)
+        } else {
+            Ok(ColumnarValue::Array(result))
+        }
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        if !(2..=3).contains(&arg_types.len()) {
+            return plan_err!(
+                "regexp_extract_groups was called with {} arguments. It requires at least 2 and at most 3.",
+                arg_types.len()
+            );
+        }
+        let mut coerced = vec![coerce_string_arg(self.name(), &arg_types[0])?];
+        coerced.extend([Utf8, Utf8].into_iter().take(arg_types.len() - 1));
+        Ok(coerced)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_regexp_extract_groups_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_regexp_extract_groups_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_REGEX)
+            .with_description("Returns a struct with one field per named capture group of a [regular expression](https://docs.rs/regex/latest/regex/#syntax), holding the text the group matched in the first match. Returns NULL if there is no match.")
+            .with_syntax_example("regexp_extract_groups(str, regexp[, flags])")
+            .with_sql_example(r#"```sql
+> select regexp_extract_groups('2024-10-18', '(?P<year>\d+)-(?P<month>\d+)-(?P<day>\d+)');
++---------------------------------------------------------------------------------------------+
+| regexp_extract_groups(Utf8("2024-10-18"),Utf8("(?P<year>\d+)-(?P<month>\d+)-(?P<day>\d+)")) |
++---------------------------------------------------------------------------------------------+
+| {year: 2024, month: 10, day: 18}                                                            |
++---------------------------------------------------------------------------------------------+
+```"#)
+            .with_standard_argument("str", Some("String"))
+            .with_argument("regexp", "Constant regular expression with named capture groups, written as `(?P<name>...)` or `(?<name>...)`. Groups without a name are not returned.")
+            .with_argument("flags",
+                           r#"Optional regular expression flags that control the behavior of the regular expression. The following flags are supported:
+  - **i**: case-insensitive: letters match both upper and lower case
+  - **m**: multi-line mode: ^ and $ match begin/end of line
+  - **s**: allow . to match \n
+  - **R**: enables CRLF mode: when multi-line mode is enabled, \r\n is used
+  - **U**: swap the meaning of x* and x*?"#)
+            .build()
+            .unwrap()
+    })
+}
+
+/// Returns a nullable field of type `data_type` for every named capture
+/// group of `re`
+fn group_fields(re: &Regex, data_type: &DataType) -> Fields {
+    re.capture_names()
+        .flatten()
+        .map(|name| Field::new(name, data_type.clone(), true))
+        .collect()
+}
+
+/// Flags don't change the names of the capture groups, so the struct fields
+/// are the same as those computed from the pattern alone during planning.
+/// A NULL `flags` returns a NULL struct for every row.
+fn regexp_extract_groups(
+    values: &ArrayRef,
+    pattern: &str,
+    flags: Option<&str>,
+) -> Result<ArrayRef> {
+    let Some(flags) = flags else {
+        let fields = group_fields(
+            &Regex::new(pattern).map_err(|e| DataFusionError::External(Box::new(e)))?,
+            values.data_type(),
+        );
+        return Ok(Arc::new(StructArray::new_null(fields, values.len())));
+    };
+
+    let mut patterns = HashMap::new();
+    let re = compile_and_cache_regex(
+        "regexp_extract_groups",
+        pattern,
+        Some(flags),
+        &mut patterns,
+    )?;
+    match values.data_type() {
+        Utf8 => extract(values.as_string::<i32>(), re, values.data_type()),
+        LargeUtf8 => extract(values.as_string::<i64>(), re, values.data_type()),
+        Utf8View => extract(values.as_string_view(), re, values.data_type()),
+        other => exec_err!(
+            "Unsupported data type {other:?} for function regexp_extract_groups"
+        ),
+    }
+}
+
+fn extract<'a, S: StringArrayType<'a>>(
+    values: S,
+    re: &Regex,
+    data_type: &DataType,
+) -> Result<ArrayRef> {
+    let groups = re
+        .capture_names()
+        .enumerate()
+        .filter_map(|(index, name)| name.map(|_| index))
+        .collect::<Vec<_>>();
+
+    let mut columns = vec![Vec::with_capacity(values.len()); groups.len()];
+    let mut valid = Vec::with_capacity(values.len());
+    for value in values.iter() {
+        let captures = value.and_then(|value| re.captures(value));
+        for (column, group) in columns.iter_mut().zip(&groups) {
+            column.push(
+                captures
+                    .as_ref()
+                    .and_then(|captures| captures.get(*group))
+                    .map(|m| m.as_str()),
+            );
+        }
+        valid.push(captures.is_some());
+    }
+
+    let columns = columns
+        .into_iter()
+        .map(|column| to_string_array(data_type, column))
+        .collect::<Result<Vec<_>>>()?;
+    let array =
+        StructArray::try_new(group_fields(re, data_type), columns, Some(valid.into()))?;
+    Ok(Arc::new(array))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::StringViewArray;
+
+    #[test]
+    fn test_regexp_extract_groups() -> Result<()> {
+        let values: ArrayRef = Arc::new(StringViewArray::from(vec![
+            Some("key=a value=1"),
+            Some("key=b"),
+            Some("nothing"),
+            None,
+        ]));
+        let result = regexp_extract_groups(
+            &values,
+            r"key=(?P<key>\w+)(?: (value)=(?P<value>\d+))?",
+            Some(""),
+        )?;
+        let result = result.as_struct();
+        assert_eq!(result.column_names(), vec!["key", "value"]);
+        assert_eq!(result.column(0).data_type(), &Utf8View);
+        assert_eq!(result.logical_nulls().unwrap().null_count(), 2);
+        assert!(result.is_null(2));
+        assert!(result.is_null(3));
+
+        let keys = result.column(0).as_string_view();
+        let values = result.column(1).as_string_view();
+        assert_eq!(keys.value(0), "a");
+        assert_eq!(values.value(0), "1");
+        assert_eq!(keys.value(1), "b");
+        assert!(values.is_null(1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_regexp_extract_groups_flags() -> Result<()> {
+        let values: ArrayRef =
+            Arc::new(arrow::array::StringArray::from(vec!["ID: X1", "id: y2"]));
+        let result = regexp_extract_groups(&values, r"id: (?<id>\w+)", Some("i"))?;
+        let ids = result.as_struct().column(0).as_string::<i32>().clone();
+        assert_eq!(ids.iter().collect::<Vec<_>>(), vec![Some("X1"), Some("y2")]);
+
+        let result = regexp_extract_groups(&values, r"id: (?<id>\w+)", None)?;
+        assert_eq!(result.null_count(), 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Regex expressions
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{ArrayRef, AsArray, Int64Array};
+use arrow::datatypes::DataType;
+use arrow::datatypes::DataType::{Int64, LargeUtf8, Utf8, Utf8View};
+use datafusion_common::{exec_err, plan_err, Result};
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_REGEX;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+
+use crate::regex::common::{
+    byte_to_char_position, coerce_string_arg, compile_and_cache_regex, find_match_group,
+    int_arg, str_arg,
+};
+use crate::strings::StringArrayType;
+use crate::utils::make_scalar_function;
+
+#[derive(Debug)]
+pub struct RegexpInstrFunc {
+    signature: Signature,
+}
+
+impl Default for RegexpInstrFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegexpInstrFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for RegexpInstrFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "regexp_instr"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(Int64)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(regexp_instr_func, vec![])(args)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        if !(2..=7).contains(&arg_types.len()) {
+            return plan_err!(
+                "regexp_instr was called with {} arguments. It requires at least 2 and at most 7.",
+                arg_types.len()
+            );
+        }
+        let mut coerced = vec![coerce_string_arg(self.name(), &arg_types[0])?];
+        coerced.extend(
+            [Utf8, Int64, Int64, Int64, Utf8, Int64]
+                .into_iter()
+                .take(arg_types.len() - 1),
+        );
+        Ok(coerced)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_regexp_instr_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_regexp_instr_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_REGEX)
+            .with_description("Returns the position in a string where a [regular expression](https://docs.rs/regex/latest/regex/#syntax) match was found, or 0 if there is no match. Positions are 1 based.")
+            .with_syntax_example("regexp_instr(str, regexp[, start[, occurrence[, endoption[, flags[, group]]]]])")
+            .with_sql_example(r#"```sql
+> select regexp_instr('ABCDEFGHI', '(c..)(...)', 1, 1, 0, 'i', 2);
++--------------------------------------------------------------------------------------------------+
+| regexp_instr(Utf8("ABCDEFGHI"),Utf8("(c..)(...)"),Int64(1),Int64(1),Int64(0),Utf8("i"),Int64(2)) |
++--------------------------------------------------------------------------------------------------+
+| 6                                                                                                |
++--------------------------------------------------------------------------------------------------+
+```"#)
+            .with_standard_argument("str", Some("String"))
+            .with_standard_argument("regexp", Some("Regular"))
+            .with_argument("start", "Optional start position (the first position is 1) to search for the regular expression. Can be a constant, column, or function.")
+            .with_argument("occurrence", "Optional number of the match to find, 1 by default.")
+            .with_argument("endoption", "Optional. If 0 (the default) the position of the first character of the match is returned, if 1 the position after the match.")
+            .with_argument("flags",
+                           r#"Optional regular expression flags that control the behavior of the regular expression. The following flags are supported:
+  - **i**: case-insensitive: letters match both upper and lower case
+  - **m**: multi-line mode: ^ and $ match begin/end of line
+  - **s**: allow . to match \n
+  - **R**: enables CRLF mode: when multi-line mode is enabled, \r\n is used
+  - **U**: swap the meaning of x* and x*?"#)
+            .with_argument("group", "Optional capture group to find, where 1 is the first group. Defaults to 0, the whole match.")
+            .build()
+            .unwrap()
+    })
+}
+
+fn regexp_instr_func(args: &[ArrayRef]) -> Result<ArrayRef> {
+    match args[0].data_type() {
+        Utf8 => regexp_instr(args[0].as_string::<i32>(), args),
+        LargeUtf8 => regexp_instr(args[0].as_string::<i64>(), args),
+        Utf8View => regexp_instr(args[0].as_string_view(), args),
+        other => exec_err!("Unsupported data type {other:?} for function regexp_instr"),
+    }
+}
+
+fn regexp_instr<'a, S: StringArrayType<'a>>(
+    values: S,
+    args: &[ArrayRef],
+) -> Result<ArrayRef> {
+    let pattern = str_arg(args, 1);
+    let start = int_arg(args, 2, 1);
+    let occurrence = int_arg(args, 3, 1);
+    let end_option = int_arg(args, 4, 0);
+    let flags = str_arg(args, 5);
+    let group = int_arg(args, 6, 0);
+
+    // creating Regex is expensive so create hashmap for memoization
+    let mut patterns = HashMap::new();
+    let mut result = Vec::with_capacity(values.len());
+    for (row, value) in values.iter().enumerate() {
+        let (
+            Some(value),
+            Some(pattern),
+            Some(start),
+            Some(occurrence),
+            Some(end_option),
+            Some(flags),
+            Some(group),
+        ) = (
+            value,
+            pattern(row),
+            start(row),
+            occurrence(row),
+            end_option(row),
+            flags(row),
+            group(row),
+        )
+        else {
+            result.push(None);
+            continue;
+        };
+
+        if end_option != 0 && end_option != 1 {
+            return exec_err!(
+                "regexp_instr() requires endoption to be 0 or 1, got {end_option}"
+            );
+        }
+        let re =
+            compile_and_cache_regex("regexp_instr", pattern, Some(flags), &mut patterns)?;
+        let position = match find_match_group(
+            "regexp_instr",
+            re,
+            value,
+            start,
+            occurrence,
+            group,
+        )? {
+            Some((start, _)) if end_option == 0 => byte_to_char_position(value, start),
+            Some((_, end)) => byte_to_char_position(value, end),
+            None => 0,
+        };
+        result.push(Some(position));
+    }
+    Ok(Arc::new(Int64Array::from(result)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{LargeStringArray, StringArray};
+
+    #[test]
+    fn test_regexp_instr() -> Result<()> {
+        let values: ArrayRef = Arc::new(LargeStringArray::from(vec![
+            Some("abc123def456"),
+            Some("no digits"),
+            None,
+            Some("ÄÖÜ 7 8 9"),
+        ]));
+        let patterns: ArrayRef = Arc::new(StringArray::from(vec!["[0-9]+"; 4]));
+        let starts: ArrayRef = Arc::new(Int64Array::from(vec![1, 1, 1, 6]));
+        let occurrences: ArrayRef = Arc::new(Int64Array::from(vec![2, 1, 1, 2]));
+
+        let result = regexp_instr_func(&[
+            Arc::clone(&values),
+            Arc::clone(&patterns),
+            Arc::clone(&starts),
+            Arc::clone(&occurrences),
+        ])?;
+        let expected = Int64Array::from(vec![Some(10), Some(0), None, Some(9)]);
+        assert_eq!(
+            result.as_primitive::<arrow::datatypes::Int64Type>(),
+            &expected
+        );
+
+        let end_options: ArrayRef = Arc::new(Int64Array::from(vec![1; 4]));
+        let result =
+            regexp_instr_func(&[values, patterns, starts, occurrences, end_options])?;
+        let expected = Int64Array::from(vec![Some(13), Some(0), None, Some(10)]);
+        assert_eq!(
+            result.as_primitive::<arrow::datatypes::Int64Type>(),
+            &expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_regexp_instr_invalid_end_option() {
+        let values: ArrayRef = Arc::new(StringArray::from(vec!["abc"]));
+        let patterns: ArrayRef = Arc::new(StringArray::from(vec!["b"]));
+        let ones: ArrayRef = Arc::new(Int64Array::from(vec![1]));
+        let end_options: ArrayRef = Arc::new(Int64Array::from(vec![2]));
+        let err =
+            regexp_instr_func(&[values, patterns, Arc::clone(&ones), ones, end_options])
+                .unwrap_err();
+        assert!(err.to_string().contains("endoption to be 0 or 1"), "{err}");
+    }
+}
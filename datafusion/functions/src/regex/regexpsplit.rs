@@ -0,0 +1,255 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Regex expressions
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{Array, ArrayRef, AsArray, ListArray};
+use arrow::buffer::OffsetBuffer;
+use arrow::datatypes::DataType::{LargeUtf8, List, Utf8, Utf8View};
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::{exec_err, plan_err, Result};
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_REGEX;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+use regex::Regex;
+
+use crate::regex::common::{
+    coerce_string_arg, compile_and_cache_regex, str_arg, to_string_array,
+};
+use crate::strings::StringArrayType;
+use crate::utils::make_scalar_function;
+
+#[derive(Debug)]
+pub struct RegexpSplitToArrayFunc {
+    signature: Signature,
+}
+
+impl Default for RegexpSplitToArrayFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegexpSplitToArrayFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for RegexpSplitToArrayFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "regexp_split_to_array"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(List(Arc::new(Field::new(
+            "item",
+            arg_types[0].clone(),
+            true,
+        ))))
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(regexp_split_to_array_func, vec![])(args)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        if !(2..=3).contains(&arg_types.len()) {
+            return plan_err!(
+                "regexp_split_to_array was called with {} arguments. It requires at least 2 and at most 3.",
+                arg_types.len()
+            );
+        }
+        let mut coerced = vec![coerce_string_arg(self.name(), &arg_types[0])?];
+        coerced.extend([Utf8, Utf8].into_iter().take(arg_types.len() - 1));
+        Ok(coerced)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_regexp_split_to_array_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_regexp_split_to_array_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_REGEX)
+            .with_description("Splits a string using a [regular expression](https://docs.rs/regex/latest/regex/#syntax) as the delimiter, returning an array of the parts. `regexp_split_to_table` takes the same arguments and returns one row per part.")
+            .with_syntax_example("regexp_split_to_array(str, regexp[, flags])")
+            .with_sql_example(r#"```sql
+> select regexp_split_to_array('the quick  brown fox', '\s+');
++-----------------------------------------------------------------+
+| regexp_split_to_array(Utf8("the quick  brown fox"),Utf8("\s+")) |
++-----------------------------------------------------------------+
+| [the, quick, brown, fox]                                        |
++-----------------------------------------------------------------+
+```"#)
+            .with_standard_argument("str", Some("String"))
+            .with_standard_argument("regexp", Some("Regular"))
+            .with_argument("flags",
+                           r#"Optional regular expression flags that control the behavior of the regular expression. The following flags are supported:
+  - **i**: case-insensitive: letters match both upper and lower case
+  - **m**: multi-line mode: ^ and $ match begin/end of line
+  - **s**: allow . to match \n
+  - **R**: enables CRLF mode: when multi-line mode is enabled, \r\n is used
+  - **U**: swap the meaning of x* and x*?"#)
+            .build()
+            .unwrap()
+    })
+}
+
+fn regexp_split_to_array_func(args: &[ArrayRef]) -> Result<ArrayRef> {
+    match args[0].data_type() {
+        Utf8 => regexp_split_to_array(args[0].as_string::<i32>(), args),
+        LargeUtf8 => regexp_split_to_array(args[0].as_string::<i64>(), args),
+        Utf8View => regexp_split_to_array(args[0].as_string_view(), args),
+        other => exec_err!(
+            "Unsupported data type {other:?} for function regexp_split_to_array"
+        ),
+    }
+}
+
+fn regexp_split_to_array<'a, S: StringArrayType<'a>>(
+    values: S,
+    args: &[ArrayRef],
+) -> Result<ArrayRef> {
+    let pattern = str_arg(args, 1);
+    let flags = str_arg(args, 2);
+
+    // creating Regex is expensive so create hashmap for memoization
+    let mut patterns = HashMap::new();
+    let mut parts = vec![];
+    let mut lengths = Vec::with_capacity(values.len());
+    let mut valid = Vec::with_capacity(values.len());
+    for (row, value) in values.iter().enumerate() {
+        let (Some(value), Some(pattern), Some(flags)) = (value, pattern(row), flags(row))
+        else {
+            lengths.push(0);
+            valid.push(false);
+            continue;
+        };
+
+        let re = compile_and_cache_regex(
+            "regexp_split_to_array",
+            pattern,
+            Some(flags),
+            &mut patterns,
+        )?;
+        let before = parts.len();
+        split(re, value, &mut parts);
+        lengths.push(parts.len() - before);
+        valid.push(true);
+    }
+
+    let data_type = args[0].data_type();
+    let parts = to_string_array(data_type, parts.into_iter().map(Some))?;
+    let list = ListArray::try_new(
+        Arc::new(Field::new("item", data_type.clone(), true)),
+        OffsetBuffer::from_lengths(lengths),
+        parts,
+        Some(valid.into()),
+    )?;
+    Ok(Arc::new(list))
+}
+
+/// Splits `value` at the matches of `re`, appending the parts to `parts`.
+///
+/// Like PostgreSQL, matches that are empty at the start or end of the string
+/// or directly after the previous match are ignored, so that for example
+/// splitting on `\s*` returns the individual characters of each word.
+fn split<'a>(re: &Regex, value: &'a str, parts: &mut Vec<&'a str>) {
+    let mut previous_end = 0;
+    let mut position = 0;
+    while position <= value.len() {
+        let Some(m) = re.find_at(value, position) else {
+            break;
+        };
+        if m.start() < value.len() && m.end() > previous_end {
+            parts.push(&value[previous_end..m.start()]);
+            previous_end = m.end();
+        }
+        position = if m.end() > m.start() {
+            m.end()
+        } else {
+            value[m.end()..]
+                .chars()
+                .next()
+                .map_or(value.len() + 1, |c| m.end() + c.len_utf8())
+        };
+    }
+    parts.push(&value[previous_end..]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::StringArray;
+
+    fn split_all(pattern: &str, value: &str) -> Vec<String> {
+        let mut parts = vec![];
+        split(&Regex::new(pattern).unwrap(), value, &mut parts);
+        parts.into_iter().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_split() {
+        assert_eq!(
+            split_all(r"\s+", "the quick  brown"),
+            ["the", "quick", "brown"]
+        );
+        assert_eq!(split_all(r"\s*", "hi you"), ["h", "i", "y", "o", "u"]);
+        assert_eq!(split_all(",", ",a,,b,"), ["", "a", "", "b", ""]);
+        assert_eq!(split_all("x", ""), [""]);
+        assert_eq!(split_all("", "äb"), ["ä", "b"]);
+    }
+
+    #[test]
+    fn test_regexp_split_to_array() -> Result<()> {
+        let values: ArrayRef =
+            Arc::new(StringArray::from(vec![Some("a1b22c"), None, Some("xyz")]));
+        let patterns: ArrayRef = Arc::new(StringArray::from(vec!["[0-9]+"; 3]));
+        let result = regexp_split_to_array_func(&[values, patterns])?;
+        let result = result.as_list::<i32>();
+        assert_eq!(result.len(), 3);
+        assert!(result.is_null(1));
+        let first = result.value(0);
+        let first = first.as_string::<i32>();
+        assert_eq!(
+            first.iter().collect::<Vec<_>>(),
+            vec![Some("a"), Some("b"), Some("c")]
+        );
+        assert_eq!(result.value(2).as_string::<i32>().value(0), "xyz");
+        Ok(())
+    }
+}
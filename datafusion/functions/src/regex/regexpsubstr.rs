@@ -0,0 +1,256 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Regex expressions
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use arrow::array::{ArrayRef, AsArray};
+use arrow::datatypes::DataType;
+use arrow::datatypes::DataType::{Int64, LargeUtf8, Utf8, Utf8View};
+use datafusion_common::{exec_err, plan_err, Result};
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_REGEX;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+
+use crate::regex::common::{
+    coerce_string_arg, compile_and_cache_regex, find_match_group, int_arg, str_arg,
+    to_string_array,
+};
+use crate::strings::StringArrayType;
+use crate::utils::make_scalar_function;
+
+#[derive(Debug)]
+pub struct RegexpSubstrFunc {
+    signature: Signature,
+}
+
+impl Default for RegexpSubstrFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegexpSubstrFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for RegexpSubstrFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "regexp_substr"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(arg_types[0].clone())
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(regexp_substr_func, vec![])(args)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        if !(2..=6).contains(&arg_types.len()) {
+            return plan_err!(
+                "regexp_substr was called with {} arguments. It requires at least 2 and at most 6.",
+                arg_types.len()
+            );
+        }
+        let mut coerced = vec![coerce_string_arg(self.name(), &arg_types[0])?];
+        coerced.extend(
+            [Utf8, Int64, Int64, Utf8, Int64]
+                .into_iter()
+                .take(arg_types.len() - 1),
+        );
+        Ok(coerced)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_regexp_substr_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_regexp_substr_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_REGEX)
+            .with_description("Returns the substring that matches a [regular expression](https://docs.rs/regex/latest/regex/#syntax), or NULL if there is no match.")
+            .with_syntax_example("regexp_substr(str, regexp[, start[, occurrence[, flags[, group]]]])")
+            .with_sql_example(r#"```sql
+> select regexp_substr('ABCDEF', 'c(.)(..)', 1, 1, 'i', 2);
++-------------------------------------------------------------------------------------+
+| regexp_substr(Utf8("ABCDEF"),Utf8("c(.)(..)"),Int64(1),Int64(1),Utf8("i"),Int64(2)) |
++-------------------------------------------------------------------------------------+
+| EF                                                                                  |
++-------------------------------------------------------------------------------------+
+```"#)
+            .with_standard_argument("str", Some("String"))
+            .with_standard_argument("regexp", Some("Regular"))
+            .with_argument("start", "Optional start position (the first position is 1) to search for the regular expression. Can be a constant, column, or function.")
+            .with_argument("occurrence", "Optional number of the match to return, 1 by default.")
+            .with_argument("flags",
+                           r#"Optional regular expression flags that control the behavior of the regular expression. The following flags are supported:
+  - **i**: case-insensitive: letters match both upper and lower case
+  - **m**: multi-line mode: ^ and $ match begin/end of line
+  - **s**: allow . to match \n
+  - **R**: enables CRLF mode: when multi-line mode is enabled, \r\n is used
+  - **U**: swap the meaning of x* and x*?"#)
+            .with_argument("group", "Optional capture group to return, where 1 is the first group. Defaults to 0, the whole match.")
+            .build()
+            .unwrap()
+    })
+}
+
+fn regexp_substr_func(args: &[ArrayRef]) -> Result<ArrayRef> {
+    match args[0].data_type() {
+        Utf8 => regexp_substr(args[0].as_string::<i32>(), args),
+        LargeUtf8 => regexp_substr(args[0].as_string::<i64>(), args),
+        Utf8View => regexp_substr(args[0].as_string_view(), args),
+        other => exec_err!("Unsupported data type {other:?} for function regexp_substr"),
+    }
+}
+
+fn regexp_substr<'a, S: StringArrayType<'a>>(
+    values: S,
+    args: &[ArrayRef],
+) -> Result<ArrayRef> {
+    let pattern = str_arg(args, 1);
+    let start = int_arg(args, 2, 1);
+    let occurrence = int_arg(args, 3, 1);
+    let flags = str_arg(args, 4);
+    let group = int_arg(args, 5, 0);
+
+    // creating Regex is expensive so create hashmap for memoization
+    let mut patterns = HashMap::new();
+    let mut result = Vec::with_capacity(values.len());
+    for (row, value) in values.iter().enumerate() {
+        let (
+            Some(value),
+            Some(pattern),
+            Some(start),
+            Some(occurrence),
+            Some(flags),
+            Some(group),
+        ) = (
+            value,
+            pattern(row),
+            start(row),
+            occurrence(row),
+            flags(row),
+            group(row),
+        )
+        else {
+            result.push(None);
+            continue;
+        };
+
+        let re = compile_and_cache_regex(
+            "regexp_substr",
+            pattern,
+            Some(flags),
+            &mut patterns,
+        )?;
+        let range =
+            find_match_group("regexp_substr", re, value, start, occurrence, group)?;
+        result.push(range.map(|(start, end)| &value[start..end]));
+    }
+    to_string_array(args[0].data_type(), result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int64Array, StringArray, StringViewArray};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_regexp_substr() -> Result<()> {
+        let values: ArrayRef = Arc::new(StringViewArray::from(vec![
+            Some("abc123def456"),
+            Some("no digits"),
+            None,
+            Some("ÄÖÜ 7 8 9"),
+        ]));
+        let patterns: ArrayRef = Arc::new(StringArray::from(vec!["[0-9]+"; 4]));
+        let starts: ArrayRef = Arc::new(Int64Array::from(vec![1, 1, 1, 6]));
+        let occurrences: ArrayRef = Arc::new(Int64Array::from(vec![2, 1, 1, 2]));
+
+        let result = regexp_substr_func(&[values, patterns, starts, occurrences])?;
+        let result = result.as_string_view();
+        let result = result.iter().collect::<Vec<_>>();
+        assert_eq!(result, vec![Some("456"), None, None, Some("9")]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_regexp_substr_group() -> Result<()> {
+        let values: ArrayRef = Arc::new(StringArray::from(vec!["ABCDEF", "ab"]));
+        let patterns: ArrayRef = Arc::new(StringArray::from(vec!["c(.)(..)", "(a)|(b)"]));
+        let starts: ArrayRef = Arc::new(Int64Array::from(vec![1, 1]));
+        let occurrences: ArrayRef = Arc::new(Int64Array::from(vec![1, 1]));
+        let flags: ArrayRef = Arc::new(StringArray::from(vec!["i", ""]));
+        let groups: ArrayRef = Arc::new(Int64Array::from(vec![2, 2]));
+
+        let result =
+            regexp_substr_func(&[values, patterns, starts, occurrences, flags, groups])?;
+        let result = result.as_string::<i32>();
+        // the second group does not participate in the first match of "ab"
+        assert_eq!(result.iter().collect::<Vec<_>>(), vec![Some("EF"), None]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_regexp_substr_invalid_arguments() {
+        let values: ArrayRef = Arc::new(StringArray::from(vec!["abc"]));
+        let patterns: ArrayRef = Arc::new(StringArray::from(vec!["b"]));
+        let starts: ArrayRef = Arc::new(Int64Array::from(vec![0]));
+        let err =
+            regexp_substr_func(&[Arc::clone(&values), Arc::clone(&patterns), starts])
+                .unwrap_err();
+        assert!(
+            err.to_string().contains("requires start to be 1 based"),
+            "{err}"
+        );
+
+        let starts: ArrayRef = Arc::new(Int64Array::from(vec![1]));
+        let occurrences: ArrayRef = Arc::new(Int64Array::from(vec![1]));
+        let flags: ArrayRef = Arc::new(StringArray::from(vec!["g"]));
+        let err = regexp_substr_func(&[values, patterns, starts, occurrences, flags])
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("does not support the \"global\" flag"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{ArrayRef, UInt64Array};
+use arrow::datatypes::DataType;
+use datafusion_common::cast::as_binary_array;
+use datafusion_common::Result;
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_SKETCH;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+use datafusion_functions_aggregate_common::hyperloglog::HyperLogLog;
+
+use crate::utils::make_scalar_function;
+
+#[derive(Debug)]
+pub struct HllEstimateFunc {
+    signature: Signature,
+}
+
+impl Default for HllEstimateFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HllEstimateFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::coercible(
+                vec![DataType::Binary],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for HllEstimateFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "hll_estimate"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::UInt64)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(hll_estimate, vec![])(args)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_hll_estimate_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_hll_estimate_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_SKETCH)
+            .with_description(
+                "Returns the estimated number of distinct values of a HyperLogLog sketch produced by `hll_sketch` or `hll_merge`.",
+            )
+            .with_syntax_example("hll_estimate(sketch)")
+            .with_sql_example(
+                r#"```sql
+> select hll_estimate(hll_sketch(column_name)) from table_name;
++--------------------------------------------------+
+| hll_estimate(hll_sketch(table_name.column_name)) |
++--------------------------------------------------+
+| 42                                               |
++--------------------------------------------------+
+```"#,
+            )
+            .with_argument("sketch", "Binary expression containing a serialized HyperLogLog sketch.")
+            .build()
+            .unwrap()
+    })
+}
+
+/// hll_estimate SQL function
+pub fn hll_estimate(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let sketches = as_binary_array(&args[0])?;
+    let estimates = sketches
+        .iter()
+        .map(|sketch| {
+            sketch
+                .map(|bytes| {
+                    HyperLogLog::<[u8]>::try_from_bytes(bytes)
+                        .map(|hll| hll.count() as u64)
+                })
+                .transpose()
+        })
+        .collect::<Result<UInt64Array>>()?;
+    Ok(Arc::new(estimates))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use arrow::array::{Array, ArrayRef, BinaryArray};
+    use datafusion_common::cast::as_uint64_array;
+    use datafusion_functions_aggregate_common::hyperloglog::HyperLogLog;
+
+    use crate::sketch::hll_estimate::hll_estimate;
+
+    #[test]
+    fn test_hll_estimate() {
+        let mut hll = HyperLogLog::<u64>::new();
+        hll.extend(0..10_u64);
+        let sketch = hll.to_bytes();
+
+        let args: Vec<ArrayRef> = vec![Arc::new(BinaryArray::from_opt_vec(vec![
+            Some(&sketch),
+            None,
+        ]))];
+        let result = hll_estimate(&args).unwrap();
+        let result = as_uint64_array(&result).unwrap();
+        assert_eq!(result.value(0), 10);
+        assert!(result.is_null(1));
+    }
+
+    #[test]
+    fn test_hll_estimate_invalid() {
+        let args: Vec<ArrayRef> =
+            vec![Arc::new(BinaryArray::from_vec(vec![b"not a sketch"]))];
+        let err = hll_estimate(&args).unwrap_err();
+        assert!(err.to_string().contains("HyperLogLog sketch"), "{err}");
+    }
+}
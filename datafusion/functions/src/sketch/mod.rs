@@ -0,0 +1,49 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! "sketch" DataFusion functions, operating on the serialized sketches
+//! produced by aggregates such as `hll_sketch` and `tdigest_sketch`
+
+use datafusion_expr::ScalarUDF;
+use std::sync::Arc;
+
+pub mod hll_estimate;
+pub mod tdigest_quantile;
+
+make_udf_function!(hll_estimate::HllEstimateFunc, HLL_ESTIMATE, hll_estimate);
+make_udf_function!(
+    tdigest_quantile::TDigestQuantileFunc,
+    TDIGEST_QUANTILE,
+    tdigest_quantile
+);
+
+pub mod expr_fn {
+    export_functions!((
+        hll_estimate,
+        "Returns the estimated number of distinct values of a serialized HyperLogLog sketch.",
+        sketch
+    ),(
+        tdigest_quantile,
+        "Returns the estimated quantile of a serialized t-digest sketch.",
+        sketch quantile
+    ));
+}
+
+/// Returns all DataFusion functions defined in this package
+pub fn functions() -> Vec<Arc<ScalarUDF>> {
+    vec![hll_estimate(), tdigest_quantile()]
+}
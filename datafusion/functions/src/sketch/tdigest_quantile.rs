@@ -0,0 +1,183 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::{ArrayRef, Float64Array};
+use arrow::datatypes::DataType;
+use datafusion_common::cast::{as_binary_array, as_float64_array};
+use datafusion_common::{exec_err, Result};
+use datafusion_expr::scalar_doc_sections::DOC_SECTION_SKETCH;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarUDFImpl, Signature, Volatility,
+};
+use datafusion_functions_aggregate_common::tdigest::TDigest;
+
+use crate::utils::make_scalar_function;
+
+#[derive(Debug)]
+pub struct TDigestQuantileFunc {
+    signature: Signature,
+}
+
+impl Default for TDigestQuantileFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TDigestQuantileFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::coercible(
+                vec![DataType::Binary, DataType::Float64],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for TDigestQuantileFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "tdigest_quantile"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(tdigest_quantile, vec![])(args)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        Some(get_tdigest_quantile_doc())
+    }
+}
+
+static DOCUMENTATION: OnceLock<Documentation> = OnceLock::new();
+
+fn get_tdigest_quantile_doc() -> &'static Documentation {
+    DOCUMENTATION.get_or_init(|| {
+        Documentation::builder()
+            .with_doc_section(DOC_SECTION_SKETCH)
+            .with_description(
+                "Returns the estimated quantile of the values summarized by a t-digest sketch produced by `tdigest_sketch` or `tdigest_merge`. Returns NULL for an empty sketch.",
+            )
+            .with_syntax_example("tdigest_quantile(sketch, quantile)")
+            .with_sql_example(
+                r#"```sql
+> select tdigest_quantile(tdigest_sketch(column_name), 0.75) from table_name;
++------------------------------------------------------------------------+
+| tdigest_quantile(tdigest_sketch(table_name.column_name),Float64(0.75)) |
++------------------------------------------------------------------------+
+| 65.0                                                                   |
++------------------------------------------------------------------------+
+```"#,
+            )
+            .with_argument("sketch", "Binary expression containing a serialized t-digest sketch.")
+            .with_argument("quantile", "Quantile to compute. Must be a float value between 0 and 1 (inclusive).")
+            .build()
+            .unwrap()
+    })
+}
+
+/// tdigest_quantile SQL function
+pub fn tdigest_quantile(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let sketches = as_binary_array(&args[0])?;
+    let quantiles = as_float64_array(&args[1])?;
+    let result = sketches
+        .iter()
+        .zip(quantiles.iter())
+        .map(|(sketch, quantile)| match (sketch, quantile) {
+            (Some(sketch), Some(quantile)) => {
+                if !(0.0..=1.0).contains(&quantile) {
+                    return exec_err!(
+                        "Quantile value must be between 0.0 and 1.0 inclusive, {quantile} is invalid"
+                    );
+                }
+                let digest = TDigest::try_from_bytes(sketch)?;
+                if digest.count() == 0 {
+                    return Ok(None);
+                }
+                Ok(Some(digest.estimate_quantile(quantile)))
+            }
+            _ => Ok(None),
+        })
+        .collect::<Result<Float64Array>>()?;
+    Ok(Arc::new(result))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use arrow::array::{Array, ArrayRef, BinaryArray, Float64Array};
+    use datafusion_common::cast::as_float64_array;
+    use datafusion_functions_aggregate_common::tdigest::TDigest;
+
+    use crate::sketch::tdigest_quantile::tdigest_quantile;
+
+    #[test]
+    fn test_tdigest_quantile() {
+        let digest =
+            TDigest::new(100).merge_unsorted_f64((1..=100).map(f64::from).collect());
+        let sketch = digest.to_bytes();
+        let empty = TDigest::new(100).to_bytes();
+
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(BinaryArray::from_opt_vec(vec![
+                Some(&sketch),
+                Some(&sketch),
+                Some(&empty),
+                None,
+            ])),
+            Arc::new(Float64Array::from(vec![
+                Some(0.0),
+                Some(1.0),
+                Some(0.5),
+                Some(0.5),
+            ])),
+        ];
+        let result = tdigest_quantile(&args).unwrap();
+        let result = as_float64_array(&result).unwrap();
+        assert_eq!(result.value(0), 1.0);
+        assert_eq!(result.value(1), 100.0);
+        assert!(result.is_null(2));
+        assert!(result.is_null(3));
+    }
+
+    #[test]
+    fn test_tdigest_quantile_out_of_range() {
+        let sketch = TDigest::new(100).to_bytes();
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(BinaryArray::from_vec(vec![&sketch])),
+            Arc::new(Float64Array::from(vec![1.5])),
+        ];
+        let err = tdigest_quantile(&args).unwrap_err();
+        assert!(err.to_string().contains("1.5 is invalid"), "{err}");
+    }
+}
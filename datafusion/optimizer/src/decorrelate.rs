@@ -27,7 +27,7 @@ use crate::utils::collect_subquery_cols;
 use datafusion_common::tree_node::{
     Transformed, TransformedResult, TreeNode, TreeNodeRecursion, TreeNodeRewriter,
 };
-use datafusion_common::{plan_err, Column, DFSchemaRef, Result, ScalarValue};
+use datafusion_common::{plan_err, Column, DFSchema, DFSchemaRef, Result, ScalarValue};
 use datafusion_expr::expr::Alias;
 use datafusion_expr::simplify::SimplifyContext;
 use datafusion_expr::utils::{conjunction, find_join_exprs, split_conjunction};
@@ -401,6 +401,103 @@ impl PullUpCorrelatedExpr {
     }
 }
 
+/// Returns true if `subquery` can not be decorrelated against an outer plan
+/// with `outer_schema` by pulling up its correlated predicates, either because
+/// it references columns the outer plan does not provide, because one of its
+/// own nested subqueries references the outer plan, or because a correlated
+/// predicate would carry a nested subquery into the join filter.
+///
+/// Such subqueries are decorrelated by [`DecorrelateGeneralSubquery`] instead.
+///
+/// [`DecorrelateGeneralSubquery`]: crate::decorrelate_general::DecorrelateGeneralSubquery
+pub fn has_unresolved_correlation(
+    subquery: &LogicalPlan,
+    outer_schema: &DFSchema,
+) -> bool {
+    let unresolved = subquery.all_out_ref_exprs().iter().any(|expr| {
+        matches!(expr, Expr::OuterReferenceColumn(_, col) if !outer_schema.has_column(col))
+    });
+    if unresolved {
+        return true;
+    }
+
+    let mut nested = false;
+    subquery
+        .apply(|plan| {
+            plan.apply_expressions(|expr| {
+                nested = split_conjunction(expr).into_iter().any(|conjunct| {
+                    conjunct.contains_outer()
+                        && conjunct.exists(|e| Ok(subquery_plan(e).is_some())).unwrap()
+                });
+                if nested {
+                    return Ok(TreeNodeRecursion::Stop);
+                }
+                expr.apply(|expr| {
+                    if let Some(subquery) = subquery_plan(expr) {
+                        nested = plan_outer_ref_columns(subquery)
+                            .iter()
+                            .any(|col| outer_schema.has_column(col));
+                    }
+                    Ok(if nested {
+                        TreeNodeRecursion::Stop
+                    } else {
+                        TreeNodeRecursion::Continue
+                    })
+                })
+            })
+        })
+        // closure always returns OK
+        .unwrap();
+    nested
+}
+
+/// Returns the columns referenced by the outer reference expressions of `plan`,
+/// including those within its nested subqueries
+pub(crate) fn plan_outer_ref_columns(plan: &LogicalPlan) -> Vec<Column> {
+    let mut columns = vec![];
+    plan.apply_with_subqueries(|plan| {
+        plan.apply_expressions(|expr| {
+            expr.apply(|expr| {
+                if let Expr::OuterReferenceColumn(_, col) = expr {
+                    if !columns.contains(col) {
+                        columns.push(col.clone());
+                    }
+                }
+                Ok(TreeNodeRecursion::Continue)
+            })
+        })
+    })
+    // closure always returns OK
+    .unwrap();
+    columns
+}
+
+/// Returns the columns referenced by the outer reference expressions of `expr`,
+/// including those within its subqueries
+pub(crate) fn expr_outer_ref_columns(expr: &Expr) -> Vec<Column> {
+    let mut columns = vec![];
+    expr.apply(|expr| {
+        if let Expr::OuterReferenceColumn(_, col) = expr {
+            columns.push(col.clone());
+        } else if let Some(subquery) = subquery_plan(expr) {
+            columns.extend(plan_outer_ref_columns(subquery));
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })
+    // closure always returns OK
+    .unwrap();
+    columns
+}
+
+fn subquery_plan(expr: &Expr) -> Option<&LogicalPlan> {
+    match expr {
+        Expr::Exists(expr::Exists { subquery, .. })
+        | Expr::InSubquery(expr::InSubquery { subquery, .. })
+        | Expr::ScalarSubquery(subquery) => Some(subquery.subquery.as_ref()),
+        _ => None,
+    }
+}
+
 fn can_pullup_over_aggregation(expr: &Expr) -> bool {
     if let Expr::BinaryExpr(BinaryExpr {
         left,
@@ -465,7 +562,7 @@ fn remove_duplicated_filter(filters: Vec<Expr>, in_predicate: &Expr) -> Vec<Expr
         .collect::<Vec<_>>()
 }
 
-fn agg_exprs_evaluation_result_on_empty_batch(
+pub(crate) fn agg_exprs_evaluation_result_on_empty_batch(
     agg_expr: &[Expr],
     schema: &DFSchemaRef,
     expr_result_map_for_count_bug: &mut ExprResultMap,
@@ -0,0 +1,1492 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`DecorrelateGeneralSubquery`] decorrelates arbitrary subqueries using
+//! dependent joins and magic sets
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::decorrelate::{
+    agg_exprs_evaluation_result_on_empty_batch, expr_outer_ref_columns,
+    plan_outer_ref_columns,
+};
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{Column, Result, ScalarValue, TableReference};
+use datafusion_expr::expr::{Exists, InSubquery, WindowFunction};
+use datafusion_expr::expr_rewriter::NamePreserver;
+use datafusion_expr::logical_plan::builder::build_join_schema;
+use datafusion_expr::logical_plan::{
+    Aggregate, Distinct, FetchType, Filter, Join, JoinType, Limit, Projection, SkipType,
+    Sort, Subquery, SubqueryAlias, Window,
+};
+use datafusion_expr::utils::{conjunction, split_conjunction};
+use datafusion_expr::{
+    lit, not, when, BinaryExpr, Expr, ExprFunctionExt, ExprSchemable, LogicalPlan,
+    LogicalPlanBuilder, Operator, SortExpr, WindowFunctionDefinition,
+};
+
+/// Optimizer rule decorrelating the subqueries that
+/// [`DecorrelatePredicateSubquery`] and [`ScalarSubqueryToJoin`] leave in
+/// place, such as subqueries with non-equality correlations, subqueries
+/// correlated across more than one level, and subqueries embedded in `OR`
+/// predicates or projections.
+///
+/// The approach follows "Unnesting Arbitrary Queries" (Neumann, Kemper): a
+/// subquery is evaluated as a dependent join against the distinct values of
+/// the outer columns it references, its domain (or magic set). The domain is
+/// pushed down the subquery plan until no operator depends on the outer query
+/// anymore, and the decorrelated subquery, which now produces a result for
+/// each domain value, is joined back to the outer plan on the domain columns.
+///
+/// For example, given a query like:
+/// `select t1.a, (select max(t2.b) from t2 where t2.a < t1.a) from t1`
+///
+/// The optimized plan will be:
+///
+/// ```text
+/// Projection: t1.a, __scalar_sq_2.max(t2.b) AS (<subquery>)
+///   Left Join: t1.a = __scalar_sq_2.__outer_a
+///     TableScan: t1
+///     SubqueryAlias: __scalar_sq_2
+///       Projection: max(t2.b), __domain_1.__outer_a
+///         Projection: max(t2.b), __domain_1.__outer_a
+///           Left Join: __domain_1.__outer_a = __agg_3.__outer_a
+///             SubqueryAlias: __domain_1
+///               Distinct:
+///                 Projection: t1.a AS __outer_a
+///                   TableScan: t1
+///             SubqueryAlias: __agg_3
+///               Aggregate: groupBy=[[__domain_1.__outer_a]], aggr=[[max(t2.b)]]
+///                 Filter: t2.a < __domain_1.__outer_a
+///                   Cross Join:
+///                     TableScan: t2
+///                     SubqueryAlias: __domain_1
+///                       ...
+/// ```
+///
+/// [`DecorrelatePredicateSubquery`]: crate::decorrelate_predicate_subquery::DecorrelatePredicateSubquery
+/// [`ScalarSubqueryToJoin`]: crate::scalar_subquery_to_join::ScalarSubqueryToJoin
+#[derive(Default, Debug)]
+pub struct DecorrelateGeneralSubquery {}
+
+impl DecorrelateGeneralSubquery {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl OptimizerRule for DecorrelateGeneralSubquery {
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        // Subqueries that still reference this plan are decorrelated after it
+        plan.map_subqueries(|subquery| {
+            subquery.transform_down(|p| self.rewrite(p, config))
+        })?
+        .transform_data(|plan| match plan {
+            LogicalPlan::Filter(filter) if contains_subquery(&filter.predicate) => {
+                rewrite_filter(filter, config)
+            }
+            LogicalPlan::Projection(projection)
+                if projection.expr.iter().any(contains_subquery) =>
+            {
+                rewrite_projection(projection, config)
+            }
+            _ => Ok(Transformed::no(plan)),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "decorrelate_general_subquery"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+}
+
+fn contains_subquery(expr: &Expr) -> bool {
+    expr.exists(|e| {
+        Ok(matches!(
+            e,
+            Expr::Exists(_) | Expr::InSubquery(_) | Expr::ScalarSubquery(_)
+        ))
+    })
+    .unwrap()
+}
+
+fn rewrite_filter(
+    filter: Filter,
+    config: &dyn OptimizerConfig,
+) -> Result<Transformed<LogicalPlan>> {
+    let mut decorrelator = SubqueryDecorrelator::new(filter.input.as_ref(), config);
+    let mut transformed = false;
+    let mut predicates = vec![];
+    for conjunct in split_conjunction(&filter.predicate) {
+        let conjunct = decorrelator.rewrite_conjunct(conjunct.clone())?;
+        transformed |= conjunct.transformed;
+        predicates.extend(conjunct.data);
+    }
+    if !transformed {
+        return Ok(Transformed::no(LogicalPlan::Filter(filter)));
+    }
+
+    let mut builder = LogicalPlanBuilder::from(decorrelator.plan);
+    if let Some(predicate) = conjunction(predicates) {
+        builder = builder.filter(predicate)?;
+    }
+    // remove the columns of the joined subqueries
+    if builder.schema().fields().len() != filter.input.schema().fields().len() {
+        builder = builder.project(
+            filter
+                .input
+                .schema()
+                .columns()
+                .into_iter()
+                .map(Expr::Column),
+        )?;
+    }
+    builder.build().map(Transformed::yes)
+}
+
+fn rewrite_projection(
+    projection: Projection,
+    config: &dyn OptimizerConfig,
+) -> Result<Transformed<LogicalPlan>> {
+    let mut decorrelator = SubqueryDecorrelator::new(projection.input.as_ref(), config);
+    let name_preserver = NamePreserver::new_for_projection();
+    let mut transformed = false;
+    let expr = projection
+        .expr
+        .iter()
+        .map(|expr| {
+            let saved_name = name_preserver.save(expr);
+            let new_expr = decorrelator.rewrite_expr(expr.clone())?;
+            transformed |= new_expr.transformed;
+            Ok(saved_name.restore(new_expr.data))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if !transformed {
+        return Ok(Transformed::no(LogicalPlan::Projection(projection)));
+    }
+
+    Projection::try_new(expr, Arc::new(decorrelator.plan))
+        .map(|projection| Transformed::yes(LogicalPlan::Projection(projection)))
+}
+
+/// Replaces the subquery expressions of a `Filter` or `Projection` with
+/// columns of the decorrelated subqueries joined to its input
+struct SubqueryDecorrelator<'a> {
+    /// The input plan, with the decorrelated subqueries joined so far
+    plan: LogicalPlan,
+    /// The input plan the domains are computed from
+    base: LogicalPlan,
+    config: &'a dyn OptimizerConfig,
+}
+
+impl<'a> SubqueryDecorrelator<'a> {
+    fn new(plan: &LogicalPlan, config: &'a dyn OptimizerConfig) -> Self {
+        Self {
+            plan: plan.clone(),
+            base: plan.clone(),
+            config,
+        }
+    }
+
+    /// Rewrites a conjunct of a filter predicate, returning `None` if the
+    /// conjunct was replaced by a semi or anti join
+    fn rewrite_conjunct(&mut self, expr: Expr) -> Result<Transformed<Option<Expr>>> {
+        let replaced = match &expr {
+            Expr::Exists(Exists { subquery, negated }) => {
+                self.semi_join(&subquery.subquery, *negated)?
+            }
+            Expr::InSubquery(in_subquery) if !in_subquery.negated => {
+                match self.in_subquery_match(in_subquery)? {
+                    Some(subquery) => self.semi_join(&subquery, false)?,
+                    None => false,
+                }
+            }
+            _ => false,
+        };
+        if replaced {
+            return Ok(Transformed::yes(None));
+        }
+        self.rewrite_predicate(expr)?
+            .map_data(|expr| Ok(Some(expr)))
+    }
+
+    /// Rewrites a filter predicate. Below `AND` and `OR` a NULL `IN` result
+    /// filters out the row just like false, so only the matching subquery rows
+    /// need to be joined.
+    fn rewrite_predicate(&mut self, expr: Expr) -> Result<Transformed<Expr>> {
+        match expr {
+            Expr::BinaryExpr(BinaryExpr { left, op, right })
+                if matches!(op, Operator::And | Operator::Or) =>
+            {
+                let left = self.rewrite_predicate(*left)?;
+                let right = self.rewrite_predicate(*right)?;
+                let transformed = left.transformed || right.transformed;
+                let expr = Expr::BinaryExpr(BinaryExpr::new(
+                    Box::new(left.data),
+                    op,
+                    Box::new(right.data),
+                ));
+                Ok(Transformed::new_transformed(expr, transformed))
+            }
+            Expr::InSubquery(in_subquery) if !in_subquery.negated => {
+                let original = (self.plan.clone(), self.base.clone());
+                let matched = match self.in_subquery_match(&in_subquery)? {
+                    Some(subquery) => self.exists(&subquery)?,
+                    None => None,
+                };
+                match matched {
+                    Some(marker) => Ok(Transformed::yes(marker.is_not_null())),
+                    None => {
+                        (self.plan, self.base) = original;
+                        self.rewrite_expr(Expr::InSubquery(in_subquery))
+                    }
+                }
+            }
+            expr => self.rewrite_expr(expr),
+        }
+    }
+
+    fn rewrite_expr(&mut self, expr: Expr) -> Result<Transformed<Expr>> {
+        expr.transform_up(|expr| {
+            let new_expr = match &expr {
+                Expr::Exists(Exists { subquery, negated }) => self
+                    .exists(&subquery.subquery)?
+                    .map(|marker| match negated {
+                        true => marker.is_null(),
+                        false => marker.is_not_null(),
+                    }),
+                Expr::InSubquery(in_subquery) => self.in_subquery(in_subquery)?,
+                Expr::ScalarSubquery(subquery) => {
+                    self.scalar_subquery(&subquery.subquery)?
+                }
+                _ => None,
+            };
+            Ok(match new_expr {
+                Some(new_expr) => Transformed::yes(new_expr),
+                None => Transformed::no(expr),
+            })
+        })
+    }
+
+    /// Computes the domain of `subquery` and pushes it down the subquery plan
+    fn decorrelate(
+        &self,
+        subquery: &LogicalPlan,
+    ) -> Result<Option<(Domain<'a>, LogicalPlan)>> {
+        let schema = self.base.schema();
+        // A subquery that still references columns of an enclosing query is
+        // decorrelated once that query has been decorrelated itself
+        let unresolved = subquery.all_out_ref_exprs().iter().any(|expr| {
+            matches!(expr, Expr::OuterReferenceColumn(_, col) if !schema.has_column(col))
+        });
+        if unresolved {
+            return Ok(None);
+        }
+
+        let outer_columns = plan_outer_ref_columns(subquery)
+            .into_iter()
+            .filter(|col| schema.has_column(col))
+            .collect();
+        let domain = Domain::new(&self.base, outer_columns, self.config);
+        Ok(domain.push_down(subquery)?.map(|plan| (domain, plan)))
+    }
+
+    /// Joins the decorrelated subquery `right`, aliased as `alias`, to the
+    /// input on the domain columns
+    fn join(
+        &mut self,
+        domain: &Domain,
+        right: LogicalPlan,
+        alias: &str,
+        join_type: JoinType,
+    ) -> Result<()> {
+        let (left_keys, right_keys): (Vec<_>, Vec<_>) = domain
+            .columns
+            .iter()
+            .map(|(outer, col)| (outer.clone(), Column::new(Some(alias), &col.name)))
+            .unzip();
+        // the subquery is evaluated for NULL outer values as well
+        self.plan = LogicalPlanBuilder::from(self.plan.clone())
+            .join_detailed(right, join_type, (left_keys, right_keys), None, true)?
+            .build()?;
+        Ok(())
+    }
+
+    /// Replaces a top level (`NOT`) `EXISTS` predicate with a semi (anti) join
+    fn semi_join(&mut self, subquery: &LogicalPlan, negated: bool) -> Result<bool> {
+        let Some((domain, plan)) = self.decorrelate(subquery)? else {
+            return Ok(false);
+        };
+        if domain.columns.is_empty() {
+            return Ok(false);
+        }
+
+        let alias = self.config.alias_generator().next("__correlated_sq");
+        let right = LogicalPlanBuilder::from(plan)
+            .project(domain.exprs())?
+            .alias(&alias)?
+            .build()?;
+        let join_type = match negated {
+            true => JoinType::LeftAnti,
+            false => JoinType::LeftSemi,
+        };
+        self.join(&domain, right, &alias, join_type)?;
+        Ok(true)
+    }
+
+    /// Joins the domain values for which `subquery` returns rows, returning a
+    /// column that is not null for the matching input rows
+    fn exists(&mut self, subquery: &LogicalPlan) -> Result<Option<Expr>> {
+        let Some((domain, plan)) = self.decorrelate(subquery)? else {
+            return Ok(None);
+        };
+        // uncorrelated subqueries are left in place
+        if domain.columns.is_empty() {
+            return Ok(None);
+        }
+
+        let alias = self.config.alias_generator().next("__correlated_sq");
+        let mut expr = domain.exprs();
+        expr.push(lit(true).alias("__exists"));
+        let right = LogicalPlanBuilder::from(plan)
+            .project(domain.exprs())?
+            .distinct()?
+            .project(expr)?
+            .alias(&alias)?
+            .build()?;
+        self.join(&domain, right, &alias, JoinType::Left)?;
+        Ok(Some(Expr::Column(Column::new(Some(alias), "__exists"))))
+    }
+
+    /// Rewrites `expr IN (subquery)` following the SQL three valued logic: the
+    /// result is true if a subquery row equals `expr`, NULL if there is no
+    /// such row but `expr` or a subquery row is NULL, and false otherwise
+    fn in_subquery(&mut self, in_subquery: &InSubquery) -> Result<Option<Expr>> {
+        let original_plan = self.plan.clone();
+        let original_base = self.base.clone();
+        let Some(match_subquery) = self.in_subquery_match(in_subquery)? else {
+            return Ok(None);
+        };
+        let Some(matched) = self.exists(&match_subquery)? else {
+            (self.plan, self.base) = (original_plan, original_base);
+            return Ok(None);
+        };
+
+        let (value, outer_value) = in_subquery_values(&match_subquery)?;
+        let nullable = value.nullable(in_subquery.subquery.subquery.schema())?
+            || in_subquery.expr.nullable(original_plan.schema())?;
+        let expr = if nullable {
+            let null_subquery =
+                LogicalPlanBuilder::from(in_subquery.subquery.subquery.as_ref().clone())
+                    .filter(value.is_null().or(outer_value.is_null()))?
+                    .build()?;
+            let Some(null) = self.exists(&null_subquery)? else {
+                (self.plan, self.base) = (original_plan, original_base);
+                return Ok(None);
+            };
+            when(matched.is_not_null(), lit(true))
+                .when(null.is_not_null(), lit(ScalarValue::Boolean(None)))
+                .otherwise(lit(false))?
+        } else {
+            matched.is_not_null()
+        };
+        Ok(Some(match in_subquery.negated {
+            true => not(expr),
+            false => expr,
+        }))
+    }
+
+    /// Returns the subquery of `expr IN (subquery)` filtered to the rows equal
+    /// to `expr`, projecting `expr` to the input if it is not a column
+    fn in_subquery_match(
+        &mut self,
+        in_subquery: &InSubquery,
+    ) -> Result<Option<LogicalPlan>> {
+        let subquery = in_subquery.subquery.subquery.as_ref();
+        if subquery.schema().fields().len() != 1 {
+            return Ok(None);
+        }
+
+        let schema = Arc::clone(self.base.schema());
+        let outer_column = match in_subquery.expr.as_ref() {
+            Expr::Column(col) if schema.has_column(col) => col.clone(),
+            expr => {
+                // project the expression to both the input and the domain base
+                let name = self.config.alias_generator().next("__in_expr");
+                let project = |plan: &LogicalPlan| {
+                    let mut exprs: Vec<Expr> = plan
+                        .schema()
+                        .columns()
+                        .into_iter()
+                        .map(Expr::Column)
+                        .collect();
+                    exprs.push(expr.clone().alias(&name));
+                    LogicalPlanBuilder::from(plan.clone())
+                        .project(exprs)?
+                        .build()
+                };
+                self.plan = project(&self.plan)?;
+                self.base = project(&self.base)?;
+                Column::from_name(name)
+            }
+        };
+        let data_type = in_subquery.expr.get_type(&schema)?;
+        let (qualifier, field) = subquery.schema().qualified_field(0);
+        let value = Expr::Column(Column::from((qualifier, field)));
+        LogicalPlanBuilder::from(subquery.clone())
+            .filter(value.eq(Expr::OuterReferenceColumn(data_type, outer_column)))?
+            .build()
+            .map(Some)
+    }
+
+    /// Joins the result of the scalar `subquery` for each domain value,
+    /// returning the column of the result
+    fn scalar_subquery(&mut self, subquery: &LogicalPlan) -> Result<Option<Expr>> {
+        let Some((domain, plan)) = self.decorrelate(subquery)? else {
+            return Ok(None);
+        };
+
+        let alias = self.config.alias_generator().next("__scalar_sq");
+        let (qualifier, field) = subquery.schema().qualified_field(0);
+        let mut expr = vec![Expr::Column(Column::from((qualifier, field)))];
+        expr.extend(domain.exprs());
+        let right = LogicalPlanBuilder::from(plan)
+            .project(expr)?
+            .alias(&alias)?
+            .build()?;
+        self.join(&domain, right, &alias, JoinType::Left)?;
+        Ok(Some(Expr::Column(Column::new(Some(alias), field.name()))))
+    }
+}
+
+/// Returns the output column of the match subquery built by
+/// [`SubqueryDecorrelator::in_subquery_match`] and the outer reference it is
+/// compared to
+fn in_subquery_values(match_subquery: &LogicalPlan) -> Result<(Expr, Expr)> {
+    match match_subquery {
+        LogicalPlan::Filter(Filter { predicate, .. }) => match predicate {
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::Eq,
+                right,
+            }) => Ok((left.as_ref().clone(), right.as_ref().clone())),
+            _ => unreachable!("in subquery match predicate is an equality"),
+        },
+        _ => unreachable!("in subquery match is a filter"),
+    }
+}
+
+/// The distinct values of the outer columns referenced by a subquery, exposed
+/// to the subquery plan as the columns of the relation aliased `alias`
+struct Domain<'a> {
+    alias: TableReference,
+    /// The outer columns and the corresponding domain columns
+    columns: Vec<(Column, Column)>,
+    /// The outer plan the domain values are taken from
+    outer: LogicalPlan,
+    config: &'a dyn OptimizerConfig,
+}
+
+impl<'a> Domain<'a> {
+    fn new(
+        outer: &LogicalPlan,
+        outer_columns: Vec<Column>,
+        config: &'a dyn OptimizerConfig,
+    ) -> Self {
+        let alias = TableReference::bare(config.alias_generator().next("__domain"));
+        let mut columns: Vec<(Column, Column)> = vec![];
+        for (i, outer_column) in outer_columns.into_iter().enumerate() {
+            // the outer column of a nested subquery may be a domain column itself
+            let outer_name = outer_column.name.trim_start_matches("__outer_");
+            let mut name = format!("__outer_{outer_name}");
+            if columns.iter().any(|(_, col)| col.name == name) {
+                name = format!("{name}_{i}");
+            }
+            columns.push((outer_column, Column::new(Some(alias.clone()), name)));
+        }
+        Self {
+            alias,
+            columns,
+            outer: outer.clone(),
+            config,
+        }
+    }
+
+    /// The domain column expressions
+    fn exprs(&self) -> Vec<Expr> {
+        self.columns
+            .iter()
+            .map(|(_, col)| Expr::Column(col.clone()))
+            .collect()
+    }
+
+    /// The domain relation, the distinct outer values
+    fn relation(&self) -> Result<LogicalPlan> {
+        LogicalPlanBuilder::from(self.outer.clone())
+            .project(
+                self.columns
+                    .iter()
+                    .map(|(outer, col)| Expr::Column(outer.clone()).alias(&col.name)),
+            )?
+            .distinct()?
+            .alias(self.alias.clone())?
+            .build()
+    }
+
+    fn domain_column(&self, outer: &Column) -> Option<&Column> {
+        self.columns
+            .iter()
+            .find(|(outer_column, _)| outer_column == outer)
+            .map(|(_, col)| col)
+    }
+
+    fn is_correlated(&self, plan: &LogicalPlan) -> bool {
+        plan_outer_ref_columns(plan)
+            .iter()
+            .any(|col| self.domain_column(col).is_some())
+    }
+
+    fn is_expr_correlated(&self, expr: &Expr) -> bool {
+        expr_outer_ref_columns(expr)
+            .iter()
+            .any(|col| self.domain_column(col).is_some())
+    }
+
+    /// Rewrites `plan` into a plan producing its rows for every domain value,
+    /// with the domain columns appended to its output. Returns `None` if the
+    /// plan contains an operator the domain can not be pushed through.
+    fn push_down(&self, plan: &LogicalPlan) -> Result<Option<LogicalPlan>> {
+        if !self.is_correlated(plan) {
+            if self.columns.is_empty() {
+                return Ok(Some(plan.clone()));
+            }
+            return LogicalPlanBuilder::from(plan.clone())
+                .cross_join(self.relation()?)?
+                .build()
+                .map(Some);
+        }
+
+        match plan {
+            LogicalPlan::Filter(filter) => {
+                if let Some(plan) = self.push_down_equijoin_filter(filter)? {
+                    return Ok(Some(plan));
+                }
+                let Some(input) = self.push_down(&filter.input)? else {
+                    return Ok(None);
+                };
+                let predicate = self.rewrite_outer_refs(filter.predicate.clone())?;
+                Filter::try_new(predicate, Arc::new(input))
+                    .map(|filter| Some(LogicalPlan::Filter(filter)))
+            }
+            LogicalPlan::Projection(projection) => {
+                let Some(input) = self.push_down(&projection.input)? else {
+                    return Ok(None);
+                };
+                let mut expr = self.rewrite_named_exprs(&projection.expr)?;
+                expr.extend(self.exprs());
+                Projection::try_new(expr, Arc::new(input))
+                    .map(|projection| Some(LogicalPlan::Projection(projection)))
+            }
+            LogicalPlan::Aggregate(aggregate) => self.push_down_aggregate(aggregate),
+            LogicalPlan::Distinct(Distinct::All(input)) => Ok(self
+                .push_down(input)?
+                .map(|input| LogicalPlan::Distinct(Distinct::All(Arc::new(input))))),
+            LogicalPlan::SubqueryAlias(subquery_alias) => {
+                let Some(input) = self.push_down(&subquery_alias.input)? else {
+                    return Ok(None);
+                };
+                let alias = &subquery_alias.alias;
+                let aliased = SubqueryAlias::try_new(Arc::new(input), alias.clone())?;
+                // the alias requalifies the domain columns as well, restore them
+                let mut expr: Vec<Expr> = subquery_alias
+                    .schema
+                    .columns()
+                    .into_iter()
+                    .map(Expr::Column)
+                    .collect();
+                expr.extend(self.columns.iter().map(|(_, col)| {
+                    Expr::Column(Column::new(Some(alias.clone()), &col.name))
+                        .alias_qualified(Some(self.alias.clone()), &col.name)
+                }));
+                Projection::try_new(expr, Arc::new(LogicalPlan::SubqueryAlias(aliased)))
+                    .map(|projection| Some(LogicalPlan::Projection(projection)))
+            }
+            LogicalPlan::Join(join) => self.push_down_join(join),
+            LogicalPlan::Union(union) => {
+                let mut builder: Option<LogicalPlanBuilder> = None;
+                for input in &union.inputs {
+                    let Some(new_input) = self.push_down(input)? else {
+                        return Ok(None);
+                    };
+                    // every input has to output the domain columns last
+                    let mut expr: Vec<Expr> = input
+                        .schema()
+                        .columns()
+                        .into_iter()
+                        .map(Expr::Column)
+                        .collect();
+                    expr.extend(self.exprs());
+                    let new_input = LogicalPlanBuilder::from(new_input).project(expr)?;
+                    builder = Some(match builder {
+                        Some(builder) => builder.union(new_input.build()?)?,
+                        None => new_input,
+                    });
+                }
+                builder.map(|builder| builder.build()).transpose()
+            }
+            LogicalPlan::Window(window) => self.push_down_window(window),
+            LogicalPlan::Sort(sort) if sort.fetch.is_none() => {
+                let Some(input) = self.push_down(&sort.input)? else {
+                    return Ok(None);
+                };
+                Ok(Some(LogicalPlan::Sort(Sort {
+                    expr: self.rewrite_sort_exprs(&sort.expr)?,
+                    input: Arc::new(input),
+                    fetch: None,
+                })))
+            }
+            LogicalPlan::Sort(sort) => {
+                self.push_down_top_n(&sort.input, &sort.expr, 0, sort.fetch)
+            }
+            LogicalPlan::Limit(limit) => self.push_down_limit(limit),
+            _ => Ok(None),
+        }
+    }
+
+    /// Pushes the domain through a filter of an uncorrelated input whose
+    /// predicate equates every outer column to an expression of the input.
+    /// The domain values are then determined by the input rows, so the input
+    /// does not need to be joined with the domain relation.
+    fn push_down_equijoin_filter(&self, filter: &Filter) -> Result<Option<LogicalPlan>> {
+        if self.columns.is_empty()
+            || self.is_correlated(&filter.input)
+            || contains_subquery(&filter.predicate)
+        {
+            return Ok(None);
+        }
+
+        let mut bindings: HashMap<Column, Expr> = HashMap::new();
+        let mut predicates = vec![];
+        for conjunct in split_conjunction(&filter.predicate) {
+            match self.equijoin_binding(conjunct) {
+                Some((col, expr)) if !bindings.contains_key(&col) => {
+                    bindings.insert(col, expr);
+                }
+                _ => predicates.push(conjunct.clone()),
+            }
+        }
+        if bindings.len() != self.columns.len() {
+            return Ok(None);
+        }
+
+        let predicates = predicates
+            .into_iter()
+            .map(|predicate| {
+                predicate
+                    .transform(|expr| match &expr {
+                        Expr::OuterReferenceColumn(_, col) => {
+                            Ok(match bindings.get(col) {
+                                Some(binding) => Transformed::yes(binding.clone()),
+                                None => Transformed::no(expr),
+                            })
+                        }
+                        _ => Ok(Transformed::no(expr)),
+                    })
+                    .data()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // the equality only holds for non null values
+        let predicate = self
+            .columns
+            .iter()
+            .map(|(outer, _)| bindings[outer].clone().is_not_null())
+            .chain(predicates)
+            .reduce(Expr::and)
+            .expect("domain is not empty");
+
+        let mut expr: Vec<Expr> = filter
+            .input
+            .schema()
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect();
+        expr.extend(self.columns.iter().map(|(outer, col)| {
+            bindings[outer]
+                .clone()
+                .alias_qualified(Some(self.alias.clone()), &col.name)
+        }));
+        LogicalPlanBuilder::from(filter.input.as_ref().clone())
+            .filter(predicate)?
+            .project(expr)?
+            .build()
+            .map(Some)
+    }
+
+    /// Matches `outer_column = expr` where `expr` is uncorrelated
+    fn equijoin_binding(&self, expr: &Expr) -> Option<(Column, Expr)> {
+        let Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Eq,
+            right,
+        }) = expr
+        else {
+            return None;
+        };
+        match (left.as_ref(), right.as_ref()) {
+            (Expr::OuterReferenceColumn(_, col), other)
+            | (other, Expr::OuterReferenceColumn(_, col))
+                if self.domain_column(col).is_some()
+                    && expr_outer_ref_columns(other).is_empty()
+                    && !contains_subquery(other) =>
+            {
+                Some((col.clone(), other.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    fn push_down_aggregate(&self, aggregate: &Aggregate) -> Result<Option<LogicalPlan>> {
+        if aggregate
+            .group_expr
+            .iter()
+            .any(|expr| matches!(expr, Expr::GroupingSet(_)))
+        {
+            return Ok(None);
+        }
+        let Some(input) = self.push_down(&aggregate.input)? else {
+            return Ok(None);
+        };
+
+        let mut group_expr = self.rewrite_named_exprs(&aggregate.group_expr)?;
+        group_expr.extend(self.exprs());
+        let aggr_expr = self.rewrite_named_exprs(&aggregate.aggr_expr)?;
+        let grouped = LogicalPlan::Aggregate(Aggregate::try_new(
+            Arc::new(input),
+            group_expr,
+            aggr_expr,
+        )?);
+        if !aggregate.group_expr.is_empty() {
+            return Ok(Some(grouped));
+        }
+
+        // An aggregate without grouping produces a row for empty inputs as
+        // well, so every domain value needs a row: join the groups to the
+        // domain relation, using the empty input result for missing groups
+        let alias = self.config.alias_generator().next("__agg");
+        let mut empty_results = HashMap::new();
+        agg_exprs_evaluation_result_on_empty_batch(
+            &aggregate.aggr_expr,
+            aggregate.input.schema(),
+            &mut empty_results,
+        )?;
+        let right_keys: Vec<Column> = self
+            .columns
+            .iter()
+            .map(|(_, col)| Column::new(Some(alias.as_str()), &col.name))
+            .collect();
+        let joined = LogicalPlanBuilder::from(self.relation()?)
+            .join_detailed(
+                LogicalPlanBuilder::from(grouped).alias(&alias)?.build()?,
+                JoinType::Left,
+                (
+                    self.columns.iter().map(|(_, col)| col.clone()).collect(),
+                    right_keys,
+                ),
+                None,
+                true,
+            )?
+            .build()?;
+
+        let mut expr = aggregate
+            .schema
+            .iter()
+            .map(|(qualifier, field)| {
+                let col = Expr::Column(Column::new(Some(alias.as_str()), field.name()));
+                let expr = match empty_results.get(field.name()) {
+                    Some(result) => {
+                        when(col.clone().is_null(), result.clone()).otherwise(col)?
+                    }
+                    None => col,
+                };
+                Ok(expr.alias_qualified(qualifier.cloned(), field.name()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        expr.extend(self.exprs());
+        Projection::try_new(expr, Arc::new(joined))
+            .map(|projection| Some(LogicalPlan::Projection(projection)))
+    }
+
+    /// Pushes the domain into the correlated join inputs. The output side of
+    /// outer, semi and anti joins always needs the domain columns; if both
+    /// inputs get them, the join matches equal domain values.
+    fn push_down_join(&self, join: &Join) -> Result<Option<LogicalPlan>> {
+        if join
+            .on
+            .iter()
+            .any(|(l, r)| self.is_expr_correlated(l) || self.is_expr_correlated(r))
+        {
+            return Ok(None);
+        }
+        let left_correlated = self.is_correlated(&join.left);
+        let right_correlated = self.is_correlated(&join.right);
+        if join.join_type == JoinType::Inner && !left_correlated && !right_correlated {
+            // only the filter is correlated, evaluate it on top of the join
+            let Some(filter) = join.filter.clone() else {
+                return Ok(None);
+            };
+            let uncorrelated = LogicalPlan::Join(Join {
+                filter: None,
+                ..join.clone()
+            });
+            return self.push_down(&LogicalPlan::Filter(Filter::try_new(
+                filter,
+                Arc::new(uncorrelated),
+            )?));
+        }
+        let (push_left, push_right) = match join.join_type {
+            JoinType::Inner => (left_correlated || !right_correlated, right_correlated),
+            JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti => {
+                (true, right_correlated)
+            }
+            JoinType::Right | JoinType::RightSemi | JoinType::RightAnti => {
+                (left_correlated, true)
+            }
+            JoinType::Full => return Ok(None),
+        };
+
+        let left = match push_left {
+            true => match self.push_down(&join.left)? {
+                Some(left) => left,
+                None => return Ok(None),
+            },
+            false => join.left.as_ref().clone(),
+        };
+        let right = match push_right {
+            true => match self.push_down(&join.right)? {
+                Some(right) => right,
+                None => return Ok(None),
+            },
+            false => join.right.as_ref().clone(),
+        };
+        let mut filter = join
+            .filter
+            .clone()
+            .map(|filter| self.rewrite_outer_refs(filter))
+            .transpose()?;
+
+        let (left, right) = if push_left && push_right {
+            // rename the domain columns of the input not forming the output
+            let alias = self.config.alias_generator().next("__domain");
+            let rename = |plan: LogicalPlan, original: &LogicalPlan| {
+                let mut expr: Vec<Expr> = original
+                    .schema()
+                    .columns()
+                    .into_iter()
+                    .map(Expr::Column)
+                    .collect();
+                expr.extend(self.columns.iter().map(|(_, col)| {
+                    Expr::Column(col.clone())
+                        .alias_qualified(Some(alias.as_str()), &col.name)
+                }));
+                LogicalPlanBuilder::from(plan).project(expr)?.build()
+            };
+            let conditions = self.columns.iter().map(|(_, col)| {
+                Expr::BinaryExpr(BinaryExpr::new(
+                    Box::new(Expr::Column(col.clone())),
+                    Operator::IsNotDistinctFrom,
+                    Box::new(Expr::Column(Column::new(Some(alias.as_str()), &col.name))),
+                ))
+            });
+            filter = conjunction(filter.into_iter().chain(conditions));
+            match join.join_type {
+                JoinType::Right | JoinType::RightSemi | JoinType::RightAnti => {
+                    (rename(left, &join.left)?, right)
+                }
+                _ => (left, rename(right, &join.right)?),
+            }
+        } else {
+            (left, right)
+        };
+
+        let schema = build_join_schema(left.schema(), right.schema(), &join.join_type)?;
+        let new_join = LogicalPlan::Join(Join {
+            left: Arc::new(left),
+            right: Arc::new(right),
+            on: join.on.clone(),
+            filter,
+            join_type: join.join_type,
+            join_constraint: join.join_constraint,
+            schema: Arc::new(schema),
+            null_equals_null: join.null_equals_null,
+        });
+        let outputs_both = matches!(
+            join.join_type,
+            JoinType::Inner | JoinType::Left | JoinType::Right
+        );
+        if !(push_left && push_right && outputs_both) {
+            return Ok(Some(new_join));
+        }
+
+        // remove the renamed domain columns
+        let mut expr: Vec<Expr> = join
+            .schema
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect();
+        expr.extend(self.exprs());
+        Projection::try_new(expr, Arc::new(new_join))
+            .map(|projection| Some(LogicalPlan::Projection(projection)))
+    }
+
+    /// Adds the domain columns to the partitioning of the window functions
+    fn push_down_window(&self, window: &Window) -> Result<Option<LogicalPlan>> {
+        let Some(input) = self.push_down(&window.input)? else {
+            return Ok(None);
+        };
+        let window_expr = window
+            .window_expr
+            .iter()
+            .map(|expr| {
+                self.rewrite_outer_refs(expr.clone())?
+                    .transform(|expr| match expr {
+                        Expr::WindowFunction(mut window_function) => {
+                            window_function.partition_by.extend(self.exprs());
+                            Ok(Transformed::yes(Expr::WindowFunction(window_function)))
+                        }
+                        _ => Ok(Transformed::no(expr)),
+                    })
+                    .data()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let new_window = Window::try_new(window_expr, Arc::new(input))?;
+
+        // the partitioning changes the names of the window expressions, restore them
+        let name_preserver = NamePreserver::new_for_projection();
+        let mut expr: Vec<Expr> = window
+            .input
+            .schema()
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect();
+        let window_fields = new_window
+            .schema
+            .iter()
+            .skip(new_window.input.schema().fields().len());
+        for (original, (qualifier, field)) in window.window_expr.iter().zip(window_fields)
+        {
+            expr.push(
+                name_preserver
+                    .save(original)
+                    .restore(Expr::Column(Column::from((qualifier, field)))),
+            );
+        }
+        expr.extend(self.exprs());
+        Projection::try_new(expr, Arc::new(LogicalPlan::Window(new_window)))
+            .map(|projection| Some(LogicalPlan::Projection(projection)))
+    }
+
+    fn push_down_limit(&self, limit: &Limit) -> Result<Option<LogicalPlan>> {
+        let (SkipType::Literal(skip), FetchType::Literal(fetch)) =
+            (limit.get_skip_type()?, limit.get_fetch_type()?)
+        else {
+            return Ok(None);
+        };
+        match limit.input.as_ref() {
+            LogicalPlan::Sort(sort) if sort.fetch.is_none() => {
+                self.push_down_top_n(&sort.input, &sort.expr, skip, fetch)
+            }
+            input => self.push_down_top_n(input, &[], skip, fetch),
+        }
+    }
+
+    /// Limits the rows of every domain value by numbering them with a
+    /// `row_number` window function partitioned by the domain columns
+    fn push_down_top_n(
+        &self,
+        input: &LogicalPlan,
+        sort_expr: &[SortExpr],
+        skip: usize,
+        fetch: Option<usize>,
+    ) -> Result<Option<LogicalPlan>> {
+        let Some(row_number) = self
+            .config
+            .function_registry()
+            .and_then(|registry| registry.udwf("row_number").ok())
+        else {
+            return Ok(None);
+        };
+        let Some(new_input) = self.push_down(input)? else {
+            return Ok(None);
+        };
+
+        let row_number = Expr::WindowFunction(WindowFunction::new(
+            WindowFunctionDefinition::WindowUDF(row_number),
+            vec![],
+        ))
+        .partition_by(self.exprs())
+        .order_by(self.rewrite_sort_exprs(sort_expr)?)
+        .build()?;
+        let row_number_col =
+            Expr::Column(Column::from_name(row_number.schema_name().to_string()));
+        let mut predicate = row_number_col.clone().gt(lit(skip as u64));
+        if let Some(fetch) = fetch {
+            predicate = predicate.and(row_number_col.lt_eq(lit((skip + fetch) as u64)));
+        }
+        let expr: Vec<Expr> = new_input
+            .schema()
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect();
+        LogicalPlanBuilder::from(new_input)
+            .window(vec![row_number])?
+            .filter(predicate)?
+            .project(expr)?
+            .build()
+            .map(Some)
+    }
+
+    fn rewrite_sort_exprs(&self, sort_expr: &[SortExpr]) -> Result<Vec<SortExpr>> {
+        sort_expr
+            .iter()
+            .map(|sort| {
+                Ok(SortExpr {
+                    expr: self.rewrite_outer_refs(sort.expr.clone())?,
+                    ..sort.clone()
+                })
+            })
+            .collect()
+    }
+
+    /// Rewrites `exprs` like [`Self::rewrite_outer_refs`], preserving their names
+    fn rewrite_named_exprs(&self, exprs: &[Expr]) -> Result<Vec<Expr>> {
+        let name_preserver = NamePreserver::new_for_projection();
+        exprs
+            .iter()
+            .map(|expr| {
+                let saved_name = name_preserver.save(expr);
+                Ok(saved_name.restore(self.rewrite_outer_refs(expr.clone())?))
+            })
+            .collect()
+    }
+
+    /// Replaces the outer references to the domain with the domain columns,
+    /// and makes the nested subqueries of `expr` reference the domain columns
+    /// instead
+    fn rewrite_outer_refs(&self, expr: Expr) -> Result<Expr> {
+        self.replace_outer_refs(expr, false).data()
+    }
+
+    fn replace_outer_refs(&self, expr: Expr, nested: bool) -> Result<Transformed<Expr>> {
+        expr.transform(|expr| match expr {
+            Expr::OuterReferenceColumn(data_type, col) => {
+                Ok(match self.domain_column(&col) {
+                    Some(domain_col) if nested => Transformed::yes(
+                        Expr::OuterReferenceColumn(data_type, domain_col.clone()),
+                    ),
+                    Some(domain_col) => {
+                        Transformed::yes(Expr::Column(domain_col.clone()))
+                    }
+                    None => Transformed::no(Expr::OuterReferenceColumn(data_type, col)),
+                })
+            }
+            Expr::Exists(Exists { subquery, negated }) => Ok(self
+                .replace_subquery_outer_refs(subquery)?
+                .update_data(|subquery| Expr::Exists(Exists { subquery, negated }))),
+            Expr::InSubquery(InSubquery {
+                expr,
+                subquery,
+                negated,
+            }) => {
+                Ok(self
+                    .replace_subquery_outer_refs(subquery)?
+                    .update_data(|subquery| {
+                        Expr::InSubquery(InSubquery {
+                            expr,
+                            subquery,
+                            negated,
+                        })
+                    }))
+            }
+            Expr::ScalarSubquery(subquery) => Ok(self
+                .replace_subquery_outer_refs(subquery)?
+                .update_data(Expr::ScalarSubquery)),
+            _ => Ok(Transformed::no(expr)),
+        })
+    }
+
+    fn replace_subquery_outer_refs(
+        &self,
+        subquery: Subquery,
+    ) -> Result<Transformed<Subquery>> {
+        if !self.is_correlated(&subquery.subquery) {
+            return Ok(Transformed::no(subquery));
+        }
+        let plan = Arc::unwrap_or_clone(subquery.subquery).transform_up(|plan| {
+            let name_preserver = matches!(
+                plan,
+                LogicalPlan::Projection(_)
+                    | LogicalPlan::Aggregate(_)
+                    | LogicalPlan::Window(_)
+            )
+            .then(NamePreserver::new_for_projection);
+            plan.map_expressions(|expr| {
+                let saved_name = name_preserver.as_ref().map(|p| p.save(&expr));
+                self.replace_outer_refs(expr, true)?.map_data(|expr| {
+                    Ok(match saved_name {
+                        Some(saved_name) => saved_name.restore(expr),
+                        None => expr,
+                    })
+                })
+            })
+        })?;
+        let outer_ref_columns = subquery
+            .outer_ref_columns
+            .into_iter()
+            .map(|expr| self.replace_outer_refs(expr, true).data())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Transformed::yes(Subquery {
+            subquery: Arc::new(plan.data),
+            outer_ref_columns,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+
+    use arrow::datatypes::DataType;
+    use datafusion_expr::{col, exists, in_subquery, out_ref_col, scalar_subquery};
+    use datafusion_functions_aggregate::count::count;
+    use datafusion_functions_aggregate::min_max::max;
+
+    fn assert_optimized_plan_equal(plan: LogicalPlan, expected: &str) -> Result<()> {
+        assert_optimized_plan_eq(
+            Arc::new(DecorrelateGeneralSubquery::new()),
+            plan,
+            expected,
+        )
+    }
+
+    /// Test for scalar subquery with a non-equality correlation in a projection
+    #[test]
+    fn scalar_subquery_non_equality() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("orders"))
+                .filter(
+                    col("orders.o_custkey")
+                        .lt(out_ref_col(DataType::Int64, "customer.c_custkey")),
+                )?
+                .aggregate(Vec::<Expr>::new(), vec![max(col("orders.o_totalprice"))])?
+                .project(vec![max(col("orders.o_totalprice"))])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .project(vec![
+                col("customer.c_custkey"),
+                scalar_subquery(sq).alias("m"),
+            ])?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey, __scalar_sq_3.max(orders.o_totalprice) AS m\
+        \n  Left Join: customer.c_custkey = __scalar_sq_3.__outer_c_custkey\
+        \n    TableScan: customer\
+        \n    SubqueryAlias: __scalar_sq_3\
+        \n      Projection: max(orders.o_totalprice), __domain_1.__outer_c_custkey\
+        \n        Projection: max(orders.o_totalprice), __domain_1.__outer_c_custkey\
+        \n          Projection: __agg_2.max(orders.o_totalprice) AS max(orders.o_totalprice), __domain_1.__outer_c_custkey\
+        \n            Left Join: __domain_1.__outer_c_custkey = __agg_2.__outer_c_custkey\
+        \n              SubqueryAlias: __domain_1\
+        \n                Distinct:\
+        \n                  Projection: customer.c_custkey AS __outer_c_custkey\
+        \n                    TableScan: customer\
+        \n              SubqueryAlias: __agg_2\
+        \n                Aggregate: groupBy=[[__domain_1.__outer_c_custkey]], aggr=[[max(orders.o_totalprice)]]\
+        \n                  Filter: orders.o_custkey < __domain_1.__outer_c_custkey\
+        \n                    Cross Join: \
+        \n                      TableScan: orders\
+        \n                      SubqueryAlias: __domain_1\
+        \n                        Distinct:\
+        \n                          Projection: customer.c_custkey AS __outer_c_custkey\
+        \n                            TableScan: customer";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test for count subquery, which returns 0 instead of NULL for missing groups
+    #[test]
+    fn scalar_subquery_count() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("orders"))
+                .filter(
+                    col("orders.o_custkey")
+                        .gt(out_ref_col(DataType::Int64, "customer.c_custkey")),
+                )?
+                .aggregate(Vec::<Expr>::new(), vec![count(col("orders.o_orderkey"))])?
+                .project(vec![count(col("orders.o_orderkey"))])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .project(vec![
+                col("customer.c_custkey"),
+                scalar_subquery(sq).alias("c"),
+            ])?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey, __scalar_sq_3.count(orders.o_orderkey) AS c\
+        \n  Left Join: customer.c_custkey = __scalar_sq_3.__outer_c_custkey\
+        \n    TableScan: customer\
+        \n    SubqueryAlias: __scalar_sq_3\
+        \n      Projection: count(orders.o_orderkey), __domain_1.__outer_c_custkey\
+        \n        Projection: count(orders.o_orderkey), __domain_1.__outer_c_custkey\
+        \n          Projection: CASE WHEN __agg_2.count(orders.o_orderkey) IS NULL THEN Int64(0) ELSE __agg_2.count(orders.o_orderkey) END AS count(orders.o_orderkey), __domain_1.__outer_c_custkey\
+        \n            Left Join: __domain_1.__outer_c_custkey = __agg_2.__outer_c_custkey\
+        \n              SubqueryAlias: __domain_1\
+        \n                Distinct:\
+        \n                  Projection: customer.c_custkey AS __outer_c_custkey\
+        \n                    TableScan: customer\
+        \n              SubqueryAlias: __agg_2\
+        \n                Aggregate: groupBy=[[__domain_1.__outer_c_custkey]], aggr=[[count(orders.o_orderkey)]]\
+        \n                  Filter: orders.o_custkey > __domain_1.__outer_c_custkey\
+        \n                    Cross Join: \
+        \n                      TableScan: orders\
+        \n                      SubqueryAlias: __domain_1\
+        \n                        Distinct:\
+        \n                          Projection: customer.c_custkey AS __outer_c_custkey\
+        \n                            TableScan: customer";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test for EXISTS subquery in a projection
+    #[test]
+    fn exists_subquery_in_projection() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("orders"))
+                .filter(
+                    col("orders.o_custkey")
+                        .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+                )?
+                .project(vec![col("orders.o_orderkey")])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .project(vec![col("customer.c_custkey"), exists(sq).alias("e")])?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey, __correlated_sq_2.__exists IS NOT NULL AS e\
+        \n  Left Join: customer.c_custkey = __correlated_sq_2.__outer_c_custkey\
+        \n    TableScan: customer\
+        \n    SubqueryAlias: __correlated_sq_2\
+        \n      Projection: __domain_1.__outer_c_custkey, Boolean(true) AS __exists\
+        \n        Distinct:\
+        \n          Projection: __domain_1.__outer_c_custkey\
+        \n            Projection: orders.o_orderkey, __domain_1.__outer_c_custkey\
+        \n              Projection: orders.o_orderkey, orders.o_custkey, orders.o_orderstatus, orders.o_totalprice, orders.o_custkey AS __outer_c_custkey\
+        \n                Filter: orders.o_custkey IS NOT NULL\
+        \n                  TableScan: orders";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test for EXISTS subquery with a non-equality correlation in a disjunction
+    #[test]
+    fn exists_subquery_disjunction() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("orders"))
+                .filter(
+                    col("orders.o_custkey")
+                        .lt(out_ref_col(DataType::Int64, "customer.c_custkey")),
+                )?
+                .project(vec![col("orders.o_orderkey")])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .filter(exists(sq).or(col("customer.c_custkey").eq(lit(1))))?
+            .project(vec![col("customer.c_custkey")])?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey\
+        \n  Projection: customer.c_custkey, customer.c_name\
+        \n    Filter: __correlated_sq_2.__exists IS NOT NULL OR customer.c_custkey = Int32(1)\
+        \n      Left Join: customer.c_custkey = __correlated_sq_2.__outer_c_custkey\
+        \n        TableScan: customer\
+        \n        SubqueryAlias: __correlated_sq_2\
+        \n          Projection: __domain_1.__outer_c_custkey, Boolean(true) AS __exists\
+        \n            Distinct:\
+        \n              Projection: __domain_1.__outer_c_custkey\
+        \n                Projection: orders.o_orderkey, __domain_1.__outer_c_custkey\
+        \n                  Filter: orders.o_custkey < __domain_1.__outer_c_custkey\
+        \n                    Cross Join: \
+        \n                      TableScan: orders\
+        \n                      SubqueryAlias: __domain_1\
+        \n                        Distinct:\
+        \n                          Projection: customer.c_custkey AS __outer_c_custkey\
+        \n                            TableScan: customer";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test for IN subquery in a disjunction, which only joins the matching rows
+    #[test]
+    fn in_subquery_disjunction() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("orders"))
+                .filter(
+                    col("orders.o_orderkey")
+                        .gt(out_ref_col(DataType::Int64, "customer.c_custkey")),
+                )?
+                .project(vec![col("orders.o_custkey")])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .filter(
+                in_subquery(col("customer.c_custkey"), sq)
+                    .or(col("customer.c_custkey").eq(lit(1))),
+            )?
+            .project(vec![col("customer.c_custkey")])?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey\
+        \n  Projection: customer.c_custkey, customer.c_name\
+        \n    Filter: __correlated_sq_2.__exists IS NOT NULL OR customer.c_custkey = Int32(1)\
+        \n      Left Join: customer.c_custkey = __correlated_sq_2.__outer_c_custkey\
+        \n        TableScan: customer\
+        \n        SubqueryAlias: __correlated_sq_2\
+        \n          Projection: __domain_1.__outer_c_custkey, Boolean(true) AS __exists\
+        \n            Distinct:\
+        \n              Projection: __domain_1.__outer_c_custkey\
+        \n                Filter: orders.o_custkey = __domain_1.__outer_c_custkey\
+        \n                  Projection: orders.o_custkey, __domain_1.__outer_c_custkey\
+        \n                    Filter: orders.o_orderkey > __domain_1.__outer_c_custkey\
+        \n                      Cross Join: \
+        \n                        TableScan: orders\
+        \n                        SubqueryAlias: __domain_1\
+        \n                          Distinct:\
+        \n                            Projection: customer.c_custkey AS __outer_c_custkey\
+        \n                              TableScan: customer";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test for EXISTS subquery whose nested subquery references the outer query
+    #[test]
+    fn exists_subquery_nested_correlation() -> Result<()> {
+        let lineitem = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("lineitem"))
+                .filter(
+                    col("lineitem.l_orderkey")
+                        .eq(out_ref_col(DataType::Int64, "orders.o_orderkey"))
+                        .and(
+                            col("lineitem.l_partkey")
+                                .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+                        ),
+                )?
+                .project(vec![col("lineitem.l_orderkey")])?
+                .build()?,
+        );
+        let orders = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("orders"))
+                .filter(exists(lineitem))?
+                .project(vec![col("orders.o_orderkey")])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .filter(exists(orders))?
+            .project(vec![col("customer.c_custkey")])?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey\
+        \n  LeftSemi Join: customer.c_custkey = __correlated_sq_2.__outer_c_custkey\
+        \n    TableScan: customer\
+        \n    SubqueryAlias: __correlated_sq_2\
+        \n      Projection: __domain_1.__outer_c_custkey\
+        \n        Projection: orders.o_orderkey, __domain_1.__outer_c_custkey\
+        \n          LeftSemi Join: orders.o_orderkey = __correlated_sq_4.__outer_o_orderkey, __domain_1.__outer_c_custkey = __correlated_sq_4.__outer_c_custkey\
+        \n            Cross Join: \
+        \n              TableScan: orders\
+        \n              SubqueryAlias: __domain_1\
+        \n                Distinct:\
+        \n                  Projection: customer.c_custkey AS __outer_c_custkey\
+        \n                    TableScan: customer\
+        \n            SubqueryAlias: __correlated_sq_4\
+        \n              Projection: __domain_3.__outer_o_orderkey, __domain_3.__outer_c_custkey\
+        \n                Projection: lineitem.l_orderkey, __domain_3.__outer_o_orderkey, __domain_3.__outer_c_custkey\
+        \n                  Projection: lineitem.l_orderkey, lineitem.l_partkey, lineitem.l_suppkey, lineitem.l_linenumber, lineitem.l_quantity, lineitem.l_extendedprice, lineitem.l_orderkey AS __outer_o_orderkey, lineitem.l_partkey AS __outer_c_custkey\
+        \n                    Filter: lineitem.l_orderkey IS NOT NULL AND lineitem.l_partkey IS NOT NULL\
+        \n                      TableScan: lineitem";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test for subquery with a correlated grouping set, which is not decorrelated
+    #[test]
+    fn scalar_subquery_grouping_set_not_decorrelated() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("orders"))
+                .filter(
+                    col("orders.o_custkey")
+                        .lt(out_ref_col(DataType::Int64, "customer.c_custkey")),
+                )?
+                .aggregate(
+                    vec![Expr::GroupingSet(datafusion_expr::GroupingSet::Rollup(
+                        vec![col("orders.o_custkey")],
+                    ))],
+                    vec![max(col("orders.o_totalprice"))],
+                )?
+                .project(vec![max(col("orders.o_totalprice"))])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .project(vec![
+                col("customer.c_custkey"),
+                scalar_subquery(sq).alias("m"),
+            ])?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey, (<subquery>) AS m\
+        \n  Subquery:\
+        \n    Projection: max(orders.o_totalprice)\
+        \n      Aggregate: groupBy=[[ROLLUP (orders.o_custkey)]], aggr=[[max(orders.o_totalprice)]]\
+        \n        Filter: orders.o_custkey < outer_ref(customer.c_custkey)\
+        \n          TableScan: orders\
+        \n  TableScan: customer";
+        assert_optimized_plan_equal(plan, expected)
+    }
+}
@@ -21,7 +21,7 @@ use std::iter;
 use std::ops::Deref;
 use std::sync::Arc;
 
-use crate::decorrelate::PullUpCorrelatedExpr;
+use crate::decorrelate::{has_unresolved_correlation, PullUpCorrelatedExpr};
 use crate::optimizer::ApplyOrder;
 use crate::utils::replace_qualified_name;
 use crate::{OptimizerConfig, OptimizerRule};
@@ -31,7 +31,7 @@ use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
 use datafusion_common::{internal_err, plan_err, Column, Result};
 use datafusion_expr::expr::{Exists, InSubquery};
 use datafusion_expr::expr_rewriter::create_col_from_scalar_expr;
-use datafusion_expr::logical_plan::{JoinType, Subquery};
+use datafusion_expr::logical_plan::{JoinType, Subquery, SubqueryAlias};
 use datafusion_expr::utils::{conjunction, split_conjunction_owned};
 use datafusion_expr::{
     exists, in_subquery, lit, not, not_exists, not_in_subquery, BinaryExpr, Expr, Filter,
@@ -127,6 +127,11 @@ impl OptimizerRule for DecorrelatePredicateSubquery {
     }
 }
 
+/// Replaces the subqueries of `expr` with existence joins. The subqueries
+/// that can not be rewritten are left in place for
+/// [`DecorrelateGeneralSubquery`].
+///
+/// [`DecorrelateGeneralSubquery`]: crate::decorrelate_general::DecorrelateGeneralSubquery
 fn rewrite_inner_subqueries(
     outer: LogicalPlan,
     expr: Expr,
@@ -300,8 +305,9 @@ fn build_join_top(
 ///     Left Join:  Filter: t1.id = __correlated_sq_1.id
 ///       TableScan: t1
 ///       SubqueryAlias: __correlated_sq_1
-///         Projection: t2.id, true as __exists
-///           TableScan: t2
+///         Distinct:
+///           Projection: t2.id, true as __exists
+///             TableScan: t2
 fn existence_join(
     left: &LogicalPlan,
     subquery: Arc<LogicalPlan>,
@@ -325,10 +331,99 @@ fn existence_join(
         exists_col.is_not_null()
     };
 
-    Ok(
-        build_join(left, &subquery, in_predicate_opt, JoinType::Left, alias)?
-            .map(|plan| (plan, exists_expr)),
-    )
+    let Some(join) = build_join(
+        left,
+        &subquery,
+        in_predicate_opt,
+        JoinType::Left,
+        alias.clone(),
+    )?
+    else {
+        return Ok(None);
+    };
+    Ok(distinct_existence_join(join, alias)?.map(|plan| (plan, exists_expr)))
+}
+
+/// Removes the duplicate rows of the subquery side of an existence join, so
+/// that each row of the outer query is matched at most once.
+///
+/// Only the subquery columns compared for equality with the outer query are
+/// kept. Returns `None` if the join condition compares subquery columns in any
+/// other way.
+fn distinct_existence_join(
+    plan: LogicalPlan,
+    alias: String,
+) -> Result<Option<LogicalPlan>> {
+    let LogicalPlan::Join(join) = plan else {
+        return internal_err!("Expected an existence join, got {}", plan.display());
+    };
+    let LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) = join.right.as_ref()
+    else {
+        return internal_err!("Expected an aliased subquery as the existence join input");
+    };
+
+    let conjuncts = join
+        .on
+        .iter()
+        .map(|(l, r)| l.clone().eq(r.clone()))
+        .chain(
+            join.filter
+                .iter()
+                .cloned()
+                .flat_map(split_conjunction_owned),
+        )
+        .collect::<Vec<_>>();
+    let right_schema = join.right.schema();
+    let references_right = |expr: &Expr| {
+        expr.column_refs()
+            .iter()
+            .any(|c| right_schema.has_column(c))
+    };
+
+    let mut keys = vec![];
+    for conjunct in &conjuncts {
+        if !references_right(conjunct) {
+            continue;
+        }
+        let key = match conjunct {
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::Eq,
+                right,
+            }) => match (left.as_ref(), right.as_ref()) {
+                (Expr::Column(col), other) | (other, Expr::Column(col))
+                    if right_schema.has_column(col) && !references_right(other) =>
+                {
+                    col
+                }
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        let key = Column::from(
+            input
+                .schema()
+                .qualified_field(right_schema.index_of_column(key)?),
+        );
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    let exists_col = Column::from_name("__exists");
+    let right = LogicalPlanBuilder::from(Arc::clone(input))
+        .project(
+            keys.into_iter()
+                .chain(iter::once(exists_col))
+                .map(Expr::Column),
+        )?
+        .distinct()?
+        .alias(alias)?
+        .build()?;
+    let new_plan = LogicalPlanBuilder::from(Arc::unwrap_or_clone(join.left))
+        .join_on(right, join.join_type, conjuncts)?
+        .build()?;
+    Ok(Some(new_plan))
 }
 
 fn build_join(
@@ -338,6 +433,10 @@ fn build_join(
     join_type: JoinType,
     alias: String,
 ) -> Result<Option<LogicalPlan>> {
+    if has_unresolved_correlation(subquery, left.schema()) {
+        return Ok(None);
+    }
+
     let mut pull_up = PullUpCorrelatedExpr::new()
         .with_in_predicate_opt(in_predicate_opt.clone())
         .with_exists_sub_query(in_predicate_opt.is_none());
@@ -1049,6 +1148,59 @@ mod tests {
         Ok(())
     }
 
+    /// Test for IN subquery in an OR filter
+    #[test]
+    fn in_subquery_or() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(
+                col("a")
+                    .eq(lit(1_u32))
+                    .or(in_subquery(col("c"), test_subquery_with_name("sq")?)),
+            )?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  Filter: test.a = UInt32(1) OR __correlated_sq_1.__exists IS NOT NULL [a:UInt32, b:UInt32, c:UInt32, c:UInt32;N, __exists:Boolean;N]\
+        \n    Left Join:  Filter: test.c = __correlated_sq_1.c [a:UInt32, b:UInt32, c:UInt32, c:UInt32;N, __exists:Boolean;N]\
+        \n      TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n      SubqueryAlias: __correlated_sq_1 [c:UInt32, __exists:Boolean]\
+        \n        Distinct: [c:UInt32, __exists:Boolean]\
+        \n          Projection: sq.c, __exists [c:UInt32, __exists:Boolean]\
+        \n            Projection: sq.c, Boolean(true) AS __exists [c:UInt32, __exists:Boolean]\
+        \n              Projection: sq.c [c:UInt32]\
+        \n                TableScan: sq [a:UInt32, b:UInt32, c:UInt32]";
+
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test for EXISTS subquery with a non-equality correlation in an OR filter,
+    /// which is left in place
+    #[test]
+    fn exists_subquery_or_non_equal_correlation() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(test_table_scan_with_name("sq")?)
+                .filter(out_ref_col(DataType::UInt32, "test.a").lt(col("sq.a")))?
+                .project(vec![col("sq.c")])?
+                .build()?,
+        );
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").eq(lit(1_u32)).or(exists(sq)))?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  Filter: test.a = UInt32(1) OR EXISTS (<subquery>) [a:UInt32, b:UInt32, c:UInt32]\
+        \n    Subquery: [c:UInt32]\
+        \n      Projection: sq.c [c:UInt32]\
+        \n        Filter: outer_ref(test.a) < sq.a [a:UInt32, b:UInt32, c:UInt32]\
+        \n          TableScan: sq [a:UInt32, b:UInt32, c:UInt32]\
+        \n    TableScan: test [a:UInt32, b:UInt32, c:UInt32]";
+
+        assert_optimized_plan_equal(plan, expected)
+    }
+
     /// Test for single NOT IN subquery filter
     #[test]
     fn not_in_subquery_simple() -> Result<()> {
@@ -0,0 +1,625 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`EagerAggregation`] pushes partial aggregations below inner joins
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::{get_required_group_by_exprs_indices, Column, Result};
+use datafusion_expr::builder::{build_join_schema, project};
+use datafusion_expr::expr::{AggregateFunction, Alias};
+use datafusion_expr::logical_plan::{Aggregate, Join, JoinType, LogicalPlan};
+use datafusion_expr::{cast, col, AggregateUDF, Expr, TableSource};
+
+/// Prefix of the aliases of the partial aggregates
+const EAGER_AGGREGATION_PREFIX: &str = "__eager_agg";
+
+/// Eager aggregation optimizer rule: pushes a partial aggregation below an
+/// inner join, into the input of the join with the arguments of all the
+/// aggregate functions, so that fewer rows are joined.
+///
+/// ```text
+///    Before:
+///    SELECT d.cat, sum(f.amount)
+///    FROM fact f JOIN dim d ON f.k = d.k
+///    GROUP BY d.cat
+///
+///    After:
+///    SELECT d.cat, sum(__eager_agg_1)
+///    FROM (
+///      SELECT f.k, sum(f.amount) AS __eager_agg_1
+///      FROM fact f
+///      GROUP BY f.k
+///    ) JOIN dim d ON f.k = d.k
+///    GROUP BY d.cat
+/// ```
+///
+/// The partial aggregation groups by the join keys of its input, besides the
+/// grouping columns of this input, so that all the rows of one of its groups
+/// match the same rows of the other input. Its aggregate functions must then
+/// be decomposable, and give the same result when the rows they aggregate are
+/// repeated once for each matching row: `sum`, `count`, `min`, `max`,
+/// `bool_and` and `bool_or`.
+///
+/// As the partial aggregation only pays off when it reduces the number of
+/// joined rows, it is only pushed down when the statistics of its input (see
+/// [`TableSource::statistics`]) estimate that it has at most
+/// `eager_aggregation_max_group_ratio` groups per row. The functional
+/// dependencies of the input narrow the grouping columns this estimate uses,
+/// and no partial aggregation is pushed down if its grouping columns are
+/// already unique.
+#[derive(Default, Debug)]
+pub struct EagerAggregation {}
+
+impl EagerAggregation {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for EagerAggregation {
+    fn name(&self) -> &str {
+        "eager_aggregation"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        if !config.options().optimizer.enable_eager_aggregation {
+            return Ok(Transformed::no(plan));
+        }
+        let LogicalPlan::Aggregate(aggregate) = &plan else {
+            return Ok(Transformed::no(plan));
+        };
+        let LogicalPlan::Join(join) = aggregate.input.as_ref() else {
+            return Ok(Transformed::no(plan));
+        };
+        // Without grouping columns, `count` returns 0 for empty inputs while
+        // `sum` over the partial counts returns null
+        if join.join_type != JoinType::Inner
+            || aggregate.group_expr.is_empty()
+            || !aggregate
+                .group_expr
+                .iter()
+                .all(|expr| expr.try_as_col().is_some())
+        {
+            return Ok(Transformed::no(plan));
+        }
+        let Some(final_functions) = final_functions(&aggregate.aggr_expr, config) else {
+            return Ok(Transformed::no(plan));
+        };
+
+        for push_left in [true, false] {
+            if let Some(rewritten) =
+                try_push_down(aggregate, join, push_left, &final_functions, config)?
+            {
+                return Ok(Transformed::yes(rewritten));
+            }
+        }
+        Ok(Transformed::no(plan))
+    }
+}
+
+/// The aggregate functions which combine the results of the partial
+/// aggregates, if all of them can be pushed below a join
+fn final_functions(
+    aggr_expr: &[Expr],
+    config: &dyn OptimizerConfig,
+) -> Option<Vec<Arc<AggregateUDF>>> {
+    aggr_expr
+        .iter()
+        .map(|expr| match expr.clone().unalias() {
+            Expr::AggregateFunction(AggregateFunction {
+                func,
+                distinct: false,
+                filter: None,
+                order_by: None,
+                ..
+            }) => match func.name() {
+                "sum" | "min" | "max" | "bool_and" | "bool_or" => Some(func),
+                "count" => config.function_registry()?.udaf("sum").ok(),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Pushes a partial aggregation into the left or right input of `join`,
+/// returning `None` if it cannot be pushed or does not pay off
+fn try_push_down(
+    aggregate: &Aggregate,
+    join: &Join,
+    push_left: bool,
+    final_functions: &[Arc<AggregateUDF>],
+    config: &dyn OptimizerConfig,
+) -> Result<Option<LogicalPlan>> {
+    let (input, other) = if push_left {
+        (&join.left, &join.right)
+    } else {
+        (&join.right, &join.left)
+    };
+    let input_schema = input.schema();
+
+    // The arguments of all the aggregate functions must come from the input
+    if !aggregate
+        .aggr_expr
+        .iter()
+        .flat_map(|expr| expr.column_refs())
+        .all(|column| input_schema.has_column(column))
+    {
+        return Ok(None);
+    }
+
+    // The partial aggregation groups by the grouping columns of the input, and
+    // by the columns it joins on
+    let mut group_columns = HashSet::new();
+    for expr in &aggregate.group_expr {
+        let Some(column) = expr.try_as_col() else {
+            return Ok(None);
+        };
+        if input_schema.has_column(column) {
+            group_columns.insert(column);
+        } else if !other.schema().has_column(column) {
+            return Ok(None);
+        }
+    }
+    for (left_key, right_key) in &join.on {
+        let key = if push_left { left_key } else { right_key };
+        group_columns.extend(key.column_refs());
+    }
+    if let Some(filter) = &join.filter {
+        group_columns.extend(
+            filter
+                .column_refs()
+                .into_iter()
+                .filter(|column| input_schema.has_column(column)),
+        );
+    }
+    let mut group_columns = group_columns
+        .into_iter()
+        .map(|column| Ok((input_schema.index_of_column(column)?, column.clone())))
+        .collect::<Result<Vec<_>>>()?;
+    group_columns.sort_by_key(|(index, _)| *index);
+
+    let indices = group_columns
+        .iter()
+        .map(|(index, _)| *index)
+        .collect::<Vec<_>>();
+    if input_schema
+        .functional_dependencies()
+        .contains_unique_key(&indices)
+    {
+        return Ok(None);
+    }
+    let ratio = config.options().optimizer.eager_aggregation_max_group_ratio;
+    let group_names = group_columns
+        .iter()
+        .map(|(_, column)| column.flat_name())
+        .collect::<Vec<_>>();
+    let required_columns =
+        get_required_group_by_exprs_indices(input_schema, &group_names)
+            .map(|indices| {
+                indices
+                    .into_iter()
+                    .map(|index| &group_columns[index].1)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_else(|| group_columns.iter().map(|(_, column)| column).collect());
+    if !reduces_rows(input, &required_columns, ratio) {
+        return Ok(None);
+    }
+
+    let mut partial_aggr_expr = vec![];
+    let mut final_aggr_expr = vec![];
+    for (expr, func) in aggregate.aggr_expr.iter().zip(final_functions) {
+        let alias = config.alias_generator().next(EAGER_AGGREGATION_PREFIX);
+        partial_aggr_expr.push(expr.clone().unalias().alias(&alias));
+        final_aggr_expr.push(Expr::AggregateFunction(AggregateFunction::new_udf(
+            Arc::clone(func),
+            vec![col(alias)],
+            false,
+            None,
+            None,
+            None,
+        )));
+    }
+    let partial = Arc::new(LogicalPlan::Aggregate(Aggregate::try_new(
+        Arc::clone(input),
+        group_columns
+            .into_iter()
+            .map(|(_, column)| Expr::Column(column))
+            .collect(),
+        partial_aggr_expr,
+    )?));
+
+    let (left, right) = if push_left {
+        (partial, Arc::clone(&join.right))
+    } else {
+        (Arc::clone(&join.left), partial)
+    };
+    let schema = build_join_schema(left.schema(), right.schema(), &join.join_type)?;
+    let join = LogicalPlan::Join(Join {
+        left,
+        right,
+        schema: Arc::new(schema),
+        ..join.clone()
+    });
+    let final_aggregate = LogicalPlan::Aggregate(Aggregate::try_new(
+        Arc::new(join),
+        aggregate.group_expr.clone(),
+        final_aggr_expr,
+    )?);
+
+    // Keep the names and types of the original aggregates
+    let expr = final_aggregate
+        .schema()
+        .iter()
+        .zip(aggregate.schema.iter())
+        .map(
+            |((qualifier, field), (original_qualifier, original_field))| {
+                let expr = Expr::Column(Column::from((qualifier, field)));
+                if field.data_type() != original_field.data_type() {
+                    cast(expr, original_field.data_type().clone()).alias_qualified(
+                        original_qualifier.cloned(),
+                        original_field.name(),
+                    )
+                } else if qualifier != original_qualifier
+                    || field.name() != original_field.name()
+                {
+                    expr.alias_qualified(
+                        original_qualifier.cloned(),
+                        original_field.name(),
+                    )
+                } else {
+                    expr
+                }
+            },
+        )
+        .collect::<Vec<_>>();
+    Ok(Some(project(final_aggregate, expr)?))
+}
+
+/// Whether the statistics of `plan` estimate that grouping it by `columns`
+/// gives at most `ratio` groups per row
+fn reduces_rows(plan: &LogicalPlan, columns: &[&Column], ratio: f64) -> bool {
+    let Some(num_rows) = num_rows(plan) else {
+        return false;
+    };
+    let mut num_groups = 1.0;
+    for column in columns {
+        match distinct_count(plan, column) {
+            Some(distinct_count) => num_groups *= distinct_count as f64,
+            None => return false,
+        }
+    }
+    num_groups.min(num_rows as f64) <= ratio * num_rows as f64
+}
+
+/// The number of rows of the table scanned by `plan`, ignoring filters
+pub(crate) fn num_rows(plan: &LogicalPlan) -> Option<usize> {
+    match plan {
+        LogicalPlan::TableScan(scan) => {
+            let num_rows = *scan.source.statistics()?.num_rows.get_value()?;
+            Some(scan.fetch.map_or(num_rows, |fetch| fetch.min(num_rows)))
+        }
+        LogicalPlan::Filter(_)
+        | LogicalPlan::Projection(_)
+        | LogicalPlan::SubqueryAlias(_) => num_rows(plan.inputs()[0]),
+        _ => None,
+    }
+}
+
+/// The number of distinct values of `column` in the table scanned by `plan`
+fn distinct_count(plan: &LogicalPlan, column: &Column) -> Option<usize> {
+    let (source, index) = source_column(plan, column)?;
+    let statistics = source.statistics()?;
+    statistics
+        .column_statistics
+        .get(index)?
+        .distinct_count
+        .get_value()
+        .copied()
+}
+
+/// The table source and the index of the field of `column` of `plan`, if it is
+/// a column of a scanned table
+fn source_column(
+    plan: &LogicalPlan,
+    column: &Column,
+) -> Option<(Arc<dyn TableSource>, usize)> {
+    match plan {
+        LogicalPlan::TableScan(scan) => {
+            if !scan.projected_schema.has_column(column) {
+                return None;
+            }
+            let index = scan.source.schema().index_of(&column.name).ok()?;
+            Some((Arc::clone(&scan.source), index))
+        }
+        LogicalPlan::Filter(filter) => source_column(&filter.input, column),
+        LogicalPlan::SubqueryAlias(alias) => {
+            let index = alias.schema.index_of_column(column).ok()?;
+            let input_column = Column::from(alias.input.schema().qualified_field(index));
+            source_column(&alias.input, &input_column)
+        }
+        LogicalPlan::Projection(projection) => {
+            let index = projection.schema.index_of_column(column).ok()?;
+            match &projection.expr[index] {
+                Expr::Column(input_column) => {
+                    source_column(&projection.input, input_column)
+                }
+                Expr::Alias(Alias { expr, .. }) => match expr.as_ref() {
+                    Expr::Column(input_column) => {
+                        source_column(&projection.input, input_column)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use datafusion_common::stats::Precision;
+    use datafusion_common::{ColumnStatistics, Statistics};
+    use datafusion_expr::{LogicalPlanBuilder, TableType};
+    use datafusion_functions_aggregate::expr_fn::{count, max, sum};
+
+    /// A table source with statistics
+    struct StatisticsSource {
+        schema: SchemaRef,
+        statistics: Option<Statistics>,
+    }
+
+    impl TableSource for StatisticsSource {
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            Arc::clone(&self.schema)
+        }
+
+        fn table_type(&self) -> TableType {
+            TableType::Base
+        }
+
+        fn statistics(&self) -> Option<Statistics> {
+            self.statistics.clone()
+        }
+    }
+
+    /// Scans a table with the given number of rows and of distinct values for
+    /// each of its `Int32` columns
+    fn scan(
+        name: &str,
+        num_rows: Option<usize>,
+        columns: &[(&str, Option<usize>)],
+    ) -> Result<LogicalPlanBuilder> {
+        let schema = Schema::new(
+            columns
+                .iter()
+                .map(|(name, _)| Field::new(*name, DataType::Int32, true))
+                .collect::<Vec<_>>(),
+        );
+        let statistics = num_rows.map(|num_rows| Statistics {
+            num_rows: Precision::Exact(num_rows),
+            total_byte_size: Precision::Absent,
+            column_statistics: columns
+                .iter()
+                .map(|(_, distinct_count)| ColumnStatistics {
+                    distinct_count: distinct_count
+                        .map_or(Precision::Absent, Precision::Exact),
+                    ..ColumnStatistics::new_unknown()
+                })
+                .collect(),
+        });
+        let source = StatisticsSource {
+            schema: Arc::new(schema),
+            statistics,
+        };
+        LogicalPlanBuilder::scan(name, Arc::new(source), None)
+    }
+
+    fn fact(num_rows: usize) -> Result<LogicalPlanBuilder> {
+        scan(
+            "fact",
+            Some(num_rows),
+            &[("k", Some(10)), ("amount", None), ("region", Some(4))],
+        )
+    }
+
+    fn dim() -> Result<LogicalPlanBuilder> {
+        scan("dim", Some(10), &[("k", Some(10)), ("cat", Some(3))])
+    }
+
+    fn assert_optimized_plan_equal(plan: LogicalPlan, expected: &str) -> Result<()> {
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(EagerAggregation::new()),
+            plan,
+            expected,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn push_down_sum() -> Result<()> {
+        let plan = fact(1000)?
+            .join(
+                dim()?.build()?,
+                JoinType::Inner,
+                (vec!["k"], vec!["k"]),
+                None,
+            )?
+            .aggregate(vec![col("dim.cat")], vec![sum(col("fact.amount"))])?
+            .build()?;
+
+        let expected = "Projection: dim.cat, sum(__eager_agg_1) AS sum(fact.amount) [cat:Int32;N, sum(fact.amount):Int64;N]\
+            \n  Aggregate: groupBy=[[dim.cat]], aggr=[[sum(__eager_agg_1)]] [cat:Int32;N, sum(__eager_agg_1):Int64;N]\
+            \n    Inner Join: fact.k = dim.k [k:Int32;N, __eager_agg_1:Int64;N, k:Int32;N, cat:Int32;N]\
+            \n      Aggregate: groupBy=[[fact.k]], aggr=[[sum(fact.amount) AS __eager_agg_1]] [k:Int32;N, __eager_agg_1:Int64;N]\
+            \n        TableScan: fact [k:Int32;N, amount:Int32;N, region:Int32;N]\
+            \n      TableScan: dim [k:Int32;N, cat:Int32;N]";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn push_down_with_grouping_columns_of_both_inputs() -> Result<()> {
+        let plan = fact(1000)?
+            .join(
+                dim()?.build()?,
+                JoinType::Inner,
+                (vec!["k"], vec!["k"]),
+                None,
+            )?
+            .aggregate(
+                vec![col("fact.region"), col("dim.cat")],
+                vec![sum(col("fact.amount")), max(col("fact.amount"))],
+            )?
+            .build()?;
+
+        let expected = "Projection: fact.region, dim.cat, sum(__eager_agg_1) AS sum(fact.amount), max(__eager_agg_2) AS max(fact.amount) [region:Int32;N, cat:Int32;N, sum(fact.amount):Int64;N, max(fact.amount):Int32;N]\
+            \n  Aggregate: groupBy=[[fact.region, dim.cat]], aggr=[[sum(__eager_agg_1), max(__eager_agg_2)]] [region:Int32;N, cat:Int32;N, sum(__eager_agg_1):Int64;N, max(__eager_agg_2):Int32;N]\
+            \n    Inner Join: fact.k = dim.k [k:Int32;N, region:Int32;N, __eager_agg_1:Int64;N, __eager_agg_2:Int32;N, k:Int32;N, cat:Int32;N]\
+            \n      Aggregate: groupBy=[[fact.k, fact.region]], aggr=[[sum(fact.amount) AS __eager_agg_1, max(fact.amount) AS __eager_agg_2]] [k:Int32;N, region:Int32;N, __eager_agg_1:Int64;N, __eager_agg_2:Int32;N]\
+            \n        TableScan: fact [k:Int32;N, amount:Int32;N, region:Int32;N]\
+            \n      TableScan: dim [k:Int32;N, cat:Int32;N]";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn no_push_down_without_statistics() -> Result<()> {
+        let plan = scan("fact", None, &[("k", None), ("amount", None)])?
+            .join(
+                dim()?.build()?,
+                JoinType::Inner,
+                (vec!["k"], vec!["k"]),
+                None,
+            )?
+            .aggregate(vec![col("dim.cat")], vec![sum(col("fact.amount"))])?
+            .build()?;
+
+        let expected = "Aggregate: groupBy=[[dim.cat]], aggr=[[sum(fact.amount)]] [cat:Int32;N, sum(fact.amount):Int64;N]\
+            \n  Inner Join: fact.k = dim.k [k:Int32;N, amount:Int32;N, k:Int32;N, cat:Int32;N]\
+            \n    TableScan: fact [k:Int32;N, amount:Int32;N]\
+            \n    TableScan: dim [k:Int32;N, cat:Int32;N]";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn no_push_down_with_too_many_groups() -> Result<()> {
+        // 10 groups for 15 rows
+        let plan = fact(15)?
+            .join(
+                dim()?.build()?,
+                JoinType::Inner,
+                (vec!["k"], vec!["k"]),
+                None,
+            )?
+            .aggregate(vec![col("dim.cat")], vec![sum(col("fact.amount"))])?
+            .build()?;
+
+        let expected = "Aggregate: groupBy=[[dim.cat]], aggr=[[sum(fact.amount)]] [cat:Int32;N, sum(fact.amount):Int64;N]\
+            \n  Inner Join: fact.k = dim.k [k:Int32;N, amount:Int32;N, region:Int32;N, k:Int32;N, cat:Int32;N]\
+            \n    TableScan: fact [k:Int32;N, amount:Int32;N, region:Int32;N]\
+            \n    TableScan: dim [k:Int32;N, cat:Int32;N]";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn no_push_down_of_count_without_function_registry() -> Result<()> {
+        let plan = fact(1000)?
+            .join(
+                dim()?.build()?,
+                JoinType::Inner,
+                (vec!["k"], vec!["k"]),
+                None,
+            )?
+            .aggregate(vec![col("dim.cat")], vec![count(col("fact.amount"))])?
+            .build()?;
+
+        let expected = "Aggregate: groupBy=[[dim.cat]], aggr=[[count(fact.amount)]] [cat:Int32;N, count(fact.amount):Int64]\
+            \n  Inner Join: fact.k = dim.k [k:Int32;N, amount:Int32;N, region:Int32;N, k:Int32;N, cat:Int32;N]\
+            \n    TableScan: fact [k:Int32;N, amount:Int32;N, region:Int32;N]\
+            \n    TableScan: dim [k:Int32;N, cat:Int32;N]";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn no_push_down_of_aggregates_of_both_inputs() -> Result<()> {
+        let plan = fact(1000)?
+            .join(
+                dim()?.build()?,
+                JoinType::Inner,
+                (vec!["k"], vec!["k"]),
+                None,
+            )?
+            .aggregate(
+                vec![col("dim.cat")],
+                vec![sum(col("fact.amount")), max(col("dim.k"))],
+            )?
+            .build()?;
+
+        let expected = "Aggregate: groupBy=[[dim.cat]], aggr=[[sum(fact.amount), max(dim.k)]] [cat:Int32;N, sum(fact.amount):Int64;N, max(dim.k):Int32;N]\
+            \n  Inner Join: fact.k = dim.k [k:Int32;N, amount:Int32;N, region:Int32;N, k:Int32;N, cat:Int32;N]\
+            \n    TableScan: fact [k:Int32;N, amount:Int32;N, region:Int32;N]\
+            \n    TableScan: dim [k:Int32;N, cat:Int32;N]";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn no_push_down_of_left_join() -> Result<()> {
+        let plan = fact(1000)?
+            .join(
+                dim()?.build()?,
+                JoinType::Left,
+                (vec!["k"], vec!["k"]),
+                None,
+            )?
+            .aggregate(vec![col("dim.cat")], vec![sum(col("fact.amount"))])?
+            .build()?;
+
+        let expected = "Aggregate: groupBy=[[dim.cat]], aggr=[[sum(fact.amount)]] [cat:Int32;N, sum(fact.amount):Int64;N]\
+            \n  Left Join: fact.k = dim.k [k:Int32;N, amount:Int32;N, region:Int32;N, k:Int32;N, cat:Int32;N]\
+            \n    TableScan: fact [k:Int32;N, amount:Int32;N, region:Int32;N]\
+            \n    TableScan: dim [k:Int32;N, cat:Int32;N]";
+        assert_optimized_plan_equal(plan, expected)
+    }
+}
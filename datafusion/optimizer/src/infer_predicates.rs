@@ -0,0 +1,670 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`InferPredicates`] infers the predicates implied by join equivalences
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::optimizer::ApplyOrder;
+use crate::push_down_filter::on_lr_is_preserved;
+use crate::utils::has_all_column_refs;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_common::{Column, DFSchema, Result};
+use datafusion_expr::expr_rewriter::replace_col;
+use datafusion_expr::logical_plan::{Join, JoinType, LogicalPlan};
+use datafusion_expr::utils::{conjunction, split_conjunction};
+use datafusion_expr::{BinaryExpr, Expr, Filter, Operator, TableProviderFilterPushDown};
+
+/// Optimizer rule that adds the predicates implied by the equi-join keys of
+/// joins to their inputs, so that [`PushDownFilter`] pushes them down.
+///
+/// For example, the predicate `t1.a > 10` of the left input of the join below
+/// implies that only the rows of `t2` with `t2.b > 10` can match:
+///
+/// ```text
+/// Inner Join: t1.a = t2.b
+///   Filter: t1.a > 10
+///     TableScan: t1
+///   TableScan: t2
+/// ```
+///
+/// So it is rewritten to:
+///
+/// ```text
+/// Inner Join: t1.a = t2.b
+///   Filter: t1.a > 10
+///     TableScan: t1
+///   Filter: t2.b > 10
+///     TableScan: t2
+/// ```
+///
+/// The predicates known to hold for an input are those of its filters and of
+/// the filters its table scans apply exactly, mapped through projections,
+/// aggregates and subquery aliases. The predicates of a union are those that
+/// hold for all of its inputs, and those of an inner join include its equi-join
+/// keys, so that predicates are inferred transitively through chains of joins
+/// and equality predicates (`t1.a = t2.b AND t2.b = t3.c AND t1.a > 10`
+/// implies `t3.c > 10`).
+///
+/// Only deterministic predicates of a single column are inferred, and only into
+/// the inputs of a join which may be filtered by its join condition: both for
+/// inner and semi joins, and only the non-preserved input of outer and anti
+/// joins.
+///
+/// [`PushDownFilter`]: crate::push_down_filter::PushDownFilter
+#[derive(Default, Debug)]
+pub struct InferPredicates {}
+
+impl InferPredicates {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for InferPredicates {
+    fn name(&self) -> &str {
+        "infer_predicates"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::BottomUp)
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let LogicalPlan::Join(join) = plan else {
+            return Ok(Transformed::no(plan));
+        };
+        let (left_inferred, right_inferred) = infer_join_predicates(&join)?;
+        if left_inferred.is_empty() && right_inferred.is_empty() {
+            return Ok(Transformed::no(LogicalPlan::Join(join)));
+        }
+
+        let mut join = join;
+        if let Some(predicate) = conjunction(left_inferred) {
+            join.left =
+                Arc::new(LogicalPlan::Filter(Filter::try_new(predicate, join.left)?));
+        }
+        if let Some(predicate) = conjunction(right_inferred) {
+            join.right =
+                Arc::new(LogicalPlan::Filter(Filter::try_new(predicate, join.right)?));
+        }
+        Ok(Transformed::yes(LogicalPlan::Join(join)))
+    }
+}
+
+/// Returns the new predicates implied for the left and right inputs of `join`
+fn infer_join_predicates(join: &Join) -> Result<(Vec<Expr>, Vec<Expr>)> {
+    let join_keys = join
+        .on
+        .iter()
+        .filter_map(|(l, r)| Some((l.try_as_col()?.clone(), r.try_as_col()?.clone())))
+        .collect::<Vec<_>>();
+    if join_keys.is_empty() {
+        return Ok((vec![], vec![]));
+    }
+    let (left_filtered, right_filtered) = on_lr_is_preserved(join.join_type);
+    if !left_filtered && !right_filtered {
+        return Ok((vec![], vec![]));
+    }
+
+    let left_known = known_predicates(&join.left)?;
+    let right_known = known_predicates(&join.right)?;
+
+    let mut classes = EquivalenceClasses::default();
+    for (l, r) in &join_keys {
+        classes.add_equality(l, r);
+    }
+    for predicate in left_known.iter().chain(&right_known) {
+        if let Some((l, r)) = column_equality(predicate) {
+            classes.add_equality(l, r);
+        }
+    }
+
+    let left_inferred = if left_filtered {
+        infer_predicates(&right_known, &left_known, join.left.schema(), &classes)
+    } else {
+        vec![]
+    };
+    let right_inferred = if right_filtered {
+        infer_predicates(&left_known, &right_known, join.right.schema(), &classes)
+    } else {
+        vec![]
+    };
+    Ok((left_inferred, right_inferred))
+}
+
+/// Returns the predicates of the columns of `schema` which are implied by the
+/// `source` predicates and the equivalences of `classes`, and are not already
+/// `known`
+fn infer_predicates(
+    source: &[Expr],
+    known: &[Expr],
+    schema: &DFSchema,
+    classes: &EquivalenceClasses,
+) -> Vec<Expr> {
+    let mut inferred = vec![];
+    for predicate in source {
+        let Some(column) = single_column(predicate) else {
+            continue;
+        };
+        for equivalent in classes.equivalents(column) {
+            if !schema.has_column(equivalent) {
+                continue;
+            }
+            let replace_map = HashMap::from([(column, equivalent)]);
+            let Ok(predicate) = replace_col(predicate.clone(), &replace_map) else {
+                continue;
+            };
+            if !known.contains(&predicate) && !inferred.contains(&predicate) {
+                inferred.push(predicate);
+            }
+        }
+    }
+    inferred
+}
+
+/// The column referenced by `predicate`, if it is a deterministic predicate of
+/// a single column which can be inferred for its equivalent columns
+fn single_column(predicate: &Expr) -> Option<&Column> {
+    if column_equality(predicate).is_some() {
+        return None;
+    }
+    let mut columns = predicate.column_refs().into_iter();
+    let column = columns.next()?;
+    if columns.next().is_some() {
+        return None;
+    }
+    let is_inferable = !predicate
+        .exists(|expr| {
+            Ok(expr.is_volatile_node()
+                || matches!(
+                    expr,
+                    Expr::ScalarSubquery(_)
+                        | Expr::Exists(_)
+                        | Expr::InSubquery(_)
+                        | Expr::OuterReferenceColumn(_, _)
+                ))
+        })
+        .unwrap_or(true);
+    is_inferable.then_some(column)
+}
+
+/// The columns of `predicate` if it is an equality of two columns
+fn column_equality(predicate: &Expr) -> Option<(&Column, &Column)> {
+    match predicate {
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Eq,
+            right,
+        }) => Some((left.try_as_col()?, right.try_as_col()?)),
+        _ => None,
+    }
+}
+
+/// Returns the deterministic predicates known to hold for all the rows
+/// produced by `plan`, in terms of the columns of its schema
+fn known_predicates(plan: &LogicalPlan) -> Result<Vec<Expr>> {
+    let predicates = match plan {
+        LogicalPlan::Filter(filter) => {
+            let mut predicates = known_predicates(&filter.input)?;
+            predicates.extend(split_conjunction(&filter.predicate).into_iter().cloned());
+            predicates
+        }
+        LogicalPlan::TableScan(scan) => {
+            let filters = scan.filters.iter().collect::<Vec<_>>();
+            let Ok(pushdown) = scan.source.supports_filters_pushdown(&filters) else {
+                return Ok(vec![]);
+            };
+            let columns = scan
+                .projected_schema
+                .columns()
+                .into_iter()
+                .collect::<HashSet<_>>();
+            filters
+                .into_iter()
+                .zip(pushdown)
+                .filter(|(filter, pushdown)| {
+                    *pushdown == TableProviderFilterPushDown::Exact
+                        && has_all_column_refs(filter, &columns)
+                })
+                .flat_map(|(filter, _)| split_conjunction(filter))
+                .cloned()
+                .collect()
+        }
+        LogicalPlan::Projection(projection) => {
+            let replace_map = projection
+                .expr
+                .iter()
+                .enumerate()
+                .map(|(i, expr)| {
+                    (
+                        expr.clone().unalias(),
+                        Column::from(projection.schema.qualified_field(i)),
+                    )
+                })
+                .collect();
+            replace_exprs(known_predicates(&projection.input)?, &replace_map)
+        }
+        LogicalPlan::SubqueryAlias(alias) => {
+            let replace_map = alias
+                .input
+                .schema()
+                .columns()
+                .into_iter()
+                .zip(alias.schema.columns())
+                .map(|(column, alias_column)| (Expr::Column(column), alias_column))
+                .collect();
+            replace_exprs(known_predicates(&alias.input)?, &replace_map)
+        }
+        LogicalPlan::Aggregate(aggregate) => {
+            let group_expr = aggregate.group_expr.iter().all(|expr| {
+                !matches!(expr, Expr::GroupingSet(_)) && !expr.is_volatile_node()
+            });
+            if !group_expr {
+                return Ok(vec![]);
+            }
+            let replace_map = aggregate
+                .group_expr
+                .iter()
+                .enumerate()
+                .map(|(i, expr)| {
+                    (
+                        expr.clone().unalias(),
+                        Column::from(aggregate.schema.qualified_field(i)),
+                    )
+                })
+                .collect();
+            replace_exprs(known_predicates(&aggregate.input)?, &replace_map)
+        }
+        LogicalPlan::Join(join) => {
+            let (left_preserved, right_preserved) = match join.join_type {
+                JoinType::Inner => (true, true),
+                JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti => (true, false),
+                JoinType::Right | JoinType::RightSemi | JoinType::RightAnti => {
+                    (false, true)
+                }
+                JoinType::Full => (false, false),
+            };
+            let mut predicates = vec![];
+            if left_preserved {
+                predicates.extend(known_predicates(&join.left)?);
+            }
+            if right_preserved {
+                predicates.extend(known_predicates(&join.right)?);
+            }
+            if join.join_type == JoinType::Inner {
+                predicates.extend(join.on.iter().map(|(l, r)| l.clone().eq(r.clone())));
+                if let Some(filter) = &join.filter {
+                    predicates.extend(split_conjunction(filter).into_iter().cloned());
+                }
+            }
+            predicates
+        }
+        LogicalPlan::Union(union) => {
+            let inputs = union
+                .inputs
+                .iter()
+                .map(|input| {
+                    let replace_map = input
+                        .schema()
+                        .columns()
+                        .into_iter()
+                        .zip(union.schema.columns())
+                        .map(|(column, union_column)| {
+                            (Expr::Column(column), union_column)
+                        })
+                        .collect();
+                    Ok(replace_exprs(known_predicates(input)?, &replace_map))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let mut inputs = inputs.into_iter();
+            let Some(mut predicates) = inputs.next() else {
+                return Ok(vec![]);
+            };
+            for input in inputs {
+                predicates.retain(|predicate| input.contains(predicate));
+            }
+            predicates
+        }
+        LogicalPlan::Sort(_)
+        | LogicalPlan::Limit(_)
+        | LogicalPlan::Repartition(_)
+        | LogicalPlan::Distinct(_) => {
+            let input = plan.inputs()[0];
+            if input.schema().columns() != plan.schema().columns() {
+                return Ok(vec![]);
+            }
+            known_predicates(input)?
+        }
+        _ => vec![],
+    };
+
+    let mut known = Vec::with_capacity(predicates.len());
+    for predicate in predicates {
+        if !predicate.is_volatile()? && !known.contains(&predicate) {
+            known.push(predicate);
+        }
+    }
+    Ok(known)
+}
+
+/// Rewrites `predicates` in terms of the outputs of a plan, by replacing the
+/// expressions it computes with the columns of its output. Predicates which
+/// reference other columns are dropped.
+fn replace_exprs(
+    predicates: Vec<Expr>,
+    replace_map: &HashMap<Expr, Column>,
+) -> Vec<Expr> {
+    predicates
+        .into_iter()
+        .filter_map(|predicate| {
+            let mut complete = true;
+            let predicate = predicate
+                .transform_down(|expr| {
+                    if let Some(column) = replace_map.get(&expr) {
+                        return Ok(Transformed::new(
+                            Expr::Column(column.clone()),
+                            true,
+                            TreeNodeRecursion::Jump,
+                        ));
+                    }
+                    if matches!(expr, Expr::Column(_)) {
+                        complete = false;
+                    }
+                    Ok(Transformed::no(expr))
+                })
+                .ok()?
+                .data;
+            complete.then_some(predicate)
+        })
+        .collect()
+}
+
+/// Classes of columns known to be equal
+#[derive(Default, Debug)]
+struct EquivalenceClasses {
+    classes: Vec<Vec<Column>>,
+}
+
+impl EquivalenceClasses {
+    /// Records that the columns `l` and `r` are equal
+    fn add_equality(&mut self, l: &Column, r: &Column) {
+        let l_class = self.classes.iter().position(|class| class.contains(l));
+        let r_class = self.classes.iter().position(|class| class.contains(r));
+        match (l_class, r_class) {
+            (Some(l_class), Some(r_class)) if l_class == r_class => {}
+            (Some(l_class), Some(r_class)) => {
+                let r_columns = self.classes.swap_remove(r_class);
+                // `swap_remove` moved the last class to `r_class`
+                let l_class = if l_class == self.classes.len() {
+                    r_class
+                } else {
+                    l_class
+                };
+                self.classes[l_class].extend(r_columns);
+            }
+            (Some(l_class), None) => self.classes[l_class].push(r.clone()),
+            (None, Some(r_class)) => self.classes[r_class].push(l.clone()),
+            (None, None) => self.classes.push(vec![l.clone(), r.clone()]),
+        }
+    }
+
+    /// The other columns known to be equal to `column`
+    fn equivalents<'a>(&'a self, column: &'a Column) -> impl Iterator<Item = &'a Column> {
+        self.classes
+            .iter()
+            .filter(|class| class.contains(column))
+            .flatten()
+            .filter(move |equivalent| *equivalent != column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+
+    use datafusion_expr::{col, in_list, lit, LogicalPlanBuilder};
+
+    fn assert_optimized_plan_equal(plan: LogicalPlan, expected: &str) -> Result<()> {
+        assert_optimized_plan_eq(Arc::new(InferPredicates::new()), plan, expected)
+    }
+
+    fn filtered_scan(name: &str, predicate: Expr) -> Result<LogicalPlan> {
+        LogicalPlanBuilder::from(test_table_scan_with_name(name)?)
+            .filter(predicate)?
+            .build()
+    }
+
+    #[test]
+    fn infer_through_inner_join() -> Result<()> {
+        let plan =
+            LogicalPlanBuilder::from(filtered_scan("t1", col("t1.a").gt(lit(10u32)))?)
+                .join(
+                    test_table_scan_with_name("t2")?,
+                    JoinType::Inner,
+                    (vec!["a"], vec!["b"]),
+                    None,
+                )?
+                .build()?;
+
+        let expected = "Inner Join: t1.a = t2.b\
+            \n  Filter: t1.a > UInt32(10)\
+            \n    TableScan: t1\
+            \n  Filter: t2.b > UInt32(10)\
+            \n    TableScan: t2";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn infer_in_list_into_left_input() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan_with_name("t1")?)
+            .join(
+                filtered_scan(
+                    "t2",
+                    in_list(col("t2.b"), vec![lit(1u32), lit(2u32)], false),
+                )?,
+                JoinType::Inner,
+                (vec!["a"], vec!["b"]),
+                None,
+            )?
+            .build()?;
+
+        let expected = "Inner Join: t1.a = t2.b\
+            \n  Filter: t1.a IN ([UInt32(1), UInt32(2)])\
+            \n    TableScan: t1\
+            \n  Filter: t2.b IN ([UInt32(1), UInt32(2)])\
+            \n    TableScan: t2";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn infer_transitively() -> Result<()> {
+        let plan =
+            LogicalPlanBuilder::from(filtered_scan("t1", col("t1.a").gt(lit(10u32)))?)
+                .join(
+                    test_table_scan_with_name("t2")?,
+                    JoinType::Inner,
+                    (vec!["t1.a"], vec!["t2.a"]),
+                    None,
+                )?
+                .join(
+                    test_table_scan_with_name("t3")?,
+                    JoinType::Inner,
+                    (vec!["t2.a"], vec!["t3.c"]),
+                    None,
+                )?
+                .build()?;
+
+        let expected = "Inner Join: t2.a = t3.c\
+            \n  Inner Join: t1.a = t2.a\
+            \n    Filter: t1.a > UInt32(10)\
+            \n      TableScan: t1\
+            \n    Filter: t2.a > UInt32(10)\
+            \n      TableScan: t2\
+            \n  Filter: t3.c > UInt32(10)\
+            \n    TableScan: t3";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn infer_through_column_equality() -> Result<()> {
+        let left = LogicalPlanBuilder::from(test_table_scan_with_name("t1")?)
+            .filter(col("t1.a").eq(col("t1.b")).and(col("t1.b").lt(lit(5u32))))?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(left)
+            .join(
+                test_table_scan_with_name("t2")?,
+                JoinType::Inner,
+                (vec!["a"], vec!["a"]),
+                None,
+            )?
+            .build()?;
+
+        let expected = "Inner Join: t1.a = t2.a\
+            \n  Filter: t1.a = t1.b AND t1.b < UInt32(5)\
+            \n    TableScan: t1\
+            \n  Filter: t2.a < UInt32(5)\
+            \n    TableScan: t2";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn infer_only_into_non_preserved_input_of_left_join() -> Result<()> {
+        let plan =
+            LogicalPlanBuilder::from(filtered_scan("t1", col("t1.a").gt(lit(10u32)))?)
+                .join(
+                    filtered_scan("t2", col("t2.b").lt(lit(20u32)))?,
+                    JoinType::Left,
+                    (vec!["a"], vec!["b"]),
+                    None,
+                )?
+                .build()?;
+
+        let expected = "Left Join: t1.a = t2.b\
+            \n  Filter: t1.a > UInt32(10)\
+            \n    TableScan: t1\
+            \n  Filter: t2.b > UInt32(10)\
+            \n    Filter: t2.b < UInt32(20)\
+            \n      TableScan: t2";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn no_inference_for_full_join() -> Result<()> {
+        let plan =
+            LogicalPlanBuilder::from(filtered_scan("t1", col("t1.a").gt(lit(10u32)))?)
+                .join(
+                    test_table_scan_with_name("t2")?,
+                    JoinType::Full,
+                    (vec!["a"], vec!["b"]),
+                    None,
+                )?
+                .build()?;
+
+        let expected = "Full Join: t1.a = t2.b\
+            \n  Filter: t1.a > UInt32(10)\
+            \n    TableScan: t1\
+            \n  TableScan: t2";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn infer_predicates_of_all_union_inputs() -> Result<()> {
+        let union = LogicalPlanBuilder::from(filtered_scan(
+            "t1",
+            col("t1.a").gt(lit(10u32)).and(col("t1.b").gt(lit(1u32))),
+        )?)
+        .union(filtered_scan("t2", col("t2.a").gt(lit(10u32)))?)?
+        .build()?;
+        let plan = LogicalPlanBuilder::from(union)
+            .join(
+                test_table_scan_with_name("t3")?,
+                JoinType::Inner,
+                (vec!["a"], vec!["a"]),
+                None,
+            )?
+            .build()?;
+
+        let expected = "Inner Join: t1.a = t3.a\
+            \n  Union\
+            \n    Filter: t1.a > UInt32(10) AND t1.b > UInt32(1)\
+            \n      TableScan: t1\
+            \n    Filter: t2.a > UInt32(10)\
+            \n      TableScan: t2\
+            \n  Filter: t3.a > UInt32(10)\
+            \n    TableScan: t3";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn infer_through_projection_alias() -> Result<()> {
+        let left =
+            LogicalPlanBuilder::from(filtered_scan("t1", col("t1.a").gt(lit(10u32)))?)
+                .project(vec![col("t1.a").alias("x")])?
+                .alias("s")?
+                .build()?;
+        let plan = LogicalPlanBuilder::from(left)
+            .join(
+                test_table_scan_with_name("t2")?,
+                JoinType::Inner,
+                (vec!["s.x"], vec!["t2.a"]),
+                None,
+            )?
+            .build()?;
+
+        let expected = "Inner Join: s.x = t2.a\
+            \n  SubqueryAlias: s\
+            \n    Projection: t1.a AS x\
+            \n      Filter: t1.a > UInt32(10)\
+            \n        TableScan: t1\
+            \n  Filter: t2.a > UInt32(10)\
+            \n    TableScan: t2";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn no_inference_of_multi_column_predicates() -> Result<()> {
+        let plan =
+            LogicalPlanBuilder::from(filtered_scan("t1", col("t1.a").gt(col("t1.c")))?)
+                .join(
+                    test_table_scan_with_name("t2")?,
+                    JoinType::Inner,
+                    (vec!["a"], vec!["b"]),
+                    None,
+                )?
+                .build()?;
+
+        let expected = "Inner Join: t1.a = t2.b\
+            \n  Filter: t1.a > t1.c\
+            \n    TableScan: t1\
+            \n  TableScan: t2";
+        assert_optimized_plan_equal(plan, expected)
+    }
+}
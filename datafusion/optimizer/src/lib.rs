@@ -33,7 +33,9 @@
 pub mod analyzer;
 pub mod common_subexpr_eliminate;
 pub mod decorrelate;
+pub mod decorrelate_general;
 pub mod decorrelate_predicate_subquery;
+pub mod eager_aggregation;
 pub mod eliminate_cross_join;
 pub mod eliminate_duplicated_expr;
 pub mod eliminate_filter;
@@ -45,13 +47,16 @@ pub mod eliminate_one_union;
 pub mod eliminate_outer_join;
 pub mod extract_equijoin_predicate;
 pub mod filter_null_join_keys;
+pub mod infer_predicates;
 pub mod optimize_projections;
 pub mod optimizer;
+pub mod plan_signature;
 pub mod propagate_empty_relation;
 pub mod push_down_filter;
 pub mod push_down_limit;
 pub mod replace_distinct_aggregate;
 pub mod scalar_subquery_to_join;
+pub mod semi_join_reduction;
 pub mod simplify_expressions;
 pub mod single_distinct_to_groupby;
 pub mod unwrap_cast_in_comparison;
@@ -66,7 +71,6 @@ pub use optimizer::{Optimizer, OptimizerConfig, OptimizerContext, OptimizerRule}
 pub use utils::optimize_children;
 
 pub(crate) mod join_key_set;
-mod plan_signature;
 
 #[cfg(test)]
 #[ctor::ctor]
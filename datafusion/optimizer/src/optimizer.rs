@@ -27,13 +27,16 @@ use log::{debug, warn};
 
 use datafusion_common::alias::AliasGenerator;
 use datafusion_common::config::ConfigOptions;
+use datafusion_common::display::{OptimizerPhase, OptimizerTraceEvent};
 use datafusion_common::instant::Instant;
 use datafusion_common::tree_node::{Transformed, TreeNodeRewriter};
 use datafusion_common::{internal_err, DFSchema, DataFusionError, Result};
 use datafusion_expr::logical_plan::LogicalPlan;
 
 use crate::common_subexpr_eliminate::CommonSubexprEliminate;
+use crate::decorrelate_general::DecorrelateGeneralSubquery;
 use crate::decorrelate_predicate_subquery::DecorrelatePredicateSubquery;
+use crate::eager_aggregation::EagerAggregation;
 use crate::eliminate_cross_join::EliminateCrossJoin;
 use crate::eliminate_duplicated_expr::EliminateDuplicatedExpr;
 use crate::eliminate_filter::EliminateFilter;
@@ -45,6 +48,7 @@ use crate::eliminate_one_union::EliminateOneUnion;
 use crate::eliminate_outer_join::EliminateOuterJoin;
 use crate::extract_equijoin_predicate::ExtractEquijoinPredicate;
 use crate::filter_null_join_keys::FilterNullJoinKeys;
+use crate::infer_predicates::InferPredicates;
 use crate::optimize_projections::OptimizeProjections;
 use crate::plan_signature::LogicalPlanSignature;
 use crate::propagate_empty_relation::PropagateEmptyRelation;
@@ -52,6 +56,7 @@ use crate::push_down_filter::PushDownFilter;
 use crate::push_down_limit::PushDownLimit;
 use crate::replace_distinct_aggregate::ReplaceDistinctWithAggregate;
 use crate::scalar_subquery_to_join::ScalarSubqueryToJoin;
+use crate::semi_join_reduction::SemiJoinReduction;
 use crate::simplify_expressions::SimplifyExpressions;
 use crate::single_distinct_to_groupby::SingleDistinctToGroupBy;
 use crate::unwrap_cast_in_comparison::UnwrapCastInComparison;
@@ -249,6 +254,7 @@ impl Optimizer {
             Arc::new(EliminateJoin::new()),
             Arc::new(DecorrelatePredicateSubquery::new()),
             Arc::new(ScalarSubqueryToJoin::new()),
+            Arc::new(DecorrelateGeneralSubquery::new()),
             Arc::new(ExtractEquijoinPredicate::new()),
             Arc::new(EliminateDuplicatedExpr::new()),
             Arc::new(EliminateFilter::new()),
@@ -262,7 +268,10 @@ impl Optimizer {
             Arc::new(EliminateOuterJoin::new()),
             // Filters can't be pushed down past Limits, we should do PushDownFilter after PushDownLimit
             Arc::new(PushDownLimit::new()),
+            Arc::new(InferPredicates::new()),
             Arc::new(PushDownFilter::new()),
+            Arc::new(SemiJoinReduction::new()),
+            Arc::new(EagerAggregation::new()),
             Arc::new(SingleDistinctToGroupBy::new()),
             // The previous optimizations added expressions and projections,
             // that might benefit from the following rules
@@ -349,10 +358,40 @@ impl Optimizer {
     /// Optimizes the logical plan by applying optimizer rules, and
     /// invoking observer function after each call
     pub fn optimize<F>(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+        observer: F,
+    ) -> Result<LogicalPlan>
+    where
+        F: FnMut(&LogicalPlan, &dyn OptimizerRule),
+    {
+        self.optimize_internal(plan, config, observer, None)
+    }
+
+    /// Optimizes the logical plan like [`Self::optimize`], additionally
+    /// invoking `tracer` with an [`OptimizerTraceEvent`] describing the
+    /// elapsed time and plan changes of each successfully applied rule
+    pub fn optimize_with_trace<F, T>(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+        observer: F,
+        mut tracer: T,
+    ) -> Result<LogicalPlan>
+    where
+        F: FnMut(&LogicalPlan, &dyn OptimizerRule),
+        T: FnMut(&OptimizerTraceEvent),
+    {
+        self.optimize_internal(plan, config, observer, Some(&mut tracer))
+    }
+
+    fn optimize_internal<F>(
         &self,
         plan: LogicalPlan,
         config: &dyn OptimizerConfig,
         mut observer: F,
+        mut tracer: Option<&mut dyn FnMut(&OptimizerTraceEvent)>,
     ) -> Result<LogicalPlan>
     where
         F: FnMut(&LogicalPlan, &dyn OptimizerRule),
@@ -364,6 +403,9 @@ impl Optimizer {
         let mut previous_plans = HashSet::with_capacity(16);
         previous_plans.insert(LogicalPlanSignature::new(&new_plan));
 
+        // text of the current plan, only rendered when tracing
+        let mut plan_text = None;
+
         let mut i = 0;
         while i < options.optimizer.max_passes {
             log_plan(&format!("Optimizer input (pass {i})"), &new_plan);
@@ -377,6 +419,10 @@ impl Optimizer {
                     .then(|| new_plan.clone());
 
                 let starting_schema = Arc::clone(new_plan.schema());
+                if tracer.is_some() && plan_text.is_none() {
+                    plan_text = Some(new_plan.display_indent().to_string());
+                }
+                let rule_start = Instant::now();
 
                 let result = match rule.apply_order() {
                     // optimizer handles recursion
@@ -391,6 +437,7 @@ impl Optimizer {
                     assert_schema_is_the_same(rule.name(), &starting_schema, &tnr.data)?;
                     Ok(tnr)
                 });
+                let elapsed = rule_start.elapsed();
 
                 // Handle results
                 match (result, prev_plan) {
@@ -403,6 +450,19 @@ impl Optimizer {
                     ) => {
                         new_plan = data;
                         observer(&new_plan, rule.as_ref());
+                        if let Some(tracer) = tracer.as_mut() {
+                            let before = plan_text.take().unwrap_or_default();
+                            let after = new_plan.display_indent().to_string();
+                            tracer(&OptimizerTraceEvent::new(
+                                OptimizerPhase::Logical,
+                                rule.name(),
+                                i,
+                                elapsed,
+                                &before,
+                                &after,
+                            ));
+                            plan_text = Some(after);
+                        }
                         if transformed {
                             log_plan(rule.name(), &new_plan);
                         } else {
@@ -482,6 +542,7 @@ pub(crate) fn assert_schema_is_the_same(
 mod tests {
     use std::sync::{Arc, Mutex};
 
+    use datafusion_common::display::OptimizerTraceEvent;
     use datafusion_common::tree_node::Transformed;
     use datafusion_common::{plan_err, DFSchema, DFSchemaRef, DataFusionError, Result};
     use datafusion_expr::logical_plan::EmptyRelation;
@@ -640,6 +701,51 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn optimizer_traces_rules() -> Result<()> {
+        let opt = Optimizer::with_rules(vec![
+            Arc::new(RotateProjectionRule::new(false)),
+            Arc::new(BadRule {}),
+        ]);
+        let config = OptimizerContext::new()
+            .with_max_passes(16)
+            .with_skip_failing_rules(true);
+
+        let initial_plan = LogicalPlanBuilder::empty(false)
+            .project([lit(1), lit(2), lit(3)])?
+            .project([lit(100)])? // to not trigger changed schema error
+            .build()?;
+
+        let mut events: Vec<OptimizerTraceEvent> = Vec::new();
+        opt.optimize_with_trace(initial_plan, &config, observe, |e| {
+            events.push(e.clone())
+        })?;
+
+        // skipped failing rules are not traced
+        let summary: Vec<_> = events
+            .iter()
+            .map(|e| (e.rule_name.as_str(), e.pass, e.changed))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("rotate_projection", 0, true),
+                ("rotate_projection", 1, true),
+                ("rotate_projection", 2, true)
+            ]
+        );
+        assert_eq!(
+            events[0].diff,
+            "--- before\n+++ after\n\
+             @@ -1,3 +1,3 @@\n \
+             Projection: Int32(100)\n\
+             -  Projection: Int32(1), Int32(2), Int32(3)\n\
+             +  Projection: Int32(2), Int32(3), Int32(1)\n\
+             \x20    EmptyRelation\n"
+        );
+        Ok(())
+    }
+
     fn add_metadata_to_fields(schema: &DFSchema) -> DFSchemaRef {
         let new_fields = schema
             .iter()
@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+//! [`LogicalPlanSignature`]: a non-unique identifier of a [`LogicalPlan`]
+
 use std::{
     collections::hash_map::DefaultHasher,
     hash::{Hash, Hasher},
@@ -27,7 +29,7 @@ use datafusion_expr::LogicalPlan;
 /// Non-unique identifier of a [`LogicalPlan`].
 ///
 /// See [`LogicalPlanSignature::new`] for details.
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct LogicalPlanSignature {
     node_number: NonZeroUsize,
     plan_hash: u64,
@@ -0,0 +1,461 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`SemiJoinReduction`] pre-filters the scan of the larger input of a join
+//! with the join keys of its smaller, filtered input
+
+use std::sync::Arc;
+
+use crate::eager_aggregation::num_rows;
+use crate::optimize_projections::OptimizeProjections;
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_common::{Column, Result};
+use datafusion_expr::expr::{Alias, InSubquery};
+use datafusion_expr::logical_plan::{Join, JoinType, LogicalPlan, TableScan};
+use datafusion_expr::{
+    in_subquery, Expr, LogicalPlanBuilder, TableProviderFilterPushDown,
+};
+
+/// Semi-join reduction optimizer rule: filters the scan of the larger input
+/// of an equi join with the join keys of its smaller input, when this input
+/// is filtered.
+///
+/// ```text
+///    Before:
+///    SELECT f.amount, d.cat
+///    FROM fact f JOIN dim d ON f.k = d.k
+///    WHERE d.cat = 'a'
+///
+///    After:
+///    SELECT f.amount, d.cat
+///    FROM (
+///      SELECT * FROM fact f
+///      WHERE f.k IN (SELECT d.k FROM dim d WHERE d.cat = 'a')
+///    ) JOIN dim d ON f.k = d.k
+///    WHERE d.cat = 'a'
+/// ```
+///
+/// The `IN` subquery is only added to the filters of the [`TableScan`] of the
+/// larger input, and only if the table reports that it uses it inexactly (see
+/// [`TableProviderFilterPushDown::Inexact`]), as for parquet files: the join
+/// still removes the rows without a matching key, and the subquery only lets
+/// the scan skip files, row groups and rows in advance. The physical plan
+/// computes the keys of the subquery when it is executed, before scanning the
+/// table (see `SemiJoinReductionExec`), and passes them to the scan as an
+/// `IN` list or a bloom filter. The reduction is skipped when the subquery
+/// returns more than `semi_join_reduction_max_keys` rows. As the subquery is
+/// not part of a filter,
+/// [`DecorrelatePredicateSubquery`] does not rewrite it into a join.
+///
+/// The reduction only pays off when the filtered input has few rows
+/// compared to the other input, so it is only done when the statistics of
+/// the inputs (see [`TableSource::statistics`]) estimate that the filtered
+/// input has at most `semi_join_reduction_max_ratio` rows per row of the
+/// other input. The filters are estimated to keep
+/// `default_filter_selectivity` percent of the rows.
+///
+/// [`DecorrelatePredicateSubquery`]: crate::decorrelate_predicate_subquery::DecorrelatePredicateSubquery
+/// [`TableSource::statistics`]: datafusion_expr::TableSource::statistics
+#[derive(Default, Debug)]
+pub struct SemiJoinReduction {}
+
+impl SemiJoinReduction {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for SemiJoinReduction {
+    fn name(&self) -> &str {
+        "semi_join_reduction"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        if !config.options().optimizer.enable_semi_join_reduction {
+            return Ok(Transformed::no(plan));
+        }
+        let LogicalPlan::Join(join) = plan else {
+            return Ok(Transformed::no(plan));
+        };
+        // `IN` never matches nulls
+        if join.on.is_empty() || join.null_equals_null {
+            return Ok(Transformed::no(LogicalPlan::Join(join)));
+        }
+        // only the rows of an input without a match in the other input
+        // are removed by the join
+        let (reduce_left, reduce_right) = match join.join_type {
+            JoinType::Inner => (true, true),
+            JoinType::LeftSemi => (true, false),
+            JoinType::RightSemi => (false, true),
+            _ => (false, false),
+        };
+
+        if reduce_left {
+            let keys = join.on.iter().map(|(l, r)| (l, r)).collect::<Vec<_>>();
+            if let Some(left) = try_reduce(&join.left, &join.right, &keys, config)? {
+                return Ok(Transformed::yes(LogicalPlan::Join(Join {
+                    left: Arc::new(left),
+                    ..join
+                })));
+            }
+        }
+        if reduce_right {
+            let keys = join.on.iter().map(|(l, r)| (r, l)).collect::<Vec<_>>();
+            if let Some(right) = try_reduce(&join.right, &join.left, &keys, config)? {
+                return Ok(Transformed::yes(LogicalPlan::Join(Join {
+                    right: Arc::new(right),
+                    ..join
+                })));
+            }
+        }
+        Ok(Transformed::no(LogicalPlan::Join(join)))
+    }
+}
+
+/// Returns `input` with its scan filtered by the keys of `other`, if the
+/// statistics estimate that `other` is small enough. `keys` pairs the join
+/// keys of `input` with those of `other`.
+fn try_reduce(
+    input: &LogicalPlan,
+    other: &LogicalPlan,
+    keys: &[(&Expr, &Expr)],
+    config: &dyn OptimizerConfig,
+) -> Result<Option<LogicalPlan>> {
+    let options = &config.options().optimizer;
+    if !is_filtered(other) {
+        return Ok(None);
+    }
+    let (Some(input_rows), Some(other_rows)) = (num_rows(input), num_rows(other)) else {
+        return Ok(None);
+    };
+    let selectivity = options.default_filter_selectivity as f64 / 100.0;
+    if other_rows as f64 * selectivity
+        > options.semi_join_reduction_max_ratio * input_rows as f64
+    {
+        return Ok(None);
+    }
+
+    for (input_key, other_key) in keys {
+        let Expr::Column(column) = input_key else {
+            continue;
+        };
+        let Some((scan, scan_column)) = scan_column(input, column) else {
+            continue;
+        };
+        if has_reduction(scan, &scan_column) {
+            continue;
+        }
+
+        // the subquery is not visited by the other rules, only scan the key
+        let subquery = LogicalPlanBuilder::from(other.clone())
+            .project(vec![(*other_key).clone()])?
+            .build()?;
+        let subquery = OptimizeProjections::new().rewrite(subquery, config)?.data;
+        let filter = in_subquery(Expr::Column(scan_column), Arc::new(subquery));
+        let pushdown = scan.source.supports_filters_pushdown(&[&filter])?;
+        if pushdown != [TableProviderFilterPushDown::Inexact] {
+            continue;
+        }
+        let reduced = add_scan_filter(input.clone(), filter)?;
+        return Ok(Some(reduced));
+    }
+    Ok(None)
+}
+
+/// Whether the rows of `plan`, a chain of nodes over a table scan, are
+/// filtered
+fn is_filtered(plan: &LogicalPlan) -> bool {
+    match plan {
+        LogicalPlan::TableScan(scan) => !scan.filters.is_empty(),
+        LogicalPlan::Filter(_) => true,
+        LogicalPlan::Projection(_) | LogicalPlan::SubqueryAlias(_) => {
+            is_filtered(plan.inputs()[0])
+        }
+        _ => false,
+    }
+}
+
+/// The table scan of `plan` and its column `column` of `plan` refers to, if
+/// `plan` is a chain of filters, projections and aliases over this scan
+fn scan_column<'a>(
+    plan: &'a LogicalPlan,
+    column: &Column,
+) -> Option<(&'a TableScan, Column)> {
+    match plan {
+        LogicalPlan::TableScan(scan) => {
+            let index = scan.projected_schema.index_of_column(column).ok()?;
+            let column = Column::from(scan.projected_schema.qualified_field(index));
+            Some((scan, column))
+        }
+        LogicalPlan::Filter(filter) => scan_column(&filter.input, column),
+        LogicalPlan::SubqueryAlias(alias) => {
+            let index = alias.schema.index_of_column(column).ok()?;
+            let input_column = Column::from(alias.input.schema().qualified_field(index));
+            scan_column(&alias.input, &input_column)
+        }
+        LogicalPlan::Projection(projection) => {
+            let index = projection.schema.index_of_column(column).ok()?;
+            match &projection.expr[index] {
+                Expr::Column(input_column) => {
+                    scan_column(&projection.input, input_column)
+                }
+                Expr::Alias(Alias { expr, .. }) => match expr.as_ref() {
+                    Expr::Column(input_column) => {
+                        scan_column(&projection.input, input_column)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Whether `scan` is already filtered by a semi-join reduction on `column`
+fn has_reduction(scan: &TableScan, column: &Column) -> bool {
+    scan.filters.iter().any(|filter| {
+        matches!(
+            filter,
+            Expr::InSubquery(InSubquery { expr, negated: false, .. })
+                if matches!(expr.as_ref(), Expr::Column(c) if c == column)
+        )
+    })
+}
+
+/// Adds `filter` to the filters of the table scan of `plan`
+fn add_scan_filter(plan: LogicalPlan, filter: Expr) -> Result<LogicalPlan> {
+    let mut filter = Some(filter);
+    plan.transform_down(|plan| match plan {
+        LogicalPlan::TableScan(mut scan) => {
+            scan.filters.extend(filter.take());
+            Ok(Transformed::new(
+                LogicalPlan::TableScan(scan),
+                true,
+                TreeNodeRecursion::Stop,
+            ))
+        }
+        _ => Ok(Transformed::no(plan)),
+    })
+    .map(|transformed| transformed.data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimizer::Optimizer;
+
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use chrono::{DateTime, Utc};
+    use datafusion_common::alias::AliasGenerator;
+    use datafusion_common::config::ConfigOptions;
+    use datafusion_common::stats::Precision;
+    use datafusion_common::{ColumnStatistics, Statistics};
+    use datafusion_expr::{col, lit, TableSource, TableType};
+
+    /// A table source with statistics
+    struct StatisticsSource {
+        schema: SchemaRef,
+        num_rows: Option<usize>,
+        pushdown: TableProviderFilterPushDown,
+    }
+
+    impl TableSource for StatisticsSource {
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            Arc::clone(&self.schema)
+        }
+
+        fn table_type(&self) -> TableType {
+            TableType::Base
+        }
+
+        fn supports_filters_pushdown(
+            &self,
+            filters: &[&Expr],
+        ) -> Result<Vec<TableProviderFilterPushDown>> {
+            Ok(vec![self.pushdown.clone(); filters.len()])
+        }
+
+        fn statistics(&self) -> Option<Statistics> {
+            self.num_rows.map(|num_rows| Statistics {
+                num_rows: Precision::Exact(num_rows),
+                total_byte_size: Precision::Absent,
+                column_statistics: vec![
+                    ColumnStatistics::new_unknown();
+                    self.schema.fields().len()
+                ],
+            })
+        }
+    }
+
+    /// Scans a table with `Int32` columns `k` and `v`
+    fn scan(
+        name: &str,
+        num_rows: Option<usize>,
+        pushdown: TableProviderFilterPushDown,
+    ) -> Result<LogicalPlanBuilder> {
+        let schema = Schema::new(vec![
+            Field::new("k", DataType::Int32, true),
+            Field::new("v", DataType::Int32, true),
+        ]);
+        let source = StatisticsSource {
+            schema: Arc::new(schema),
+            num_rows,
+            pushdown,
+        };
+        LogicalPlanBuilder::scan(name, Arc::new(source), None)
+    }
+
+    fn fact() -> Result<LogicalPlanBuilder> {
+        scan("fact", Some(1000), TableProviderFilterPushDown::Inexact)
+    }
+
+    fn filtered_dim(num_rows: usize) -> Result<LogicalPlan> {
+        scan("dim", Some(num_rows), TableProviderFilterPushDown::Inexact)?
+            .filter(col("dim.v").eq(lit(1)))?
+            .build()
+    }
+
+    /// An optimizer config with semi-join reduction enabled
+    struct TestConfig {
+        options: ConfigOptions,
+        alias_generator: Arc<AliasGenerator>,
+    }
+
+    impl OptimizerConfig for TestConfig {
+        fn query_execution_start_time(&self) -> DateTime<Utc> {
+            Utc::now()
+        }
+
+        fn alias_generator(&self) -> &Arc<AliasGenerator> {
+            &self.alias_generator
+        }
+
+        fn options(&self) -> &ConfigOptions {
+            &self.options
+        }
+    }
+
+    fn optimize(plan: LogicalPlan) -> Result<String> {
+        let mut options = ConfigOptions::default();
+        options.optimizer.enable_semi_join_reduction = true;
+        let config = TestConfig {
+            options,
+            alias_generator: Arc::new(AliasGenerator::new()),
+        };
+        let rule = Arc::new(SemiJoinReduction::new());
+        let optimized =
+            Optimizer::with_rules(vec![rule]).optimize(plan, &config, |_, _| {})?;
+        Ok(optimized.to_string())
+    }
+
+    #[test]
+    fn reduce_inner_join() -> Result<()> {
+        let plan = fact()?
+            .join(
+                filtered_dim(100)?,
+                JoinType::Inner,
+                (vec!["k"], vec!["k"]),
+                None,
+            )?
+            .build()?;
+
+        let expected = "Inner Join: fact.k = dim.k\
+            \n  TableScan: fact, partial_filters=[fact.k IN (<subquery>)]\
+            \n    Subquery:\
+            \n      Projection: dim.k\
+            \n        Filter: dim.v = Int32(1)\
+            \n          TableScan: dim projection=[k, v]\
+            \n  Filter: dim.v = Int32(1)\
+            \n    TableScan: dim";
+        assert_eq!(optimize(plan)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn reduce_through_alias_and_projection() -> Result<()> {
+        let fact = fact()?
+            .project(vec![col("k").alias("key"), col("v")])?
+            .alias("f")?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(filtered_dim(100)?)
+            .join(fact, JoinType::RightSemi, (vec!["k"], vec!["key"]), None)?
+            .build()?;
+
+        let expected = "RightSemi Join: dim.k = f.key\
+            \n  Filter: dim.v = Int32(1)\
+            \n    TableScan: dim\
+            \n  SubqueryAlias: f\
+            \n    Projection: fact.k AS key, fact.v\
+            \n      TableScan: fact, partial_filters=[fact.k IN (<subquery>)]\
+            \n        Subquery:\
+            \n          Projection: dim.k\
+            \n            Filter: dim.v = Int32(1)\
+            \n              TableScan: dim projection=[k, v]";
+        assert_eq!(optimize(plan)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn not_reduced() -> Result<()> {
+        let join = |fact: LogicalPlanBuilder, dim: LogicalPlan, join_type| {
+            fact.join(dim, join_type, (vec!["k"], vec!["k"]), None)?
+                .build()
+        };
+        let unfiltered_dim =
+            scan("dim", Some(100), TableProviderFilterPushDown::Inexact)?.build()?;
+        let exact_fact = scan("fact", Some(1000), TableProviderFilterPushDown::Exact)?;
+        let fact_without_statistics =
+            scan("fact", None, TableProviderFilterPushDown::Inexact)?;
+
+        for plan in [
+            // the rows of the fact table without a match are kept
+            join(fact()?, filtered_dim(100)?, JoinType::Left)?,
+            // the dimension table is not filtered
+            join(fact()?, unfiltered_dim, JoinType::Inner)?,
+            // the filtered dimension table is estimated to be too large
+            join(fact()?, filtered_dim(600)?, JoinType::Inner)?,
+            // the subquery would have to be evaluated exactly
+            join(exact_fact, filtered_dim(100)?, JoinType::Inner)?,
+            join(fact_without_statistics, filtered_dim(100)?, JoinType::Inner)?,
+        ] {
+            let expected = plan.to_string();
+            assert_eq!(optimize(plan)?, expected);
+        }
+        Ok(())
+    }
+}
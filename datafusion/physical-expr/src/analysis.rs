@@ -26,7 +26,7 @@ use crate::utils::collect_columns;
 use crate::PhysicalExpr;
 
 use arrow::datatypes::Schema;
-use datafusion_common::stats::Precision;
+use datafusion_common::stats::{Histogram, MostCommonValues, Precision};
 use datafusion_common::{
     internal_datafusion_err, internal_err, ColumnStatistics, Result, ScalarValue,
 };
@@ -85,6 +85,11 @@ pub struct ExprBoundaries {
     pub interval: Interval,
     /// Maximum number of distinct values this expression can produce, if known.
     pub distinct_count: Precision<usize>,
+    /// Histogram of the non null values of this expression, except for its
+    /// most common values, if known.
+    pub histogram: Precision<Histogram>,
+    /// Most common non null values of this expression, if known.
+    pub most_common_values: Precision<MostCommonValues>,
 }
 
 impl ExprBoundaries {
@@ -120,6 +125,8 @@ impl ExprBoundaries {
             column,
             interval,
             distinct_count: col_stats.distinct_count,
+            histogram: col_stats.histogram.clone(),
+            most_common_values: col_stats.most_common_values.clone(),
         })
     }
 
@@ -135,6 +142,8 @@ impl ExprBoundaries {
                     column: Column::new(field.name(), i),
                     interval: Interval::make_unbounded(field.data_type())?,
                     distinct_count: Precision::Absent,
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 })
             })
             .collect()
@@ -146,7 +155,9 @@ impl ExprBoundaries {
 /// The function accepts boundaries of the input columns in the `context` parameter.
 /// It then tries to tighten these boundaries based on the provided `expr`.
 /// The resulting selectivity value is calculated by comparing the initial and final boundaries.
-/// The computation assumes that the data within the column is uniformly distributed and not sorted.
+/// The computation uses the histograms and most common values of the columns when they are
+/// known, and otherwise assumes that the data within the column is uniformly distributed and
+/// not sorted.
 ///
 /// # Arguments
 ///
@@ -243,6 +254,39 @@ fn calculate_selectivity(
         .iter()
         .zip(target_boundaries.iter())
         .fold(1.0, |acc, (initial, target)| {
-            acc * cardinality_ratio(&initial.interval, &target.interval)
+            let ratio =
+                distribution_ratio(initial, &target.interval).unwrap_or_else(|| {
+                    cardinality_ratio(&initial.interval, &target.interval)
+                });
+            acc * ratio
         })
 }
+
+/// Estimates the ratio of the non null values of a column within the pruned
+/// interval `target` from its histogram and most common values, or returns
+/// `None` if its histogram is not known.
+fn distribution_ratio(initial: &ExprBoundaries, target: &Interval) -> Option<f64> {
+    let histogram = initial.histogram.get_value()?;
+    let most_common_values = initial.most_common_values.get_value();
+    let total = histogram.count() + most_common_values.map_or(0, |values| values.count());
+    if total == 0 || initial.interval == *target {
+        return Some(1.0);
+    }
+
+    let (lower, upper) = (target.lower(), target.upper());
+    let most_common_count = if lower == upper {
+        most_common_values.and_then(|values| values.get(lower))
+    } else {
+        None
+    };
+    let count = match most_common_count {
+        // The most common values are not in the histogram
+        Some(count) => count as f64,
+        None => {
+            histogram.estimate_range_count(lower, upper)
+                + most_common_values.map_or(0, |values| values.range_count(lower, upper))
+                    as f64
+        }
+    };
+    Some((count / total as f64).clamp(0.0, 1.0))
+}
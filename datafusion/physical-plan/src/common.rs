@@ -26,12 +26,14 @@ use super::SendableRecordBatchStream;
 use crate::stream::RecordBatchReceiverStream;
 use crate::{ColumnStatistics, Statistics};
 
-use arrow::datatypes::Schema;
+use arrow::array::{make_comparator, ArrayRef};
+use arrow::compute::{concat, sort_to_indices, take, SortOptions};
+use arrow::datatypes::{DataType, Schema};
 use arrow::ipc::writer::{FileWriter, IpcWriteOptions};
 use arrow::record_batch::RecordBatch;
 use arrow_array::Array;
-use datafusion_common::stats::Precision;
-use datafusion_common::{plan_err, DataFusionError, Result};
+use datafusion_common::stats::{Histogram, HistogramBucket, MostCommonValues, Precision};
+use datafusion_common::{plan_err, DataFusionError, Result, ScalarValue};
 use datafusion_execution::memory_pool::MemoryReservation;
 
 use futures::{StreamExt, TryStreamExt};
@@ -180,6 +182,135 @@ pub fn compute_record_batch_statistics(
     }
 }
 
+/// Computes the statistics of the values of a column, split in `arrays`,
+/// including an equi-depth [`Histogram`] of at most `histogram_buckets`
+/// buckets and at most `most_common_values` [`MostCommonValues`].
+///
+/// Unlike [`compute_record_batch_statistics`], this sorts the values of the
+/// column, and is meant to be used to analyze tables. Only the null count is
+/// computed for the columns whose values cannot be sorted.
+pub fn compute_column_statistics(
+    arrays: &[ArrayRef],
+    histogram_buckets: usize,
+    most_common_values: usize,
+) -> Result<ColumnStatistics> {
+    let mut statistics = ColumnStatistics::new_unknown();
+    if arrays.is_empty() {
+        statistics.null_count = Precision::Exact(0);
+        statistics.distinct_count = Precision::Exact(0);
+        return Ok(statistics);
+    }
+
+    let arrays = arrays
+        .iter()
+        .map(|array| array.as_ref())
+        .collect::<Vec<_>>();
+    let array = concat(&arrays)?;
+    let null_count = array
+        .logical_nulls()
+        .map(|nulls| nulls.null_count())
+        .unwrap_or_default();
+    statistics.null_count = Precision::Exact(null_count);
+    if !is_sortable(array.data_type()) {
+        return Ok(statistics);
+    }
+
+    let options = SortOptions {
+        descending: false,
+        nulls_first: false,
+    };
+    let indices = sort_to_indices(&array, Some(options), None)?;
+    let sorted = take(&array, &indices, None)?;
+    let values = sorted.slice(0, sorted.len() - null_count);
+
+    // The runs of equal values of the sorted values, as their first index
+    // and their length
+    let comparator = make_comparator(&values, &values, options)?;
+    let mut runs: Vec<(usize, usize)> = vec![];
+    for index in 0..values.len() {
+        match runs.last_mut() {
+            Some((start, len)) if comparator(*start, index).is_eq() => *len += 1,
+            _ => runs.push((index, 1)),
+        }
+    }
+    statistics.distinct_count = Precision::Exact(runs.len());
+    if runs.is_empty() {
+        return Ok(statistics);
+    }
+    statistics.min_value = Precision::Exact(ScalarValue::try_from_array(&values, 0)?);
+    statistics.max_value =
+        Precision::Exact(ScalarValue::try_from_array(&values, values.len() - 1)?);
+
+    // The most common values are the values repeated more than average, which
+    // are left out of the histogram so that they don't skew its buckets
+    let average = values.len() as f64 / runs.len() as f64;
+    let mut common = runs
+        .iter()
+        .filter(|(_, len)| *len > 1 && *len as f64 > average)
+        .copied()
+        .collect::<Vec<_>>();
+    common.sort_by(|(_, a), (_, b)| b.cmp(a));
+    common.truncate(most_common_values);
+    if !common.is_empty() {
+        let common_values = common
+            .iter()
+            .map(|(start, len)| Ok((ScalarValue::try_from_array(&values, *start)?, *len)))
+            .collect::<Result<Vec<_>>>()?;
+        statistics.most_common_values =
+            Precision::Exact(MostCommonValues::new(common_values));
+        runs.retain(|run| !common.contains(run));
+    }
+
+    if histogram_buckets > 0 && !runs.is_empty() {
+        let count = runs.iter().map(|(_, len)| len).sum::<usize>();
+        let depth = count.div_ceil(histogram_buckets).max(1);
+        let mut buckets = vec![];
+        // The first and last runs of the current bucket, with its number of
+        // values and of distinct values. Runs are never split across buckets.
+        let mut current: Option<(usize, usize, usize, usize)> = None;
+        for (start, len) in runs {
+            let bucket = current.get_or_insert((start, start, 0, 0));
+            bucket.1 = start;
+            bucket.2 += len;
+            bucket.3 += 1;
+            if bucket.2 >= depth {
+                buckets.push(current.take().unwrap());
+            }
+        }
+        buckets.extend(current);
+        let buckets = buckets
+            .into_iter()
+            .map(|(first, last, count, distinct_count)| {
+                Ok(HistogramBucket {
+                    lower: ScalarValue::try_from_array(&values, first)?,
+                    upper: ScalarValue::try_from_array(&values, last)?,
+                    count,
+                    distinct_count,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        statistics.histogram = Precision::Exact(Histogram::try_new(buckets)?);
+    }
+
+    Ok(statistics)
+}
+
+/// Whether the values of `data_type` can be sorted to compute their
+/// distribution
+pub fn is_sortable(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Interval(_) => false,
+        DataType::Boolean
+        | DataType::Utf8
+        | DataType::LargeUtf8
+        | DataType::Utf8View
+        | DataType::Binary
+        | DataType::LargeBinary
+        | DataType::BinaryView => true,
+        data_type => data_type.is_primitive(),
+    }
+}
+
 /// Write in Arrow IPC format.
 pub struct IPCWriter {
     /// Path
@@ -282,8 +413,8 @@ mod tests {
     use super::*;
 
     use arrow::{
-        array::{Float32Array, Float64Array, UInt64Array},
-        datatypes::{DataType, Field},
+        array::{Float32Array, Float64Array, Int32Array, StringArray, UInt64Array},
+        datatypes::Field,
     };
 
     #[test]
@@ -334,12 +465,16 @@ mod tests {
                     max_value: Precision::Absent,
                     min_value: Precision::Absent,
                     null_count: Precision::Exact(0),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Absent,
                     min_value: Precision::Absent,
                     null_count: Precision::Exact(0),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
             ],
         };
@@ -372,10 +507,98 @@ mod tests {
                 max_value: Precision::Absent,
                 min_value: Precision::Absent,
                 null_count: Precision::Exact(3),
+                histogram: Precision::Absent,
+                most_common_values: Precision::Absent,
             }],
         };
 
         assert_eq!(actual, expected);
         Ok(())
     }
+
+    #[test]
+    fn test_compute_column_statistics() -> Result<()> {
+        let arrays: Vec<ArrayRef> = vec![
+            Arc::new(Int32Array::from(vec![
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(2),
+                None,
+                Some(3),
+                Some(4),
+            ])),
+            Arc::new(Int32Array::from(vec![
+                Some(5),
+                Some(6),
+                Some(2),
+                Some(7),
+                Some(8),
+                None,
+            ])),
+        ];
+        let actual = compute_column_statistics(&arrays, 3, 2)?;
+
+        let bucket = |lower, upper, count, distinct_count| HistogramBucket {
+            lower: ScalarValue::Int32(Some(lower)),
+            upper: ScalarValue::Int32(Some(upper)),
+            count,
+            distinct_count,
+        };
+        let expected = ColumnStatistics {
+            null_count: Precision::Exact(2),
+            max_value: Precision::Exact(ScalarValue::Int32(Some(8))),
+            min_value: Precision::Exact(ScalarValue::Int32(Some(1))),
+            distinct_count: Precision::Exact(8),
+            histogram: Precision::Exact(Histogram::try_new(vec![
+                bucket(1, 4, 3, 3),
+                bucket(5, 7, 3, 3),
+                bucket(8, 8, 1, 1),
+            ])?),
+            most_common_values: Precision::Exact(MostCommonValues::new(vec![(
+                ScalarValue::Int32(Some(2)),
+                4,
+            )])),
+        };
+
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_compute_column_statistics_strings() -> Result<()> {
+        let arrays: Vec<ArrayRef> = vec![Arc::new(StringArray::from(vec![
+            "b", "a", "c", "a", "b", "a", "d",
+        ]))];
+        let actual = compute_column_statistics(&arrays, 2, 0)?;
+
+        let bucket = |lower: &str, upper: &str, count, distinct_count| HistogramBucket {
+            lower: ScalarValue::from(lower),
+            upper: ScalarValue::from(upper),
+            count,
+            distinct_count,
+        };
+        assert_eq!(actual.distinct_count, Precision::Exact(4));
+        assert_eq!(actual.most_common_values, Precision::Absent);
+        assert_eq!(
+            actual.histogram,
+            Precision::Exact(Histogram::try_new(vec![
+                bucket("a", "b", 5, 2),
+                bucket("c", "d", 2, 2),
+            ])?)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_compute_column_statistics_empty() -> Result<()> {
+        let arrays: Vec<ArrayRef> = vec![Arc::new(Int32Array::from(vec![None, None]))];
+        let actual = compute_column_statistics(&arrays, 8, 8)?;
+
+        let mut expected = ColumnStatistics::new_unknown();
+        expected.null_count = Precision::Exact(2);
+        expected.distinct_count = Precision::Exact(0);
+        assert_eq!(actual, expected);
+        Ok(())
+    }
 }
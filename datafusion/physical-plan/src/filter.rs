@@ -410,6 +410,8 @@ fn collect_new_statistics(
                     max_value,
                     min_value,
                     distinct_count: distinct_count.to_inexact(),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 }
             },
         )
@@ -558,6 +560,7 @@ mod tests {
 
     use arrow::datatypes::{Field, Schema};
     use arrow_schema::{UnionFields, UnionMode};
+    use datafusion_common::stats::{Histogram, HistogramBucket, MostCommonValues};
     use datafusion_common::ScalarValue;
 
     #[tokio::test]
@@ -650,6 +653,51 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_filter_statistics_with_histogram() -> Result<()> {
+        // Table:
+        //      a: min=1, max=100, half of the values are 1
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let bucket = |lower, upper, count, distinct_count| HistogramBucket {
+            lower: ScalarValue::Int32(Some(lower)),
+            upper: ScalarValue::Int32(Some(upper)),
+            count,
+            distinct_count,
+        };
+        let input = Arc::new(StatisticsExec::new(
+            Statistics {
+                num_rows: Precision::Inexact(100),
+                total_byte_size: Precision::Absent,
+                column_statistics: vec![ColumnStatistics {
+                    min_value: Precision::Inexact(ScalarValue::Int32(Some(1))),
+                    max_value: Precision::Inexact(ScalarValue::Int32(Some(100))),
+                    histogram: Precision::Inexact(Histogram::try_new(vec![
+                        bucket(2, 10, 40, 9),
+                        bucket(11, 100, 10, 10),
+                    ])?),
+                    most_common_values: Precision::Inexact(MostCommonValues::new(vec![
+                        (ScalarValue::Int32(Some(1)), 50),
+                    ])),
+                    ..Default::default()
+                }],
+            },
+            schema.clone(),
+        ));
+
+        let num_rows = |op, value: i32| -> Result<Precision<usize>> {
+            let predicate = binary(col("a", &schema)?, op, lit(value), &schema)?;
+            let filter = FilterExec::try_new(predicate, Arc::clone(&input) as _)?;
+            Ok(filter.statistics()?.num_rows)
+        };
+        // The uniform distribution of the values would select 10 rows
+        assert_eq!(num_rows(Operator::LtEq, 10)?, Precision::Inexact(90));
+        assert_eq!(num_rows(Operator::Gt, 10)?, Precision::Inexact(10));
+        assert_eq!(num_rows(Operator::Eq, 1)?, Precision::Inexact(50));
+        assert_eq!(num_rows(Operator::Eq, 50)?, Precision::Inexact(1));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_filter_statistics_column_level_nested() -> Result<()> {
         // Table:
@@ -1128,6 +1176,8 @@ mod tests {
                 min_value: Precision::Inexact(ScalarValue::Int32(Some(5))),
                 max_value: Precision::Inexact(ScalarValue::Int32(Some(10))),
                 distinct_count: Precision::Absent,
+                histogram: Precision::Absent,
+                most_common_values: Precision::Absent,
             }],
         };
 
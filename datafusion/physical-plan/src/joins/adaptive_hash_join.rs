@@ -0,0 +1,850 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`AdaptiveHashJoinExec`] Hash Join Operator choosing its partitioning mode
+//! at runtime
+
+use std::any::Any;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use super::utils::OnceAsync;
+use super::{HashJoinExec, PartitionMode};
+use crate::memory::MemoryExec;
+use crate::metrics::{Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet};
+use crate::repartition::RepartitionExec;
+use crate::stream::RecordBatchStreamAdapter;
+use crate::{
+    DisplayAs, DisplayFormatType, ExecutionMode, ExecutionPlan, ExecutionPlanProperties,
+    Partitioning, PlanProperties, SendableRecordBatchStream, Statistics,
+};
+
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{internal_err, DataFusionError, Result};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::TaskContext;
+use datafusion_physical_expr::PhysicalExprRef;
+
+use futures::future::try_join_all;
+use futures::stream::BoxStream;
+use futures::{stream, StreamExt, TryFutureExt};
+use parking_lot::Mutex;
+
+/// The join strategy chosen by an [`AdaptiveHashJoinExec`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdaptiveJoinStrategy {
+    /// The left side is collected into a single hash table
+    CollectLeft,
+    /// The inputs are swapped, and the right side is collected into a single
+    /// hash table
+    CollectRight,
+    /// Both sides are hash partitioned on the join keys
+    Partitioned,
+}
+
+impl fmt::Display for AdaptiveJoinStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AdaptiveJoinStrategy::CollectLeft => write!(f, "CollectLeft"),
+            AdaptiveJoinStrategy::CollectRight => write!(f, "CollectRight"),
+            AdaptiveJoinStrategy::Partitioned => write!(f, "Partitioned"),
+        }
+    }
+}
+
+/// The decision taken by an [`AdaptiveHashJoinExec`] once its inputs have
+/// been observed
+#[derive(Debug)]
+struct AdaptiveDecision {
+    /// The chosen strategy
+    strategy: AdaptiveJoinStrategy,
+    /// The hash join executing the strategy
+    join: Arc<dyn ExecutionPlan>,
+}
+
+/// Hash join choosing between a [`PartitionMode::CollectLeft`] and a
+/// [`PartitionMode::Partitioned`] join at runtime, instead of relying on the
+/// estimated statistics of its inputs.
+///
+/// When first executed, the join reads its left (build) side up to the
+/// configured size and row thresholds:
+///
+/// * If the left side fits in the thresholds, it is collected into a single
+///   hash table probed by every partition of the right side
+///   ([`AdaptiveJoinStrategy::CollectLeft`]).
+///
+/// * Otherwise the right side is read up to the same thresholds. If it fits,
+///   the inputs are swapped and the right side is collected
+///   ([`AdaptiveJoinStrategy::CollectRight`]).
+///
+/// * Otherwise both sides are hash repartitioned on the join keys
+///   ([`AdaptiveJoinStrategy::Partitioned`]). The batches already read are
+///   not read again.
+///
+/// The partitions of an input are read concurrently, and buffered in memory
+/// up to the thresholds only, which should be small enough to build a hash
+/// table from. The buffered batches are accounted for in the memory pool. The chosen strategy is shown by
+/// `EXPLAIN ANALYZE`.
+#[derive(Debug)]
+pub struct AdaptiveHashJoinExec {
+    /// The join being executed, in [`PartitionMode::Auto`]
+    join: Arc<HashJoinExec>,
+    /// Maximum size in bytes of the collected side
+    threshold_byte_size: usize,
+    /// Maximum number of rows of the collected side
+    threshold_num_rows: usize,
+    /// The number of output partitions
+    partitions: usize,
+    /// Future planning the join once the inputs have been observed
+    planned: OnceAsync<Arc<dyn ExecutionPlan>>,
+    /// The decision taken once the inputs have been observed
+    decision: Arc<OnceLock<AdaptiveDecision>>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl AdaptiveHashJoinExec {
+    /// Create a new [`AdaptiveHashJoinExec`] executing `join` with
+    /// `partitions` output partitions. A side of the join is collected into a
+    /// single hash table if it has at most `threshold_byte_size` bytes and
+    /// `threshold_num_rows` rows.
+    pub fn try_new(
+        join: &HashJoinExec,
+        threshold_byte_size: usize,
+        threshold_num_rows: usize,
+        partitions: usize,
+    ) -> Result<Self> {
+        let join = HashJoinExec::try_new(
+            Arc::clone(join.left()),
+            Arc::clone(join.right()),
+            join.on().to_vec(),
+            join.filter().cloned(),
+            join.join_type(),
+            join.projection.clone(),
+            PartitionMode::Auto,
+            join.null_equals_null(),
+        )?;
+        let cache = Self::compute_properties(&join, partitions);
+        Ok(Self {
+            join: Arc::new(join),
+            threshold_byte_size,
+            threshold_num_rows,
+            partitions,
+            planned: Default::default(),
+            decision: Arc::new(OnceLock::new()),
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        })
+    }
+
+    /// The join being executed
+    pub fn join(&self) -> &HashJoinExec {
+        &self.join
+    }
+
+    /// The strategy chosen by the join, if it has been executed
+    pub fn strategy(&self) -> Option<AdaptiveJoinStrategy> {
+        self.decision.get().map(|decision| decision.strategy)
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(join: &HashJoinExec, partitions: usize) -> PlanProperties {
+        // The inputs may be repartitioned, which does not maintain their order
+        let mut eq_properties = join.properties().equivalence_properties().clone();
+        eq_properties.clear_orderings();
+        PlanProperties::new(
+            eq_properties,
+            Partitioning::UnknownPartitioning(partitions),
+            join.properties().execution_mode(),
+        )
+    }
+}
+
+impl DisplayAs for AdaptiveHashJoinExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let on = self
+                    .join
+                    .on()
+                    .iter()
+                    .map(|(c1, c2)| format!("({}, {})", c1, c2))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "AdaptiveHashJoinExec: join_type={:?}, on=[{}]",
+                    self.join.join_type(),
+                    on
+                )?;
+                if let Some(filter) = self.join.filter() {
+                    write!(f, ", filter={}", filter.expression())?;
+                }
+                if let Some(projection) = &self.join.projection {
+                    let schema = self.join.join_schema();
+                    let columns = projection
+                        .iter()
+                        .map(|index| format!("{}@{}", schema.field(*index).name(), index))
+                        .collect::<Vec<_>>();
+                    write!(f, ", projection=[{}]", columns.join(", "))?;
+                }
+                if let Some(strategy) = self.strategy() {
+                    write!(f, ", strategy={strategy}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for AdaptiveHashJoinExec {
+    fn name(&self) -> &'static str {
+        "AdaptiveHashJoinExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![self.join.left(), self.join.right()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let join = HashJoinExec::try_new(
+            Arc::clone(&children[0]),
+            Arc::clone(&children[1]),
+            self.join.on().to_vec(),
+            self.join.filter().cloned(),
+            self.join.join_type(),
+            self.join.projection.clone(),
+            PartitionMode::Auto,
+            self.join.null_equals_null(),
+        )?;
+        Ok(Arc::new(Self::try_new(
+            &join,
+            self.threshold_byte_size,
+            self.threshold_num_rows,
+            self.partitions,
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition >= self.partitions {
+            return internal_err!(
+                "Invalid partition {partition} for AdaptiveHashJoinExec with {} partitions",
+                self.partitions
+            );
+        }
+
+        let mut planned = self.planned.once(|| {
+            let planner = AdaptiveJoinPlanner {
+                join: Arc::clone(&self.join),
+                threshold_byte_size: self.threshold_byte_size,
+                threshold_num_rows: self.threshold_num_rows,
+                partitions: self.partitions,
+                decision: Arc::clone(&self.decision),
+                left_buffered_rows: MetricBuilder::new(&self.metrics)
+                    .global_counter("left_buffered_rows"),
+                right_buffered_rows: MetricBuilder::new(&self.metrics)
+                    .global_counter("right_buffered_rows"),
+            };
+            planner.plan(Arc::clone(&context))
+        });
+        let stream = futures::future::poll_fn(move |cx| planned.get_shared(cx))
+            .map_ok(move |plan| plan.execute(partition, context))
+            .and_then(futures::future::ready)
+            .try_flatten_stream();
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        let mut metrics = self.metrics.clone_inner();
+        if let Some(decision) = self.decision.get() {
+            for metric in decision.join.metrics().unwrap_or_default().iter() {
+                metrics.push(Arc::clone(metric));
+            }
+        }
+        Some(metrics)
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        self.join.statistics()
+    }
+}
+
+/// Plans the join of an [`AdaptiveHashJoinExec`] from its observed inputs
+struct AdaptiveJoinPlanner {
+    join: Arc<HashJoinExec>,
+    threshold_byte_size: usize,
+    threshold_num_rows: usize,
+    partitions: usize,
+    decision: Arc<OnceLock<AdaptiveDecision>>,
+    left_buffered_rows: Count,
+    right_buffered_rows: Count,
+}
+
+impl AdaptiveJoinPlanner {
+    async fn plan(self, context: Arc<TaskContext>) -> Result<Arc<dyn ExecutionPlan>> {
+        let join = self.join;
+        let left = BufferedInput::read(
+            Arc::clone(join.left()),
+            &context,
+            self.threshold_byte_size,
+            self.threshold_num_rows,
+        )
+        .await?;
+        self.left_buffered_rows.add(left.num_rows);
+
+        let (strategy, hash_join, plan) = if left.complete {
+            let hash_join = Self::hash_join(
+                &join,
+                left.into_memory_exec()?,
+                round_robin(Arc::clone(join.right()), self.partitions)?,
+                PartitionMode::CollectLeft,
+            )?;
+            let plan = Arc::clone(&hash_join);
+            (AdaptiveJoinStrategy::CollectLeft, hash_join, plan)
+        } else {
+            let right = BufferedInput::read(
+                Arc::clone(join.right()),
+                &context,
+                self.threshold_byte_size,
+                self.threshold_num_rows,
+            )
+            .await?;
+            self.right_buffered_rows.add(right.num_rows);
+
+            if right.complete {
+                let swapped = HashJoinExec::try_new(
+                    round_robin(left.into_exec(), self.partitions)?,
+                    right.into_memory_exec()?,
+                    join.on().to_vec(),
+                    join.filter().cloned(),
+                    join.join_type(),
+                    join.projection.clone(),
+                    PartitionMode::Auto,
+                    join.null_equals_null(),
+                )?
+                .swap_inputs(PartitionMode::CollectLeft)?;
+                let hash_join = find_hash_join(&swapped)?;
+                (AdaptiveJoinStrategy::CollectRight, hash_join, swapped)
+            } else {
+                let (left_keys, right_keys) = join
+                    .on()
+                    .iter()
+                    .map(|(l, r)| (Arc::clone(l), Arc::clone(r)))
+                    .unzip();
+                let hash_join = Self::hash_join(
+                    &join,
+                    hash_repartition(left.into_exec(), left_keys, self.partitions)?,
+                    hash_repartition(right.into_exec(), right_keys, self.partitions)?,
+                    PartitionMode::Partitioned,
+                )?;
+                let plan = Arc::clone(&hash_join);
+                (AdaptiveJoinStrategy::Partitioned, hash_join, plan)
+            }
+        };
+
+        let _ = self.decision.set(AdaptiveDecision {
+            strategy,
+            join: hash_join,
+        });
+        Ok(plan)
+    }
+
+    /// Creates the hash join of `join` with new inputs
+    fn hash_join(
+        join: &HashJoinExec,
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        mode: PartitionMode,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(HashJoinExec::try_new(
+            left,
+            right,
+            join.on().to_vec(),
+            join.filter().cloned(),
+            join.join_type(),
+            join.projection.clone(),
+            mode,
+            join.null_equals_null(),
+        )?))
+    }
+}
+
+/// Returns the hash join at the root of `plan`, below the projection added
+/// when swapping its inputs
+fn find_hash_join(plan: &Arc<dyn ExecutionPlan>) -> Result<Arc<dyn ExecutionPlan>> {
+    if plan.as_any().is::<HashJoinExec>() {
+        return Ok(Arc::clone(plan));
+    }
+    match plan.children().as_slice() {
+        [child] if child.as_any().is::<HashJoinExec>() => Ok(Arc::clone(child)),
+        _ => internal_err!("Expected a HashJoinExec, got {}", plan.name()),
+    }
+}
+
+/// Repartitions `input` into `partitions` partitions, if needed
+fn round_robin(
+    input: Arc<dyn ExecutionPlan>,
+    partitions: usize,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    if input.output_partitioning().partition_count() == partitions {
+        return Ok(input);
+    }
+    Ok(Arc::new(RepartitionExec::try_new(
+        input,
+        Partitioning::RoundRobinBatch(partitions),
+    )?))
+}
+
+/// Hash repartitions `input` on `keys` into `partitions` partitions
+fn hash_repartition(
+    input: Arc<dyn ExecutionPlan>,
+    keys: Vec<PhysicalExprRef>,
+    partitions: usize,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    Ok(Arc::new(RepartitionExec::try_new(
+        input,
+        Partitioning::Hash(keys, partitions),
+    )?))
+}
+
+/// The batches of a partition read by a [`BufferedInput`], and the rest of
+/// the partition stream if it was not read entirely
+struct BufferedPartition {
+    /// The batches read
+    batches: Vec<RecordBatch>,
+    /// The rest of the partition, if it was not read entirely
+    rest: Option<SendableRecordBatchStream>,
+    /// Memory reservation for the batches read
+    reservation: MemoryReservation,
+}
+
+impl BufferedPartition {
+    /// Returns a stream of the buffered batches followed by the rest of the
+    /// partition. The memory of a batch is released once it is returned.
+    fn into_stream(self) -> BoxStream<'static, Result<RecordBatch>> {
+        let Self {
+            batches,
+            rest,
+            mut reservation,
+        } = self;
+        let batches = stream::iter(batches).map(move |batch| {
+            reservation.shrink(batch.get_array_memory_size());
+            Ok(batch)
+        });
+        match rest {
+            Some(rest) => batches.chain(rest).boxed(),
+            None => batches.boxed(),
+        }
+    }
+}
+
+/// The first batches of a join input, read to observe its size
+struct BufferedInput {
+    /// The join input
+    input: Arc<dyn ExecutionPlan>,
+    /// The partitions read
+    partitions: Vec<BufferedPartition>,
+    /// True if all the partitions have been read entirely
+    complete: bool,
+    /// The number of rows read
+    num_rows: usize,
+}
+
+impl BufferedInput {
+    /// Reads the partitions of `input` concurrently until more than
+    /// `max_bytes` bytes or `max_rows` rows have been read
+    async fn read(
+        input: Arc<dyn ExecutionPlan>,
+        context: &Arc<TaskContext>,
+        max_bytes: usize,
+        max_rows: usize,
+    ) -> Result<Self> {
+        let num_rows = &AtomicUsize::new(0);
+        let num_bytes = &AtomicUsize::new(0);
+        let exceeded = || {
+            num_rows.load(Ordering::Relaxed) > max_rows
+                || num_bytes.load(Ordering::Relaxed) > max_bytes
+        };
+
+        let partitions =
+            (0..input.output_partitioning().partition_count()).map(|partition| {
+                let stream = input.execute(partition, Arc::clone(context));
+                let reservation =
+                    MemoryConsumer::new(format!("BufferedInput[{partition}]"))
+                        .register(context.memory_pool());
+                async move {
+                    let mut stream = stream?;
+                    let mut buffered = BufferedPartition {
+                        batches: vec![],
+                        rest: None,
+                        reservation,
+                    };
+                    while !exceeded() {
+                        let Some(batch) = stream.next().await.transpose()? else {
+                            return Ok(buffered);
+                        };
+                        let size = batch.get_array_memory_size();
+                        buffered.reservation.try_grow(size)?;
+                        num_rows.fetch_add(batch.num_rows(), Ordering::Relaxed);
+                        num_bytes.fetch_add(size, Ordering::Relaxed);
+                        buffered.batches.push(batch);
+                    }
+                    buffered.rest = Some(stream);
+                    Ok::<_, DataFusionError>(buffered)
+                }
+            });
+        let partitions = try_join_all(partitions).await?;
+
+        Ok(Self {
+            input,
+            complete: !exceeded() && partitions.iter().all(|p| p.rest.is_none()),
+            partitions,
+            num_rows: num_rows.load(Ordering::Relaxed),
+        })
+    }
+
+    /// Returns a single partition plan scanning the batches of a complete input
+    fn into_memory_exec(self) -> Result<Arc<dyn ExecutionPlan>> {
+        let batches = self
+            .partitions
+            .into_iter()
+            .flat_map(|partition| partition.batches)
+            .collect();
+        Ok(Arc::new(MemoryExec::try_new(
+            &[batches],
+            self.input.schema(),
+            None,
+        )?))
+    }
+
+    /// Returns a plan reading the buffered batches followed by the rest of the
+    /// input
+    fn into_exec(self) -> Arc<dyn ExecutionPlan> {
+        if self.partitions.is_empty() {
+            return self.input;
+        }
+        Arc::new(BufferedInputExec::new(self.input, self.partitions))
+    }
+}
+
+/// Execution plan resuming the partitions of an input partially read by a
+/// [`BufferedInput`]. Executing a partition again reads the input again.
+struct BufferedInputExec {
+    /// The input
+    input: Arc<dyn ExecutionPlan>,
+    /// The partitions read so far
+    partitions: Vec<Mutex<Option<BufferedPartition>>>,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl BufferedInputExec {
+    fn new(input: Arc<dyn ExecutionPlan>, partitions: Vec<BufferedPartition>) -> Self {
+        let cache = PlanProperties::new(
+            input.equivalence_properties().clone(),
+            Partitioning::UnknownPartitioning(
+                input.output_partitioning().partition_count(),
+            ),
+            ExecutionMode::Bounded,
+        );
+        Self {
+            input,
+            partitions: partitions
+                .into_iter()
+                .map(|partition| Mutex::new(Some(partition)))
+                .collect(),
+            cache,
+        }
+    }
+}
+
+impl fmt::Debug for BufferedInputExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferedInputExec")
+            .field("input", &self.input)
+            .finish()
+    }
+}
+
+impl DisplayAs for BufferedInputExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "BufferedInputExec")
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for BufferedInputExec {
+    fn name(&self) -> &'static str {
+        "BufferedInputExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let Some(partition_buffer) = self.partitions.get(partition) else {
+            return self.input.execute(partition, context);
+        };
+        // the buffered batches are consumed by the first execution
+        let Some(buffered) = partition_buffer.lock().take() else {
+            return self.input.execute(partition, context);
+        };
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.input.schema(),
+            buffered.into_stream(),
+        )))
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        self.input.statistics()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect;
+
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::util::pretty::pretty_format_batches;
+    use datafusion_common::{assert_contains, JoinType};
+    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+    use datafusion_physical_expr::expressions::Column;
+
+    fn table(
+        names: (&str, &str),
+        rows: usize,
+        partitions: usize,
+    ) -> Arc<dyn ExecutionPlan> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(names.0, DataType::Int32, false),
+            Field::new(names.1, DataType::Int32, false),
+        ]));
+        let chunk = rows.div_ceil(partitions);
+        let partitions = (0..partitions)
+            .map(|i| {
+                let values =
+                    (i * chunk).min(rows) as i32..((i + 1) * chunk).min(rows) as i32;
+                vec![RecordBatch::try_new(
+                    Arc::clone(&schema),
+                    vec![
+                        Arc::new(Int32Array::from_iter_values(values.clone())),
+                        Arc::new(Int32Array::from_iter_values(values.map(|v| v * 10))),
+                    ],
+                )
+                .unwrap()]
+            })
+            .collect::<Vec<_>>();
+        Arc::new(MemoryExec::try_new(&partitions, schema, None).unwrap())
+    }
+
+    fn adaptive_join(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        join_type: JoinType,
+        threshold_num_rows: usize,
+    ) -> Result<AdaptiveHashJoinExec> {
+        let on = vec![(
+            Arc::new(Column::new_with_schema("a", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b", &right.schema())?) as _,
+        )];
+        let join = HashJoinExec::try_new(
+            left,
+            right,
+            on,
+            None,
+            &join_type,
+            None,
+            PartitionMode::Auto,
+            false,
+        )?;
+        AdaptiveHashJoinExec::try_new(&join, usize::MAX, threshold_num_rows, 3)
+    }
+
+    async fn run(join: Arc<AdaptiveHashJoinExec>) -> Result<usize> {
+        let batches = collect(join as _, Arc::new(TaskContext::default())).await?;
+        Ok(batches.iter().map(|batch| batch.num_rows()).sum())
+    }
+
+    #[tokio::test]
+    async fn collects_small_left_side() -> Result<()> {
+        let join = Arc::new(adaptive_join(
+            table(("a", "x"), 10, 2),
+            table(("b", "y"), 1000, 2),
+            JoinType::Inner,
+            100,
+        )?);
+        assert_eq!(run(Arc::clone(&join)).await?, 10);
+        assert_eq!(join.strategy(), Some(AdaptiveJoinStrategy::CollectLeft));
+        assert_eq!(join.properties().output_partitioning().partition_count(), 3);
+
+        let metrics = join.metrics().unwrap();
+        assert_eq!(
+            metrics
+                .sum_by_name("left_buffered_rows")
+                .unwrap()
+                .as_usize(),
+            10
+        );
+        assert_eq!(metrics.output_rows(), Some(10));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn swaps_inputs_when_right_side_is_small() -> Result<()> {
+        let join = Arc::new(adaptive_join(
+            table(("a", "x"), 1000, 2),
+            table(("b", "y"), 10, 1),
+            JoinType::Left,
+            100,
+        )?);
+        assert_eq!(run(Arc::clone(&join)).await?, 1000);
+        assert_eq!(join.strategy(), Some(AdaptiveJoinStrategy::CollectRight));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn partitions_large_inputs() -> Result<()> {
+        let join = Arc::new(adaptive_join(
+            table(("a", "x"), 1000, 2),
+            table(("b", "y"), 500, 2),
+            JoinType::Inner,
+            100,
+        )?);
+        assert_eq!(run(Arc::clone(&join)).await?, 500);
+        assert_eq!(join.strategy(), Some(AdaptiveJoinStrategy::Partitioned));
+
+        let metrics = join.metrics().unwrap();
+        assert_eq!(
+            metrics
+                .sum_by_name("right_buffered_rows")
+                .unwrap()
+                .as_usize(),
+            250
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn buffered_input_reserves_memory() -> Result<()> {
+        let input = table(("a", "x"), 1000, 4);
+        let context = Arc::new(TaskContext::default());
+        let pool = Arc::clone(context.memory_pool());
+
+        let buffered = BufferedInput::read(input, &context, usize::MAX, 600).await?;
+        assert!(!buffered.complete);
+        // every partition is read until the threshold is exceeded
+        assert_eq!(buffered.num_rows, 750);
+        assert!(pool.reserved() > 0);
+
+        let exec = buffered.into_exec();
+        for partition in 0..4 {
+            let batches =
+                crate::common::collect(exec.execute(partition, Arc::clone(&context))?)
+                    .await?;
+            assert_eq!(batches.len(), 1);
+        }
+        assert_eq!(pool.reserved(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn buffered_input_exceeding_memory_limit() -> Result<()> {
+        let input = table(("a", "x"), 1000, 2);
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(100, 1.0)
+            .build_arc()?;
+        let context = Arc::new(TaskContext::default().with_runtime(runtime));
+
+        let Err(err) = BufferedInput::read(input, &context, usize::MAX, 600).await else {
+            panic!("Buffering the input should exceed the memory limit");
+        };
+        assert_contains!(err.to_string(), "Resources exhausted");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn output_columns_are_not_swapped() -> Result<()> {
+        let join = Arc::new(adaptive_join(
+            table(("a", "x"), 4, 1),
+            table(("b", "y"), 2, 1),
+            JoinType::Inner,
+            3,
+        )?);
+        let batches =
+            collect(Arc::clone(&join) as _, Arc::new(TaskContext::default())).await?;
+        assert_eq!(join.strategy(), Some(AdaptiveJoinStrategy::CollectRight));
+        let expected = [
+            "+---+----+---+----+",
+            "| a | x  | b | y  |",
+            "+---+----+---+----+",
+            "| 0 | 0  | 0 | 0  |",
+            "| 1 | 10 | 1 | 10 |",
+            "+---+----+---+----+",
+        ];
+        let mut actual = pretty_format_batches(&batches)?.to_string();
+        actual.push('\n');
+        let actual = actual.lines().collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+}
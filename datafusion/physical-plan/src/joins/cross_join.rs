@@ -322,12 +322,16 @@ fn stats_cartesian_product(
             distinct_count: s.distinct_count,
             min_value: s.min_value,
             max_value: s.max_value,
+            histogram: Precision::Absent,
+            most_common_values: Precision::Absent,
         })
         .chain(right_col_stats.into_iter().map(|s| ColumnStatistics {
             null_count: s.null_count.multiply(&left_row_count),
             distinct_count: s.distinct_count,
             min_value: s.min_value,
             max_value: s.max_value,
+            histogram: Precision::Absent,
+            most_common_values: Precision::Absent,
         }))
         .collect();
 
@@ -561,12 +565,16 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
             ],
         };
@@ -579,6 +587,8 @@ mod tests {
                 max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
                 min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
                 null_count: Precision::Exact(2),
+                histogram: Precision::Absent,
+                most_common_values: Precision::Absent,
             }],
         };
 
@@ -593,18 +603,24 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3 * right_row_count),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(3),
                     max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
                     null_count: Precision::Exact(2 * left_row_count),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
             ],
         };
@@ -625,12 +641,16 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
             ],
         };
@@ -643,6 +663,8 @@ mod tests {
                 max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
                 min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
                 null_count: Precision::Exact(2),
+                histogram: Precision::Absent,
+                most_common_values: Precision::Absent,
             }],
         };
 
@@ -656,19 +678,25 @@ mod tests {
                     distinct_count: Precision::Exact(5),
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
-                    null_count: Precision::Absent, // we don't know the row count on the right
+                    null_count: Precision::Absent, // we don't know the row count on the right,
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
-                    null_count: Precision::Absent, // we don't know the row count on the right
+                    null_count: Precision::Absent, // we don't know the row count on the right,
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(3),
                     max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
                     null_count: Precision::Exact(2 * left_row_count),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
             ],
         };
@@ -38,11 +38,13 @@ use crate::{
         adjust_indices_by_join_type, apply_join_filter_to_indices,
         build_batch_from_indices, build_join_schema, check_join_is_valid,
         estimate_join_statistics, get_final_indices_from_bit_map,
-        need_produce_result_in_final, symmetric_join_output_partitioning,
-        BuildProbeJoinMetrics, ColumnIndex, JoinFilter, JoinHashMap, JoinHashMapOffset,
-        JoinHashMapType, JoinOn, JoinOnRef, StatefulStreamResult,
+        need_produce_result_in_final, swap_join_projection, swap_reverting_projection,
+        symmetric_join_output_partitioning, BuildProbeJoinMetrics, ColumnIndex,
+        JoinFilter, JoinHashMap, JoinHashMapOffset, JoinHashMapType, JoinOn, JoinOnRef,
+        StatefulStreamResult,
     },
     metrics::{ExecutionPlanMetricsSet, MetricsSet},
+    projection::ProjectionExec,
     DisplayAs, DisplayFormatType, Distribution, ExecutionMode, ExecutionPlan,
     Partitioning, PlanProperties, RecordBatchStream, SendableRecordBatchStream,
     Statistics,
@@ -479,6 +481,55 @@ impl HashJoinExec {
         )
     }
 
+    /// Returns a new [`HashJoinExec`] with the left and right inputs swapped,
+    /// so that the right input becomes the build side.
+    ///
+    /// A [`ProjectionExec`] is added on top of the swapped join if needed to
+    /// restore the original order of the output columns.
+    pub fn swap_inputs(
+        &self,
+        partition_mode: PartitionMode,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let left = self.left();
+        let right = self.right();
+        let new_join = HashJoinExec::try_new(
+            Arc::clone(right),
+            Arc::clone(left),
+            self.on()
+                .iter()
+                .map(|(l, r)| (Arc::clone(r), Arc::clone(l)))
+                .collect(),
+            self.filter().map(JoinFilter::swap),
+            &self.join_type().swap(),
+            swap_join_projection(
+                left.schema().fields().len(),
+                right.schema().fields().len(),
+                self.projection.as_ref(),
+                self.join_type(),
+            ),
+            partition_mode,
+            self.null_equals_null(),
+        )?;
+        // In case of anti / semi joins or if there is embedded projection in HashJoinExec, output column order is preserved, no need to add projection again
+        if matches!(
+            self.join_type(),
+            JoinType::LeftSemi
+                | JoinType::RightSemi
+                | JoinType::LeftAnti
+                | JoinType::RightAnti
+        ) || self.projection.is_some()
+        {
+            Ok(Arc::new(new_join))
+        } else {
+            // TODO avoid adding ProjectionExec again and again, only adding Final Projection
+            let proj = ProjectionExec::try_new(
+                swap_reverting_projection(&left.schema(), &right.schema()),
+                Arc::new(new_join),
+            )?;
+            Ok(Arc::new(proj))
+        }
+    }
+
     /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
     fn compute_properties(
         left: &Arc<dyn ExecutionPlan>,
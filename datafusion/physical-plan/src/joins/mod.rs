@@ -17,12 +17,14 @@
 
 //! DataFusion Join implementations
 
+pub use adaptive_hash_join::{AdaptiveHashJoinExec, AdaptiveJoinStrategy};
 pub use cross_join::CrossJoinExec;
 pub use hash_join::HashJoinExec;
 pub use nested_loop_join::NestedLoopJoinExec;
 // Note: SortMergeJoin is not used in plans yet
 pub use sort_merge_join::SortMergeJoinExec;
 pub use symmetric_hash_join::SymmetricHashJoinExec;
+mod adaptive_hash_join;
 mod cross_join;
 mod hash_join;
 mod nested_loop_join;
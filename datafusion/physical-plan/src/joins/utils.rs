@@ -40,7 +40,7 @@ use arrow_array::builder::UInt64Builder;
 use arrow_array::{ArrowPrimitiveType, NativeAdapter, PrimitiveArray};
 use arrow_buffer::ArrowNativeType;
 use datafusion_common::cast::as_boolean_array;
-use datafusion_common::stats::Precision;
+use datafusion_common::stats::{MostCommonValues, Precision};
 use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
 use datafusion_common::{
     plan_err, DataFusionError, JoinSide, JoinType, Result, SharedResult,
@@ -604,6 +604,82 @@ impl JoinFilter {
     pub fn schema(&self) -> &Schema {
         &self.schema
     }
+
+    /// Rewrites the join filter if the inputs to the join are swapped
+    pub fn swap(&self) -> JoinFilter {
+        let column_indices = self
+            .column_indices()
+            .iter()
+            .map(|idx| ColumnIndex {
+                index: idx.index,
+                side: idx.side.negate(),
+            })
+            .collect();
+
+        JoinFilter::new(
+            Arc::clone(self.expression()),
+            column_indices,
+            self.schema().clone(),
+        )
+    }
+}
+
+/// This function swaps the given join's projection.
+pub fn swap_join_projection(
+    left_schema_len: usize,
+    right_schema_len: usize,
+    projection: Option<&Vec<usize>>,
+    join_type: &JoinType,
+) -> Option<Vec<usize>> {
+    match join_type {
+        // For Anti/Semi join types, projection should remain unmodified,
+        // since these joins output schema remains the same after swap
+        JoinType::LeftAnti
+        | JoinType::LeftSemi
+        | JoinType::RightAnti
+        | JoinType::RightSemi => projection.cloned(),
+
+        _ => projection.map(|p| {
+            p.iter()
+                .map(|i| {
+                    // If the index is less than the left schema length, it is from
+                    // the left schema, so we add the right schema length to it.
+                    // Otherwise, it is from the right schema, so we subtract the left
+                    // schema length from it.
+                    if *i < left_schema_len {
+                        *i + right_schema_len
+                    } else {
+                        *i - left_schema_len
+                    }
+                })
+                .collect()
+        }),
+    }
+}
+
+/// When the order of the join inputs is changed, the columns in the output
+/// should not be impacted. This function creates the expressions that will
+/// allow to swap back the values from the original left as the first columns
+/// and those on the right next.
+pub fn swap_reverting_projection(
+    left_schema: &Schema,
+    right_schema: &Schema,
+) -> Vec<(Arc<dyn PhysicalExpr>, String)> {
+    let right_cols = right_schema.fields().iter().enumerate().map(|(i, f)| {
+        (
+            Arc::new(Column::new(f.name(), i)) as Arc<dyn PhysicalExpr>,
+            f.name().to_owned(),
+        )
+    });
+    let right_len = right_cols.len();
+    let left_cols = left_schema.fields().iter().enumerate().map(|(i, f)| {
+        (
+            Arc::new(Column::new(f.name(), right_len + i)) as Arc<dyn PhysicalExpr>,
+            f.name().to_owned(),
+        )
+    });
+
+    left_cols.chain(right_cols).collect()
 }
 
 /// Returns the output field given the input field. Outer joins may
@@ -918,6 +994,20 @@ fn estimate_inner_join_cardinality(
         return Some(estimation);
     };
 
+    // Use the histograms of the join columns when they are known on both sides,
+    // taking the most selective join column
+    let histogram_cardinality = left_stats
+        .column_statistics
+        .iter()
+        .zip(right_stats.column_statistics.iter())
+        .filter_map(|(left_stat, right_stat)| {
+            estimate_cardinality_from_histograms(left_stat, right_stat)
+        })
+        .min();
+    if let Some(cardinality) = histogram_cardinality {
+        return Some(Precision::Inexact(cardinality));
+    }
+
     // The algorithm here is partly based on the non-histogram selectivity estimation
     // from Spark's Catalyst optimizer.
     let mut join_selectivity = Precision::Absent;
@@ -966,6 +1056,73 @@ fn estimate_inner_join_cardinality(
     }
 }
 
+/// Estimates the cardinality of an equijoin of two columns from their histograms
+/// and most common values, as the sum over the values of the product of their
+/// estimated numbers of occurrences in both columns. Returns `None` if the
+/// histogram of either column is not known.
+fn estimate_cardinality_from_histograms(
+    left_stat: &ColumnStatistics,
+    right_stat: &ColumnStatistics,
+) -> Option<usize> {
+    let left_histogram = left_stat.histogram.get_value()?;
+    let right_histogram = right_stat.histogram.get_value()?;
+    let no_values = MostCommonValues::default();
+    let left_values = left_stat
+        .most_common_values
+        .get_value()
+        .unwrap_or(&no_values);
+    let right_values = right_stat
+        .most_common_values
+        .get_value()
+        .unwrap_or(&no_values);
+
+    let mut cardinality = 0.0;
+    for (value, count) in left_values.values() {
+        let right_count = match right_values.get(value) {
+            Some(right_count) => right_count as f64,
+            None => right_histogram.estimate_eq_count(value),
+        };
+        cardinality += *count as f64 * right_count;
+    }
+    for (value, count) in right_values.values() {
+        if left_values.get(value).is_none() {
+            cardinality += *count as f64 * left_histogram.estimate_eq_count(value);
+        }
+    }
+
+    // Within overlapping buckets, the values of the bucket with fewer distinct
+    // values are assumed to be in the other bucket
+    for left in left_histogram.buckets() {
+        for right in right_histogram.buckets() {
+            if right.lower > left.upper {
+                break;
+            }
+            let lower = if left.lower > right.lower {
+                &left.lower
+            } else {
+                &right.lower
+            };
+            let upper = if left.upper < right.upper {
+                &left.upper
+            } else {
+                &right.upper
+            };
+            if lower > upper {
+                continue;
+            }
+            let left_fraction = left.estimate_fraction(lower, upper);
+            let right_fraction = right.estimate_fraction(lower, upper);
+            let distinct_count = (left.distinct_count as f64 * left_fraction)
+                .max(right.distinct_count as f64 * right_fraction)
+                .max(1.0);
+            cardinality +=
+                left.count as f64 * left_fraction * right.count as f64 * right_fraction
+                    / distinct_count;
+        }
+    }
+    Some(cardinality.round() as usize)
+}
+
 /// Estimates if inputs are non-overlapping, using input statistics.
 /// If inputs are disjoint, returns zero estimation, otherwise returns None
 fn estimate_disjoint_inputs(
@@ -1775,6 +1932,7 @@ mod tests {
     use arrow_array::Int32Array;
     use arrow_schema::SortOptions;
     use datafusion_common::stats::Precision::{Absent, Exact, Inexact};
+    use datafusion_common::stats::{Histogram, HistogramBucket};
     use datafusion_common::{arrow_datafusion_err, arrow_err, ScalarValue};
 
     use rstest::rstest;
@@ -1973,6 +2131,8 @@ mod tests {
             min_value: min.map(ScalarValue::from),
             max_value: max.map(ScalarValue::from),
             null_count,
+            histogram: Precision::Absent,
+            most_common_values: Precision::Absent,
         }
     }
 
@@ -2253,6 +2413,53 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_inner_join_cardinality_histograms() -> Result<()> {
+        let bucket = |lower: i64, upper: i64, count, distinct_count| HistogramBucket {
+            lower: ScalarValue::from(lower),
+            upper: ScalarValue::from(upper),
+            count,
+            distinct_count,
+        };
+        // Left table (rows=1000)
+        //   a: min=1, max=100, distinct=100, half of the values are 1
+        let left_col_stats = vec![ColumnStatistics {
+            min_value: Precision::Inexact(ScalarValue::from(1i64)),
+            max_value: Precision::Inexact(ScalarValue::from(100i64)),
+            distinct_count: Precision::Inexact(100),
+            histogram: Precision::Inexact(Histogram::try_new(vec![bucket(
+                2, 100, 500, 99,
+            )])?),
+            most_common_values: Precision::Inexact(MostCommonValues::new(vec![(
+                ScalarValue::from(1i64),
+                500,
+            )])),
+            ..Default::default()
+        }];
+        // Right table (rows=10)
+        //   a: min=1, max=10, distinct=10
+        let right_col_stats = vec![ColumnStatistics {
+            min_value: Precision::Inexact(ScalarValue::from(1i64)),
+            max_value: Precision::Inexact(ScalarValue::from(10i64)),
+            distinct_count: Precision::Inexact(10),
+            histogram: Precision::Inexact(Histogram::try_new(vec![bucket(
+                1, 10, 10, 10,
+            )])?),
+            ..Default::default()
+        }];
+
+        // Without the histograms, the cardinality would be estimated as
+        // 1000 * 10 / 100 = 100
+        assert_eq!(
+            estimate_inner_join_cardinality(
+                create_stats(Some(1000), left_col_stats, false),
+                create_stats(Some(10), right_col_stats, false),
+            ),
+            Some(Precision::Inexact(545))
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_join_cardinality() -> Result<()> {
         // Left table (rows=1000)
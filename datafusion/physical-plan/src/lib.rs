@@ -70,6 +70,8 @@ pub mod placeholder_row;
 pub mod projection;
 pub mod recursive_query;
 pub mod repartition;
+pub mod semi_join_reduction;
+pub mod shared_scan;
 pub mod sorts;
 pub mod spill;
 pub mod stream;
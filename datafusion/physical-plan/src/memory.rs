@@ -55,6 +55,9 @@ pub struct MemoryExec {
     cache: PlanProperties,
     /// if partition sizes should be displayed
     show_sizes: bool,
+    /// Known statistics of the data before projection, which fill the
+    /// statistics that are not computed from the batches
+    statistics: Option<Statistics>,
 }
 
 impl fmt::Debug for MemoryExec {
@@ -149,11 +152,25 @@ impl ExecutionPlan for MemoryExec {
 
     /// We recompute the statistics dynamically from the arrow metadata as it is pretty cheap to do so
     fn statistics(&self) -> Result<Statistics> {
-        Ok(common::compute_record_batch_statistics(
+        let statistics = common::compute_record_batch_statistics(
             &self.partitions,
             &self.schema,
             self.projection.clone(),
-        ))
+        );
+        let Some(known) = &self.statistics else {
+            return Ok(statistics);
+        };
+        let known = match &self.projection {
+            Some(projection) => Statistics {
+                column_statistics: projection
+                    .iter()
+                    .map(|index| known.column_statistics[*index].clone())
+                    .collect(),
+                ..known.clone()
+            },
+            None => known.clone(),
+        };
+        Ok(statistics.fill_absent(&known))
     }
 }
 
@@ -176,6 +193,7 @@ impl MemoryExec {
             sort_information: vec![],
             cache,
             show_sizes: true,
+            statistics: None,
         })
     }
 
@@ -185,6 +203,24 @@ impl MemoryExec {
         self
     }
 
+    /// Set the known statistics of the data before projection, such as the
+    /// ones computed by `ANALYZE TABLE`. They fill the statistics that are not
+    /// computed from the batches, as inexact statistics.
+    ///
+    /// Returns an error if they don't have a column for each field of the
+    /// schema.
+    pub fn try_with_statistics(mut self, statistics: Statistics) -> Result<Self> {
+        if statistics.column_statistics.len() != self.schema.fields().len() {
+            return internal_err!(
+                "Expected statistics for {} columns, got {}",
+                self.schema.fields().len(),
+                statistics.column_statistics.len()
+            );
+        }
+        self.statistics = Some(statistics);
+        Ok(self)
+    }
+
     pub fn partitions(&self) -> &[Vec<RecordBatch>] {
         &self.partitions
     }
@@ -366,7 +402,11 @@ mod tests {
     use crate::memory::MemoryExec;
     use crate::ExecutionPlan;
 
+    use arrow::record_batch::RecordBatch;
+    use arrow_array::Int64Array;
     use arrow_schema::{DataType, Field, Schema, SortOptions};
+    use datafusion_common::stats::Precision;
+    use datafusion_common::Statistics;
     use datafusion_physical_expr::expressions::col;
     use datafusion_physical_expr::PhysicalSortExpr;
 
@@ -408,4 +448,36 @@ mod tests {
         assert!(eq_properties.oeq_class().contains(&sort2));
         Ok(())
     }
+
+    #[test]
+    fn test_memory_exec_with_statistics() -> datafusion_common::Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Int64, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3])),
+                Arc::new(Int64Array::from(vec![4, 5, 5])),
+            ],
+        )?;
+        let mut statistics = Statistics::new_unknown(&schema);
+        statistics.num_rows = Precision::Exact(100);
+        statistics.column_statistics[1].distinct_count = Precision::Exact(2);
+
+        let mem_exec = MemoryExec::try_new(&[vec![batch]], schema, Some(vec![1]))?
+            .try_with_statistics(statistics)?;
+        let actual = mem_exec.statistics()?;
+
+        // The statistics computed from the batches take precedence
+        assert_eq!(actual.num_rows, Precision::Exact(3));
+        assert_eq!(actual.column_statistics.len(), 1);
+        assert_eq!(actual.column_statistics[0].null_count, Precision::Exact(0));
+        assert_eq!(
+            actual.column_statistics[0].distinct_count,
+            Precision::Inexact(2)
+        );
+        Ok(())
+    }
 }
@@ -389,18 +389,24 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Exact(ScalarValue::Float32(Some(1.1))),
                     min_value: Precision::Exact(ScalarValue::Float32(Some(0.1))),
                     null_count: Precision::Absent,
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
             ],
         }
@@ -433,12 +439,16 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(5),
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
             ],
         };
@@ -467,12 +477,16 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Float32(Some(1.1))),
                     min_value: Precision::Exact(ScalarValue::Float32(Some(0.1))),
                     null_count: Precision::Absent,
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(5),
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
             ],
         };
@@ -0,0 +1,833 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`SemiJoinReductionExec`] computes the join keys of semi-join reductions
+//! at execution, and passes them to the scans they filter
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, Weak};
+
+use crate::joins::utils::OnceFut;
+use crate::metrics::{
+    BaselineMetrics, Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+};
+use crate::stream::{ObservedStream, RecordBatchStreamAdapter};
+use crate::{
+    DisplayAs, DisplayFormatType, Distribution, ExecutionMode, ExecutionPlan,
+    ExecutionPlanProperties, PhysicalExpr, PlanProperties, SendableRecordBatchStream,
+    Statistics,
+};
+
+use ahash::RandomState;
+use arrow::array::{Array, ArrayRef, BooleanArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use datafusion_common::hash_utils::create_hashes;
+use datafusion_common::tree_node::{Transformed, TreeNode};
+use datafusion_common::{exec_err, internal_err, Result, ScalarValue};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::TaskContext;
+use datafusion_expr::expr::ScalarFunction;
+use datafusion_expr::{
+    ColumnarValue, Expr, ScalarUDF, ScalarUDFImpl, Signature, Volatility,
+};
+use datafusion_physical_expr::expressions::{in_list, lit, Column};
+use datafusion_physical_expr::ScalarFunctionExpr;
+
+use futures::{StreamExt, TryFutureExt};
+use parking_lot::Mutex;
+
+/// The false positive probability of the bloom filters of semi-join
+/// reductions
+const BLOOM_FILTER_FPP: f64 = 0.01;
+
+/// The join keys of a semi-join reduction, computed by a
+/// [`SemiJoinReductionExec`] for each execution.
+///
+/// The keys are passed to the scan as a filter expression over a single
+/// column, the key, which is either an `IN` list or a bloom filter check.
+#[derive(Debug)]
+pub struct SemiJoinKeys {
+    /// Name of the key column
+    name: String,
+    /// The filters of the executions still running
+    filters: Mutex<Vec<KeysFilter>>,
+}
+
+/// The filter of the join keys computed for one execution
+#[derive(Debug)]
+struct KeysFilter {
+    /// The task context the keys are computed with
+    context: Weak<TaskContext>,
+    /// The filter over the key column at index 0
+    filter: Arc<dyn PhysicalExpr>,
+}
+
+impl SemiJoinKeys {
+    /// Create the keys of the key column `name`, which are not computed yet
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            filters: Mutex::new(vec![]),
+        }
+    }
+
+    /// Name of the key column
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the filter of the keys computed for `context`, over the key
+    /// column at index 0, if they are computed
+    pub fn filter(&self, context: &Arc<TaskContext>) -> Option<Arc<dyn PhysicalExpr>> {
+        self.filters
+            .lock()
+            .iter()
+            .find(|f| std::ptr::eq(f.context.as_ptr(), Arc::as_ptr(context)))
+            .map(|f| Arc::clone(&f.filter))
+    }
+
+    fn set_filter(&self, context: &Arc<TaskContext>, filter: Arc<dyn PhysicalExpr>) {
+        let mut filters = self.filters.lock();
+        // the keys of the finished executions are not needed anymore
+        filters.retain(|f| f.context.strong_count() > 0);
+        filters.push(KeysFilter {
+            context: Arc::downgrade(context),
+            filter,
+        });
+    }
+}
+
+/// Returns an expression filtering `expr` with the join keys of a semi-join
+/// reduction, to be passed to the scan of a table.
+///
+/// The keys are only known at execution: the scan must replace the
+/// expression with the keys computed for its [`TaskContext`] with
+/// [`resolve_semi_join_reductions`] before using it. Evaluated without being
+/// resolved, the expression is true for all values.
+pub fn semi_join_reduction_filter(keys: Arc<SemiJoinKeys>, expr: Expr) -> Expr {
+    let udf = ScalarUDF::new_from_impl(SemiJoinReductionFilter::new(keys));
+    Expr::ScalarFunction(ScalarFunction::new_udf(Arc::new(udf), vec![expr]))
+}
+
+/// Replaces the semi-join reduction filters of `predicate`, see
+/// [`semi_join_reduction_filter`], with the join keys computed for `context`.
+/// The filters whose keys are not computed are left unchanged.
+pub fn resolve_semi_join_reductions(
+    predicate: Arc<dyn PhysicalExpr>,
+    context: &Arc<TaskContext>,
+) -> Result<Transformed<Arc<dyn PhysicalExpr>>> {
+    predicate.transform_up(|expr| {
+        let Some(function) = expr.as_any().downcast_ref::<ScalarFunctionExpr>() else {
+            return Ok(Transformed::no(expr));
+        };
+        let Some(reduction) = function
+            .fun()
+            .inner()
+            .as_any()
+            .downcast_ref::<SemiJoinReductionFilter>()
+        else {
+            return Ok(Transformed::no(expr));
+        };
+        let Some(filter) = reduction.keys.filter(context) else {
+            return Ok(Transformed::no(expr));
+        };
+        let key = Arc::clone(&function.args()[0]);
+        filter
+            .transform(|expr| {
+                Ok(match expr.as_any().downcast_ref::<Column>() {
+                    Some(column) if column.index() == 0 => {
+                        Transformed::yes(Arc::clone(&key))
+                    }
+                    _ => Transformed::no(expr),
+                })
+            })
+            .map(|filter| Transformed::yes(filter.data))
+    })
+}
+
+/// The function of [`semi_join_reduction_filter`]
+#[derive(Debug)]
+struct SemiJoinReductionFilter {
+    keys: Arc<SemiJoinKeys>,
+    signature: Signature,
+}
+
+impl SemiJoinReductionFilter {
+    fn new(keys: Arc<SemiJoinKeys>) -> Self {
+        Self {
+            keys,
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for SemiJoinReductionFilter {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "semi_join_reduction"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        match args {
+            [ColumnarValue::Array(array)] => Ok(ColumnarValue::Array(Arc::new(
+                BooleanArray::from(vec![true; array.len()]),
+            ))),
+            [ColumnarValue::Scalar(_)] => {
+                Ok(ColumnarValue::Scalar(ScalarValue::Boolean(Some(true))))
+            }
+            _ => exec_err!("semi_join_reduction expects a single argument"),
+        }
+    }
+
+    fn equals(&self, other: &dyn ScalarUDFImpl) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<Self>()
+            .is_some_and(|other| Arc::ptr_eq(&self.keys, &other.keys))
+    }
+
+    fn hash_value(&self) -> u64 {
+        let hasher = &mut DefaultHasher::new();
+        self.name().hash(hasher);
+        Arc::as_ptr(&self.keys).hash(hasher);
+        hasher.finish()
+    }
+}
+
+/// A semi-join reduction of a [`SemiJoinReductionExec`]: the plan returning
+/// the join keys, and where to store them
+#[derive(Debug, Clone)]
+pub struct SemiJoinReduction {
+    /// The plan returning the join keys in its first column
+    pub keys_plan: Arc<dyn ExecutionPlan>,
+    /// The join keys passed to the scan
+    pub keys: Arc<SemiJoinKeys>,
+}
+
+/// The semi-join reductions computed for one execution
+struct Resolution {
+    /// The task context the keys are computed with
+    context: Weak<TaskContext>,
+    /// The memory reservations of the keys of each reduction
+    reservations: OnceFut<Vec<MemoryReservation>>,
+}
+
+/// Computes the join keys of semi-join reductions before executing its
+/// input, which scans a table filtered by the keys.
+///
+/// The keys plans are executed once per execution, that is for each
+/// [`TaskContext`], when the first partition is polled. Their keys are
+/// stored in the [`SemiJoinKeys`] of the reductions, as an `IN` list if they
+/// have at most `semi_join_reduction_max_in_list_size` distinct values, and
+/// as a bloom filter otherwise. The input is only executed once the keys are
+/// stored, so that its scan can use them, see
+/// [`resolve_semi_join_reductions`].
+///
+/// If a keys plan returns more than `semi_join_reduction_max_keys` rows, or
+/// its keys do not fit in the memory pool, the keys are not stored: the scan
+/// is not filtered, and the join still removes the rows without a matching
+/// key.
+pub struct SemiJoinReductionExec {
+    input: Arc<dyn ExecutionPlan>,
+    reductions: Vec<SemiJoinReduction>,
+    /// The reductions of the executions still running
+    resolutions: Mutex<Vec<Resolution>>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Number of join keys returned by the keys plans
+    num_keys: Count,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl fmt::Debug for SemiJoinReductionExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemiJoinReductionExec")
+            .field("input", &self.input)
+            .field("reductions", &self.reductions)
+            .finish()
+    }
+}
+
+impl SemiJoinReductionExec {
+    /// Create a plan computing the keys of `reductions` before executing
+    /// `input`
+    pub fn try_new(
+        input: Arc<dyn ExecutionPlan>,
+        reductions: Vec<SemiJoinReduction>,
+    ) -> Result<Self> {
+        if reductions.is_empty() {
+            return internal_err!("SemiJoinReductionExec requires a reduction");
+        }
+        let mut cache = input.properties().clone();
+        if reductions
+            .iter()
+            .any(|reduction| reduction.keys_plan.execution_mode().is_unbounded())
+        {
+            cache.execution_mode = ExecutionMode::PipelineBreaking;
+        }
+        let metrics = ExecutionPlanMetricsSet::new();
+        let num_keys = MetricBuilder::new(&metrics).global_counter("num_keys");
+        Ok(Self {
+            input,
+            reductions,
+            resolutions: Mutex::new(vec![]),
+            metrics,
+            num_keys,
+            cache,
+        })
+    }
+
+    /// The input scanning the table filtered by the keys
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// The semi-join reductions
+    pub fn reductions(&self) -> &[SemiJoinReduction] {
+        &self.reductions
+    }
+
+    /// Returns the future computing the keys for `context`, starting it if
+    /// no partition has been executed with this context yet
+    fn resolution(&self, context: &Arc<TaskContext>) -> OnceFut<Vec<MemoryReservation>> {
+        let mut resolutions = self.resolutions.lock();
+        resolutions.retain(|r| r.context.strong_count() > 0);
+        if let Some(current) = resolutions
+            .iter()
+            .find(|r| std::ptr::eq(r.context.as_ptr(), Arc::as_ptr(context)))
+        {
+            return current.reservations.clone();
+        }
+
+        let reductions = self.reductions.clone();
+        let context_ = Arc::clone(context);
+        let num_keys = self.num_keys.clone();
+        let reservations = OnceFut::new(async move {
+            let mut reservations = Vec::with_capacity(reductions.len());
+            for reduction in reductions {
+                reservations.extend(
+                    resolve_keys(reduction, Arc::clone(&context_), &num_keys).await?,
+                );
+            }
+            Ok(reservations)
+        });
+        resolutions.push(Resolution {
+            context: Arc::downgrade(context),
+            reservations: reservations.clone(),
+        });
+        reservations
+    }
+}
+
+/// Executes the keys plan of `reduction` and stores its keys, returning the
+/// memory reservation of the keys if they are stored
+async fn resolve_keys(
+    reduction: SemiJoinReduction,
+    context: Arc<TaskContext>,
+    num_keys: &Count,
+) -> Result<Option<MemoryReservation>> {
+    let options = &context.session_config().options().optimizer;
+    let max_keys = options.semi_join_reduction_max_keys;
+    let max_in_list_size = options.semi_join_reduction_max_in_list_size;
+
+    let mut reservation =
+        MemoryConsumer::new("SemiJoinReductionExec").register(context.memory_pool());
+    let mut stream = reduction.keys_plan.execute(0, Arc::clone(&context))?;
+    let mut arrays = vec![];
+    let mut num_rows = 0;
+    while let Some(batch) = stream.next().await.transpose()? {
+        let column = Arc::clone(batch.column(0));
+        num_rows += column.len();
+        if num_rows > max_keys
+            || reservation
+                .try_grow(column.get_array_memory_size())
+                .is_err()
+        {
+            return Ok(None);
+        }
+        arrays.push(column);
+    }
+    num_keys.add(num_rows);
+
+    let schema = reduction.keys_plan.schema();
+    let field = schema.field(0);
+    let key_schema = Schema::new(vec![Field::new(
+        reduction.keys.name(),
+        field.data_type().clone(),
+        true,
+    )]);
+    let key: Arc<dyn PhysicalExpr> = Arc::new(Column::new(reduction.keys.name(), 0));
+    let (filter, size) = match distinct_keys(&arrays, max_in_list_size)? {
+        // `IN` never matches nulls
+        Some(values) if values.is_empty() => (lit(false), 0),
+        Some(mut values) => {
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            let size = values.iter().map(ScalarValue::size).sum();
+            let list = values.into_iter().map(lit).collect();
+            (in_list(key, list, &false, &key_schema)?, size)
+        }
+        None => {
+            let filter = Arc::new(BloomFilter::try_new(&arrays, BLOOM_FILTER_FPP)?);
+            let size = filter.size();
+            let udf = ScalarUDF::new_from_impl(BloomFilterContains::new(filter));
+            let filter = ScalarFunctionExpr::new(
+                "bloom_filter_contains",
+                Arc::new(udf),
+                vec![key],
+                DataType::Boolean,
+            );
+            (Arc::new(filter) as _, size)
+        }
+    };
+    reservation.resize(size);
+    reduction.keys.set_filter(&context, filter);
+    Ok(Some(reservation))
+}
+
+/// The distinct non-null values of `arrays`, or `None` if there are more
+/// than `limit`
+fn distinct_keys(arrays: &[ArrayRef], limit: usize) -> Result<Option<Vec<ScalarValue>>> {
+    let mut values = HashSet::new();
+    for array in arrays {
+        for index in 0..array.len() {
+            if array.is_null(index) {
+                continue;
+            }
+            values.insert(ScalarValue::try_from_array(array, index)?);
+            if values.len() > limit {
+                return Ok(None);
+            }
+        }
+    }
+    Ok(Some(values.into_iter().collect()))
+}
+
+impl DisplayAs for SemiJoinReductionExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let keys = self
+                    .reductions
+                    .iter()
+                    .map(|reduction| reduction.keys.name())
+                    .collect::<Vec<_>>();
+                write!(f, "SemiJoinReductionExec: keys=[{}]", keys.join(", "))
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for SemiJoinReductionExec {
+    fn name(&self) -> &'static str {
+        "SemiJoinReductionExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    /// The input, followed by the keys plans
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        std::iter::once(&self.input)
+            .chain(self.reductions.iter().map(|reduction| &reduction.keys_plan))
+            .collect()
+    }
+
+    /// The keys are read from the first partition of the keys plans
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        std::iter::once(Distribution::UnspecifiedDistribution)
+            .chain(
+                self.reductions
+                    .iter()
+                    .map(|_| Distribution::SinglePartition),
+            )
+            .collect()
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        std::iter::once(true)
+            .chain(self.reductions.iter().map(|_| false))
+            .collect()
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        vec![false; self.reductions.len() + 1]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let mut children = children.into_iter();
+        let Some(input) = children.next() else {
+            return internal_err!("SemiJoinReductionExec requires an input");
+        };
+        let reductions = self
+            .reductions
+            .iter()
+            .zip(children)
+            .map(|(reduction, keys_plan)| SemiJoinReduction {
+                keys_plan,
+                keys: Arc::clone(&reduction.keys),
+            })
+            .collect();
+        Ok(Arc::new(Self::try_new(input, reductions)?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let mut reservations = self.resolution(&context);
+        let input = Arc::clone(&self.input);
+        let stream = futures::future::poll_fn(move |cx| reservations.get_shared(cx))
+            .map_ok(move |_| input.execute(partition, context))
+            .and_then(futures::future::ready)
+            .try_flatten_stream();
+
+        let stream = RecordBatchStreamAdapter::new(self.schema(), stream);
+        let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+        Ok(Box::pin(ObservedStream::new(
+            Box::pin(stream),
+            baseline_metrics,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        self.input.statistics()
+    }
+}
+
+/// A bloom filter of the hashes of a set of values
+#[derive(Debug)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    num_hashes: u32,
+    random_state: RandomState,
+}
+
+impl BloomFilter {
+    /// Creates a bloom filter containing the non-null values of `arrays`,
+    /// which returns false positives with a probability of about `fpp`
+    pub fn try_new(arrays: &[ArrayRef], fpp: f64) -> Result<Self> {
+        let num_values = arrays
+            .iter()
+            .map(|array| array.len() - array.null_count())
+            .sum::<usize>()
+            .max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits = (-num_values * fpp.ln() / (ln2 * ln2)).ceil() as usize;
+        let num_words = num_bits.div_ceil(64).next_power_of_two();
+        let num_hashes = ((num_words * 64) as f64 / num_values * ln2)
+            .round()
+            .clamp(1.0, 16.0) as u32;
+
+        let mut filter = Self {
+            bits: vec![0; num_words],
+            num_hashes,
+            random_state: RandomState::with_seeds(0, 0, 0, 0),
+        };
+        let mut hashes = vec![];
+        for array in arrays {
+            filter.hash(array, &mut hashes)?;
+            for (index, hash) in hashes.iter().enumerate() {
+                if array.is_valid(index) {
+                    for bit in filter.bit_indices(*hash) {
+                        filter.bits[bit / 64] |= 1 << (bit % 64);
+                    }
+                }
+            }
+        }
+        Ok(filter)
+    }
+
+    /// Returns whether each value of `array` may be contained in the filter,
+    /// or null for null values
+    pub fn contains(&self, array: &ArrayRef) -> Result<BooleanArray> {
+        let mut hashes = vec![];
+        self.hash(array, &mut hashes)?;
+        Ok(hashes
+            .iter()
+            .enumerate()
+            .map(|(index, hash)| {
+                array.is_valid(index).then(|| {
+                    self.bit_indices(*hash)
+                        .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+                })
+            })
+            .collect())
+    }
+
+    /// Returns the size of the filter in bytes
+    pub fn size(&self) -> usize {
+        self.bits.len() * 8
+    }
+
+    fn hash(&self, array: &ArrayRef, hashes: &mut Vec<u64>) -> Result<()> {
+        hashes.clear();
+        hashes.resize(array.len(), 0);
+        create_hashes(&[Arc::clone(array)], &self.random_state, hashes)?;
+        Ok(())
+    }
+
+    /// The bits set for a value with `hash`, using double hashing
+    fn bit_indices(&self, hash: u64) -> impl Iterator<Item = usize> {
+        let mask = self.bits.len() * 64 - 1;
+        let step = hash.rotate_left(32) | 1;
+        (0..self.num_hashes as u64)
+            .map(move |i| hash.wrapping_add(i.wrapping_mul(step)) as usize & mask)
+    }
+}
+
+/// Checks whether its argument may be contained in a [`BloomFilter`]
+#[derive(Debug)]
+struct BloomFilterContains {
+    filter: Arc<BloomFilter>,
+    signature: Signature,
+}
+
+impl BloomFilterContains {
+    fn new(filter: Arc<BloomFilter>) -> Self {
+        Self {
+            filter,
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for BloomFilterContains {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "bloom_filter_contains"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        match args {
+            [ColumnarValue::Array(array)] => {
+                Ok(ColumnarValue::Array(Arc::new(self.filter.contains(array)?)))
+            }
+            [ColumnarValue::Scalar(scalar)] => {
+                let contains = self.filter.contains(&scalar.to_array()?)?;
+                Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+                    &contains, 0,
+                )?))
+            }
+            _ => exec_err!("bloom_filter_contains expects a single argument"),
+        }
+    }
+
+    fn equals(&self, other: &dyn ScalarUDFImpl) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<Self>()
+            .is_some_and(|other| Arc::ptr_eq(&self.filter, &other.filter))
+    }
+
+    fn hash_value(&self) -> u64 {
+        let hasher = &mut DefaultHasher::new();
+        self.name().hash(hasher);
+        Arc::as_ptr(&self.filter).hash(hasher);
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect;
+    use crate::memory::MemoryExec;
+
+    use arrow::array::{Int64Array, RecordBatch, StringArray};
+    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+
+    fn memory_exec(values: Vec<i64>) -> Arc<dyn ExecutionPlan> {
+        let schema = Arc::new(Schema::new(vec![Field::new("k", DataType::Int64, true)]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(Int64Array::from(values))],
+        )
+        .unwrap();
+        Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None).unwrap())
+    }
+
+    fn reduction_exec(
+        keys: &Arc<SemiJoinKeys>,
+        values: Vec<i64>,
+    ) -> SemiJoinReductionExec {
+        let reduction = SemiJoinReduction {
+            keys_plan: memory_exec(values),
+            keys: Arc::clone(keys),
+        };
+        SemiJoinReductionExec::try_new(memory_exec(vec![1, 2, 3]), vec![reduction])
+            .unwrap()
+    }
+
+    /// The predicate `k@0 = semi_join_reduction(k@0)` resolved for `context`
+    fn resolved(keys: &Arc<SemiJoinKeys>, context: &Arc<TaskContext>) -> Result<String> {
+        let udf =
+            ScalarUDF::new_from_impl(SemiJoinReductionFilter::new(Arc::clone(keys)));
+        let predicate: Arc<dyn PhysicalExpr> = Arc::new(ScalarFunctionExpr::new(
+            "semi_join_reduction",
+            Arc::new(udf),
+            vec![Arc::new(Column::new("k", 0))],
+            DataType::Boolean,
+        ));
+        let resolved = resolve_semi_join_reductions(predicate, context)?;
+        Ok(resolved.data.to_string())
+    }
+
+    #[tokio::test]
+    async fn keys_are_computed_per_execution() -> Result<()> {
+        let keys = Arc::new(SemiJoinKeys::new("k"));
+        let exec = Arc::new(reduction_exec(&keys, vec![3, 1, 3]));
+        let context = Arc::new(TaskContext::default());
+        // creating the plan does not compute the keys
+        assert_eq!(resolved(&keys, &context)?, "semi_join_reduction(k@0)");
+
+        let batches = collect(Arc::clone(&exec) as _, Arc::clone(&context)).await?;
+        assert_eq!(batches[0].num_rows(), 3);
+        assert_eq!(resolved(&keys, &context)?, "Use k@0 IN (SET) ([Literal { value: Int64(1) }, Literal { value: Int64(3) }])");
+        let metrics = exec.metrics().unwrap();
+        assert_eq!(metrics.sum_by_name("num_keys").unwrap().as_usize(), 3);
+
+        // another execution computes the keys again
+        let other = Arc::new(TaskContext::default());
+        assert_eq!(resolved(&keys, &other)?, "semi_join_reduction(k@0)");
+        collect(exec, Arc::clone(&other)).await?;
+        assert_eq!(resolved(&keys, &other)?, "Use k@0 IN (SET) ([Literal { value: Int64(1) }, Literal { value: Int64(3) }])");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn keys_as_bloom_filter() -> Result<()> {
+        let keys = Arc::new(SemiJoinKeys::new("k"));
+        let exec = Arc::new(reduction_exec(&keys, vec![3, 1, 4]));
+        let mut config = datafusion_execution::config::SessionConfig::new();
+        config
+            .options_mut()
+            .optimizer
+            .semi_join_reduction_max_in_list_size = 2;
+        let context = Arc::new(TaskContext::default().with_session_config(config));
+        collect(exec, Arc::clone(&context)).await?;
+        assert_eq!(resolved(&keys, &context)?, "bloom_filter_contains(k@0)");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn too_many_keys() -> Result<()> {
+        let keys = Arc::new(SemiJoinKeys::new("k"));
+        let exec = Arc::new(reduction_exec(&keys, vec![3, 1, 4]));
+        let mut config = datafusion_execution::config::SessionConfig::new();
+        config.options_mut().optimizer.semi_join_reduction_max_keys = 2;
+        let context = Arc::new(TaskContext::default().with_session_config(config));
+        let batches = collect(exec, Arc::clone(&context)).await?;
+        assert_eq!(batches[0].num_rows(), 3);
+        assert_eq!(resolved(&keys, &context)?, "semi_join_reduction(k@0)");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn keys_exceeding_memory_limit() -> Result<()> {
+        let keys = Arc::new(SemiJoinKeys::new("k"));
+        let exec = Arc::new(reduction_exec(&keys, (0..1000).collect()));
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(1000, 1.0)
+            .build_arc()?;
+        let context = Arc::new(TaskContext::default().with_runtime(runtime));
+        let batches = collect(exec, Arc::clone(&context)).await?;
+        assert_eq!(batches[0].num_rows(), 3);
+        assert_eq!(resolved(&keys, &context)?, "semi_join_reduction(k@0)");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn keys_are_reserved_while_executing() -> Result<()> {
+        let keys = Arc::new(SemiJoinKeys::new("k"));
+        let exec = reduction_exec(&keys, vec![3, 1, 4]);
+        let context = Arc::new(TaskContext::default());
+        let mut stream = exec.execute(0, Arc::clone(&context))?;
+        stream.next().await.transpose()?;
+        assert!(context.memory_pool().reserved() > 0);
+        drop(stream);
+        drop(exec);
+        assert_eq!(context.memory_pool().reserved(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn bloom_filter() -> Result<()> {
+        let values: ArrayRef = Arc::new(Int64Array::from_iter_values(0..1000));
+        let filter = BloomFilter::try_new(&[Arc::clone(&values)], 0.01)?;
+        assert!(filter.size() > 0);
+
+        // no false negatives
+        let contains = filter.contains(&values)?;
+        assert_eq!(contains.true_count(), 1000);
+
+        // few false positives
+        let other: ArrayRef = Arc::new(Int64Array::from_iter_values(1000..11000));
+        let false_positives = filter.contains(&other)?.true_count();
+        assert!(false_positives < 300, "{false_positives}");
+
+        // nulls are neither inserted nor contained
+        let values: ArrayRef =
+            Arc::new(StringArray::from(vec![Some("a"), None, Some("c")]));
+        let filter = BloomFilter::try_new(&[Arc::clone(&values)], 0.01)?;
+        let contains = filter.contains(&values)?;
+        assert_eq!(
+            contains,
+            BooleanArray::from(vec![Some(true), None, Some(true)])
+        );
+        Ok(())
+    }
+}
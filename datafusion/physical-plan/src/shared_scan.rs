@@ -0,0 +1,601 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the shared scan plan, which computes its input once and fans the
+//! results out to several consumers
+
+use std::any::Any;
+use std::sync::{Arc, Mutex, Weak};
+
+use super::metrics::{BaselineMetrics, Count, ExecutionPlanMetricsSet, MetricsSet};
+use super::{SendableRecordBatchStream, Statistics};
+use crate::common::IPCWriter;
+use crate::joins::utils::OnceFut;
+use crate::metrics::MetricBuilder;
+use crate::spill::read_spill;
+use crate::stream::{
+    ObservedStream, RecordBatchReceiverStream, RecordBatchStreamAdapter,
+};
+use crate::{
+    DisplayAs, DisplayFormatType, ExecutionMode, ExecutionPlan, ExecutionPlanProperties,
+    PlanProperties,
+};
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::stats::Precision;
+use datafusion_common::{DataFusionError, Result};
+use datafusion_common_runtime::SpawnedTask;
+use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::TaskContext;
+use datafusion_physical_expr::{EquivalenceProperties, Partitioning};
+
+use futures::{stream, StreamExt, TryFutureExt};
+use log::debug;
+
+/// The results of one input partition of a [`Spool`]
+#[derive(Debug)]
+enum SpooledPartition {
+    /// The batches are buffered in memory
+    Memory {
+        batches: Vec<RecordBatch>,
+        #[allow(dead_code)]
+        reservation: MemoryReservation,
+    },
+    /// The batches did not fit in memory and were written to a spill file
+    Spilled(Arc<RefCountedTempFile>),
+}
+
+/// Spill metrics of a [`Spool`]
+#[derive(Debug, Clone)]
+struct SpoolMetrics {
+    spill_count: Count,
+    spilled_bytes: Count,
+    spilled_rows: Count,
+}
+
+impl SpoolMetrics {
+    fn new(metrics: &ExecutionPlanMetricsSet) -> Self {
+        Self {
+            spill_count: MetricBuilder::new(metrics).spill_count(0),
+            spilled_bytes: MetricBuilder::new(metrics).spilled_bytes(0),
+            spilled_rows: MetricBuilder::new(metrics).spilled_rows(0),
+        }
+    }
+}
+
+/// The results of a [`Spool`] computed for one execution
+struct Materialization {
+    /// The task context the input is executed with
+    context: Weak<TaskContext>,
+    /// The results of the input partitions
+    partitions: OnceFut<Vec<SpooledPartition>>,
+}
+
+/// Computes the results of a shared relation once per execution and buffers
+/// them for every [`SharedScanExec`] reading from it.
+///
+/// The input of the first consumer executed with a [`TaskContext`] is executed
+/// and its partitions are buffered in memory. A partition whose results do not
+/// fit in the memory pool is written to a spill file of the [`DiskManager`].
+///
+/// The results are only shared by the consumers executed with the same
+/// [`TaskContext`], executing them with a new context recomputes the input.
+/// The spool does not hold any plan: each consumer carries its own input, so
+/// a spool can outlive the plans it was created for.
+///
+/// [`DiskManager`]: datafusion_execution::DiskManager
+pub struct Spool {
+    /// Name of the shared relation
+    name: String,
+    /// The materializations of the executions still running
+    materializations: Mutex<Vec<Materialization>>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Spill metrics, shared by all the materializations
+    spool_metrics: SpoolMetrics,
+}
+
+impl std::fmt::Debug for Spool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Spool").field("name", &self.name).finish()
+    }
+}
+
+impl Spool {
+    /// Create a new spool for the shared relation `name`
+    pub fn new(name: impl Into<String>) -> Self {
+        let metrics = ExecutionPlanMetricsSet::new();
+        let spool_metrics = SpoolMetrics::new(&metrics);
+        Self {
+            name: name.into(),
+            materializations: Mutex::new(vec![]),
+            metrics,
+            spool_metrics,
+        }
+    }
+
+    /// Name of the shared relation
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the results for `context`, starting to compute them with
+    /// `input` if no consumer has been executed with this context yet
+    fn partitions(
+        &self,
+        input: &Arc<dyn ExecutionPlan>,
+        context: &Arc<TaskContext>,
+    ) -> OnceFut<Vec<SpooledPartition>> {
+        let mut materializations = self.materializations.lock().unwrap();
+        // the results of the finished executions are not needed anymore
+        materializations.retain(|m| m.context.strong_count() > 0);
+        if let Some(current) = materializations
+            .iter()
+            .find(|m| std::ptr::eq(m.context.as_ptr(), Arc::as_ptr(context)))
+        {
+            return current.partitions.clone();
+        }
+
+        let partitions = OnceFut::new(materialize(
+            self.name.clone(),
+            Arc::clone(input),
+            Arc::clone(context),
+            self.spool_metrics.clone(),
+        ));
+        materializations.push(Materialization {
+            context: Arc::downgrade(context),
+            partitions: partitions.clone(),
+        });
+        partitions
+    }
+}
+
+/// Executes all partitions of `input` and buffers their results
+async fn materialize(
+    name: String,
+    input: Arc<dyn ExecutionPlan>,
+    context: Arc<TaskContext>,
+    metrics: SpoolMetrics,
+) -> Result<Vec<SpooledPartition>> {
+    let tasks = (0..input.output_partitioning().partition_count())
+        .map(|partition| {
+            let stream = input.execute(partition, Arc::clone(&context))?;
+            let reservation =
+                MemoryConsumer::new(format!("SharedScanExec[{name}][{partition}]"))
+                    .with_can_spill(true)
+                    .register(context.memory_pool());
+            Ok(SpawnedTask::spawn(spool_partition(
+                stream,
+                reservation,
+                Arc::clone(&context),
+                metrics.clone(),
+            )))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut partitions = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        partitions.push(
+            task.join_unwind()
+                .await
+                .map_err(DataFusionError::ExecutionJoin)??,
+        );
+    }
+    Ok(partitions)
+}
+
+/// Buffers the batches of `stream` in memory, writing them to a spill file
+/// once they do not fit in the memory pool anymore
+async fn spool_partition(
+    mut stream: SendableRecordBatchStream,
+    mut reservation: MemoryReservation,
+    context: Arc<TaskContext>,
+    metrics: SpoolMetrics,
+) -> Result<SpooledPartition> {
+    let mut batches = vec![];
+    let mut spill: Option<(RefCountedTempFile, IPCWriter)> = None;
+    while let Some(batch) = stream.next().await.transpose()? {
+        if let Some((_, writer)) = spill.as_mut() {
+            writer.write(&batch)?;
+            continue;
+        }
+
+        let size = batch.get_array_memory_size();
+        if reservation.try_grow(size).is_ok() {
+            batches.push(batch);
+            continue;
+        }
+
+        // the partition does not fit in memory, write it to disk
+        let disk_manager = &context.runtime_env().disk_manager;
+        if !disk_manager.tmp_files_enabled() {
+            reservation.try_grow(size)?;
+        }
+        let file = disk_manager.create_tmp_file("SharedScanExec")?;
+        let mut writer = IPCWriter::new(file.path(), stream.schema().as_ref())?;
+        for batch in batches.drain(..).chain(std::iter::once(batch)) {
+            writer.write(&batch)?;
+        }
+        reservation.free();
+        spill = Some((file, writer));
+    }
+
+    match spill {
+        Some((file, mut writer)) => {
+            writer.finish()?;
+            debug!(
+                "Spilled {} shared scan batches of total {} rows to disk",
+                writer.num_batches, writer.num_rows
+            );
+            metrics.spill_count.add(1);
+            metrics.spilled_bytes.add(writer.num_bytes);
+            metrics.spilled_rows.add(writer.num_rows);
+            Ok(SpooledPartition::Spilled(Arc::new(file)))
+        }
+        None => Ok(SpooledPartition::Memory {
+            batches,
+            reservation,
+        }),
+    }
+}
+
+/// Reads the results of a [`Spool`], which are computed once for all the
+/// shared scans of the spool.
+///
+/// This is used to compute a common table expression referenced several times
+/// in a query only once. Each reference scans the same spool, projecting the
+/// columns it needs. All the scans of a spool executed together must have
+/// equivalent inputs, only the input of the first one executed is computed.
+#[derive(Debug, Clone)]
+pub struct SharedScanExec {
+    /// The spool sharing the results
+    spool: Arc<Spool>,
+    /// The plan computing the results
+    input: Arc<dyn ExecutionPlan>,
+    /// Optional projection of the spooled columns
+    projection: Option<Vec<usize>>,
+    /// The schema of the output
+    schema: SchemaRef,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl SharedScanExec {
+    /// Create a new scan of `spool` computing its results with `input`,
+    /// optionally projecting its columns
+    pub fn try_new(
+        spool: Arc<Spool>,
+        input: Arc<dyn ExecutionPlan>,
+        projection: Option<Vec<usize>>,
+    ) -> Result<Self> {
+        let input_schema = input.schema();
+        let schema = match &projection {
+            Some(projection) => Arc::new(input_schema.project(projection)?),
+            None => input_schema,
+        };
+        let cache = Self::compute_properties(
+            Arc::clone(&schema),
+            input.output_partitioning().partition_count(),
+        );
+        Ok(Self {
+            spool,
+            input,
+            projection,
+            schema,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        })
+    }
+
+    /// The spool sharing the results
+    pub fn spool(&self) -> &Arc<Spool> {
+        &self.spool
+    }
+
+    /// The plan computing the results
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// Optional projection of the spooled columns
+    pub fn projection(&self) -> Option<&Vec<usize>> {
+        self.projection.as_ref()
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(schema: SchemaRef, partitions: usize) -> PlanProperties {
+        PlanProperties::new(
+            EquivalenceProperties::new(schema),
+            Partitioning::UnknownPartitioning(partitions),
+            ExecutionMode::Bounded,
+        )
+    }
+}
+
+impl DisplayAs for SharedScanExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "SharedScanExec: name={}", self.spool.name)?;
+                if let Some(projection) = &self.projection {
+                    let input_schema = self.input.schema();
+                    let columns = projection
+                        .iter()
+                        .map(|i| input_schema.field(*i).name().as_str())
+                        .collect::<Vec<_>>();
+                    write!(f, ", projection=[{}]", columns.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for SharedScanExec {
+    fn name(&self) -> &'static str {
+        "SharedScanExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    /// The input is not a child: only the input of one of the scans of the
+    /// spool is executed, and the results of all of them must be the same
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let mut partitions = self.spool.partitions(&self.input, &context);
+        let input_schema = self.input.schema();
+        let projection = self.projection.clone();
+        let stream = futures::future::poll_fn(move |cx| partitions.get_shared(cx))
+            .map_ok(move |partitions| {
+                let batches = match &partitions[partition] {
+                    SpooledPartition::Memory { batches, .. } => {
+                        stream::iter(batches.clone().into_iter().map(Ok)).boxed()
+                    }
+                    SpooledPartition::Spilled(file) => {
+                        let mut builder = RecordBatchReceiverStream::builder(
+                            Arc::clone(&input_schema),
+                            2,
+                        );
+                        let sender = builder.tx();
+                        let file = Arc::clone(file);
+                        builder.spawn_blocking(move || read_spill(sender, file.path()));
+                        builder.build().boxed()
+                    }
+                };
+                let projection = projection.clone();
+                batches.map(move |batch| match &projection {
+                    Some(projection) => Ok(batch?.project(projection)?),
+                    None => batch,
+                })
+            })
+            .try_flatten_stream();
+
+        let stream = RecordBatchStreamAdapter::new(Arc::clone(&self.schema), stream);
+        let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+        Ok(Box::pin(ObservedStream::new(
+            Box::pin(stream),
+            baseline_metrics,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        let mut metrics = self.metrics.clone_inner();
+        for metric in self.spool.metrics.clone_inner().iter() {
+            metrics.push(Arc::clone(metric));
+        }
+        Some(metrics)
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        let mut statistics = self.input.statistics()?;
+        if let Some(projection) = &self.projection {
+            statistics.column_statistics = projection
+                .iter()
+                .map(|i| statistics.column_statistics[*i].clone())
+                .collect();
+            statistics.total_byte_size = Precision::Absent;
+        }
+        Ok(statistics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect;
+    use crate::memory::MemoryExec;
+
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_execution::runtime_env::RuntimeEnvBuilder;
+
+    fn spool_input(partitions: usize, batches: usize) -> Arc<dyn ExecutionPlan> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]));
+        let partitions = (0..partitions)
+            .map(|partition| {
+                (0..batches)
+                    .map(|i| {
+                        let start = (partition * batches + i) as i32 * 100;
+                        RecordBatch::try_new(
+                            Arc::clone(&schema),
+                            vec![
+                                Arc::new(Int32Array::from_iter_values(
+                                    start..start + 100,
+                                )),
+                                Arc::new(Int32Array::from_iter_values(
+                                    (start..start + 100).map(|v| v * 2),
+                                )),
+                            ],
+                        )
+                        .unwrap()
+                    })
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        Arc::new(MemoryExec::try_new(&partitions, schema, None).unwrap())
+    }
+
+    fn rows(batches: &[RecordBatch]) -> usize {
+        batches.iter().map(|batch| batch.num_rows()).sum()
+    }
+
+    #[tokio::test]
+    async fn shared_scans_read_the_same_results() -> Result<()> {
+        let spool = Arc::new(Spool::new("cte"));
+        let input = spool_input(2, 3);
+        let scan = SharedScanExec::try_new(Arc::clone(&spool), Arc::clone(&input), None)?;
+        let projected =
+            SharedScanExec::try_new(Arc::clone(&spool), input, Some(vec![1]))?;
+        assert_eq!(projected.schema().fields().len(), 1);
+        assert_eq!(projected.schema().field(0).name(), "b");
+
+        let context = Arc::new(TaskContext::default());
+        let results = collect(Arc::new(scan), Arc::clone(&context)).await?;
+        let projected_results = collect(Arc::new(projected), context).await?;
+        assert_eq!(rows(&results), 600);
+        assert_eq!(rows(&projected_results), 600);
+        for (batch, projected) in results.iter().zip(&projected_results) {
+            assert_eq!(batch.column(1), projected.column(0));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn shared_scan_spills() -> Result<()> {
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(2000, 1.0)
+            .build_arc()?;
+        let context = Arc::new(TaskContext::default().with_runtime(runtime));
+
+        let spool = Arc::new(Spool::new("cte"));
+        let scan = Arc::new(SharedScanExec::try_new(spool, spool_input(1, 10), None)?);
+        let results = collect(Arc::clone(&scan) as _, Arc::clone(&context)).await?;
+        assert_eq!(rows(&results), 1000);
+        let values = results
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, (0..1000).collect::<Vec<_>>());
+
+        let metrics = scan.metrics().unwrap();
+        assert_eq!(metrics.spill_count(), Some(1));
+        assert_eq!(metrics.spilled_rows(), Some(1000));
+        assert_eq!(metrics.output_rows(), Some(1000));
+        assert_eq!(context.memory_pool().reserved(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn shared_scan_fails_without_disk_manager() -> Result<()> {
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(2000, 1.0)
+            .with_disk_manager(
+                datafusion_execution::disk_manager::DiskManagerConfig::Disabled,
+            )
+            .build_arc()?;
+        let context = Arc::new(TaskContext::default().with_runtime(runtime));
+
+        let spool = Arc::new(Spool::new("cte"));
+        let scan = Arc::new(SharedScanExec::try_new(spool, spool_input(1, 10), None)?);
+        let err = collect(scan, context).await.unwrap_err();
+        assert!(
+            err.to_string().contains("Resources exhausted"),
+            "unexpected error: {err}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn new_context_recomputes_results() -> Result<()> {
+        let spool = Arc::new(Spool::new("cte"));
+        let scan: Arc<dyn ExecutionPlan> = Arc::new(SharedScanExec::try_new(
+            Arc::clone(&spool),
+            spool_input(1, 1),
+            None,
+        )?);
+        let materialized_contexts = || {
+            let materializations = spool.materializations.lock().unwrap();
+            materializations
+                .iter()
+                .map(|m| m.context.as_ptr())
+                .collect::<Vec<_>>()
+        };
+
+        let context = Arc::new(TaskContext::default());
+        collect(Arc::clone(&scan), Arc::clone(&context)).await?;
+        collect(Arc::clone(&scan), Arc::clone(&context)).await?;
+        assert_eq!(materialized_contexts(), vec![Arc::as_ptr(&context)]);
+
+        // a concurrent execution does not evict the results of the first one
+        let other_context = Arc::new(TaskContext::default());
+        let results = collect(Arc::clone(&scan), Arc::clone(&other_context)).await?;
+        assert_eq!(rows(&results), 100);
+        assert_eq!(
+            materialized_contexts(),
+            vec![Arc::as_ptr(&context), Arc::as_ptr(&other_context)]
+        );
+
+        // the results of a finished execution are released
+        drop(context);
+        let last_context = Arc::new(TaskContext::default());
+        collect(scan, Arc::clone(&last_context)).await?;
+        assert_eq!(
+            materialized_contexts(),
+            vec![Arc::as_ptr(&other_context), Arc::as_ptr(&last_context)]
+        );
+        Ok(())
+    }
+}
@@ -671,18 +671,24 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Exact(ScalarValue::Float32(Some(1.1))),
                     min_value: Precision::Exact(ScalarValue::Float32(Some(0.1))),
                     null_count: Precision::Absent,
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
             ],
         };
@@ -696,18 +702,24 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(34))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(1))),
                     null_count: Precision::Exact(1),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Exact(ScalarValue::from("c")),
                     min_value: Precision::Exact(ScalarValue::from("b")),
                     null_count: Precision::Absent,
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Absent,
                     min_value: Precision::Absent,
                     null_count: Precision::Absent,
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
             ],
         };
@@ -722,18 +734,24 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(34))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(1),
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Absent,
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Absent,
                     min_value: Precision::Absent,
                     null_count: Precision::Absent,
+                    histogram: Precision::Absent,
+                    most_common_values: Precision::Absent,
                 },
             ],
         };
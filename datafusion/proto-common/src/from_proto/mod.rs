@@ -17,6 +17,7 @@
 
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
+use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::common::proto_error;
@@ -699,6 +700,8 @@ impl From<&protobuf::ColumnStats> for ColumnStatistics {
             } else {
                 Precision::Absent
             },
+            histogram: Precision::Absent,
+            most_common_values: Precision::Absent,
         }
     }
 }
@@ -869,8 +872,13 @@ impl TryFrom<&protobuf::CsvOptions> for CsvOptions {
             newlines_in_values: proto_opts.newlines_in_values.first().map(|h| *h != 0),
             compression: proto_opts.compression().into(),
             schema_infer_max_rec: proto_opts.schema_infer_max_rec as usize,
+            schema_infer_strategy: parse_or_default(&proto_opts.schema_infer_strategy)?,
+            schema_infer_sample_ranges: proto_opts.schema_infer_sample_ranges as usize,
+            schema_infer_decimal: proto_opts.schema_infer_decimal,
             date_format: (!proto_opts.date_format.is_empty())
                 .then(|| proto_opts.date_format.clone()),
+            delimiter_sequence: (!proto_opts.delimiter_sequence.is_empty())
+                .then(|| proto_opts.delimiter_sequence.clone()),
             datetime_format: (!proto_opts.datetime_format.is_empty())
                 .then(|| proto_opts.datetime_format.clone()),
             timestamp_format: (!proto_opts.timestamp_format.is_empty())
@@ -882,6 +890,10 @@ impl TryFrom<&protobuf::CsvOptions> for CsvOptions {
             null_value: (!proto_opts.null_value.is_empty())
                 .then(|| proto_opts.null_value.clone()),
             comment: proto_opts.comment.first().copied(),
+            on_error: parse_or_default(&proto_opts.on_error)?,
+            max_errors: proto_opts.max_errors.map(|n| n as usize),
+            bad_records_path: (!proto_opts.bad_records_path.is_empty())
+                .then(|| proto_opts.bad_records_path.clone()),
         })
     }
 }
@@ -959,6 +971,7 @@ impl TryFrom<&protobuf::ParquetOptions> for ParquetOptions {
             maximum_buffered_record_batches_per_stream: value.maximum_buffered_record_batches_per_stream as usize,
             schema_force_view_types: value.schema_force_view_types,
             binary_as_string: value.binary_as_string,
+            adaptive_filters: value.adaptive_filters,
         })
     }
 }
@@ -1050,10 +1063,28 @@ impl TryFrom<&protobuf::JsonOptions> for JsonOptions {
         Ok(JsonOptions {
             compression: compression.into(),
             schema_infer_max_rec: proto_opts.schema_infer_max_rec as usize,
+            schema_infer_strategy: parse_or_default(&proto_opts.schema_infer_strategy)?,
+            schema_infer_sample_ranges: proto_opts.schema_infer_sample_ranges as usize,
+            on_error: parse_or_default(&proto_opts.on_error)?,
+            max_errors: proto_opts.max_errors.map(|n| n as usize),
+            bad_records_path: (!proto_opts.bad_records_path.is_empty())
+                .then(|| proto_opts.bad_records_path.clone()),
         })
     }
 }
 
+/// Parses an option such as an [`OnError`] mode, empty for the default value
+fn parse_or_default<T>(value: &str) -> datafusion_common::Result<T>
+where
+    T: FromStr + Default,
+    DataFusionError: From<T::Err>,
+{
+    if value.is_empty() {
+        return Ok(T::default());
+    }
+    Ok(value.parse()?)
+}
+
 pub fn parse_i32_to_time_unit(value: &i32) -> datafusion_common::Result<TimeUnit, Error> {
     protobuf::TimeUnit::try_from(*value)
         .map(|t| t.into())
@@ -1545,6 +1545,27 @@ impl serde::Serialize for CsvOptions {
         if !self.terminator.is_empty() {
             len += 1;
         }
+        if !self.on_error.is_empty() {
+            len += 1;
+        }
+        if self.max_errors.is_some() {
+            len += 1;
+        }
+        if !self.bad_records_path.is_empty() {
+            len += 1;
+        }
+        if !self.schema_infer_strategy.is_empty() {
+            len += 1;
+        }
+        if self.schema_infer_sample_ranges != 0 {
+            len += 1;
+        }
+        if self.schema_infer_decimal {
+            len += 1;
+        }
+        if !self.delimiter_sequence.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion_common.CsvOptions", len)?;
         if !self.has_header.is_empty() {
             #[allow(clippy::needless_borrow)]
@@ -1614,6 +1635,31 @@ impl serde::Serialize for CsvOptions {
             #[allow(clippy::needless_borrows_for_generic_args)]
             struct_ser.serialize_field("terminator", pbjson::private::base64::encode(&self.terminator).as_str())?;
         }
+        if !self.on_error.is_empty() {
+            struct_ser.serialize_field("onError", &self.on_error)?;
+        }
+        if let Some(v) = self.max_errors.as_ref() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("maxErrors", ToString::to_string(&v).as_str())?;
+        }
+        if !self.bad_records_path.is_empty() {
+            struct_ser.serialize_field("badRecordsPath", &self.bad_records_path)?;
+        }
+        if !self.schema_infer_strategy.is_empty() {
+            struct_ser.serialize_field("schemaInferStrategy", &self.schema_infer_strategy)?;
+        }
+        if self.schema_infer_sample_ranges != 0 {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("schemaInferSampleRanges", ToString::to_string(&self.schema_infer_sample_ranges).as_str())?;
+        }
+        if self.schema_infer_decimal {
+            struct_ser.serialize_field("schemaInferDecimal", &self.schema_infer_decimal)?;
+        }
+        if !self.delimiter_sequence.is_empty() {
+            struct_ser.serialize_field("delimiterSequence", &self.delimiter_sequence)?;
+        }
         struct_ser.end()
     }
 }
@@ -1650,6 +1696,20 @@ impl<'de> serde::Deserialize<'de> for CsvOptions {
             "newlines_in_values",
             "newlinesInValues",
             "terminator",
+            "on_error",
+            "onError",
+            "max_errors",
+            "maxErrors",
+            "bad_records_path",
+            "badRecordsPath",
+            "schema_infer_strategy",
+            "schemaInferStrategy",
+            "schema_infer_sample_ranges",
+            "schemaInferSampleRanges",
+            "schema_infer_decimal",
+            "schemaInferDecimal",
+            "delimiter_sequence",
+            "delimiterSequence",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -1670,6 +1730,13 @@ impl<'de> serde::Deserialize<'de> for CsvOptions {
             DoubleQuote,
             NewlinesInValues,
             Terminator,
+            OnError,
+            MaxErrors,
+            BadRecordsPath,
+            SchemaInferStrategy,
+            SchemaInferSampleRanges,
+            SchemaInferDecimal,
+            DelimiterSequence,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1707,6 +1774,13 @@ impl<'de> serde::Deserialize<'de> for CsvOptions {
                             "doubleQuote" | "double_quote" => Ok(GeneratedField::DoubleQuote),
                             "newlinesInValues" | "newlines_in_values" => Ok(GeneratedField::NewlinesInValues),
                             "terminator" => Ok(GeneratedField::Terminator),
+                            "onError" | "on_error" => Ok(GeneratedField::OnError),
+                            "maxErrors" | "max_errors" => Ok(GeneratedField::MaxErrors),
+                            "badRecordsPath" | "bad_records_path" => Ok(GeneratedField::BadRecordsPath),
+                            "schemaInferStrategy" | "schema_infer_strategy" => Ok(GeneratedField::SchemaInferStrategy),
+                            "schemaInferSampleRanges" | "schema_infer_sample_ranges" => Ok(GeneratedField::SchemaInferSampleRanges),
+                            "schemaInferDecimal" | "schema_infer_decimal" => Ok(GeneratedField::SchemaInferDecimal),
+                            "delimiterSequence" | "delimiter_sequence" => Ok(GeneratedField::DelimiterSequence),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1742,6 +1816,13 @@ impl<'de> serde::Deserialize<'de> for CsvOptions {
                 let mut double_quote__ = None;
                 let mut newlines_in_values__ = None;
                 let mut terminator__ = None;
+                let mut on_error__ = None;
+                let mut max_errors__ = None;
+                let mut bad_records_path__ = None;
+                let mut schema_infer_strategy__ = None;
+                let mut schema_infer_sample_ranges__ = None;
+                let mut schema_infer_decimal__ = None;
+                let mut delimiter_sequence__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::HasHeader => {
@@ -1858,6 +1939,52 @@ impl<'de> serde::Deserialize<'de> for CsvOptions {
                                 Some(map_.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
                             ;
                         }
+                        GeneratedField::OnError => {
+                            if on_error__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("onError"));
+                            }
+                            on_error__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::MaxErrors => {
+                            if max_errors__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("maxErrors"));
+                            }
+                            max_errors__ = 
+                                map_.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::BadRecordsPath => {
+                            if bad_records_path__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("badRecordsPath"));
+                            }
+                            bad_records_path__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::SchemaInferStrategy => {
+                            if schema_infer_strategy__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("schemaInferStrategy"));
+                            }
+                            schema_infer_strategy__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::SchemaInferSampleRanges => {
+                            if schema_infer_sample_ranges__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("schemaInferSampleRanges"));
+                            }
+                            schema_infer_sample_ranges__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::SchemaInferDecimal => {
+                            if schema_infer_decimal__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("schemaInferDecimal"));
+                            }
+                            schema_infer_decimal__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::DelimiterSequence => {
+                            if delimiter_sequence__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("delimiterSequence"));
+                            }
+                            delimiter_sequence__ = Some(map_.next_value()?);
+                        }
                     }
                 }
                 Ok(CsvOptions {
@@ -1877,6 +2004,13 @@ impl<'de> serde::Deserialize<'de> for CsvOptions {
                     double_quote: double_quote__.unwrap_or_default(),
                     newlines_in_values: newlines_in_values__.unwrap_or_default(),
                     terminator: terminator__.unwrap_or_default(),
+                    on_error: on_error__.unwrap_or_default(),
+                    max_errors: max_errors__,
+                    bad_records_path: bad_records_path__.unwrap_or_default(),
+                    schema_infer_strategy: schema_infer_strategy__.unwrap_or_default(),
+                    schema_infer_sample_ranges: schema_infer_sample_ranges__.unwrap_or_default(),
+                    schema_infer_decimal: schema_infer_decimal__.unwrap_or_default(),
+                    delimiter_sequence: delimiter_sequence__.unwrap_or_default(),
                 })
             }
         }
@@ -3926,6 +4060,21 @@ impl serde::Serialize for JsonOptions {
         if self.schema_infer_max_rec != 0 {
             len += 1;
         }
+        if !self.on_error.is_empty() {
+            len += 1;
+        }
+        if self.max_errors.is_some() {
+            len += 1;
+        }
+        if !self.bad_records_path.is_empty() {
+            len += 1;
+        }
+        if !self.schema_infer_strategy.is_empty() {
+            len += 1;
+        }
+        if self.schema_infer_sample_ranges != 0 {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion_common.JsonOptions", len)?;
         if self.compression != 0 {
             let v = CompressionTypeVariant::try_from(self.compression)
@@ -3937,6 +4086,25 @@ impl serde::Serialize for JsonOptions {
             #[allow(clippy::needless_borrows_for_generic_args)]
             struct_ser.serialize_field("schemaInferMaxRec", ToString::to_string(&self.schema_infer_max_rec).as_str())?;
         }
+        if !self.on_error.is_empty() {
+            struct_ser.serialize_field("onError", &self.on_error)?;
+        }
+        if let Some(v) = self.max_errors.as_ref() {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("maxErrors", ToString::to_string(&v).as_str())?;
+        }
+        if !self.bad_records_path.is_empty() {
+            struct_ser.serialize_field("badRecordsPath", &self.bad_records_path)?;
+        }
+        if !self.schema_infer_strategy.is_empty() {
+            struct_ser.serialize_field("schemaInferStrategy", &self.schema_infer_strategy)?;
+        }
+        if self.schema_infer_sample_ranges != 0 {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("schemaInferSampleRanges", ToString::to_string(&self.schema_infer_sample_ranges).as_str())?;
+        }
         struct_ser.end()
     }
 }
@@ -3950,12 +4118,27 @@ impl<'de> serde::Deserialize<'de> for JsonOptions {
             "compression",
             "schema_infer_max_rec",
             "schemaInferMaxRec",
+            "on_error",
+            "onError",
+            "max_errors",
+            "maxErrors",
+            "bad_records_path",
+            "badRecordsPath",
+            "schema_infer_strategy",
+            "schemaInferStrategy",
+            "schema_infer_sample_ranges",
+            "schemaInferSampleRanges",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             Compression,
             SchemaInferMaxRec,
+            OnError,
+            MaxErrors,
+            BadRecordsPath,
+            SchemaInferStrategy,
+            SchemaInferSampleRanges,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -3979,6 +4162,11 @@ impl<'de> serde::Deserialize<'de> for JsonOptions {
                         match value {
                             "compression" => Ok(GeneratedField::Compression),
                             "schemaInferMaxRec" | "schema_infer_max_rec" => Ok(GeneratedField::SchemaInferMaxRec),
+                            "onError" | "on_error" => Ok(GeneratedField::OnError),
+                            "maxErrors" | "max_errors" => Ok(GeneratedField::MaxErrors),
+                            "badRecordsPath" | "bad_records_path" => Ok(GeneratedField::BadRecordsPath),
+                            "schemaInferStrategy" | "schema_infer_strategy" => Ok(GeneratedField::SchemaInferStrategy),
+                            "schemaInferSampleRanges" | "schema_infer_sample_ranges" => Ok(GeneratedField::SchemaInferSampleRanges),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -4000,6 +4188,11 @@ impl<'de> serde::Deserialize<'de> for JsonOptions {
             {
                 let mut compression__ = None;
                 let mut schema_infer_max_rec__ = None;
+                let mut on_error__ = None;
+                let mut max_errors__ = None;
+                let mut bad_records_path__ = None;
+                let mut schema_infer_strategy__ = None;
+                let mut schema_infer_sample_ranges__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::Compression => {
@@ -4016,11 +4209,50 @@ impl<'de> serde::Deserialize<'de> for JsonOptions {
                                 Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
                             ;
                         }
+                        GeneratedField::OnError => {
+                            if on_error__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("onError"));
+                            }
+                            on_error__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::MaxErrors => {
+                            if max_errors__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("maxErrors"));
+                            }
+                            max_errors__ = 
+                                map_.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::BadRecordsPath => {
+                            if bad_records_path__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("badRecordsPath"));
+                            }
+                            bad_records_path__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::SchemaInferStrategy => {
+                            if schema_infer_strategy__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("schemaInferStrategy"));
+                            }
+                            schema_infer_strategy__ = Some(map_.next_value()?);
+                        }
+                        GeneratedField::SchemaInferSampleRanges => {
+                            if schema_infer_sample_ranges__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("schemaInferSampleRanges"));
+                            }
+                            schema_infer_sample_ranges__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
                     }
                 }
                 Ok(JsonOptions {
                     compression: compression__.unwrap_or_default(),
                     schema_infer_max_rec: schema_infer_max_rec__.unwrap_or_default(),
+                    on_error: on_error__.unwrap_or_default(),
+                    max_errors: max_errors__,
+                    bad_records_path: bad_records_path__.unwrap_or_default(),
+                    schema_infer_strategy: schema_infer_strategy__.unwrap_or_default(),
+                    schema_infer_sample_ranges: schema_infer_sample_ranges__.unwrap_or_default(),
                 })
             }
         }
@@ -4916,6 +5148,9 @@ impl serde::Serialize for ParquetOptions {
         if self.binary_as_string {
             len += 1;
         }
+        if self.adaptive_filters {
+            len += 1;
+        }
         if self.dictionary_page_size_limit != 0 {
             len += 1;
         }
@@ -5009,6 +5244,9 @@ impl serde::Serialize for ParquetOptions {
         if self.binary_as_string {
             struct_ser.serialize_field("binaryAsString", &self.binary_as_string)?;
         }
+        if self.adaptive_filters {
+            struct_ser.serialize_field("adaptiveFilters", &self.adaptive_filters)?;
+        }
         if self.dictionary_page_size_limit != 0 {
             #[allow(clippy::needless_borrow)]
             #[allow(clippy::needless_borrows_for_generic_args)]
@@ -5137,6 +5375,8 @@ impl<'de> serde::Deserialize<'de> for ParquetOptions {
             "schemaForceViewTypes",
             "binary_as_string",
             "binaryAsString",
+            "adaptive_filters",
+            "adaptiveFilters",
             "dictionary_page_size_limit",
             "dictionaryPageSizeLimit",
             "data_page_row_count_limit",
@@ -5180,6 +5420,7 @@ impl<'de> serde::Deserialize<'de> for ParquetOptions {
             BloomFilterOnWrite,
             SchemaForceViewTypes,
             BinaryAsString,
+            AdaptiveFilters,
             DictionaryPageSizeLimit,
             DataPageRowCountLimit,
             MaxRowGroupSize,
@@ -5229,6 +5470,7 @@ impl<'de> serde::Deserialize<'de> for ParquetOptions {
                             "bloomFilterOnWrite" | "bloom_filter_on_write" => Ok(GeneratedField::BloomFilterOnWrite),
                             "schemaForceViewTypes" | "schema_force_view_types" => Ok(GeneratedField::SchemaForceViewTypes),
                             "binaryAsString" | "binary_as_string" => Ok(GeneratedField::BinaryAsString),
+                            "adaptiveFilters" | "adaptive_filters" => Ok(GeneratedField::AdaptiveFilters),
                             "dictionaryPageSizeLimit" | "dictionary_page_size_limit" => Ok(GeneratedField::DictionaryPageSizeLimit),
                             "dataPageRowCountLimit" | "data_page_row_count_limit" => Ok(GeneratedField::DataPageRowCountLimit),
                             "maxRowGroupSize" | "max_row_group_size" => Ok(GeneratedField::MaxRowGroupSize),
@@ -5276,6 +5518,7 @@ impl<'de> serde::Deserialize<'de> for ParquetOptions {
                 let mut bloom_filter_on_write__ = None;
                 let mut schema_force_view_types__ = None;
                 let mut binary_as_string__ = None;
+                let mut adaptive_filters__ = None;
                 let mut dictionary_page_size_limit__ = None;
                 let mut data_page_row_count_limit__ = None;
                 let mut max_row_group_size__ = None;
@@ -5389,6 +5632,12 @@ impl<'de> serde::Deserialize<'de> for ParquetOptions {
                             }
                             binary_as_string__ = Some(map_.next_value()?);
                         }
+                        GeneratedField::AdaptiveFilters => {
+                            if adaptive_filters__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("adaptiveFilters"));
+                            }
+                            adaptive_filters__ = Some(map_.next_value()?);
+                        }
                         GeneratedField::DictionaryPageSizeLimit => {
                             if dictionary_page_size_limit__.is_some() {
                                 return Err(serde::de::Error::duplicate_field("dictionaryPageSizeLimit"));
@@ -5491,6 +5740,7 @@ impl<'de> serde::Deserialize<'de> for ParquetOptions {
                     bloom_filter_on_write: bloom_filter_on_write__.unwrap_or_default(),
                     schema_force_view_types: schema_force_view_types__.unwrap_or_default(),
                     binary_as_string: binary_as_string__.unwrap_or_default(),
+                    adaptive_filters: adaptive_filters__.unwrap_or_default(),
                     dictionary_page_size_limit: dictionary_page_size_limit__.unwrap_or_default(),
                     data_page_row_count_limit: data_page_row_count_limit__.unwrap_or_default(),
                     max_row_group_size: max_row_group_size__.unwrap_or_default(),
@@ -40,7 +40,7 @@ pub struct ParquetFormat {
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct AvroFormat {}
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NdJsonFormat {
     #[prost(message, optional, tag = "1")]
     pub options: ::core::option::Option<JsonOptions>,
@@ -605,9 +605,30 @@ pub struct CsvOptions {
     /// Optional terminator character as a byte
     #[prost(bytes = "vec", tag = "16")]
     pub terminator: ::prost::alloc::vec::Vec<u8>,
+    /// How records which fail to parse are handled
+    #[prost(string, tag = "17")]
+    pub on_error: ::prost::alloc::string::String,
+    /// Optional maximum number of records which fail to parse
+    #[prost(uint64, optional, tag = "18")]
+    pub max_errors: ::core::option::Option<u64>,
+    /// Optional path of the file recording records which fail to parse
+    #[prost(string, tag = "19")]
+    pub bad_records_path: ::prost::alloc::string::String,
+    /// Strategy used to sample records for schema inference
+    #[prost(string, tag = "20")]
+    pub schema_infer_strategy: ::prost::alloc::string::String,
+    /// Number of byte ranges sampled per file for schema inference
+    #[prost(uint64, tag = "21")]
+    pub schema_infer_sample_ranges: u64,
+    /// Indicates if decimals are inferred for fractional numbers
+    #[prost(bool, tag = "22")]
+    pub schema_infer_decimal: bool,
+    /// Optional delimiter of multiple bytes
+    #[prost(string, tag = "23")]
+    pub delimiter_sequence: ::prost::alloc::string::String,
 }
 /// Options controlling CSV format
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct JsonOptions {
     /// Compression type
     #[prost(enumeration = "CompressionTypeVariant", tag = "1")]
@@ -615,6 +636,21 @@ pub struct JsonOptions {
     /// Max records for schema inference
     #[prost(uint64, tag = "2")]
     pub schema_infer_max_rec: u64,
+    /// How records which fail to parse are handled
+    #[prost(string, tag = "3")]
+    pub on_error: ::prost::alloc::string::String,
+    /// Optional maximum number of records which fail to parse
+    #[prost(uint64, optional, tag = "4")]
+    pub max_errors: ::core::option::Option<u64>,
+    /// Optional path of the file recording records which fail to parse
+    #[prost(string, tag = "5")]
+    pub bad_records_path: ::prost::alloc::string::String,
+    /// Strategy used to sample records for schema inference
+    #[prost(string, tag = "6")]
+    pub schema_infer_strategy: ::prost::alloc::string::String,
+    /// Number of byte ranges sampled per file for schema inference
+    #[prost(uint64, tag = "7")]
+    pub schema_infer_sample_ranges: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TableParquetOptions {
@@ -760,6 +796,9 @@ pub struct ParquetOptions {
     /// default = false
     #[prost(bool, tag = "29")]
     pub binary_as_string: bool,
+    /// default = false
+    #[prost(bool, tag = "30")]
+    pub adaptive_filters: bool,
     #[prost(uint64, tag = "12")]
     pub dictionary_page_size_limit: u64,
     #[prost(uint64, tag = "18")]
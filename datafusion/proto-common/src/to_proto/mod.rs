@@ -832,6 +832,7 @@ impl TryFrom<&ParquetOptions> for protobuf::ParquetOptions {
             maximum_buffered_record_batches_per_stream: value.maximum_buffered_record_batches_per_stream as u64,
             schema_force_view_types: value.schema_force_view_types,
             binary_as_string: value.binary_as_string,
+            adaptive_filters: value.adaptive_filters,
         })
     }
 }
@@ -921,13 +922,20 @@ impl TryFrom<&CsvOptions> for protobuf::CsvOptions {
                 .map_or_else(Vec::new, |h| vec![h as u8]),
             compression: compression.into(),
             schema_infer_max_rec: opts.schema_infer_max_rec as u64,
+            schema_infer_strategy: opts.schema_infer_strategy.to_string(),
+            schema_infer_sample_ranges: opts.schema_infer_sample_ranges as u64,
+            schema_infer_decimal: opts.schema_infer_decimal,
             date_format: opts.date_format.clone().unwrap_or_default(),
+            delimiter_sequence: opts.delimiter_sequence.clone().unwrap_or_default(),
             datetime_format: opts.datetime_format.clone().unwrap_or_default(),
             timestamp_format: opts.timestamp_format.clone().unwrap_or_default(),
             timestamp_tz_format: opts.timestamp_tz_format.clone().unwrap_or_default(),
             time_format: opts.time_format.clone().unwrap_or_default(),
             null_value: opts.null_value.clone().unwrap_or_default(),
             comment: opts.comment.map_or_else(Vec::new, |h| vec![h]),
+            on_error: opts.on_error.to_string(),
+            max_errors: opts.max_errors.map(|n| n as u64),
+            bad_records_path: opts.bad_records_path.clone().unwrap_or_default(),
         })
     }
 }
@@ -940,6 +948,11 @@ impl TryFrom<&JsonOptions> for protobuf::JsonOptions {
         Ok(protobuf::JsonOptions {
             compression: compression.into(),
             schema_infer_max_rec: opts.schema_infer_max_rec as u64,
+            schema_infer_strategy: opts.schema_infer_strategy.to_string(),
+            schema_infer_sample_ranges: opts.schema_infer_sample_ranges as u64,
+            on_error: opts.on_error.to_string(),
+            max_errors: opts.max_errors.map(|n| n as u64),
+            bad_records_path: opts.bad_records_path.clone().unwrap_or_default(),
         })
     }
 }
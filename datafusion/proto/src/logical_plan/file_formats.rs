@@ -58,7 +58,14 @@ impl CsvOptionsProto {
                 double_quote: options.double_quote.map_or(vec![], |v| vec![v as u8]),
                 compression: options.compression as i32,
                 schema_infer_max_rec: options.schema_infer_max_rec as u64,
+                schema_infer_strategy: options.schema_infer_strategy.to_string(),
+                schema_infer_sample_ranges: options.schema_infer_sample_ranges as u64,
+                schema_infer_decimal: options.schema_infer_decimal,
                 date_format: options.date_format.clone().unwrap_or_default(),
+                delimiter_sequence: options
+                    .delimiter_sequence
+                    .clone()
+                    .unwrap_or_default(),
                 datetime_format: options.datetime_format.clone().unwrap_or_default(),
                 timestamp_format: options.timestamp_format.clone().unwrap_or_default(),
                 timestamp_tz_format: options
@@ -71,6 +78,9 @@ impl CsvOptionsProto {
                 newlines_in_values: options
                     .newlines_in_values
                     .map_or(vec![], |v| vec![v as u8]),
+                on_error: options.on_error.to_string(),
+                max_errors: options.max_errors.map(|n| n as u64),
+                bad_records_path: options.bad_records_path.clone().unwrap_or_default(),
             }
         } else {
             CsvOptionsProto::default()
@@ -111,6 +121,17 @@ impl From<&CsvOptionsProto> for CsvOptions {
                 _ => CompressionTypeVariant::UNCOMPRESSED,
             },
             schema_infer_max_rec: proto.schema_infer_max_rec as usize,
+            schema_infer_strategy: proto
+                .schema_infer_strategy
+                .parse()
+                .unwrap_or_default(),
+            schema_infer_sample_ranges: proto.schema_infer_sample_ranges as usize,
+            schema_infer_decimal: proto.schema_infer_decimal,
+            delimiter_sequence: if proto.delimiter_sequence.is_empty() {
+                None
+            } else {
+                Some(proto.delimiter_sequence.clone())
+            },
             date_format: if proto.date_format.is_empty() {
                 None
             } else {
@@ -151,6 +172,13 @@ impl From<&CsvOptionsProto> for CsvOptions {
             } else {
                 Some(proto.newlines_in_values[0] != 0)
             },
+            on_error: proto.on_error.parse().unwrap_or_default(),
+            max_errors: proto.max_errors.map(|n| n as usize),
+            bad_records_path: if proto.bad_records_path.is_empty() {
+                None
+            } else {
+                Some(proto.bad_records_path.clone())
+            },
         }
     }
 }
@@ -240,6 +268,11 @@ impl JsonOptionsProto {
             JsonOptionsProto {
                 compression: options.compression as i32,
                 schema_infer_max_rec: options.schema_infer_max_rec as u64,
+                schema_infer_strategy: options.schema_infer_strategy.to_string(),
+                schema_infer_sample_ranges: options.schema_infer_sample_ranges as u64,
+                on_error: options.on_error.to_string(),
+                max_errors: options.max_errors.map(|n| n as u64),
+                bad_records_path: options.bad_records_path.clone().unwrap_or_default(),
             }
         } else {
             JsonOptionsProto::default()
@@ -258,6 +291,15 @@ impl From<&JsonOptionsProto> for JsonOptions {
                 _ => CompressionTypeVariant::UNCOMPRESSED,
             },
             schema_infer_max_rec: proto.schema_infer_max_rec as usize,
+            schema_infer_strategy: proto
+                .schema_infer_strategy
+                .parse()
+                .unwrap_or_default(),
+            schema_infer_sample_ranges: proto.schema_infer_sample_ranges as usize,
+            on_error: proto.on_error.parse().unwrap_or_default(),
+            max_errors: proto.max_errors.map(|n| n as usize),
+            bad_records_path: (!proto.bad_records_path.is_empty())
+                .then(|| proto.bad_records_path.clone()),
         }
     }
 }
@@ -404,6 +446,7 @@ impl TableParquetOptionsProto {
                 maximum_buffered_record_batches_per_stream: global_options.global.maximum_buffered_record_batches_per_stream as u64,
                 schema_force_view_types: global_options.global.schema_force_view_types,
                 binary_as_string: global_options.global.binary_as_string,
+                adaptive_filters: global_options.global.adaptive_filters,
             }),
             column_specific_options: column_specific_options.into_iter().map(|(column_name, options)| {
                 ParquetColumnSpecificOptions {
@@ -495,6 +538,7 @@ impl From<&ParquetOptionsProto> for ParquetOptions {
             maximum_buffered_record_batches_per_stream: proto.maximum_buffered_record_batches_per_stream as usize,
             schema_force_view_types: proto.schema_force_view_types,
             binary_as_string: proto.binary_as_string,
+            adaptive_filters: proto.adaptive_filters,
         }
     }
 }
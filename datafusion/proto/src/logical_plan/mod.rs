@@ -397,7 +397,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                             }
                             Arc::new(json)
                         }
-                        FileFormatType::Avro(..) => Arc::new(AvroFormat),
+                        FileFormatType::Avro(..) => Arc::new(AvroFormat::default()),
                     };
 
                 let table_paths = &scan
@@ -1624,6 +1624,9 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlan::Ddl(DdlStatement::DropFunction(_)) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for DropFunction",
             )),
+            LogicalPlan::Ddl(DdlStatement::AnalyzeTable(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for AnalyzeTable",
+            )),
             LogicalPlan::Statement(_) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for Statement",
             )),
@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+use std::ops::ControlFlow;
 use std::sync::Arc;
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
@@ -25,13 +27,35 @@ use datafusion_common::{
     tree_node::{TreeNode, TreeNodeRecursion},
     Result,
 };
-use datafusion_expr::{LogicalPlan, LogicalPlanBuilder, TableSource};
-use sqlparser::ast::{Query, SetExpr, SetOperator, With};
+use datafusion_expr::{Expr, LogicalPlan, LogicalPlanBuilder, TableSource};
+use sqlparser::ast::{
+    visit_relations, Cte, CteAsMaterialized, Query, SetExpr, SetOperator, With,
+};
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
+    /// Counts the references to each table name in a query and its `WITH`
+    /// clause, used to decide which CTEs are worth materializing
+    pub(super) fn cte_references(
+        &self,
+        with: &With,
+        query: &Query,
+    ) -> HashMap<String, usize> {
+        let mut references = HashMap::new();
+        let mut count = |name: &sqlparser::ast::ObjectName| {
+            if let Ok(table_ref) = self.object_name_to_table_reference(name.clone()) {
+                *references.entry(table_ref.to_string()).or_insert(0) += 1;
+            }
+            ControlFlow::<()>::Continue(())
+        };
+        let _ = visit_relations(with, &mut count);
+        let _ = visit_relations(query, &mut count);
+        references
+    }
+
     pub(super) fn plan_with_clause(
         &self,
         with: With,
+        cte_references: &HashMap<String, usize>,
         planner_context: &mut PlannerContext,
     ) -> Result<()> {
         let is_recursive = with.recursive;
@@ -45,6 +69,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 );
             }
 
+            let materialize = self.should_materialize(
+                &cte,
+                cte_references.get(&cte_name).copied().unwrap_or(0),
+            );
+
             // Create a logical plan for the CTE
             let cte_plan = if is_recursive {
                 self.recursive_cte(cte_name.clone(), *cte.query, planner_context)?
@@ -54,13 +83,34 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
             // Each `WITH` block can change the column names in the last
             // projection (e.g. "WITH table(t1, t2) AS SELECT 1, 2").
-            let final_plan = self.apply_table_alias(cte_plan, cte.alias)?;
+            let mut final_plan = self.apply_table_alias(cte_plan, cte.alias)?;
+            if materialize && can_materialize(&final_plan)? {
+                // Compute the CTE once and scan its results for each reference
+                let source = self
+                    .context_provider
+                    .create_materialized_cte(&cte_name, final_plan)?;
+                final_plan =
+                    LogicalPlanBuilder::scan(cte_name.clone(), source, None)?.build()?;
+            }
             // Export the CTE to the outer query
             planner_context.insert_cte(cte_name, final_plan);
         }
         Ok(())
     }
 
+    /// Returns true if the CTE should be computed once for all its
+    /// `references`, instead of being inlined in each of them
+    fn should_materialize(&self, cte: &Cte, references: usize) -> bool {
+        match cte.materialized {
+            Some(CteAsMaterialized::Materialized) => true,
+            Some(CteAsMaterialized::NotMaterialized) => false,
+            None => {
+                self.context_provider.options().execution.materialize_ctes
+                    && references > 1
+            }
+        }
+    }
+
     fn non_recursive_cte(
         &self,
         cte_query: Query,
@@ -205,3 +255,29 @@ fn has_work_table_reference(
     .unwrap();
     has_reference
 }
+
+/// Returns true if the results of the CTE plan do not depend on the query it
+/// is referenced from, so that they can be computed once and shared.
+///
+/// Recursive CTEs are computed by a work table of their own, and plans with
+/// outer references or placeholders depend on the values of their referencing
+/// query or statement.
+fn can_materialize(plan: &LogicalPlan) -> Result<bool> {
+    let mut independent = true;
+    plan.apply_with_subqueries(|plan| {
+        if matches!(plan, LogicalPlan::RecursiveQuery(_)) {
+            independent = false;
+            return Ok(TreeNodeRecursion::Stop);
+        }
+        plan.apply_expressions(|expr| {
+            expr.apply(|expr| {
+                if matches!(expr, Expr::OuterReferenceColumn(..) | Expr::Placeholder(_)) {
+                    independent = false;
+                    return Ok(TreeNodeRecursion::Stop);
+                }
+                Ok(TreeNodeRecursion::Continue)
+            })
+        })
+    })?;
+    Ok(independent)
+}
@@ -254,6 +254,19 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             return Ok(Expr::Unnest(Unnest::new(expr)));
         }
 
+        // regexp_split_to_table(...) is planned as unnest(regexp_split_to_array(...))
+        if name.eq("regexp_split_to_table") {
+            if let Some(fm) = self
+                .context_provider
+                .get_function_meta("regexp_split_to_array")
+            {
+                let args = self.function_args_to_expr(args, schema, planner_context)?;
+                return Ok(Expr::Unnest(Unnest::new(Expr::ScalarFunction(
+                    ScalarFunction::new_udf(fm, args),
+                ))));
+            }
+        }
+
         if !order_by.is_empty() && is_function_window {
             return plan_err!(
                 "Aggregate ORDER BY is not implemented for window functions"
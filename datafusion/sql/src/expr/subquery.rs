@@ -16,10 +16,11 @@
 // under the License.
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use datafusion_common::{DFSchema, Result};
+use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
+use datafusion_common::{DFSchema, DFSchemaRef, Result};
 use datafusion_expr::expr::Exists;
 use datafusion_expr::expr::InSubquery;
-use datafusion_expr::{Expr, Subquery};
+use datafusion_expr::{Expr, LogicalPlan, Subquery};
 use sqlparser::ast::Expr as SQLExpr;
 use sqlparser::ast::Query;
 use std::sync::Arc;
@@ -32,10 +33,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         input_schema: &DFSchema,
         planner_context: &mut PlannerContext,
     ) -> Result<Expr> {
+        let outer_query_schema = outer_query_schema(input_schema, planner_context);
         let old_outer_query_schema =
-            planner_context.set_outer_query_schema(Some(input_schema.clone().into()));
+            planner_context.set_outer_query_schema(Some(Arc::clone(&outer_query_schema)));
         let sub_plan = self.query_to_plan(subquery, planner_context)?;
-        let outer_ref_columns = sub_plan.all_out_ref_exprs();
+        let outer_ref_columns = outer_ref_columns(&sub_plan, &outer_query_schema);
         planner_context.set_outer_query_schema(old_outer_query_schema);
         Ok(Expr::Exists(Exists {
             subquery: Subquery {
@@ -54,10 +56,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         input_schema: &DFSchema,
         planner_context: &mut PlannerContext,
     ) -> Result<Expr> {
+        let outer_query_schema = outer_query_schema(input_schema, planner_context);
         let old_outer_query_schema =
-            planner_context.set_outer_query_schema(Some(input_schema.clone().into()));
+            planner_context.set_outer_query_schema(Some(Arc::clone(&outer_query_schema)));
         let sub_plan = self.query_to_plan(subquery, planner_context)?;
-        let outer_ref_columns = sub_plan.all_out_ref_exprs();
+        let outer_ref_columns = outer_ref_columns(&sub_plan, &outer_query_schema);
         planner_context.set_outer_query_schema(old_outer_query_schema);
         let expr = Box::new(self.sql_to_expr(expr, input_schema, planner_context)?);
         Ok(Expr::InSubquery(InSubquery::new(
@@ -76,10 +79,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         input_schema: &DFSchema,
         planner_context: &mut PlannerContext,
     ) -> Result<Expr> {
+        let outer_query_schema = outer_query_schema(input_schema, planner_context);
         let old_outer_query_schema =
-            planner_context.set_outer_query_schema(Some(input_schema.clone().into()));
+            planner_context.set_outer_query_schema(Some(Arc::clone(&outer_query_schema)));
         let sub_plan = self.query_to_plan(subquery, planner_context)?;
-        let outer_ref_columns = sub_plan.all_out_ref_exprs();
+        let outer_ref_columns = outer_ref_columns(&sub_plan, &outer_query_schema);
         planner_context.set_outer_query_schema(old_outer_query_schema);
         Ok(Expr::ScalarSubquery(Subquery {
             subquery: Arc::new(sub_plan),
@@ -87,3 +91,55 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }))
     }
 }
+
+/// The schema of the columns a subquery of a query with the `input_schema` may
+/// reference: those of the query, and those the query may reference itself
+/// when it is a subquery
+fn outer_query_schema(
+    input_schema: &DFSchema,
+    planner_context: &PlannerContext,
+) -> DFSchemaRef {
+    let mut schema = input_schema.clone();
+    if let Some(outer_query_schema) = planner_context.outer_query_schema() {
+        schema.merge(outer_query_schema);
+    }
+    Arc::new(schema)
+}
+
+/// The outer reference columns of `sub_plan`, including those of the
+/// subqueries nested in it which reference the columns of `outer_query_schema`
+fn outer_ref_columns(sub_plan: &LogicalPlan, outer_query_schema: &DFSchema) -> Vec<Expr> {
+    let mut outer_ref_columns = sub_plan.all_out_ref_exprs();
+    sub_plan
+        .apply_with_subqueries(|plan| {
+            let input_schemas = plan.inputs().into_iter().map(|input| input.schema());
+            let input_schemas = input_schemas.collect::<Vec<_>>();
+            plan.apply_expressions(|expr| {
+                expr.apply(|expr| {
+                    let subquery = match expr {
+                        Expr::Exists(Exists { subquery, .. })
+                        | Expr::InSubquery(InSubquery { subquery, .. })
+                        | Expr::ScalarSubquery(subquery) => subquery,
+                        _ => return Ok(TreeNodeRecursion::Continue),
+                    };
+                    for outer_ref in &subquery.outer_ref_columns {
+                        let Expr::OuterReferenceColumn(_, column) = outer_ref else {
+                            continue;
+                        };
+                        if outer_query_schema.has_column(column)
+                            && !input_schemas
+                                .iter()
+                                .any(|schema| schema.has_column(column))
+                            && !outer_ref_columns.contains(outer_ref)
+                        {
+                            outer_ref_columns.push(outer_ref.clone());
+                        }
+                    }
+                    Ok(TreeNodeRecursion::Continue)
+                })
+            })
+        })
+        // closure always returns OK
+        .unwrap();
+    outer_ref_columns
+}
@@ -20,6 +20,8 @@
 use std::collections::VecDeque;
 use std::fmt;
 
+use datafusion_common::display::ExplainFormat;
+
 use sqlparser::{
     ast::{
         ColumnDef, ColumnOptionDef, Expr, ObjectName, OrderByExpr, Query,
@@ -47,6 +49,7 @@ fn parse_file_type(s: &str) -> Result<String, ParserError> {
 pub struct ExplainStatement {
     pub analyze: bool,
     pub verbose: bool,
+    pub format: Option<ExplainFormat>,
     pub statement: Box<Statement>,
 }
 
@@ -55,6 +58,7 @@ impl fmt::Display for ExplainStatement {
         let Self {
             analyze,
             verbose,
+            format,
             statement,
         } = self;
 
@@ -65,6 +69,9 @@ impl fmt::Display for ExplainStatement {
         if *verbose {
             write!(f, "VERBOSE ")?;
         }
+        if let Some(format) = format {
+            write!(f, "(FORMAT {format}) ")?;
+        }
 
         write!(f, "{statement}")
     }
@@ -527,15 +534,63 @@ impl<'a> DFParser<'a> {
     }
 
     /// Parse a SQL `EXPLAIN`
+    ///
+    /// ```sql
+    /// EXPLAIN [ANALYZE] [VERBOSE] [( option [, ...] )] statement
+    ///
+    /// where option is one of
+    ///     ANALYZE
+    ///     VERBOSE
+    ///     FORMAT { INDENT | TRACE }
+    /// ```
     pub fn parse_explain(&mut self) -> Result<Statement, ParserError> {
-        let analyze = self.parser.parse_keyword(Keyword::ANALYZE);
-        let verbose = self.parser.parse_keyword(Keyword::VERBOSE);
+        let mut analyze = self.parser.parse_keyword(Keyword::ANALYZE);
+        let mut verbose = self.parser.parse_keyword(Keyword::VERBOSE);
+        let mut format = None;
+
+        // distinguish an option list from a parenthesized query
+        let explain_options = [Keyword::ANALYZE, Keyword::VERBOSE, Keyword::FORMAT];
+        let has_options = matches!(
+            self.parser.peek_tokens(),
+            [Token::LParen, Token::Word(w)] if explain_options.contains(&w.keyword)
+        );
+        if has_options {
+            self.parser.expect_token(&Token::LParen)?;
+            loop {
+                match self.parser.parse_one_of_keywords(&explain_options) {
+                    Some(Keyword::ANALYZE) => analyze = true,
+                    Some(Keyword::VERBOSE) => verbose = true,
+                    Some(Keyword::FORMAT) => {
+                        let token = self.parser.next_token();
+                        format = match &token.token {
+                            Token::Word(w) => w.value.parse::<ExplainFormat>().ok(),
+                            _ => None,
+                        };
+                        if format.is_none() {
+                            return self.parser.expected("INDENT or TRACE", token);
+                        }
+                    }
+                    _ => {
+                        return self.parser.expected(
+                            "ANALYZE, VERBOSE or FORMAT",
+                            self.parser.peek_token(),
+                        )
+                    }
+                }
+                if !self.parser.consume_token(&Token::Comma) {
+                    break;
+                }
+            }
+            self.parser.expect_token(&Token::RParen)?;
+        }
+
         let statement = self.parse_statement()?;
 
         Ok(Statement::Explain(ExplainStatement {
             statement: Box::new(statement),
             analyze,
             verbose,
+            format,
         }))
     }
 
@@ -1425,6 +1480,7 @@ mod tests {
             let expected = Statement::Explain(ExplainStatement {
                 analyze,
                 verbose,
+                format: None,
                 statement: Box::new(expected_copy),
             });
             assert_eq!(verified_stmt(sql), expected);
@@ -1432,6 +1488,69 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn explain_options() -> Result<(), ParserError> {
+        let select = verified_stmt("SELECT 1");
+        let cases = vec![
+            (
+                "EXPLAIN (FORMAT TRACE) SELECT 1",
+                false,
+                false,
+                Some(ExplainFormat::Trace),
+            ),
+            (
+                "EXPLAIN VERBOSE (FORMAT INDENT) SELECT 1",
+                false,
+                true,
+                Some(ExplainFormat::Indent),
+            ),
+        ];
+        for (sql, analyze, verbose, format) in cases {
+            let expected = Statement::Explain(ExplainStatement {
+                analyze,
+                verbose,
+                format,
+                statement: Box::new(select.clone()),
+            });
+            assert_eq!(verified_stmt(sql), expected);
+        }
+
+        let expected = Statement::Explain(ExplainStatement {
+            analyze: true,
+            verbose: true,
+            format: Some(ExplainFormat::Trace),
+            statement: Box::new(select.clone()),
+        });
+        assert_eq!(
+            one_statement_parses_to(
+                "EXPLAIN (analyze, verbose, format trace) SELECT 1",
+                "EXPLAIN ANALYZE VERBOSE (FORMAT TRACE) SELECT 1",
+            ),
+            expected
+        );
+
+        // a parenthesized query is not an option list
+        assert_eq!(
+            one_statement_parses_to("EXPLAIN (SELECT 1)", "EXPLAIN (SELECT 1)"),
+            Statement::Explain(ExplainStatement {
+                analyze: false,
+                verbose: false,
+                format: None,
+                statement: Box::new(verified_stmt("(SELECT 1)")),
+            })
+        );
+
+        expect_parse_error(
+            "EXPLAIN (FORMAT JSON) SELECT 1",
+            "sql parser error: Expected: INDENT or TRACE, found: JSON",
+        );
+        expect_parse_error(
+            "EXPLAIN (VERBOSE, COSTS) SELECT 1",
+            "sql parser error: Expected: ANALYZE, VERBOSE or FORMAT, found: COSTS",
+        );
+        Ok(())
+    }
+
     #[test]
     fn copy_to_query_to_table() -> Result<(), ParserError> {
         let statement = verified_stmt("SELECT 1");
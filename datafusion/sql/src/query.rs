@@ -41,8 +41,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         let mut query_plan_context = outer_planner_context.clone();
         let planner_context = &mut query_plan_context;
 
-        if let Some(with) = query.with {
-            self.plan_with_clause(with, planner_context)?;
+        let mut query = query;
+        if let Some(with) = query.with.take() {
+            let cte_references = self.cte_references(&with, &query);
+            self.plan_with_clause(with, &cte_references, planner_context)?;
         }
 
         let set_expr = *query.body;
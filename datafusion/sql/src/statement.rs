@@ -31,6 +31,7 @@ use crate::utils::normalize_ident;
 
 use arrow_schema::{DataType, Fields};
 use datafusion_common::error::_plan_err;
+use datafusion_common::file_options::avro_writer::AvroCompression;
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::{
     exec_err, not_impl_err, plan_datafusion_err, plan_err, schema_err,
@@ -41,14 +42,14 @@ use datafusion_common::{
 use datafusion_expr::dml::{CopyTo, InsertOp};
 use datafusion_expr::expr_rewriter::normalize_col_with_schemas_and_ambiguity_check;
 use datafusion_expr::logical_plan::builder::project;
-use datafusion_expr::logical_plan::DdlStatement;
+use datafusion_expr::logical_plan::{DdlStatement, INFERRED_CONFIDENCE_METADATA_KEY};
 use datafusion_expr::utils::expr_to_columns;
 use datafusion_expr::{
-    cast, col, Analyze, CreateCatalog, CreateCatalogSchema,
+    cast, col, Analyze, AnalyzeTable, CreateCatalog, CreateCatalogSchema,
     CreateExternalTable as PlanCreateExternalTable, CreateFunction, CreateFunctionBody,
     CreateIndex as PlanCreateIndex, CreateMemoryTable, CreateView, DescribeTable,
     DmlStatement, DropCatalogSchema, DropFunction, DropTable, DropView, EmptyRelation,
-    Explain, Expr, ExprSchemable, Filter, LogicalPlan, LogicalPlanBuilder,
+    Explain, ExplainFormat, Expr, ExprSchemable, Filter, LogicalPlan, LogicalPlanBuilder,
     OperateFunctionArg, PlanType, Prepare, SetVariable, SortExpr,
     Statement as PlanStatement, ToStringifiedPlan, TransactionAccessMode,
     TransactionConclusion, TransactionEnd, TransactionIsolationLevel, TransactionStart,
@@ -169,8 +170,14 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             DFStatement::Explain(ExplainStatement {
                 verbose,
                 analyze,
+                format,
                 statement,
-            }) => self.explain_to_plan(verbose, analyze, *statement),
+            }) => self.explain_to_plan(
+                verbose,
+                analyze,
+                format.unwrap_or_default(),
+                *statement,
+            ),
         }
     }
 
@@ -210,9 +217,12 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 format: _,
                 describe_alias: _,
                 ..
-            } => {
-                self.explain_to_plan(verbose, analyze, DFStatement::Statement(statement))
-            }
+            } => self.explain_to_plan(
+                verbose,
+                analyze,
+                ExplainFormat::Indent,
+                DFStatement::Statement(statement),
+            ),
             Statement::Query(query) => self.query_to_plan(*query, planner_context),
             Statement::ShowVariable { variable } => self.show_variable_to_plan(&variable),
             Statement::SetVariable {
@@ -994,6 +1004,52 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     },
                 )))
             }
+            Statement::Analyze {
+                table_name,
+                partitions,
+                for_columns: _,
+                columns,
+                cache_metadata,
+                noscan,
+                compute_statistics: _,
+            } => {
+                if partitions.is_some() {
+                    return not_impl_err!("ANALYZE TABLE ... PARTITION not supported");
+                }
+                if cache_metadata {
+                    return not_impl_err!(
+                        "ANALYZE TABLE ... CACHE METADATA not supported"
+                    );
+                }
+                if noscan {
+                    return not_impl_err!("ANALYZE TABLE ... NOSCAN not supported");
+                }
+                let name = self.object_name_to_table_reference(table_name)?;
+                let table_schema = self
+                    .context_provider
+                    .get_table_source(name.clone())?
+                    .schema()
+                    .to_dfschema_ref()?;
+                // `FOR COLUMNS` without any column analyzes all the columns
+                let columns = if columns.is_empty() {
+                    None
+                } else {
+                    let columns = columns
+                        .into_iter()
+                        .map(|column| {
+                            let column = self.ident_normalizer.normalize(column);
+                            table_schema.field_with_unqualified_name(&column)?;
+                            Ok(column)
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    Some(columns)
+                };
+                Ok(LogicalPlan::Ddl(DdlStatement::AnalyzeTable(AnalyzeTable {
+                    name,
+                    columns,
+                    schema: DFSchemaRef::new(DFSchema::empty()),
+                })))
+            }
             _ => {
                 not_impl_err!("Unsupported SQL statement: {sql:?}")
             }
@@ -1056,7 +1112,17 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
         let schema = table_source.schema();
 
-        let output_schema = DFSchema::try_from(LogicalPlan::describe_schema()).unwrap();
+        let inferred = schema.fields().iter().any(|field| {
+            field
+                .metadata()
+                .contains_key(INFERRED_CONFIDENCE_METADATA_KEY)
+        });
+        let output_schema = if inferred {
+            LogicalPlan::describe_schema_with_confidence()
+        } else {
+            LogicalPlan::describe_schema()
+        };
+        let output_schema = DFSchema::try_from(output_schema).unwrap();
 
         Ok(LogicalPlan::DescribeTable(DescribeTable {
             schema,
@@ -1218,18 +1284,26 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
         let options_map = self.parse_options_map(options, false)?;
 
-        let compression = options_map
-            .get("format.compression")
-            .map(|c| CompressionTypeVariant::from_str(c))
-            .transpose()?;
-        if (file_type == "PARQUET" || file_type == "AVRO" || file_type == "ARROW")
-            && compression
-                .map(|c| c != CompressionTypeVariant::UNCOMPRESSED)
-                .unwrap_or(false)
-        {
-            plan_err!(
-                "File compression type cannot be set for PARQUET, AVRO, or ARROW files."
-            )?;
+        let compression = options_map.get("format.compression");
+        if file_type == "AVRO" {
+            // Avro files are never compressed as a whole, the option selects
+            // the codec of the data blocks instead
+            if let Some(codec) = compression {
+                AvroCompression::from_str(codec)?;
+            }
+        } else {
+            let compression = compression
+                .map(|c| CompressionTypeVariant::from_str(c))
+                .transpose()?;
+            if (file_type == "PARQUET" || file_type == "ARROW")
+                && compression
+                    .map(|c| c != CompressionTypeVariant::UNCOMPRESSED)
+                    .unwrap_or(false)
+            {
+                plan_err!(
+                    "File compression type cannot be set for PARQUET or ARROW files."
+                )?;
+            }
         }
 
         let mut planner_context = PlannerContext::new();
@@ -1374,6 +1448,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         &self,
         verbose: bool,
         analyze: bool,
+        explain_format: ExplainFormat,
         statement: DFStatement,
     ) -> Result<LogicalPlan> {
         let plan = self.statement_to_plan(statement)?;
@@ -1385,6 +1460,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         let schema = schema.to_dfschema_ref()?;
 
         if analyze {
+            if explain_format != ExplainFormat::Indent {
+                return not_impl_err!(
+                    "EXPLAIN ANALYZE does not support FORMAT {explain_format}"
+                );
+            }
             Ok(LogicalPlan::Analyze(Analyze {
                 verbose,
                 input: plan,
@@ -1395,6 +1475,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 vec![plan.to_stringified(PlanType::InitialLogicalPlan)];
             Ok(LogicalPlan::Explain(Explain {
                 verbose,
+                explain_format,
                 plan,
                 stringified_plans,
                 schema,
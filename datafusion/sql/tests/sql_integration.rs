@@ -1965,6 +1965,8 @@ fn create_external_table_with_compression_type() {
         "CREATE EXTERNAL TABLE t(c1 int) STORED AS JSON LOCATION 'foo.json.gz' OPTIONS ('format.compression' 'gzip')",
         "CREATE EXTERNAL TABLE t(c1 int) STORED AS JSON LOCATION 'foo.json.bz2' OPTIONS ('format.compression' 'bzip2')",
         "CREATE EXTERNAL TABLE t(c1 int) STORED AS NONSTANDARD LOCATION 'foo.unk' OPTIONS ('format.compression' 'gzip')",
+        "CREATE EXTERNAL TABLE t(c1 int) STORED AS AVRO LOCATION 'foo.avro' OPTIONS ('format.compression' 'snappy')",
+        "CREATE EXTERNAL TABLE t(c1 int) STORED AS AVRO LOCATION 'foo.avro' OPTIONS ('format.compression' 'bzip2')",
          ];
     for sql in sqls {
         let expected = "CreateExternalTable: Bare { table: \"t\" }";
@@ -1972,9 +1974,13 @@ fn create_external_table_with_compression_type() {
     }
 
     // negative case
+    let sql = "CREATE EXTERNAL TABLE t STORED AS AVRO LOCATION 'foo.avro' OPTIONS ('format.compression' 'gzip')";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert!(err
+        .strip_backtrace()
+        .starts_with("Invalid or Unsupported Configuration: Unknown or unsupported avro compression codec gzip"));
+
     let sqls = vec![
-        "CREATE EXTERNAL TABLE t STORED AS AVRO LOCATION 'foo.avro' OPTIONS ('format.compression' 'gzip')",
-        "CREATE EXTERNAL TABLE t STORED AS AVRO LOCATION 'foo.avro' OPTIONS ('format.compression' 'bzip2')",
         "CREATE EXTERNAL TABLE t STORED AS PARQUET LOCATION 'foo.parquet' OPTIONS ('format.compression' 'gzip')",
         "CREATE EXTERNAL TABLE t STORED AS PARQUET LOCATION 'foo.parquet' OPTIONS ('format.compression' 'bzip2')",
         "CREATE EXTERNAL TABLE t STORED AS ARROW LOCATION 'foo.arrow' OPTIONS ('format.compression' 'gzip')",
@@ -1983,7 +1989,7 @@ fn create_external_table_with_compression_type() {
     for sql in sqls {
         let err = logical_plan(sql).expect_err("query should have failed");
         assert_eq!(
-            "Error during planning: File compression type cannot be set for PARQUET, AVRO, or ARROW files.",
+            "Error during planning: File compression type cannot be set for PARQUET or ARROW files.",
             err.strip_backtrace()
         );
     }
@@ -4456,6 +4462,30 @@ fn plan_create_index() {
     }
 }
 
+#[test]
+fn plan_analyze_table() {
+    quick_test(
+        "ANALYZE TABLE person",
+        "AnalyzeTable: Bare { table: \"person\" }",
+    );
+    quick_test(
+        "ANALYZE TABLE person COMPUTE STATISTICS FOR COLUMNS",
+        "AnalyzeTable: Bare { table: \"person\" }",
+    );
+    quick_test(
+        "ANALYZE TABLE person COMPUTE STATISTICS FOR COLUMNS Age, \"last_name\"",
+        "AnalyzeTable: Bare { table: \"person\" } columns:=[age, last_name]",
+    );
+
+    let err = logical_plan("ANALYZE TABLE person FOR COLUMNS x").unwrap_err();
+    assert_field_not_found(err, "x");
+    let err = logical_plan("ANALYZE TABLE person NOSCAN").unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "This feature is not implemented: ANALYZE TABLE ... NOSCAN not supported"
+    );
+}
+
 fn assert_field_not_found(err: DataFusionError, name: &str) {
     match err {
         DataFusionError::SchemaError { .. } => {